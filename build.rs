@@ -0,0 +1,113 @@
+//! Compresses the bundled fallback word lists into `OUT_DIR` and records
+//! where and how the binary was built.
+
+use std::{
+    env, fs,
+    io::Write,
+    path::Path,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use flate2::{Compression, write::DeflateEncoder};
+
+/// Lists under `assets/wordlists`, embedded for when they can't be downloaded.
+const LISTS: [&str; 2] = ["words.txt", "solutions.txt"];
+
+/// Output of `git args` in the crate directory, or `None` outside a checkout
+/// or without git installed.
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(env::var("CARGO_MANIFEST_DIR").ok()?)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Commit, build date, target and features, read back by `build_info`.
+fn build_info() {
+    // A crates.io install has no checkout, so the commit is left out
+    if let Some(commit) = git(&["rev-parse", "HEAD"]) {
+        let dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+            .is_some_and(|status| !status.is_empty());
+        println!("cargo:rustc-env=BUILD_GIT_COMMIT={}", commit);
+        println!("cargo:rustc-env=BUILD_GIT_DIRTY={}", dirty);
+        if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+            println!("cargo:rerun-if-changed={}/HEAD", git_dir);
+            println!("cargo:rerun-if-changed={}/index", git_dir);
+        }
+        // Edits change the dirty flag without touching the index
+        println!("cargo:rerun-if-changed=src");
+    }
+
+    // Reproducible builds pin the date
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    let built_at = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs())
+        });
+    println!("cargo:rustc-env=BUILD_TIMESTAMP={}", built_at);
+
+    let target = env::var("TARGET").expect("cargo sets TARGET");
+    println!("cargo:rustc-env=BUILD_TARGET={}", target);
+
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|name| name.to_lowercase().replace('_', "-"))
+        })
+        .filter(|name| name != "default")
+        .collect();
+    features.sort();
+    println!("cargo:rustc-env=BUILD_FEATURES={}", features.join(","));
+}
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").expect("cargo sets OUT_DIR");
+    println!("cargo:rerun-if-changed=assets/wordlists");
+    build_info();
+
+    for name in LISTS {
+        let source = Path::new("assets/wordlists").join(name);
+        println!("cargo:rerun-if-changed={}", source.display());
+
+        // An offline first run has nothing else to fall back on
+        let text = fs::read_to_string(&source).unwrap_or_else(|e| {
+            panic!(
+                "{} can't be read ({}); fetch it as assets/wordlists/README.md describes",
+                source.display(),
+                e
+            )
+        });
+        let words: Vec<&str> = text
+            .lines()
+            .map(str::trim)
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+        encoder
+            .write_all(words.join("\n").as_bytes())
+            .expect("compressing into memory");
+        let compressed = encoder.finish().expect("compressing into memory");
+        let count_var = name.trim_end_matches(".txt").to_uppercase();
+        println!(
+            "cargo:rustc-env=BUNDLED_{}_COUNT={}",
+            count_var,
+            words.len()
+        );
+        fs::write(
+            Path::new(&out_dir).join(format!("{}.deflate", name)),
+            compressed,
+        )
+        .expect("writing to OUT_DIR");
+    }
+}
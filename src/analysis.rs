@@ -1,8 +1,13 @@
-use crate::solver::{Feedback, SolverState};
-use std::collections::{HashMap, HashSet};
+use crate::{
+    solver::{Feedback, SolverState},
+    wordtable::WordTable,
+};
+use std::collections::HashMap;
 
 #[derive(Debug)]
 pub struct LetterAnalysis {
+    /// Pool words containing each letter, or its information score in
+    /// thousandths of a bit when built by [`compute_letter_information`].
     pub frequencies: HashMap<char, usize>,
     pub total_words: usize,
     pub max_frequency: usize,
@@ -12,55 +17,200 @@ pub struct LetterAnalysis {
 pub struct PositionAnalysis {
     pub possible_letters: Vec<Vec<char>>,
     pub solved_positions: Vec<Option<char>>,
+    /// Why each solved position is solved, in `solved_positions` order.
+    pub solved_source: Vec<Option<SolvedSource>>,
     pub position_frequencies: Vec<HashMap<char, usize>>,
 }
 
+/// How a position came to have a single possible letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolvedSource {
+    /// A guess showed the letter green there; `from_guess` is its 0-based index.
+    Green { from_guess: usize },
+    /// No guess was green there, but every remaining candidate agrees.
+    Inferred,
+}
+
 #[derive(Debug)]
 pub struct ConstraintSummary {
     pub greens: Vec<(char, usize, String)>,
     pub yellows: Vec<(char, Vec<usize>, String)>,
+    /// Letters ruled out entirely, alphabetically.
     pub grays: Vec<char>,
+    /// Letters in the answer with a known count: seen green or yellow, with
+    /// a surplus copy gray. Alphabetical, each with its count.
+    pub limited: Vec<(char, usize)>,
     pub min_counts: HashMap<char, usize>,
     pub max_counts: HashMap<char, usize>,
 }
 
+impl ConstraintSummary {
+    /// Returns true if `c` is known to be absent from the answer.
+    pub fn is_excluded(&self, c: char) -> bool {
+        self.max_counts.get(&c) == Some(&0)
+    }
+}
+
+/// Positions a yellow letter can still occupy, 0-based.
+#[derive(Debug, PartialEq)]
+pub struct YellowPlacement {
+    pub letter: char,
+    pub positions: Vec<usize>,
+}
+
+impl YellowPlacement {
+    /// The one position left for the letter, if it has nowhere else to go.
+    pub fn forced(&self) -> Option<usize> {
+        match self.positions[..] {
+            [pos] => Some(pos),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct SolutionPoolStats {
     pub total_remaining: usize,
     pub eliminated_percentage: f64,
     pub entropy: f64,
+    /// Guesses likely still needed, from [`expected_guesses_remaining`].
+    pub expected_guesses: Option<f64>,
 }
 
-pub fn compute_letter_analysis(words: &[&String]) -> LetterAnalysis {
-    let mut frequencies = HashMap::new();
-
-    for word in words {
-        let mut seen = HashSet::new();
-        for c in word.chars() {
-            if seen.insert(c) {
-                *frequencies.entry(c).or_insert(0) += 1;
-            }
+/// How many ways a good guess splits a pool, by the smallest pool size it
+/// applies from. Small pools split little, since most of their words share
+/// letters; large pools split widely.
+const BRANCHING_CALIBRATION: [(usize, f64); 4] = [(1, 4.0), (3, 8.0), (10, 24.0), (100, 60.0)];
+
+/// Guesses likely needed to solve a pool of `pool_size`, counting the final
+/// guess of the answer; `None` for an empty pool.
+///
+/// Each guess divides the pool by the branching factor for its size, so the
+/// estimate is a log of the pool size taken band by band through
+/// [`BRANCHING_CALIBRATION`]. It grows with the pool and is exactly 1 for a
+/// single word.
+pub fn expected_guesses_remaining(pool_size: usize) -> Option<f64> {
+    if pool_size == 0 {
+        return None;
+    }
+    let size = pool_size as f64;
+    let mut guesses = 1.0;
+    for (i, &(from, branching)) in BRANCHING_CALIBRATION.iter().enumerate() {
+        let from = from as f64;
+        if size <= from {
+            break;
         }
+        let to = BRANCHING_CALIBRATION
+            .get(i + 1)
+            .map_or(size, |&(next, _)| size.min(next as f64));
+        guesses += (to / from).ln() / branching.ln();
+    }
+    Some(guesses)
+}
+
+/// An estimate from [`expected_guesses_remaining`] for display, e.g.
+/// `≈2.3 guesses to go`.
+pub fn format_guesses_to_go(expected: Option<f64>) -> String {
+    match expected {
+        None => "—".to_string(),
+        Some(guesses) if guesses <= 1.0 => "1 guess to go".to_string(),
+        Some(guesses) => format!("≈{:.1} guesses to go", guesses),
+    }
+}
+
+pub fn compute_letter_analysis(table: &WordTable, pool: &[u32]) -> LetterAnalysis {
+    let counts = unique_letter_counts(table, pool);
+    let frequencies: HashMap<char, usize> = (0..=u8::MAX)
+        .filter(|&b| counts[b as usize] > 0)
+        .map(|b| (b as char, counts[b as usize]))
+        .collect();
+
+    let max_frequency = frequencies.values().copied().max().unwrap_or(0);
+
+    LetterAnalysis {
+        frequencies,
+        total_words: pool.len(),
+        max_frequency,
     }
+}
+
+/// Score letters by how much testing them would tell us, in thousandths of a bit.
+///
+/// A letter's score is the entropy of splitting the pool into words that
+/// contain it and words that do not, so a letter in half the pool scores
+/// highest. Letters whose count in the answer is already pinned down by the
+/// constraints score 0, as does any letter in every or no remaining word.
+pub fn compute_letter_information(
+    table: &WordTable,
+    pool: &[u32],
+    constraints: &ConstraintSummary,
+) -> LetterAnalysis {
+    let counts = unique_letter_counts(table, pool);
+    let frequencies: HashMap<char, usize> = (0..=u8::MAX)
+        .filter(|&b| counts[b as usize] > 0)
+        .map(|b| {
+            let c = b as char;
+            let determined = constraints
+                .max_counts
+                .get(&c)
+                .is_some_and(|max| constraints.min_counts.get(&c) == Some(max));
+            let millibits = if determined {
+                0
+            } else {
+                let p = counts[b as usize] as f64 / pool.len() as f64;
+                (binary_entropy(p) * 1000.0).round() as usize
+            };
+            (c, millibits)
+        })
+        .collect();
 
     let max_frequency = frequencies.values().copied().max().unwrap_or(0);
 
     LetterAnalysis {
         frequencies,
-        total_words: words.len(),
+        total_words: pool.len(),
         max_frequency,
     }
 }
 
-pub fn compute_position_analysis(words: &[&String], solver: &SolverState) -> PositionAnalysis {
+/// Entropy in bits of a yes/no outcome that is yes with probability `p`.
+fn binary_entropy(p: f64) -> f64 {
+    [p, 1.0 - p]
+        .into_iter()
+        .filter(|&q| q > 0.0)
+        .map(|q| -q * q.log2())
+        .sum()
+}
+
+/// Number of pool words containing each letter at least once, indexed by byte.
+fn unique_letter_counts(table: &WordTable, pool: &[u32]) -> [usize; 256] {
+    let mut counts = [0; 256];
+
+    for &i in pool {
+        let word = table.bytes(i);
+        for p in 0..word.len() {
+            if !word[..p].contains(&word[p]) {
+                counts[word[p] as usize] += 1;
+            }
+        }
+    }
+
+    counts
+}
+
+pub fn compute_position_analysis(
+    table: &WordTable,
+    pool: &[u32],
+    solver: &SolverState,
+) -> PositionAnalysis {
     let word_len = solver.word_len();
 
     let mut possible_letters = vec![Vec::new(); word_len];
     let mut position_frequencies = vec![HashMap::new(); word_len];
     let mut solved_positions = vec![None; word_len];
 
-    for word in words {
-        for (pos, c) in word.chars().enumerate() {
+    for &i in pool {
+        for (pos, c) in table.word(i).chars().enumerate() {
             if !possible_letters[pos].contains(&c) {
                 possible_letters[pos].push(c);
             }
@@ -82,9 +232,29 @@ pub fn compute_position_analysis(words: &[&String], solver: &SolverState) -> Pos
         });
     }
 
+    let solved_source = solved_positions
+        .iter()
+        .enumerate()
+        .map(|(pos, solved)| {
+            solved.map(|letter| {
+                solver
+                    .guesses()
+                    .iter()
+                    .position(|g| {
+                        g.feedback.get(pos) == Some(&Feedback::Green)
+                            && g.word.chars().nth(pos) == Some(letter)
+                    })
+                    .map_or(SolvedSource::Inferred, |from_guess| SolvedSource::Green {
+                        from_guess,
+                    })
+            })
+        })
+        .collect();
+
     PositionAnalysis {
         possible_letters,
         solved_positions,
+        solved_source,
         position_frequencies,
     }
 }
@@ -92,7 +262,6 @@ pub fn compute_position_analysis(words: &[&String], solver: &SolverState) -> Pos
 pub fn compute_constraint_summary(solver: &SolverState) -> ConstraintSummary {
     let mut greens = Vec::new();
     let mut yellows: Vec<(char, Vec<usize>, String)> = Vec::new();
-    let mut gray_letters = HashSet::new();
 
     let mut min_counts = HashMap::new();
     let mut max_counts = HashMap::new();
@@ -137,7 +306,6 @@ pub fn compute_constraint_summary(solver: &SolverState) -> ConstraintSummary {
             let gy = *gy_counts.get(&c).unwrap_or(&0);
 
             if guess_total > gy {
-                gray_letters.insert(c);
                 max_counts
                     .entry(c)
                     .and_modify(|m: &mut usize| *m = (*m).min(gy))
@@ -146,43 +314,117 @@ pub fn compute_constraint_summary(solver: &SolverState) -> ConstraintSummary {
         }
     }
 
+    // A gray copy only caps a letter; it is ruled out when the cap is zero
+    let mut grays: Vec<char> = max_counts
+        .iter()
+        .filter(|&(c, &max)| max == 0 && !min_counts.contains_key(c))
+        .map(|(&c, _)| c)
+        .collect();
+    grays.sort_unstable();
+    let mut limited: Vec<(char, usize)> = max_counts
+        .iter()
+        .filter(|&(c, _)| min_counts.contains_key(c))
+        .map(|(&c, &max)| (c, max))
+        .collect();
+    limited.sort_unstable();
+
     ConstraintSummary {
         greens,
         yellows,
-        grays: gray_letters.into_iter().collect(),
+        grays,
+        limited,
         min_counts,
         max_counts,
     }
 }
 
-pub fn compute_solution_pool_stats(
-    all_words: &[String],
-    filtered: &[&String],
-) -> SolutionPoolStats {
-    let total_remaining = filtered.len();
+/// Work out where each yellow letter can still go.
+///
+/// A position stays open when it is not green for a different letter, the
+/// letter has not been seen yellow there, and some word in `pool` has the
+/// letter there. A single open position is a green the guesses only imply.
+pub fn derive_forced_positions(
+    table: &WordTable,
+    pool: &[u32],
+    summary: &ConstraintSummary,
+) -> Vec<YellowPlacement> {
+    summary
+        .yellows
+        .iter()
+        .map(|(letter, seen, _)| {
+            let positions = (0..table.word_len())
+                .filter(|pos| !seen.contains(pos))
+                .filter(|&pos| {
+                    !summary
+                        .greens
+                        .iter()
+                        .any(|&(c, p, _)| p == pos && c != *letter)
+                })
+                .filter(|&pos| pool.iter().any(|&i| table.bytes(i)[pos] as char == *letter))
+                .collect();
+            YellowPlacement {
+                letter: *letter,
+                positions,
+            }
+        })
+        .collect()
+}
+
+/// One line summing up `summary`, e.g. "greens: _RA_E, yellows: S(not 1), grays: ILNOT".
+///
+/// Yellow positions are 1-based. Grays list only letters ruled out entirely,
+/// in alphabetical order.
+pub fn format_constraints_compact(summary: &ConstraintSummary, word_len: usize) -> String {
+    let mut greens = vec!['_'; word_len];
+    for &(letter, pos, _) in &summary.greens {
+        if let Some(slot) = greens.get_mut(pos) {
+            *slot = letter.to_ascii_uppercase();
+        }
+    }
 
-    let eliminated_percentage = if all_words.is_empty() {
+    let yellows: Vec<String> = summary
+        .yellows
+        .iter()
+        .map(|(letter, positions, _)| {
+            let positions: Vec<String> = positions.iter().map(|p| (p + 1).to_string()).collect();
+            format!(
+                "{}(not {})",
+                letter.to_ascii_uppercase(),
+                positions.join(",")
+            )
+        })
+        .collect();
+
+    let grays: Vec<char> = summary
+        .grays
+        .iter()
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    let or_none = |s: String| if s.is_empty() { "none".to_string() } else { s };
+    format!(
+        "greens: {}, yellows: {}, grays: {}",
+        greens.into_iter().collect::<String>(),
+        or_none(yellows.join(" ")),
+        or_none(grays.into_iter().collect()),
+    )
+}
+
+pub fn compute_solution_pool_stats(table: &WordTable, pool: &[u32]) -> SolutionPoolStats {
+    let total_remaining = pool.len();
+
+    let eliminated_percentage = if table.is_empty() {
         0.0
     } else {
-        (1.0 - total_remaining as f64 / all_words.len() as f64) * 100.0
+        (1.0 - total_remaining as f64 / table.len() as f64) * 100.0
     };
 
     let entropy = if total_remaining <= 1 {
         0.0
     } else {
-        let mut letter_counts = HashMap::new();
-
-        for word in filtered {
-            let mut seen = HashSet::new();
-            for c in word.chars() {
-                if seen.insert(c) {
-                    *letter_counts.entry(c).or_insert(0) += 1;
-                }
-            }
-        }
-
-        letter_counts
-            .values()
+        unique_letter_counts(table, pool)
+            .iter()
+            .filter(|&&count| count > 0)
             .map(|&count| {
                 let p = count as f64 / total_remaining as f64;
                 -p * p.log2()
@@ -194,9 +436,80 @@ pub fn compute_solution_pool_stats(
         total_remaining,
         eliminated_percentage,
         entropy,
+        expected_guesses: expected_guesses_remaining(total_remaining),
+    }
+}
+
+/// How one word's place in the top suggestions changed after a guess.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuggestionDelta {
+    /// Still in the top ranks, moved from `from` to `to` (0-based).
+    Moved {
+        word: String,
+        from: usize,
+        to: usize,
+    },
+    /// Newly in the top ranks, at `rank`.
+    Entered { word: String, rank: usize },
+    /// Dropped out of the top ranks from `rank`; `eliminated` when it is no
+    /// longer suggested at all.
+    Left {
+        word: String,
+        rank: usize,
+        eliminated: bool,
+    },
+}
+
+impl SuggestionDelta {
+    pub fn word(&self) -> &str {
+        match self {
+            SuggestionDelta::Moved { word, .. }
+            | SuggestionDelta::Entered { word, .. }
+            | SuggestionDelta::Left { word, .. } => word,
+        }
     }
 }
 
+/// Compare the top `n` of two ranked suggestion lists.
+///
+/// Movers and entrants come first in their new order, then departures in
+/// their old order. Words that kept their rank are left out.
+pub fn diff_suggestions(old: &[&str], new: &[&str], n: usize) -> Vec<SuggestionDelta> {
+    let old_top = &old[..old.len().min(n)];
+    let new_top = &new[..new.len().min(n)];
+    let rank_in = |list: &[&str], word: &str| list.iter().position(|w| *w == word);
+
+    let mut deltas: Vec<SuggestionDelta> = new_top
+        .iter()
+        .enumerate()
+        .filter_map(|(to, &word)| match rank_in(old_top, word) {
+            Some(from) if from == to => None,
+            Some(from) => Some(SuggestionDelta::Moved {
+                word: word.to_string(),
+                from,
+                to,
+            }),
+            None => Some(SuggestionDelta::Entered {
+                word: word.to_string(),
+                rank: to,
+            }),
+        })
+        .collect();
+
+    deltas.extend(
+        old_top
+            .iter()
+            .enumerate()
+            .filter(|(_, word)| rank_in(new_top, word).is_none())
+            .map(|(rank, &word)| SuggestionDelta::Left {
+                word: word.to_string(),
+                rank,
+                eliminated: rank_in(new, word).is_none(),
+            }),
+    );
+    deltas
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -205,20 +518,19 @@ mod tests {
     fn make_solver_state(guesses: Vec<(&str, Vec<Feedback>)>, word_len: usize) -> SolverState {
         let mut solver = SolverState::new(word_len);
         for (word, feedback) in guesses {
-            solver.add_guess(Guess::new(word.to_string(), feedback));
+            solver
+                .add_guess(Guess::new(word.to_string(), feedback))
+                .unwrap();
         }
         solver
     }
 
     #[test]
     fn test_compute_letter_analysis() {
-        let apple = "apple".to_string();
-        let angle = "angle".to_string();
-        let ample = "ample".to_string();
-
-        let words = vec![&apple, &angle, &ample];
+        let table = WordTable::new(["apple", "angle", "ample"], 5);
+        let pool: Vec<u32> = table.indices().collect();
 
-        let analysis = compute_letter_analysis(words.as_slice());
+        let analysis = compute_letter_analysis(&table, &pool);
         assert_eq!(analysis.total_words, 3);
         assert_eq!(analysis.frequencies.get(&'a'), Some(&3));
         assert_eq!(analysis.frequencies.get(&'p'), Some(&2));
@@ -226,20 +538,92 @@ mod tests {
     }
 
     #[test]
-    fn test_compute_position_analysis() {
-        let apple = "apple".to_string();
-        let angle = "angle".to_string();
-        let ample = "ample".to_string();
+    fn test_letter_information_zero_for_solved_letter() {
+        let table = WordTable::new(["stone", "shone", "spine", "slate"], 5);
+        let solver = make_solver_state(
+            vec![(
+                "sieve",
+                vec![
+                    Feedback::Green,
+                    Feedback::Gray,
+                    Feedback::Gray,
+                    Feedback::Gray,
+                    Feedback::Green,
+                ],
+            )],
+            5,
+        );
+        let pool = solver.filter(&table);
+        assert_eq!(pool.len(), 3);
+        let constraints = compute_constraint_summary(&solver);
+
+        let information = compute_letter_information(&table, &pool, &constraints);
+        // S is in every word; E is green once and gray elsewhere, so exactly one
+        assert_eq!(information.frequencies.get(&'s'), Some(&0));
+        assert_eq!(information.frequencies.get(&'e'), Some(&0));
+    }
+
+    #[test]
+    fn test_letter_information_highest_for_even_split() {
+        let table = WordTable::new(["stone", "shone", "slate", "spate"], 5);
+        let pool: Vec<u32> = table.indices().collect();
+        let constraints = compute_constraint_summary(&SolverState::new(5));
+
+        let information = compute_letter_information(&table, &pool, &constraints);
+        // O and A each split the pool 2/2, a full bit; H is in one word in four
+        assert_eq!(information.frequencies.get(&'o'), Some(&1000));
+        assert_eq!(information.frequencies.get(&'a'), Some(&1000));
+        assert_eq!(information.frequencies.get(&'h'), Some(&811));
+        assert_eq!(information.max_frequency, 1000);
+        assert_eq!(information.frequencies.get(&'s'), Some(&0));
+    }
 
-        let words = vec![&apple, &angle, &ample];
+    #[test]
+    fn test_compute_position_analysis() {
+        let table = WordTable::new(["apple", "angle", "ample"], 5);
+        let pool: Vec<u32> = table.indices().collect();
 
         let solver = SolverState::new(5);
-        let analysis = compute_position_analysis(words.as_slice(), &solver);
+        let analysis = compute_position_analysis(&table, &pool, &solver);
         assert_eq!(analysis.possible_letters[0], vec!['a']);
         assert!(analysis.possible_letters[1].contains(&'p'));
         assert!(analysis.position_frequencies[4].contains_key(&'e'));
     }
 
+    #[test]
+    fn test_solved_source_green_and_inferred() {
+        use crate::solver::parse_pattern;
+
+        // No guess has a green, yet every candidate left starts with S
+        let table = WordTable::new(["shone", "spore", "smoke", "tails"], 5);
+        let solver = make_solver_state(vec![("tails", parse_pattern("XXXXY").unwrap())], 5);
+        let pool = solver.filter(&table);
+        let analysis = compute_position_analysis(&table, &pool, &solver);
+
+        assert_eq!(
+            analysis.solved_positions,
+            [Some('s'), None, Some('o'), None, Some('e')]
+        );
+        let inferred = Some(SolvedSource::Inferred);
+        assert_eq!(
+            analysis.solved_source,
+            [inferred, None, inferred, None, inferred]
+        );
+
+        // Once a guess shows a letter green, the position is credited to that guess
+        let solver = make_solver_state(
+            vec![
+                ("tails", parse_pattern("XXXXY").unwrap()),
+                ("spore", parse_pattern("GXGXG").unwrap()),
+            ],
+            5,
+        );
+        let pool = solver.filter(&table);
+        let analysis = compute_position_analysis(&table, &pool, &solver);
+        let green = Some(SolvedSource::Green { from_guess: 1 });
+        assert_eq!(analysis.solved_source, [green, None, green, None, green]);
+    }
+
     #[test]
     fn test_compute_constraint_summary() {
         let guesses = vec![
@@ -276,18 +660,244 @@ mod tests {
         assert!(summary.grays.contains(&'p'));
     }
 
+    #[test]
+    fn test_surplus_copy_caps_letter_without_excluding_it() {
+        use crate::solver::parse_pattern;
+
+        // Against CRANE: SHEEP shows one E yellow and the other gray, then
+        // EERIE has its last E green and the other two gray
+        let solver = make_solver_state(
+            vec![
+                ("sheep", parse_pattern("XXYXX").unwrap()),
+                ("eerie", parse_pattern("XXYXG").unwrap()),
+            ],
+            5,
+        );
+        let summary = compute_constraint_summary(&solver);
+
+        assert_eq!(summary.grays, vec!['h', 'i', 'p', 's']);
+        assert_eq!(summary.limited, vec![('e', 1)]);
+        assert!(!summary.is_excluded('e'));
+    }
+
+    #[test]
+    fn test_letter_gray_only_in_later_guess_stays_limited() {
+        use crate::solver::parse_pattern;
+
+        // Both copies of E accounted for, then a third copy comes back gray
+        let solver = make_solver_state(
+            vec![
+                ("geese", parse_pattern("XYXXG").unwrap()),
+                ("eerie", parse_pattern("YXXXG").unwrap()),
+            ],
+            5,
+        );
+        let summary = compute_constraint_summary(&solver);
+
+        assert_eq!(summary.limited, vec![('e', 2)]);
+        assert!(!summary.grays.contains(&'e'));
+        assert_eq!(summary.grays, vec!['g', 'i', 'r', 's']);
+    }
+
+    #[test]
+    fn test_derive_forced_positions_from_pool() {
+        // REACT against CRANE: R, E and C yellow, A green, T gray
+        let table = WordTable::new(["crane", "scare", "trace", "brace"], 5);
+        let solver = make_solver_state(
+            vec![(
+                "react",
+                vec![
+                    Feedback::Yellow,
+                    Feedback::Yellow,
+                    Feedback::Green,
+                    Feedback::Yellow,
+                    Feedback::Gray,
+                ],
+            )],
+            5,
+        );
+        let pool = solver.filter(&table);
+        assert_eq!(pool.len(), 2);
+        let summary = compute_constraint_summary(&solver);
+
+        let placements = derive_forced_positions(&table, &pool, &summary);
+        let placement = |letter| placements.iter().find(|p| p.letter == letter).unwrap();
+        assert_eq!(placement('r').positions, vec![1, 3]);
+        assert_eq!(placement('c').positions, vec![0, 1]);
+        assert_eq!(placement('r').forced(), None);
+        // The guess alone leaves E at 1, 4 or 5, but every remaining word ends in it
+        assert_eq!(placement('e').positions, vec![4]);
+        assert_eq!(placement('e').forced(), Some(4));
+    }
+
+    #[test]
+    fn test_derive_forced_positions_skips_greens_and_seen() {
+        let solver = make_solver_state(
+            vec![(
+                "qaxyz",
+                vec![
+                    Feedback::Green,
+                    Feedback::Yellow,
+                    Feedback::Gray,
+                    Feedback::Gray,
+                    Feedback::Gray,
+                ],
+            )],
+            5,
+        );
+        let summary = compute_constraint_summary(&solver);
+        // A pool that puts A everywhere, so only the guesses rule positions out
+        let table = WordTable::new(["aaaaa"], 5);
+        let pool: Vec<u32> = table.indices().collect();
+
+        let placements = derive_forced_positions(&table, &pool, &summary);
+        // Position 1 is green for Q and A was yellow at 2
+        assert_eq!(placements[0].positions, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_format_constraints_compact() {
+        use crate::solver::parse_pattern;
+
+        let solver = make_solver_state(
+            vec![
+                ("tolis", parse_pattern("XXXXY").unwrap()),
+                ("grace", parse_pattern("XGGXG").unwrap()),
+                ("spree", parse_pattern("GXYXG").unwrap()),
+            ],
+            5,
+        );
+        let summary = compute_constraint_summary(&solver);
+
+        assert_eq!(
+            format_constraints_compact(&summary, 5),
+            "greens: SRA_E, yellows: S(not 5) R(not 3), grays: CGILOPT"
+        );
+    }
+
+    #[test]
+    fn test_format_constraints_compact_empty_and_repeated_letters() {
+        use crate::solver::parse_pattern;
+
+        assert_eq!(
+            format_constraints_compact(&compute_constraint_summary(&SolverState::new(5)), 5),
+            "greens: _____, yellows: none, grays: none"
+        );
+
+        // The second E is gray, but E is in the answer so it is not listed
+        let solver = make_solver_state(vec![("geese", parse_pattern("XYXXG").unwrap())], 5);
+        let summary = compute_constraint_summary(&solver);
+        assert_eq!(
+            format_constraints_compact(&summary, 5),
+            "greens: ____E, yellows: E(not 2), grays: GS"
+        );
+    }
+
     #[test]
     fn test_compute_solution_pool_stats() {
-        let all_words = vec![
-            "apple".to_string(),
-            "angle".to_string(),
-            "ample".to_string(),
-        ];
-        let apple = "apple".to_string();
-        let filtered: Vec<&String> = vec![&apple];
-        let stats = compute_solution_pool_stats(all_words.as_slice(), &filtered);
+        let table = WordTable::new(["apple", "angle", "ample"], 5);
+        let filtered = vec![table.index_of("apple").unwrap()];
+        let stats = compute_solution_pool_stats(&table, &filtered);
         assert_eq!(stats.total_remaining, 1);
         assert!(stats.eliminated_percentage > 0.0);
         assert_eq!(stats.entropy, 0.0);
+        assert_eq!(stats.expected_guesses, Some(1.0));
+    }
+
+    #[test]
+    fn test_expected_guesses_boundaries() {
+        assert_eq!(expected_guesses_remaining(0), None);
+        assert_eq!(expected_guesses_remaining(1), Some(1.0));
+        assert_eq!(format_guesses_to_go(None), "—");
+        assert_eq!(format_guesses_to_go(Some(1.0)), "1 guess to go");
+
+        // Two words: one guess picks either, so half the time a second is needed
+        assert_eq!(expected_guesses_remaining(2), Some(1.5));
+        assert_eq!(format_guesses_to_go(Some(1.5)), "≈1.5 guesses to go");
+
+        let opening = expected_guesses_remaining(2315).unwrap();
+        assert!((3.5..4.5).contains(&opening), "{}", opening);
+    }
+
+    #[test]
+    fn test_expected_guesses_grow_with_the_pool() {
+        let estimates: Vec<f64> = (1..=5000)
+            .map(|n| expected_guesses_remaining(n).unwrap())
+            .collect();
+        assert!(estimates.windows(2).all(|w| w[0] < w[1]));
+        // and keep growing across each band boundary
+        for &(from, _) in &BRANCHING_CALIBRATION[1..] {
+            assert!(estimates[from - 2] < estimates[from - 1]);
+            assert!(estimates[from - 1] < estimates[from]);
+        }
+    }
+
+    #[test]
+    fn test_diff_suggestions_promotion_and_demotion() {
+        let deltas = diff_suggestions(
+            &["crane", "slate", "stone"],
+            &["stone", "crane", "slate"],
+            3,
+        );
+
+        assert_eq!(
+            deltas,
+            [
+                SuggestionDelta::Moved {
+                    word: "stone".to_string(),
+                    from: 2,
+                    to: 0
+                },
+                SuggestionDelta::Moved {
+                    word: "crane".to_string(),
+                    from: 0,
+                    to: 1
+                },
+                SuggestionDelta::Moved {
+                    word: "slate".to_string(),
+                    from: 1,
+                    to: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_suggestions_entry_and_exit() {
+        // house was below the cut, apple was never suggested, slate is gone
+        let old = ["crane", "slate", "house"];
+        let new = ["crane", "house", "apple"];
+
+        let deltas = diff_suggestions(&old, &new, 2);
+
+        assert_eq!(
+            deltas,
+            [
+                SuggestionDelta::Entered {
+                    word: "house".to_string(),
+                    rank: 1
+                },
+                SuggestionDelta::Left {
+                    word: "slate".to_string(),
+                    rank: 1,
+                    eliminated: true
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_suggestions_exit_still_suggested() {
+        let deltas = diff_suggestions(&["crane", "slate"], &["house", "crane", "slate"], 2);
+
+        assert_eq!(
+            deltas.last(),
+            Some(&SuggestionDelta::Left {
+                word: "slate".to_string(),
+                rank: 1,
+                eliminated: false
+            })
+        );
+        assert!(diff_suggestions(&["crane"], &["crane"], 10).is_empty());
     }
 }
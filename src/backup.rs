@@ -0,0 +1,344 @@
+//! Dated copies of the history database behind `wordle-warlord db backup`
+//! and `db restore`, and the automatic backup taken at startup.
+//!
+//! Backups are named `history-YYYYMMDD.db` after the day they were taken, so
+//! their age is read from the name rather than from file times a copy or sync
+//! could change.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use chrono::{Duration, NaiveDate};
+
+use crate::{
+    local_time::{TimePrefs, format_local},
+    settings::Settings,
+    storage::Database,
+};
+
+const PREFIX: &str = "history-";
+const EXTENSION: &str = ".db";
+
+/// A backup found in the backup directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    pub path: PathBuf,
+    /// The day it was taken.
+    pub date: NaiveDate,
+}
+
+/// The file name of a backup taken on `date`.
+pub fn backup_file_name(date: NaiveDate) -> String {
+    format!("{}{}{}", PREFIX, date.format("%Y%m%d"), EXTENSION)
+}
+
+fn parse_file_name(name: &str) -> Option<NaiveDate> {
+    let stamp = name.strip_prefix(PREFIX)?.strip_suffix(EXTENSION)?;
+    // chrono accepts unpadded fields, which backup_file_name never writes
+    if stamp.len() != 8 || !stamp.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    NaiveDate::parse_from_str(stamp, "%Y%m%d").ok()
+}
+
+/// The backups in `dir`, oldest first; none if it doesn't exist yet.
+pub fn list_backups(dir: &Path) -> Result<Vec<Backup>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        let date = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(parse_file_name);
+        if let Some(date) = date {
+            backups.push(Backup { path, date });
+        }
+    }
+    backups.sort_by_key(|b| b.date);
+    Ok(backups)
+}
+
+/// Whether the newest backup is `interval_days` or more old on `today`, or
+/// there is none. An interval of 0 never asks for one.
+pub fn is_stale(backups: &[Backup], today: NaiveDate, interval_days: u32) -> bool {
+    if interval_days == 0 {
+        return false;
+    }
+    backups
+        .iter()
+        .map(|b| b.date)
+        .max()
+        .is_none_or(|newest| today - newest >= Duration::days(interval_days.into()))
+}
+
+/// Back `db` up into `dir` as taken on `today`, replacing a backup already
+/// taken that day. Returns the backup's path.
+pub fn create_backup(db: &Database, dir: &Path, today: NaiveDate) -> Result<PathBuf> {
+    fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let dest = dir.join(backup_file_name(today));
+    // Written beside it first so a failed backup never leaves half a file under the real name
+    let partial = dest.with_extension("db.partial");
+    if partial.exists() {
+        fs::remove_file(&partial)?;
+    }
+    db.backup_to(&partial)?;
+    fs::rename(&partial, &dest)?;
+    Ok(dest)
+}
+
+/// Delete all but the newest `keep` backups in `dir`, at least one is always
+/// kept. Returns the paths deleted.
+pub fn prune_backups(dir: &Path, keep: usize) -> Result<Vec<PathBuf>> {
+    let backups = list_backups(dir)?;
+    let excess = backups.len().saturating_sub(keep.max(1));
+    let mut pruned = Vec::new();
+    for backup in backups.into_iter().take(excess) {
+        fs::remove_file(&backup.path)
+            .with_context(|| format!("failed to delete {}", backup.path.display()))?;
+        pruned.push(backup.path);
+    }
+    Ok(pruned)
+}
+
+/// Take a backup if the newest is stale and prune old ones, logging both.
+/// Returns the new backup's path, if one was taken.
+pub fn auto_backup(
+    db: &Database,
+    dir: &Path,
+    today: NaiveDate,
+    interval_days: u32,
+    keep: usize,
+) -> Result<Option<PathBuf>> {
+    if !is_stale(&list_backups(dir)?, today, interval_days) {
+        return Ok(None);
+    }
+    let path = create_backup(db, dir, today)?;
+    tracing::info!("Backed up the history database to {}", path.display());
+    for pruned in prune_backups(dir, keep)? {
+        tracing::info!("Deleted old backup {}", pruned.display());
+    }
+    Ok(Some(path))
+}
+
+/// Replace the database at `database` with the backup at `backup`.
+///
+/// Refuses if the database has games or sessions newer than any in the
+/// backup, as they would be lost, unless `force` is set. The app must not be
+/// running on the same database.
+pub fn restore(backup: &Path, database: &Path, force: bool) -> Result<()> {
+    if !backup.is_file() {
+        bail!("no backup at {}", backup.display());
+    }
+    let restored = Database::open_read_only(backup)
+        .and_then(|db| db.latest_activity())
+        .with_context(|| format!("{} is not a readable history database", backup.display()))?;
+
+    if database.exists() && !force {
+        let db = Database::open_read_only(database)?;
+        if let Some(current) = db.latest_activity()?
+            && restored.is_none_or(|restored| current > restored)
+        {
+            let prefs = TimePrefs::from_settings(&Settings::load(&db)?);
+            bail!(
+                "{} has games or sessions newer than the backup (last played {}); \
+                 pass --force to replace it anyway",
+                database.display(),
+                format_local(current, &prefs)
+            );
+        }
+    }
+
+    let partial = database.with_extension("db.partial");
+    fs::copy(backup, &partial).with_context(|| format!("failed to copy {}", backup.display()))?;
+    // A write-ahead log left beside the old database would be applied to the restored one
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = database.as_os_str().to_owned();
+        sidecar.push(suffix);
+        let sidecar = PathBuf::from(sidecar);
+        if sidecar.exists() {
+            fs::remove_file(&sidecar)?;
+        }
+    }
+    fs::rename(&partial, database)?;
+    tracing::info!("Restored the history database from {}", backup.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    fn backup(d: u32) -> Backup {
+        Backup {
+            path: PathBuf::from(backup_file_name(day(d))),
+            date: day(d),
+        }
+    }
+
+    /// A database in `dir` with one solver session played on day `d`.
+    fn database_played_on(dir: &Path, name: &str, d: u32) -> PathBuf {
+        let path = dir.join(name);
+        let db = Database::open(&path).unwrap();
+        db.save_solver_session(Utc.with_ymd_and_hms(2026, 10, d, 12, 0, 0).unwrap(), &[])
+            .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_file_names_round_trip() {
+        assert_eq!(backup_file_name(day(5)), "history-20261005.db");
+        assert_eq!(parse_file_name("history-20261005.db"), Some(day(5)));
+        assert_eq!(parse_file_name("history-2026105.db"), None);
+        assert_eq!(parse_file_name("history.db"), None);
+        assert_eq!(parse_file_name("history-20261005.db.partial"), None);
+    }
+
+    #[test]
+    fn test_staleness() {
+        assert!(is_stale(&[], day(10), 7));
+        assert!(!is_stale(&[], day(10), 0));
+
+        let backups = [backup(1), backup(4)];
+        assert!(!is_stale(&backups, day(10), 7));
+        assert!(is_stale(&backups, day(11), 7));
+        assert!(!is_stale(&backups, day(30), 0));
+        // A backup taken today is never stale
+        assert!(!is_stale(&[backup(10)], day(10), 1));
+    }
+
+    #[test]
+    fn test_prune_keeps_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        for d in [3, 1, 7, 5] {
+            fs::write(dir.path().join(backup_file_name(day(d))), "").unwrap();
+        }
+        fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        let pruned = prune_backups(dir.path(), 2).unwrap();
+        assert_eq!(
+            pruned,
+            [1, 3].map(|d| dir.path().join(backup_file_name(day(d))))
+        );
+        let left: Vec<_> = list_backups(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|b| b.date)
+            .collect();
+        assert_eq!(left, [day(5), day(7)]);
+        assert!(dir.path().join("notes.txt").exists());
+
+        // The newest backup survives a retention of 0
+        prune_backups(dir.path(), 0).unwrap();
+        assert_eq!(list_backups(dir.path()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_auto_backup_only_when_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let backups = dir.path().join("backups");
+        let db = Database::open(database_played_on(dir.path(), "history.db", 1)).unwrap();
+
+        let first = auto_backup(&db, &backups, day(1), 7, 2).unwrap();
+        assert_eq!(first, Some(backups.join("history-20261001.db")));
+        assert_eq!(auto_backup(&db, &backups, day(7), 7, 2).unwrap(), None);
+        for d in [8, 15, 22] {
+            assert!(auto_backup(&db, &backups, day(d), 7, 2).unwrap().is_some());
+        }
+
+        let dates: Vec<_> = list_backups(&backups)
+            .unwrap()
+            .into_iter()
+            .map(|b| b.date)
+            .collect();
+        assert_eq!(dates, [day(15), day(22)]);
+    }
+
+    #[test]
+    fn test_restore_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let database = database_played_on(dir.path(), "history.db", 1);
+        let backup = {
+            let db = Database::open(&database).unwrap();
+            create_backup(&db, &dir.path().join("backups"), day(1)).unwrap()
+        };
+
+        // Play on after the backup, then lose the database
+        let db = Database::open(&database).unwrap();
+        db.save_solver_session(Utc.with_ymd_and_hms(2026, 10, 2, 12, 0, 0).unwrap(), &[])
+            .unwrap();
+        assert_eq!(db.load_solver_sessions().unwrap().len(), 2);
+        let settings = Settings {
+            timezone: Some("Asia/Tokyo".to_string()),
+            ..Settings::default()
+        };
+        settings.save(&db).unwrap();
+        drop(db);
+
+        let error = restore(&backup, &database, false).unwrap_err().to_string();
+        assert!(error.contains("--force"), "{}", error);
+        // Shown in the zone the settings choose, as the app shows it
+        assert!(error.contains("2026-10-02 21:00"), "{}", error);
+        assert_eq!(
+            Database::open(&database)
+                .unwrap()
+                .load_solver_sessions()
+                .unwrap()
+                .len(),
+            2
+        );
+
+        restore(&backup, &database, true).unwrap();
+        let db = Database::open(&database).unwrap();
+        assert_eq!(db.load_solver_sessions().unwrap().len(), 1);
+        drop(db);
+
+        // Nothing newer than the backup now, so no --force needed
+        restore(&backup, &database, false).unwrap();
+    }
+
+    #[test]
+    fn test_restore_into_missing_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let backup = database_played_on(dir.path(), "copy.db", 3);
+        let database = dir.path().join("data").join("history.db");
+        fs::create_dir_all(database.parent().unwrap()).unwrap();
+
+        restore(&backup, &database, false).unwrap();
+        assert_eq!(
+            Database::open(&database)
+                .unwrap()
+                .latest_activity()
+                .unwrap(),
+            Some(Utc.with_ymd_and_hms(2026, 10, 3, 12, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_restore_rejects_non_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let bogus = dir.path().join("history-20261001.db");
+        fs::write(&bogus, "not a database").unwrap();
+        let database = database_played_on(dir.path(), "history.db", 1);
+
+        assert!(restore(&bogus, &database, true).is_err());
+        assert!(restore(&dir.path().join("missing.db"), &database, true).is_err());
+        assert!(
+            Database::open(&database)
+                .unwrap()
+                .latest_activity()
+                .unwrap()
+                .is_some()
+        );
+    }
+}
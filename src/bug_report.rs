@@ -0,0 +1,541 @@
+//! The bundle behind `wordle-warlord bug-report`: what a maintainer needs to
+//! look into a problem, gathered into one `.tar.gz` in the data directory.
+//!
+//! The bundle holds the doctor checks, the build, the terminal and
+//! environment, the last lines of the log, the spectator snapshot, the
+//! settings and aggregate history stats. Words never leave unless asked for:
+//! every answer and guess stored in the database is masked wherever it turns
+//! up in the log or the snapshot, and the games themselves are only added,
+//! as a copy of the database, with `--include-history`.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use flate2::{Compression, write::GzEncoder};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+use crate::{
+    build_info::BuildInfo, doctor::Report, paths::Paths, settings::Settings, storage::Database,
+    ui::STATE_FILE,
+};
+
+/// Log lines kept, newest last.
+pub const LOG_LINES: usize = 500;
+
+/// Settings left out of the bundle. None hold anything private yet; a token
+/// or account setting added later belongs here.
+pub const SECRET_SETTINGS: &[&str] = &[];
+
+/// Environment variables recorded; anything else could hold a secret.
+const ENV_VARS: &[&str] = &[
+    "TERM",
+    "TERM_PROGRAM",
+    "TERM_PROGRAM_VERSION",
+    "COLORTERM",
+    "LANG",
+    "LC_ALL",
+    "NO_COLOR",
+    "WW_LOW_REFRESH",
+];
+
+/// The terminal and system the app runs on.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Environment {
+    pub os: &'static str,
+    pub arch: &'static str,
+    /// Columns and rows; absent when stdout isn't a terminal.
+    pub terminal_size: Option<(u16, u16)>,
+    pub colors: u16,
+    /// The [`ENV_VARS`] that are set.
+    pub env: BTreeMap<&'static str, String>,
+}
+
+impl Environment {
+    pub fn current() -> Self {
+        Self {
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            terminal_size: crossterm::terminal::size().ok(),
+            colors: crossterm::style::available_color_count(),
+            env: ENV_VARS
+                .iter()
+                .filter_map(|&name| Some((name, std::env::var(name).ok()?)))
+                .collect(),
+        }
+    }
+}
+
+/// The files of a bug report, ready to be written out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BugReport {
+    entries: Vec<(&'static str, Vec<u8>)>,
+}
+
+impl BugReport {
+    /// Gather everything from the database and the files under `paths`. The
+    /// stored games are added only if `include_history`.
+    pub fn collect(
+        db: &Database,
+        paths: &Paths,
+        doctor: &Report,
+        environment: &Environment,
+        include_history: bool,
+    ) -> Result<Self> {
+        let words = stored_words(db)?;
+        let mut entries = vec![
+            ("doctor.txt", doctor.render().into_bytes()),
+            ("version.json", BuildInfo::current().to_json().into_bytes()),
+            ("environment.json", to_json(environment)?),
+            (
+                "events.log",
+                redact(&recent_log_lines(&paths.logs(), LOG_LINES)?, &words).into_bytes(),
+            ),
+            ("settings.json", to_json(&settings_json(db)?)?),
+            ("stats.json", to_json(&stats_json(db)?)?),
+        ];
+        // Only there while the app runs or after it quit with the file on
+        if let Ok(state) = fs::read_to_string(paths.root().join(STATE_FILE)) {
+            entries.push((STATE_FILE, redact(&state, &words).into_bytes()));
+        }
+        if include_history {
+            entries.push(("history.db", database_copy(db, paths.root())?));
+        }
+        Ok(Self { entries })
+    }
+
+    /// The names of the files in the bundle, in the order written.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.entries.iter().map(|(name, _)| *name).collect()
+    }
+
+    /// The contents of the file `name`, if the bundle has it.
+    pub fn get(&self, name: &str) -> Option<&[u8]> {
+        self.entries
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, contents)| contents.as_slice())
+    }
+
+    /// Write `bug-report-YYYYMMDD-HHMMSS.tar.gz` into `dir`, the files under
+    /// a directory of the same name, and return its path.
+    pub fn write(&self, dir: &Path, now: DateTime<Local>) -> Result<PathBuf> {
+        let stem = format!("bug-report-{}", now.format("%Y%m%d-%H%M%S"));
+        let path = dir.join(format!("{}.tar.gz", stem));
+        let file = fs::File::create(&path)
+            .with_context(|| format!("failed to create {}", path.display()))?;
+        let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        for (name, contents) in &self.entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(now.timestamp().max(0) as u64);
+            archive.append_data(
+                &mut header,
+                format!("{}/{}", stem, name),
+                contents.as_slice(),
+            )?;
+        }
+        archive
+            .into_inner()?
+            .finish()
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+fn to_json(value: &impl Serialize) -> Result<Vec<u8>> {
+    let mut json = serde_json::to_vec_pretty(value)?;
+    json.push(b'\n');
+    Ok(json)
+}
+
+/// Every answer and guess the database holds, lowercased: games, solver
+/// sessions and their suggestions, snapshots, and the blacklist.
+fn stored_words(db: &Database) -> Result<HashSet<String>> {
+    let mut words = HashSet::new();
+    for (_, game) in db.load_games_with_ids(true)? {
+        words.insert(game.target_word);
+        words.extend(game.guesses.into_iter().map(|g| g.word));
+    }
+    for session in db.load_solver_sessions()? {
+        words.extend(session.resolved_word);
+        for guess in session.guesses {
+            words.insert(guess.word);
+            words.extend(guess.optimal_word);
+        }
+    }
+    for game in db.load_kids_games()? {
+        words.insert(game.target_word);
+        words.extend(game.guesses);
+    }
+    for game in db.load_multi_board_games()? {
+        words.extend(game.boards.into_iter().map(|b| b.target));
+        words.extend(game.guesses);
+    }
+    for snapshot in db.load_snapshots()? {
+        words.extend(snapshot.state.guesses().iter().map(|g| g.word.clone()));
+    }
+    words.extend(db.in_progress_words()?);
+    words.extend(db.load_blacklist()?);
+    // Phrase games store their words space-separated
+    Ok(words
+        .iter()
+        .flat_map(|w| w.split_whitespace())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect())
+}
+
+/// `text` with every run of letters that spells one of `words`, in any case,
+/// replaced by as many `*`s.
+pub fn redact(text: &str, words: &HashSet<String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut run = String::new();
+    let flush = |run: &mut String, out: &mut String| {
+        if words.contains(&run.to_lowercase()) {
+            out.extend(run.chars().map(|_| '*'));
+        } else {
+            out.push_str(run);
+        }
+        run.clear();
+    };
+    for c in text.chars() {
+        if c.is_alphabetic() {
+            run.push(c);
+        } else {
+            flush(&mut run, &mut out);
+            out.push(c);
+        }
+    }
+    flush(&mut run, &mut out);
+    out
+}
+
+/// The last `count` lines of the daily log files in `dir`, across files.
+fn recent_log_lines(dir: &Path, count: usize) -> Result<String> {
+    if !dir.exists() {
+        return Ok(String::new());
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+    // Named by day, so the newest sorts last
+    files.sort();
+
+    let mut lines: Vec<String> = Vec::new();
+    for path in files.iter().rev() {
+        let text = fs::read_to_string(path).unwrap_or_default();
+        let mut older: Vec<String> = text.lines().map(str::to_string).collect();
+        older.append(&mut lines);
+        lines = older;
+        if lines.len() >= count {
+            break;
+        }
+    }
+    let start = lines.len().saturating_sub(count);
+    Ok(lines[start..].iter().map(|l| format!("{}\n", l)).collect())
+}
+
+/// The settings as stored, without the [`SECRET_SETTINGS`].
+fn settings_json(db: &Database) -> Result<Value> {
+    let mut settings = serde_json::to_value(Settings::load(db)?)?;
+    if let Value::Object(map) = &mut settings {
+        strip_secrets(map, SECRET_SETTINGS);
+    }
+    Ok(settings)
+}
+
+/// Drop the `secrets` from a settings object.
+pub fn strip_secrets(settings: &mut Map<String, Value>, secrets: &[&str]) {
+    settings.retain(|key, _| !secrets.contains(&key.as_str()));
+}
+
+/// Counts and averages over the history; never a word.
+fn stats_json(db: &Database) -> Result<Value> {
+    let quick = db.quick_stats()?;
+    let by_version = |averages: Vec<crate::storage::VersionAverage>| -> Vec<Value> {
+        averages
+            .into_iter()
+            .map(|a| {
+                json!({
+                    "app_version": a.app_version,
+                    "count": a.count,
+                    "average_guesses": a.average_guesses,
+                })
+            })
+            .collect()
+    };
+    Ok(json!({
+        "schema_version": db.schema_version()?,
+        "database_bytes": db.path().and_then(|p| fs::metadata(p).ok()).map(|m| m.len()),
+        "pending_writes": db.pending_writes(),
+        "games": db.count_games(true)?,
+        "archived_games": db.count_games(true)? - db.count_games(false)?,
+        "solver_sessions": db.count_solver_sessions()?,
+        "kids_games": db.load_kids_games()?.len(),
+        "snapshots": db.load_snapshots()?.len(),
+        "blacklisted_words": db.load_blacklist()?.len(),
+        "wins": quick.wins,
+        "losses": quick.losses,
+        "average_guesses": quick.average_guesses,
+        "current_streak": quick.current_streak,
+        "by_difficulty": db
+            .game_stats_by_difficulty(true)?
+            .into_iter()
+            .map(|s| json!({
+                "difficulty": s.key.as_str(),
+                "games": s.games,
+                "wins": s.wins,
+                "average_guesses": s.average_guesses,
+            }))
+            .collect::<Vec<_>>(),
+        "multi_board": db
+            .multi_board_stats()?
+            .into_iter()
+            .map(|s| json!({
+                "boards": s.key,
+                "games": s.games,
+                "wins": s.wins,
+                "average_guesses": s.average_guesses,
+            }))
+            .collect::<Vec<_>>(),
+        "games_by_version": by_version(db.game_averages_by_version(true)?),
+        "sessions_by_version": by_version(db.session_averages_by_version()?),
+    }))
+}
+
+/// A consistent copy of the database's bytes, taken through a scratch file
+/// in `dir` that is removed again.
+fn database_copy(db: &Database, dir: &Path) -> Result<Vec<u8>> {
+    let scratch = dir.join(".bug-report-history.db");
+    let _ = fs::remove_file(&scratch);
+    db.backup_to(&scratch)?;
+    let bytes = fs::read(&scratch);
+    let _ = fs::remove_file(&scratch);
+    Ok(bytes?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        settings::Difficulty,
+        storage::new_write_id,
+        ui::history::{GameGuess, GameOutcome, GameRecord, solver_types::SolverGuess},
+    };
+    use chrono::{TimeZone, Utc};
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    fn environment() -> Environment {
+        Environment {
+            os: "linux",
+            arch: "x86_64",
+            terminal_size: Some((120, 40)),
+            colors: 256,
+            env: BTreeMap::from([("TERM", "xterm-256color".to_string())]),
+        }
+    }
+
+    /// A data directory with a game won on `crane` after `slate`, a log
+    /// mentioning both, and a spectator snapshot of the finished board.
+    fn data_dir() -> (tempfile::TempDir, Paths, Database) {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = Paths::new(dir.path());
+        let db = Database::open(paths.database()).unwrap();
+        db.save_game(&GameRecord {
+            timestamp: Utc.with_ymd_and_hms(2026, 10, 14, 21, 0, 0).unwrap(),
+            target_word: "crane".to_string(),
+            guesses: vec![
+                GameGuess::new("slate".to_string(), vec![]),
+                GameGuess::new("crane".to_string(), vec![]),
+            ],
+            outcome: GameOutcome::Won { guesses: 2 },
+            blitz: false,
+            duration_secs: Some(40.0),
+            app_version: None,
+            difficulty: Difficulty::Normal,
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        })
+        .unwrap();
+
+        fs::create_dir_all(paths.logs()).unwrap();
+        fs::write(
+            paths.logs().join("wordle-warlord.log.2026-10-14"),
+            "INFO Guess: slate -> XXYXG\nINFO Game won! The word was CRANE\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join(STATE_FILE),
+            r#"{"board":[{"word":"slate","pattern":"XXYXG"}],"answer":"crane"}"#,
+        )
+        .unwrap();
+        (dir, paths, db)
+    }
+
+    fn contents(report: &BugReport, name: &str) -> String {
+        String::from_utf8_lossy(report.get(name).unwrap()).into_owned()
+    }
+
+    #[test]
+    fn test_default_bundle_holds_no_stored_words() {
+        let (_dir, paths, db) = data_dir();
+        let doctor = Report::new(vec![crate::doctor::check_data_dir(paths.root())]);
+        let report = BugReport::collect(&db, &paths, &doctor, &environment(), false).unwrap();
+
+        assert_eq!(
+            report.names(),
+            [
+                "doctor.txt",
+                "version.json",
+                "environment.json",
+                "events.log",
+                "settings.json",
+                "stats.json",
+                STATE_FILE,
+            ]
+        );
+        for name in report.names() {
+            let text = contents(&report, name).to_lowercase();
+            assert!(!text.contains("crane"), "{} has the answer", name);
+            assert!(!text.contains("slate"), "{} has a guess", name);
+        }
+        assert_eq!(
+            contents(&report, "events.log"),
+            "INFO Guess: ***** -> XXYXG\nINFO Game won! The word was *****\n"
+        );
+
+        let stats: Value = serde_json::from_slice(report.get("stats.json").unwrap()).unwrap();
+        assert_eq!(stats["games"], 1);
+        assert_eq!(stats["wins"], 1);
+    }
+
+    #[test]
+    fn test_in_progress_words_are_redacted() {
+        let (_dir, paths, db) = data_dir();
+        db.checkpoint_game(
+            &new_write_id(),
+            &GameRecord {
+                timestamp: Utc::now(),
+                target_word: "magic".to_string(),
+                guesses: vec![GameGuess::new("house".to_string(), vec![])],
+                outcome: GameOutcome::Lost,
+                blitz: false,
+                duration_secs: None,
+                app_version: None,
+                difficulty: Difficulty::Normal,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty: None,
+            },
+        )
+        .unwrap();
+        let guess = SolverGuess {
+            word: "world".to_string(),
+            pool_size_before: 10,
+            pool_size_after: 2,
+            entropy: 1.0,
+            optimal_word: Some("apple".to_string()),
+            optimal_entropy: None,
+            deviation_score: None,
+            metric: None,
+            feedback: None,
+            note: None,
+        };
+        db.checkpoint_solver_session(&new_write_id(), Utc::now(), &[guess])
+            .unwrap();
+        fs::write(
+            paths.logs().join("wordle-warlord.log.2026-10-15"),
+            "INFO Guess: house\nINFO Solver guess: world, best apple\nINFO Answer: magic\n",
+        )
+        .unwrap();
+
+        let report =
+            BugReport::collect(&db, &paths, &Report::new(vec![]), &environment(), false).unwrap();
+
+        let log = contents(&report, "events.log");
+        for word in ["magic", "house", "world", "apple"] {
+            assert!(!log.contains(word), "{} in {}", word, log);
+        }
+    }
+
+    #[test]
+    fn test_include_history_adds_the_database() {
+        let (_dir, paths, db) = data_dir();
+        let report =
+            BugReport::collect(&db, &paths, &Report::new(vec![]), &environment(), true).unwrap();
+
+        let copy = report.get("history.db").unwrap();
+        assert!(copy.starts_with(b"SQLite format 3\0"));
+        assert!(!paths.root().join(".bug-report-history.db").exists());
+    }
+
+    #[test]
+    fn test_write_archives_every_file() {
+        let (dir, paths, db) = data_dir();
+        let report =
+            BugReport::collect(&db, &paths, &Report::new(vec![]), &environment(), false).unwrap();
+        let now = Local.with_ymd_and_hms(2026, 10, 15, 9, 30, 0).unwrap();
+        let path = report.write(dir.path(), now).unwrap();
+        assert_eq!(path, dir.path().join("bug-report-20261015-093000.tar.gz"));
+
+        let mut archive = tar::Archive::new(GzDecoder::new(fs::File::open(&path).unwrap()));
+        let mut names = Vec::new();
+        for entry in archive.entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let name = entry.path().unwrap().display().to_string();
+            let mut text = String::new();
+            entry.read_to_string(&mut text).unwrap();
+            assert!(!text.contains("crane"), "{}", name);
+            names.push(name);
+        }
+        assert_eq!(names.len(), report.names().len());
+        assert_eq!(names[0], "bug-report-20261015-093000/doctor.txt");
+    }
+
+    #[test]
+    fn test_log_keeps_the_newest_lines_across_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("wordle-warlord.log.2026-10-13"), "a\nb\n").unwrap();
+        fs::write(dir.path().join("wordle-warlord.log.2026-10-14"), "c\n").unwrap();
+
+        assert_eq!(recent_log_lines(dir.path(), 2).unwrap(), "b\nc\n");
+        assert_eq!(recent_log_lines(dir.path(), 10).unwrap(), "a\nb\nc\n");
+        assert_eq!(recent_log_lines(&dir.path().join("none"), 10).unwrap(), "");
+    }
+
+    #[test]
+    fn test_redact_matches_whole_words_in_any_case() {
+        let words = HashSet::from(["crane".to_string()]);
+        assert_eq!(
+            redact("Crane, CRANE cranes crane.", &words),
+            "*****, ***** cranes *****."
+        );
+    }
+
+    #[test]
+    fn test_strip_secrets() {
+        let mut settings = Map::new();
+        settings.insert("api_token".to_string(), json!("hunter2"));
+        settings.insert("blitz_seconds".to_string(), json!(30));
+
+        strip_secrets(&mut settings, &["api_token"]);
+        assert_eq!(settings.keys().collect::<Vec<_>>(), ["blitz_seconds"]);
+    }
+}
@@ -0,0 +1,136 @@
+//! What this binary was built from, for packagers and bug reports.
+
+use serde::Serialize;
+
+use crate::storage::{APP_VERSION, SCHEMA_VERSION};
+
+/// Details of the build, captured by `build.rs`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    /// Commit built from; absent outside a git checkout, e.g. from crates.io.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<&'static str>,
+    /// Whether tracked files differed from the commit; absent with it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dirty: Option<bool>,
+    /// UTC date of the build, `YYYY-MM-DD`.
+    pub build_date: String,
+    pub target: &'static str,
+    pub features: Vec<&'static str>,
+    /// Schema version the binary migrates databases to.
+    pub schema_version: i64,
+}
+
+impl BuildInfo {
+    pub fn current() -> Self {
+        let built_at = env!("BUILD_TIMESTAMP").parse().unwrap_or(0);
+        Self {
+            version: APP_VERSION,
+            commit: option_env!("BUILD_GIT_COMMIT"),
+            dirty: option_env!("BUILD_GIT_DIRTY").map(|dirty| dirty == "true"),
+            build_date: chrono::DateTime::from_timestamp(built_at, 0)
+                .map_or("unknown".to_string(), |t| t.format("%Y-%m-%d").to_string()),
+            target: env!("BUILD_TARGET"),
+            features: env!("BUILD_FEATURES")
+                .split(',')
+                .filter(|f| !f.is_empty())
+                .collect(),
+            schema_version: SCHEMA_VERSION,
+        }
+    }
+
+    /// The commit shortened to 12 characters, marked `-dirty` when tracked
+    /// files had changed.
+    pub fn short_commit(&self) -> Option<String> {
+        self.commit.map(|commit| {
+            let short = &commit[..commit.len().min(12)];
+            if self.dirty == Some(true) {
+                format!("{}-dirty", short)
+            } else {
+                short.to_string()
+            }
+        })
+    }
+
+    /// One line for logs and help text, e.g.
+    /// `wordle-warlord 1.0.0 (1a2b3c4d5e6f, 2026-10-15, x86_64-unknown-linux-gnu, schema 14, features: parallel)`.
+    pub fn summary(&self) -> String {
+        let mut details: Vec<String> = self.short_commit().into_iter().collect();
+        details.push(self.build_date.clone());
+        details.push(self.target.to_string());
+        details.push(format!("schema {}", self.schema_version));
+        let features = if self.features.is_empty() {
+            "none".to_string()
+        } else {
+            self.features.join(", ")
+        };
+        details.push(format!("features: {}", features));
+        format!("wordle-warlord {} ({})", self.version, details.join(", "))
+    }
+
+    /// The details as a JSON object.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("build info serializes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_has_required_keys() {
+        let json: serde_json::Value =
+            serde_json::from_str(&BuildInfo::current().to_json()).unwrap();
+        let object = json.as_object().unwrap();
+        for key in [
+            "version",
+            "build_date",
+            "target",
+            "features",
+            "schema_version",
+        ] {
+            assert!(object.contains_key(key), "missing {}", key);
+        }
+        assert_eq!(json["version"], APP_VERSION);
+        assert_eq!(json["schema_version"], SCHEMA_VERSION);
+        assert!(json["features"].is_array());
+        // The commit and its dirty flag come together or not at all
+        assert_eq!(object.contains_key("commit"), object.contains_key("dirty"));
+    }
+
+    #[test]
+    fn test_commit_omitted_outside_a_checkout() {
+        let info = BuildInfo {
+            commit: None,
+            dirty: None,
+            features: vec![],
+            ..BuildInfo::current()
+        };
+        let json: serde_json::Value = serde_json::from_str(&info.to_json()).unwrap();
+        assert!(json.get("commit").is_none());
+        assert!(json.get("dirty").is_none());
+        assert!(!info.summary().contains("dirty"));
+        assert!(info.summary().ends_with("features: none)"));
+    }
+
+    #[test]
+    fn test_summary_marks_a_dirty_tree() {
+        let info = BuildInfo {
+            commit: Some("0123456789abcdef0123"),
+            dirty: Some(true),
+            build_date: "2026-10-15".to_string(),
+            target: "x86_64-unknown-linux-gnu",
+            features: vec!["parallel"],
+            ..BuildInfo::current()
+        };
+        assert_eq!(
+            info.summary(),
+            format!(
+                "wordle-warlord {} (0123456789ab-dirty, 2026-10-15, x86_64-unknown-linux-gnu, schema {}, features: parallel)",
+                APP_VERSION, SCHEMA_VERSION
+            )
+        );
+    }
+}
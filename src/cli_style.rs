@@ -0,0 +1,160 @@
+//! Colour for the text printed by subcommands outside the TUI.
+//!
+//! Whether to colour is settled once, by [`CliStyle::detect`], and everything
+//! printed goes through the resulting style, so piped output stays plain.
+
+use std::{ffi::OsString, fmt::Display, io::IsTerminal};
+
+use crossterm::style::{Color, Stylize};
+
+use crate::solver::Feedback;
+
+/// The `--color` choice.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Colour when stdout is a terminal and `NO_COLOR` isn't set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to colour, given whether stdout is a terminal and the value of
+    /// `NO_COLOR`. An empty `NO_COLOR` counts as unset, as the convention asks.
+    pub fn resolve(self, stdout_is_tty: bool, no_color: Option<OsString>) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => stdout_is_tty && no_color.is_none_or(|v| v.is_empty()),
+        }
+    }
+}
+
+/// How a subcommand's output is styled: coloured, or plain text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CliStyle {
+    color: bool,
+}
+
+impl CliStyle {
+    /// Plain text, as written to files.
+    pub const PLAIN: CliStyle = CliStyle { color: false };
+    /// Coloured whatever stdout is.
+    pub const COLOR: CliStyle = CliStyle { color: true };
+
+    /// The style for this process's stdout under `choice`.
+    pub fn detect(choice: ColorChoice) -> Self {
+        let color = choice.resolve(
+            std::io::stdout().is_terminal(),
+            std::env::var_os("NO_COLOR"),
+        );
+        CliStyle { color }
+    }
+
+    pub fn is_color(self) -> bool {
+        self.color
+    }
+
+    /// `text` in `color`, or unchanged when plain.
+    pub fn paint(self, text: impl Display, color: Color) -> String {
+        if self.color {
+            text.to_string().with(color).to_string()
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// `text` in bold, or unchanged when plain.
+    pub fn bold(self, text: impl Display) -> String {
+        if self.color {
+            text.to_string().bold().to_string()
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// `word` as a row of tiles coloured by `feedback`; plain, the word and
+    /// its pattern as a transcript line has them, e.g. `crane XXGGG`.
+    pub fn tiles(self, word: &str, feedback: &[Feedback]) -> String {
+        if !self.color {
+            return format!("{} {}", word, crate::scoring::pattern_string(feedback));
+        }
+        word.chars()
+            .zip(feedback)
+            .map(|(c, f)| {
+                format!(" {} ", c.to_ascii_uppercase())
+                    .with(Color::Black)
+                    .on(tile_color(*f))
+                    .to_string()
+            })
+            .collect()
+    }
+
+    /// `text` coloured by how close `value` comes to `best`: green from 90%
+    /// of it, yellow from 60%, dark gray below.
+    pub fn graded(self, text: impl Display, value: f64, best: f64) -> String {
+        let fraction = if best > 0.0 { value / best } else { 1.0 };
+        let color = if fraction >= 0.9 {
+            Color::Green
+        } else if fraction >= 0.6 {
+            Color::Yellow
+        } else {
+            Color::DarkGrey
+        };
+        self.paint(text, color)
+    }
+}
+
+/// The background of a tile with `feedback`, as the TUI draws it.
+fn tile_color(feedback: Feedback) -> Color {
+    match feedback {
+        Feedback::Green => Color::Green,
+        Feedback::Yellow => Color::Yellow,
+        Feedback::Gray => Color::Grey,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Feedback::{Gray, Green, Yellow};
+
+    #[test]
+    fn test_resolve() {
+        assert!(ColorChoice::Always.resolve(false, Some("1".into())));
+        assert!(!ColorChoice::Never.resolve(true, None));
+        assert!(ColorChoice::Auto.resolve(true, None));
+        assert!(ColorChoice::Auto.resolve(true, Some("".into())));
+        assert!(!ColorChoice::Auto.resolve(true, Some("1".into())));
+        assert!(!ColorChoice::Auto.resolve(false, None));
+    }
+
+    #[test]
+    fn test_plain_output() {
+        let style = CliStyle::PLAIN;
+        assert_eq!(
+            style.tiles("crane", &[Gray, Yellow, Green, Green, Green]),
+            "crane XYGGG"
+        );
+        assert_eq!(style.graded("slate 40", 10.0, 40.0), "slate 40");
+        assert_eq!(style.paint("+crane", Color::Green), "+crane");
+        assert_eq!(style.bold("Games"), "Games");
+    }
+
+    #[test]
+    fn test_colored_output() {
+        let style = CliStyle::COLOR;
+        let tiles = style.tiles("ab", &[Green, Gray]);
+        assert_eq!(
+            tiles,
+            "\x1b[48;5;10m\x1b[38;5;0m A \x1b[49m\x1b[39m\
+             \x1b[48;5;7m\x1b[38;5;0m B \x1b[49m\x1b[39m"
+        );
+
+        assert_eq!(style.graded("x", 40.0, 40.0), "\x1b[38;5;10mx\x1b[39m");
+        assert_eq!(style.graded("x", 30.0, 40.0), "\x1b[38;5;11mx\x1b[39m");
+        assert_eq!(style.graded("x", 10.0, 40.0), "\x1b[38;5;8mx\x1b[39m");
+        assert!(style.bold("Games").starts_with("\x1b[1m"));
+    }
+}
@@ -0,0 +1,380 @@
+//! Environment checks behind `wordle-warlord doctor`.
+//!
+//! Each check stands alone and returns a [`CheckResult`], so the TUI can run
+//! the cheap ones at startup and warn about soft failures.
+
+use std::{fs, io, path::Path};
+
+use crate::{
+    error::Result,
+    storage::{Database, SCHEMA_VERSION},
+    wordlist::{Fetcher, Wordlist, WordlistSource},
+};
+
+/// Smallest terminal the TUI lays out without clipping panels.
+pub const MIN_TERMINAL_SIZE: (u16, u16) = (80, 24);
+
+/// Whether a failed check stops the app from working at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Critical,
+    Soft,
+}
+
+/// The outcome of one check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckResult {
+    pub name: String,
+    pub passed: bool,
+    pub severity: Severity,
+    pub detail: String,
+    /// What to try when the check fails.
+    pub hint: Option<&'static str>,
+}
+
+impl CheckResult {
+    fn pass(name: impl Into<String>, severity: Severity, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            passed: true,
+            severity,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn fail(
+        name: impl Into<String>,
+        severity: Severity,
+        detail: impl Into<String>,
+        hint: &'static str,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            passed: false,
+            severity,
+            detail: detail.into(),
+            hint: Some(hint),
+        }
+    }
+
+    /// A failure that doesn't stop the app but is worth a warning.
+    pub fn is_soft_failure(&self) -> bool {
+        !self.passed && self.severity == Severity::Soft
+    }
+}
+
+/// Every check's result, in the order they ran.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub results: Vec<CheckResult>,
+}
+
+impl Report {
+    pub fn new(results: Vec<CheckResult>) -> Self {
+        Self { results }
+    }
+
+    /// 1 if any critical check failed, otherwise 0.
+    pub fn exit_code(&self) -> i32 {
+        let critical = self
+            .results
+            .iter()
+            .any(|r| !r.passed && r.severity == Severity::Critical);
+        i32::from(critical)
+    }
+
+    /// A PASS/FAIL/WARN table, with a hint under each failure.
+    pub fn render(&self) -> String {
+        let width = self.results.iter().map(|r| r.name.len()).max().unwrap_or(0);
+
+        let mut out = String::new();
+        for result in &self.results {
+            let status = match (result.passed, result.severity) {
+                (true, _) => "PASS",
+                (false, Severity::Critical) => "FAIL",
+                (false, Severity::Soft) => "WARN",
+            };
+            out.push_str(&format!(
+                "{}  {:<width$}  {}\n",
+                status, result.name, result.detail
+            ));
+            if let Some(hint) = result.hint {
+                out.push_str(&format!("      {:<width$}  → {}\n", "", hint));
+            }
+        }
+        out
+    }
+}
+
+/// Can files be created in `dir`?
+pub fn check_data_dir(dir: &Path) -> CheckResult {
+    let name = "Data directory";
+    let probe = dir.join(".doctor-probe");
+    match fs::write(&probe, b"").and_then(|_| fs::remove_file(&probe)) {
+        Ok(()) => CheckResult::pass(
+            name,
+            Severity::Critical,
+            format!("{} is writable", dir.display()),
+        ),
+        Err(e) => CheckResult::fail(
+            name,
+            Severity::Critical,
+            format!("cannot write to {}: {}", dir.display(), e),
+            "pick a writable directory with --data-dir or WORDLE_WARLORD_HOME; history and word lists are kept there",
+        ),
+    }
+}
+
+/// Does the database open and reach the current schema version?
+pub fn check_database(path: &Path) -> CheckResult {
+    let name = "Database";
+    let opened = Database::open(path).and_then(|db| db.schema_version());
+    match opened {
+        Ok(SCHEMA_VERSION) => CheckResult::pass(
+            name,
+            Severity::Critical,
+            format!("{} at schema version {}", path.display(), SCHEMA_VERSION),
+        ),
+        Ok(version) => CheckResult::fail(
+            name,
+            Severity::Critical,
+            format!(
+                "{} is at schema version {}, expected {}",
+                path.display(),
+                version,
+                SCHEMA_VERSION
+            ),
+            "the database was written by a newer version; upgrade, or move it aside",
+        ),
+        Err(e) => CheckResult::fail(
+            name,
+            Severity::Critical,
+            format!("cannot open {}: {:#}", path.display(), e),
+            "check the file isn't locked by another instance; move it aside to start fresh",
+        ),
+    }
+}
+
+/// Is the `name` list available without the network, and does it look right?
+///
+/// A list that can't be loaded offline is only a warning when it is
+/// `downloadable`, since the next start fetches it.
+pub fn check_wordlist(name: &str, loaded: Result<Wordlist>, downloadable: bool) -> CheckResult {
+    let check = format!("{} list", name);
+    let list = match loaded {
+        Ok(list) => list,
+        Err(e) => {
+            let severity = if downloadable {
+                Severity::Soft
+            } else {
+                Severity::Critical
+            };
+            return CheckResult::fail(
+                check,
+                severity,
+                e.to_string(),
+                "start once with network access, or pass --words/--solutions",
+            );
+        }
+    };
+
+    let word_len = list.words.first().map_or(0, |w| w.len());
+    let malformed = list
+        .words
+        .iter()
+        .filter(|w| w.len() != word_len || !w.bytes().all(|b| b.is_ascii_lowercase()))
+        .count();
+    if list.words.is_empty() || malformed > 0 {
+        return CheckResult::fail(
+            check,
+            Severity::Critical,
+            format!(
+                "{} words, {} not lowercase {}-letter words",
+                list.words.len(),
+                malformed,
+                word_len
+            ),
+            "delete the cached file so it is downloaded again, or fix the file passed in",
+        );
+    }
+
+    let source = match list.source {
+        WordlistSource::Path => "given path",
+        WordlistSource::Cache => "cache",
+        WordlistSource::Download => "download",
+        WordlistSource::Bundled => "bundled copy",
+    };
+    CheckResult::pass(
+        check,
+        Severity::Critical,
+        format!("{} words from the {}", list.words.len(), source),
+    )
+}
+
+/// Can the `name` list be downloaded from `url`?
+pub fn check_download(name: &str, url: &str, fetcher: &impl Fetcher) -> CheckResult {
+    let check = format!("{} download", name);
+    match fetcher.fetch(url) {
+        Ok(_) => CheckResult::pass(check, Severity::Soft, format!("{} is reachable", url)),
+        Err(e) => CheckResult::fail(
+            check,
+            Severity::Soft,
+            format!("{}: {}", url, e),
+            "cached or bundled lists still work; check proxy and firewall settings to refresh them",
+        ),
+    }
+}
+
+/// Is the terminal big enough, and does it show colors?
+///
+/// Takes the measurements rather than querying the terminal so it can be
+/// tested; pass `crossterm::terminal::size()` and
+/// `crossterm::style::available_color_count()`.
+pub fn check_terminal(size: io::Result<(u16, u16)>, colors: u16) -> CheckResult {
+    let name = "Terminal";
+    let (min_cols, min_rows) = MIN_TERMINAL_SIZE;
+    let (cols, rows) = match size {
+        Ok(size) => size,
+        Err(e) => {
+            return CheckResult::fail(
+                name,
+                Severity::Soft,
+                format!("size unknown: {}", e),
+                "run in an interactive terminal, or use --plain",
+            );
+        }
+    };
+
+    if cols < min_cols || rows < min_rows {
+        return CheckResult::fail(
+            name,
+            Severity::Soft,
+            format!("{}x{}, smaller than {}x{}", cols, rows, min_cols, min_rows),
+            "enlarge the window, or use --plain",
+        );
+    }
+    if colors < 8 {
+        return CheckResult::fail(
+            name,
+            Severity::Soft,
+            format!("{}x{}, {} colors", cols, rows, colors),
+            "feedback tiles need color; set TERM to a color terminal, or use --plain",
+        );
+    }
+    CheckResult::pass(
+        name,
+        Severity::Soft,
+        format!("{}x{}, {} colors", cols, rows, colors),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::WordleError, wordlist::Cleanup};
+
+    struct StubFetcher(bool);
+
+    impl Fetcher for StubFetcher {
+        fn fetch(&self, _url: &str) -> Result<String> {
+            if self.0 {
+                Ok("crane\n".to_string())
+            } else {
+                Err(WordleError::Io(io::Error::other("timed out")))
+            }
+        }
+    }
+
+    fn list(words: &[&str], source: WordlistSource) -> Result<Wordlist> {
+        Ok(Wordlist {
+            words: words.iter().map(|w| w.to_string()).collect(),
+            source,
+            path: None,
+            cleanup: Cleanup::default(),
+        })
+    }
+
+    #[test]
+    fn test_data_dir_and_database_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(check_data_dir(dir.path()).passed);
+        assert!(!check_data_dir(&dir.path().join("missing")).passed);
+
+        assert!(check_database(&dir.path().join("history.db")).passed);
+        let garbage = dir.path().join("garbage.db");
+        fs::write(&garbage, "not a database, just text that is long enough").unwrap();
+        let result = check_database(&garbage);
+        assert!(!result.passed);
+        assert_eq!(result.severity, Severity::Critical);
+    }
+
+    #[test]
+    fn test_wordlist_check_grades_problems() {
+        let ok = check_wordlist(
+            "Word",
+            list(&["crane", "slate"], WordlistSource::Cache),
+            true,
+        );
+        assert!(ok.passed);
+        assert_eq!(ok.detail, "2 words from the cache");
+
+        let malformed = check_wordlist(
+            "Word",
+            list(&["crane", "<html>"], WordlistSource::Cache),
+            true,
+        );
+        assert!(!malformed.passed);
+        assert_eq!(malformed.severity, Severity::Critical);
+
+        let missing = || Err(WordleError::NoWordlist("word list"));
+        assert!(check_wordlist("Word", missing(), true).is_soft_failure());
+        let unreachable = check_wordlist("Word", missing(), false);
+        assert!(!unreachable.passed);
+        assert_eq!(unreachable.severity, Severity::Critical);
+    }
+
+    #[test]
+    fn test_download_and_terminal_failures_are_soft() {
+        assert!(check_download("Word list", "https://example.invalid", &StubFetcher(true)).passed);
+        assert!(
+            check_download("Word list", "https://example.invalid", &StubFetcher(false))
+                .is_soft_failure()
+        );
+
+        assert!(check_terminal(Ok((120, 40)), 256).passed);
+        assert!(check_terminal(Ok((60, 20)), 256).is_soft_failure());
+        assert!(check_terminal(Ok((120, 40)), 2).is_soft_failure());
+        assert!(check_terminal(Err(io::Error::other("not a tty")), 0).is_soft_failure());
+    }
+
+    #[test]
+    fn test_exit_code_only_for_critical_failures() {
+        let soft = check_terminal(Ok((60, 20)), 256);
+        let critical = check_wordlist("Word", list(&[], WordlistSource::Path), true);
+        let pass = check_terminal(Ok((120, 40)), 256);
+
+        assert_eq!(Report::new(vec![pass.clone(), soft.clone()]).exit_code(), 0);
+        assert_eq!(Report::new(vec![pass, soft, critical]).exit_code(), 1);
+        assert_eq!(Report::default().exit_code(), 0);
+    }
+
+    #[test]
+    fn test_report_table() {
+        let report = Report::new(vec![
+            check_terminal(Ok((120, 40)), 256),
+            check_terminal(Ok((60, 20)), 256),
+            check_wordlist("Word", list(&[], WordlistSource::Path), true),
+        ]);
+
+        let table = report.render();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "PASS  Terminal   120x40, 256 colors");
+        assert_eq!(lines[1], "WARN  Terminal   60x20, smaller than 80x24");
+        assert_eq!(
+            lines[2],
+            "                 → enlarge the window, or use --plain"
+        );
+        assert!(lines[3].starts_with("FAIL  Word list  0 words"));
+    }
+}
@@ -0,0 +1,61 @@
+use thiserror::Error;
+
+/// Errors produced by the solver, scoring, and word list modules.
+#[derive(Debug, Error)]
+pub enum WordleError {
+    #[error("character must be G/Y/X, got {0}")]
+    PatternChar(char),
+
+    #[error("length mismatch: expected {expected}, got {got}")]
+    LengthMismatch { expected: usize, got: usize },
+
+    #[error("not in allowed list: {0}")]
+    WordNotAllowed(String),
+
+    #[error("no candidate words remain")]
+    EmptyPool,
+
+    #[error("needed {wanted} different words, only {available} available")]
+    TooFewWords { wanted: usize, available: usize },
+
+    #[error("expected `word pattern`, got {0} field(s)")]
+    FieldCount(usize),
+
+    #[error("expected {expected} word(s), got {got}")]
+    SegmentCount { expected: usize, got: usize },
+
+    #[error("hard mode: {0}")]
+    HardMode(String),
+
+    #[error("impossible pattern: {0}")]
+    ImpossiblePattern(String),
+
+    #[error("cannot use data directory {}: {source}", path.display())]
+    DataDir {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("no {0} available: none cached or downloaded, and none bundled")]
+    NoWordlist(&'static str),
+
+    #[error("download failed: {0}")]
+    Download(#[from] reqwest::Error),
+
+    #[error("database error: {0}")]
+    Db(#[from] sqlx::Error),
+}
+
+pub type Result<T> = std::result::Result<T, WordleError>;
+
+/// The first line of a guess transcript that could not be read.
+#[derive(Debug, Error)]
+#[error("line {line}: {source}")]
+pub struct TranscriptError {
+    /// 1-based line number within the transcript.
+    pub line: usize,
+    pub source: WordleError,
+}
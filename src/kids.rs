@@ -0,0 +1,109 @@
+//! A kids' profile for game mode: everyday answers, no way to lose, and a
+//! few common words always on offer.
+
+use crate::{
+    priors::{PriorWeights, prior},
+    wordtable::WordTable,
+};
+
+/// Everyday words a kids' game draws its answers from, sorted.
+pub const KIDS_WORDS: &[&str] = &[
+    "apple", "beach", "berry", "black", "block", "board", "brave", "bread", "brick", "bring",
+    "brown", "brush", "bunny", "candy", "chair", "chalk", "cheer", "chick", "child", "clean",
+    "clock", "cloud", "clown", "color", "couch", "crane", "crown", "daisy", "dance", "dream",
+    "dress", "drink", "eagle", "earth", "eight", "fairy", "feast", "field", "float", "floor",
+    "flute", "fresh", "fruit", "funny", "ghost", "giant", "glass", "glove", "grape", "grass",
+    "green", "happy", "heart", "honey", "horse", "house", "jelly", "jolly", "juice", "kitty",
+    "laugh", "lemon", "light", "lucky", "lunch", "magic", "mango", "melon", "money", "moose",
+    "mouse", "music", "night", "ocean", "olive", "otter", "paint", "panda", "party", "peach",
+    "pearl", "penny", "piano", "pizza", "plane", "plant", "plate", "puppy", "queen", "quiet",
+    "rainy", "river", "robin", "round", "ruler", "salad", "sandy", "seven", "sheep", "shell",
+    "shine", "shirt", "silly", "skate", "sleep", "small", "smile", "snack", "snail", "snake",
+    "snowy", "sound", "spoon", "stone", "storm", "story", "sugar", "sunny", "sweet", "swing",
+    "table", "teddy", "teeth", "three", "tiger", "toast", "tooth", "towel", "tower", "train",
+    "treat", "truck", "tulip", "uncle", "water", "whale", "wheel", "white", "world", "write",
+    "zebra",
+];
+
+/// Suggestions a kids' game shows.
+pub const KIDS_SUGGESTIONS: usize = 3;
+
+pub fn is_kids_word(word: &str) -> bool {
+    KIDS_WORDS.binary_search(&word).is_ok()
+}
+
+/// The answers in `solutions` a kids' game may use: the everyday words among
+/// them, or none if the list shares none, e.g. one of another word length.
+pub fn kids_answers(solutions: &WordTable) -> WordTable {
+    WordTable::new(
+        KIDS_WORDS.iter().filter(|w| solutions.contains(w)),
+        solutions.word_len(),
+    )
+}
+
+/// The [`KIDS_SUGGESTIONS`] everyday words among `scored`, best first once
+/// each score is weighted by how common its word looks.
+///
+/// There is no word-frequency list to go by, so the answer prior stands in
+/// for how often a word is used, as it does for answer priors.
+pub fn kids_suggestions(table: &WordTable, scored: &[(u32, usize)]) -> Vec<(u32, usize)> {
+    let weights = PriorWeights::default();
+    let mut kept: Vec<(u32, usize)> = scored
+        .iter()
+        .filter(|&&(i, _)| is_kids_word(table.word(i)))
+        .map(|&(i, score)| {
+            (
+                i,
+                (score as f64 * prior(table.word(i), &weights)).round() as usize,
+            )
+        })
+        .collect();
+    kept.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    kept.truncate(KIDS_SUGGESTIONS);
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::score_and_sort;
+
+    #[test]
+    fn test_kids_words_sorted_five_letters() {
+        assert!(KIDS_WORDS.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(KIDS_WORDS.iter().all(|w| w.len() == 5));
+        assert!(is_kids_word("apple"));
+        assert!(!is_kids_word("raise"));
+    }
+
+    #[test]
+    fn test_kids_answers_shared_with_solutions() {
+        let solutions = WordTable::new(["apple", "raise", "house", "crane"], 5);
+        let answers = kids_answers(&solutions);
+        assert_eq!(
+            answers.words().collect::<Vec<_>>(),
+            ["apple", "crane", "house"]
+        );
+
+        let longer = WordTable::new(["planet", "rocket"], 6);
+        assert!(kids_answers(&longer).is_empty());
+    }
+
+    #[test]
+    fn test_kids_suggestions_keep_three_everyday_words() {
+        let table = WordTable::new(
+            [
+                "raise", "stone", "slate", "crane", "house", "apple", "world", "magic", "tiger",
+            ],
+            5,
+        );
+        let pool: Vec<u32> = table.indices().collect();
+        let scored = score_and_sort(&table, &pool, &table);
+
+        let kept = kids_suggestions(&table, &scored);
+        assert_eq!(kept.len(), KIDS_SUGGESTIONS);
+        let words: Vec<&str> = kept.iter().map(|&(i, _)| table.word(i)).collect();
+        assert!(words.iter().all(|w| is_kids_word(w)));
+        assert!(kept.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    }
+}
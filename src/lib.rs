@@ -1,6 +1,25 @@
 pub mod analysis;
+pub mod backup;
+pub mod bug_report;
+pub mod build_info;
+pub mod cli_style;
+pub mod doctor;
+pub mod error;
+pub mod kids;
+pub mod local_time;
+pub mod normalizer;
+pub mod notifier;
+pub mod openers;
+pub mod paths;
+pub mod planner;
+pub mod priors;
+pub mod report;
 pub mod scoring;
+pub mod search;
+pub mod settings;
 pub mod solver;
 pub mod storage;
+pub mod tiers;
 pub mod ui;
 pub mod wordlist;
+pub mod wordtable;
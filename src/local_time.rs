@@ -0,0 +1,257 @@
+//! Stored times as the user reads them.
+//!
+//! Timestamps are stored in UTC and converted only for display, in the zone
+//! and clock the settings ask for. Every view formats through
+//! [`format_local`] and its siblings, and anything bucketing by day or hour
+//! converts through the same [`DisplayZone`], so a game played at 23:00
+//! shows on, and counts toward, the day it was played.
+
+use chrono::{
+    DateTime, FixedOffset, Local, MappedLocalTime, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
+
+/// How hours are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HourFormat {
+    /// `21:05`.
+    #[default]
+    TwentyFour,
+    /// `9:05 PM`.
+    Twelve,
+}
+
+impl HourFormat {
+    fn time_format(self) -> &'static str {
+        match self {
+            HourFormat::TwentyFour => "%H:%M",
+            HourFormat::Twelve => "%-I:%M %p",
+        }
+    }
+}
+
+/// The zone times are shown in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisplayZone {
+    /// Whatever the system says, `TZ` included.
+    System,
+    /// A zone from the tz database, e.g. `Europe/Berlin`.
+    Named(Tz),
+    /// A fixed offset from UTC, e.g. `+05:30`.
+    Fixed(FixedOffset),
+}
+
+impl DisplayZone {
+    /// The zone named by the `timezone` setting: a tz database name or an
+    /// offset like `-05:00`. `None` for anything else.
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim();
+        if let Ok(tz) = name.parse::<Tz>() {
+            return Some(DisplayZone::Named(tz));
+        }
+        name.parse::<FixedOffset>().ok().map(DisplayZone::Fixed)
+    }
+}
+
+impl TimeZone for DisplayZone {
+    type Offset = FixedOffset;
+
+    fn from_offset(offset: &FixedOffset) -> Self {
+        DisplayZone::Fixed(*offset)
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<FixedOffset> {
+        self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).expect("midnight is valid"))
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> MappedLocalTime<FixedOffset> {
+        match self {
+            DisplayZone::System => Local.offset_from_local_datetime(local).map(|o| o.fix()),
+            DisplayZone::Named(tz) => tz.offset_from_local_datetime(local).map(|o| o.fix()),
+            DisplayZone::Fixed(offset) => MappedLocalTime::Single(*offset),
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+        self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).expect("midnight is valid"))
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+        match self {
+            DisplayZone::System => Local.offset_from_utc_datetime(utc).fix(),
+            DisplayZone::Named(tz) => tz.offset_from_utc_datetime(utc).fix(),
+            DisplayZone::Fixed(offset) => *offset,
+        }
+    }
+}
+
+/// How times are shown, from the settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimePrefs {
+    pub zone: DisplayZone,
+    pub hour_format: HourFormat,
+}
+
+impl Default for TimePrefs {
+    fn default() -> Self {
+        Self {
+            zone: DisplayZone::System,
+            hour_format: HourFormat::TwentyFour,
+        }
+    }
+}
+
+impl TimePrefs {
+    /// The zone and clock `settings` choose; a timezone that doesn't parse
+    /// falls back to the system's.
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            zone: settings
+                .timezone
+                .as_deref()
+                .and_then(DisplayZone::parse)
+                .unwrap_or(DisplayZone::System),
+            hour_format: settings.hour_format,
+        }
+    }
+
+    /// `dt` on the wall clock of the display zone.
+    pub fn local(&self, dt: DateTime<Utc>) -> DateTime<FixedOffset> {
+        dt.with_timezone(&self.zone).fixed_offset()
+    }
+
+    /// The day `dt` falls on in the display zone.
+    pub fn date(&self, dt: DateTime<Utc>) -> NaiveDate {
+        self.local(dt).date_naive()
+    }
+
+    /// Today in the display zone.
+    pub fn today(&self) -> NaiveDate {
+        self.date(Utc::now())
+    }
+
+    /// Columns [`format_local`] takes, for sizing table columns.
+    pub fn datetime_width(&self) -> u16 {
+        match self.hour_format {
+            HourFormat::TwentyFour => 16,
+            HourFormat::Twelve => 19,
+        }
+    }
+
+    /// The offset in effect at local midnight starting `date`, or now if that
+    /// midnight was skipped.
+    pub fn offset_on(&self, date: NaiveDate) -> FixedOffset {
+        self.zone
+            .from_local_datetime(&date.and_hms_opt(0, 0, 0).expect("midnight is valid"))
+            .earliest()
+            .map_or_else(
+                || self.local(Utc::now()).offset().fix(),
+                |dt| dt.offset().fix(),
+            )
+    }
+}
+
+/// `dt` as a local date and time, e.g. `2026-10-14 21:05` or
+/// `2026-10-14 9:05 PM`.
+pub fn format_local(dt: DateTime<Utc>, prefs: &TimePrefs) -> String {
+    format!(
+        "{} {}",
+        format_local_date(dt, prefs),
+        format_local_time(dt, prefs)
+    )
+}
+
+/// The local date of `dt`, e.g. `2026-10-14`.
+pub fn format_local_date(dt: DateTime<Utc>, prefs: &TimePrefs) -> String {
+    prefs.local(dt).format("%Y-%m-%d").to_string()
+}
+
+/// The local time of `dt`, e.g. `21:05` or `9:05 PM`.
+pub fn format_local_time(dt: DateTime<Utc>, prefs: &TimePrefs) -> String {
+    prefs
+        .local(dt)
+        .format(prefs.hour_format.time_format())
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prefs(zone: &str, hour_format: HourFormat) -> TimePrefs {
+        TimePrefs {
+            zone: DisplayZone::parse(zone).unwrap(),
+            hour_format,
+        }
+    }
+
+    /// 03:30 UTC on the 15th, still the evening of the 14th in New York.
+    fn late_evening_in_new_york() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 15, 3, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn test_day_boundary_follows_the_zone() {
+        let dt = late_evening_in_new_york();
+        let new_york = prefs("America/New_York", HourFormat::TwentyFour);
+        assert_eq!(format_local(dt, &new_york), "2026-10-14 23:30");
+        assert_eq!(
+            new_york.date(dt),
+            NaiveDate::from_ymd_opt(2026, 10, 14).unwrap()
+        );
+
+        let utc = prefs("UTC", HourFormat::TwentyFour);
+        assert_eq!(format_local(dt, &utc), "2026-10-15 03:30");
+    }
+
+    #[test]
+    fn test_hour_formats() {
+        let dt = late_evening_in_new_york();
+        let twelve = prefs("America/New_York", HourFormat::Twelve);
+        assert_eq!(format_local(dt, &twelve), "2026-10-14 11:30 PM");
+        assert_eq!(format_local_time(dt, &twelve), "11:30 PM");
+
+        let morning = Utc.with_ymd_and_hms(2026, 10, 15, 9, 5, 0).unwrap();
+        let utc = prefs("UTC", HourFormat::Twelve);
+        assert_eq!(format_local_time(morning, &utc), "9:05 AM");
+        let utc = prefs("UTC", HourFormat::TwentyFour);
+        assert_eq!(format_local_time(morning, &utc), "09:05");
+    }
+
+    #[test]
+    fn test_parse_zone() {
+        assert_eq!(
+            DisplayZone::parse(" Europe/Berlin "),
+            Some(DisplayZone::Named(Tz::Europe__Berlin))
+        );
+        assert_eq!(
+            DisplayZone::parse("+05:30"),
+            Some(DisplayZone::Fixed(
+                FixedOffset::east_opt(5 * 3600 + 1800).unwrap()
+            ))
+        );
+        assert_eq!(DisplayZone::parse("Mars/Olympus"), None);
+
+        let settings = Settings {
+            timezone: Some("Mars/Olympus".to_string()),
+            ..Settings::default()
+        };
+        assert_eq!(
+            TimePrefs::from_settings(&settings).zone,
+            DisplayZone::System
+        );
+    }
+
+    #[test]
+    fn test_offset_follows_daylight_saving() {
+        let new_york = prefs("America/New_York", HourFormat::TwentyFour);
+        let summer = NaiveDate::from_ymd_opt(2026, 7, 1).unwrap();
+        let winter = NaiveDate::from_ymd_opt(2026, 12, 1).unwrap();
+        assert_eq!(new_york.offset_on(summer).local_minus_utc(), -4 * 3600);
+        assert_eq!(new_york.offset_on(winter).local_minus_utc(), -5 * 3600);
+    }
+}
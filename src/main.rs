@@ -1,15 +1,353 @@
-use anyhow::Result;
-use wordle_warlord::ui;
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use crossterm::style::Color;
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use wordle_warlord::{
+    backup,
+    bug_report::{BugReport, Environment},
+    build_info::BuildInfo,
+    cli_style::{CliStyle, ColorChoice},
+    doctor::{self, Report},
+    local_time::{TimePrefs, format_local},
+    normalizer::CharNormalizer,
+    openers::{DEFAULT_SEED, cached_openers},
+    paths::Paths,
+    report::{IsoWeek, ReportFormat, WeeklyReport},
+    settings::Settings,
+    storage::Database,
+    ui,
+    wordlist::{
+        SOLUTIONS_URL, TimedFetcher, WORDLIST_URL, WordlistOptions, diff_wordlists, load_solutions,
+        load_words, normalize_words,
+    },
+    wordtable::WordTable,
+};
 
 use once_cell::sync::OnceCell;
 use tracing_appender::rolling;
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::{EnvFilter, fmt};
 
+/// Wordle solver, local game, and history viewer.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Use a line-oriented text interface suited to screen readers
+    #[arg(long)]
+    plain: bool,
+
+    /// Start the solver with the guesses in FILE, one `word pattern` per
+    /// line; `-` reads them from standard input
+    #[arg(long, value_name = "FILE")]
+    transcript: Option<PathBuf>,
+
+    /// Read allowed guesses from FILE instead of the downloaded list
+    #[arg(long, value_name = "FILE")]
+    words: Option<PathBuf>,
+
+    /// Read possible answers from FILE instead of the downloaded list
+    #[arg(long, value_name = "FILE")]
+    solutions: Option<PathBuf>,
+
+    /// Start with a gauntlet of N games played back to back
+    #[arg(long, value_name = "N", conflicts_with_all = ["plain", "pool", "transcript", "guesses"])]
+    gauntlet: Option<usize>,
+
+    /// Start with a kids' game: everyday answers, no guess limit, and three
+    /// common words always suggested; new games stay kids' games until exit
+    #[arg(long, conflicts_with_all = ["plain", "pool", "transcript", "guesses", "gauntlet"])]
+    kids: bool,
+
+    /// Flag suggestions missing from the word list in FILE, e.g. a newer
+    /// published one
+    #[arg(long, value_name = "FILE")]
+    compare_wordlist: Option<PathBuf>,
+
+    /// Letters the word lists count as the same for feedback, e.g. `spanish`
+    /// for Á and A
+    #[arg(long, value_enum, default_value_t = CharNormalizer::Identity)]
+    alphabet: CharNormalizer,
+
+    /// Don't download word lists; use the bundled ones if none are cached
+    #[arg(long)]
+    offline: bool,
+
+    /// Enter a guess before starting, as `word pattern`; may be repeated
+    #[arg(long = "guess", value_name = "WORD PATTERN")]
+    guesses: Vec<String>,
+
+    /// Print the remaining candidates in score order and exit
+    #[arg(long)]
+    pool: bool,
+
+    /// With --pool, follow each candidate with its score
+    #[arg(long, requires = "pool")]
+    scores: bool,
+
+    /// With --pool, leave out bad transcript lines rather than failing; each
+    /// is still reported on stderr
+    #[arg(long, requires = "pool")]
+    skip_invalid: bool,
+
+    /// Keep the database, word lists, exports and logs in DIR instead of the
+    /// working directory; overrides WORDLE_WARLORD_HOME
+    #[arg(long, value_name = "DIR")]
+    data_dir: Option<PathBuf>,
+
+    /// Keep everything beside the executable, e.g. on a USB stick
+    #[arg(long, conflicts_with = "data_dir")]
+    portable: bool,
+
+    /// Colour printed output: `auto` colours when stdout is a terminal and
+    /// NO_COLOR isn't set
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check the data directory, database, word lists, network and terminal
+    Doctor,
+    /// Maintain the history database
+    Db {
+        #[command(subcommand)]
+        command: DbCommand,
+    },
+    /// Plan opening guesses that share no letters and cover the most common ones
+    Openers {
+        /// How many openers to plan
+        #[arg(long, default_value_t = 3)]
+        count: usize,
+        /// Seed for the search's restarts; the same seed gives the same openers
+        #[arg(long, default_value_t = DEFAULT_SEED)]
+        seed: u64,
+    },
+    /// Compare word lists
+    Wordlist {
+        #[command(subcommand)]
+        command: WordlistCommand,
+    },
+    /// Print win rates and average guesses of the games played
+    Stats {
+        /// Break games down by opening word; openers used fewer than three
+        /// times are grouped as "other"
+        #[arg(long)]
+        openers: bool,
+        /// Break games down by how hard their answers were; games played
+        /// before answers were scored are listed as "unscored"
+        #[arg(long, conflicts_with = "openers")]
+        tiers: bool,
+    },
+    /// Print the version with the commit, build date, target, features and
+    /// database schema version
+    Version {
+        /// Print as JSON, for packaging scripts and bug reports
+        #[arg(long)]
+        json: bool,
+    },
+    /// Bundle the doctor checks, version, terminal, recent log lines,
+    /// settings and history totals into a .tar.gz in the data directory for
+    /// attaching to a bug report; stored words are masked
+    BugReport {
+        /// Add a copy of the database, with every game and session played
+        #[arg(long)]
+        include_history: bool,
+    },
+    /// Summarize a week of games and solver sessions
+    Report {
+        /// ISO week to summarize; defaults to the current week
+        #[arg(long, value_name = "YYYY-WW")]
+        week: Option<IsoWeek>,
+        /// Format as Markdown, for pasting into a journal
+        #[arg(long)]
+        markdown: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DbCommand {
+    /// Replay stored solver sessions against the current word lists and
+    /// rewrite their optimal words and deviations
+    RecomputeOptimal {
+        /// Report how much would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Permanently delete games archived more than DAYS days ago
+    PurgeArchived {
+        /// Keep games archived within the last DAYS days
+        #[arg(long, value_name = "DAYS", default_value_t = 30)]
+        older_than: u32,
+    },
+    /// Delete abandoned solver sessions with no guesses, as older releases
+    /// left one each time solver mode was entered
+    PurgeEmptySessions {
+        /// Keep sessions started within the last HOURS hours
+        #[arg(long, value_name = "HOURS", default_value_t = 1)]
+        older_than: u32,
+    },
+    /// Copy the database to backups/history-YYYYMMDD.db now, whatever the
+    /// age of the newest backup
+    Backup,
+    /// Replace the database with a backup; quit the app first
+    Restore {
+        /// The backup to restore
+        path: PathBuf,
+        /// Replace a database with games or sessions newer than the backup
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum WordlistCommand {
+    /// Print the words added and removed going from OLD to NEW
+    Diff { old: PathBuf, new: PathBuf },
+}
+
+/// Whether `path` names standard input rather than a file.
+fn is_stdin(path: &Path) -> bool {
+    path == Path::new("-")
+}
+
+/// Read the text at `path`, or all of standard input for `-`.
+fn read_input(path: &Path) -> Result<String> {
+    if is_stdin(path) {
+        return std::io::read_to_string(std::io::stdin()).context("failed to read standard input");
+    }
+    std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+}
+
+/// Read and normalize the word list at `path`; `-` reads standard input.
+fn read_wordlist(path: &Path) -> Result<Vec<String>> {
+    Ok(normalize_words(read_input(path)?.lines()).0)
+}
+
+/// Print a table of won and lost games by opener, by answer tier, or by
+/// difficulty, with win rates graded against the best.
+fn print_stats(db: &Database, openers: bool, tiers: bool, style: CliStyle) -> Result<()> {
+    let average = |a: Option<f64>| a.map_or("-".to_string(), |a| format!("{:.2}", a));
+    let rows: Vec<(String, usize, usize, String)> = if tiers {
+        db.game_stats_by_tier(false)?
+            .into_iter()
+            .map(|s| {
+                let name = s.key.map_or("Unscored", |t| t.name()).to_string();
+                (name, s.games, s.wins, average(s.average_guesses))
+            })
+            .collect()
+    } else if openers {
+        db.game_stats_by_opener(false)?
+            .into_iter()
+            .map(|s| (s.label(), s.games, s.wins, average(s.average_guesses)))
+            .collect()
+    } else {
+        db.game_stats_by_difficulty(false)?
+            .into_iter()
+            .map(|s| {
+                let name = s.key.name().to_string();
+                (name, s.games, s.wins, average(s.average_guesses))
+            })
+            .collect()
+    };
+    if rows.is_empty() {
+        println!("No games recorded");
+        return Ok(());
+    }
+
+    let heading = if tiers {
+        "Answers"
+    } else if openers {
+        "Opener"
+    } else {
+        "Difficulty"
+    };
+    let header = format!(
+        "{:<10}  {:>5}  {:>4}  {:>11}",
+        heading, "Games", "Won", "Avg guesses"
+    );
+    println!("{}", style.bold(header));
+    let rate = |wins: usize, games: usize| wins as f64 * 100.0 / games.max(1) as f64;
+    let best = rows
+        .iter()
+        .map(|&(_, games, wins, _)| rate(wins, games))
+        .fold(0.0, f64::max);
+    for (label, games, wins, average) in rows {
+        let won = format!("{:>4}", format!("{:.0}%", rate(wins, games)));
+        println!(
+            "{:<10}  {:>5}  {}  {:>11}",
+            label,
+            games,
+            style.graded(won, rate(wins, games), best),
+            average
+        );
+    }
+    Ok(())
+}
+
+/// How long the doctor waits on each word list URL.
+const DOCTOR_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Run every check.
+fn doctor_report(paths: &Paths, wordlists: &WordlistOptions) -> Report {
+    // Only what is available without the network; the download checks cover the rest
+    let offline = WordlistOptions {
+        offline: true,
+        ..wordlists.clone()
+    };
+    let fetcher = TimedFetcher(DOCTOR_TIMEOUT);
+    let words_download = doctor::check_download("Word list", WORDLIST_URL, &fetcher);
+    let solutions_download = doctor::check_download("Solution list", SOLUTIONS_URL, &fetcher);
+    Report::new(vec![
+        doctor::check_data_dir(paths.root()),
+        doctor::check_database(&paths.database()),
+        doctor::check_wordlist("Word", load_words(&offline), words_download.passed),
+        doctor::check_wordlist(
+            "Solution",
+            load_solutions(&offline),
+            solutions_download.passed,
+        ),
+        words_download,
+        solutions_download,
+        doctor::check_terminal(
+            crossterm::terminal::size(),
+            crossterm::style::available_color_count(),
+        ),
+    ])
+}
+
+/// Run every check, print the table, and return the exit code.
+fn run_doctor(paths: &Paths, wordlists: &WordlistOptions) -> i32 {
+    let report = doctor_report(paths, wordlists);
+    print!("{}", report.render());
+    report.exit_code()
+}
+
 static LOG_GUARD: OnceCell<tracing_appender::non_blocking::WorkerGuard> = OnceCell::new();
 
-fn init_logging() {
-    let file_appender = rolling::daily("logs", "wordle-warlord.log");
+/// Take the automatic backup if the newest is older than the configured
+/// interval. A failed backup is logged rather than stopping the app.
+fn backup_at_startup(db: &Database, paths: &Paths) {
+    let settings = Settings::load(db).unwrap_or_default();
+    let today = chrono::Local::now().date_naive();
+    if let Err(e) = backup::auto_backup(
+        db,
+        &paths.backups(),
+        today,
+        settings.backup_interval_days,
+        settings.backups_kept,
+    ) {
+        tracing::warn!("Automatic backup failed: {:#}", e);
+    }
+}
+
+fn init_logging(paths: &Paths) {
+    let file_appender = rolling::daily(paths.logs(), "wordle-warlord.log");
     let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
 
     // Store the guard so it lives for the duration of the program
@@ -22,7 +360,174 @@ fn init_logging() {
 }
 
 fn main() -> Result<()> {
-    init_logging();
-    let db = wordle_warlord::storage::Database::open("history.db")?;
-    ui::run_ui(db)
+    let cli = Cli::parse();
+    let style = CliStyle::detect(cli.color);
+    let paths = Paths::from_args(cli.data_dir, cli.portable)?;
+    let created = paths.create();
+    // The doctor reports an unusable directory rather than failing on it
+    if !matches!(cli.command, Some(Command::Doctor)) {
+        created?;
+    }
+    init_logging(&paths);
+    tracing::info!("{}", BuildInfo::current().summary());
+    // The plain interface reads its commands from standard input
+    if cli.plain && cli.transcript.as_deref().is_some_and(is_stdin) {
+        anyhow::bail!("--plain reads standard input, so --transcript can't be -");
+    }
+    let transcript = cli.transcript.as_deref().map(read_input).transpose()?;
+    // --guess lines follow the transcript file, if any
+    let transcript = match (transcript, cli.guesses.is_empty()) {
+        (transcript, true) => transcript,
+        (Some(text), false) => Some(format!("{}\n{}", text, cli.guesses.join("\n"))),
+        (None, false) => Some(cli.guesses.join("\n")),
+    };
+    let wordlists = WordlistOptions {
+        cache_dir: paths.wordlists(),
+        words_path: cli.words,
+        solutions_path: cli.solutions,
+        offline: cli.offline,
+        compare_path: cli.compare_wordlist,
+        normalizer: cli.alphabet,
+    };
+    match cli.command {
+        Some(Command::Doctor) => std::process::exit(run_doctor(&paths, &wordlists)),
+        Some(Command::Db {
+            command: DbCommand::RecomputeOptimal { dry_run },
+        }) => {
+            let db = Database::open(paths.database())?;
+            println!("{}", ui::recompute_optimal(db, &wordlists, dry_run)?);
+            return Ok(());
+        }
+        Some(Command::Db {
+            command: DbCommand::PurgeArchived { older_than },
+        }) => {
+            let db = Database::open(paths.database())?;
+            let prefs = TimePrefs::from_settings(&Settings::load(&db)?);
+            let cutoff = chrono::Utc::now() - chrono::Duration::days(older_than.into());
+            let purged = db.purge_archived(cutoff)?;
+            println!(
+                "Deleted {} game(s) archived before {}",
+                purged,
+                format_local(cutoff, &prefs)
+            );
+            return Ok(());
+        }
+        Some(Command::Db {
+            command: DbCommand::PurgeEmptySessions { older_than },
+        }) => {
+            let db = Database::open(paths.database())?;
+            let prefs = TimePrefs::from_settings(&Settings::load(&db)?);
+            let cutoff = chrono::Utc::now() - chrono::Duration::hours(older_than.into());
+            let purged = db.purge_empty_sessions(cutoff)?;
+            println!(
+                "Deleted {} empty solver session(s) started before {}",
+                purged,
+                format_local(cutoff, &prefs)
+            );
+            return Ok(());
+        }
+        Some(Command::Db {
+            command: DbCommand::Backup,
+        }) => {
+            let db = Database::open(paths.database())?;
+            let today = chrono::Local::now().date_naive();
+            let settings = Settings::load(&db)?;
+            println!(
+                "Backed up to {}",
+                backup::create_backup(&db, &paths.backups(), today)?.display()
+            );
+            for pruned in backup::prune_backups(&paths.backups(), settings.backups_kept)? {
+                println!("Deleted old backup {}", pruned.display());
+            }
+            return Ok(());
+        }
+        Some(Command::Db {
+            command: DbCommand::Restore { path, force },
+        }) => {
+            backup::restore(&path, &paths.database(), force)?;
+            println!(
+                "Restored {} from {}",
+                paths.database().display(),
+                path.display()
+            );
+            return Ok(());
+        }
+        Some(Command::Openers { count, seed }) => {
+            // The word lists hold five-letter words
+            let allowed = WordTable::new(&load_words(&wordlists)?.words, 5);
+            let solutions = WordTable::new(&load_solutions(&wordlists)?.words, 5);
+            let openers = cached_openers(&allowed, &solutions, count, seed);
+            println!("{} ({} letters)", openers.joined(), openers.letters);
+            return Ok(());
+        }
+        Some(Command::Wordlist {
+            command: WordlistCommand::Diff { old, new },
+        }) => {
+            let diff = diff_wordlists(&read_wordlist(&old)?, &read_wordlist(&new)?);
+            for word in &diff.added {
+                println!("{}", style.paint(format!("+{}", word), Color::Green));
+            }
+            for word in &diff.removed {
+                println!("{}", style.paint(format!("-{}", word), Color::Red));
+            }
+            println!("{} added, {} removed", diff.added.len(), diff.removed.len());
+            return Ok(());
+        }
+        Some(Command::Stats { openers, tiers }) => {
+            let db = Database::open(paths.database())?;
+            return print_stats(&db, openers, tiers, style);
+        }
+        Some(Command::Version { json }) => {
+            let info = BuildInfo::current();
+            if json {
+                println!("{}", info.to_json());
+            } else {
+                println!("{}", info.summary());
+            }
+            return Ok(());
+        }
+        Some(Command::BugReport { include_history }) => {
+            let doctor = doctor_report(&paths, &wordlists);
+            let db = Database::open(paths.database())?;
+            let report = BugReport::collect(
+                &db,
+                &paths,
+                &doctor,
+                &Environment::current(),
+                include_history,
+            )?;
+            let path = report.write(paths.root(), chrono::Local::now())?;
+            println!("Wrote {}", path.display());
+            return Ok(());
+        }
+        Some(Command::Report { week, markdown }) => {
+            let db = Database::open(paths.database())?;
+            let prefs = TimePrefs::from_settings(&Settings::load(&db)?);
+            let week = week.unwrap_or_else(|| IsoWeek::current(&prefs));
+            let report = WeeklyReport::build(&db, week, week.local_offset(&prefs))?;
+            let format = if markdown {
+                ReportFormat::Markdown
+            } else {
+                ReportFormat::Plain
+            };
+            print!("{}", report.render(format));
+            return Ok(());
+        }
+        None => {}
+    }
+    if cli.pool {
+        print!(
+            "{}",
+            ui::export_pool(transcript, &wordlists, cli.scores, cli.skip_invalid, style)?
+        );
+        return Ok(());
+    }
+    let db = Database::open(paths.database())?;
+    backup_at_startup(&db, &paths);
+
+    if cli.plain {
+        ui::run_plain(db, transcript, &wordlists)
+    } else {
+        ui::run_ui(db, transcript, &wordlists, cli.gauntlet, cli.kids)
+    }
 }
@@ -0,0 +1,181 @@
+//! Letters a dictionary counts as the same for feedback, such as Spanish Á and A.
+//!
+//! Word lists and typed guesses are folded to a canonical spelling when they
+//! come in, so feedback, filtering and constraints only ever compare canonical
+//! letters. [`Spellings`] keeps each word's original spelling for display.
+
+use std::{borrow::Cow, collections::HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::solver::{Feedback, generate_feedback, matches};
+
+/// How a word set's letters fold to canonical ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum CharNormalizer {
+    /// Every letter is its own; English lists are left exactly as they are.
+    #[default]
+    Identity,
+    /// Acute accents and the diaeresis are dropped. Ñ is a letter of its own
+    /// and isn't folded; the word tables hold A-Z only, so words with it are
+    /// left out.
+    Spanish,
+    /// Grave, acute and circumflex accents, the diaeresis and the cedilla are dropped.
+    French,
+}
+
+impl CharNormalizer {
+    /// The canonical letter for `c`, lowercase for an accented letter of
+    /// either case; letters without an equivalent come back unchanged.
+    pub fn fold(self, c: char) -> char {
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        let folded = match self {
+            CharNormalizer::Identity => None,
+            CharNormalizer::Spanish => match lower {
+                'á' => Some('a'),
+                'é' => Some('e'),
+                'í' => Some('i'),
+                'ó' => Some('o'),
+                'ú' | 'ü' => Some('u'),
+                _ => None,
+            },
+            CharNormalizer::French => match lower {
+                'à' | 'â' | 'ä' => Some('a'),
+                'ç' => Some('c'),
+                'é' | 'è' | 'ê' | 'ë' => Some('e'),
+                'î' | 'ï' => Some('i'),
+                'ô' | 'ö' => Some('o'),
+                'ù' | 'û' | 'ü' => Some('u'),
+                'ÿ' => Some('y'),
+                _ => None,
+            },
+        };
+        folded.unwrap_or(c)
+    }
+
+    /// `word` with every letter folded; borrowed when nothing changed.
+    pub fn normalize(self, word: &str) -> Cow<'_, str> {
+        if self == CharNormalizer::Identity || word.chars().all(|c| self.fold(c) == c) {
+            return Cow::Borrowed(word);
+        }
+        Cow::Owned(word.chars().map(|c| self.fold(c)).collect())
+    }
+
+    /// [`generate_feedback`] with equivalent letters treated as one.
+    pub fn feedback(self, target: &str, guess: &str) -> Vec<Feedback> {
+        generate_feedback(&self.normalize(target), &self.normalize(guess))
+    }
+
+    /// [`matches`] with equivalent letters treated as one.
+    pub fn matches(self, word: &str, guess: &str, pattern: &[Feedback]) -> bool {
+        matches(&self.normalize(word), &self.normalize(guess), pattern)
+    }
+}
+
+/// Original spellings of words whose canonical form differs, for display.
+#[derive(Debug, Clone, Default)]
+pub struct Spellings {
+    originals: HashMap<String, String>,
+}
+
+impl Spellings {
+    /// Fold every word of `words` in place, remembering the spellings that changed.
+    /// Where two spellings fold to the same word, the first is kept.
+    pub fn fold_list(&mut self, normalizer: CharNormalizer, words: &mut [String]) {
+        if normalizer == CharNormalizer::Identity {
+            return;
+        }
+        for word in words.iter_mut() {
+            if let Cow::Owned(canonical) = normalizer.normalize(word) {
+                let original = std::mem::replace(word, canonical.clone());
+                self.originals.entry(canonical).or_insert(original);
+            }
+        }
+    }
+
+    /// `text` with each space-separated canonical word in its original spelling.
+    pub fn display(&self, text: &str) -> String {
+        if self.originals.is_empty() {
+            return text.to_string();
+        }
+        text.split(' ')
+            .map(|word| self.originals.get(word).map_or(word, String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::Feedback::{Gray as X, Green as G, Yellow as Y};
+
+    #[test]
+    fn test_spanish_accents_count_as_plain_letters() {
+        let spanish = CharNormalizer::Spanish;
+        assert_eq!(spanish.feedback("tacón", "tacon"), [G, G, G, G, G]);
+        assert_eq!(spanish.feedback("árbol", "labor"), [Y, Y, G, G, Y]);
+        assert!(spanish.matches("tacón", "canto", &[Y, G, Y, Y, Y]));
+        assert!(!spanish.matches("tacón", "canto", &[Y, G, Y, Y, X]));
+    }
+
+    #[test]
+    fn test_spanish_keeps_enye_apart_from_n() {
+        let spanish = CharNormalizer::Spanish;
+        assert_eq!(spanish.normalize("cañón"), "cañon");
+        assert_ne!(spanish.feedback("cañon", "canon"), [G, G, G, G, G]);
+    }
+
+    #[test]
+    fn test_identity_leaves_english_untouched() {
+        let identity = CharNormalizer::Identity;
+        assert!(matches!(
+            identity.normalize("crane"),
+            Cow::Borrowed("crane")
+        ));
+        // Identity doesn't fold accents, so these stay different letters
+        assert_eq!(
+            identity.feedback("cañon", "canon"),
+            generate_feedback("cañon", "canon")
+        );
+        for (target, guess) in [("apple", "allay"), ("eerie", "geese"), ("crane", "slate")] {
+            assert_eq!(
+                identity.feedback(target, guess),
+                generate_feedback(target, guess)
+            );
+        }
+
+        let mut words = vec!["crane".to_string(), "café".to_string()];
+        let mut spellings = Spellings::default();
+        spellings.fold_list(identity, &mut words);
+        assert_eq!(words, ["crane", "café"]);
+        assert_eq!(spellings.display("café"), "café");
+    }
+
+    #[test]
+    fn test_french_folds_accents_and_cedilla() {
+        let french = CharNormalizer::French;
+        assert_eq!(french.normalize("façade"), "facade");
+        assert_eq!(french.normalize("Élève"), "eleve");
+        assert_eq!(french.normalize("noël"), "noel");
+    }
+
+    #[test]
+    fn test_spellings_round_trip() {
+        let mut words = vec![
+            "tacón".to_string(),
+            "perro".to_string(),
+            "árbol".to_string(),
+        ];
+        let mut spellings = Spellings::default();
+        spellings.fold_list(CharNormalizer::Spanish, &mut words);
+
+        assert_eq!(words, ["tacon", "perro", "arbol"]);
+        assert_eq!(spellings.display("tacon"), "tacón");
+        assert_eq!(spellings.display("perro"), "perro");
+        assert_eq!(spellings.display("arbol tacon"), "árbol tacón");
+        // No word with Ñ folds away, so a plain N is never shown as one
+        assert_eq!(spellings.display("canon"), "canon");
+    }
+}
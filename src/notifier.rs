@@ -0,0 +1,57 @@
+//! Notices that something finished, for when the terminal is out of sight.
+
+use std::io::Write;
+
+/// Somewhere to say that a game, gauntlet or background job has finished.
+pub trait Notifier {
+    /// Tell the user `summary`, a short line such as `Game won in 4 guesses`.
+    fn notify(&self, summary: &str);
+}
+
+/// Says nothing.
+pub struct NoopNotifier;
+
+impl Notifier for NoopNotifier {
+    fn notify(&self, _summary: &str) {}
+}
+
+/// Rings the terminal bell.
+///
+/// The bell goes to stderr, so it never lands in output piped from stdout.
+pub struct BellNotifier;
+
+impl Notifier for BellNotifier {
+    fn notify(&self, _summary: &str) {
+        let mut stderr = std::io::stderr();
+        // A bell that can't be rung isn't worth reporting
+        let _ = stderr.write_all(b"\x07");
+        let _ = stderr.flush();
+    }
+}
+
+/// Rings the terminal bell and shows the summary as a desktop notification.
+#[cfg(feature = "notify-rust")]
+pub struct DesktopNotifier;
+
+#[cfg(feature = "notify-rust")]
+impl Notifier for DesktopNotifier {
+    fn notify(&self, summary: &str) {
+        BellNotifier.notify(summary);
+        let shown = notify_rust::Notification::new()
+            .summary("Wordle Warlord")
+            .body(summary)
+            .show();
+        if let Err(e) = shown {
+            tracing::warn!("Failed to show desktop notification: {}", e);
+        }
+    }
+}
+
+/// The notifier this build offers: desktop notifications with the
+/// `notify-rust` feature, the terminal bell without it.
+pub fn default_notifier() -> Box<dyn Notifier> {
+    #[cfg(feature = "notify-rust")]
+    return Box::new(DesktopNotifier);
+    #[cfg(not(feature = "notify-rust"))]
+    Box::new(BellNotifier)
+}
@@ -0,0 +1,302 @@
+//! Fixed opening guesses covering as many common letters as possible.
+//!
+//! Each opener has no repeated letter and shares none with the others, so
+//! three five-letter openers try fifteen letters before any feedback is read.
+//! Letters are weighted by how many answers contain them. The search is
+//! greedy: from a starting word, keep adding the heaviest word that fits.
+//! Restarts from other strong words, drawn by a seeded generator, find sets
+//! the heaviest start misses, and the same seed always gives the same set.
+
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::Mutex,
+};
+
+use once_cell::sync::Lazy;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::wordtable::WordTable;
+
+/// Seed the app plans with, so the same lists always show the same openers.
+pub const DEFAULT_SEED: u64 = 0x5eed;
+
+/// Greedy searches run after the one from the heaviest word.
+const RESTARTS: usize = 64;
+
+/// Restarts begin from one of this many heaviest words.
+const START_POOL: usize = 300;
+
+/// Planned openers, keyed by a hash of the word lists, count, and seed.
+static CACHE: Lazy<Mutex<HashMap<u64, Openers>>> = Lazy::new(Default::default);
+
+/// Openers in the order to play them, and the letters they cover together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Openers {
+    pub words: Vec<String>,
+    pub letters: usize,
+}
+
+impl Openers {
+    /// The openers in capitals, separated by dots: `SLATE · CRONY · HUMID`.
+    pub fn joined(&self) -> String {
+        self.words
+            .iter()
+            .map(|w| w.to_uppercase())
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+/// A word with no repeated letter, and the letters it covers.
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    index: u32,
+    mask: u32,
+    weight: usize,
+}
+
+/// [`plan_openers`], remembering the result for the same lists, count and seed.
+pub fn cached_openers(
+    allowed: &WordTable,
+    solutions: &WordTable,
+    count: usize,
+    seed: u64,
+) -> Openers {
+    let mut hasher = DefaultHasher::new();
+    for table in [allowed, solutions] {
+        table.word_len().hash(&mut hasher);
+        table.len().hash(&mut hasher);
+        table.words().for_each(|w| w.hash(&mut hasher));
+    }
+    (count, seed).hash(&mut hasher);
+    let key = hasher.finish();
+
+    if let Some(openers) = CACHE.lock().unwrap_or_else(|e| e.into_inner()).get(&key) {
+        return openers.clone();
+    }
+    let openers = plan_openers(allowed, solutions, count, seed);
+    CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(key, openers.clone());
+    openers
+}
+
+/// Up to `count` allowed words sharing no letters, chosen to cover the
+/// letters most answers contain.
+///
+/// Fewer words come back when no word fits the letters left.
+pub fn plan_openers(
+    allowed: &WordTable,
+    solutions: &WordTable,
+    count: usize,
+    seed: u64,
+) -> Openers {
+    let weights = letter_weights(solutions);
+    let candidates = candidates(allowed, solutions, &weights);
+    if candidates.is_empty() || count == 0 {
+        return Openers {
+            words: Vec::new(),
+            letters: 0,
+        };
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let starts = candidates.len().min(START_POOL);
+    let mut best = greedy(&candidates, 0, count, &weights);
+    for _ in 0..RESTARTS {
+        let plan = greedy(&candidates, rng.random_range(0..starts), count, &weights);
+        if plan.1 > best.1 {
+            best = plan;
+        }
+    }
+
+    let (chosen, _) = best;
+    Openers {
+        letters: chosen.iter().fold(0, |mask, c| mask | c.mask).count_ones() as usize,
+        words: chosen
+            .iter()
+            .map(|c| allowed.word(c.index).to_string())
+            .collect(),
+    }
+}
+
+/// How many answers contain each letter, `a` first.
+fn letter_weights(solutions: &WordTable) -> [usize; 26] {
+    let mut weights = [0; 26];
+    for i in solutions.indices() {
+        let mask = letter_mask(solutions.bytes(i));
+        for (letter, weight) in weights.iter_mut().enumerate() {
+            if mask & (1 << letter) != 0 {
+                *weight += 1;
+            }
+        }
+    }
+    weights
+}
+
+/// One bit per letter of `word`, which is lowercase ASCII like every table word.
+fn letter_mask(word: &[u8]) -> u32 {
+    word.iter().fold(0, |mask, &b| mask | 1 << (b - b'a'))
+}
+
+fn mask_weight(mask: u32, weights: &[usize; 26]) -> usize {
+    (0..26)
+        .filter(|&letter| mask & (1 << letter) != 0)
+        .map(|letter| weights[letter])
+        .sum()
+}
+
+/// Allowed words without repeated letters, one per letter set, heaviest
+/// first. Of anagrams, a possible answer is kept over a word that isn't.
+fn candidates(allowed: &WordTable, solutions: &WordTable, weights: &[usize; 26]) -> Vec<Candidate> {
+    let mut by_mask: HashMap<u32, Candidate> = HashMap::new();
+    for index in allowed.indices() {
+        let word = allowed.bytes(index);
+        let mask = letter_mask(word);
+        if mask.count_ones() as usize != word.len() {
+            continue;
+        }
+        let candidate = Candidate {
+            index,
+            mask,
+            weight: mask_weight(mask, weights),
+        };
+        by_mask
+            .entry(mask)
+            .and_modify(|kept| {
+                if !solutions.contains(allowed.word(kept.index))
+                    && solutions.contains(allowed.word(index))
+                {
+                    *kept = candidate;
+                }
+            })
+            .or_insert(candidate);
+    }
+
+    let mut candidates: Vec<Candidate> = by_mask.into_values().collect();
+    candidates.sort_by(|a, b| b.weight.cmp(&a.weight).then(a.index.cmp(&b.index)));
+    candidates
+}
+
+/// Openers from `candidates[start]`, each next word the heaviest that shares
+/// no letter with those already chosen, and the weight they cover.
+fn greedy(
+    candidates: &[Candidate],
+    start: usize,
+    count: usize,
+    weights: &[usize; 26],
+) -> (Vec<Candidate>, usize) {
+    let mut chosen = vec![candidates[start]];
+    let mut used = candidates[start].mask;
+    while chosen.len() < count {
+        // Heaviest first, so the first word that fits is the best one
+        let Some(&next) = candidates.iter().find(|c| c.mask & used == 0) else {
+            break;
+        };
+        chosen.push(next);
+        used |= next.mask;
+    }
+    (chosen, mask_weight(used, weights))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Common five-letter words, a stand-in for the answer list.
+    const DICTIONARY: [&str; 60] = [
+        "slate", "crane", "raise", "stone", "house", "apple", "world", "magic", "crony", "humid",
+        "pilot", "dumpy", "brick", "glent", "waltz", "nymph", "fjord", "gucks", "vibex", "trace",
+        "audio", "adieu", "roast", "least", "stare", "arise", "tears", "later", "alert", "irate",
+        "round", "mount", "light", "fight", "sound", "found", "music", "quick", "jumpy", "vivid",
+        "proxy", "badge", "cloth", "windy", "spunk", "frogs", "champ", "pound", "dwelt", "knoll",
+        "shirt", "baker", "candy", "plumb", "ghost", "sweat", "yield", "bunch", "fuzzy", "wreck",
+    ];
+
+    fn tables(words: &[&str]) -> (WordTable, WordTable) {
+        (WordTable::new(words, 5), WordTable::new(words, 5))
+    }
+
+    #[test]
+    fn test_trio_covers_at_least_fourteen_letters() {
+        let (allowed, solutions) = tables(&DICTIONARY);
+        let openers = plan_openers(&allowed, &solutions, 3, DEFAULT_SEED);
+
+        assert_eq!(openers.words.len(), 3);
+        assert!(openers.letters >= 14, "covered {}", openers.letters);
+    }
+
+    #[test]
+    fn test_openers_share_no_letters() {
+        let (allowed, solutions) = tables(&DICTIONARY);
+        let openers = plan_openers(&allowed, &solutions, 4, DEFAULT_SEED);
+
+        let letters: Vec<char> = openers.words.iter().flat_map(|w| w.chars()).collect();
+        let mut distinct = letters.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        assert_eq!(distinct.len(), letters.len());
+        assert_eq!(openers.letters, letters.len());
+    }
+
+    #[test]
+    fn test_same_seed_same_openers() {
+        let (allowed, solutions) = tables(&DICTIONARY);
+        for seed in [1, 2, DEFAULT_SEED] {
+            assert_eq!(
+                plan_openers(&allowed, &solutions, 3, seed),
+                plan_openers(&allowed, &solutions, 3, seed)
+            );
+        }
+    }
+
+    #[test]
+    fn test_words_with_repeated_letters_skipped() {
+        let (allowed, solutions) = tables(&["apple", "fuzzy", "crane"]);
+        let openers = plan_openers(&allowed, &solutions, 3, DEFAULT_SEED);
+        assert_eq!(openers.words, ["crane"]);
+    }
+
+    #[test]
+    fn test_full_size_list_plans_within_a_second() {
+        // About as many words as the allowed list, spread over the alphabet
+        let words: Vec<String> = (0..13_000usize)
+            .map(|n| {
+                let mut code = n * 7919 % 26usize.pow(5);
+                (0..5)
+                    .map(|_| {
+                        let letter = (b'a' + (code % 26) as u8) as char;
+                        code /= 26;
+                        letter
+                    })
+                    .collect()
+            })
+            .collect();
+        let allowed = WordTable::new(&words, 5);
+        let solutions = WordTable::new(DICTIONARY, 5);
+
+        let start = std::time::Instant::now();
+        let openers = plan_openers(&allowed, &solutions, 3, DEFAULT_SEED);
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+        assert_eq!(openers.letters, 15);
+    }
+
+    #[test]
+    fn test_cached_matches_planned() {
+        let (allowed, solutions) = tables(&DICTIONARY);
+        let planned = plan_openers(&allowed, &solutions, 3, 7);
+        assert_eq!(cached_openers(&allowed, &solutions, 3, 7), planned);
+        assert_eq!(cached_openers(&allowed, &solutions, 3, 7), planned);
+    }
+
+    #[test]
+    fn test_joined() {
+        let openers = Openers {
+            words: vec!["slate".to_string(), "crony".to_string()],
+            letters: 10,
+        };
+        assert_eq!(openers.joined(), "SLATE · CRONY");
+    }
+}
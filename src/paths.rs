@@ -0,0 +1,180 @@
+//! Where the app keeps its files.
+//!
+//! Everything written — the history database, its journal and backups,
+//! cached word lists, exports and logs — goes under one root directory, resolved once at
+//! startup. By default that is the working directory; `--data-dir`, then
+//! `--portable` (the directory holding the executable), then the
+//! `WORDLE_WARLORD_HOME` environment variable override it.
+
+use std::{
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::error::{Result, WordleError};
+
+/// Environment variable naming the data directory.
+pub const HOME_ENV: &str = "WORDLE_WARLORD_HOME";
+
+const DATABASE_FILE: &str = "history.db";
+const LOG_DIR: &str = "logs";
+const BACKUP_DIR: &str = "backups";
+
+/// The data directory and the files kept in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paths {
+    root: PathBuf,
+}
+
+impl Paths {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The root from the command line flags, else the environment.
+    pub fn from_args(data_dir: Option<PathBuf>, portable: bool) -> Result<Self> {
+        Self::resolve(data_dir, portable, std::env::var_os(HOME_ENV), || {
+            std::env::current_exe()
+        })
+    }
+
+    /// Pick the root: `data_dir`, then the directory of `exe` if `portable`,
+    /// then `home` unless it's empty, then the working directory.
+    pub fn resolve(
+        data_dir: Option<PathBuf>,
+        portable: bool,
+        home: Option<OsString>,
+        exe: impl FnOnce() -> io::Result<PathBuf>,
+    ) -> Result<Self> {
+        if let Some(dir) = data_dir {
+            return Ok(Self::new(dir));
+        }
+        if portable {
+            let exe = exe()?;
+            let dir = exe.parent().ok_or_else(|| {
+                io::Error::other(format!("{} has no parent directory", exe.display()))
+            })?;
+            return Ok(Self::new(dir));
+        }
+        match home {
+            Some(home) if !home.is_empty() => Ok(Self::new(home)),
+            _ => Ok(Self::new(".")),
+        }
+    }
+
+    /// Create the root and check files can be written in it.
+    pub fn create(&self) -> Result<()> {
+        let probe = self.root.join(".write-probe");
+        fs::create_dir_all(&self.root)
+            .and_then(|_| fs::write(&probe, b""))
+            .and_then(|_| fs::remove_file(&probe))
+            .map_err(|source| WordleError::DataDir {
+                path: self.root.clone(),
+                source,
+            })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The history database; its journal and exports sit beside it.
+    pub fn database(&self) -> PathBuf {
+        self.root.join(DATABASE_FILE)
+    }
+
+    /// Where downloaded word lists are cached.
+    pub fn wordlists(&self) -> PathBuf {
+        self.root.clone()
+    }
+
+    /// Where copies of the database are kept.
+    pub fn backups(&self) -> PathBuf {
+        self.root.join(BACKUP_DIR)
+    }
+
+    /// The directory the daily log files roll over in.
+    pub fn logs(&self) -> PathBuf {
+        self.root.join(LOG_DIR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Database;
+
+    fn no_exe() -> io::Result<PathBuf> {
+        panic!("the executable is only looked up in portable mode")
+    }
+
+    #[test]
+    fn test_flag_wins_over_portable_and_env() {
+        let paths = Paths::resolve(Some("flag".into()), true, Some("env".into()), no_exe).unwrap();
+        assert_eq!(paths.root(), Path::new("flag"));
+    }
+
+    #[test]
+    fn test_portable_wins_over_env() {
+        let paths = Paths::resolve(None, true, Some("env".into()), || {
+            Ok(PathBuf::from("usb/bin/wordle-warlord"))
+        })
+        .unwrap();
+        assert_eq!(paths.root(), Path::new("usb/bin"));
+    }
+
+    #[test]
+    fn test_env_then_working_directory() {
+        let paths = Paths::resolve(None, false, Some("env".into()), no_exe).unwrap();
+        assert_eq!(paths.root(), Path::new("env"));
+
+        for home in [None, Some(OsString::new())] {
+            let paths = Paths::resolve(None, false, home, no_exe).unwrap();
+            assert_eq!(paths.root(), Path::new("."));
+        }
+    }
+
+    #[test]
+    fn test_create_makes_nested_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = Paths::new(dir.path().join("usb").join("warlord"));
+
+        paths.create().unwrap();
+        assert!(paths.root().is_dir());
+        // Nothing left behind by the write check
+        assert_eq!(fs::read_dir(paths.root()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_create_fails_with_the_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("file");
+        fs::write(&file, "").unwrap();
+        let paths = Paths::new(file.join("warlord"));
+
+        let error = paths.create().unwrap_err().to_string();
+        assert!(error.contains("cannot use data directory"), "{}", error);
+        assert!(error.contains(&file.display().to_string()), "{}", error);
+    }
+
+    #[test]
+    fn test_everything_written_under_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = Paths::new(dir.path().join("data"));
+        paths.create().unwrap();
+        let cwd_had_database = Path::new(DATABASE_FILE).exists();
+
+        let db = Database::open(paths.database()).unwrap();
+        db.save_setting("probe", "1").unwrap();
+        assert!(paths.database().exists());
+        // Journal and exports go beside the database
+        assert_eq!(db.data_dir(), Some(paths.root()));
+
+        assert!(paths.wordlists().starts_with(paths.root()));
+        assert!(paths.logs().starts_with(paths.root()));
+        assert!(paths.backups().starts_with(paths.root()));
+
+        assert_eq!(Path::new(DATABASE_FILE).exists(), cwd_had_database);
+    }
+}
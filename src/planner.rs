@@ -0,0 +1,228 @@
+//! How many guesses a pool could still take, by minimax over its feedback buckets.
+//!
+//! A guess splits the pool into buckets by the feedback each candidate would
+//! give it. The worst case for a pool is the fewest guesses that win however
+//! the buckets fall, which small pools search for exactly. Larger pools use a
+//! greedy strategy instead; it is a real strategy, so its worst case is still
+//! a guarantee, only not always the tightest one.
+
+use std::collections::HashMap;
+
+use crate::{scoring::score_and_sort, solver::generate_feedback, wordtable::WordTable};
+
+/// Largest pool searched exhaustively.
+const EXACT_POOL_LIMIT: usize = 30;
+
+/// Guesses tried at each step of the greedy bound, best-scoring first.
+const PROBE_WIDTH: usize = 20;
+
+/// Exact results kept before the memo is cleared.
+const MEMO_LIMIT: usize = 100_000;
+
+/// The fewest and most guesses, counting the winning one, the game could still take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WinWindow {
+    /// 1 whenever any candidate is left: the next guess could be the answer.
+    pub best: usize,
+    /// Guesses that guarantee a win with the right choices.
+    pub worst: usize,
+}
+
+/// Minimax search over candidate pools, remembering pools it has solved.
+#[derive(Debug, Default)]
+pub struct Planner {
+    /// Exact worst case of each pool searched, keyed by its sorted indices.
+    memo: HashMap<Vec<u32>, usize>,
+    /// The last pool asked about and its window.
+    last: Option<(Vec<u32>, WinWindow)>,
+}
+
+impl Planner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The win window for `pool`, or `None` if no candidate is left.
+    ///
+    /// Asking again about the same pool returns the cached window.
+    pub fn win_window(&mut self, table: &WordTable, pool: &[u32]) -> Option<WinWindow> {
+        if pool.is_empty() {
+            return None;
+        }
+        let mut key = pool.to_vec();
+        key.sort_unstable();
+        if let Some((last, window)) = &self.last
+            && *last == key
+        {
+            return Some(*window);
+        }
+
+        if self.memo.len() > MEMO_LIMIT {
+            self.memo.clear();
+        }
+        let window = WinWindow {
+            best: 1,
+            worst: self.worst_case(table, &key),
+        };
+        self.last = Some((key, window));
+        Some(window)
+    }
+
+    /// Guesses needed to be sure of a win from `pool`, exactly when the pool
+    /// is small enough and by the greedy bound otherwise.
+    fn worst_case(&mut self, table: &WordTable, pool: &[u32]) -> usize {
+        if pool.len() <= EXACT_POOL_LIMIT {
+            return self.exact(table, pool);
+        }
+
+        // Of the best-scoring candidates, guess the one leaving the smallest largest bucket
+        let probes = score_and_sort(table, pool, table);
+        let buckets = probes
+            .iter()
+            .take(PROBE_WIDTH)
+            .map(|&(probe, _)| partition(table, probe, pool))
+            .min_by_key(|buckets| buckets.first().map_or(0, Vec::len))
+            .unwrap_or_default();
+        1 + buckets
+            .iter()
+            .map(|bucket| self.worst_case(table, bucket))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The exact worst case for `pool`, guessing only candidates.
+    fn exact(&mut self, table: &WordTable, pool: &[u32]) -> usize {
+        // One candidate takes one guess; two take two, whichever is tried first
+        if pool.len() <= 2 {
+            return pool.len();
+        }
+        if let Some(&known) = self.memo.get(pool) {
+            return known;
+        }
+
+        // Guessing the candidates one by one always works
+        let mut best = pool.len();
+        for &probe in pool {
+            // No guess wins three candidates at once, so 2 can't be beaten
+            if best == 2 {
+                break;
+            }
+            let buckets = partition(table, probe, pool);
+            // A bucket of two or more needs two more guesses
+            let floor = match buckets.first().map_or(0, Vec::len) {
+                0 => 1,
+                1 => 2,
+                _ => 3,
+            };
+            if floor >= best {
+                continue;
+            }
+
+            let mut worst = floor;
+            for bucket in &buckets {
+                worst = worst.max(1 + self.exact(table, bucket));
+                if worst >= best {
+                    break;
+                }
+            }
+            best = best.min(worst);
+        }
+
+        self.memo.insert(pool.to_vec(), best);
+        best
+    }
+}
+
+/// The candidates other than `probe` itself, split by the feedback they
+/// would give it; largest bucket first, each bucket in pool order.
+pub(crate) fn partition(table: &WordTable, probe: u32, pool: &[u32]) -> Vec<Vec<u32>> {
+    let guess = table.word(probe);
+    let mut buckets: HashMap<_, Vec<u32>> = HashMap::new();
+    for &answer in pool.iter().filter(|&&i| i != probe) {
+        buckets
+            .entry(generate_feedback(table.word(answer), guess))
+            .or_default()
+            .push(answer);
+    }
+    let mut buckets: Vec<Vec<u32>> = buckets.into_values().collect();
+    buckets.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table_and_pool(words: &[&str]) -> (WordTable, Vec<u32>) {
+        let table = WordTable::new(words, 5);
+        let pool = (0..table.len() as u32).collect();
+        (table, pool)
+    }
+
+    fn worst(words: &[&str]) -> usize {
+        let (table, pool) = table_and_pool(words);
+        Planner::new().win_window(&table, &pool).unwrap().worst
+    }
+
+    #[test]
+    fn test_empty_pool_has_no_window() {
+        let (table, _) = table_and_pool(&["crane"]);
+        assert_eq!(Planner::new().win_window(&table, &[]), None);
+    }
+
+    #[test]
+    fn test_single_candidate_wins_next_guess() {
+        assert_eq!(
+            Planner::new().win_window(&table_and_pool(&["crane"]).0, &[0]),
+            Some(WinWindow { best: 1, worst: 1 })
+        );
+        assert_eq!(worst(&["crane", "slate"]), 2);
+    }
+
+    #[test]
+    fn test_one_probe_distinguishing_all() {
+        // BATCH, LATCH and MATCH give each other the same pattern, so after
+        // any one misses the other two are still tied; guessing the pool
+        // one by one takes three
+        assert_eq!(worst(&["batch", "latch", "match"]), 3);
+        // CRANE tells TRAIN and SLATE apart: it wins now or names the answer
+        assert_eq!(worst(&["crane", "train", "slate"]), 2);
+    }
+
+    #[test]
+    fn test_cover_words_in_a_chain() {
+        // Five -ATCH words: each miss rules out only the word guessed
+        assert_eq!(worst(&["batch", "catch", "hatch", "latch", "match"]), 5);
+    }
+
+    #[test]
+    fn test_window_cached_per_pool() {
+        let (table, pool) = table_and_pool(&["crane", "train", "slate"]);
+        let mut planner = Planner::new();
+        let window = planner.win_window(&table, &pool);
+
+        // The same pool in another order hits the cache
+        let reversed: Vec<u32> = pool.iter().rev().copied().collect();
+        assert_eq!(planner.win_window(&table, &reversed), window);
+        assert_eq!(planner.last.as_ref().unwrap().0, pool);
+    }
+
+    #[test]
+    fn test_greedy_bound_on_large_pool() {
+        // 40 words sharing nothing but a first letter: too many to search
+        let words: Vec<String> = (0..40u8)
+            .map(|i| {
+                let a = (b'a' + i % 26) as char;
+                let b = (b'a' + (i / 26) * 5 + i % 5) as char;
+                format!("q{}{}xy", a, b)
+            })
+            .collect();
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        let (table, pool) = table_and_pool(&words);
+        assert!(pool.len() > EXACT_POOL_LIMIT);
+
+        let worst = Planner::new().win_window(&table, &pool).unwrap().worst;
+        // Never worse than guessing one by one, and at least two
+        assert!((2..=pool.len()).contains(&worst), "worst case {}", worst);
+    }
+}
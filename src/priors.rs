@@ -0,0 +1,190 @@
+//! How likely a candidate is to be the answer, judged from its shape alone.
+//!
+//! Curated answer lists avoid plurals ending in S and lean toward common words
+//! with few repeated letters. A few cheap features of the word capture that,
+//! and configurable weights combine them into a prior between 0 and 1 that is
+//! multiplied into a candidate's score. All-zero weights leave every score as is.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::wordtable::WordTable;
+
+/// Letters rare enough in everyday words that each one marks a word as less common.
+const RARE_LETTERS: &[u8] = b"jqxzvkw";
+
+/// Vowel count of a typical answer; each vowel more or fewer is penalized.
+const TYPICAL_VOWELS: usize = 2;
+
+/// Penalty per unit of each feature; higher weights push a feature's words further down.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PriorWeights {
+    pub plural: f64,
+    pub duplicates: f64,
+    pub vowels: f64,
+    pub rarity: f64,
+}
+
+impl Default for PriorWeights {
+    fn default() -> Self {
+        Self {
+            plural: 1.5,
+            duplicates: 0.3,
+            vowels: 0.2,
+            rarity: 0.4,
+        }
+    }
+}
+
+/// 1.0 for a word that reads as a plural ending in S, 0.0 otherwise.
+///
+/// Endings in SS, US and IS are usually singular (GLASS, FOCUS, BASIS).
+pub fn plural_likelihood(word: &str) -> f64 {
+    let bytes = word.as_bytes();
+    match bytes {
+        [.., b's' | b'u' | b'i', b's'] => 0.0,
+        [.., b's'] => 1.0,
+        _ => 0.0,
+    }
+}
+
+/// Letters that repeat one already in the word; SPEED has one, GEESE two.
+pub fn duplicate_letters(word: &str) -> usize {
+    let mut seen = 0u32;
+    word.bytes()
+        .filter(|b| b.is_ascii_lowercase())
+        .filter(|&b| {
+            let bit = 1 << (b - b'a');
+            let repeat = seen & bit != 0;
+            seen |= bit;
+            repeat
+        })
+        .count()
+}
+
+/// Vowels in the word, Y not counted.
+pub fn vowel_count(word: &str) -> usize {
+    word.bytes().filter(|b| b"aeiou".contains(b)).count()
+}
+
+/// 0 for a word of everyday letters, rising by one for each rare letter in it.
+///
+/// There is no word-frequency list to go by, so rare letters stand in for how
+/// common a word is.
+pub fn commonness_tier(word: &str) -> usize {
+    word.bytes().filter(|b| RARE_LETTERS.contains(b)).count()
+}
+
+/// Prior for `word` under `weights`, in (0, 1]; 1 means no penalty.
+pub fn prior(word: &str, weights: &PriorWeights) -> f64 {
+    let penalty = weights.plural * plural_likelihood(word)
+        + weights.duplicates * duplicate_letters(word) as f64
+        + weights.vowels * vowel_count(word).abs_diff(TYPICAL_VOWELS) as f64
+        + weights.rarity * commonness_tier(word) as f64;
+    (-penalty.max(0.0)).exp()
+}
+
+/// Multiply each score in `scored` by its word's prior and re-sort, best first.
+///
+/// Returns how many places each word moved, positive for up, leaving out words
+/// that kept their rank. Words with equal adjusted scores keep their order.
+pub fn apply_priors(
+    table: &WordTable,
+    scored: &mut [(u32, usize)],
+    weights: &PriorWeights,
+) -> HashMap<u32, isize> {
+    let before: HashMap<u32, usize> = scored
+        .iter()
+        .enumerate()
+        .map(|(rank, &(i, _))| (i, rank))
+        .collect();
+
+    for (i, score) in scored.iter_mut() {
+        *score = (*score as f64 * prior(table.word(*i), weights)).round() as usize;
+    }
+    scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+
+    scored
+        .iter()
+        .enumerate()
+        .filter_map(|(rank, &(i, _))| {
+            let shift = before[&i] as isize - rank as isize;
+            (shift != 0).then_some((i, shift))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::score_and_sort;
+
+    #[test]
+    fn test_plural_likelihood() {
+        assert_eq!(plural_likelihood("foxes"), 1.0);
+        assert_eq!(plural_likelihood("cats"), 1.0);
+        assert_eq!(plural_likelihood("glass"), 0.0);
+        assert_eq!(plural_likelihood("focus"), 0.0);
+        assert_eq!(plural_likelihood("basis"), 0.0);
+        assert_eq!(plural_likelihood("focal"), 0.0);
+    }
+
+    #[test]
+    fn test_duplicate_letters() {
+        assert_eq!(duplicate_letters("crane"), 0);
+        assert_eq!(duplicate_letters("speed"), 1);
+        assert_eq!(duplicate_letters("geese"), 2);
+    }
+
+    #[test]
+    fn test_vowel_count() {
+        assert_eq!(vowel_count("crane"), 2);
+        assert_eq!(vowel_count("lynch"), 0);
+        assert_eq!(vowel_count("audio"), 4);
+    }
+
+    #[test]
+    fn test_commonness_tier() {
+        assert_eq!(commonness_tier("crane"), 0);
+        assert_eq!(commonness_tier("foxes"), 1);
+        assert_eq!(commonness_tier("jazzy"), 3);
+    }
+
+    #[test]
+    fn test_zero_weights_change_nothing() {
+        let weights = PriorWeights {
+            plural: 0.0,
+            duplicates: 0.0,
+            vowels: 0.0,
+            rarity: 0.0,
+        };
+        assert_eq!(prior("foxes", &weights), 1.0);
+        assert_eq!(prior("geese", &weights), 1.0);
+    }
+
+    #[test]
+    fn test_foxes_drops_below_focal() {
+        let words: Vec<String> = ["foxes", "focal", "boxes", "hoses", "fores", "coals"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let table = WordTable::new(&words, 5);
+        let pool: Vec<u32> = (0..words.len() as u32).collect();
+        let mut scored = score_and_sort(&table, &pool, &table);
+        let rank = |scored: &[(u32, usize)], word: &str| {
+            scored
+                .iter()
+                .position(|&(i, _)| table.word(i) == word)
+                .unwrap()
+        };
+        assert!(rank(&scored, "foxes") < rank(&scored, "focal"));
+
+        let shifts = apply_priors(&table, &mut scored, &PriorWeights::default());
+
+        assert!(rank(&scored, "foxes") > rank(&scored, "focal"));
+        assert!(shifts[&table.index_of("focal").unwrap()] > 0);
+        assert!(shifts[&table.index_of("foxes").unwrap()] < 0);
+    }
+}
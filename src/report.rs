@@ -0,0 +1,519 @@
+//! Weekly summaries behind `wordle-warlord report` and history mode's `w`.
+//!
+//! Days are calendar days in one fixed UTC offset, so a game played just
+//! before local midnight counts toward the day it was played on.
+
+use std::{fmt, str::FromStr};
+
+use anyhow::Result;
+use chrono::{Datelike, FixedOffset, NaiveDate, Weekday};
+
+use crate::{
+    local_time::TimePrefs,
+    storage::Database,
+    ui::history::{GameOutcome, GameRecord, SolverStats},
+};
+
+/// An ISO 8601 week: Monday to Sunday, numbered within its ISO year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IsoWeek {
+    year: i32,
+    week: u32,
+}
+
+impl IsoWeek {
+    /// The week `date` falls in.
+    pub fn containing(date: NaiveDate) -> Self {
+        let iso = date.iso_week();
+        Self {
+            year: iso.year(),
+            week: iso.week(),
+        }
+    }
+
+    /// The week today falls in, in the display zone of `prefs`.
+    pub fn current(prefs: &TimePrefs) -> Self {
+        Self::containing(prefs.today())
+    }
+
+    pub fn monday(&self) -> NaiveDate {
+        NaiveDate::from_isoywd_opt(self.year, self.week, Weekday::Mon)
+            .expect("weeks are validated when built")
+    }
+
+    pub fn sunday(&self) -> NaiveDate {
+        NaiveDate::from_isoywd_opt(self.year, self.week, Weekday::Sun)
+            .expect("weeks are validated when built")
+    }
+
+    /// The UTC offset of the display zone of `prefs` at the start of the week.
+    pub fn local_offset(&self, prefs: &TimePrefs) -> FixedOffset {
+        prefs.offset_on(self.monday())
+    }
+}
+
+impl FromStr for IsoWeek {
+    type Err = String;
+
+    /// Parses `YYYY-WW`, also accepting `YYYY-Www`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected YYYY-WW, got {:?}", s);
+        let (year, week) = s.split_once('-').ok_or_else(invalid)?;
+        let week = week.strip_prefix(['W', 'w']).unwrap_or(week);
+        let year: i32 = year.parse().map_err(|_| invalid())?;
+        let week: u32 = week.parse().map_err(|_| invalid())?;
+        NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)
+            .ok_or_else(|| format!("{} has no week {}", year, week))?;
+        Ok(Self { year, week })
+    }
+}
+
+impl fmt::Display for IsoWeek {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-W{:02}", self.year, self.week)
+    }
+}
+
+/// How a report is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Plain,
+    /// Headed, with a table, for pasting into a journal.
+    Markdown,
+}
+
+/// What happened on one day of the week.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DaySummary {
+    pub date: NaiveDate,
+    pub games: usize,
+    pub wins: usize,
+    pub sessions: usize,
+}
+
+/// A game singled out as the week's best or worst.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameHighlight {
+    pub word: String,
+    pub guesses: usize,
+    pub outcome: GameOutcome,
+    pub date: NaiveDate,
+}
+
+/// The solver guess that shrank its pool the most.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolReduction {
+    pub word: String,
+    pub before: usize,
+    pub after: usize,
+    pub date: NaiveDate,
+}
+
+/// Games and solver sessions over one week.
+#[derive(Debug, Clone)]
+pub struct WeeklyReport {
+    pub week: IsoWeek,
+    /// Monday to Sunday, including days with nothing played.
+    pub days: Vec<DaySummary>,
+    pub games: usize,
+    pub wins: usize,
+    /// Mean guesses per won game; `None` without a win.
+    pub average_guesses: Option<f64>,
+    /// The win in the fewest guesses, earliest first on a tie.
+    pub best: Option<GameHighlight>,
+    /// The game with the most guesses, preferring one not won; `None` unless
+    /// at least two games were played.
+    pub worst: Option<GameHighlight>,
+    pub sessions: usize,
+    /// Percentage of measured solver guesses that were optimal.
+    pub adherence: Option<f64>,
+    pub biggest_reduction: Option<PoolReduction>,
+}
+
+impl WeeklyReport {
+    /// Gather `week` from `db`, with days taken in `offset`.
+    pub fn build(db: &Database, week: IsoWeek, offset: FixedOffset) -> Result<Self> {
+        let (monday, sunday) = (week.monday(), week.sunday());
+        let day_games = db.games_by_local_date(monday, sunday, offset)?;
+        let day_sessions = db.sessions_by_local_date(monday, sunday, offset)?;
+
+        let days: Vec<DaySummary> = monday
+            .iter_days()
+            .take(7)
+            .map(|date| {
+                let games = day_games.iter().find(|d| d.key == date);
+                DaySummary {
+                    date,
+                    games: games.map_or(0, |d| d.games),
+                    wins: games.map_or(0, |d| d.wins),
+                    sessions: day_sessions
+                        .iter()
+                        .find(|&&(d, _)| d == date)
+                        .map_or(0, |&(_, count)| count),
+                }
+            })
+            .collect();
+
+        let wins: usize = day_games.iter().map(|d| d.wins).sum();
+        let won_guesses: f64 = day_games
+            .iter()
+            .filter_map(|d| Some(d.average_guesses? * d.wins as f64))
+            .sum();
+
+        let local_date = |timestamp: &chrono::DateTime<chrono::Utc>| {
+            timestamp.with_timezone(&offset).date_naive()
+        };
+        let in_week = |date: NaiveDate| (monday..=sunday).contains(&date);
+
+        let games: Vec<GameRecord> = db
+            .load_games()?
+            .into_iter()
+            .filter(|g| in_week(local_date(&g.timestamp)))
+            .collect();
+        let highlight = |game: &GameRecord| GameHighlight {
+            word: game.target_word.clone(),
+            guesses: game.guess_count(),
+            outcome: game.outcome.clone(),
+            date: local_date(&game.timestamp),
+        };
+        let won = |game: &GameRecord| matches!(game.outcome, GameOutcome::Won { .. });
+        let best = games
+            .iter()
+            .filter(|g| won(g))
+            .min_by_key(|g| (g.guess_count(), g.timestamp))
+            .map(highlight);
+        let worst = games
+            .iter()
+            .filter(|_| games.len() > 1)
+            .min_by_key(|g| (won(g), std::cmp::Reverse(g.guess_count()), g.timestamp))
+            .map(highlight);
+
+        let sessions: Vec<_> = db
+            .load_solver_sessions()?
+            .into_iter()
+            .filter(|s| in_week(local_date(&s.timestamp)))
+            .collect();
+        let measured = sessions
+            .iter()
+            .any(|s| s.guesses.iter().any(|g| g.deviation_score.is_some()));
+        let adherence = measured.then(|| SolverStats::from_sessions(&sessions).optimal_adherence);
+        let biggest_reduction = sessions
+            .iter()
+            .flat_map(|s| s.guesses.iter().map(move |g| (s, g)))
+            .filter(|(_, g)| g.pool_size_after < g.pool_size_before)
+            .min_by_key(|(s, g)| {
+                (
+                    std::cmp::Reverse(g.pool_size_before - g.pool_size_after),
+                    s.timestamp,
+                )
+            })
+            .map(|(s, g)| PoolReduction {
+                word: g.word.clone(),
+                before: g.pool_size_before,
+                after: g.pool_size_after,
+                date: local_date(&s.timestamp),
+            });
+
+        Ok(Self {
+            week,
+            days,
+            games: games.len(),
+            wins,
+            average_guesses: (wins > 0).then(|| won_guesses / wins as f64),
+            best,
+            worst,
+            sessions: sessions.len(),
+            adherence,
+            biggest_reduction,
+        })
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Plain => self.render_plain(),
+            ReportFormat::Markdown => self.render_markdown(),
+        }
+    }
+
+    fn render_plain(&self) -> String {
+        let mut out = format!("Week {}: {}\n\n", self.week, self.date_range());
+        out.push_str(&format!(
+            "{:<12}{:>7}{:>6}{:>10}\n",
+            "Day", "Games", "Won", "Sessions"
+        ));
+        for day in &self.days {
+            out.push_str(&format!(
+                "{:<12}{:>7}{:>6}{:>10}\n",
+                short_date(day.date),
+                day.games,
+                day.wins,
+                day.sessions
+            ));
+        }
+        out.push('\n');
+        for (label, value) in self.summary() {
+            out.push_str(&format!("{:<18}{}\n", format!("{}:", label), value));
+        }
+        out
+    }
+
+    fn render_markdown(&self) -> String {
+        let mut out = format!("# Week {}\n\n{}\n\n", self.week, self.date_range());
+        out.push_str("| Day | Games | Won | Solver sessions |\n");
+        out.push_str("| --- | ---: | ---: | ---: |\n");
+        for day in &self.days {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                short_date(day.date),
+                day.games,
+                day.wins,
+                day.sessions
+            ));
+        }
+        out.push('\n');
+        for (label, value) in self.summary() {
+            out.push_str(&format!("- **{}:** {}\n", label, value));
+        }
+        out
+    }
+
+    /// "Mon 12 Oct – Sun 18 Oct 2026"
+    fn date_range(&self) -> String {
+        format!(
+            "{} – {} {}",
+            short_date(self.week.monday()),
+            short_date(self.week.sunday()),
+            self.week.sunday().year()
+        )
+    }
+
+    /// The summary lines shared by both formats, as label and value.
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        let mut lines = Vec::new();
+        if self.games == 0 {
+            lines.push(("Games", "none".to_string()));
+        } else {
+            lines.push((
+                "Games",
+                format!(
+                    "{} played, {} won ({:.1}%)",
+                    self.games,
+                    self.wins,
+                    self.wins as f64 / self.games as f64 * 100.0
+                ),
+            ));
+        }
+        if let Some(average) = self.average_guesses {
+            lines.push(("Average guesses", format!("{:.2} per win", average)));
+        }
+        if let Some(best) = &self.best {
+            lines.push(("Best game", describe_game(best)));
+        }
+        if let Some(worst) = &self.worst {
+            lines.push(("Worst game", describe_game(worst)));
+        }
+        let sessions = match (self.sessions, self.adherence) {
+            (0, _) => "none".to_string(),
+            (n, Some(adherence)) => format!("{}, {:.1}% of guesses optimal", n, adherence),
+            (n, None) => n.to_string(),
+        };
+        lines.push(("Solver sessions", sessions));
+        if let Some(cut) = &self.biggest_reduction {
+            lines.push((
+                "Biggest pool cut",
+                format!(
+                    "{} took the pool from {} to {} ({})",
+                    cut.word.to_uppercase(),
+                    cut.before,
+                    cut.after,
+                    short_date(cut.date)
+                ),
+            ));
+        }
+        lines
+    }
+}
+
+/// "Mon 12 Oct"
+fn short_date(date: NaiveDate) -> String {
+    date.format("%a %-d %b").to_string()
+}
+
+/// "STONE in 3 (Tue 13 Oct)", or how it was lost.
+fn describe_game(game: &GameHighlight) -> String {
+    let result = match game.outcome {
+        GameOutcome::Won { .. } => format!("in {}", game.guesses),
+        GameOutcome::Lost => format!("lost after {}", game.guesses),
+        GameOutcome::Forfeited => format!("given up after {}", game.guesses),
+        GameOutcome::Abandoned(reason) => {
+            format!("abandoned after {} ({})", game.guesses, reason.name())
+        }
+    };
+    format!(
+        "{} {} ({})",
+        game.word.to_uppercase(),
+        result,
+        short_date(game.date)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        settings::Difficulty,
+        ui::history::{DeviationMetric, GameGuess, solver_types::SolverGuess},
+    };
+    use chrono::{TimeZone, Utc};
+    use std::path::Path;
+
+    /// Compare with `tests/golden/<name>`; set `UPDATE_GOLDEN=1` to rewrite it instead.
+    fn assert_golden(name: &str, actual: &str) {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(name);
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(&path, actual).unwrap();
+        }
+        assert_eq!(actual, std::fs::read_to_string(&path).unwrap());
+    }
+
+    fn week() -> IsoWeek {
+        "2026-42".parse().unwrap()
+    }
+
+    /// Five hours behind UTC, so late-evening games fall on the previous UTC day.
+    fn offset() -> FixedOffset {
+        FixedOffset::west_opt(5 * 3600).unwrap()
+    }
+
+    fn save_game(db: &Database, utc: (u32, u32), word: &str, outcome: GameOutcome) {
+        let guesses = match outcome {
+            GameOutcome::Won { guesses } => guesses,
+            GameOutcome::Lost => 6,
+            GameOutcome::Forfeited | GameOutcome::Abandoned(_) => 2,
+        };
+        db.save_game(&GameRecord {
+            timestamp: Utc.with_ymd_and_hms(2026, 10, utc.0, utc.1, 0, 0).unwrap(),
+            target_word: word.to_string(),
+            guesses: vec![GameGuess::new("crane".to_string(), vec![]); guesses],
+            outcome,
+            blitz: false,
+            duration_secs: None,
+            app_version: None,
+            difficulty: Difficulty::Normal,
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        })
+        .unwrap();
+    }
+
+    fn solver_guess(word: &str, before: usize, after: usize, deviation: f64) -> SolverGuess {
+        SolverGuess {
+            word: word.to_string(),
+            pool_size_before: before,
+            pool_size_after: after,
+            entropy: 5.0,
+            optimal_word: Some("raise".to_string()),
+            optimal_entropy: Some(5.0),
+            deviation_score: Some(deviation),
+            metric: Some(DeviationMetric::Entropy),
+            feedback: None,
+            note: None,
+        }
+    }
+
+    fn fixture() -> Database {
+        let db = Database::open_memory().unwrap();
+        // Sunday evening locally, the week before
+        save_game(&db, (12, 3), "apple", GameOutcome::Won { guesses: 1 });
+        // Monday evening locally
+        save_game(&db, (13, 2), "stone", GameOutcome::Won { guesses: 3 });
+        // Tuesday
+        save_game(&db, (13, 15), "slate", GameOutcome::Won { guesses: 2 });
+        save_game(&db, (13, 16), "crane", GameOutcome::Lost);
+        save_game(&db, (15, 14), "house", GameOutcome::Won { guesses: 4 });
+        save_game(&db, (15, 18), "world", GameOutcome::Forfeited);
+        // Sunday evening locally
+        save_game(&db, (19, 4), "magic", GameOutcome::Won { guesses: 5 });
+
+        let at = |day, hour| Utc.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap();
+        db.save_solver_session(
+            at(14, 13),
+            &[
+                solver_guess("raise", 2315, 132, 0.0),
+                solver_guess("tonic", 132, 6, -0.4),
+                solver_guess("shone", 6, 1, 0.0),
+            ],
+        )
+        .unwrap();
+        db.save_solver_session(at(16, 20), &[solver_guess("crane", 2315, 80, 0.0)])
+            .unwrap();
+        db
+    }
+
+    #[test]
+    fn test_parse_week() {
+        assert_eq!(
+            week().monday(),
+            NaiveDate::from_ymd_opt(2026, 10, 12).unwrap()
+        );
+        assert_eq!("2026-W42".parse::<IsoWeek>().unwrap(), week());
+        assert_eq!(week().to_string(), "2026-W42");
+        // 2020 ran to week 53; 2021 did not
+        assert!("2020-53".parse::<IsoWeek>().is_ok());
+        assert_eq!(
+            "2021-53".parse::<IsoWeek>().unwrap_err(),
+            "2021 has no week 53"
+        );
+        assert!("2026".parse::<IsoWeek>().is_err());
+        assert!("2026-xx".parse::<IsoWeek>().is_err());
+    }
+
+    #[test]
+    fn test_week_containing_crosses_years() {
+        let week = IsoWeek::containing(NaiveDate::from_ymd_opt(2027, 1, 1).unwrap());
+        assert_eq!(week.to_string(), "2026-W53");
+        assert_eq!(week.sunday(), NaiveDate::from_ymd_opt(2027, 1, 3).unwrap());
+    }
+
+    #[test]
+    fn test_report_uses_local_days() {
+        let report = WeeklyReport::build(&fixture(), week(), offset()).unwrap();
+
+        let games: Vec<usize> = report.days.iter().map(|d| d.games).collect();
+        assert_eq!(games, [1, 2, 0, 2, 0, 0, 1]);
+        assert_eq!((report.games, report.wins), (6, 4));
+        assert_eq!(report.average_guesses, Some(3.5));
+        assert_eq!(report.best.as_ref().unwrap().word, "slate");
+        assert_eq!(report.worst.as_ref().unwrap().word, "crane");
+        assert_eq!(report.sessions, 2);
+        assert_eq!(report.biggest_reduction.as_ref().unwrap().word, "crane");
+    }
+
+    #[test]
+    fn test_markdown_golden() {
+        let report = WeeklyReport::build(&fixture(), week(), offset()).unwrap();
+        assert_golden("weekly_report.md", &report.render(ReportFormat::Markdown));
+    }
+
+    #[test]
+    fn test_plain_golden() {
+        let report = WeeklyReport::build(&fixture(), week(), offset()).unwrap();
+        assert_golden("weekly_report.txt", &report.render(ReportFormat::Plain));
+    }
+
+    #[test]
+    fn test_empty_week_markdown_golden() {
+        let db = Database::open_memory().unwrap();
+        let report = WeeklyReport::build(&db, week(), offset()).unwrap();
+        assert!(report.best.is_none() && report.adherence.is_none());
+        assert_golden(
+            "weekly_report_empty.md",
+            &report.render(ReportFormat::Markdown),
+        );
+    }
+}
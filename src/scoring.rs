@@ -1,64 +1,332 @@
 use std::collections::{HashMap, HashSet};
 
-pub fn score_and_sort(words: &[&String], solutions: &HashSet<String>) -> Vec<(String, usize)> {
-    let mut freq: HashMap<char, usize> = HashMap::new();
+use crate::{
+    analysis::ConstraintSummary,
+    solver::{Feedback, generate_feedback},
+    wordtable::WordTable,
+};
+
+const SOLUTION_BONUS: usize = 10;
+
+/// Known constraint state of a letter, as shown in a score explanation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LetterStatus {
+    Green,
+    Yellow,
+    Gray,
+    Unknown,
+}
+
+/// How much a single unique letter contributes to a word's score.
+#[derive(Debug, Clone)]
+pub struct LetterContribution {
+    pub letter: char,
+    pub frequency: usize,
+    pub status: LetterStatus,
+}
+
+/// Breakdown of why a word received its suggestion score.
+#[derive(Debug, Clone)]
+pub struct ScoreExplanation {
+    pub word: String,
+    pub letters: Vec<LetterContribution>,
+    pub solution_bonus: usize,
+    pub total: usize,
+    pub is_candidate: bool,
+    pub likely_feedback: Option<(Vec<Feedback>, usize)>,
+    /// Answer prior the total is multiplied by when priors are on.
+    pub prior: Option<f64>,
+}
+
+/// A feedback pattern a guess could get, and how likely it is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternOdds {
+    pub feedback: Vec<Feedback>,
+    /// Candidates that give this pattern, and so are left after it.
+    pub remaining: usize,
+    /// Share of the pool giving this pattern.
+    pub probability: f64,
+}
+
+/// Occurrences of each letter across the pool, indexed by byte.
+fn letter_frequencies(table: &WordTable, pool: &[u32]) -> [usize; 256] {
+    let mut freq = [0; 256];
 
-    for word in words {
-        for c in word.chars() {
-            *freq.entry(c).or_insert(0) += 1;
+    for &i in pool {
+        for &b in table.bytes(i) {
+            freq[b as usize] += 1;
         }
     }
 
-    const SOLUTION_BONUS: usize = 10;
+    freq
+}
+
+/// Name stored with optimal words recomputed by [`entropy_optimal`], or by
+/// [`score_and_sort`] for pools over [`ENTROPY_SCORING_MAX_POOL`].
+pub const STRATEGY: &str = "expected-entropy";
+
+/// Largest pool whose optimal word is found by [`expected_entropy`]; every
+/// candidate is played against every other, so larger pools fall back to
+/// [`score_and_sort`].
+pub const ENTROPY_SCORING_MAX_POOL: usize = 500;
+
+/// Pools at least this large are scored across threads.
+#[cfg(feature = "parallel")]
+const PARALLEL_POOL: usize = 2048;
+
+/// Score every word in `pool`, best first. Words also in `solutions` get a bonus.
+///
+/// Words with equal scores keep their pool order, however the pool was scored.
+pub fn score_and_sort(table: &WordTable, pool: &[u32], solutions: &WordTable) -> Vec<(u32, usize)> {
+    #[cfg(feature = "parallel")]
+    if pool.len() >= PARALLEL_POOL {
+        return sorted(score_parallel(table, pool, solutions));
+    }
+    sorted(score_serial(table, pool, solutions))
+}
+
+fn sorted(mut scored: Vec<(u32, usize)>) -> Vec<(u32, usize)> {
+    scored.sort_by_key(|b| std::cmp::Reverse(b.1));
+    scored
+}
 
-    let mut scored: Vec<(String, usize)> = words
+fn score_serial(table: &WordTable, pool: &[u32], solutions: &WordTable) -> Vec<(u32, usize)> {
+    let freq = letter_frequencies(table, pool);
+    pool.iter()
+        .map(|&i| (i, word_score(table, i, &freq, solutions)))
+        .collect()
+}
+
+#[cfg(feature = "parallel")]
+fn score_parallel(table: &WordTable, pool: &[u32], solutions: &WordTable) -> Vec<(u32, usize)> {
+    use rayon::prelude::*;
+
+    let freq = letter_frequencies(table, pool);
+    pool.par_iter()
+        .map(|&i| (i, word_score(table, i, &freq, solutions)))
+        .collect()
+}
+
+/// Pool frequency of each distinct letter of word `i`, plus the solution bonus.
+fn word_score(table: &WordTable, i: u32, freq: &[usize; 256], solutions: &WordTable) -> usize {
+    // Table words are lowercase ASCII, so a bit per letter marks those seen
+    let mut seen = 0u32;
+    let mut score = 0;
+    for &b in table.bytes(i) {
+        let bit = 1 << (b - b'a');
+        if seen & bit == 0 {
+            seen |= bit;
+            score += freq[b as usize];
+        }
+    }
+
+    if solutions.contains(table.word(i)) {
+        score += SOLUTION_BONUS;
+    }
+    score
+}
+
+/// The optimal word of `scored`, as [`score_and_sort`] returns it, and its
+/// score, passing over words `skip` rejects: the highest score, and the word
+/// earliest in the pool among equals. `None` when no word is left.
+///
+/// Skipped words still count toward the letter frequencies, as blacklisted
+/// words do for the suggestions, so this is the first suggestion for the same
+/// pool and anything measured against it agrees with what was suggested.
+pub fn get_optimal_word(
+    table: &WordTable,
+    scored: &[(u32, usize)],
+    skip: impl Fn(&str) -> bool,
+) -> Option<(u32, usize)> {
+    scored.iter().find(|&&(i, _)| !skip(table.word(i))).copied()
+}
+
+/// The score `word` has in `scored`, or `None` if it wasn't scored.
+pub fn score_of(table: &WordTable, word: &str, scored: &[(u32, usize)]) -> Option<usize> {
+    scored
         .iter()
-        .map(|word| {
-            let unique: HashSet<char> = word.chars().collect();
+        .find(|&&(i, _)| table.word(i) == word)
+        .map(|&(_, score)| score)
+}
 
-            let mut score: usize = unique.iter().map(|c| freq[c]).sum();
+/// Explain the score `word` would receive against `pool`.
+///
+/// Letters are reported in word order with their known constraint status, and
+/// the most likely feedback pattern is the largest bucket when `word` is
+/// scored against every remaining candidate.
+pub fn explain_score(
+    word: &str,
+    table: &WordTable,
+    pool: &[u32],
+    solutions: &WordTable,
+    constraints: &ConstraintSummary,
+) -> ScoreExplanation {
+    let freq = letter_frequencies(table, pool);
+
+    let mut seen = HashSet::new();
+    let letters: Vec<LetterContribution> = word
+        .chars()
+        .filter(|c| seen.insert(*c))
+        .map(|c| LetterContribution {
+            letter: c,
+            frequency: if c.is_ascii() { freq[c as usize] } else { 0 },
+            status: letter_status(c, constraints),
+        })
+        .collect();
 
-            if solutions.contains(*word) {
-                score += SOLUTION_BONUS;
-            }
+    let solution_bonus = if solutions.contains(word) {
+        SOLUTION_BONUS
+    } else {
+        0
+    };
+    let total = letters.iter().map(|l| l.frequency).sum::<usize>() + solution_bonus;
+
+    let likely_feedback = pattern_distribution(word, table, pool)
+        .into_iter()
+        .next()
+        .map(|odds| (odds.feedback, odds.remaining));
+
+    ScoreExplanation {
+        word: word.to_string(),
+        letters,
+        solution_bonus,
+        total,
+        is_candidate: pool.iter().any(|&i| table.word(i) == word),
+        likely_feedback,
+        prior: None,
+    }
+}
+
+/// Partition `pool` by the feedback each candidate would give to `word`.
+pub fn feedback_buckets(
+    word: &str,
+    table: &WordTable,
+    pool: &[u32],
+) -> HashMap<Vec<Feedback>, usize> {
+    let mut buckets = HashMap::new();
+    for &i in pool {
+        *buckets
+            .entry(generate_feedback(table.word(i), word))
+            .or_insert(0) += 1;
+    }
+    buckets
+}
 
-            ((*word).clone(), score)
+/// Every pattern `word` could get against `pool`, most likely first and
+/// equally likely ones in pattern order.
+pub fn pattern_distribution(word: &str, table: &WordTable, pool: &[u32]) -> Vec<PatternOdds> {
+    let mut odds: Vec<PatternOdds> = feedback_buckets(word, table, pool)
+        .into_iter()
+        .map(|(feedback, remaining)| PatternOdds {
+            feedback,
+            remaining,
+            probability: remaining as f64 / pool.len() as f64,
         })
         .collect();
+    odds.sort_by(|a, b| {
+        b.remaining
+            .cmp(&a.remaining)
+            .then_with(|| pattern_string(&a.feedback).cmp(&pattern_string(&b.feedback)))
+    });
+    odds
+}
 
-    scored.sort_by(|a, b| b.1.cmp(&a.1));
-    scored
+/// Pool size to expect after guessing `word`, averaged over every candidate
+/// being the answer.
+pub fn expected_pool_after(word: &str, table: &WordTable, pool: &[u32]) -> f64 {
+    if pool.is_empty() {
+        return 0.0;
+    }
+
+    let sum_of_squares: usize = feedback_buckets(word, table, pool)
+        .values()
+        .map(|&size| size * size)
+        .sum();
+    sum_of_squares as f64 / pool.len() as f64
+}
+
+/// Bits of information `word` is expected to give about `pool`: the entropy
+/// of the patterns it splits the pool into.
+pub fn expected_entropy(word: &str, table: &WordTable, pool: &[u32]) -> f64 {
+    let total = pool.len() as f64;
+    feedback_buckets(word, table, pool)
+        .values()
+        .map(|&size| {
+            let p = size as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// The word of `pool` with the highest [`expected_entropy`] against it, the
+/// first in pool order among equals, and that entropy.
+pub fn entropy_optimal(table: &WordTable, pool: &[u32]) -> Option<(u32, f64)> {
+    pool.iter()
+        .map(|&i| (i, expected_entropy(table.word(i), table, pool)))
+        .fold(None, |best, (i, bits)| match best {
+            // Bucket order varies, so equal entropies can differ in the last bits
+            Some((_, best_bits)) if best_bits >= bits - 1e-9 => best,
+            _ => Some((i, bits)),
+        })
+}
+
+/// How many more candidates a guess eliminated than expected.
+///
+/// Positive means the real feedback was kinder than average; negative means
+/// the answer happened to sit in one of the larger buckets.
+pub fn luck(word: &str, table: &WordTable, pool_before: &[u32], pool_after: usize) -> f64 {
+    expected_pool_after(word, table, pool_before) - pool_after as f64
+}
+
+fn letter_status(c: char, constraints: &ConstraintSummary) -> LetterStatus {
+    if constraints.greens.iter().any(|(l, _, _)| *l == c) {
+        LetterStatus::Green
+    } else if constraints.yellows.iter().any(|(l, _, _)| *l == c) {
+        LetterStatus::Yellow
+    } else if constraints.is_excluded(c) {
+        LetterStatus::Gray
+    } else {
+        LetterStatus::Unknown
+    }
 }
 
-/// Returns the best word (highest score) from the given words
-pub fn get_optimal_word(words: &[&String], solutions: &HashSet<String>) -> Option<(String, usize)> {
-    let scored = score_and_sort(words, solutions);
-    scored.first().cloned()
+/// Render feedback in the `G`/`Y`/`X` input notation.
+pub fn pattern_string(feedback: &[Feedback]) -> String {
+    feedback
+        .iter()
+        .map(|f| match f {
+            Feedback::Green => 'G',
+            Feedback::Yellow => 'Y',
+            Feedback::Gray => 'X',
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashSet;
+    use crate::{
+        analysis::compute_constraint_summary,
+        solver::{Guess, SolverState, parse_pattern},
+    };
+
+    /// A table of `words` and a pool covering all of it.
+    fn table_and_pool(words: &[&str]) -> (WordTable, Vec<u32>) {
+        let table = WordTable::new(words, 5);
+        let pool = table.indices().collect();
+        (table, pool)
+    }
 
     #[test]
     fn test_score_and_sort_basic() {
-        let words = vec![
-            String::from("apple"),
-            String::from("grape"),
-            String::from("peach"),
-            String::from("plumb"),
-        ];
-
-        let word_refs: Vec<&String> = words.iter().collect();
+        let words = ["apple", "grape", "peach", "plumb"];
+        let (table, pool) = table_and_pool(&words);
 
         // Treat all words as valid solutions for neutrality
-        let solutions: HashSet<String> = words.iter().cloned().collect();
-
-        let scored = score_and_sort(&word_refs, &solutions);
+        let scored = score_and_sort(&table, &pool, &table);
 
         // All words should be present
-        let scored_words: Vec<String> = scored.iter().map(|(w, _)| w.clone()).collect();
+        let scored_words: Vec<&str> = scored.iter().map(|&(i, _)| table.word(i)).collect();
 
         for w in &words {
             assert!(scored_words.contains(w));
@@ -72,27 +340,73 @@ mod tests {
 
     #[test]
     fn test_score_and_sort_unique_letters() {
-        let words = [String::from("abcde"), String::from("aaaaa")];
-
-        let word_refs: Vec<&String> = words.iter().collect();
+        let (table, pool) = table_and_pool(&["abcde", "aaaaa"]);
 
         // Only "abcde" is a solution, reinforcing ordering
-        let mut solutions = HashSet::new();
-        solutions.insert("abcde".to_string());
+        let solutions = WordTable::new(["abcde"], 5);
 
-        let scored = score_and_sort(&word_refs, &solutions);
+        let scored = score_and_sort(&table, &pool, &solutions);
 
-        assert_eq!(scored[0].0, "abcde");
+        assert_eq!(table.word(scored[0].0), "abcde");
         assert!(scored[0].1 > scored[1].1);
     }
 
+    /// `count` distinct five-letter words spread across the alphabet.
+    fn synthetic_words(count: usize) -> Vec<String> {
+        (0..count)
+            .map(|n| {
+                // A stride coprime to 26^5 visits every word once
+                let mut code = n * 7919 % 26usize.pow(5);
+                (0..5)
+                    .map(|_| {
+                        let letter = (b'a' + (code % 26) as u8) as char;
+                        code /= 26;
+                        letter
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_matches_serial() {
+        let words = synthetic_words(PARALLEL_POOL * 2);
+        let table = WordTable::new(&words, 5);
+        let pool: Vec<u32> = table.indices().collect();
+        let solutions = WordTable::new(&words[..500], 5);
+
+        let serial = sorted(score_serial(&table, &pool, &solutions));
+        let parallel = sorted(score_parallel(&table, &pool, &solutions));
+        assert_eq!(parallel, serial);
+        assert_eq!(score_and_sort(&table, &pool, &solutions), serial);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_large_pool_scores_quickly() {
+        // About the size of the allowed list; scoring is linear, so even an
+        // unoptimized build finishes far inside this
+        let words = synthetic_words(13_000);
+        let table = WordTable::new(&words, 5);
+        let pool: Vec<u32> = table.indices().collect();
+
+        let start = std::time::Instant::now();
+        let scored = score_and_sort(&table, &pool, &table);
+        let elapsed = start.elapsed();
+        assert_eq!(scored.len(), 13_000);
+        assert!(
+            elapsed < std::time::Duration::from_secs(2),
+            "scoring 13k words took {:?}",
+            elapsed
+        );
+    }
+
     #[test]
     fn test_score_and_sort_empty() {
-        let words: Vec<String> = vec![];
-        let word_refs: Vec<&String> = words.iter().collect();
-        let solutions: HashSet<String> = HashSet::new();
+        let (table, pool) = table_and_pool(&[]);
 
-        let scored = score_and_sort(&word_refs, &solutions);
+        let scored = score_and_sort(&table, &pool, &WordTable::default());
 
         assert!(scored.is_empty());
     }
@@ -100,28 +414,228 @@ mod tests {
     #[test]
     fn test_solution_bonus_applied() {
         // Same letter distribution, solution should win
-        let words = [String::from("crate"), String::from("trace")];
+        let (table, pool) = table_and_pool(&["crate", "trace"]);
+        let solutions = WordTable::new(["crate"], 5);
 
-        let word_refs: Vec<&String> = words.iter().collect();
+        let scored = score_and_sort(&table, &pool, &solutions);
 
-        let mut solutions = HashSet::new();
-        solutions.insert("crate".to_string());
+        assert_eq!(table.word(scored[0].0), "crate");
+    }
 
-        let scored = score_and_sort(&word_refs, &solutions);
+    #[test]
+    fn solution_words_get_bonus() {
+        let (table, pool) = table_and_pool(&["crate", "probe"]);
+        let solutions = WordTable::new(["probe"], 5);
 
-        assert_eq!(scored[0].0, "crate");
+        let scored = score_and_sort(&table, &pool, &solutions);
+
+        assert_eq!(table.word(scored[0].0), "probe");
     }
 
     #[test]
-    fn solution_words_get_bonus() {
-        let words = [String::from("crate"), String::from("probe")];
-        let word_refs: Vec<&String> = words.iter().collect();
+    fn test_explain_score_matches_ranking() {
+        let (table, pool) = table_and_pool(&["crate", "trace", "probe"]);
+        let constraints = compute_constraint_summary(&SolverState::new(5));
+
+        let scored = score_and_sort(&table, &pool, &table);
+        for &(i, score) in &scored {
+            let explanation = explain_score(table.word(i), &table, &pool, &table, &constraints);
+            assert_eq!(explanation.total, score);
+            assert_eq!(explanation.solution_bonus, SOLUTION_BONUS);
+            assert!(explanation.is_candidate);
+        }
+    }
+
+    #[test]
+    fn test_explain_score_reports_known_letters() {
+        let mut solver = SolverState::new(5);
+        solver
+            .add_guess(Guess::new(
+                "crane".to_string(),
+                parse_pattern("GXYXX").unwrap(),
+            ))
+            .unwrap();
+        let constraints = compute_constraint_summary(&solver);
+
+        let (table, pool) = table_and_pool(&["cloak"]);
+
+        let explanation =
+            explain_score("crank", &table, &pool, &WordTable::default(), &constraints);
+        let status: Vec<(char, LetterStatus)> = explanation
+            .letters
+            .iter()
+            .map(|l| (l.letter, l.status))
+            .collect();
+
+        assert_eq!(
+            status,
+            vec![
+                ('c', LetterStatus::Green),
+                ('r', LetterStatus::Gray),
+                ('a', LetterStatus::Yellow),
+                ('n', LetterStatus::Gray),
+                ('k', LetterStatus::Unknown),
+            ]
+        );
+        assert!(!explanation.is_candidate);
+        assert_eq!(explanation.solution_bonus, 0);
+    }
+
+    #[test]
+    fn test_explain_score_likely_feedback() {
+        let (table, pool) = table_and_pool(&["stone", "shone", "apple"]);
+        let constraints = compute_constraint_summary(&SolverState::new(5));
+
+        let explanation =
+            explain_score("crane", &table, &pool, &WordTable::default(), &constraints);
+        let (pattern, size) = explanation.likely_feedback.unwrap();
 
-        let mut solutions = HashSet::new();
-        solutions.insert("probe".to_string());
+        // STONE and SHONE both answer XXXGG to CRANE
+        assert_eq!(pattern_string(&pattern), "XXXGG");
+        assert_eq!(size, 2);
+    }
+
+    #[test]
+    fn test_pattern_distribution() {
+        let (table, pool) = table_and_pool(&["stone", "shone", "apple", "crane"]);
+
+        let odds = pattern_distribution("crane", &table, &pool);
+        let summary: Vec<(String, usize)> = odds
+            .iter()
+            .map(|o| (pattern_string(&o.feedback), o.remaining))
+            .collect();
+
+        // STONE and SHONE share XXXGG; APPLE and CRANE are alone, in pattern order
+        assert_eq!(
+            summary,
+            [
+                ("XXXGG".to_string(), 2),
+                ("GGGGG".to_string(), 1),
+                ("XXYXG".to_string(), 1),
+            ]
+        );
+        assert_eq!(odds[0].probability, 0.5);
+        assert_eq!(odds[1].probability, 0.25);
+        let total: f64 = odds.iter().map(|o| o.probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(pattern_distribution("crane", &table, &[]).is_empty());
+    }
 
-        let scored = score_and_sort(&word_refs, &solutions);
+    #[test]
+    fn test_get_optimal_word_empty_pool() {
+        let table = WordTable::default();
+
+        assert_eq!(get_optimal_word(&table, &[], |_| false), None);
+    }
 
-        assert_eq!(scored[0].0, "probe");
+    #[test]
+    fn test_get_optimal_word_single_word() {
+        let (table, pool) = table_and_pool(&["stone"]);
+
+        // Five letters seen once each, plus the solution bonus
+        assert_eq!(
+            get_optimal_word(&table, &score_and_sort(&table, &pool, &table), |_| false),
+            Some((pool[0], 5 + SOLUTION_BONUS))
+        );
+        assert_eq!(
+            get_optimal_word(
+                &table,
+                &score_and_sort(&table, &pool, &WordTable::default()),
+                |_| false
+            ),
+            Some((pool[0], 5))
+        );
+    }
+
+    #[test]
+    fn test_get_optimal_word_is_first_suggestion() {
+        let (table, pool) = table_and_pool(&["apple", "grape", "peach", "plumb", "stone"]);
+        let scored = score_and_sort(&table, &pool, &table);
+
+        let best = get_optimal_word(&table, &scored, |_| false);
+
+        assert_eq!(best, scored.first().copied());
+        // GRAPE scores as well, but APPLE comes first
+        assert_eq!(table.word(best.unwrap().0), "apple");
+
+        // Skipping APPLE leaves GRAPE, scored as before
+        let (next, score) = get_optimal_word(&table, &scored, |w| w == "apple").unwrap();
+        assert_eq!(table.word(next), "grape");
+        assert_eq!(Some(score), best.map(|(_, s)| s));
+        assert_eq!(get_optimal_word(&table, &scored, |_| true), None);
+    }
+
+    #[test]
+    fn test_get_optimal_word_ties_go_to_pool_order() {
+        // Anagrams share their letters, so they score the same
+        let (table, pool) = table_and_pool(&["least", "slate", "stale"]);
+
+        let (best, score) =
+            get_optimal_word(&table, &score_and_sort(&table, &pool, &table), |_| false).unwrap();
+        assert_eq!(table.word(best), "least");
+
+        let reversed: Vec<u32> = pool.iter().rev().copied().collect();
+        let (best_reversed, score_reversed) =
+            get_optimal_word(&table, &score_and_sort(&table, &reversed, &table), |_| {
+                false
+            })
+            .unwrap();
+        assert_eq!(table.word(best_reversed), "stale");
+        assert_eq!(score, score_reversed);
+    }
+
+    #[test]
+    fn test_score_of() {
+        let (table, pool) = table_and_pool(&["apple", "stone"]);
+        let scored = score_and_sort(&table, &pool, &table);
+
+        assert_eq!(score_of(&table, "stone", &scored), Some(scored[0].1));
+        assert_eq!(score_of(&table, "crane", &scored), None);
+    }
+
+    #[test]
+    fn test_expected_pool_after() {
+        let (table, pool) = table_and_pool(&["stone", "shone", "apple"]);
+
+        // CRANE splits the pool into {stone, shone} and {apple}: (2² + 1²) / 3
+        let expected = expected_pool_after("crane", &table, &pool);
+
+        assert!((expected - 5.0 / 3.0).abs() < 1e-9);
+        assert_eq!(expected_pool_after("crane", &table, &[]), 0.0);
+    }
+
+    #[test]
+    fn test_expected_entropy() {
+        let (table, pool) = table_and_pool(&["stone", "shone", "apple"]);
+
+        // CRANE splits off APPLE; STONE tells all three apart
+        let crane = expected_entropy("crane", &table, &pool);
+        assert!((crane - (3.0f64.log2() - 2.0 / 3.0)).abs() < 1e-9);
+        assert!((expected_entropy("stone", &table, &pool) - 3.0f64.log2()).abs() < 1e-9);
+        assert_eq!(expected_entropy("stone", &table, &pool[..1]), 0.0);
+    }
+
+    #[test]
+    fn test_entropy_optimal_prefers_first_of_equals() {
+        let (table, pool) = table_and_pool(&["apple", "stone", "shone"]);
+
+        // SHONE and STONE both tell all three apart; the table keeps SHONE first
+        let (best, bits) = entropy_optimal(&table, &pool).unwrap();
+        assert_eq!(table.word(best), "shone");
+        assert!((bits - 3.0f64.log2()).abs() < 1e-9);
+        assert_eq!(entropy_optimal(&table, &[]), None);
+    }
+
+    #[test]
+    fn test_luck_depends_on_answer() {
+        let (table, pool) = table_and_pool(&["stone", "shone", "apple"]);
+
+        // Answer APPLE isolates the singleton bucket: lucky
+        assert!((luck("crane", &table, &pool, 1) - 2.0 / 3.0).abs() < 1e-9);
+        // Answer STONE lands in the pair: unlucky
+        assert!((luck("crane", &table, &pool, 2) + 1.0 / 3.0).abs() < 1e-9);
+        // A guess that splits every candidate apart carries no luck either way
+        let (table, pool) = table_and_pool(&["stone", "apple"]);
+        assert_eq!(luck("stone", &table, &pool, 1), 0.0);
     }
 }
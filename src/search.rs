@@ -0,0 +1,123 @@
+//! Looking words up in a word list by prefix or wildcard pattern.
+//!
+//! A query without wildcards matches every word it begins, so a full word
+//! matches only itself. With wildcards the query must match the whole word:
+//! `_` stands for exactly one letter and `*` for any run of letters, empty
+//! included, so `s_a_e` finds SHAKE and STARE and `*ight` finds LIGHT.
+
+use crate::wordtable::WordTable;
+
+/// Whether `word` matches `query`, which is lowercase like every table word.
+pub fn matches_query(word: &str, query: &str) -> bool {
+    if query.contains(['_', '*']) {
+        matches_wildcard(word.as_bytes(), query.as_bytes())
+    } else {
+        word.starts_with(query)
+    }
+}
+
+/// Indices of the words in `table` matching `query`, in table order.
+///
+/// The query is trimmed and lowercased first; a blank query matches nothing.
+pub fn search(table: &WordTable, query: &str) -> Vec<u32> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    table
+        .indices()
+        .filter(|&i| matches_query(table.word(i), &query))
+        .collect()
+}
+
+fn matches_wildcard(word: &[u8], pattern: &[u8]) -> bool {
+    // The last `*` seen, and where in the word its run would end if it
+    // swallowed one more letter
+    let (mut w, mut p) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while w < word.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                star = Some((p, w));
+                p += 1;
+            }
+            Some(&c) if c == b'_' || c == word[w] => {
+                w += 1;
+                p += 1;
+            }
+            _ => match star {
+                Some((star_p, star_w)) => {
+                    star = Some((star_p, star_w + 1));
+                    p = star_p + 1;
+                    w = star_w + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_query_matches_prefix() {
+        assert!(matches_query("zesty", "zes"));
+        assert!(matches_query("zesty", "zesty"));
+        assert!(matches_query("zesty", ""));
+        assert!(!matches_query("zesty", "esty"));
+        assert!(!matches_query("zest", "zesty"));
+    }
+
+    #[test]
+    fn test_underscore_matches_one_letter() {
+        assert!(matches_query("shake", "s_a_e"));
+        assert!(matches_query("stare", "s_a_e"));
+        assert!(matches_query("stare", "_____"));
+        assert!(!matches_query("stare", "____"));
+        assert!(!matches_query("stare", "s_a_"));
+        assert!(!matches_query("stone", "s_a_e"));
+    }
+
+    #[test]
+    fn test_star_matches_any_run() {
+        assert!(matches_query("light", "*ight"));
+        assert!(matches_query("ight", "*ight"));
+        assert!(matches_query("light", "l*"));
+        assert!(matches_query("light", "*"));
+        assert!(matches_query("light", "l*h*"));
+        assert!(!matches_query("lights", "*ight"));
+        assert!(!matches_query("fight", "l*"));
+    }
+
+    #[test]
+    fn test_star_backtracks() {
+        // The first `g` is too early; the star must swallow it
+        assert!(matches_query("gaggle", "*gle"));
+        assert!(matches_query("abcbc", "*bc"));
+        assert!(matches_query("mississippi", "m*iss*ppi"));
+        assert!(!matches_query("mississippi", "m*iss*ppix"));
+        assert!(matches_query("stare", "*a_e"));
+        assert!(matches_query("stare", "_*_"));
+    }
+
+    #[test]
+    fn test_search_in_table_order() {
+        let table = WordTable::new(["stare", "light", "shake", "fight", "slate"], 5);
+        let found = |query| -> Vec<&str> {
+            search(&table, query)
+                .into_iter()
+                .map(|i| table.word(i))
+                .collect()
+        };
+
+        assert_eq!(found("s_a_e"), ["shake", "slate", "stare"]);
+        assert_eq!(found(" *IGHT "), ["fight", "light"]);
+        assert_eq!(found("s"), ["shake", "slate", "stare"]);
+        assert_eq!(found("slate"), ["slate"]);
+        assert!(found("").is_empty());
+        assert!(found("zesty").is_empty());
+    }
+}
@@ -0,0 +1,296 @@
+//! User preferences persisted in the `settings` table.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{local_time::HourFormat, priors::PriorWeights, storage::Database, tiers::WordTier};
+
+/// A panel in the analysis column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PanelKind {
+    Letters,
+    Positions,
+    Constraints,
+    Pool,
+    Logs,
+}
+
+impl PanelKind {
+    /// Every panel, in the default top-to-bottom order.
+    pub const ALL: [PanelKind; 5] = [
+        PanelKind::Letters,
+        PanelKind::Positions,
+        PanelKind::Constraints,
+        PanelKind::Pool,
+        PanelKind::Logs,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PanelKind::Letters => "Letters",
+            PanelKind::Positions => "Positions",
+            PanelKind::Constraints => "Constraints",
+            PanelKind::Pool => "Pool",
+            PanelKind::Logs => "Logs",
+        }
+    }
+}
+
+/// How much help Game mode gives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    /// Suggestions and analysis shown from the start, with undo.
+    Easy,
+    #[default]
+    Normal,
+    /// Hard mode, no hints, analysis or pool counts, and no undo.
+    Expert,
+}
+
+impl Difficulty {
+    /// Every difficulty, easiest first.
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Expert];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Expert => "Expert",
+        }
+    }
+
+    /// The value stored in the `games.difficulty` column.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Expert => "expert",
+        }
+    }
+
+    /// Parse a stored value, treating anything unknown as Normal.
+    pub fn from_stored(value: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|d| d.as_str() == value)
+            .unwrap_or_default()
+    }
+
+    /// The next difficulty, wrapping from Expert back to Easy.
+    pub fn next(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Expert,
+            Difficulty::Expert => Difficulty::Easy,
+        }
+    }
+}
+
+/// User-configurable preferences.
+///
+/// Each field is stored as its own row so new settings can be added without a
+/// migration; missing rows fall back to the defaults below.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Hide suggestions containing letters already known to be absent.
+    pub hide_gray_suggestions: bool,
+    /// Re-rank candidates by how answer-like they look.
+    pub answer_priors: bool,
+    /// How strongly each answer-likelihood feature counts against a candidate.
+    pub prior_weights: PriorWeights,
+    /// Seconds allowed per guess in blitz games.
+    pub blitz_seconds: u64,
+    /// Games in a gauntlet started with Ctrl+N.
+    pub gauntlet_size: usize,
+    /// Ask before a mode switch throws away an unfinished game or solver session.
+    pub confirm_discard: bool,
+    /// Ask before taking a word that was already guessed, rather than refusing it.
+    pub confirm_repeat_guess: bool,
+    /// Hours a solver session left by quitting is offered for resuming at
+    /// startup; 0 never offers.
+    pub resume_window_hours: u64,
+    /// Rank letters in the Letters panel by information rather than frequency.
+    pub letter_information: bool,
+    /// Log full analysis dumps at debug level on every recompute.
+    pub verbose_analysis_logging: bool,
+    /// Words in each game's answer; more than one plays a phrase game.
+    pub phrase_words: usize,
+    /// Visible analysis panels, top to bottom; panels left out are hidden.
+    pub analysis_panels: Vec<PanelKind>,
+    /// Redraw at most five times a second, leave out the entropy charts, and
+    /// draw bars with plain characters, for slow terminals and SSH.
+    pub low_refresh: bool,
+    /// Vim-style input: Esc switches to a normal mode where letters are commands.
+    pub modal_input: bool,
+    /// Help given in games started from now on.
+    pub difficulty: Difficulty,
+    /// Tier of answers new games are drawn from; every answer when unset.
+    pub word_tier: Option<WordTier>,
+    /// Show lifetime wins, losses and streak on a line above the game panels.
+    pub quick_stats: bool,
+    /// Days between the automatic database backups taken at startup; 0
+    /// never takes one.
+    pub backup_interval_days: u32,
+    /// Backups kept under `backups/`; older ones are deleted.
+    pub backups_kept: usize,
+    /// Hint points each game allows for showing suggestions or analysis and
+    /// revealing best guesses; unlimited when unset.
+    pub hint_budget: Option<usize>,
+    /// Keep `current-state.json` in the data directory up to date for
+    /// external tools to poll.
+    pub spectator_file: bool,
+    /// End a solver session as soon as one answer is left, without waiting
+    /// for it to be entered all green or confirmed.
+    pub solver_auto_complete: bool,
+    /// Submit a game guess on its own once the input holds a whole allowed
+    /// word, after a short pause Backspace cancels.
+    pub auto_submit: bool,
+    /// Boards in a multi-board practice game, from 2 to 8.
+    pub board_count: usize,
+    /// New games are kids' games: everyday answers, no guess limit, and
+    /// three common words always suggested.
+    pub kids_mode: bool,
+    /// Ring the terminal bell, and show a desktop notification in builds
+    /// with them, when a game, gauntlet or background job finishes.
+    pub notify_when_done: bool,
+    /// Clock times in the history views are shown in.
+    pub hour_format: HourFormat,
+    /// Zone times are shown in, as a tz database name like `Europe/Berlin`
+    /// or an offset like `+05:30`; the system's when unset.
+    pub timezone: Option<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            hide_gray_suggestions: false,
+            answer_priors: false,
+            prior_weights: PriorWeights::default(),
+            blitz_seconds: 30,
+            gauntlet_size: 5,
+            confirm_discard: true,
+            confirm_repeat_guess: false,
+            resume_window_hours: 24,
+            letter_information: false,
+            verbose_analysis_logging: false,
+            phrase_words: 1,
+            analysis_panels: PanelKind::ALL.to_vec(),
+            low_refresh: false,
+            modal_input: false,
+            difficulty: Difficulty::Normal,
+            word_tier: None,
+            quick_stats: false,
+            backup_interval_days: 7,
+            backups_kept: 8,
+            hint_budget: None,
+            spectator_file: true,
+            solver_auto_complete: false,
+            auto_submit: false,
+            board_count: 4,
+            kids_mode: false,
+            notify_when_done: false,
+            hour_format: HourFormat::TwentyFour,
+            timezone: None,
+        }
+    }
+}
+
+impl Settings {
+    /// Load settings from the database, ignoring unknown or malformed rows.
+    pub fn load(db: &Database) -> Result<Self> {
+        let mut map = serde_json::Map::new();
+        for (key, value) in db.load_settings()? {
+            if let Ok(value) = serde_json::from_str(&value) {
+                map.insert(key, value);
+            }
+        }
+
+        let defaults = serde_json::to_value(Self::default())?;
+        if let serde_json::Value::Object(defaults) = defaults {
+            for (key, value) in defaults {
+                map.entry(key).or_insert(value);
+            }
+        }
+
+        Ok(serde_json::from_value(serde_json::Value::Object(map)).unwrap_or_default())
+    }
+
+    /// Persist every setting.
+    pub fn save(&self, db: &Database) -> Result<()> {
+        if let serde_json::Value::Object(map) = serde_json::to_value(self)? {
+            for (key, value) in map {
+                db.save_setting(&key, &value.to_string())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_default_when_empty() {
+        let db = Database::open_memory().unwrap();
+
+        assert_eq!(Settings::load(&db).unwrap(), Settings::default());
+    }
+
+    #[test]
+    fn test_settings_round_trip() {
+        let db = Database::open_memory().unwrap();
+
+        let settings = Settings {
+            hide_gray_suggestions: true,
+            answer_priors: true,
+            prior_weights: PriorWeights {
+                plural: 2.0,
+                duplicates: 0.0,
+                vowels: 0.5,
+                rarity: 1.0,
+            },
+            blitz_seconds: 45,
+            gauntlet_size: 3,
+            confirm_discard: false,
+            confirm_repeat_guess: true,
+            resume_window_hours: 6,
+            letter_information: true,
+            verbose_analysis_logging: true,
+            phrase_words: 2,
+            analysis_panels: vec![PanelKind::Pool, PanelKind::Constraints, PanelKind::Logs],
+            low_refresh: true,
+            modal_input: true,
+            difficulty: Difficulty::Expert,
+            word_tier: Some(WordTier::Hard),
+            quick_stats: true,
+            backup_interval_days: 1,
+            backups_kept: 3,
+            hint_budget: Some(2),
+            spectator_file: false,
+            solver_auto_complete: true,
+            auto_submit: true,
+            board_count: 8,
+            kids_mode: true,
+            notify_when_done: true,
+            hour_format: HourFormat::Twelve,
+            timezone: Some("Europe/Berlin".to_string()),
+        };
+        settings.save(&db).unwrap();
+
+        assert_eq!(Settings::load(&db).unwrap(), settings);
+    }
+
+    #[test]
+    fn test_settings_ignore_malformed_rows() {
+        let db = Database::open_memory().unwrap();
+        db.save_setting("hide_gray_suggestions", "not json")
+            .unwrap();
+        db.save_setting("unknown_setting", "1").unwrap();
+
+        assert_eq!(Settings::load(&db).unwrap(), Settings::default());
+    }
+}
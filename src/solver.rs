@@ -1,8 +1,13 @@
-use anyhow::Result;
 use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::fmt;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+use crate::{
+    error::{Result, TranscriptError, WordleError},
+    wordtable::WordTable,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Feedback {
     Green,
     Yellow,
@@ -12,7 +17,7 @@ pub enum Feedback {
 impl TryFrom<char> for Feedback {
     type Error = char;
 
-    fn try_from(value: char) -> Result<Self, Self::Error> {
+    fn try_from(value: char) -> std::result::Result<Self, Self::Error> {
         match value.to_ascii_uppercase() {
             'G' => Ok(Self::Green),
             'Y' => Ok(Self::Yellow),
@@ -34,16 +39,29 @@ impl Guess {
     }
 }
 
-#[derive(Debug)]
+/// Guesses so far and the shape of the answer.
+///
+/// An answer may be a phrase of several words, each a segment scored as its
+/// own Wordle. Guess words and feedback then run the segments together with
+/// no separator, so positions count across the whole phrase.
+#[derive(Debug, Clone)]
 pub struct SolverState {
+    /// Total letters across every segment.
     word_len: usize,
+    segments: Vec<usize>,
     guesses: Vec<Guess>,
 }
 
 impl SolverState {
     pub fn new(word_len: usize) -> Self {
+        Self::with_segments(vec![word_len])
+    }
+
+    /// A solver for a phrase of words with these lengths, in order.
+    pub fn with_segments(segments: Vec<usize>) -> Self {
         Self {
-            word_len,
+            word_len: segments.iter().sum(),
+            segments,
             guesses: Vec::new(),
         }
     }
@@ -52,6 +70,40 @@ impl SolverState {
         self.word_len
     }
 
+    /// Length of each word in the answer; a single entry unless it is a phrase.
+    pub fn segments(&self) -> &[usize] {
+        &self.segments
+    }
+
+    /// The guesses restricted to segment `index`, as a single-word solver.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn segment(&self, index: usize) -> Self {
+        let start: usize = self.segments[..index].iter().sum();
+        let range = start..start + self.segments[index];
+        let mut segment = Self::new(self.segments[index]);
+        segment.guesses = self
+            .guesses
+            .iter()
+            .map(|g| {
+                Guess::new(
+                    g.word[range.clone()].to_string(),
+                    g.feedback[range.clone()].to_vec(),
+                )
+            })
+            .collect();
+        segment
+    }
+
+    /// Candidates for each segment, in order. See [`filter`](Self::filter).
+    pub fn filter_segments(&self, table: &WordTable) -> Vec<Vec<u32>> {
+        (0..self.segments.len())
+            .map(|i| self.segment(i).filter(table))
+            .collect()
+    }
+
     pub fn guesses(&self) -> &[Guess] {
         &self.guesses
     }
@@ -60,36 +112,260 @@ impl SolverState {
         self.guesses.pop();
     }
 
-    pub fn add_guess(&mut self, guess: Guess) {
-        assert_eq!(guess.word.len(), self.word_len);
-        assert_eq!(guess.feedback.len(), self.word_len);
+    pub fn add_guess(&mut self, guess: Guess) -> Result<()> {
+        check_len(guess.word.chars().count(), self.word_len)?;
+        check_len(guess.feedback.len(), self.word_len)?;
 
         self.guesses.push(guess);
+        Ok(())
     }
 
-    pub fn filter<'a>(&self, words: &'a [String]) -> Vec<&'a String> {
-        words
-            .iter()
-            .filter(|w| w.len() == self.word_len)
-            .filter(|w| {
-                self.guesses
-                    .iter()
-                    .all(|g| matches(w, &g.word, &g.feedback))
-            })
-            .collect()
+    /// Replace the guess at `index`, keeping every later guess in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn replace_guess(&mut self, index: usize, guess: Guess) -> Result<()> {
+        check_len(guess.word.chars().count(), self.word_len)?;
+        check_len(guess.feedback.len(), self.word_len)?;
+
+        self.guesses[index] = guess;
+        Ok(())
+    }
+
+    /// A solver holding only the first `count` guesses.
+    pub fn truncated(&self, count: usize) -> Self {
+        Self {
+            word_len: self.word_len,
+            segments: self.segments.clone(),
+            guesses: self.guesses[..count.min(self.guesses.len())].to_vec(),
+        }
+    }
+
+    /// Indices of the words in `table` consistent with every guess so far.
+    pub fn filter(&self, table: &WordTable) -> Vec<u32> {
+        let mut out = Vec::new();
+        self.filter_into(table, &mut out);
+        out
+    }
+
+    /// Like [`filter`](Self::filter), but reuses the allocation in `out`.
+    pub fn filter_into(&self, table: &WordTable, out: &mut Vec<u32>) {
+        out.clear();
+        if table.word_len() != self.word_len {
+            return;
+        }
+        // A pattern nothing can give empties the pool without a scan
+        if self.segments.len() == 1
+            && self
+                .guesses
+                .iter()
+                .any(|g| check_pattern(&g.word, &g.feedback).is_err())
+        {
+            return;
+        }
+
+        out.extend(table.indices().filter(|&i| {
+            let word = table.bytes(i);
+            self.guesses
+                .iter()
+                .all(|g| matches_bytes(word, g.word.as_bytes(), &g.feedback))
+        }));
     }
 }
 
 pub fn parse_pattern(pattern: &str) -> Result<Vec<Feedback>> {
     pattern
         .chars()
-        .map(|c| {
-            Feedback::try_from(c)
-                .map_err(|bad| anyhow::anyhow!("invalid pattern character: {}", bad))
-        })
+        .map(|c| Feedback::try_from(c).map_err(WordleError::PatternChar))
         .collect()
 }
 
+/// Ensure some word could give `guess` the feedback `pattern`, judging from
+/// the guess alone.
+///
+/// Each yellow letter needs a place of its own that isn't green and didn't
+/// hold that letter in the guess, so `EERIE YYXXY` or `EEEEE YYYYY` can never
+/// be the feedback for any word. Different letters can always trade places,
+/// so only one letter at a time needs checking.
+pub fn check_pattern(guess: &str, pattern: &[Feedback]) -> Result<()> {
+    let g: Vec<char> = guess.chars().collect();
+    check_len(pattern.len(), g.len())?;
+
+    let mut yellows: Vec<(char, usize)> = Vec::new();
+    for (&c, &fb) in g.iter().zip(pattern) {
+        if fb != Feedback::Yellow {
+            continue;
+        }
+        match yellows.iter_mut().find(|(y, _)| *y == c) {
+            Some((_, count)) => *count += 1,
+            None => yellows.push((c, 1)),
+        }
+    }
+
+    for (c, needed) in yellows {
+        let free = g
+            .iter()
+            .zip(pattern)
+            .filter(|&(&l, &fb)| fb != Feedback::Green && l != c)
+            .count();
+        if needed <= free {
+            continue;
+        }
+        let c = c.to_ascii_uppercase();
+        return Err(WordleError::ImpossiblePattern(match free {
+            0 => format!("no place is left for the yellow {}", c),
+            _ => format!("{} yellow {}s but only {} place(s) left", needed, c, free),
+        }));
+    }
+    Ok(())
+}
+
+/// Ensure `got` matches the expected word length.
+pub fn check_len(got: usize, expected: usize) -> Result<()> {
+    if got != expected {
+        return Err(WordleError::LengthMismatch { expected, got });
+    }
+    Ok(())
+}
+
+/// Ensure `word` has the expected length and is in the allowed list.
+pub fn check_word(word: &str, word_len: usize, allowed: &WordTable) -> Result<()> {
+    check_len(word.chars().count(), word_len)?;
+
+    if !allowed.contains(word) {
+        return Err(WordleError::WordNotAllowed(word.to_string()));
+    }
+    Ok(())
+}
+
+/// Ensure `phrase` has one allowed word per segment, separated by whitespace.
+pub fn check_phrase(phrase: &str, segments: &[usize], allowed: &WordTable) -> Result<()> {
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    if words.len() != segments.len() {
+        return Err(WordleError::SegmentCount {
+            expected: segments.len(),
+            got: words.len(),
+        });
+    }
+    words
+        .iter()
+        .zip(segments)
+        .try_for_each(|(word, &len)| check_word(word, len, allowed))
+}
+
+/// Ensure `word` uses every hint `guesses` revealed, as in Wordle's hard mode:
+/// green letters stay where they are and yellow letters are used again.
+pub fn check_hard_mode(word: &str, guesses: &[Guess]) -> Result<()> {
+    let letters: Vec<char> = word.chars().collect();
+    for guess in guesses {
+        let mut required: Vec<(char, usize)> = Vec::new();
+        for (i, (c, feedback)) in guess.word.chars().zip(&guess.feedback).enumerate() {
+            if *feedback == Feedback::Gray {
+                continue;
+            }
+            if *feedback == Feedback::Green && letters.get(i) != Some(&c) {
+                return Err(WordleError::HardMode(format!(
+                    "{} letter must be {}",
+                    ordinal(i + 1),
+                    c.to_ascii_uppercase()
+                )));
+            }
+            match required.iter_mut().find(|(r, _)| *r == c) {
+                Some((_, count)) => *count += 1,
+                None => required.push((c, 1)),
+            }
+        }
+
+        for (c, count) in required {
+            if letters.iter().filter(|&&l| l == c).count() < count {
+                return Err(WordleError::HardMode(format!(
+                    "guess must contain {}",
+                    c.to_ascii_uppercase()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (1, 11) | (2, 12) | (3, 13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+/// Put spaces back between the words of a run-together phrase.
+pub fn join_segments(word: &str, segments: &[usize]) -> String {
+    let mut rest = word;
+    let mut words = Vec::with_capacity(segments.len());
+    for &len in segments {
+        let (segment, tail) = rest.split_at(len.min(rest.len()));
+        words.push(segment);
+        rest = tail;
+    }
+    words.join(" ")
+}
+
+/// Parse a transcript of `word pattern` lines into guesses, in order.
+///
+/// Blank lines are skipped. Every line is checked before anything is
+/// returned, so callers can apply the guesses all-or-nothing.
+pub fn parse_transcript(
+    text: &str,
+    word_len: usize,
+    allowed: &WordTable,
+) -> std::result::Result<Vec<Guess>, TranscriptError> {
+    let (guesses, errors) = parse_transcript_lines(text, word_len, allowed);
+    match errors.into_iter().next() {
+        Some(e) => Err(e),
+        None => Ok(guesses),
+    }
+}
+
+/// [`parse_transcript`], carrying on past bad lines: the guesses of the good
+/// lines in order, and an error for each bad one.
+pub fn parse_transcript_lines(
+    text: &str,
+    word_len: usize,
+    allowed: &WordTable,
+) -> (Vec<Guess>, Vec<TranscriptError>) {
+    let mut guesses = Vec::new();
+    let mut errors = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match parse_transcript_line(line, word_len, allowed) {
+            Ok(guess) => guesses.push(guess),
+            Err(source) => errors.push(TranscriptError {
+                line: i + 1,
+                source,
+            }),
+        }
+    }
+    (guesses, errors)
+}
+
+fn parse_transcript_line(line: &str, word_len: usize, allowed: &WordTable) -> Result<Guess> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [word, pattern] = fields[..] else {
+        return Err(WordleError::FieldCount(fields.len()));
+    };
+
+    let word = word.to_lowercase();
+    check_word(&word, word_len, allowed)?;
+    check_len(pattern.chars().count(), word_len)?;
+    let feedback = parse_pattern(pattern)?;
+    check_pattern(&word, &feedback)?;
+    Ok(Guess::new(word, feedback))
+}
+
 pub fn matches(word: &str, guess: &str, pattern: &[Feedback]) -> bool {
     let w: Vec<char> = word.chars().collect();
     let g: Vec<char> = guess.chars().collect();
@@ -138,7 +414,212 @@ pub fn matches(word: &str, guess: &str, pattern: &[Feedback]) -> bool {
     true
 }
 
+/// Whether a word fits a guess's pattern and, if not, the first rule it
+/// breaks, checked in the order [`matches`] checks them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchResult {
+    Matches,
+    /// The word and the guess have different lengths.
+    WrongLength,
+    /// A green letter isn't in its place; positions count from 0.
+    GreenMismatch {
+        position: usize,
+        letter: char,
+    },
+    /// A yellow letter is where the guess had it, so it would have been green.
+    YellowInPlace {
+        position: usize,
+        letter: char,
+    },
+    /// The word has fewer of a letter than the guess's greens and yellows show.
+    MissingYellow {
+        letter: char,
+        needed: usize,
+    },
+    /// The word has more of a gray letter than the greens and yellows allow.
+    GraySurplus {
+        letter: char,
+        allowed: usize,
+    },
+}
+
+impl MatchResult {
+    pub fn is_match(self) -> bool {
+        self == MatchResult::Matches
+    }
+
+    /// The rule broken, e.g. `needs E at position 3`; `None` for a match.
+    pub fn describe(self) -> Option<String> {
+        let upper = |c: char| c.to_ascii_uppercase();
+        let letters = |c: char, n: usize| match n {
+            1 => upper(c).to_string(),
+            n => format!("{} {}s", n, upper(c)),
+        };
+        Some(match self {
+            MatchResult::Matches => return None,
+            MatchResult::WrongLength => "has the wrong number of letters".to_string(),
+            MatchResult::GreenMismatch { position, letter } => {
+                format!("needs {} at position {}", upper(letter), position + 1)
+            }
+            MatchResult::YellowInPlace { position, letter } => {
+                format!(
+                    "needs {}, but not at position {}",
+                    upper(letter),
+                    position + 1
+                )
+            }
+            MatchResult::MissingYellow { letter, needed } => {
+                format!("needs {}", letters(letter, needed))
+            }
+            MatchResult::GraySurplus { letter, allowed: 0 } => {
+                format!("can't contain {}", upper(letter))
+            }
+            MatchResult::GraySurplus { letter, allowed } => {
+                format!("can't have more than {}", letters(letter, allowed))
+            }
+        })
+    }
+}
+
+/// [`matches`], saying which rule of `pattern` the word breaks first.
+pub fn explain_match(word: &str, guess: &str, pattern: &[Feedback]) -> MatchResult {
+    let w: Vec<char> = word.chars().collect();
+    let g: Vec<char> = guess.chars().collect();
+
+    if w.len() != g.len() || g.len() != pattern.len() {
+        return MatchResult::WrongLength;
+    }
+
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for &c in &w {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    // Greens and yellows of a letter, for how many the word needs
+    let shown = |letter: char| {
+        g.iter()
+            .zip(pattern)
+            .filter(|&(&c, &fb)| c == letter && fb != Feedback::Gray)
+            .count()
+    };
+
+    for i in 0..w.len() {
+        if pattern[i] == Feedback::Green {
+            if w[i] != g[i] {
+                return MatchResult::GreenMismatch {
+                    position: i,
+                    letter: g[i],
+                };
+            }
+            *counts.get_mut(&g[i]).unwrap() -= 1;
+        }
+    }
+
+    for i in 0..w.len() {
+        if pattern[i] == Feedback::Yellow {
+            if w[i] == g[i] {
+                return MatchResult::YellowInPlace {
+                    position: i,
+                    letter: g[i],
+                };
+            }
+            match counts.get_mut(&g[i]) {
+                Some(c) if *c > 0 => *c -= 1,
+                _ => {
+                    return MatchResult::MissingYellow {
+                        letter: g[i],
+                        needed: shown(g[i]),
+                    };
+                }
+            }
+        }
+    }
+
+    for i in 0..w.len() {
+        if pattern[i] == Feedback::Gray && counts.get(&g[i]).is_some_and(|&c| c > 0) {
+            return MatchResult::GraySurplus {
+                letter: g[i],
+                allowed: shown(g[i]),
+            };
+        }
+    }
+
+    MatchResult::Matches
+}
+
+/// The first guess a word fails and the rule it breaks there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EliminationReason {
+    /// Index of the guess; shown counting from 1.
+    pub guess_index: usize,
+    pub guess: String,
+    pub failure: MatchResult,
+}
+
+impl fmt::Display for EliminationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "fails guess {} ({}): {}",
+            self.guess_index + 1,
+            self.guess.to_uppercase(),
+            self.failure.describe().unwrap_or_default()
+        )
+    }
+}
+
+/// Why `word` is out of the pool `guesses` leave, or `None` if it fits them all.
+pub fn explain_elimination(word: &str, guesses: &[Guess]) -> Option<EliminationReason> {
+    guesses.iter().enumerate().find_map(|(i, guess)| {
+        let failure = explain_match(word, &guess.word, &guess.feedback);
+        (!failure.is_match()).then(|| EliminationReason {
+            guess_index: i,
+            guess: guess.word.clone(),
+            failure,
+        })
+    })
+}
+
+/// Byte-level [`matches`] for table words; allocation-free.
+pub fn matches_bytes(word: &[u8], guess: &[u8], pattern: &[Feedback]) -> bool {
+    if word.len() != guess.len() || guess.len() != pattern.len() {
+        return false;
+    }
+
+    // Count letters in candidate
+    let mut counts = [0u8; 256];
+    for &b in word {
+        counts[b as usize] += 1;
+    }
+
+    // First pass: enforce greens and reduce counts
+    for i in 0..word.len() {
+        if pattern[i] == Feedback::Green {
+            if word[i] != guess[i] {
+                return false;
+            }
+            counts[guess[i] as usize] -= 1;
+        }
+    }
+
+    // Second pass: yellows
+    for i in 0..word.len() {
+        if pattern[i] == Feedback::Yellow {
+            let count = &mut counts[guess[i] as usize];
+            if word[i] == guess[i] || *count == 0 {
+                return false;
+            }
+            *count -= 1;
+        }
+    }
+
+    // Third pass: grays must have no remaining matches
+    (0..word.len()).all(|i| pattern[i] != Feedback::Gray || counts[guess[i] as usize] == 0)
+}
+
 pub fn filter_words<'a>(words: &'a [String], guess: &str, pattern: &[Feedback]) -> Vec<&'a String> {
+    if check_pattern(guess, pattern).is_err() {
+        return Vec::new();
+    }
     words
         .iter()
         .filter(|w| w.len() == guess.len())
@@ -182,6 +663,30 @@ pub fn generate_feedback(target: &str, guess: &str) -> Vec<Feedback> {
     result
 }
 
+/// Feedback for a guess phrase, scoring each word against the target word in
+/// the same place. Words are separated by whitespace in both phrases.
+pub fn generate_phrase_feedback(target: &str, guess: &str) -> Vec<Feedback> {
+    target
+        .split_whitespace()
+        .zip(guess.split_whitespace())
+        .flat_map(|(t, g)| generate_feedback(t, g))
+        .collect()
+}
+
+/// Guesses whose entered feedback disagrees with what `answer` would have given.
+///
+/// Returns each such guess's index with the feedback `answer` actually produces.
+pub fn inconsistent_guesses(guesses: &[Guess], answer: &str) -> Vec<(usize, Vec<Feedback>)> {
+    guesses
+        .iter()
+        .enumerate()
+        .filter_map(|(i, guess)| {
+            let expected = generate_feedback(answer, &guess.word);
+            (expected != guess.feedback).then_some((i, expected))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,29 +705,28 @@ mod tests {
 
     #[test]
     fn test_solver_state_multiple_guesses_compound() {
-        let words = vec![
-            "dusky".to_string(),
-            "dusty".to_string(),
-            "dumpy".to_string(),
-            "daisy".to_string(),
-        ];
+        let words = WordTable::new(["dusky", "dusty", "dumpy", "daisy"], 5);
 
         let mut state = SolverState::new(5);
 
         // DAISY → GXXYG
-        state.add_guess(Guess::new(
-            "daisy".to_string(),
-            feedback_vec(&[2, 0, 0, 1, 2]),
-        ));
+        state
+            .add_guess(Guess::new(
+                "daisy".to_string(),
+                feedback_vec(&[2, 0, 0, 1, 2]),
+            ))
+            .unwrap();
 
         let remaining = state.filter(&words);
         assert_eq!(remaining.len(), 2);
 
         // DUSTY → GGXGG
-        state.add_guess(Guess::new(
-            "dusty".to_string(),
-            feedback_vec(&[2, 2, 0, 2, 2]),
-        ));
+        state
+            .add_guess(Guess::new(
+                "dusty".to_string(),
+                feedback_vec(&[2, 2, 0, 2, 2]),
+            ))
+            .unwrap();
 
         let remaining = state.filter(&words);
         assert!(remaining.is_empty());
@@ -327,4 +831,635 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_inconsistent_guesses() {
+        let guesses = vec![
+            Guess::new("crane".to_string(), feedback_vec(&[0, 0, 0, 2, 2])),
+            // Mistyped: STONE gives O yellow, not green
+            Guess::new("house".to_string(), feedback_vec(&[0, 2, 0, 1, 2])),
+        ];
+
+        assert!(inconsistent_guesses(&guesses[..1], "stone").is_empty());
+        assert_eq!(
+            inconsistent_guesses(&guesses, "stone"),
+            vec![(1, feedback_vec(&[0, 1, 0, 1, 2]))]
+        );
+        assert_eq!(
+            inconsistent_guesses(&guesses, "slate"),
+            vec![
+                (0, feedback_vec(&[0, 0, 2, 0, 2])),
+                (1, feedback_vec(&[0, 0, 0, 1, 2])),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_pattern_rejects_bad_char() {
+        assert!(matches!(
+            parse_pattern("GYZXX"),
+            Err(WordleError::PatternChar('Z'))
+        ));
+    }
+
+    #[test]
+    fn test_check_pattern_letter_yellow_everywhere() {
+        let err = check_pattern("eeeee", &parse_pattern("YYYYY").unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "impossible pattern: no place is left for the yellow E"
+        );
+        assert!(matches!(err, WordleError::ImpossiblePattern(_)));
+    }
+
+    #[test]
+    fn test_check_pattern_greens_pin_the_free_places() {
+        // S is green, so the three yellow Es share the G's place
+        let err = check_pattern("geese", &parse_pattern("XYYGY").unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "impossible pattern: 3 yellow Es but only 1 place(s) left"
+        );
+        // With G green too, there's nowhere at all
+        assert!(check_pattern("geese", &parse_pattern("GYYGY").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_check_pattern_accepts_possible_duplicates() {
+        for (guess, pattern) in [
+            ("eerie", "YXXXY"),
+            ("geese", "XYYXX"),
+            ("eeeee", "GGGGG"),
+            ("eeeee", "GXXXX"),
+            ("abbey", "YYYYY"),
+            ("eerie", "XXXXX"),
+        ] {
+            assert!(
+                check_pattern(guess, &parse_pattern(pattern).unwrap()).is_ok(),
+                "{} {}",
+                guess,
+                pattern
+            );
+        }
+        assert!(matches!(
+            check_pattern("eerie", &parse_pattern("YYY").unwrap()),
+            Err(WordleError::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_check_pattern_agrees_with_generated_feedback() {
+        let words = [
+            "eerie", "geese", "abbey", "crane", "slate", "emcee", "lever",
+        ];
+        for target in words {
+            for guess in words {
+                let feedback = generate_feedback(target, guess);
+                assert!(
+                    check_pattern(guess, &feedback).is_ok(),
+                    "{} {}",
+                    target,
+                    guess
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_impossible_pattern_empties_pool_without_matching() {
+        let words = vec!["eerie".to_string(), "geese".to_string()];
+        let pattern = parse_pattern("YYYYY").unwrap();
+        assert!(filter_words(&words, "eeeee", &pattern).is_empty());
+
+        let table = WordTable::new(&words, 5);
+        let mut state = SolverState::new(5);
+        state.guesses.push(Guess::new("eeeee".to_string(), pattern));
+        assert!(state.filter(&table).is_empty());
+    }
+
+    #[test]
+    fn test_add_guess_length_mismatch() {
+        let mut state = SolverState::new(5);
+
+        let short_word = state.add_guess(Guess::new("cran".to_string(), vec![Feedback::Gray; 5]));
+        assert!(matches!(
+            short_word,
+            Err(WordleError::LengthMismatch {
+                expected: 5,
+                got: 4
+            })
+        ));
+
+        let short_pattern =
+            state.add_guess(Guess::new("crane".to_string(), vec![Feedback::Gray; 3]));
+        assert!(matches!(
+            short_pattern,
+            Err(WordleError::LengthMismatch {
+                expected: 5,
+                got: 3
+            })
+        ));
+
+        assert!(state.guesses().is_empty());
+    }
+
+    #[test]
+    fn test_check_word() {
+        let allowed = WordTable::new(["crane"], 5);
+
+        assert!(check_word("crane", 5, &allowed).is_ok());
+        assert!(matches!(
+            check_word("zzzzz", 5, &allowed),
+            Err(WordleError::WordNotAllowed(w)) if w == "zzzzz"
+        ));
+        assert!(matches!(
+            check_word("cranes", 5, &allowed),
+            Err(WordleError::LengthMismatch {
+                expected: 5,
+                got: 6
+            })
+        ));
+    }
+
+    #[test]
+    fn test_replace_guess_matches_fresh_sequence() {
+        let words = WordTable::new(["stone", "house", "slate", "crane"], 5);
+        let corrected = vec![
+            Guess::new("raise".to_string(), parse_pattern("XXXYG").unwrap()),
+            Guess::new("house".to_string(), parse_pattern("XYXYG").unwrap()),
+        ];
+
+        let mut edited = SolverState::new(5);
+        edited
+            .add_guess(Guess::new(
+                "raise".to_string(),
+                parse_pattern("XXXXG").unwrap(),
+            ))
+            .unwrap();
+        edited.add_guess(corrected[1].clone()).unwrap();
+        assert!(edited.filter(&words).is_empty());
+
+        edited.replace_guess(0, corrected[0].clone()).unwrap();
+
+        let mut fresh = SolverState::new(5);
+        for guess in corrected {
+            fresh.add_guess(guess).unwrap();
+        }
+        assert_eq!(edited.filter(&words), fresh.filter(&words));
+        assert_eq!(
+            edited.filter(&words),
+            vec![words.index_of("stone").unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_replace_guess_rejects_length_mismatch() {
+        let mut state = SolverState::new(5);
+        state
+            .add_guess(Guess::new("crane".to_string(), vec![Feedback::Gray; 5]))
+            .unwrap();
+
+        let result =
+            state.replace_guess(0, Guess::new("crane".to_string(), vec![Feedback::Gray; 4]));
+
+        assert!(matches!(
+            result,
+            Err(WordleError::LengthMismatch {
+                expected: 5,
+                got: 4
+            })
+        ));
+        assert_eq!(state.guesses()[0].feedback.len(), 5);
+    }
+
+    #[test]
+    fn test_matches_bytes_equivalent_to_matches() {
+        let words = [
+            "stone", "house", "slate", "crane", "apple", "geese", "eerie", "llama", "sassy",
+        ];
+        let patterns: Vec<Vec<Feedback>> = (0..243)
+            .map(|mut n| {
+                (0..5)
+                    .map(|_| {
+                        let fb = [Feedback::Gray, Feedback::Yellow, Feedback::Green][n % 3];
+                        n /= 3;
+                        fb
+                    })
+                    .collect()
+            })
+            .collect();
+
+        for word in words {
+            for guess in words {
+                for pattern in &patterns {
+                    assert_eq!(
+                        matches_bytes(word.as_bytes(), guess.as_bytes(), pattern),
+                        matches(word, guess, pattern),
+                        "{} vs {} with {:?}",
+                        word,
+                        guess,
+                        pattern
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_filter_equivalent_to_string_path() {
+        let words: Vec<String> = [
+            "stone", "house", "slate", "crane", "apple", "world", "magic",
+        ]
+        .iter()
+        .map(|w| w.to_string())
+        .collect();
+        let table = WordTable::new(&words, 5);
+
+        let mut state = SolverState::new(5);
+        state
+            .add_guess(Guess::new(
+                "raise".to_string(),
+                parse_pattern("XXXXG").unwrap(),
+            ))
+            .unwrap();
+
+        let old: Vec<&str> = filter_words(&words, "raise", &parse_pattern("XXXXG").unwrap())
+            .into_iter()
+            .map(|w| w.as_str())
+            .collect();
+        let new: Vec<&str> = state
+            .filter(&table)
+            .into_iter()
+            .map(|i| table.word(i))
+            .collect();
+
+        assert_eq!(new, old);
+    }
+
+    /// Rough budget for the filtering hot path over a scrabble-sized list.
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_filter_large_table_within_budget() {
+        const WORDS: usize = 170_000;
+        let letters = b"abcdefghijklmnopqrstuvwxyz";
+        let words: Vec<String> = (0..WORDS)
+            .map(|mut n| {
+                (0..5)
+                    .map(|_| {
+                        let c = letters[n % 26] as char;
+                        n /= 26;
+                        c
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let table = WordTable::new(&words, 5);
+        drop(words);
+        assert_eq!(table.len(), WORDS);
+        assert_eq!(table.heap_size(), WORDS * 5);
+
+        let mut state = SolverState::new(5);
+        state
+            .add_guess(Guess::new(
+                "raise".to_string(),
+                parse_pattern("XYXXG").unwrap(),
+            ))
+            .unwrap();
+        state
+            .add_guess(Guess::new(
+                "clout".to_string(),
+                parse_pattern("XXXXX").unwrap(),
+            ))
+            .unwrap();
+
+        let mut scratch = Vec::with_capacity(table.len());
+        let start = std::time::Instant::now();
+        for _ in 0..10 {
+            state.filter_into(&table, &mut scratch);
+        }
+        let elapsed = start.elapsed();
+
+        assert!(scratch.capacity() >= table.len());
+        assert!(
+            elapsed < std::time::Duration::from_secs(5),
+            "10 filters over {} words took {:?}",
+            table.len(),
+            elapsed
+        );
+    }
+
+    /// Stacked duplicate-letter guesses over a scrabble-sized list stay
+    /// cheap, whether or not any word could fit them.
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_stacked_duplicate_letter_guesses_within_budget() {
+        let letters = b"abcdefghijklmnopqrstuvwxyz";
+        let words: Vec<String> = (0..170_000usize)
+            .map(|mut n| {
+                (0..5)
+                    .map(|_| {
+                        let c = letters[n % 26] as char;
+                        n /= 26;
+                        c
+                    })
+                    .collect()
+            })
+            .collect();
+        let table = WordTable::new(&words, 5);
+
+        let mut possible = SolverState::new(5);
+        for (guess, pattern) in [
+            ("eerie", "YXXXX"),
+            ("geese", "XYXXX"),
+            ("emcee", "YXXXX"),
+            ("levee", "XYXXX"),
+            ("abbey", "YXYXX"),
+        ] {
+            let feedback = parse_pattern(pattern).unwrap();
+            check_pattern(guess, &feedback).unwrap();
+            possible
+                .add_guess(Guess::new(guess.to_string(), feedback))
+                .unwrap();
+        }
+        let mut impossible = SolverState::new(5);
+        for _ in 0..5 {
+            impossible.guesses.push(Guess::new(
+                "eeeee".to_string(),
+                parse_pattern("YYYYY").unwrap(),
+            ));
+        }
+
+        let start = std::time::Instant::now();
+        possible.filter(&table);
+        assert!(impossible.filter(&table).is_empty());
+        let elapsed = start.elapsed();
+        assert!(
+            elapsed < std::time::Duration::from_secs(5),
+            "stacked duplicate-letter guesses took {:?}",
+            elapsed
+        );
+    }
+
+    #[test]
+    fn test_phrase_feedback_scores_each_word_separately() {
+        // Every letter is in the phrase, but only the E of each word is in its partner
+        let feedback = generate_phrase_feedback("green apple", "apple green");
+
+        assert_eq!(feedback, parse_pattern("XXXXYXXYXX").unwrap());
+    }
+
+    #[test]
+    fn test_two_segment_filtering() {
+        let table = WordTable::new(["green", "grown", "apple", "ample", "eagle"], 5);
+        let mut solver = SolverState::with_segments(vec![5, 5]);
+        let feedback = generate_phrase_feedback("green apple", "grown eagle");
+        solver
+            .add_guess(Guess::new("growneagle".to_string(), feedback))
+            .unwrap();
+
+        let pools: Vec<Vec<&str>> = solver
+            .filter_segments(&table)
+            .iter()
+            .map(|pool| pool.iter().map(|&i| table.word(i)).collect())
+            .collect();
+        assert_eq!(pools, [vec!["green"], vec!["ample", "apple"]]);
+        assert!(solver.filter(&table).is_empty());
+        assert_eq!(solver.segment(1).guesses()[0].word, "eagle");
+    }
+
+    #[test]
+    fn test_check_and_join_phrase() {
+        let allowed = WordTable::new(["green", "apple"], 5);
+
+        assert!(check_phrase("green  apple", &[5, 5], &allowed).is_ok());
+        assert!(matches!(
+            check_phrase("greenapple", &[5, 5], &allowed),
+            Err(WordleError::SegmentCount {
+                expected: 2,
+                got: 1
+            })
+        ));
+        assert!(check_phrase("green eagle", &[5, 5], &allowed).is_err());
+        assert_eq!(join_segments("greenapple", &[5, 5]), "green apple");
+        assert_eq!(join_segments("green", &[5]), "green");
+    }
+
+    #[test]
+    fn test_parse_transcript() {
+        let allowed = WordTable::new(["crane", "stone", "house"], 5);
+        let guesses = parse_transcript("CRANE xxxyg\n\n  stone GGGGG  \n", 5, &allowed).unwrap();
+
+        assert_eq!(guesses.len(), 2);
+        assert_eq!(guesses[0].word, "crane");
+        assert_eq!(guesses[0].feedback, parse_pattern("XXXYG").unwrap());
+        assert_eq!(guesses[1].word, "stone");
+    }
+
+    #[test]
+    fn test_parse_transcript_reports_first_bad_line() {
+        let allowed = WordTable::new(["crane", "stone", "house"], 5);
+
+        let err =
+            parse_transcript("crane XXXYG\n\nstone GGGG\nzzzzz GGGGG", 5, &allowed).unwrap_err();
+        assert_eq!(err.line, 3);
+        assert!(matches!(
+            err.source,
+            WordleError::LengthMismatch {
+                expected: 5,
+                got: 4
+            }
+        ));
+
+        let err = parse_transcript("zzzzz XXXXX", 5, &allowed).unwrap_err();
+        assert!(matches!(err.source, WordleError::WordNotAllowed(_)));
+
+        let err = parse_transcript("crane", 5, &allowed).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1: expected `word pattern`, got 1 field(s)"
+        );
+
+        let err = parse_transcript("house XXQXX", 5, &allowed).unwrap_err();
+        assert!(matches!(err.source, WordleError::PatternChar('Q')));
+
+        let allowed = WordTable::new(["crane", "geese"], 5);
+        let err = parse_transcript("crane XXXYG\ngeese XYYGY", 5, &allowed).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(matches!(err.source, WordleError::ImpossiblePattern(_)));
+    }
+
+    #[test]
+    fn test_parse_transcript_lines_keeps_going() {
+        let allowed = WordTable::new(["crane", "stone", "house"], 5);
+
+        let (guesses, errors) = parse_transcript_lines(
+            "crane XXXYG\nzzzzz GGGGG\n\nstone GGGG\nhouse XXXXX",
+            5,
+            &allowed,
+        );
+        let words: Vec<&str> = guesses.iter().map(|g| g.word.as_str()).collect();
+        assert_eq!(words, ["crane", "house"]);
+        let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, [2, 4]);
+    }
+
+    #[test]
+    fn test_hard_mode_requires_revealed_hints() {
+        // crane against caper: C green, R, A and E yellow, N gray
+        let guesses = [Guess::new(
+            "crane".to_string(),
+            generate_feedback("caper", "crane"),
+        )];
+
+        assert!(check_hard_mode("caper", &guesses).is_ok());
+        assert!(check_hard_mode("clear", &guesses).is_ok());
+        assert_eq!(
+            check_hard_mode("stare", &guesses).unwrap_err().to_string(),
+            "hard mode: 1st letter must be C"
+        );
+        assert_eq!(
+            check_hard_mode("cable", &guesses).unwrap_err().to_string(),
+            "hard mode: guess must contain R"
+        );
+        assert!(check_hard_mode("anything", &[]).is_ok());
+    }
+
+    fn explained(word: &str, guess: &str, pattern: &str) -> MatchResult {
+        explain_match(word, guess, &parse_pattern(pattern).unwrap())
+    }
+
+    #[test]
+    fn test_explain_green_mismatch() {
+        let result = explained("slate", "crane", "XXGXG");
+        assert!(result.is_match());
+
+        let result = explained("stone", "crane", "XXGXG");
+        assert_eq!(
+            result,
+            MatchResult::GreenMismatch {
+                position: 2,
+                letter: 'a'
+            }
+        );
+        assert_eq!(result.describe().as_deref(), Some("needs A at position 3"));
+    }
+
+    #[test]
+    fn test_explain_missing_yellow() {
+        let result = explained("world", "crane", "XXXXY");
+        assert_eq!(
+            result,
+            MatchResult::MissingYellow {
+                letter: 'e',
+                needed: 1
+            }
+        );
+        assert_eq!(result.describe().as_deref(), Some("needs E"));
+
+        // Two Es shown, one of them green, and the word has only that one
+        let result = explained("stone", "geese", "XYXXG");
+        assert_eq!(
+            result,
+            MatchResult::MissingYellow {
+                letter: 'e',
+                needed: 2
+            }
+        );
+        assert_eq!(result.describe().as_deref(), Some("needs 2 Es"));
+    }
+
+    #[test]
+    fn test_explain_yellow_in_place() {
+        assert!(explained("crane", "trace", "XGGYG").is_match());
+
+        let result = explained("crane", "crane", "YXXXX");
+        assert_eq!(
+            result,
+            MatchResult::YellowInPlace {
+                position: 0,
+                letter: 'c'
+            }
+        );
+        assert_eq!(
+            result.describe().as_deref(),
+            Some("needs C, but not at position 1")
+        );
+    }
+
+    #[test]
+    fn test_explain_gray_surplus() {
+        let result = explained("stone", "crane", "XXXXX");
+        assert_eq!(
+            result,
+            MatchResult::GraySurplus {
+                letter: 'n',
+                allowed: 0
+            }
+        );
+        assert_eq!(result.describe().as_deref(), Some("can't contain N"));
+
+        // One E green, the other gray: exactly one E
+        let result = explained("geese", "eerie", "XGXXG");
+        assert_eq!(
+            result,
+            MatchResult::GraySurplus {
+                letter: 'e',
+                allowed: 2
+            }
+        );
+        assert_eq!(
+            result.describe().as_deref(),
+            Some("can't have more than 2 Es")
+        );
+    }
+
+    #[test]
+    fn test_explain_agrees_with_matches() {
+        let words = [
+            "raise", "stone", "slate", "crane", "house", "apple", "world", "magic", "geese",
+            "eerie", "sassy",
+        ];
+        for guess in words {
+            for target in words {
+                let pattern = generate_feedback(target, guess);
+                for word in words {
+                    assert_eq!(
+                        explain_match(word, guess, &pattern).is_match(),
+                        matches(word, guess, &pattern),
+                        "{} against {} {:?}",
+                        word,
+                        guess,
+                        pattern
+                    );
+                }
+            }
+        }
+        assert_eq!(
+            explained("stone", "cranes", "XXXXXX"),
+            MatchResult::WrongLength
+        );
+    }
+
+    #[test]
+    fn test_explain_elimination_names_first_failed_guess() {
+        let guesses = [
+            Guess::new("raise".to_string(), generate_feedback("slate", "raise")),
+            Guess::new("crane".to_string(), generate_feedback("slate", "crane")),
+        ];
+        assert_eq!(explain_elimination("slate", &guesses), None);
+
+        // Fits RAISE's pattern, but CRANE showed no N
+        let reason = explain_elimination("stane", &guesses).unwrap();
+        assert_eq!(reason.to_string(), "fails guess 2 (CRANE): can't contain N");
+        let reason = explain_elimination("house", &guesses).unwrap();
+        assert_eq!(reason.guess_index, 0);
+        assert_eq!(
+            reason.to_string(),
+            format!(
+                "fails guess 1 (RAISE): {}",
+                reason.failure.describe().unwrap()
+            )
+        );
+    }
 }
@@ -1,31 +1,456 @@
-use anyhow::Result;
-use chrono::{DateTime, Utc};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
 use sqlx::sqlite::SqliteConnectOptions;
 
+use crate::{
+    tiers::{WordDifficulty, WordTier},
+    ui::history::{AbandonReason, DeviationMetric, HintKind},
+};
+
 #[derive(serde::Serialize, serde::Deserialize)]
 struct StoredGuess {
     word: String,
+    /// Superseded by the `guess_feedback` table but still written so older
+    /// builds can read the file; only used when a game has no rows there.
     feedback: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    timed_out: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    luck: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    target_rank: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+/// Schema changes applied in order on top of the base tables. The database's
+/// `user_version` pragma records how many have already run.
+const MIGRATIONS: &[&str] = &[
+    // 1: flag blitz games so stats can be split
+    "ALTER TABLE games ADD COLUMN blitz INTEGER NOT NULL DEFAULT 0",
+    // 2: time from game start to game over, for solve-time stats
+    "ALTER TABLE games ADD COLUMN duration_secs REAL",
+    // 3: words never to suggest again
+    "CREATE TABLE blacklist (word TEXT PRIMARY KEY)",
+    // 4: one row per letter of feedback so it can be queried, backfilled from guesses_json
+    "CREATE TABLE guess_feedback (
+         game_id INTEGER NOT NULL REFERENCES games(id),
+         guess_index INTEGER NOT NULL,
+         position INTEGER NOT NULL,
+         feedback TEXT NOT NULL CHECK (feedback IN ('G', 'Y', 'X')),
+         PRIMARY KEY (game_id, guess_index, position)
+     );
+     WITH RECURSIVE positions(position) AS (
+         SELECT 0 UNION ALL SELECT position + 1 FROM positions WHERE position < 31
+     )
+     INSERT INTO guess_feedback (game_id, guess_index, position, feedback)
+     SELECT games.id, guess.key, positions.position,
+            substr(json_extract(guess.value, '$.feedback'), positions.position + 1, 1)
+     FROM games, json_each(games.guesses_json) AS guess, positions
+     WHERE positions.position < length(json_extract(guess.value, '$.feedback'))",
+    // 5: named solver states to switch between
+    "CREATE TABLE solver_snapshots (
+         name TEXT PRIMARY KEY,
+         word_len INTEGER NOT NULL,
+         guesses_json TEXT NOT NULL,
+         created_at TEXT NOT NULL
+     )",
+    // 6: release that recorded each game and session, so stats can be compared across versions
+    "ALTER TABLE games ADD COLUMN app_version TEXT;
+     ALTER TABLE solver_sessions ADD COLUMN app_version TEXT",
+    // 7: null out the placeholder optimal word and bogus deviation recorded for unscorable pools
+    "UPDATE solver_sessions SET guesses_json = (
+         SELECT json_group_array(
+             CASE WHEN json_extract(guess.value, '$.optimal_word') = '-----'
+             THEN json_set(guess.value,
+                           '$.optimal_word', json('null'),
+                           '$.optimal_entropy', json('null'),
+                           '$.deviation', json('null'))
+             ELSE json(guess.value) END)
+         FROM json_each(solver_sessions.guesses_json) AS guess
+     )
+     WHERE guesses_json LIKE '%\"optimal_word\":\"-----\"%'",
+    // 8: the answer the user confirmed or typed after a session, and whether it matched
+    "ALTER TABLE solver_sessions ADD COLUMN resolved_word TEXT;
+     ALTER TABLE solver_sessions ADD COLUMN outcome TEXT NOT NULL DEFAULT 'completed'",
+    // 9: client-generated ids so writes replayed from the journal are applied once
+    "ALTER TABLE games ADD COLUMN uuid TEXT;
+     ALTER TABLE solver_sessions ADD COLUMN uuid TEXT;
+     CREATE UNIQUE INDEX games_uuid ON games (uuid);
+     CREATE UNIQUE INDEX solver_sessions_uuid ON solver_sessions (uuid)",
+    // 10: when and with which scorer a session's optimal words were last recomputed
+    "ALTER TABLE solver_sessions ADD COLUMN recomputed_at TEXT;
+     ALTER TABLE solver_sessions ADD COLUMN strategy TEXT",
+    // 11: difficulty each game was played at, so stats can be segmented
+    "ALTER TABLE games ADD COLUMN difficulty TEXT NOT NULL DEFAULT 'normal'",
+    // 12: why an abandoned game or session was left, so crashes aren't counted as choices
+    "ALTER TABLE games ADD COLUMN reason TEXT;
+     ALTER TABLE solver_sessions ADD COLUMN reason TEXT",
+    // 13: best guesses revealed mid-game, so assisted games can be told apart
+    "ALTER TABLE games ADD COLUMN hints_used INTEGER NOT NULL DEFAULT 0",
+    // 14: when a game was archived; archived games are left out of history until purged
+    "ALTER TABLE games ADD COLUMN archived_at TEXT",
+    // 15: gauntlets of games played back to back, and which one each game was part of
+    "CREATE TABLE gauntlets (
+         id INTEGER PRIMARY KEY,
+         started_at TEXT NOT NULL,
+         size INTEGER NOT NULL
+     );
+     ALTER TABLE games ADD COLUMN gauntlet_id INTEGER",
+    // 16: sessions whose resume prompt was turned down, so it isn't offered again
+    "ALTER TABLE solver_sessions ADD COLUMN resume_declined INTEGER NOT NULL DEFAULT 0",
+    // 17: how hard each game's answer was, and each answer list's scores, cached by list hash
+    "ALTER TABLE games ADD COLUMN word_score REAL;
+     ALTER TABLE games ADD COLUMN word_tier TEXT;
+     CREATE TABLE word_difficulty (
+         list_hash TEXT NOT NULL,
+         word TEXT NOT NULL,
+         score REAL NOT NULL,
+         PRIMARY KEY (list_hash, word)
+     )",
+    // 18: hint points each game allowed and the hints taken, so clean wins can be told apart
+    "ALTER TABLE games ADD COLUMN hint_budget INTEGER;
+     ALTER TABLE games ADD COLUMN hints TEXT NOT NULL DEFAULT ''",
+    // 19: what each solver guess's deviation was measured in; every earlier one was by score
+    "UPDATE solver_sessions SET guesses_json = (
+         SELECT json_group_array(
+             CASE WHEN json_extract(guess.value, '$.deviation') IS NOT NULL
+             THEN json_set(guess.value, '$.metric', 'heuristic')
+             ELSE json(guess.value) END)
+         FROM json_each(solver_sessions.guesses_json) AS guess
+     )
+     WHERE guesses_json LIKE '%\"deviation\":%'",
+    // 20: multi-board practice games, kept apart from single-board games and their stats
+    "CREATE TABLE multi_board_games (
+         id INTEGER PRIMARY KEY AUTOINCREMENT,
+         timestamp TEXT NOT NULL,
+         board_count INTEGER NOT NULL,
+         outcome TEXT NOT NULL,
+         reason TEXT,
+         guess_count INTEGER NOT NULL,
+         guesses_json TEXT NOT NULL,
+         boards_json TEXT NOT NULL,
+         difficulty TEXT NOT NULL,
+         duration_secs REAL,
+         app_version TEXT
+     )",
+    // 21: kids' games, kept apart from the other games and their stats
+    "CREATE TABLE kids_games (
+         id INTEGER PRIMARY KEY AUTOINCREMENT,
+         timestamp TEXT NOT NULL,
+         target_word TEXT NOT NULL,
+         outcome TEXT NOT NULL,
+         reason TEXT,
+         guess_count INTEGER NOT NULL,
+         guesses_json TEXT NOT NULL,
+         duration_secs REAL,
+         app_version TEXT
+     )",
+];
+
+/// Outcome of a game or session row written while it is still being played.
+/// Such rows are left out of history, and rows still open at the next start
+/// are closed as crashed.
+const IN_PROGRESS: &str = "in_progress";
+
+/// Filter for the games every stat counts: finished, and not closed by a
+/// crash, which says nothing about how the game was going.
+const COUNTED_GAME: &str =
+    "outcome != 'in_progress' AND NOT (outcome = 'abandoned' AND reason IS 'crashed')";
+
+/// [`COUNTED_GAME`], with archived games only if `include_archived`.
+fn counted_games(include_archived: bool) -> String {
+    let archived = if include_archived {
+        ""
+    } else {
+        " AND archived_at IS NULL"
+    };
+    format!("{}{}", COUNTED_GAME, archived)
 }
 
+/// Writes the database refused, one JSON command per line, next to the database file.
+pub const JOURNAL_FILE: &str = "pending-writes.jsonl";
+
+/// The schema version a database reaches once every migration has run.
+pub const SCHEMA_VERSION: i64 = MIGRATIONS.len() as i64;
+
+/// Version of this build, stamped on every saved game and solver session.
+pub const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Hours an abandoned solver session with no guesses is kept before startup
+/// purges it.
+pub const EMPTY_SESSION_HOURS: i64 = 1;
+
 #[derive(serde::Serialize, serde::Deserialize)]
 struct StoredSolverGuess {
     word: String,
     pool_before: usize,
     pool_after: usize,
     entropy: f64,
-    optimal_word: String,
-    optimal_entropy: f64,
-    deviation: f64,
+    /// Null when the guess had no optimal word to be measured against.
+    optimal_word: Option<String>,
+    optimal_entropy: Option<f64>,
+    deviation: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    metric: Option<DeviationMetric>,
+    /// Pattern the guess got; absent from sessions saved before it was kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    feedback: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+impl From<&crate::ui::history::solver_types::SolverGuess> for StoredSolverGuess {
+    fn from(g: &crate::ui::history::solver_types::SolverGuess) -> Self {
+        Self {
+            word: g.word.clone(),
+            pool_before: g.pool_size_before,
+            pool_after: g.pool_size_after,
+            entropy: g.entropy,
+            optimal_word: g.optimal_word.clone(),
+            optimal_entropy: g.optimal_entropy,
+            deviation: g.deviation_score,
+            metric: g.metric,
+            feedback: g.feedback.as_deref().map(crate::scoring::pattern_string),
+            note: g.note.clone(),
+        }
+    }
+}
+
+/// The guesses of a `solver_sessions.guesses_json` value; unreadable JSON has none.
+fn solver_guesses_from_json(json: &str) -> Vec<crate::ui::history::solver_types::SolverGuess> {
+    let stored: Vec<StoredSolverGuess> = serde_json::from_str(json).unwrap_or_default();
+    stored
+        .into_iter()
+        .map(|sg| crate::ui::history::solver_types::SolverGuess {
+            word: sg.word,
+            pool_size_before: sg.pool_before,
+            pool_size_after: sg.pool_after,
+            entropy: sg.entropy,
+            optimal_word: sg.optimal_word,
+            optimal_entropy: sg.optimal_entropy,
+            // Journaled writes from older builds can still lack the metric
+            metric: sg
+                .metric
+                .or(sg.deviation.map(|_| DeviationMetric::Heuristic)),
+            deviation_score: sg.deviation,
+            feedback: sg
+                .feedback
+                .and_then(|p| crate::solver::parse_pattern(&p).ok()),
+            note: sg.note,
+        })
+        .collect()
+}
+
+/// Mean guesses over the games or solver sessions recorded by one release.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionAverage {
+    /// `None` for rows saved before versions were recorded.
+    pub app_version: Option<String>,
+    pub count: usize,
+    pub average_guesses: f64,
+}
+
+/// Lifetime results of the unarchived games, for the quick stats line.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QuickStats {
+    pub wins: usize,
+    /// Games lost, given up or left; crashed games count as neither.
+    pub losses: usize,
+    /// Mean guesses per won game; `None` without a win.
+    pub average_guesses: Option<f64>,
+    /// Wins since the last game that wasn't one.
+    pub current_streak: usize,
+}
+
+impl QuickStats {
+    /// Wins as a percentage of the games counted; `None` without any.
+    pub fn win_rate(&self) -> Option<f64> {
+        let games = self.wins + self.losses;
+        (games > 0).then(|| self.wins as f64 * 100.0 / games as f64)
+    }
+}
+
+/// Results of the games sharing one key, e.g. a difficulty or an opener.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupStats<K> {
+    pub key: K,
+    pub games: usize,
+    pub wins: usize,
+    /// Mean guesses per won game; `None` without a win.
+    pub average_guesses: Option<f64>,
+}
+
+impl<K> GroupStats<K> {
+    /// Share of games won, as a percentage.
+    pub fn win_rate(&self) -> f64 {
+        self.wins as f64 * 100.0 / self.games.max(1) as f64
+    }
+}
+
+/// Results of the games played at one difficulty.
+pub type DifficultyStats = GroupStats<crate::settings::Difficulty>;
+
+/// Results of the multi-board games played with one number of boards; a win
+/// is a game with every board solved.
+pub type MultiBoardStats = GroupStats<usize>;
+
+/// Results of the games whose answers fell in one tier; `None` for games
+/// played before answers were scored.
+pub type TierStats = GroupStats<Option<WordTier>>;
+
+/// Games a first guess must open before it gets its own row in the by-opener
+/// stats; rarer openers are pooled together.
+pub const MIN_OPENER_GAMES: usize = 3;
+
+/// Results of the won and lost games opened with one word; `None` for the
+/// openers used fewer than [`MIN_OPENER_GAMES`] times, pooled.
+pub type OpenerStats = GroupStats<Option<String>>;
+
+impl OpenerStats {
+    /// The opener in capitals, or "other" for the pooled row.
+    pub fn label(&self) -> String {
+        self.key
+            .as_deref()
+            .map_or("other".to_string(), str::to_uppercase)
+    }
+}
+
+/// Games played on one local calendar day.
+pub type DayGames = GroupStats<NaiveDate>;
+
+/// A solver session left by quitting or a crash that can be picked up again.
+#[derive(Debug, Clone)]
+pub struct ResumableSession {
+    pub id: i64,
+    /// Write id the session keeps once resumed; `None` for rows saved before
+    /// write ids were kept.
+    pub uuid: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    pub guesses: Vec<crate::ui::history::solver_types::SolverGuess>,
+}
+
+/// A solver state saved under a name.
+#[derive(Debug, Clone)]
+pub struct SolverSnapshot {
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub state: crate::solver::SolverState,
+}
+
+/// A history write, kept serializable so a refused one can wait in the journal.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum WriteCommand {
+    Game {
+        uuid: String,
+        timestamp: String,
+        target_word: String,
+        outcome: String,
+        guess_count: i64,
+        guesses_json: String,
+        blitz: bool,
+        duration_secs: Option<f64>,
+        app_version: Option<String>,
+        /// Absent from journal lines written before difficulties were recorded.
+        #[serde(default)]
+        difficulty: crate::settings::Difficulty,
+        /// Set for abandoned games.
+        #[serde(default)]
+        reason: Option<AbandonReason>,
+        /// Absent from journal lines written before hints were counted.
+        #[serde(default)]
+        hints_used: i64,
+        /// Absent from journal lines written before hint budgets.
+        #[serde(default)]
+        hint_budget: Option<i64>,
+        #[serde(default)]
+        hints: Vec<HintKind>,
+        /// Absent from journal lines written before answers were scored.
+        #[serde(default)]
+        word_difficulty: Option<WordDifficulty>,
+    },
+    SolverSession {
+        uuid: String,
+        timestamp: String,
+        guess_count: i64,
+        guesses_json: String,
+        app_version: String,
+        /// Set for abandoned sessions.
+        #[serde(default)]
+        reason: Option<AbandonReason>,
+    },
+}
+
+/// A random version 4 UUID identifying one write.
+///
+/// A game or session that is checkpointed while in progress keeps one id for
+/// all its writes, so the final write replaces the open row.
+pub fn new_write_id() -> String {
+    let mut bytes: [u8; 16] = rand::random();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// The guesses of `record` as stored in `games.guesses_json`.
+fn stored_game_guesses(record: &crate::ui::history::GameRecord) -> Result<String> {
+    let stored: Vec<StoredGuess> = record
+        .guesses
+        .iter()
+        .map(|g| StoredGuess {
+            word: g.word.clone(),
+            feedback: crate::scoring::pattern_string(&g.feedback),
+            timed_out: g.timed_out,
+            luck: g.luck,
+            target_rank: g.target_rank,
+            note: g.note.clone(),
+        })
+        .collect();
+    Ok(serde_json::to_string(&stored)?)
+}
+
+/// Hints as stored in `games.hints`: their names, comma-separated.
+fn stored_hints(hints: &[HintKind]) -> String {
+    hints
+        .iter()
+        .map(|h| h.as_str())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// SQLite date modifier shifting a UTC timestamp into `offset`.
+fn offset_modifier(offset: FixedOffset) -> String {
+    format!("{:+} minutes", offset.local_minus_utc() / 60)
 }
 
 pub struct Database {
     pool: sqlx::SqlitePool,
     rt: tokio::runtime::Runtime,
+    /// The database file; none for in-memory databases.
+    path: Option<PathBuf>,
+    /// Where refused history writes wait for the next start; none for in-memory databases.
+    journal: Option<PathBuf>,
 }
 
 impl Database {
-    pub fn open(path: &str) -> Result<Self> {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
         let rt = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()?;
@@ -37,7 +462,12 @@ impl Database {
             sqlx::SqlitePool::connect_with(opts).await
         })?;
 
-        let db = Self { pool, rt };
+        let db = Self {
+            pool,
+            rt,
+            path: Some(path.to_path_buf()),
+            journal: Some(path.with_file_name(JOURNAL_FILE)),
+        };
         db.init_schema()?;
         Ok(db)
     }
@@ -47,13 +477,52 @@ impl Database {
             .enable_all()
             .build()?;
 
-        let pool = rt.block_on(async { sqlx::SqlitePool::connect("sqlite::memory:").await })?;
+        // Every connection to `sqlite::memory:` is a separate database, so keep exactly one
+        let pool = rt.block_on(async {
+            sqlx::sqlite::SqlitePoolOptions::new()
+                .max_connections(1)
+                .connect("sqlite::memory:")
+                .await
+        })?;
 
-        let db = Self { pool, rt };
+        let db = Self {
+            pool,
+            rt,
+            path: None,
+            journal: None,
+        };
         db.init_schema()?;
         Ok(db)
     }
 
+    /// Open an existing database without creating, migrating or writing to
+    /// it, e.g. to look inside a backup.
+    pub fn open_read_only(path: impl AsRef<Path>) -> Result<Self> {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        let pool = rt.block_on(async {
+            let opts = SqliteConnectOptions::new()
+                .filename(path.as_ref())
+                .read_only(true);
+            sqlx::SqlitePool::connect_with(opts).await
+        })?;
+
+        Ok(Self {
+            pool,
+            rt,
+            path: Some(path.as_ref().to_path_buf()),
+            journal: None,
+        })
+    }
+
+    /// Close the pool so every query fails, as a locked or broken database would.
+    #[cfg(test)]
+    pub fn close(&self) {
+        self.rt.block_on(self.pool.close());
+    }
+
     fn init_schema(&self) -> Result<()> {
         self.rt.block_on(async {
             sqlx::query(
@@ -80,77 +549,342 @@ impl Database {
             .execute(&self.pool)
             .await?;
 
+            sqlx::query(
+                "CREATE TABLE IF NOT EXISTS settings (
+                    key TEXT PRIMARY KEY,
+                    value TEXT NOT NULL
+                )",
+            )
+            .execute(&self.pool)
+            .await?;
+
+            Ok::<_, anyhow::Error>(())
+        })?;
+
+        self.migrate()
+    }
+
+    fn migrate(&self) -> Result<()> {
+        self.rt.block_on(async {
+            let version: i64 = sqlx::query_scalar("PRAGMA user_version")
+                .fetch_one(&self.pool)
+                .await?;
+
+            for (index, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+                let mut tx = self.pool.begin().await?;
+                sqlx::raw_sql(migration).execute(&mut *tx).await?;
+                sqlx::raw_sql(&format!("PRAGMA user_version = {}", index + 1))
+                    .execute(&mut *tx)
+                    .await?;
+                tx.commit().await?;
+            }
+
             Ok::<_, anyhow::Error>(())
         })
     }
 
-    pub fn save_game(
-        &self,
-        timestamp: DateTime<Utc>,
-        target_word: &str,
-        guesses: &[crate::solver::Guess],
-        outcome: &crate::ui::history::GameOutcome,
-    ) -> Result<()> {
-        let stored: Vec<StoredGuess> = guesses
-            .iter()
-            .map(|g| {
-                let feedback: String = g
-                    .feedback
-                    .iter()
-                    .map(|f| match f {
-                        crate::solver::Feedback::Green => 'G',
-                        crate::solver::Feedback::Yellow => 'Y',
-                        crate::solver::Feedback::Gray => 'X',
-                    })
-                    .collect();
-                StoredGuess {
-                    word: g.word.clone(),
-                    feedback,
-                }
-            })
-            .collect();
+    /// Number of migrations applied to this database.
+    pub fn schema_version(&self) -> Result<i64> {
+        let version = self.rt.block_on(async {
+            sqlx::query_scalar("PRAGMA user_version")
+                .fetch_one(&self.pool)
+                .await
+        })?;
+        Ok(version)
+    }
 
-        let guesses_json = serde_json::to_string(&stored)?;
-        let outcome_str = match outcome {
-            crate::ui::history::GameOutcome::Won { .. } => "won",
-            crate::ui::history::GameOutcome::Lost => "lost",
-        };
-        let timestamp_str = timestamp.to_rfc3339();
-        let guess_count = guesses.len() as i64;
+    /// Load every stored setting as raw JSON-encoded values keyed by name.
+    pub fn load_settings(&self) -> Result<Vec<(String, String)>> {
+        let rows = self.rt.block_on(async {
+            sqlx::query("SELECT key, value FROM settings")
+                .fetch_all(&self.pool)
+                .await
+        })?;
+
+        use sqlx::Row;
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get("key"), row.get("value")))
+            .collect())
+    }
 
+    /// Insert or replace a single setting.
+    pub fn save_setting(&self, key: &str, value: &str) -> Result<()> {
         self.rt.block_on(async {
             sqlx::query(
-                "INSERT INTO games (timestamp, target_word, outcome, guess_count, guesses_json)
-                 VALUES (?, ?, ?, ?, ?)",
+                "INSERT INTO settings (key, value) VALUES (?, ?)
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value",
             )
-            .bind(&timestamp_str)
-            .bind(target_word)
-            .bind(outcome_str)
-            .bind(guess_count)
-            .bind(&guesses_json)
+            .bind(key)
+            .bind(value)
             .execute(&self.pool)
             .await?;
             Ok::<_, anyhow::Error>(())
         })
     }
 
+    /// Load the permanently blacklisted words.
+    pub fn load_blacklist(&self) -> Result<Vec<String>> {
+        let words = self.rt.block_on(async {
+            sqlx::query_scalar("SELECT word FROM blacklist ORDER BY word")
+                .fetch_all(&self.pool)
+                .await
+        })?;
+        Ok(words)
+    }
+
+    pub fn add_to_blacklist(&self, word: &str) -> Result<()> {
+        self.rt.block_on(async {
+            sqlx::query("INSERT OR IGNORE INTO blacklist (word) VALUES (?)")
+                .bind(word)
+                .execute(&self.pool)
+                .await?;
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    /// Remove a word from the permanent blacklist; absent words are ignored.
+    pub fn remove_from_blacklist(&self, word: &str) -> Result<()> {
+        self.rt.block_on(async {
+            sqlx::query("DELETE FROM blacklist WHERE word = ?")
+                .bind(word)
+                .execute(&self.pool)
+                .await?;
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    pub fn save_game(&self, record: &crate::ui::history::GameRecord) -> Result<()> {
+        self.save_game_as(&new_write_id(), record)
+    }
+
+    /// Save a finished game under write id `uuid`, replacing its open row if
+    /// it was checkpointed.
+    pub fn save_game_as(&self, uuid: &str, record: &crate::ui::history::GameRecord) -> Result<()> {
+        let (outcome_str, reason) = match record.outcome {
+            crate::ui::history::GameOutcome::Won { .. } => ("won", None),
+            crate::ui::history::GameOutcome::Lost => ("lost", None),
+            crate::ui::history::GameOutcome::Forfeited => ("forfeited", None),
+            crate::ui::history::GameOutcome::Abandoned(reason) => ("abandoned", Some(reason)),
+        };
+
+        self.write(WriteCommand::Game {
+            uuid: uuid.to_string(),
+            timestamp: record.timestamp.to_rfc3339(),
+            target_word: record.target_word.clone(),
+            outcome: outcome_str.to_string(),
+            guess_count: record.guesses.len() as i64,
+            guesses_json: stored_game_guesses(record)?,
+            blitz: record.blitz,
+            duration_secs: record.duration_secs,
+            app_version: record.app_version.clone(),
+            difficulty: record.difficulty,
+            reason,
+            hints_used: record.hints_used as i64,
+            hint_budget: record.hint_budget.map(|b| b as i64),
+            hints: record.hints.clone(),
+            word_difficulty: record.word_difficulty,
+        })?;
+        Ok(())
+    }
+
+    /// Write the game so far as an open row under `uuid`, replacing any
+    /// earlier checkpoint; a game with no guesses yet has no row. Not
+    /// journaled, since the next checkpoint or the final save supersedes it.
+    pub fn checkpoint_game(
+        &self,
+        uuid: &str,
+        record: &crate::ui::history::GameRecord,
+    ) -> Result<()> {
+        let guesses_json = stored_game_guesses(record)?;
+        self.rt.block_on(async {
+            let mut tx = self.pool.begin().await?;
+            sqlx::query("DELETE FROM games WHERE uuid = ? AND outcome = ?")
+                .bind(uuid)
+                .bind(IN_PROGRESS)
+                .execute(&mut *tx)
+                .await?;
+            if !record.guesses.is_empty() {
+                sqlx::query(
+                    "INSERT INTO games (timestamp, target_word, outcome, guess_count, guesses_json, blitz, duration_secs, app_version, difficulty, hints_used, hint_budget, hints, word_score, word_tier, uuid)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                )
+                .bind(record.timestamp.to_rfc3339())
+                .bind(&record.target_word)
+                .bind(IN_PROGRESS)
+                .bind(record.guesses.len() as i64)
+                .bind(guesses_json)
+                .bind(record.blitz)
+                .bind(record.duration_secs)
+                .bind(&record.app_version)
+                .bind(record.difficulty.as_str())
+                .bind(record.hints_used as i64)
+                .bind(record.hint_budget.map(|b| b as i64))
+                .bind(stored_hints(&record.hints))
+                .bind(record.word_difficulty.map(|d| d.score))
+                .bind(record.word_difficulty.map(|d| d.tier.as_str()))
+                .bind(uuid)
+                .execute(&mut *tx)
+                .await?;
+            }
+            tx.commit().await?;
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    /// Every finished game that isn't archived, oldest first.
     pub fn load_games(&self) -> Result<Vec<crate::ui::history::GameRecord>> {
+        Ok(self
+            .load_games_with_ids(false)?
+            .into_iter()
+            .map(|(_, game)| game)
+            .collect())
+    }
+
+    /// Every finished game with its row id, oldest first, archived ones
+    /// included if `include_archived`.
+    pub fn load_games_with_ids(
+        &self,
+        include_archived: bool,
+    ) -> Result<Vec<(i64, crate::ui::history::GameRecord)>> {
+        self.query_games(include_archived, None, None)
+    }
+
+    /// Answers and guessed words of the games and solver sessions still in
+    /// progress, which the loaders leave out.
+    pub fn in_progress_words(&self) -> Result<Vec<String>> {
+        let (games, sessions) = self.rt.block_on(async {
+            let games: Vec<(String, String)> =
+                sqlx::query_as("SELECT target_word, guesses_json FROM games WHERE outcome = ?")
+                    .bind(IN_PROGRESS)
+                    .fetch_all(&self.pool)
+                    .await?;
+            let sessions: Vec<String> =
+                sqlx::query_scalar("SELECT guesses_json FROM solver_sessions WHERE outcome = ?")
+                    .bind(IN_PROGRESS)
+                    .fetch_all(&self.pool)
+                    .await?;
+            Ok::<_, sqlx::Error>((games, sessions))
+        })?;
+
+        let mut words = Vec::new();
+        for (target_word, guesses_json) in games {
+            words.push(target_word);
+            let guesses: Vec<StoredGuess> = serde_json::from_str(&guesses_json)?;
+            words.extend(guesses.into_iter().map(|g| g.word));
+        }
+        for guesses_json in sessions {
+            let guesses: Vec<StoredSolverGuess> = serde_json::from_str(&guesses_json)?;
+            for guess in guesses {
+                words.push(guess.word);
+                words.extend(guess.optimal_word);
+            }
+        }
+        Ok(words)
+    }
+
+    /// Number of finished games, archived ones included if `include_archived`.
+    pub fn count_games(&self, include_archived: bool) -> Result<usize> {
+        self.rt.block_on(async {
+            let count: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM games WHERE outcome != ? AND (? OR archived_at IS NULL)",
+            )
+            .bind(IN_PROGRESS)
+            .bind(include_archived)
+            .fetch_one(&self.pool)
+            .await?;
+            Ok::<_, anyhow::Error>(count as usize)
+        })
+    }
+
+    /// Number of finished solver sessions.
+    pub fn count_solver_sessions(&self) -> Result<usize> {
+        self.rt.block_on(async {
+            let count: i64 =
+                sqlx::query_scalar("SELECT COUNT(*) FROM solver_sessions WHERE outcome != ?")
+                    .bind(IN_PROGRESS)
+                    .fetch_one(&self.pool)
+                    .await?;
+            Ok::<_, anyhow::Error>(count as usize)
+        })
+    }
+
+    /// Up to `limit` finished games with their row ids, skipping the oldest
+    /// `offset`, in the order of [`load_games_with_ids`](Self::load_games_with_ids).
+    pub fn load_games_page(
+        &self,
+        include_archived: bool,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<(i64, crate::ui::history::GameRecord)>> {
+        self.query_games(include_archived, None, Some((offset, limit)))
+    }
+
+    /// Finished games with their row ids, oldest first: archived ones only if
+    /// `include_archived`, only those of `gauntlet_id` when it is set, and
+    /// only the `(offset, limit)` slice when `page` is set.
+    fn query_games(
+        &self,
+        include_archived: bool,
+        gauntlet_id: Option<i64>,
+        page: Option<(usize, usize)>,
+    ) -> Result<Vec<(i64, crate::ui::history::GameRecord)>> {
         use crate::ui::history::{GameGuess, GameOutcome, GameRecord};
+        use sqlx::Row;
 
-        let rows = self.rt.block_on(async {
-            sqlx::query("SELECT timestamp, target_word, outcome, guess_count, guesses_json FROM games ORDER BY timestamp ASC")
+        // SQLite reads a negative limit as no limit
+        let (offset, limit) = page.map_or((0, -1), |(offset, limit)| (offset as i64, limit as i64));
+        let (rows, feedback_rows) = self.rt.block_on(async {
+            let rows = sqlx::query("SELECT id, timestamp, target_word, outcome, guess_count, guesses_json, blitz, duration_secs, app_version, difficulty, reason, hints_used, hint_budget, hints, archived_at, word_score, word_tier FROM games WHERE outcome != ? AND (? OR archived_at IS NULL) AND (? IS NULL OR gauntlet_id = ?) ORDER BY timestamp ASC, id ASC LIMIT ? OFFSET ?")
+                .bind(IN_PROGRESS)
+                .bind(include_archived)
+                .bind(gauntlet_id)
+                .bind(gauntlet_id)
+                .bind(limit)
+                .bind(offset)
                 .fetch_all(&self.pool)
-                .await
+                .await?;
+            // Only the feedback of the games fetched
+            let ids: Vec<i64> = rows.iter().map(|row| row.get("id")).collect();
+            let feedback_rows = sqlx::query(
+                "SELECT game_id, guess_index, feedback FROM guess_feedback
+                 WHERE game_id IN (SELECT value FROM json_each(?))
+                 ORDER BY game_id, guess_index, position",
+            )
+            .bind(serde_json::to_string(&ids).unwrap_or_default())
+            .fetch_all(&self.pool)
+            .await?;
+            Ok::<_, sqlx::Error>((rows, feedback_rows))
         })?;
 
+        let mut patterns: HashMap<(i64, i64), String> = HashMap::new();
+        for row in feedback_rows {
+            let feedback: String = row.get("feedback");
+            patterns
+                .entry((row.get("game_id"), row.get("guess_index")))
+                .or_default()
+                .push_str(&feedback);
+        }
+
         let mut records = Vec::new();
         for row in rows {
-            use sqlx::Row;
+            let id: i64 = row.get("id");
             let timestamp_str: String = row.get("timestamp");
             let target_word: String = row.get("target_word");
             let outcome_str: String = row.get("outcome");
             let guess_count: i64 = row.get("guess_count");
             let guesses_json: String = row.get("guesses_json");
+            let blitz: bool = row.get("blitz");
+            let duration_secs: Option<f64> = row.get("duration_secs");
+            let app_version: Option<String> = row.get("app_version");
+            let difficulty: String = row.get("difficulty");
+            let reason: Option<String> = row.get("reason");
+            let hints_used: i64 = row.get("hints_used");
+            let hint_budget: Option<i64> = row.get("hint_budget");
+            let hints: String = row.get("hints");
+            let archived_at: Option<String> = row.get("archived_at");
+            let word_score: Option<f64> = row.get("word_score");
+            let word_tier: Option<String> = row.get("word_tier");
 
             let timestamp = DateTime::parse_from_rfc3339(&timestamp_str)
                 .map(|dt| dt.with_timezone(&Utc))
@@ -160,6 +894,10 @@ impl Database {
                 "won" => GameOutcome::Won {
                     guesses: guess_count as usize,
                 },
+                "forfeited" => GameOutcome::Forfeited,
+                "abandoned" => {
+                    GameOutcome::Abandoned(AbandonReason::from_stored(reason.as_deref()))
+                }
                 _ => GameOutcome::Lost,
             };
 
@@ -168,9 +906,10 @@ impl Database {
 
             let guesses: Vec<GameGuess> = stored_guesses
                 .into_iter()
-                .map(|sg| {
-                    let feedback: Vec<crate::solver::Feedback> = sg
-                        .feedback
+                .enumerate()
+                .map(|(i, sg)| {
+                    let pattern = patterns.remove(&(id, i as i64)).unwrap_or(sg.feedback);
+                    let feedback: Vec<crate::solver::Feedback> = pattern
                         .chars()
                         .map(|c| {
                             crate::solver::Feedback::try_from(c)
@@ -180,106 +919,2833 @@ impl Database {
                     GameGuess {
                         word: sg.word,
                         feedback,
+                        timed_out: sg.timed_out,
+                        luck: sg.luck,
+                        target_rank: sg.target_rank,
+                        note: sg.note,
                     }
                 })
                 .collect();
 
-            records.push(GameRecord {
-                timestamp,
-                target_word,
-                guesses,
-                outcome,
-            });
+            records.push((
+                id,
+                GameRecord {
+                    timestamp,
+                    target_word,
+                    guesses,
+                    outcome,
+                    blitz,
+                    duration_secs,
+                    app_version,
+                    difficulty: crate::settings::Difficulty::from_stored(&difficulty),
+                    hints_used: hints_used as usize,
+                    hint_budget: hint_budget.map(|b| b as usize),
+                    hints: hints.split(',').filter_map(HintKind::from_stored).collect(),
+                    archived_at: archived_at
+                        .and_then(|at| DateTime::parse_from_rfc3339(&at).ok())
+                        .map(|at| at.with_timezone(&Utc)),
+                    word_difficulty: word_score
+                        .zip(word_tier.as_deref().and_then(WordTier::from_stored))
+                        .map(|(score, tier)| WordDifficulty { score, tier }),
+                },
+            ));
         }
 
         Ok(records)
     }
 
-    pub fn save_solver_session(
-        &self,
-        timestamp: DateTime<Utc>,
-        guesses: &[crate::ui::history::solver_types::SolverGuess],
-    ) -> Result<()> {
-        let stored: Vec<StoredSolverGuess> = guesses
+    /// Save `state` under `name`, replacing any snapshot already called that.
+    pub fn save_snapshot(&self, name: &str, state: &crate::solver::SolverState) -> Result<()> {
+        let stored: Vec<StoredGuess> = state
+            .guesses()
             .iter()
-            .map(|g| StoredSolverGuess {
+            .map(|g| StoredGuess {
                 word: g.word.clone(),
-                pool_before: g.pool_size_before,
-                pool_after: g.pool_size_after,
-                entropy: g.entropy,
-                optimal_word: g.optimal_word.clone(),
-                optimal_entropy: g.optimal_entropy,
-                deviation: g.deviation_score,
+                feedback: crate::scoring::pattern_string(&g.feedback),
+                timed_out: false,
+                luck: None,
+                target_rank: None,
+                note: None,
             })
             .collect();
-
         let guesses_json = serde_json::to_string(&stored)?;
-        let timestamp_str = timestamp.to_rfc3339();
-        let guess_count = guesses.len() as i64;
 
         self.rt.block_on(async {
             sqlx::query(
-                "INSERT INTO solver_sessions (timestamp, guess_count, guesses_json)
-                 VALUES (?, ?, ?)",
+                "INSERT OR REPLACE INTO solver_snapshots (name, word_len, guesses_json, created_at)
+                 VALUES (?, ?, ?, ?)",
             )
-            .bind(&timestamp_str)
-            .bind(guess_count)
+            .bind(name)
+            .bind(state.word_len() as i64)
             .bind(&guesses_json)
+            .bind(Utc::now().to_rfc3339())
             .execute(&self.pool)
             .await?;
             Ok::<_, anyhow::Error>(())
         })
     }
 
-    pub fn load_solver_sessions(
-        &self,
-    ) -> Result<Vec<crate::ui::history::solver_types::SolverSession>> {
-        use crate::ui::history::solver_types::{SolverGuess, SolverOutcome, SolverSession};
+    /// Load every snapshot, most recently saved first.
+    pub fn load_snapshots(&self) -> Result<Vec<SolverSnapshot>> {
+        use crate::solver::{Guess, SolverState, parse_pattern};
 
         let rows = self.rt.block_on(async {
             sqlx::query(
-                "SELECT timestamp, guess_count, guesses_json FROM solver_sessions ORDER BY timestamp ASC",
+                "SELECT name, word_len, guesses_json, created_at FROM solver_snapshots
+                 ORDER BY created_at DESC",
             )
             .fetch_all(&self.pool)
             .await
         })?;
 
-        let mut sessions = Vec::new();
+        let mut snapshots = Vec::new();
         for row in rows {
             use sqlx::Row;
-            let timestamp_str: String = row.get("timestamp");
-            let guess_count: i64 = row.get("guess_count");
+            let name: String = row.get("name");
+            let word_len: i64 = row.get("word_len");
             let guesses_json: String = row.get("guesses_json");
+            let created_at: String = row.get("created_at");
 
-            let timestamp = DateTime::parse_from_rfc3339(&timestamp_str)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now());
-
-            let stored_guesses: Vec<StoredSolverGuess> =
-                serde_json::from_str(&guesses_json).unwrap_or_default();
-
-            let guesses: Vec<SolverGuess> = stored_guesses
-                .into_iter()
-                .map(|sg| SolverGuess {
-                    word: sg.word,
-                    pool_size_before: sg.pool_before,
-                    pool_size_after: sg.pool_after,
-                    entropy: sg.entropy,
-                    optimal_word: sg.optimal_word,
-                    optimal_entropy: sg.optimal_entropy,
-                    deviation_score: sg.deviation,
-                })
-                .collect();
+            let mut state = SolverState::new(word_len as usize);
+            for stored in serde_json::from_str::<Vec<StoredGuess>>(&guesses_json)? {
+                let feedback = parse_pattern(&stored.feedback)?;
+                state.add_guess(Guess::new(stored.word, feedback))?;
+            }
 
-            sessions.push(SolverSession {
-                timestamp,
-                guesses,
-                outcome: SolverOutcome::Completed {
-                    guesses: guess_count as usize,
-                },
+            snapshots.push(SolverSnapshot {
+                name,
+                created_at: DateTime::parse_from_rfc3339(&created_at)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                state,
             });
         }
 
-        Ok(sessions)
+        Ok(snapshots)
+    }
+
+    pub fn delete_snapshot(&self, name: &str) -> Result<()> {
+        self.rt.block_on(async {
+            sqlx::query("DELETE FROM solver_snapshots WHERE name = ?")
+                .bind(name)
+                .execute(&self.pool)
+                .await?;
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    /// Fraction of games whose first guess was green at each position,
+    /// archived games counted only if `include_archived`.
+    pub fn first_guess_green_rates(&self, include_archived: bool) -> Result<Vec<f64>> {
+        let rates = self.rt.block_on(async {
+            sqlx::query_scalar(
+                "SELECT AVG(feedback = 'G') FROM guess_feedback
+                 WHERE guess_index = 0
+                   AND game_id IN (SELECT id FROM games WHERE ? OR archived_at IS NULL)
+                 GROUP BY position ORDER BY position",
+            )
+            .bind(include_archived)
+            .fetch_all(&self.pool)
+            .await
+        })?;
+        Ok(rates)
+    }
+
+    /// Mean number of green letters for the first, second, ... guess of a
+    /// game, archived games counted only if `include_archived`.
+    pub fn greens_per_guess(&self, include_archived: bool) -> Result<Vec<f64>> {
+        let means = self.rt.block_on(async {
+            sqlx::query_scalar(
+                "SELECT CAST(SUM(feedback = 'G') AS REAL) / COUNT(DISTINCT game_id)
+                 FROM guess_feedback
+                 WHERE game_id IN (SELECT id FROM games WHERE ? OR archived_at IS NULL)
+                 GROUP BY guess_index ORDER BY guess_index",
+            )
+            .bind(include_archived)
+            .fetch_all(&self.pool)
+            .await
+        })?;
+        Ok(means)
+    }
+
+    /// Mean guesses per won game for each release, oldest release first,
+    /// archived games counted only if `include_archived`.
+    pub fn game_averages_by_version(&self, include_archived: bool) -> Result<Vec<VersionAverage>> {
+        let archived = if include_archived {
+            ""
+        } else {
+            "AND archived_at IS NULL"
+        };
+        self.averages_by_version(&format!(
+            "SELECT app_version, COUNT(*) AS count, AVG(guess_count) AS average
+             FROM games WHERE outcome = 'won' {}
+             GROUP BY app_version ORDER BY MIN(timestamp)",
+            archived
+        ))
+    }
+
+    /// Mean guesses per completed solver session for each release, oldest release first.
+    pub fn session_averages_by_version(&self) -> Result<Vec<VersionAverage>> {
+        self.averages_by_version(
+            "SELECT app_version, COUNT(*) AS count, AVG(guess_count) AS average
+             FROM solver_sessions WHERE outcome NOT IN ('in_progress', 'abandoned')
+             GROUP BY app_version ORDER BY MIN(timestamp)",
+        )
+    }
+
+    /// Wins, losses, mean winning guesses and the current win streak over
+    /// the finished, unarchived games, counted as the history stats count them.
+    pub fn quick_stats(&self) -> Result<QuickStats> {
+        let counted = counted_games(false);
+        let (totals, streak) = self.rt.block_on(async {
+            let totals = sqlx::query(&format!(
+                "SELECT COALESCE(SUM(outcome = 'won'), 0) AS wins,
+                        COALESCE(SUM(outcome != 'won'), 0) AS losses,
+                        AVG(CASE WHEN outcome = 'won' THEN guess_count END) AS average
+                 FROM games WHERE {}",
+                counted
+            ))
+            .fetch_one(&self.pool)
+            .await?;
+            let streak: i64 = sqlx::query_scalar(&format!(
+                "SELECT COUNT(*) FROM games WHERE outcome = 'won' AND {0}
+                   AND timestamp > COALESCE(
+                       (SELECT MAX(timestamp) FROM games WHERE outcome != 'won' AND {0}), '')",
+                counted
+            ))
+            .fetch_one(&self.pool)
+            .await?;
+            Ok::<_, anyhow::Error>((totals, streak))
+        })?;
+
+        use sqlx::Row;
+        Ok(QuickStats {
+            wins: totals.get::<i64, _>("wins") as usize,
+            losses: totals.get::<i64, _>("losses") as usize,
+            average_guesses: totals.get("average"),
+            current_streak: streak as usize,
+        })
+    }
+
+    /// Games, wins and mean winning guesses of the rows of `table` matching
+    /// `filter`, grouped by `column`, with each key read by `key`.
+    fn grouped_stats<K>(
+        &self,
+        table: &str,
+        column: &str,
+        filter: &str,
+        key: impl Fn(&sqlx::sqlite::SqliteRow) -> K,
+    ) -> Result<Vec<GroupStats<K>>> {
+        let rows = self.rt.block_on(async {
+            sqlx::query(&format!(
+                "SELECT {} AS key, COUNT(*) AS games,
+                        SUM(outcome = 'won') AS wins,
+                        AVG(CASE WHEN outcome = 'won' THEN guess_count END) AS average
+                 FROM {} WHERE {}
+                 GROUP BY key",
+                column, table, filter
+            ))
+            .fetch_all(&self.pool)
+            .await
+        })?;
+
+        use sqlx::Row;
+        Ok(rows
+            .iter()
+            .map(|row| GroupStats {
+                key: key(row),
+                games: row.get::<i64, _>("games") as usize,
+                wins: row.get::<i64, _>("wins") as usize,
+                average_guesses: row.get("average"),
+            })
+            .collect())
+    }
+
+    /// Games, wins and mean winning guesses at each difficulty, easiest
+    /// first, archived games counted only if `include_archived`.
+    pub fn game_stats_by_difficulty(&self, include_archived: bool) -> Result<Vec<DifficultyStats>> {
+        use sqlx::Row;
+        let filter = counted_games(include_archived);
+        let mut stats = self.grouped_stats("games", "difficulty", &filter, |row| {
+            crate::settings::Difficulty::from_stored(row.get("key"))
+        })?;
+        stats.sort_by_key(|s| s.key);
+        Ok(stats)
+    }
+
+    /// Games, wins and mean winning guesses by the tier of the answer, easiest
+    /// first, archived games counted only if `include_archived`. Games played
+    /// before answers were scored come last, with no tier.
+    pub fn game_stats_by_tier(&self, include_archived: bool) -> Result<Vec<TierStats>> {
+        use sqlx::Row;
+        let filter = counted_games(include_archived);
+        let mut stats = self.grouped_stats("games", "word_tier", &filter, |row| {
+            row.get::<Option<String>, _>("key")
+                .as_deref()
+                .and_then(WordTier::from_stored)
+        })?;
+        stats.sort_by_key(|s| (s.key.is_none(), s.key));
+        Ok(stats)
+    }
+
+    /// Difficulty scores cached for the answer list hashed to `list_hash`.
+    pub fn load_word_scores(&self, list_hash: &str) -> Result<Vec<(String, f64)>> {
+        let rows = self.rt.block_on(async {
+            sqlx::query_as::<_, (String, f64)>(
+                "SELECT word, score FROM word_difficulty WHERE list_hash = ? ORDER BY word",
+            )
+            .bind(list_hash)
+            .fetch_all(&self.pool)
+            .await
+        })?;
+        Ok(rows)
+    }
+
+    /// Cache `scores` for the answer list hashed to `list_hash`, replacing
+    /// any already cached for it.
+    pub fn save_word_scores(&self, list_hash: &str, scores: &[(String, f64)]) -> Result<()> {
+        self.rt.block_on(async {
+            let mut tx = self.pool.begin().await?;
+            sqlx::query("DELETE FROM word_difficulty WHERE list_hash = ?")
+                .bind(list_hash)
+                .execute(&mut *tx)
+                .await?;
+            for (word, score) in scores {
+                sqlx::query(
+                    "INSERT INTO word_difficulty (list_hash, word, score) VALUES (?, ?, ?)",
+                )
+                .bind(list_hash)
+                .bind(word)
+                .bind(score)
+                .execute(&mut *tx)
+                .await?;
+            }
+            tx.commit().await?;
+            Ok::<_, sqlx::Error>(())
+        })?;
+        Ok(())
+    }
+
+    /// Games, wins and mean winning guesses of the won and lost games by
+    /// first guess, most played first, archived games counted only if
+    /// `include_archived`. Openers used fewer than [`MIN_OPENER_GAMES`] times
+    /// share one trailing row.
+    pub fn game_stats_by_opener(&self, include_archived: bool) -> Result<Vec<OpenerStats>> {
+        let rows = self.rt.block_on(async {
+            // A blitz turn that timed out played no word, so the opener is the first that did
+            sqlx::query(&format!(
+                "SELECT opener, COUNT(*) AS games, SUM(outcome = 'won') AS wins,
+                        SUM(CASE WHEN outcome = 'won' THEN guess_count ELSE 0 END) AS won_guesses
+                 FROM (SELECT outcome, guess_count,
+                              (SELECT json_extract(turn.value, '$.word')
+                               FROM json_each(guesses_json) AS turn
+                               WHERE NOT COALESCE(json_extract(turn.value, '$.timed_out'), 0)
+                               ORDER BY turn.key LIMIT 1) AS opener
+                       FROM games WHERE outcome IN ('won', 'lost') AND {})
+                 WHERE COALESCE(opener, '') != ''
+                 GROUP BY opener",
+                counted_games(include_archived)
+            ))
+            .fetch_all(&self.pool)
+            .await
+        })?;
+
+        use sqlx::Row;
+        let mut stats = Vec::new();
+        let (mut other_games, mut other_wins, mut other_guesses) = (0, 0, 0);
+        for row in rows {
+            let games = row.get::<i64, _>("games") as usize;
+            let wins = row.get::<i64, _>("wins") as usize;
+            let won_guesses = row.get::<i64, _>("won_guesses") as usize;
+            if games < MIN_OPENER_GAMES {
+                other_games += games;
+                other_wins += wins;
+                other_guesses += won_guesses;
+                continue;
+            }
+            stats.push(OpenerStats {
+                key: Some(row.get("opener")),
+                games,
+                wins,
+                average_guesses: (wins > 0).then(|| won_guesses as f64 / wins as f64),
+            });
+        }
+        stats.sort_by(|a, b| b.games.cmp(&a.games).then_with(|| a.key.cmp(&b.key)));
+        if other_games > 0 {
+            stats.push(OpenerStats {
+                key: None,
+                games: other_games,
+                wins: other_wins,
+                average_guesses: (other_wins > 0).then(|| other_guesses as f64 / other_wins as f64),
+            });
+        }
+        Ok(stats)
+    }
+
+    /// Games, wins and mean winning guesses for each day from `first` to
+    /// `last` inclusive on which an unarchived game was played, with days
+    /// taken in `offset`.
+    pub fn games_by_local_date(
+        &self,
+        first: NaiveDate,
+        last: NaiveDate,
+        offset: FixedOffset,
+    ) -> Result<Vec<DayGames>> {
+        let rows = self.rt.block_on(async {
+            sqlx::query(&format!(
+                "SELECT date(timestamp, ?1) AS day, COUNT(*) AS games,
+                        SUM(outcome = 'won') AS wins,
+                        AVG(CASE WHEN outcome = 'won' THEN guess_count END) AS average
+                 FROM games WHERE date(timestamp, ?1) BETWEEN ?2 AND ?3 AND {}
+                 GROUP BY day ORDER BY day",
+                counted_games(false)
+            ))
+            .bind(offset_modifier(offset))
+            .bind(first.to_string())
+            .bind(last.to_string())
+            .fetch_all(&self.pool)
+            .await
+        })?;
+
+        use sqlx::Row;
+        rows.into_iter()
+            .map(|row| {
+                Ok(DayGames {
+                    key: row.get::<String, _>("day").parse()?,
+                    games: row.get::<i64, _>("games") as usize,
+                    wins: row.get::<i64, _>("wins") as usize,
+                    average_guesses: row.get("average"),
+                })
+            })
+            .collect()
+    }
+
+    /// Number of solver sessions on each day from `first` to `last`
+    /// inclusive that had any, with days taken in `offset`.
+    pub fn sessions_by_local_date(
+        &self,
+        first: NaiveDate,
+        last: NaiveDate,
+        offset: FixedOffset,
+    ) -> Result<Vec<(NaiveDate, usize)>> {
+        let rows = self.rt.block_on(async {
+            sqlx::query(
+                "SELECT date(timestamp, ?1) AS day, COUNT(*) AS sessions
+                 FROM solver_sessions WHERE date(timestamp, ?1) BETWEEN ?2 AND ?3 AND outcome != ?4
+                 GROUP BY day ORDER BY day",
+            )
+            .bind(offset_modifier(offset))
+            .bind(first.to_string())
+            .bind(last.to_string())
+            .bind(IN_PROGRESS)
+            .fetch_all(&self.pool)
+            .await
+        })?;
+
+        use sqlx::Row;
+        rows.into_iter()
+            .map(|row| {
+                Ok((
+                    row.get::<String, _>("day").parse()?,
+                    row.get::<i64, _>("sessions") as usize,
+                ))
+            })
+            .collect()
+    }
+
+    fn averages_by_version(&self, sql: &str) -> Result<Vec<VersionAverage>> {
+        let rows = self
+            .rt
+            .block_on(async { sqlx::query(sql).fetch_all(&self.pool).await })?;
+
+        use sqlx::Row;
+        Ok(rows
+            .into_iter()
+            .map(|row| VersionAverage {
+                app_version: row.get("app_version"),
+                count: row.get::<i64, _>("count") as usize,
+                average_guesses: row.get("average"),
+            })
+            .collect())
+    }
+
+    /// Save a completed solver session, returning its id.
+    pub fn save_solver_session(
+        &self,
+        timestamp: DateTime<Utc>,
+        guesses: &[crate::ui::history::solver_types::SolverGuess],
+    ) -> Result<i64> {
+        self.save_solver_session_as(&new_write_id(), timestamp, guesses, None)
+    }
+
+    /// Save a solver session under write id `uuid`, replacing its open row
+    /// if it was checkpointed; `reason` marks it abandoned. Returns its id.
+    pub fn save_solver_session_as(
+        &self,
+        uuid: &str,
+        timestamp: DateTime<Utc>,
+        guesses: &[crate::ui::history::solver_types::SolverGuess],
+        reason: Option<AbandonReason>,
+    ) -> Result<i64> {
+        let stored: Vec<StoredSolverGuess> = guesses.iter().map(StoredSolverGuess::from).collect();
+
+        self.write(WriteCommand::SolverSession {
+            uuid: uuid.to_string(),
+            timestamp: timestamp.to_rfc3339(),
+            guess_count: guesses.len() as i64,
+            guesses_json: serde_json::to_string(&stored)?,
+            app_version: APP_VERSION.to_string(),
+            reason,
+        })
+    }
+
+    /// Write the session so far as an open row under `uuid`, replacing any
+    /// earlier checkpoint; a session with no guesses has no row.
+    pub fn checkpoint_solver_session(
+        &self,
+        uuid: &str,
+        timestamp: DateTime<Utc>,
+        guesses: &[crate::ui::history::solver_types::SolverGuess],
+    ) -> Result<()> {
+        let stored: Vec<StoredSolverGuess> = guesses.iter().map(StoredSolverGuess::from).collect();
+        let guesses_json = serde_json::to_string(&stored)?;
+        self.rt.block_on(async {
+            let mut tx = self.pool.begin().await?;
+            sqlx::query("DELETE FROM solver_sessions WHERE uuid = ? AND outcome = ?")
+                .bind(uuid)
+                .bind(IN_PROGRESS)
+                .execute(&mut *tx)
+                .await?;
+            if !guesses.is_empty() {
+                sqlx::query(
+                    "INSERT INTO solver_sessions (timestamp, guess_count, guesses_json, app_version, outcome, uuid)
+                     VALUES (?, ?, ?, ?, ?, ?)",
+                )
+                .bind(timestamp.to_rfc3339())
+                .bind(guesses.len() as i64)
+                .bind(guesses_json)
+                .bind(APP_VERSION)
+                .bind(IN_PROGRESS)
+                .bind(uuid)
+                .execute(&mut *tx)
+                .await?;
+            }
+            tx.commit().await?;
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    /// Close every game and session still open from before `boot` as
+    /// abandoned by a crash, returning how many were closed.
+    pub fn close_stale(&self, boot: DateTime<Utc>) -> Result<usize> {
+        let boot = boot.to_rfc3339();
+        self.rt.block_on(async {
+            let mut tx = self.pool.begin().await?;
+            let mut closed = 0;
+            for table in ["games", "solver_sessions"] {
+                closed += sqlx::query(&format!(
+                    "UPDATE {} SET outcome = 'abandoned', reason = ?
+                     WHERE outcome = ? AND timestamp < ?",
+                    table
+                ))
+                .bind(AbandonReason::Crashed.as_str())
+                .bind(IN_PROGRESS)
+                .bind(&boot)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected() as usize;
+            }
+            tx.commit().await?;
+            Ok::<_, anyhow::Error>(closed)
+        })
+    }
+
+    /// Write a consistent copy of the database to `dest`, which must not exist.
+    pub fn backup_to(&self, dest: &Path) -> Result<()> {
+        let dest = dest
+            .to_str()
+            .ok_or_else(|| anyhow!("backup path {} is not UTF-8", dest.display()))?;
+        self.rt.block_on(async {
+            sqlx::query("VACUUM INTO ?")
+                .bind(dest)
+                .execute(&self.pool)
+                .await
+        })?;
+        Ok(())
+    }
+
+    /// When the newest game or solver session was played, archived and
+    /// unfinished ones included; `None` for an empty history.
+    pub fn latest_activity(&self) -> Result<Option<DateTime<Utc>>> {
+        let latest: Option<String> = self.rt.block_on(async {
+            sqlx::query_scalar(
+                "SELECT MAX(timestamp) FROM (
+                     SELECT timestamp FROM games UNION ALL SELECT timestamp FROM solver_sessions
+                 )",
+            )
+            .fetch_one(&self.pool)
+            .await
+        })?;
+        latest
+            .map(|t| Ok(DateTime::parse_from_rfc3339(&t)?.with_timezone(&Utc)))
+            .transpose()
+    }
+
+    /// The directory holding the database file; none for in-memory databases.
+    pub fn data_dir(&self) -> Option<&Path> {
+        self.journal.as_deref().and_then(Path::parent)
+    }
+
+    /// The database file; none for in-memory databases.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Writes waiting in the journal for the database to accept them.
+    pub fn pending_writes(&self) -> usize {
+        self.journal
+            .as_ref()
+            .and_then(|journal| fs::read_to_string(journal).ok())
+            .map_or(0, |text| {
+                text.lines().filter(|line| !line.trim().is_empty()).count()
+            })
+    }
+
+    /// Apply `command`, or queue it in the journal if the database refuses it.
+    fn write(&self, command: WriteCommand) -> Result<i64> {
+        let error = match self.apply(&command) {
+            Ok(id) => return Ok(id),
+            Err(e) => e,
+        };
+        let Some(journal) = &self.journal else {
+            return Err(error);
+        };
+
+        let line = serde_json::to_string(&command)?;
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(journal)
+            .and_then(|mut file| writeln!(file, "{}", line))
+            .map_err(|e| anyhow!("{:#}; also failed to queue it: {}", error, e))?;
+        Err(anyhow!(
+            "{:#}; queued in {} for retry",
+            error,
+            journal.display()
+        ))
+    }
+
+    /// Run `command` unless its uuid is already stored, returning the row id.
+    fn apply(&self, command: &WriteCommand) -> Result<i64> {
+        self.rt.block_on(async {
+            let mut tx = self.pool.begin().await?;
+
+            let (table, uuid) = match command {
+                WriteCommand::Game { uuid, .. } => ("games", uuid),
+                WriteCommand::SolverSession { uuid, .. } => ("solver_sessions", uuid),
+            };
+            // The finished game or session replaces its checkpoint
+            sqlx::query(&format!(
+                "DELETE FROM {} WHERE uuid = ? AND outcome = ?",
+                table
+            ))
+            .bind(uuid)
+            .bind(IN_PROGRESS)
+            .execute(&mut *tx)
+            .await?;
+            let existing: Option<i64> =
+                sqlx::query_scalar(&format!("SELECT id FROM {} WHERE uuid = ?", table))
+                    .bind(uuid)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            if let Some(id) = existing {
+                return Ok(id);
+            }
+
+            let id = match command {
+                WriteCommand::Game {
+                    uuid,
+                    timestamp,
+                    target_word,
+                    outcome,
+                    guess_count,
+                    guesses_json,
+                    blitz,
+                    duration_secs,
+                    app_version,
+                    difficulty,
+                    reason,
+                    hints_used,
+                    hint_budget,
+                    hints,
+                    word_difficulty,
+                } => {
+                    let game_id = sqlx::query(
+                        "INSERT INTO games (timestamp, target_word, outcome, guess_count, guesses_json, blitz, duration_secs, app_version, difficulty, reason, hints_used, hint_budget, hints, word_score, word_tier, uuid)
+                         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                    )
+                    .bind(timestamp)
+                    .bind(target_word)
+                    .bind(outcome)
+                    .bind(guess_count)
+                    .bind(guesses_json)
+                    .bind(blitz)
+                    .bind(duration_secs)
+                    .bind(app_version)
+                    .bind(difficulty.as_str())
+                    .bind(reason.map(AbandonReason::as_str))
+                    .bind(hints_used)
+                    .bind(hint_budget)
+                    .bind(stored_hints(hints))
+                    .bind(word_difficulty.map(|d| d.score))
+                    .bind(word_difficulty.map(|d| d.tier.as_str()))
+                    .bind(uuid)
+                    .execute(&mut *tx)
+                    .await?
+                    .last_insert_rowid();
+
+                    let stored: Vec<StoredGuess> = serde_json::from_str(guesses_json)?;
+                    for (guess_index, guess) in stored.iter().enumerate() {
+                        for (position, feedback) in guess.feedback.chars().enumerate() {
+                            sqlx::query(
+                                "INSERT INTO guess_feedback (game_id, guess_index, position, feedback)
+                                 VALUES (?, ?, ?, ?)",
+                            )
+                            .bind(game_id)
+                            .bind(guess_index as i64)
+                            .bind(position as i64)
+                            .bind(feedback.to_string())
+                            .execute(&mut *tx)
+                            .await?;
+                        }
+                    }
+                    game_id
+                }
+                WriteCommand::SolverSession {
+                    uuid,
+                    timestamp,
+                    guess_count,
+                    guesses_json,
+                    app_version,
+                    reason,
+                } => sqlx::query(
+                    "INSERT INTO solver_sessions (timestamp, guess_count, guesses_json, app_version, outcome, reason, uuid)
+                     VALUES (?, ?, ?, ?, ?, ?, ?)",
+                )
+                .bind(timestamp)
+                .bind(guess_count)
+                .bind(guesses_json)
+                .bind(app_version)
+                .bind(if reason.is_some() { "abandoned" } else { "completed" })
+                .bind(reason.map(AbandonReason::as_str))
+                .bind(uuid)
+                .execute(&mut *tx)
+                .await?
+                .last_insert_rowid(),
+            };
+
+            tx.commit().await?;
+            Ok::<_, anyhow::Error>(id)
+        })
+    }
+
+    /// Apply the writes queued in the journal, keeping any that still fail.
+    ///
+    /// Returns how many were applied. Writes are matched on their uuid, so one
+    /// that reached the database before the journal was rewritten is not
+    /// stored twice.
+    pub fn replay_pending(&self) -> Result<usize> {
+        let Some(journal) = &self.journal else {
+            return Ok(0);
+        };
+        let text = match fs::read_to_string(journal) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut replayed = 0;
+        let mut remaining = Vec::new();
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let applied = serde_json::from_str::<WriteCommand>(line)
+                .map_err(anyhow::Error::from)
+                .and_then(|command| self.apply(&command));
+            match applied {
+                Ok(_) => replayed += 1,
+                Err(e) => {
+                    tracing::warn!("Pending write not replayed: {:#}", e);
+                    remaining.push(line);
+                }
+            }
+        }
+
+        if remaining.is_empty() {
+            fs::remove_file(journal)?;
+        } else {
+            fs::write(journal, remaining.join("\n") + "\n")?;
+        }
+        Ok(replayed)
+    }
+
+    /// Record the answer the user reported for session `id` and whether the
+    /// solver had converged on it.
+    pub fn resolve_solver_session(
+        &self,
+        id: i64,
+        resolved_word: &str,
+        matched: bool,
+    ) -> Result<()> {
+        let outcome_str = if matched {
+            "completed"
+        } else {
+            "completed_mismatch"
+        };
+
+        self.rt.block_on(async {
+            sqlx::query("UPDATE solver_sessions SET resolved_word = ?, outcome = ? WHERE id = ?")
+                .bind(resolved_word)
+                .bind(outcome_str)
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    /// Save a finished multi-board game, returning its row id.
+    pub fn save_multi_board_game(
+        &self,
+        record: &crate::ui::history::MultiBoardRecord,
+    ) -> Result<i64> {
+        use crate::ui::history::GameOutcome;
+
+        let (outcome, reason) = match record.outcome {
+            GameOutcome::Won { .. } => ("won", None),
+            GameOutcome::Lost => ("lost", None),
+            GameOutcome::Forfeited => ("forfeited", None),
+            GameOutcome::Abandoned(reason) => ("abandoned", Some(reason.as_str())),
+        };
+        let guesses_json = serde_json::to_string(&record.guesses)?;
+        let boards_json = serde_json::to_string(&record.boards)?;
+        self.rt.block_on(async {
+            let id = sqlx::query(
+                "INSERT INTO multi_board_games (timestamp, board_count, outcome, reason, guess_count, guesses_json, boards_json, difficulty, duration_secs, app_version)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(record.timestamp.to_rfc3339())
+            .bind(record.boards.len() as i64)
+            .bind(outcome)
+            .bind(reason)
+            .bind(record.guesses.len() as i64)
+            .bind(guesses_json)
+            .bind(boards_json)
+            .bind(record.difficulty.as_str())
+            .bind(record.duration_secs)
+            .bind(&record.app_version)
+            .execute(&self.pool)
+            .await?
+            .last_insert_rowid();
+            Ok::<_, anyhow::Error>(id)
+        })
+    }
+
+    /// Every multi-board game, oldest first.
+    pub fn load_multi_board_games(&self) -> Result<Vec<crate::ui::history::MultiBoardRecord>> {
+        use crate::ui::history::{GameOutcome, MultiBoardRecord};
+        use sqlx::Row;
+
+        let rows = self.rt.block_on(async {
+            sqlx::query(
+                "SELECT timestamp, outcome, reason, guesses_json, boards_json, difficulty, duration_secs, app_version
+                 FROM multi_board_games ORDER BY timestamp ASC, id ASC",
+            )
+            .fetch_all(&self.pool)
+            .await
+        })?;
+
+        let mut records = Vec::new();
+        for row in rows {
+            let guesses: Vec<String> = serde_json::from_str(row.get("guesses_json"))?;
+            let outcome = match row.get::<String, _>("outcome").as_str() {
+                "won" => GameOutcome::Won {
+                    guesses: guesses.len(),
+                },
+                "forfeited" => GameOutcome::Forfeited,
+                "abandoned" => GameOutcome::Abandoned(AbandonReason::from_stored(
+                    row.get::<Option<String>, _>("reason").as_deref(),
+                )),
+                _ => GameOutcome::Lost,
+            };
+            records.push(MultiBoardRecord {
+                timestamp: DateTime::parse_from_rfc3339(row.get("timestamp"))?.with_timezone(&Utc),
+                guesses,
+                boards: serde_json::from_str(row.get("boards_json"))?,
+                outcome,
+                difficulty: crate::settings::Difficulty::from_stored(row.get("difficulty")),
+                duration_secs: row.get("duration_secs"),
+                app_version: row.get("app_version"),
+            });
+        }
+        Ok(records)
+    }
+
+    /// Games, wins and mean winning guesses of multi-board games by the
+    /// number of boards, fewest first.
+    pub fn multi_board_stats(&self) -> Result<Vec<MultiBoardStats>> {
+        use sqlx::Row;
+        let mut stats =
+            self.grouped_stats("multi_board_games", "board_count", COUNTED_GAME, |row| {
+                row.get::<i64, _>("key") as usize
+            })?;
+        stats.sort_by_key(|s| s.key);
+        Ok(stats)
+    }
+
+    /// Save a finished kids' game, returning its row id.
+    pub fn save_kids_game(&self, record: &crate::ui::history::KidsGameRecord) -> Result<i64> {
+        use crate::ui::history::GameOutcome;
+
+        let (outcome, reason) = match record.outcome {
+            GameOutcome::Won { .. } => ("won", None),
+            GameOutcome::Lost => ("lost", None),
+            GameOutcome::Forfeited => ("forfeited", None),
+            GameOutcome::Abandoned(reason) => ("abandoned", Some(reason.as_str())),
+        };
+        let guesses_json = serde_json::to_string(&record.guesses)?;
+        self.rt.block_on(async {
+            let id = sqlx::query(
+                "INSERT INTO kids_games (timestamp, target_word, outcome, reason, guess_count, guesses_json, duration_secs, app_version)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(record.timestamp.to_rfc3339())
+            .bind(&record.target_word)
+            .bind(outcome)
+            .bind(reason)
+            .bind(record.guesses.len() as i64)
+            .bind(guesses_json)
+            .bind(record.duration_secs)
+            .bind(&record.app_version)
+            .execute(&self.pool)
+            .await?
+            .last_insert_rowid();
+            Ok::<_, anyhow::Error>(id)
+        })
+    }
+
+    /// Every kids' game, oldest first.
+    pub fn load_kids_games(&self) -> Result<Vec<crate::ui::history::KidsGameRecord>> {
+        use crate::ui::history::{GameOutcome, KidsGameRecord};
+        use sqlx::Row;
+
+        let rows = self.rt.block_on(async {
+            sqlx::query(
+                "SELECT timestamp, target_word, outcome, reason, guesses_json, duration_secs, app_version
+                 FROM kids_games ORDER BY timestamp ASC, id ASC",
+            )
+            .fetch_all(&self.pool)
+            .await
+        })?;
+
+        let mut records = Vec::new();
+        for row in rows {
+            let guesses: Vec<String> = serde_json::from_str(row.get("guesses_json"))?;
+            let outcome = match row.get::<String, _>("outcome").as_str() {
+                "won" => GameOutcome::Won {
+                    guesses: guesses.len(),
+                },
+                "forfeited" => GameOutcome::Forfeited,
+                "abandoned" => GameOutcome::Abandoned(AbandonReason::from_stored(
+                    row.get::<Option<String>, _>("reason").as_deref(),
+                )),
+                _ => GameOutcome::Lost,
+            };
+            records.push(KidsGameRecord {
+                timestamp: DateTime::parse_from_rfc3339(row.get("timestamp"))?.with_timezone(&Utc),
+                target_word: row.get("target_word"),
+                guesses,
+                outcome,
+                duration_secs: row.get("duration_secs"),
+                app_version: row.get("app_version"),
+            });
+        }
+        Ok(records)
+    }
+
+    /// Start a gauntlet of `size` games, returning its id.
+    pub fn create_gauntlet(&self, size: usize) -> Result<i64> {
+        self.rt.block_on(async {
+            let id = sqlx::query("INSERT INTO gauntlets (started_at, size) VALUES (?, ?)")
+                .bind(Utc::now().to_rfc3339())
+                .bind(size as i64)
+                .execute(&self.pool)
+                .await?
+                .last_insert_rowid();
+            Ok::<_, anyhow::Error>(id)
+        })
+    }
+
+    /// Mark the game saved under write id `uuid` as part of gauntlet `gauntlet_id`.
+    /// Fails when no game has that id, e.g. while its save is still journaled.
+    pub fn add_to_gauntlet(&self, gauntlet_id: i64, uuid: &str) -> Result<()> {
+        self.rt.block_on(async {
+            let linked = sqlx::query("UPDATE games SET gauntlet_id = ? WHERE uuid = ?")
+                .bind(gauntlet_id)
+                .bind(uuid)
+                .execute(&self.pool)
+                .await?
+                .rows_affected();
+            if linked == 0 {
+                return Err(anyhow!("no saved game has write id {}", uuid));
+            }
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    /// The finished games of gauntlet `gauntlet_id` with their row ids, in play order.
+    pub fn load_gauntlet_games(
+        &self,
+        gauntlet_id: i64,
+    ) -> Result<Vec<(i64, crate::ui::history::GameRecord)>> {
+        self.query_games(true, Some(gauntlet_id), None)
+    }
+
+    /// Archive game `id` now, or bring it back if `archived` is false.
+    pub fn set_game_archived(&self, id: i64, archived: bool) -> Result<()> {
+        let archived_at = archived.then(|| Utc::now().to_rfc3339());
+        self.rt.block_on(async {
+            sqlx::query("UPDATE games SET archived_at = ? WHERE id = ?")
+                .bind(archived_at)
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+
+    /// Delete the games archived before `cutoff`, with their feedback rows,
+    /// returning how many were deleted.
+    pub fn purge_archived(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let cutoff = cutoff.to_rfc3339();
+        self.rt.block_on(async {
+            let mut tx = self.pool.begin().await?;
+            sqlx::query(
+                "DELETE FROM guess_feedback WHERE game_id IN
+                     (SELECT id FROM games WHERE archived_at < ?)",
+            )
+            .bind(&cutoff)
+            .execute(&mut *tx)
+            .await?;
+            let purged = sqlx::query("DELETE FROM games WHERE archived_at < ?")
+                .bind(&cutoff)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected() as usize;
+            tx.commit().await?;
+            Ok::<_, anyhow::Error>(purged)
+        })
+    }
+
+    /// Delete abandoned solver sessions with no guesses started before
+    /// `cutoff`, returning how many were deleted. Older releases wrote one
+    /// each time solver mode was entered.
+    pub fn purge_empty_sessions(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let cutoff = cutoff.to_rfc3339();
+        self.rt.block_on(async {
+            let purged = sqlx::query(
+                "DELETE FROM solver_sessions
+                 WHERE guess_count = 0 AND outcome = 'abandoned' AND timestamp < ?",
+            )
+            .bind(&cutoff)
+            .execute(&self.pool)
+            .await?
+            .rows_affected() as usize;
+            Ok::<_, anyhow::Error>(purged)
+        })
+    }
+
+    pub fn load_solver_sessions(
+        &self,
+    ) -> Result<Vec<crate::ui::history::solver_types::SolverSession>> {
+        Ok(self
+            .load_solver_sessions_with_ids()?
+            .into_iter()
+            .map(|(_, session)| session)
+            .collect())
+    }
+
+    /// Every solver session with its row id, oldest first.
+    pub fn load_solver_sessions_with_ids(
+        &self,
+    ) -> Result<Vec<(i64, crate::ui::history::solver_types::SolverSession)>> {
+        use crate::ui::history::solver_types::{SolverOutcome, SolverSession};
+
+        let rows = self.rt.block_on(async {
+            sqlx::query(
+                "SELECT id, timestamp, guess_count, guesses_json, outcome, resolved_word, reason
+                 FROM solver_sessions WHERE outcome != ? ORDER BY timestamp ASC",
+            )
+            .bind(IN_PROGRESS)
+            .fetch_all(&self.pool)
+            .await
+        })?;
+
+        let mut sessions = Vec::new();
+        for row in rows {
+            use sqlx::Row;
+            let id: i64 = row.get("id");
+            let timestamp_str: String = row.get("timestamp");
+            let guess_count: i64 = row.get("guess_count");
+            let guesses_json: String = row.get("guesses_json");
+            let outcome_str: String = row.get("outcome");
+            let resolved_word: Option<String> = row.get("resolved_word");
+            let reason: Option<String> = row.get("reason");
+
+            let timestamp = DateTime::parse_from_rfc3339(&timestamp_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now());
+
+            let guesses = guess_count as usize;
+            let outcome = match outcome_str.as_str() {
+                "completed_mismatch" => SolverOutcome::CompletedMismatch { guesses },
+                "abandoned" => SolverOutcome::Abandoned {
+                    guesses,
+                    reason: AbandonReason::from_stored(reason.as_deref()),
+                },
+                _ => SolverOutcome::Completed { guesses },
+            };
+
+            sessions.push((
+                id,
+                SolverSession {
+                    timestamp,
+                    guesses: solver_guesses_from_json(&guesses_json),
+                    outcome,
+                    resolved_word,
+                },
+            ));
+        }
+
+        Ok(sessions)
+    }
+
+    /// The latest solver session if it was left by quitting or a crash since
+    /// `since`, has a pattern for every guess, and its resume wasn't declined.
+    pub fn resumable_solver_session(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Option<ResumableSession>> {
+        let row = self.rt.block_on(async {
+            sqlx::query(
+                "SELECT id, uuid, timestamp, guesses_json, outcome, reason, resume_declined
+                 FROM solver_sessions WHERE outcome != ?
+                 ORDER BY timestamp DESC, id DESC LIMIT 1",
+            )
+            .bind(IN_PROGRESS)
+            .fetch_optional(&self.pool)
+            .await
+        })?;
+
+        use sqlx::Row;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let outcome: String = row.get("outcome");
+        let reason = AbandonReason::from_stored(row.get::<Option<String>, _>("reason").as_deref());
+        if outcome != "abandoned"
+            || reason == AbandonReason::Switched
+            || row.get::<i64, _>("resume_declined") != 0
+        {
+            return Ok(None);
+        }
+        let Ok(timestamp) = DateTime::parse_from_rfc3339(row.get::<&str, _>("timestamp")) else {
+            return Ok(None);
+        };
+        let timestamp = timestamp.with_timezone(&Utc);
+        let guesses = solver_guesses_from_json(row.get("guesses_json"));
+        if timestamp < since || guesses.is_empty() || guesses.iter().any(|g| g.feedback.is_none()) {
+            return Ok(None);
+        }
+
+        Ok(Some(ResumableSession {
+            id: row.get("id"),
+            uuid: row.get("uuid"),
+            timestamp,
+            guesses,
+        }))
+    }
+
+    /// Open abandoned session `id` again under write id `uuid`, so it is
+    /// checkpointed and saved like a session that never stopped.
+    pub fn reopen_solver_session(&self, id: i64, uuid: &str) -> Result<()> {
+        self.rt.block_on(async {
+            sqlx::query(
+                "UPDATE solver_sessions SET outcome = ?, reason = NULL, uuid = ? WHERE id = ?",
+            )
+            .bind(IN_PROGRESS)
+            .bind(uuid)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+        })?;
+        Ok(())
+    }
+
+    /// Keep session `id` abandoned and stop offering to resume it.
+    pub fn decline_solver_resume(&self, id: i64) -> Result<()> {
+        self.rt.block_on(async {
+            sqlx::query("UPDATE solver_sessions SET resume_declined = 1 WHERE id = ?")
+                .bind(id)
+                .execute(&self.pool)
+                .await
+        })?;
+        Ok(())
+    }
+
+    /// Replace the stored guesses of each session in `updates` in one
+    /// transaction, marking them as recomputed now with `strategy`.
+    pub fn update_solver_session_guesses(
+        &self,
+        updates: &[(i64, Vec<crate::ui::history::solver_types::SolverGuess>)],
+        strategy: &str,
+    ) -> Result<()> {
+        let recomputed_at = Utc::now().to_rfc3339();
+        let rows = updates
+            .iter()
+            .map(|(id, guesses)| {
+                let stored: Vec<StoredSolverGuess> =
+                    guesses.iter().map(StoredSolverGuess::from).collect();
+                Ok((*id, serde_json::to_string(&stored)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.rt.block_on(async {
+            let mut tx = self.pool.begin().await?;
+            for (id, guesses_json) in rows {
+                sqlx::query(
+                    "UPDATE solver_sessions SET guesses_json = ?, recomputed_at = ?, strategy = ?
+                     WHERE id = ?",
+                )
+                .bind(guesses_json)
+                .bind(&recomputed_at)
+                .bind(strategy)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            }
+            tx.commit().await?;
+            Ok::<_, anyhow::Error>(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_applied_on_open() {
+        let db = Database::open_memory().unwrap();
+
+        assert_eq!(db.schema_version().unwrap(), MIGRATIONS.len() as i64);
+    }
+
+    #[test]
+    fn test_migrations_upgrade_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.db");
+        let path = path.to_str().unwrap();
+
+        // Lay down the schema as it was before any migrations existed
+        let db = Database::open(path).unwrap();
+        db.rt
+            .block_on(
+                sqlx::raw_sql(
+                    "DROP TABLE games;
+                     DROP TABLE solver_sessions;
+                     DROP TABLE blacklist;
+                     DROP TABLE guess_feedback;
+                     DROP TABLE solver_snapshots;
+                     DROP TABLE gauntlets;
+                     DROP TABLE word_difficulty;
+                     CREATE TABLE games (
+                         id INTEGER PRIMARY KEY AUTOINCREMENT,
+                         timestamp TEXT NOT NULL,
+                         target_word TEXT NOT NULL,
+                         outcome TEXT NOT NULL,
+                         guess_count INTEGER NOT NULL,
+                         guesses_json TEXT NOT NULL
+                     );
+                     CREATE TABLE solver_sessions (
+                         id INTEGER PRIMARY KEY AUTOINCREMENT,
+                         timestamp TEXT NOT NULL,
+                         guess_count INTEGER NOT NULL,
+                         guesses_json TEXT NOT NULL
+                     );
+                     INSERT INTO games (timestamp, target_word, outcome, guess_count, guesses_json)
+                     VALUES ('2024-01-01T00:00:00Z', 'stone', 'won', 3, '[]');
+                     DROP TABLE multi_board_games;
+                     DROP TABLE kids_games;
+                     PRAGMA user_version = 0;",
+                )
+                .execute(&db.pool),
+            )
+            .unwrap();
+        drop(db);
+
+        let db = Database::open(path).unwrap();
+        assert_eq!(db.schema_version().unwrap(), MIGRATIONS.len() as i64);
+
+        let games = db.load_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert!(!games[0].blitz);
+        assert_eq!(games[0].app_version, None);
+    }
+
+    #[test]
+    fn test_feedback_backfilled_from_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.db");
+        let path = path.to_str().unwrap();
+
+        // Roll back to just before the guess_feedback migration, with JSON-only rows
+        let db = Database::open(path).unwrap();
+        db.rt
+            .block_on(
+                sqlx::raw_sql(
+                    r#"DROP INDEX games_uuid;
+                     ALTER TABLE games DROP COLUMN hint_budget;
+                     ALTER TABLE games DROP COLUMN hints;
+                     DROP INDEX solver_sessions_uuid;
+                     ALTER TABLE solver_sessions DROP COLUMN recomputed_at;
+                     ALTER TABLE solver_sessions DROP COLUMN strategy;
+                     ALTER TABLE games DROP COLUMN word_score;
+                     ALTER TABLE games DROP COLUMN word_tier;
+                     DROP TABLE word_difficulty;
+                     ALTER TABLE solver_sessions DROP COLUMN resume_declined;
+                     ALTER TABLE games DROP COLUMN gauntlet_id;
+                     DROP TABLE gauntlets;
+                     ALTER TABLE games DROP COLUMN archived_at;
+                     ALTER TABLE games DROP COLUMN hints_used;
+                     ALTER TABLE games DROP COLUMN reason;
+                     ALTER TABLE solver_sessions DROP COLUMN reason;
+                     ALTER TABLE games DROP COLUMN difficulty;
+                     ALTER TABLE games DROP COLUMN uuid;
+                     ALTER TABLE solver_sessions DROP COLUMN uuid;
+                     DROP TABLE guess_feedback;
+                     DROP TABLE solver_snapshots;
+                     ALTER TABLE games DROP COLUMN app_version;
+                     ALTER TABLE solver_sessions DROP COLUMN app_version;
+                     ALTER TABLE solver_sessions DROP COLUMN resolved_word;
+                     ALTER TABLE solver_sessions DROP COLUMN outcome;
+                     INSERT INTO games (id, timestamp, target_word, outcome, guess_count, guesses_json)
+                     VALUES
+                         (7, '2024-01-01T00:00:00Z', 'stone', 'won', 2,
+                          '[{"word":"crane","feedback":"XXXGG"},{"word":"stone","feedback":"GGGGG"}]'),
+                         (9, '2024-01-02T00:00:00Z', 'apple', 'lost', 2,
+                          '[{"word":"","feedback":"","timed_out":true},{"word":"slate","feedback":"XYGXG"}]');
+                     DROP TABLE multi_board_games;
+                     DROP TABLE kids_games;
+                     PRAGMA user_version = 3;"#,
+                )
+                .execute(&db.pool),
+            )
+            .unwrap();
+        drop(db);
+
+        let db = Database::open(path).unwrap();
+        let rows: Vec<(i64, i64, i64, String)> = db
+            .rt
+            .block_on(
+                sqlx::query_as(
+                    "SELECT game_id, guess_index, position, feedback FROM guess_feedback
+                     ORDER BY game_id, guess_index, position",
+                )
+                .fetch_all(&db.pool),
+            )
+            .unwrap();
+
+        let patterns: Vec<(i64, i64, String)> = rows
+            .chunk_by(|a, b| (a.0, a.1) == (b.0, b.1))
+            .map(|letters| {
+                assert!(letters.iter().map(|r| r.2).eq(0..letters.len() as i64));
+                let pattern = letters.iter().map(|r| r.3.as_str()).collect();
+                (letters[0].0, letters[0].1, pattern)
+            })
+            .collect();
+        assert_eq!(
+            patterns,
+            [
+                (7, 0, "XXXGG".to_string()),
+                (7, 1, "GGGGG".to_string()),
+                (9, 1, "XYGXG".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_solver_session() {
+        use crate::ui::history::{SolverOutcome, solver_types::SolverGuess};
+
+        let db = Database::open_memory().unwrap();
+        let guess = SolverGuess {
+            word: "crane".to_string(),
+            pool_size_before: 8,
+            pool_size_after: 1,
+            entropy: 0.0,
+            optimal_word: Some("crane".to_string()),
+            optimal_entropy: Some(0.0),
+            deviation_score: Some(0.0),
+            metric: Some(DeviationMetric::Entropy),
+            feedback: None,
+            note: None,
+        };
+        let guesses = [guess];
+        let first = db.save_solver_session(Utc::now(), &guesses).unwrap();
+        let second = db.save_solver_session(Utc::now(), &guesses).unwrap();
+        db.save_solver_session(Utc::now(), &guesses).unwrap();
+
+        db.resolve_solver_session(first, "crane", true).unwrap();
+        db.resolve_solver_session(second, "slate", false).unwrap();
+
+        let sessions = db.load_solver_sessions().unwrap();
+        let resolved: Vec<_> = sessions
+            .iter()
+            .map(|s| (s.resolved_word.as_deref(), s.outcome.clone()))
+            .collect();
+        assert_eq!(
+            resolved,
+            [
+                (Some("crane"), SolverOutcome::Completed { guesses: 1 }),
+                (
+                    Some("slate"),
+                    SolverOutcome::CompletedMismatch { guesses: 1 }
+                ),
+                (None, SolverOutcome::Completed { guesses: 1 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_solver_session_guesses_marks_strategy() {
+        use crate::ui::history::solver_types::SolverGuess;
+
+        let db = Database::open_memory().unwrap();
+        let mut guess = SolverGuess {
+            word: "crane".to_string(),
+            pool_size_before: 8,
+            pool_size_after: 1,
+            entropy: 0.0,
+            optimal_word: None,
+            optimal_entropy: None,
+            deviation_score: None,
+            metric: None,
+            feedback: Some(vec![crate::solver::Feedback::Green; 5]),
+            note: None,
+        };
+        let id = db
+            .save_solver_session(Utc::now(), std::slice::from_ref(&guess))
+            .unwrap();
+        let untouched = db
+            .save_solver_session(Utc::now(), std::slice::from_ref(&guess))
+            .unwrap();
+
+        guess.optimal_word = Some("slate".to_string());
+        db.update_solver_session_guesses(&[(id, vec![guess])], "letter-frequency")
+            .unwrap();
+
+        let sessions = db.load_solver_sessions_with_ids().unwrap();
+        let optimal: Vec<_> = sessions
+            .iter()
+            .map(|(id, s)| (*id, s.guesses[0].optimal_word.as_deref()))
+            .collect();
+        assert_eq!(optimal, [(id, Some("slate")), (untouched, None)]);
+        assert!(sessions[0].1.guesses[0].feedback.is_some());
+
+        let marks: Vec<(Option<String>, bool)> = db.rt.block_on(async {
+            use sqlx::Row;
+            sqlx::query("SELECT strategy, recomputed_at FROM solver_sessions ORDER BY id")
+                .fetch_all(&db.pool)
+                .await
+                .unwrap()
+                .iter()
+                .map(|row| {
+                    let recomputed: Option<String> = row.get("recomputed_at");
+                    (row.get("strategy"), recomputed.is_some())
+                })
+                .collect()
+        });
+        assert_eq!(
+            marks,
+            [(Some("letter-frequency".to_string()), true), (None, false)]
+        );
+    }
+
+    #[test]
+    fn test_refused_writes_queued_and_replayed() {
+        use crate::solver::parse_pattern;
+        use crate::ui::history::{GameGuess, GameOutcome, GameRecord, solver_types::SolverGuess};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.db");
+        let path = path.to_str().unwrap();
+        let journal = dir.path().join(JOURNAL_FILE);
+
+        // A closed pool refuses every write, as a locked or broken database would
+        let db = Database::open(path).unwrap();
+        db.rt.block_on(db.pool.close());
+        let session = [SolverGuess {
+            word: "crane".to_string(),
+            pool_size_before: 8,
+            pool_size_after: 1,
+            entropy: 0.0,
+            optimal_word: Some("crane".to_string()),
+            optimal_entropy: Some(0.0),
+            deviation_score: Some(0.0),
+            metric: Some(DeviationMetric::Entropy),
+            feedback: None,
+            note: None,
+        }];
+        let err = db.save_solver_session(Utc::now(), &session).unwrap_err();
+        assert!(err.to_string().contains("queued in"));
+        db.save_game(&GameRecord {
+            timestamp: Utc::now(),
+            target_word: "stone".to_string(),
+            guesses: vec![GameGuess::new(
+                "stone".to_string(),
+                parse_pattern("GGGGG").unwrap(),
+            )],
+            outcome: GameOutcome::Won { guesses: 1 },
+            blitz: false,
+            duration_secs: None,
+            app_version: None,
+            difficulty: crate::settings::Difficulty::Normal,
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        })
+        .unwrap_err();
+        assert_eq!(db.pending_writes(), 2);
+        drop(db);
+        assert_eq!(fs::read_to_string(&journal).unwrap().lines().count(), 2);
+
+        let db = Database::open(path).unwrap();
+        assert_eq!(db.replay_pending().unwrap(), 2);
+        assert!(!journal.exists());
+        assert_eq!(db.pending_writes(), 0);
+        assert_eq!(db.count_solver_sessions().unwrap(), 1);
+
+        let games = db.load_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(
+            games[0].guesses[0].feedback,
+            parse_pattern("GGGGG").unwrap()
+        );
+        let feedback_rows: i64 = db
+            .rt
+            .block_on(sqlx::query_scalar("SELECT COUNT(*) FROM guess_feedback").fetch_one(&db.pool))
+            .unwrap();
+        assert_eq!(feedback_rows, 5);
+        let sessions = db.load_solver_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].guesses[0].word, "crane");
+        assert_eq!(db.replay_pending().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_replayed_write_applied_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.db");
+        let path = path.to_str().unwrap();
+        let journal = dir.path().join(JOURNAL_FILE);
+
+        let db = Database::open(path).unwrap();
+        let command = WriteCommand::SolverSession {
+            uuid: new_write_id(),
+            timestamp: Utc::now().to_rfc3339(),
+            guess_count: 0,
+            guesses_json: "[]".to_string(),
+            app_version: APP_VERSION.to_string(),
+            reason: None,
+        };
+        let id = db.apply(&command).unwrap();
+
+        // The write landed but the journal still holds it, plus a line that can't parse
+        let line = serde_json::to_string(&command).unwrap();
+        fs::write(&journal, format!("{}\nnot json\n", line)).unwrap();
+
+        assert_eq!(db.replay_pending().unwrap(), 1);
+        assert_eq!(db.apply(&command).unwrap(), id);
+        assert_eq!(db.load_solver_sessions().unwrap().len(), 1);
+        assert_eq!(fs::read_to_string(&journal).unwrap(), "not json\n");
+    }
+
+    #[test]
+    fn test_solver_guess_pattern_round_trips() {
+        use crate::{solver::parse_pattern, ui::history::solver_types::SolverGuess};
+
+        let db = Database::open_memory().unwrap();
+        let guess = |word: &str, pattern: Option<&str>| SolverGuess {
+            word: word.to_string(),
+            pool_size_before: 10,
+            pool_size_after: 2,
+            entropy: 1.0,
+            optimal_word: None,
+            optimal_entropy: None,
+            deviation_score: None,
+            metric: None,
+            feedback: pattern.map(|p| parse_pattern(p).unwrap()),
+            note: None,
+        };
+        db.save_solver_session(
+            Utc::now(),
+            &[guess("crane", Some("XYXXG")), guess("stone", None)],
+        )
+        .unwrap();
+
+        let sessions = db.load_solver_sessions().unwrap();
+        let guesses = &sessions[0].guesses;
+        assert_eq!(guesses[0].feedback, parse_pattern("XYXXG").ok());
+        assert_eq!(guesses[1].feedback, None);
+    }
+
+    #[test]
+    fn test_placeholder_optimal_word_backfilled() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.db");
+        let path = path.to_str().unwrap();
+
+        // A session saved before empty pools were skipped, with one placeholder row
+        let db = Database::open(path).unwrap();
+        db.rt
+            .block_on(
+                sqlx::raw_sql(
+                    r#"INSERT INTO solver_sessions (timestamp, guess_count, guesses_json)
+                     VALUES ('2024-01-01T00:00:00Z', 2, '[
+                         {"word":"crane","pool_before":8,"pool_after":0,"entropy":0.0,
+                          "optimal_word":"raise","optimal_entropy":1.5,"deviation":-1.5},
+                         {"word":"stone","pool_before":0,"pool_after":0,"entropy":0.0,
+                          "optimal_word":"-----","optimal_entropy":-4.0,"deviation":4.0}
+                     ]');
+                     DROP INDEX games_uuid;
+                     ALTER TABLE games DROP COLUMN hint_budget;
+                     ALTER TABLE games DROP COLUMN hints;
+                     DROP INDEX solver_sessions_uuid;
+                     ALTER TABLE solver_sessions DROP COLUMN recomputed_at;
+                     ALTER TABLE solver_sessions DROP COLUMN strategy;
+                     ALTER TABLE games DROP COLUMN word_score;
+                     ALTER TABLE games DROP COLUMN word_tier;
+                     DROP TABLE word_difficulty;
+                     ALTER TABLE solver_sessions DROP COLUMN resume_declined;
+                     ALTER TABLE games DROP COLUMN gauntlet_id;
+                     DROP TABLE gauntlets;
+                     ALTER TABLE games DROP COLUMN archived_at;
+                     ALTER TABLE games DROP COLUMN hints_used;
+                     ALTER TABLE games DROP COLUMN reason;
+                     ALTER TABLE solver_sessions DROP COLUMN reason;
+                     ALTER TABLE games DROP COLUMN difficulty;
+                     ALTER TABLE games DROP COLUMN uuid;
+                     ALTER TABLE solver_sessions DROP COLUMN uuid;
+                     ALTER TABLE solver_sessions DROP COLUMN resolved_word;
+                     ALTER TABLE solver_sessions DROP COLUMN outcome;
+                     DROP TABLE multi_board_games;
+                     DROP TABLE kids_games;
+                     PRAGMA user_version = 6;"#,
+                )
+                .execute(&db.pool),
+            )
+            .unwrap();
+        drop(db);
+
+        let db = Database::open(path).unwrap();
+        let sessions = db.load_solver_sessions().unwrap();
+        let guesses = &sessions[0].guesses;
+        assert_eq!(guesses.len(), 2);
+        assert_eq!(guesses[0].word, "crane");
+        assert_eq!(guesses[0].optimal_word.as_deref(), Some("raise"));
+        assert_eq!(guesses[0].deviation_score, Some(-1.5));
+        // Sessions from before patterns were kept have none to show
+        assert_eq!(guesses[0].feedback, None);
+        assert_eq!(guesses[1].word, "stone");
+        assert_eq!(guesses[1].optimal_word, None);
+        assert_eq!(guesses[1].optimal_entropy, None);
+        assert_eq!(guesses[1].deviation_score, None);
+        // Deviations then were all by score
+        assert_eq!(guesses[0].metric, Some(DeviationMetric::Heuristic));
+        assert_eq!(guesses[1].metric, None);
+    }
+
+    #[test]
+    fn test_deviation_metric_backfilled() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.db");
+
+        let db = Database::open(&path).unwrap();
+        db.rt
+            .block_on(
+                sqlx::raw_sql(
+                    r#"INSERT INTO solver_sessions (timestamp, guess_count, guesses_json)
+                     VALUES ('2024-01-01T00:00:00Z', 2, '[
+                         {"word":"crane","pool_before":8,"pool_after":3,"entropy":1.5,
+                          "optimal_word":"raise","optimal_entropy":30.0,"deviation":-4.0},
+                         {"word":"stone","pool_before":0,"pool_after":0,"entropy":0.0,
+                          "optimal_word":null,"optimal_entropy":null,"deviation":null}
+                     ]');
+                     DROP TABLE multi_board_games;
+                     DROP TABLE kids_games;
+                     PRAGMA user_version = 18;"#,
+                )
+                .execute(&db.pool),
+            )
+            .unwrap();
+        drop(db);
+
+        let db = Database::open(&path).unwrap();
+        let metrics: Vec<Option<String>> = db
+            .rt
+            .block_on(
+                sqlx::query_scalar(
+                    "SELECT json_extract(guess.value, '$.metric')
+                     FROM solver_sessions, json_each(solver_sessions.guesses_json) AS guess",
+                )
+                .fetch_all(&db.pool),
+            )
+            .unwrap();
+        assert_eq!(metrics, [Some("heuristic".to_string()), None]);
+    }
+
+    #[test]
+    fn test_deviation_metrics_round_trip() {
+        use crate::ui::history::solver_types::SolverGuess;
+
+        let db = Database::open_memory().unwrap();
+        let guess = |deviation_score: Option<f64>, metric| SolverGuess {
+            word: "crane".to_string(),
+            pool_size_before: 8,
+            pool_size_after: 1,
+            entropy: 0.0,
+            optimal_word: deviation_score.map(|_| "raise".to_string()),
+            optimal_entropy: deviation_score.map(|_| 2.0),
+            deviation_score,
+            metric,
+            feedback: None,
+            note: None,
+        };
+        db.save_solver_session(
+            Utc::now(),
+            &[
+                guess(Some(-0.25), Some(DeviationMetric::Entropy)),
+                guess(Some(-12.0), Some(DeviationMetric::Heuristic)),
+                guess(None, None),
+            ],
+        )
+        .unwrap();
+
+        let sessions = db.load_solver_sessions().unwrap();
+        let stored: Vec<_> = sessions[0]
+            .guesses
+            .iter()
+            .map(|g| (g.deviation_score, g.metric))
+            .collect();
+        assert_eq!(
+            stored,
+            [
+                (Some(-0.25), Some(DeviationMetric::Entropy)),
+                (Some(-12.0), Some(DeviationMetric::Heuristic)),
+                (None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalized_feedback_read_matches_json() {
+        use crate::solver::{Feedback, parse_pattern};
+        use crate::ui::history::{GameGuess, GameOutcome, GameRecord};
+
+        let db = Database::open_memory().unwrap();
+        let guess = |word: &str, pattern: &str| {
+            GameGuess::new(word.to_string(), parse_pattern(pattern).unwrap())
+        };
+        db.save_game(&GameRecord {
+            timestamp: Utc::now(),
+            target_word: "stone".to_string(),
+            guesses: vec![
+                guess("crane", "XXXGG"),
+                GameGuess::timed_out(),
+                guess("stone", "GGGGG"),
+            ],
+            outcome: GameOutcome::Won { guesses: 3 },
+            blitz: true,
+            duration_secs: None,
+            app_version: None,
+            difficulty: crate::settings::Difficulty::Normal,
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        })
+        .unwrap();
+
+        let feedback = |db: &Database| -> Vec<Vec<Feedback>> {
+            let games = db.load_games().unwrap();
+            games[0]
+                .guesses
+                .iter()
+                .map(|g| g.feedback.clone())
+                .collect()
+        };
+        let normalized = feedback(&db);
+
+        db.rt
+            .block_on(sqlx::query("DELETE FROM guess_feedback").execute(&db.pool))
+            .unwrap();
+        let from_json = feedback(&db);
+
+        assert_eq!(normalized, from_json);
+        assert_eq!(normalized[0], parse_pattern("XXXGG").unwrap());
+        assert!(normalized[1].is_empty());
+    }
+
+    #[test]
+    fn test_game_outcomes_round_trip() {
+        use crate::ui::history::{GameOutcome, GameRecord};
+
+        let db = Database::open_memory().unwrap();
+        let outcomes = [
+            GameOutcome::Won { guesses: 0 },
+            GameOutcome::Lost,
+            GameOutcome::Forfeited,
+            GameOutcome::Abandoned(AbandonReason::Switched),
+            GameOutcome::Abandoned(AbandonReason::Quit),
+            GameOutcome::Abandoned(AbandonReason::Crashed),
+        ];
+        for outcome in &outcomes {
+            db.save_game(&GameRecord {
+                timestamp: Utc::now(),
+                target_word: "crane".to_string(),
+                guesses: vec![],
+                outcome: outcome.clone(),
+                blitz: false,
+                duration_secs: None,
+                app_version: None,
+                difficulty: crate::settings::Difficulty::Normal,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty: None,
+            })
+            .unwrap();
+        }
+
+        let loaded: Vec<GameOutcome> = db
+            .load_games()
+            .unwrap()
+            .into_iter()
+            .map(|g| g.outcome)
+            .collect();
+        assert_eq!(loaded, outcomes);
+    }
+
+    fn game_at(timestamp: DateTime<Utc>) -> crate::ui::history::GameRecord {
+        crate::ui::history::GameRecord {
+            timestamp,
+            target_word: "stone".to_string(),
+            guesses: vec![crate::ui::history::GameGuess::new(
+                "crane".to_string(),
+                crate::solver::parse_pattern("XXXGG").unwrap(),
+            )],
+            outcome: crate::ui::history::GameOutcome::Lost,
+            blitz: false,
+            duration_secs: None,
+            app_version: None,
+            difficulty: crate::settings::Difficulty::Normal,
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_replaced_by_final_save() {
+        use crate::ui::history::GameOutcome;
+
+        let db = Database::open_memory().unwrap();
+        let uuid = new_write_id();
+        let mut record = game_at(Utc::now());
+        db.checkpoint_game(&uuid, &record).unwrap();
+        db.checkpoint_game(&uuid, &record).unwrap();
+        // Open rows stay out of history
+        assert!(db.load_games().unwrap().is_empty());
+        assert!(db.game_stats_by_difficulty(false).unwrap().is_empty());
+
+        record.outcome = GameOutcome::Abandoned(AbandonReason::Quit);
+        db.save_game_as(&uuid, &record).unwrap();
+        let games = db.load_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].outcome, record.outcome);
+        // Nothing left open to be taken for a crash
+        assert_eq!(db.close_stale(Utc::now()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_checkpoint_without_guesses_drops_row() {
+        let db = Database::open_memory().unwrap();
+        let uuid = new_write_id();
+        let mut record = game_at(Utc::now());
+        db.checkpoint_game(&uuid, &record).unwrap();
+
+        record.guesses.clear();
+        db.checkpoint_game(&uuid, &record).unwrap();
+        assert_eq!(db.close_stale(Utc::now()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_close_stale_rows_as_crashed() {
+        use crate::ui::history::{GameOutcome, SolverOutcome, solver_types::SolverGuess};
+        use chrono::TimeZone;
+
+        let db = Database::open_memory().unwrap();
+        let boot = Utc.with_ymd_and_hms(2026, 10, 15, 12, 0, 0).unwrap();
+        let before = boot - chrono::Duration::hours(1);
+        let after = boot + chrono::Duration::minutes(1);
+
+        db.checkpoint_game(&new_write_id(), &game_at(before))
+            .unwrap();
+        // Opened by this run, so not stale
+        db.checkpoint_game(&new_write_id(), &game_at(after))
+            .unwrap();
+        let guess = SolverGuess {
+            word: "crane".to_string(),
+            pool_size_before: 10,
+            pool_size_after: 2,
+            entropy: 1.0,
+            optimal_word: None,
+            optimal_entropy: None,
+            deviation_score: None,
+            metric: None,
+            feedback: None,
+            note: None,
+        };
+        db.checkpoint_solver_session(&new_write_id(), before, &[guess])
+            .unwrap();
+
+        assert_eq!(db.close_stale(boot).unwrap(), 2);
+        let games = db.load_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(
+            games[0].outcome,
+            GameOutcome::Abandoned(AbandonReason::Crashed)
+        );
+        assert_eq!(games[0].guess_count(), 1);
+        let sessions = db.load_solver_sessions().unwrap();
+        assert_eq!(
+            sessions[0].outcome,
+            SolverOutcome::Abandoned {
+                guesses: 1,
+                reason: AbandonReason::Crashed
+            }
+        );
+        // Abandoned sessions don't count toward the solver's averages
+        assert!(db.session_averages_by_version().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_resumable_solver_session() {
+        use crate::ui::history::solver_types::SolverGuess;
+
+        let db = Database::open_memory().unwrap();
+        let now = Utc::now();
+        let guess = |feedback: Option<&str>| SolverGuess {
+            word: "crane".to_string(),
+            pool_size_before: 10,
+            pool_size_after: 2,
+            entropy: 1.0,
+            optimal_word: None,
+            optimal_entropy: None,
+            deviation_score: None,
+            metric: None,
+            feedback: feedback.map(|p| crate::solver::parse_pattern(p).unwrap()),
+            note: None,
+        };
+        let since = now - chrono::Duration::hours(24);
+
+        // Sessions left for another mode were a choice, not an interruption
+        db.save_solver_session_as(
+            &new_write_id(),
+            now,
+            &[guess(Some("XYXXG"))],
+            Some(AbandonReason::Switched),
+        )
+        .unwrap();
+        assert!(db.resumable_solver_session(since).unwrap().is_none());
+
+        // Without a pattern the guess can't be replayed
+        db.save_solver_session_as(
+            &new_write_id(),
+            now,
+            &[guess(None)],
+            Some(AbandonReason::Quit),
+        )
+        .unwrap();
+        assert!(db.resumable_solver_session(since).unwrap().is_none());
+
+        let uuid = new_write_id();
+        let id = db
+            .save_solver_session_as(
+                &uuid,
+                now,
+                &[guess(Some("XYXXG"))],
+                Some(AbandonReason::Crashed),
+            )
+            .unwrap();
+        let session = db.resumable_solver_session(since).unwrap().unwrap();
+        assert_eq!(session.id, id);
+        assert_eq!(session.uuid.as_deref(), Some(uuid.as_str()));
+        assert_eq!(
+            session.guesses[0].feedback,
+            crate::solver::parse_pattern("XYXXG").ok()
+        );
+        assert!(
+            db.resumable_solver_session(now + chrono::Duration::minutes(1))
+                .unwrap()
+                .is_none()
+        );
+
+        db.decline_solver_resume(id).unwrap();
+        assert!(db.resumable_solver_session(since).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_reopened_session_replaced_by_final_save() {
+        use crate::ui::history::{SolverOutcome, solver_types::SolverGuess};
+
+        let db = Database::open_memory().unwrap();
+        let guess = SolverGuess {
+            word: "crane".to_string(),
+            pool_size_before: 10,
+            pool_size_after: 2,
+            entropy: 1.0,
+            optimal_word: None,
+            optimal_entropy: None,
+            deviation_score: None,
+            metric: None,
+            feedback: crate::solver::parse_pattern("XYXXG").ok(),
+            note: None,
+        };
+        let id = db
+            .save_solver_session_as(
+                &new_write_id(),
+                Utc::now(),
+                std::slice::from_ref(&guess),
+                Some(AbandonReason::Quit),
+            )
+            .unwrap();
+
+        let uuid = new_write_id();
+        db.reopen_solver_session(id, &uuid).unwrap();
+        // Open again, so out of history until it's saved
+        assert!(db.load_solver_sessions().unwrap().is_empty());
+
+        db.save_solver_session(Utc::now(), std::slice::from_ref(&guess))
+            .unwrap();
+        db.save_solver_session_as(&uuid, Utc::now(), &[guess.clone(), guess], None)
+            .unwrap();
+        let sessions = db.load_solver_sessions().unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[1].outcome, SolverOutcome::Completed { guesses: 2 });
+    }
+
+    #[test]
+    fn test_feedback_analytics() {
+        use crate::solver::parse_pattern;
+        use crate::ui::history::{GameGuess, GameOutcome, GameRecord};
+
+        let db = Database::open_memory().unwrap();
+        for patterns in [vec!["GXXXY", "GGGGG"], vec!["XXXXG"]] {
+            let guesses = patterns
+                .iter()
+                .map(|p| GameGuess::new("crane".to_string(), parse_pattern(p).unwrap()))
+                .collect();
+            db.save_game(&GameRecord {
+                timestamp: Utc::now(),
+                target_word: "crane".to_string(),
+                guesses,
+                outcome: GameOutcome::Lost,
+                blitz: false,
+                duration_secs: None,
+                app_version: None,
+                difficulty: crate::settings::Difficulty::Normal,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty: None,
+            })
+            .unwrap();
+        }
+
+        assert_eq!(
+            db.first_guess_green_rates(false).unwrap(),
+            [0.5, 0.0, 0.0, 0.0, 0.5]
+        );
+        assert_eq!(db.greens_per_guess(false).unwrap(), [1.0, 5.0]);
+    }
+
+    #[test]
+    fn test_archived_games_left_out_unless_included() {
+        use crate::ui::history::GameOutcome;
+
+        let db = Database::open_memory().unwrap();
+        db.save_game(&game_at(Utc::now())).unwrap();
+        db.save_game(&crate::ui::history::GameRecord {
+            outcome: GameOutcome::Won { guesses: 1 },
+            ..game_at(Utc::now())
+        })
+        .unwrap();
+        let won_id = db.load_games_with_ids(false).unwrap()[1].0;
+
+        db.set_game_archived(won_id, true).unwrap();
+        let games = db.load_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].outcome, GameOutcome::Lost);
+        assert_eq!(db.game_stats_by_difficulty(false).unwrap()[0].games, 1);
+        assert!(db.game_averages_by_version(false).unwrap().is_empty());
+        assert_eq!(db.greens_per_guess(false).unwrap(), [2.0]);
+
+        let all = db.load_games_with_ids(true).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[1].0, won_id);
+        assert!(all[1].1.is_archived());
+        assert_eq!(db.game_stats_by_difficulty(true).unwrap()[0].wins, 1);
+        assert_eq!(db.game_averages_by_version(true).unwrap()[0].count, 1);
+
+        db.set_game_archived(won_id, false).unwrap();
+        assert_eq!(db.load_games().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_purge_archived_before_cutoff() {
+        let db = Database::open_memory().unwrap();
+        for _ in 0..3 {
+            db.save_game(&game_at(Utc::now())).unwrap();
+        }
+        let ids: Vec<i64> = db
+            .load_games_with_ids(false)
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        db.set_game_archived(ids[0], true).unwrap();
+        db.set_game_archived(ids[1], true).unwrap();
+
+        // Archived just now, so not yet older than a day
+        assert_eq!(
+            db.purge_archived(Utc::now() - chrono::Duration::days(1))
+                .unwrap(),
+            0
+        );
+        assert_eq!(db.load_games_with_ids(true).unwrap().len(), 3);
+
+        assert_eq!(
+            db.purge_archived(Utc::now() + chrono::Duration::seconds(1))
+                .unwrap(),
+            2
+        );
+        let left = db.load_games_with_ids(true).unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].0, ids[2]);
+        // The purged games' feedback went with them
+        let feedback_rows: i64 = db
+            .rt
+            .block_on(sqlx::query_scalar("SELECT COUNT(*) FROM guess_feedback").fetch_one(&db.pool))
+            .unwrap();
+        assert_eq!(feedback_rows, 5);
+    }
+
+    #[test]
+    fn test_purge_empty_sessions() {
+        use crate::ui::history::solver_types::SolverGuess;
+
+        let db = Database::open_memory().unwrap();
+        let now = Utc::now();
+        let two_hours_ago = now - chrono::Duration::hours(2);
+        let guess = SolverGuess {
+            word: "crane".to_string(),
+            pool_size_before: 10,
+            pool_size_after: 2,
+            entropy: 1.0,
+            optimal_word: None,
+            optimal_entropy: None,
+            deviation_score: None,
+            metric: None,
+            feedback: None,
+            note: None,
+        };
+        let abandoned = |at, guesses: &[SolverGuess]| {
+            db.save_solver_session_as(&new_write_id(), at, guesses, Some(AbandonReason::Switched))
+                .unwrap()
+        };
+        abandoned(two_hours_ago, &[]);
+        abandoned(two_hours_ago, &[]);
+        // Too recent, and played, so both stay
+        abandoned(now, &[]);
+        abandoned(two_hours_ago, std::slice::from_ref(&guess));
+
+        let cutoff = now - chrono::Duration::hours(1);
+        assert_eq!(db.purge_empty_sessions(cutoff).unwrap(), 2);
+        let left = db.load_solver_sessions().unwrap();
+        assert_eq!(left.len(), 2);
+        assert_eq!(left.iter().filter(|s| s.guesses.is_empty()).count(), 1);
+        assert_eq!(db.purge_empty_sessions(cutoff).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_games_loaded_a_page_at_a_time() {
+        let db = Database::open_memory().unwrap();
+        let start = Utc::now() - chrono::Duration::days(1);
+        for i in 0..5 {
+            db.save_game(&game_at(start + chrono::Duration::minutes(i)))
+                .unwrap();
+        }
+        let all = db.load_games_with_ids(false).unwrap();
+        db.set_game_archived(all[4].0, true).unwrap();
+
+        assert_eq!(db.count_games(false).unwrap(), 4);
+        assert_eq!(db.count_games(true).unwrap(), 5);
+        let page: Vec<i64> = db
+            .load_games_page(false, 1, 2)
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(page, [all[1].0, all[2].0]);
+        // Each page brings its own feedback rows
+        let last = db.load_games_page(true, 4, 10).unwrap();
+        assert_eq!(last.len(), 1);
+        assert_eq!(
+            last[0].1.guesses[0].feedback,
+            crate::solver::parse_pattern("XXXGG").unwrap()
+        );
+        assert!(db.load_games_page(true, 5, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_gauntlet_links_its_games() {
+        let db = Database::open_memory().unwrap();
+        let gauntlet = db.create_gauntlet(2).unwrap();
+        db.save_game(&game_at(Utc::now())).unwrap();
+        for _ in 0..2 {
+            let uuid = new_write_id();
+            db.save_game_as(&uuid, &game_at(Utc::now())).unwrap();
+            db.add_to_gauntlet(gauntlet, &uuid).unwrap();
+        }
+        let ids: Vec<i64> = db
+            .load_games_with_ids(false)
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        db.set_game_archived(ids[2], true).unwrap();
+
+        // Archived or not, a gauntlet keeps every game it was played with
+        let games: Vec<i64> = db
+            .load_gauntlet_games(gauntlet)
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(games, ids[1..]);
+        // A game that never reached the table isn't silently skipped
+        assert!(db.add_to_gauntlet(gauntlet, &new_write_id()).is_err());
+        let (started_at, size): (String, i64) = db
+            .rt
+            .block_on(
+                sqlx::query_as("SELECT started_at, size FROM gauntlets WHERE id = ?")
+                    .bind(gauntlet)
+                    .fetch_one(&db.pool),
+            )
+            .unwrap();
+        assert!(DateTime::parse_from_rfc3339(&started_at).is_ok());
+        assert_eq!(size, 2);
+        assert!(db.load_gauntlet_games(gauntlet + 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        use crate::solver::{Guess, SolverState, parse_pattern};
+
+        let db = Database::open_memory().unwrap();
+        let mut state = SolverState::new(5);
+        for (word, pattern) in [("crane", "XYXXG"), ("house", "XGXYG")] {
+            state
+                .add_guess(Guess::new(
+                    word.to_string(),
+                    parse_pattern(pattern).unwrap(),
+                ))
+                .unwrap();
+        }
+
+        db.save_snapshot("alice", &state).unwrap();
+        db.save_snapshot("bob", &SolverState::new(6)).unwrap();
+
+        let snapshots = db.load_snapshots().unwrap();
+        assert_eq!(snapshots.len(), 2);
+        let alice = snapshots.iter().find(|s| s.name == "alice").unwrap();
+        assert_eq!(alice.state.word_len(), 5);
+        let guesses: Vec<_> = alice
+            .state
+            .guesses()
+            .iter()
+            .map(|g| (g.word.as_str(), g.feedback.clone()))
+            .collect();
+        assert_eq!(
+            guesses,
+            [
+                ("crane", parse_pattern("XYXXG").unwrap()),
+                ("house", parse_pattern("XGXYG").unwrap()),
+            ]
+        );
+        let bob = snapshots.iter().find(|s| s.name == "bob").unwrap();
+        assert_eq!(bob.state.word_len(), 6);
+        assert!(bob.state.guesses().is_empty());
+
+        // Saving under an existing name replaces it
+        db.save_snapshot("alice", &SolverState::new(5)).unwrap();
+        db.delete_snapshot("bob").unwrap();
+        let snapshots = db.load_snapshots().unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert!(snapshots[0].state.guesses().is_empty());
+    }
+
+    #[test]
+    fn test_blacklist_round_trip() {
+        let db = Database::open_memory().unwrap();
+
+        db.add_to_blacklist("stone").unwrap();
+        db.add_to_blacklist("crane").unwrap();
+        db.add_to_blacklist("stone").unwrap();
+        assert_eq!(db.load_blacklist().unwrap(), ["crane", "stone"]);
+
+        db.remove_from_blacklist("stone").unwrap();
+        db.remove_from_blacklist("apple").unwrap();
+        assert_eq!(db.load_blacklist().unwrap(), ["crane"]);
+    }
+
+    #[test]
+    fn test_game_stats_by_difficulty() {
+        use crate::settings::Difficulty;
+        use crate::ui::history::{GameGuess, GameOutcome, GameRecord};
+
+        let db = Database::open_memory().unwrap();
+        for (difficulty, outcome) in [
+            (Difficulty::Expert, GameOutcome::Won { guesses: 5 }),
+            (Difficulty::Normal, GameOutcome::Won { guesses: 4 }),
+            (Difficulty::Normal, GameOutcome::Won { guesses: 3 }),
+            (Difficulty::Normal, GameOutcome::Lost),
+            (Difficulty::Expert, GameOutcome::Forfeited),
+            // Counted nowhere, as in the quick stats
+            (
+                Difficulty::Expert,
+                GameOutcome::Abandoned(AbandonReason::Crashed),
+            ),
+        ] {
+            let guesses = match outcome {
+                GameOutcome::Won { guesses } => guesses,
+                GameOutcome::Lost | GameOutcome::Forfeited | GameOutcome::Abandoned(_) => 6,
+            };
+            db.save_game(&GameRecord {
+                timestamp: Utc::now(),
+                target_word: "stone".to_string(),
+                guesses: vec![GameGuess::new("crane".to_string(), vec![]); guesses],
+                outcome,
+                blitz: false,
+                duration_secs: None,
+                app_version: None,
+                difficulty,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty: None,
+            })
+            .unwrap();
+        }
+
+        let stats = |key, games, wins, average_guesses| DifficultyStats {
+            key,
+            games,
+            wins,
+            average_guesses,
+        };
+        assert_eq!(
+            db.game_stats_by_difficulty(false).unwrap(),
+            [
+                stats(Difficulty::Normal, 3, 2, Some(3.5)),
+                stats(Difficulty::Expert, 2, 1, Some(5.0)),
+            ]
+        );
+        assert_eq!(db.load_games().unwrap()[0].difficulty, Difficulty::Expert);
+    }
+
+    #[test]
+    fn test_multi_board_games_round_trip() {
+        use crate::settings::Difficulty;
+        use crate::ui::history::{BoardResult, GameOutcome, MultiBoardRecord};
+
+        let db = Database::open_memory().unwrap();
+        let board = |target: &str, solved_in| BoardResult {
+            target: target.to_string(),
+            solved_in,
+        };
+        let record = |boards: Vec<BoardResult>, outcome, guesses| MultiBoardRecord {
+            timestamp: Utc::now(),
+            guesses: vec!["crane".to_string(); guesses],
+            boards,
+            outcome,
+            difficulty: Difficulty::Normal,
+            duration_secs: Some(42.0),
+            app_version: None,
+        };
+        let won = record(
+            vec![board("crane", Some(1)), board("stone", Some(4))],
+            GameOutcome::Won { guesses: 4 },
+            4,
+        );
+        db.save_multi_board_game(&won).unwrap();
+        db.save_multi_board_game(&record(
+            vec![board("crane", Some(1)), board("stone", None)],
+            GameOutcome::Lost,
+            7,
+        ))
+        .unwrap();
+        db.save_multi_board_game(&record(
+            vec![
+                board("crane", Some(2)),
+                board("stone", Some(3)),
+                board("house", Some(5)),
+            ],
+            GameOutcome::Won { guesses: 5 },
+            5,
+        ))
+        .unwrap();
+
+        let games = db.load_multi_board_games().unwrap();
+        assert_eq!(games.len(), 3);
+        assert_eq!(games[0].boards, won.boards);
+        assert_eq!(games[0].guesses, won.guesses);
+        assert_eq!(games[0].outcome, won.outcome);
+        assert_eq!(games[1].solved(), 1);
+
+        let stats = |key, games, wins, average_guesses| MultiBoardStats {
+            key,
+            games,
+            wins,
+            average_guesses,
+        };
+        assert_eq!(
+            db.multi_board_stats().unwrap(),
+            [stats(2, 2, 1, Some(4.0)), stats(3, 1, 1, Some(5.0))]
+        );
+        // Kept apart from single-board games
+        assert!(db.load_games().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_kids_games_round_trip() {
+        use crate::ui::history::{GameOutcome, KidsGameRecord};
+
+        let db = Database::open_memory().unwrap();
+        let record = |guesses: &[&str], outcome| KidsGameRecord {
+            timestamp: Utc::now(),
+            target_word: "apple".to_string(),
+            guesses: guesses.iter().map(|g| g.to_string()).collect(),
+            outcome,
+            duration_secs: Some(60.0),
+            app_version: None,
+        };
+        let won = record(
+            &[
+                "crane", "house", "plate", "tiger", "seven", "funny", "magic", "apple",
+            ],
+            GameOutcome::Won { guesses: 8 },
+        );
+        db.save_kids_game(&won).unwrap();
+        db.save_kids_game(&record(
+            &["crane"],
+            GameOutcome::Abandoned(AbandonReason::Quit),
+        ))
+        .unwrap();
+
+        let games = db.load_kids_games().unwrap();
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].guesses, won.guesses);
+        assert_eq!(games[0].outcome, won.outcome);
+        assert_eq!(
+            games[1].outcome,
+            GameOutcome::Abandoned(AbandonReason::Quit)
+        );
+        // Kept apart from the other games and their stats
+        assert!(db.load_games().unwrap().is_empty());
+        assert_eq!(db.quick_stats().unwrap().wins, 0);
+    }
+
+    #[test]
+    fn test_game_stats_by_tier() {
+        use crate::ui::history::{GameGuess, GameOutcome, GameRecord};
+
+        let db = Database::open_memory().unwrap();
+        let rated = |tier| Some(WordDifficulty { score: 2.5, tier });
+        for (word_difficulty, outcome) in [
+            (rated(WordTier::Hard), GameOutcome::Won { guesses: 5 }),
+            (rated(WordTier::Easy), GameOutcome::Won { guesses: 2 }),
+            (rated(WordTier::Hard), GameOutcome::Lost),
+            (None, GameOutcome::Won { guesses: 4 }),
+        ] {
+            let guesses = match outcome {
+                GameOutcome::Won { guesses } => guesses,
+                _ => 6,
+            };
+            db.save_game(&GameRecord {
+                timestamp: Utc::now(),
+                target_word: "stone".to_string(),
+                guesses: vec![GameGuess::new("crane".to_string(), vec![]); guesses],
+                outcome,
+                blitz: false,
+                duration_secs: None,
+                app_version: None,
+                difficulty: crate::settings::Difficulty::Normal,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty,
+            })
+            .unwrap();
+        }
+
+        let stats = |key, games, wins, average_guesses| TierStats {
+            key,
+            games,
+            wins,
+            average_guesses,
+        };
+        assert_eq!(
+            db.game_stats_by_tier(false).unwrap(),
+            [
+                stats(Some(WordTier::Easy), 1, 1, Some(2.0)),
+                stats(Some(WordTier::Hard), 2, 1, Some(5.0)),
+                stats(None, 1, 1, Some(4.0)),
+            ]
+        );
+        let games = db.load_games().unwrap();
+        assert_eq!(games[0].word_difficulty, rated(WordTier::Hard));
+        assert_eq!(games[3].word_difficulty, None);
+    }
+
+    #[test]
+    fn test_quick_stats() {
+        use crate::ui::history::{GameGuess, GameOutcome, GameRecord};
+
+        let db = Database::open_memory().unwrap();
+        assert_eq!(db.quick_stats().unwrap(), QuickStats::default());
+
+        let start = Utc::now() - chrono::Duration::hours(1);
+        for (i, outcome) in [
+            GameOutcome::Won { guesses: 3 },
+            GameOutcome::Lost,
+            GameOutcome::Won { guesses: 2 },
+            // A crash neither breaks the streak nor counts as a loss
+            GameOutcome::Abandoned(AbandonReason::Crashed),
+            GameOutcome::Won { guesses: 4 },
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            db.save_game(&GameRecord {
+                timestamp: start + chrono::Duration::minutes(i as i64),
+                target_word: "stone".to_string(),
+                guesses: vec![GameGuess::new("crane".to_string(), vec![]); 3],
+                outcome,
+                blitz: false,
+                duration_secs: None,
+                app_version: None,
+                difficulty: crate::settings::Difficulty::Normal,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty: None,
+            })
+            .unwrap();
+        }
+
+        let stats = db.quick_stats().unwrap();
+        assert_eq!(
+            stats,
+            QuickStats {
+                wins: 3,
+                losses: 1,
+                average_guesses: Some(3.0),
+                current_streak: 2,
+            }
+        );
+        assert_eq!(stats.win_rate(), Some(75.0));
+
+        // Archiving the loss joins the wins into one streak
+        let lost = db.load_games_page(false, 0, 10).unwrap();
+        let (id, _) = lost
+            .iter()
+            .find(|(_, game)| game.outcome == GameOutcome::Lost)
+            .unwrap();
+        db.set_game_archived(*id, true).unwrap();
+        assert_eq!(db.quick_stats().unwrap().current_streak, 3);
+
+        db.close();
+        assert!(db.quick_stats().is_err());
+    }
+
+    #[test]
+    fn test_word_scores_cached_by_list() {
+        let db = Database::open_memory().unwrap();
+        assert!(db.load_word_scores("abc").unwrap().is_empty());
+
+        let scores = vec![("crane".to_string(), 2.25), ("geese".to_string(), 4.5)];
+        db.save_word_scores("abc", &scores).unwrap();
+        db.save_word_scores("def", &scores[..1]).unwrap();
+        assert_eq!(db.load_word_scores("abc").unwrap(), scores);
+
+        // Saving again replaces the list's scores rather than adding to them
+        db.save_word_scores("abc", &scores[1..]).unwrap();
+        assert_eq!(db.load_word_scores("abc").unwrap(), &scores[1..]);
+        assert_eq!(db.load_word_scores("def").unwrap(), &scores[..1]);
+    }
+
+    #[test]
+    fn test_game_stats_by_opener() {
+        use crate::ui::history::{AbandonReason, GameGuess, GameOutcome, GameRecord};
+
+        let db = Database::open_memory().unwrap();
+        for (opener, outcome) in [
+            ("crane", GameOutcome::Won { guesses: 3 }),
+            ("crane", GameOutcome::Won { guesses: 4 }),
+            ("crane", GameOutcome::Lost),
+            ("crane", GameOutcome::Won { guesses: 5 }),
+            ("slate", GameOutcome::Won { guesses: 2 }),
+            ("slate", GameOutcome::Lost),
+            ("slate", GameOutcome::Won { guesses: 4 }),
+            ("raise", GameOutcome::Won { guesses: 5 }),
+            ("raise", GameOutcome::Lost),
+            // Neither counts: only won and lost games are grouped
+            ("raise", GameOutcome::Forfeited),
+            ("raise", GameOutcome::Abandoned(AbandonReason::Quit)),
+        ] {
+            let guesses = match outcome {
+                GameOutcome::Won { guesses } => guesses,
+                GameOutcome::Lost => 6,
+                GameOutcome::Forfeited | GameOutcome::Abandoned(_) => 2,
+            };
+            let mut played = vec![GameGuess::new("house".to_string(), vec![]); guesses];
+            played[0] = GameGuess::new(opener.to_string(), vec![]);
+            db.save_game(&GameRecord {
+                timestamp: Utc::now(),
+                target_word: "stone".to_string(),
+                guesses: played,
+                outcome,
+                blitz: false,
+                duration_secs: None,
+                app_version: None,
+                difficulty: crate::settings::Difficulty::Normal,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty: None,
+            })
+            .unwrap();
+        }
+        // The turn that timed out doesn't make this a game without an opener
+        db.save_game(&GameRecord {
+            timestamp: Utc::now(),
+            target_word: "stone".to_string(),
+            guesses: vec![
+                GameGuess::timed_out(),
+                GameGuess::new("crane".to_string(), vec![]),
+                GameGuess::new("stone".to_string(), vec![]),
+            ],
+            outcome: GameOutcome::Won { guesses: 3 },
+            blitz: true,
+            duration_secs: None,
+            app_version: None,
+            difficulty: crate::settings::Difficulty::Normal,
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        })
+        .unwrap();
+
+        let stats = |opener: Option<&str>, games, wins, average_guesses| OpenerStats {
+            key: opener.map(str::to_string),
+            games,
+            wins,
+            average_guesses,
+        };
+        assert_eq!(
+            db.game_stats_by_opener(false).unwrap(),
+            [
+                stats(Some("crane"), 5, 4, Some(3.75)),
+                stats(Some("slate"), 3, 2, Some(3.0)),
+                // RAISE opened only two won or lost games
+                stats(None, 2, 1, Some(5.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_aggregates_by_local_date() {
+        use crate::ui::history::{GameGuess, GameOutcome, GameRecord};
+        use chrono::TimeZone;
+
+        let db = Database::open_memory().unwrap();
+        // 02:00 UTC on the 13th is still the 12th five hours west
+        for (hour, day, outcome) in [
+            (2, 13, GameOutcome::Won { guesses: 3 }),
+            (15, 13, GameOutcome::Won { guesses: 5 }),
+            (16, 13, GameOutcome::Lost),
+            (12, 20, GameOutcome::Won { guesses: 4 }),
+        ] {
+            let guesses = match outcome {
+                GameOutcome::Won { guesses } => guesses,
+                GameOutcome::Lost | GameOutcome::Forfeited | GameOutcome::Abandoned(_) => 6,
+            };
+            db.save_game(&GameRecord {
+                timestamp: Utc.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap(),
+                target_word: "stone".to_string(),
+                guesses: vec![GameGuess::new("crane".to_string(), vec![]); guesses],
+                outcome,
+                blitz: false,
+                duration_secs: None,
+                app_version: None,
+                difficulty: crate::settings::Difficulty::Normal,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty: None,
+            })
+            .unwrap();
+        }
+        db.save_solver_session(Utc.with_ymd_and_hms(2026, 10, 14, 3, 0, 0).unwrap(), &[])
+            .unwrap();
+
+        let west = FixedOffset::west_opt(5 * 3600).unwrap();
+        let date = |day| NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+        assert_eq!(
+            db.games_by_local_date(date(12), date(18), west).unwrap(),
+            [
+                DayGames {
+                    key: date(12),
+                    games: 1,
+                    wins: 1,
+                    average_guesses: Some(3.0),
+                },
+                DayGames {
+                    key: date(13),
+                    games: 2,
+                    wins: 1,
+                    average_guesses: Some(5.0),
+                },
+            ]
+        );
+        assert_eq!(
+            db.sessions_by_local_date(date(12), date(18), west).unwrap(),
+            [(date(13), 1)]
+        );
+
+        let utc = FixedOffset::east_opt(0).unwrap();
+        assert_eq!(
+            db.games_by_local_date(date(12), date(18), utc).unwrap()[0].games,
+            3
+        );
+    }
+
+    #[test]
+    fn test_averages_by_version() {
+        use crate::ui::history::{GameGuess, GameOutcome, GameRecord, solver_types::SolverGuess};
+        use chrono::TimeZone;
+
+        let db = Database::open_memory().unwrap();
+        let day = |d| Utc.with_ymd_and_hms(2024, 1, d, 12, 0, 0).unwrap();
+        for (d, version, outcome) in [
+            (1, "0.1.0", GameOutcome::Won { guesses: 5 }),
+            (2, "0.1.0", GameOutcome::Won { guesses: 4 }),
+            (3, "0.1.0", GameOutcome::Lost),
+            (4, "0.2.0", GameOutcome::Won { guesses: 3 }),
+        ] {
+            let guesses = match outcome {
+                GameOutcome::Won { guesses } => guesses,
+                GameOutcome::Lost | GameOutcome::Forfeited | GameOutcome::Abandoned(_) => 6,
+            };
+            db.save_game(&GameRecord {
+                timestamp: day(d),
+                target_word: "stone".to_string(),
+                guesses: vec![GameGuess::new("crane".to_string(), vec![]); guesses],
+                outcome,
+                blitz: false,
+                duration_secs: None,
+                app_version: Some(version.to_string()),
+                difficulty: crate::settings::Difficulty::Normal,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty: None,
+            })
+            .unwrap();
+        }
+
+        let guess = SolverGuess {
+            word: "crane".to_string(),
+            pool_size_before: 10,
+            pool_size_after: 2,
+            entropy: 2.0,
+            optimal_word: Some("crane".to_string()),
+            optimal_entropy: Some(2.0),
+            deviation_score: Some(0.0),
+            metric: Some(DeviationMetric::Entropy),
+            feedback: None,
+            note: None,
+        };
+        for (d, count) in [(1, 4), (2, 2), (3, 3)] {
+            db.save_solver_session(day(d), &vec![guess.clone(); count])
+                .unwrap();
+        }
+        // The first two sessions came from an older release
+        db.rt
+            .block_on(
+                sqlx::query("UPDATE solver_sessions SET app_version = '0.1.0' WHERE id <= 2")
+                    .execute(&db.pool),
+            )
+            .unwrap();
+
+        let average = |version: &str, count, average_guesses| VersionAverage {
+            app_version: Some(version.to_string()),
+            count,
+            average_guesses,
+        };
+        assert_eq!(
+            db.game_averages_by_version(false).unwrap(),
+            [average("0.1.0", 2, 4.5), average("0.2.0", 1, 3.0)]
+        );
+        assert_eq!(
+            db.session_averages_by_version().unwrap(),
+            [average("0.1.0", 2, 3.0), average(APP_VERSION, 1, 3.0)]
+        );
     }
 }
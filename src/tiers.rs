@@ -0,0 +1,347 @@
+//! How hard each answer is to find, for drawing game targets by difficulty.
+//!
+//! A word's score adds three things: the guesses an autosolver takes to find
+//! it, how rare its letters are among the answers, and its repeated letters.
+//! The autosolver always guesses the best-scoring candidate, so the guesses
+//! for every answer come from one decision tree, built a branch at a time so
+//! the UI can show progress. Answers are split into thirds by score.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    planner::partition, priors::duplicate_letters, scoring::score_and_sort, wordtable::WordTable,
+};
+
+/// Bumped when the score changes, so scores cached for a list are redone.
+const SCORE_VERSION: u64 = 1;
+
+/// Score added per repeated letter; SPEED's second E hides a letter.
+const DUPLICATE_WEIGHT: f64 = 0.5;
+
+/// A third of the answers, by difficulty score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WordTier {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl WordTier {
+    /// Every tier, easiest first.
+    pub const ALL: [WordTier; 3] = [WordTier::Easy, WordTier::Medium, WordTier::Hard];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WordTier::Easy => "Easy",
+            WordTier::Medium => "Medium",
+            WordTier::Hard => "Hard",
+        }
+    }
+
+    /// The value stored in the `games.word_tier` column.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WordTier::Easy => "easy",
+            WordTier::Medium => "medium",
+            WordTier::Hard => "hard",
+        }
+    }
+
+    /// Parse a stored value; `None` for anything unknown.
+    pub fn from_stored(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.as_str() == value)
+    }
+}
+
+/// The tier after `tier` for drawing targets, where `None` draws from every
+/// answer: any, then easy to hard, then back to any.
+pub fn next_tier(tier: Option<WordTier>) -> Option<WordTier> {
+    match tier {
+        None => Some(WordTier::Easy),
+        Some(WordTier::Easy) => Some(WordTier::Medium),
+        Some(WordTier::Medium) => Some(WordTier::Hard),
+        Some(WordTier::Hard) => None,
+    }
+}
+
+/// One answer's difficulty score and the third of the answers it falls in.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WordDifficulty {
+    pub score: f64,
+    pub tier: WordTier,
+}
+
+/// Difficulty of every answer in a list.
+#[derive(Debug, Clone, Default)]
+pub struct WordScores {
+    ratings: HashMap<String, WordDifficulty>,
+}
+
+impl WordScores {
+    /// Tier `scores` into thirds, easiest first; equal scores go in word order,
+    /// so the same scores always give the same tiers.
+    pub fn from_scores(scores: impl IntoIterator<Item = (String, f64)>) -> Self {
+        let mut scores: Vec<(String, f64)> = scores.into_iter().collect();
+        scores.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        let total = scores.len();
+        let ratings = scores
+            .into_iter()
+            .enumerate()
+            .map(|(rank, (word, score))| {
+                let tier = WordTier::ALL[rank * WordTier::ALL.len() / total];
+                (word, WordDifficulty { score, tier })
+            })
+            .collect();
+        Self { ratings }
+    }
+
+    pub fn len(&self) -> usize {
+        self.ratings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ratings.is_empty()
+    }
+
+    pub fn get(&self, word: &str) -> Option<WordDifficulty> {
+        self.ratings.get(word).copied()
+    }
+
+    /// The words in `tier`, alphabetically.
+    pub fn words_in(&self, tier: WordTier) -> Vec<&str> {
+        let mut words: Vec<&str> = self
+            .ratings
+            .iter()
+            .filter(|(_, rating)| rating.tier == tier)
+            .map(|(word, _)| word.as_str())
+            .collect();
+        words.sort_unstable();
+        words
+    }
+
+    /// Every word with its score, alphabetically, for caching.
+    pub fn scores(&self) -> Vec<(String, f64)> {
+        let mut scores: Vec<(String, f64)> = self
+            .ratings
+            .iter()
+            .map(|(word, rating)| (word.clone(), rating.score))
+            .collect();
+        scores.sort_by(|a, b| a.0.cmp(&b.0));
+        scores
+    }
+}
+
+/// Key for the scores of `solutions`, the same from one run and release to
+/// the next as long as the list and the score are.
+pub fn list_hash(solutions: &WordTable) -> String {
+    // FNV-1a, since std's hasher may change between releases
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut feed = |bytes: &[u8]| {
+        for &b in bytes {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    };
+    feed(&SCORE_VERSION.to_le_bytes());
+    feed(&(solutions.word_len() as u64).to_le_bytes());
+    for word in solutions.words() {
+        feed(word.as_bytes());
+        feed(b"\n");
+    }
+    format!("{:016x}", hash)
+}
+
+/// Autosolver games over every answer, played a branch of the decision tree
+/// at a time.
+#[derive(Debug)]
+pub struct ScoreJob {
+    /// Pools still to solve, each with the guess number its best word is played at.
+    pending: Vec<(Vec<u32>, usize)>,
+    /// Guesses taken to find each answer by index; 0 until found.
+    guesses: Vec<usize>,
+    found: usize,
+}
+
+impl ScoreJob {
+    pub fn new(solutions: &WordTable) -> Self {
+        let pool: Vec<u32> = solutions.indices().collect();
+        let pending = if pool.is_empty() {
+            Vec::new()
+        } else {
+            vec![(pool, 1)]
+        };
+        Self {
+            pending,
+            guesses: vec![0; solutions.len()],
+            found: 0,
+        }
+    }
+
+    /// Solve up to `branches` more pools. Returns true once every answer has
+    /// been found.
+    pub fn step(&mut self, solutions: &WordTable, branches: usize) -> bool {
+        for _ in 0..branches {
+            let Some((pool, guess)) = self.pending.pop() else {
+                break;
+            };
+            let best = score_and_sort(solutions, &pool, solutions)[0].0;
+            self.guesses[best as usize] = guess;
+            self.found += 1;
+            // Reversed so the largest bucket is solved first, as it was listed
+            for bucket in partition(solutions, best, &pool).into_iter().rev() {
+                self.pending.push((bucket, guess + 1));
+            }
+        }
+        self.pending.is_empty()
+    }
+
+    /// Answers found so far, and the total.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.found, self.guesses.len())
+    }
+
+    /// Every answer's score, once [`step`](Self::step) has returned true.
+    pub fn finish(self, solutions: &WordTable) -> WordScores {
+        let total = solutions.len().max(1) as f64;
+        let mut containing = [0usize; 256];
+        for word in solutions.words() {
+            let mut seen = [false; 256];
+            for b in word.bytes() {
+                if !std::mem::replace(&mut seen[b as usize], true) {
+                    containing[b as usize] += 1;
+                }
+            }
+        }
+
+        WordScores::from_scores(solutions.indices().map(|i| {
+            let word = solutions.word(i);
+            let mut letters: Vec<u8> = word.bytes().collect();
+            letters.sort_unstable();
+            letters.dedup();
+            // Mean share of answers without each letter
+            let rarity = letters
+                .iter()
+                .map(|&b| 1.0 - containing[b as usize] as f64 / total)
+                .sum::<f64>()
+                / letters.len().max(1) as f64;
+            let score = self.guesses[i as usize] as f64
+                + rarity
+                + DUPLICATE_WEIGHT * duplicate_letters(word) as f64;
+            (word.to_string(), score)
+        }))
+    }
+}
+
+/// Score every answer in `solutions` in one go.
+pub fn score_words(solutions: &WordTable) -> WordScores {
+    let mut job = ScoreJob::new(solutions);
+    while !job.step(solutions, usize::MAX) {}
+    job.finish(solutions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: [&str; 9] = [
+        "raise", "stone", "slate", "crane", "house", "apple", "world", "magic", "geese",
+    ];
+
+    fn fixture() -> WordTable {
+        WordTable::new(FIXTURE, 5)
+    }
+
+    #[test]
+    fn test_thirds_by_score() {
+        let scores = WordScores::from_scores(
+            [
+                ("aaa", 3.0),
+                ("bbb", 1.0),
+                ("ccc", 2.0),
+                ("ddd", 2.0),
+                ("eee", 5.0),
+                ("fff", 4.0),
+            ]
+            .map(|(w, s)| (w.to_string(), s)),
+        );
+        assert_eq!(scores.words_in(WordTier::Easy), ["bbb", "ccc"]);
+        assert_eq!(scores.words_in(WordTier::Medium), ["aaa", "ddd"]);
+        assert_eq!(scores.words_in(WordTier::Hard), ["eee", "fff"]);
+        assert_eq!(
+            scores.get("fff"),
+            Some(WordDifficulty {
+                score: 4.0,
+                tier: WordTier::Hard
+            })
+        );
+    }
+
+    #[test]
+    fn test_stable_tiers_over_fixture() {
+        let table = fixture();
+        let scores = score_words(&table);
+        assert_eq!(scores.len(), FIXTURE.len());
+        for tier in WordTier::ALL {
+            assert_eq!(scores.words_in(tier).len(), 3);
+        }
+        // Same list, same tiers
+        let again = score_words(&table);
+        for word in FIXTURE {
+            assert_eq!(scores.get(word), again.get(word));
+        }
+        // Repeated letters and an autosolver's late guess make GEESE hard
+        assert_eq!(scores.get("geese").unwrap().tier, WordTier::Hard);
+        // and the autosolver's first guess is as easy as they come
+        let first = score_and_sort(&table, &table.indices().collect::<Vec<_>>(), &table)[0].0;
+        assert_eq!(scores.get(table.word(first)).unwrap().tier, WordTier::Easy);
+    }
+
+    #[test]
+    fn test_job_steps_report_progress() {
+        let table = fixture();
+        let mut job = ScoreJob::new(&table);
+        assert_eq!(job.progress(), (0, FIXTURE.len()));
+        assert!(!job.step(&table, 1));
+        assert_eq!(job.progress(), (1, FIXTURE.len()));
+        while !job.step(&table, 2) {}
+        assert_eq!(job.progress(), (FIXTURE.len(), FIXTURE.len()));
+        let stepped = job.finish(&table);
+        let whole = score_words(&table);
+        assert_eq!(stepped.scores(), whole.scores());
+    }
+
+    #[test]
+    fn test_list_hash_follows_the_words() {
+        let table = fixture();
+        assert_eq!(list_hash(&table), list_hash(&fixture()));
+        assert_ne!(
+            list_hash(&table),
+            list_hash(&WordTable::new(&FIXTURE[1..], 5))
+        );
+        assert_eq!(list_hash(&table).len(), 16);
+    }
+
+    #[test]
+    fn test_tier_cycle() {
+        let mut tier = None;
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            tier = next_tier(tier);
+            seen.push(tier);
+        }
+        assert_eq!(
+            seen,
+            [
+                Some(WordTier::Easy),
+                Some(WordTier::Medium),
+                Some(WordTier::Hard),
+                None
+            ]
+        );
+        assert_eq!(WordTier::from_stored("hard"), Some(WordTier::Hard));
+        assert_eq!(WordTier::from_stored("brutal"), None);
+    }
+}
@@ -1,92 +1,360 @@
-use std::{collections::HashSet, fmt::Display, io::Stdout};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt::Display,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
 use crate::{
-    analysis::{ConstraintSummary, LetterAnalysis, PositionAnalysis, SolutionPoolStats},
-    solver::SolverState,
+    analysis::{
+        ConstraintSummary, LetterAnalysis, PositionAnalysis, SolutionPoolStats, SuggestionDelta,
+        YellowPlacement,
+    },
+    local_time::TimePrefs,
+    normalizer::{CharNormalizer, Spellings},
+    notifier::{Notifier, default_notifier},
+    openers::{DEFAULT_SEED, Openers, cached_openers},
+    planner::{Planner, WinWindow},
+    settings::{Difficulty, PanelKind, Settings},
+    solver::{Feedback, SolverState},
+    storage::{Database, QuickStats, new_write_id},
+    tiers::{WordDifficulty, WordScores},
+    wordlist::normalize_words,
+    wordtable::WordTable,
 };
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use crossterm::event::{self, Event};
-use ratatui::{Terminal, backend::CrosstermBackend};
+use crossterm::event::Event;
+use ratatui::{Terminal, backend::Backend};
 
 use super::{
-    history::{HistoryData, HistoryViewMode},
-    types::{GameMode, LogBuffer},
+    events::EventSource,
+    history::{HistoryData, HistoryViewMode, StatsSection},
+    publisher::StatePublisher,
+    types::{
+        BlitzState, FocusTarget, GameMode, GauntletState, HintBudget, InputHistory, InputMode,
+        LOW_REFRESH_FRAME, LogBuffer, LogSearch, MultiBoardState, Overlay, PanelCache,
+        PatternPreview, PendingCheckpoints, RecomputeJob, STATUS_ERROR_DURATION, SUGGESTION_ROWS,
+        ScoredPools, TierJob, Toast, UiJournal,
+    },
 };
 
+/// How long the event loop waits for input before ticking timers.
+const TICK_RATE: Duration = Duration::from_millis(250);
+
 /// Main application state container.
 pub struct App {
-    pub(in crate::ui) solution_words: Vec<String>,
-    pub(in crate::ui) allowed_lookup: HashSet<String>,
+    pub(in crate::ui) solution_words: WordTable,
+    pub(in crate::ui) allowed_lookup: WordTable,
+    /// The file the allowed guesses were read from; none for the bundled list.
+    pub(in crate::ui) words_path: Option<PathBuf>,
+    /// The file the answers were read from; none for the bundled list.
+    pub(in crate::ui) solutions_path: Option<PathBuf>,
     pub(in crate::ui) solver: SolverState,
     pub(in crate::ui) input: String,
-    pub(in crate::ui) suggestions: Vec<(String, usize)>,
+    /// Lines submitted this session, recalled with Up/Down.
+    pub(in crate::ui) input_history: InputHistory,
+    pub(in crate::ui) input_mode: InputMode,
+    /// Scored indices into `solution_words`, best first.
+    pub(in crate::ui) suggestions: Vec<(u32, usize)>,
+    /// Guesses made when `suggestions` was last scored.
+    pub(in crate::ui) suggestions_guesses: usize,
+    /// Openers covering the most common letters, offered before the first solver guess.
+    pub(in crate::ui) opening_trio: Openers,
+    /// The last pool scored for suggestions.
+    pub(in crate::ui) scored_pools: ScoredPools,
+    /// How the top suggestions changed with the last guess; empty after undo or reset.
+    pub(in crate::ui) suggestion_deltas: Vec<SuggestionDelta>,
+    /// Places the answer priors moved each suggestion, for those they moved.
+    pub(in crate::ui) prior_shifts: HashMap<u32, isize>,
+    /// Allowed words missing from the comparison list, marked `?` in suggestions.
+    pub(in crate::ui) flagged_words: HashSet<String>,
+    /// Letters the word lists count as the same; typed letters are folded with it.
+    pub(in crate::ui) normalizer: CharNormalizer,
+    /// Original spellings of the folded words, shown in place of the canonical ones.
+    pub(in crate::ui) spellings: Spellings,
     pub(in crate::ui) mode: GameMode,
     pub(in crate::ui) target_word: Option<String>,
     pub(in crate::ui) remaining_guesses: usize,
     pub(in crate::ui) game_won: bool,
     pub(in crate::ui) game_over: bool,
+    /// The game ended because the player gave up.
+    pub(in crate::ui) game_forfeited: bool,
+    /// Difficulty the current game started at; later changes wait for the next game.
+    pub(in crate::ui) game_difficulty: Difficulty,
+    /// The game shadows one played elsewhere: its answer is unknown and each
+    /// guess's pattern is typed in.
+    pub(in crate::ui) shadow: bool,
+    /// The game is a kids' game: no guess limit, so it only ends once solved
+    /// or left, and a few everyday words always suggested.
+    pub(in crate::ui) kids: bool,
     pub(in crate::ui) show_suggestions: bool,
     pub(in crate::ui) show_analysis: bool,
     pub(in crate::ui) letter_analysis: Option<LetterAnalysis>,
     pub(in crate::ui) position_analysis: Option<PositionAnalysis>,
     pub(in crate::ui) constraint_summary: Option<ConstraintSummary>,
+    /// Open positions for each yellow letter, in `constraint_summary.yellows` order.
+    pub(in crate::ui) yellow_placements: Vec<YellowPlacement>,
+    /// Positions already announced as solved by elimination since the guesses were last cleared.
+    pub(in crate::ui) inferred_positions_noted: BTreeSet<usize>,
     pub(in crate::ui) solution_pool_stats: Option<SolutionPoolStats>,
+    /// Likely feedback for the word typed into the solver input, kept until
+    /// the word or the pool changes.
+    pub(in crate::ui) pattern_preview: Option<PatternPreview>,
     pub(in crate::ui) entropy_history: Vec<f64>,
+    /// Candidates left after each guess; empty in phrase games.
+    pub(in crate::ui) pool_history: Vec<usize>,
+    /// The one answer the solver guesses leave, while it waits to be entered
+    /// all green or confirmed.
+    pub(in crate::ui) last_candidate: Option<u32>,
+    pub(in crate::ui) planner: Planner,
+    /// Best and worst case guesses left mid-game; `None` outside single-word games.
+    pub(in crate::ui) win_window: Option<WinWindow>,
+    /// Rows the Guesses panel is scrolled up from the latest guess.
+    pub(in crate::ui) guess_scroll: usize,
+    /// Guesses the panel had room for when it was last drawn.
+    pub(in crate::ui) guess_rows: Cell<usize>,
+    /// Whether the analysis panels are out of date with the guesses; only
+    /// ever true inside a refresh, never between frames.
     pub(in crate::ui) analysis_dirty: bool,
     pub(in crate::ui) logs: LogBuffer,
+    /// Lines the Logs panel is scrolled up from the latest entry.
+    pub(in crate::ui) log_scroll: usize,
+    /// Log lines the panel had room for when it was last drawn.
+    pub(in crate::ui) log_rows: Cell<usize>,
+    /// The logs fill the screen in place of the history views.
+    pub(in crate::ui) history_logs_expanded: bool,
+    pub(in crate::ui) log_search: Option<LogSearch>,
+    pub(in crate::ui) focus: FocusTarget,
     pub(in crate::ui) history_data: Option<HistoryData>,
     pub(in crate::ui) history_view_mode: HistoryViewMode,
     pub(in crate::ui) history_page: usize,
+    pub(in crate::ui) history_stats_section: StatsSection,
+    /// Whether archived games are listed and counted in the history stats.
+    pub(in crate::ui) history_include_archived: bool,
+    /// List history one short line per game even when the table would fit.
+    pub(in crate::ui) history_compact_list: bool,
     pub(in crate::ui) solver_session_active: bool,
     pub(in crate::ui) solver_session_start: Option<DateTime<Utc>>,
     pub(in crate::ui) solver_session_paused: bool,
+    /// Write id of the solver session's history row, kept while the session is checkpointed.
+    pub(in crate::ui) solver_session_id: String,
     pub(in crate::ui) db: crate::storage::Database,
     pub(in crate::ui) solver_session_guesses: Vec<crate::ui::history::solver_types::SolverGuess>,
+    /// Write id of the current game's history row, kept while the game is checkpointed.
+    pub(in crate::ui) game_id: String,
+    pub(in crate::ui) settings: Settings,
+    pub(in crate::ui) overlay: Option<Overlay>,
+    pub(in crate::ui) suggestion_selected: Option<usize>,
+    /// Why the last pasted transcript was rejected; shown until the next key press.
+    pub(in crate::ui) paste_error: Option<String>,
+    /// When Enter last submitted a guess, to tell key repeat from a new press.
+    pub(in crate::ui) last_submit: Option<Instant>,
+    /// When the typed word is auto-submitted, unless Backspace cancels it first.
+    pub(in crate::ui) pending_submit: Option<Instant>,
+    /// Suggestions the panel had room for when it was last drawn.
+    pub(in crate::ui) suggestion_rows: Cell<usize>,
+    /// Bumped whenever the analysis behind the analysis panels changes.
+    pub(in crate::ui) analysis_version: u64,
+    /// Bumped whenever `pattern_preview` changes.
+    pub(in crate::ui) preview_version: u64,
+    pub(in crate::ui) panel_cache: PanelCache,
+    /// Low-refresh mode asked for by `WW_LOW_REFRESH`, whatever the setting says.
+    pub(in crate::ui) low_refresh_forced: bool,
+    /// Kids' games asked for by `--kids`, whatever the setting says.
+    pub(in crate::ui) kids_forced: bool,
+    /// When the screen was last drawn, to space out low-refresh redraws.
+    pub(in crate::ui) last_draw: Option<Instant>,
+    pub(in crate::ui) blitz: Option<BlitzState>,
+    /// Gauntlet the current game belongs to, until its last game ends.
+    pub(in crate::ui) gauntlet: Option<GauntletState>,
+    /// Every board of a multi-board game; `solver` and `target_word` follow
+    /// the first one not yet solved.
+    pub(in crate::ui) boards: Option<MultiBoardState>,
+    pub(in crate::ui) game_started: Option<Instant>,
+    /// Luck of each guess in the current game, in guess order.
+    pub(in crate::ui) game_luck: Vec<f64>,
+    /// Where the answer ranked in the suggestions before each guess, filled in at game over.
+    pub(in crate::ui) game_target_ranks: Vec<Option<usize>>,
+    /// Hints taken in the current game against its budget.
+    pub(in crate::ui) game_hints: HintBudget,
+    /// How hard the current game's answer is, when answers have been scored.
+    pub(in crate::ui) game_word_difficulty: Option<WordDifficulty>,
+    /// Lifetime results for the quick stats line, read when the app starts
+    /// and when a game ends rather than on every frame; `None` if the history
+    /// couldn't be read.
+    pub(in crate::ui) quick_stats: Option<QuickStats>,
+    /// Notes left on guesses in play, by guess index, each with the word it was
+    /// left on; a note goes once its guess is undone or replaced.
+    pub(in crate::ui) guess_notes: BTreeMap<usize, (String, String)>,
+    pub(in crate::ui) toast: Option<Toast>,
+    /// The last error, shown in place of the status bar hints until it expires.
+    pub(in crate::ui) status_error: RefCell<Option<Toast>>,
+    pub(in crate::ui) history_compare_split: Option<DateTime<Utc>>,
+    /// Words never suggested, whether hidden for this session or saved permanently.
+    pub(in crate::ui) session_blacklist: BTreeSet<String>,
+    /// Optimal-word recompute in progress, advanced a batch per loop.
+    pub(in crate::ui) recompute: Option<RecomputeJob>,
+    /// Difficulty of every answer, once loaded or scored.
+    pub(in crate::ui) word_scores: Option<WordScores>,
+    /// Scoring of a word list not seen before, advanced a batch per loop.
+    pub(in crate::ui) tier_job: Option<TierJob>,
+    pub(in crate::ui) ui_journal: UiJournal,
+    /// Open game and session rows waiting to be written together.
+    pub(in crate::ui) checkpoints: PendingCheckpoints,
+    /// Keeps `current-state.json` up to date for external tools.
+    pub(in crate::ui) state_publisher: StatePublisher,
+    /// Last solver guess and pattern checked against the allowed list, and
+    /// whether any allowed word fit them.
+    pub(in crate::ui) pattern_fit: RefCell<Option<(String, Vec<Feedback>, bool)>>,
+    /// Where finished games, gauntlets and background jobs are announced
+    /// while `notify_when_done` is on.
+    pub(in crate::ui) notifier: Box<dyn Notifier>,
 }
 
 impl App {
-    pub fn new(
+    /// Use [`AppBuilder`](super::AppBuilder) to construct an app.
+    pub(super) fn new(
         words: Vec<String>,
         solution_words: Vec<String>,
         word_len: usize,
         logs: LogBuffer,
         db: crate::storage::Database,
     ) -> Self {
-        let allowed_lookup: HashSet<String> = words.iter().cloned().collect();
+        let allowed_lookup = WordTable::new(&words, word_len);
+        let solution_words = WordTable::new(&solution_words, word_len);
+        let opening_trio = cached_openers(&allowed_lookup, &solution_words, 3, DEFAULT_SEED);
+        let state_publisher = StatePublisher::new(db.data_dir());
+        let settings = Settings::load(&db).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load settings: {}", e);
+            Settings::default()
+        });
+        let session_blacklist = db.load_blacklist().map_or_else(
+            |e| {
+                tracing::warn!("Failed to load blacklist: {}", e);
+                BTreeSet::new()
+            },
+            |words| {
+                let (words, _) = normalize_words(words.iter().map(String::as_str));
+                BTreeSet::from_iter(words)
+            },
+        );
 
-        Self {
+        let quick_stats = load_quick_stats(&db);
+
+        let mut app = Self {
             solution_words,
             allowed_lookup,
+            words_path: None,
+            solutions_path: None,
             solver: SolverState::new(word_len),
             input: String::new(),
+            input_history: InputHistory::default(),
+            input_mode: InputMode::Insert,
             suggestions: Vec::new(),
+            suggestions_guesses: 0,
+            scored_pools: ScoredPools::default(),
+            opening_trio,
+            suggestion_deltas: Vec::new(),
+            prior_shifts: HashMap::new(),
+            flagged_words: HashSet::new(),
+            normalizer: CharNormalizer::Identity,
+            spellings: Spellings::default(),
             mode: GameMode::Solver,
             target_word: None,
             remaining_guesses: 6,
             game_won: false,
             game_over: false,
+            game_forfeited: false,
+            game_difficulty: Difficulty::Normal,
+            shadow: false,
+            kids: false,
             show_suggestions: true,
             show_analysis: true,
             letter_analysis: None,
             position_analysis: None,
             constraint_summary: None,
+            yellow_placements: Vec::new(),
+            inferred_positions_noted: BTreeSet::new(),
             solution_pool_stats: None,
+            pattern_preview: None,
             entropy_history: Vec::new(),
+            pool_history: Vec::new(),
+            last_candidate: None,
+            planner: Planner::new(),
+            win_window: None,
+            guess_scroll: 0,
+            guess_rows: Cell::new(6),
             analysis_dirty: true,
             logs,
+            log_scroll: 0,
+            log_rows: Cell::new(4),
+            history_logs_expanded: false,
+            log_search: None,
+            focus: FocusTarget::Input,
             history_data: None,
             history_view_mode: HistoryViewMode::Stats,
             history_page: 0,
+            history_stats_section: StatsSection::Overview,
+            history_include_archived: false,
+            history_compact_list: false,
             solver_session_active: true,
             solver_session_start: Some(Utc::now()),
             solver_session_paused: false,
+            solver_session_id: new_write_id(),
             db,
             solver_session_guesses: Vec::new(),
-        }
+            game_id: new_write_id(),
+            settings,
+            overlay: None,
+            suggestion_selected: None,
+            paste_error: None,
+            last_submit: None,
+            pending_submit: None,
+            suggestion_rows: Cell::new(SUGGESTION_ROWS),
+            analysis_version: 0,
+            preview_version: 0,
+            panel_cache: PanelCache::default(),
+            low_refresh_forced: false,
+            kids_forced: false,
+            last_draw: None,
+            blitz: None,
+            gauntlet: None,
+            boards: None,
+            game_started: None,
+            game_luck: Vec::new(),
+            game_target_ranks: Vec::new(),
+            game_hints: HintBudget::default(),
+            game_word_difficulty: None,
+            quick_stats,
+            guess_notes: BTreeMap::new(),
+            toast: None,
+            status_error: RefCell::new(None),
+            history_compare_split: None,
+            session_blacklist,
+            recompute: None,
+            word_scores: None,
+            tier_job: None,
+            ui_journal: UiJournal::default(),
+            checkpoints: PendingCheckpoints::default(),
+            state_publisher,
+            pattern_fit: RefCell::new(None),
+            notifier: default_notifier(),
+        };
+        super::handlers::SolverHandler::new(&mut app).recompute_analysis();
+        app
     }
 
-    pub fn run(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
+    /// Draw and handle events until the user quits.
+    pub fn run<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        events: &mut impl EventSource,
+    ) -> Result<()>
+    where
+        B::Error: Send + Sync + 'static,
+    {
         self.log("UI started");
 
         // Log solver session start (app starts in Solver mode)
@@ -95,18 +363,173 @@ impl App {
         }
 
         loop {
-            // Recompute analysis if needed
-            super::handlers::SolverHandler::new(self).recompute_analysis();
+            super::handlers::SolverHandler::new(self).refresh_pattern_preview();
 
-            terminal.draw(|f| self.draw(f))?;
+            if self.recompute.is_some() {
+                let _ = super::handlers::MaintenanceHandler::new(self).step_recompute();
+            }
+            if self.tier_job.is_some() {
+                super::handlers::TierHandler::new(self).step_scoring();
+            }
+            let history_loading = super::handlers::HistoryHandler::new(self).step_load();
 
-            let event = event::read()?;
-            if let Event::Key(key) = event {
-                // Use InputHandler to process keyboard input
-                if super::handlers::InputHandler::new(self).handle_key(key) {
-                    return Ok(());
+            // A skipped frame is drawn once the wait is over
+            let frame_wait = self.frame_wait(Instant::now());
+            if frame_wait.is_zero() {
+                let builds = self.panel_cache.builds();
+                terminal.draw(|f| self.draw(f))?;
+                self.last_draw = Some(Instant::now());
+                tracing::debug!(
+                    panels_built = self.panel_cache.builds() - builds,
+                    "Frame drawn"
+                );
+            }
+
+            // Wake up periodically so countdowns advance without input; don't
+            // wait at all while a recompute, scoring or history load has batches left
+            let timeout = if self.recompute.is_some() || self.tier_job.is_some() || history_loading
+            {
+                Duration::ZERO
+            } else if !frame_wait.is_zero() {
+                frame_wait.min(TICK_RATE)
+            } else {
+                TICK_RATE
+            };
+            // Wake up in time for a pending auto-submit
+            let timeout = match self.pending_submit {
+                Some(at) => timeout.min(at.saturating_duration_since(events.now())),
+                None => timeout,
+            };
+            match events.next_event(timeout)? {
+                Some(Event::Key(key)) => {
+                    // Use InputHandler to process keyboard input
+                    if super::handlers::InputHandler::new(self).handle_key(key) {
+                        super::handlers::UiStateHandler::new(self).flush();
+                        StatePublisher::finish(self);
+                        super::handlers::GameHandler::new(self).quit();
+                        return Ok(());
+                    }
+                }
+                Some(Event::Paste(text)) => {
+                    super::handlers::InputHandler::new(self).handle_paste(&text);
                 }
+                Some(_) => {}
+                None => super::handlers::GameHandler::new(self).tick(events.now()),
+            }
+            super::handlers::UiStateHandler::new(self).note_changes(events.now());
+            super::handlers::CheckpointHandler::new(self).note_due(events.now());
+            StatePublisher::note_changes(self, events.now());
+        }
+    }
+
+    /// Whether to redraw sparingly and draw plain characters, for slow
+    /// terminals and SSH.
+    pub(in crate::ui) fn low_refresh(&self) -> bool {
+        self.settings.low_refresh || self.low_refresh_forced
+    }
+
+    /// Whether new games are kids' games.
+    pub(in crate::ui) fn kids_mode(&self) -> bool {
+        self.settings.kids_mode || self.kids_forced
+    }
+
+    /// How long to hold off the next redraw; zero when it can happen now.
+    pub(in crate::ui) fn frame_wait(&self, now: Instant) -> Duration {
+        match self.last_draw {
+            Some(last) if self.low_refresh() => {
+                LOW_REFRESH_FRAME.saturating_sub(now.saturating_duration_since(last))
             }
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// The zone and clock times are shown in.
+    pub(in crate::ui) fn time_prefs(&self) -> TimePrefs {
+        TimePrefs::from_settings(&self.settings)
+    }
+
+    /// What bars are drawn with: a block, or `#` in low-refresh mode.
+    pub(in crate::ui) fn bar_glyph(&self) -> &'static str {
+        if self.low_refresh() { "#" } else { "█" }
+    }
+
+    /// Suggested words with their scores, best first.
+    pub(in crate::ui) fn suggested_words(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.suggestions
+            .iter()
+            .map(|&(i, score)| (self.solution_words.word(i), score))
+    }
+
+    /// Panels that can take focus as currently laid out, in Tab order.
+    pub(in crate::ui) fn focusable(&self) -> Vec<FocusTarget> {
+        if self.mode == GameMode::History {
+            if self.history_logs_expanded {
+                return vec![FocusTarget::Logs];
+            }
+            return vec![FocusTarget::History, FocusTarget::Logs];
+        }
+
+        let mut panels = vec![FocusTarget::Input];
+        if (self.mode == GameMode::Solver || self.show_suggestions) && !self.suggestions.is_empty()
+        {
+            panels.push(FocusTarget::Suggestions);
+        }
+        panels.push(FocusTarget::Guesses);
+        let analysis = self.mode == GameMode::Solver || self.show_analysis;
+        if analysis && self.settings.analysis_panels.contains(&PanelKind::Logs) {
+            panels.push(FocusTarget::Logs);
+        }
+        panels
+    }
+
+    /// The panel that gets keys first: `focus`, or the default panel if that
+    /// one has since been hidden. The suggestions have focus exactly while
+    /// one is selected.
+    pub(in crate::ui) fn focused(&self) -> FocusTarget {
+        if self.mode != GameMode::History && self.suggestion_selected.is_some() {
+            return FocusTarget::Suggestions;
+        }
+        let panels = self.focusable();
+        if self.focus != FocusTarget::Suggestions && panels.contains(&self.focus) {
+            self.focus
+        } else {
+            panels[0]
+        }
+    }
+
+    /// Whether guesses are entered with their pattern, as in Solver mode and
+    /// shadow games.
+    pub(in crate::ui) fn expects_pattern(&self) -> bool {
+        self.mode == GameMode::Solver || (self.mode == GameMode::Game && self.shadow)
+    }
+
+    /// Announce that something finished, if the settings ask for it.
+    pub(in crate::ui) fn notify(&self, summary: &str) {
+        if self.settings.notify_when_done {
+            self.notifier.notify(summary);
+        }
+    }
+
+    /// Persist the current settings, logging rather than failing on error.
+    pub(in crate::ui) fn save_settings(&self) {
+        if let Err(e) = self.settings.save(&self.db) {
+            self.log_error(format!("Warning: failed to save settings: {}", e));
+        }
+    }
+
+    /// Read the quick stats again, after the games they count have changed.
+    pub(in crate::ui) fn refresh_quick_stats(&mut self) {
+        self.quick_stats = load_quick_stats(&self.db);
+    }
+
+    /// Log a warning for each soft failure among `results`, with its hint.
+    pub(in crate::ui) fn warn_soft_failures(&self, results: &[crate::doctor::CheckResult]) {
+        for result in results.iter().filter(|r| r.is_soft_failure()) {
+            let hint = result.hint.map_or(String::new(), |h| format!("; {}", h));
+            self.log(format!(
+                "Warning: {}: {}{}",
+                result.name, result.detail, hint
+            ));
         }
     }
 
@@ -114,4 +537,21 @@ impl App {
         tracing::info!("{}", &msg);
         self.logs.push(msg.into());
     }
+
+    /// Log `msg` and show it in the status bar for a few seconds, over the hints.
+    pub(in crate::ui) fn log_error(&self, msg: impl Into<String> + Display) {
+        let msg = msg.into();
+        *self.status_error.borrow_mut() = Some(Toast::lasting(
+            msg.clone(),
+            Instant::now(),
+            STATUS_ERROR_DURATION,
+        ));
+        self.log(msg);
+    }
+}
+
+fn load_quick_stats(db: &Database) -> Option<QuickStats> {
+    db.quick_stats()
+        .inspect_err(|e| tracing::warn!("Failed to load quick stats: {}", e))
+        .ok()
 }
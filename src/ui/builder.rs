@@ -0,0 +1,172 @@
+//! Builder for [`App`].
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::{collections::HashSet, path::PathBuf};
+
+use crate::{
+    normalizer::{CharNormalizer, Spellings},
+    notifier::Notifier,
+    storage::{Database, EMPTY_SESSION_HOURS},
+};
+
+use super::{app::App, handlers::SolverHandler, types::LogBuffer};
+
+/// Configures and constructs an [`App`].
+///
+/// Every field has a default, so callers only set what they care about. Without
+/// a database the app keeps its history in memory for the session.
+#[derive(Default)]
+pub struct AppBuilder {
+    words: Vec<String>,
+    solutions: Vec<String>,
+    word_len: Option<usize>,
+    logs: Option<LogBuffer>,
+    db: Option<Database>,
+    transcript: Option<String>,
+    flagged_words: HashSet<String>,
+    normalizer: CharNormalizer,
+    words_path: Option<PathBuf>,
+    solutions_path: Option<PathBuf>,
+    notifier: Option<Box<dyn Notifier>>,
+}
+
+impl AppBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Words accepted as guesses.
+    pub fn words(mut self, words: Vec<String>) -> Self {
+        self.words = words;
+        self
+    }
+
+    /// Words that can be the answer.
+    pub fn solutions(mut self, solutions: Vec<String>) -> Self {
+        self.solutions = solutions;
+        self
+    }
+
+    /// Letters per word; defaults to 5.
+    pub fn word_len(mut self, word_len: usize) -> Self {
+        self.word_len = Some(word_len);
+        self
+    }
+
+    /// Buffer the app writes its log panel to; share a clone to read it elsewhere.
+    pub fn logs(mut self, logs: LogBuffer) -> Self {
+        self.logs = Some(logs);
+        self
+    }
+
+    pub fn db(mut self, db: Database) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// `word pattern` lines to enter as solver guesses before the UI starts.
+    pub fn transcript(mut self, transcript: String) -> Self {
+        self.transcript = Some(transcript);
+        self
+    }
+
+    /// Words to mark as doubtful in suggestions, e.g. ones a newer list dropped.
+    pub fn flagged_words(mut self, words: impl IntoIterator<Item = String>) -> Self {
+        self.flagged_words = words.into_iter().collect();
+        self
+    }
+
+    /// Letters to count as the same; the word lists are folded with it.
+    pub fn normalizer(mut self, normalizer: CharNormalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Files the allowed guesses and answers were read from, shown in the
+    /// About overlay; none for a bundled list.
+    pub fn wordlist_paths(mut self, words: Option<PathBuf>, solutions: Option<PathBuf>) -> Self {
+        self.words_path = words;
+        self.solutions_path = solutions;
+        self
+    }
+
+    /// Where finished games and jobs are announced; defaults to the bell, or
+    /// desktop notifications in builds with them.
+    pub fn notifier(mut self, notifier: impl Notifier + 'static) -> Self {
+        self.notifier = Some(Box::new(notifier));
+        self
+    }
+
+    pub fn build(mut self) -> Result<App> {
+        let db = match self.db {
+            Some(db) => db,
+            None => Database::open_memory()?,
+        };
+        // Before App::new, so the recovered rows are in the loaded history
+        let replayed = db.replay_pending();
+        // Anything still open was being played when an earlier run stopped
+        let closed = db.close_stale(Utc::now());
+        let purged =
+            db.purge_empty_sessions(Utc::now() - chrono::Duration::hours(EMPTY_SESSION_HOURS));
+
+        let mut spellings = Spellings::default();
+        spellings.fold_list(self.normalizer, &mut self.words);
+        spellings.fold_list(self.normalizer, &mut self.solutions);
+
+        let mut app = App::new(
+            self.words,
+            self.solutions,
+            self.word_len.unwrap_or(5),
+            self.logs.unwrap_or_default(),
+            db,
+        );
+        app.flagged_words = self
+            .flagged_words
+            .iter()
+            .map(|w| self.normalizer.normalize(w).into_owned())
+            .collect();
+        app.normalizer = self.normalizer;
+        app.words_path = self.words_path;
+        app.solutions_path = self.solutions_path;
+        app.spellings = spellings;
+        if let Some(notifier) = self.notifier {
+            app.notifier = notifier;
+        }
+        match replayed {
+            Ok(0) => {}
+            Ok(n) => app.log(format!("Recovered {} pending history write(s)", n)),
+            Err(e) => tracing::warn!("Failed to replay pending history writes: {:#}", e),
+        }
+        match closed {
+            Ok(0) => {}
+            Ok(n) => app.log(format!(
+                "Closed {} game(s) or solver session(s) left open by a crash",
+                n
+            )),
+            Err(e) => tracing::warn!("Failed to close stale history rows: {:#}", e),
+        }
+        match purged {
+            Ok(0) => {}
+            Ok(n) => tracing::info!("Purged {} empty solver session(s)", n),
+            Err(e) => tracing::warn!("Failed to purge empty solver sessions: {:#}", e),
+        }
+        if let Some(transcript) = self.transcript {
+            SolverHandler::new(&mut app)
+                .import_transcript(&transcript)
+                .context("invalid transcript")?;
+        }
+        Ok(app)
+    }
+
+    /// The standard fixture: eight five-letter words as both guesses and answers,
+    /// with an in-memory database.
+    #[cfg(test)]
+    pub fn for_tests() -> Self {
+        let words: Vec<String> = super::test_support::FIXTURE_WORDS
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        Self::new().words(words.clone()).solutions(words)
+    }
+}
@@ -0,0 +1,30 @@
+//! Where the event loop gets its input and its sense of time.
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use crossterm::event::{self, Event};
+
+/// Supplies terminal events and the clock to [`App::run`](super::App::run).
+pub trait EventSource {
+    /// Wait up to `timeout` for the next event; `None` means the wait timed out.
+    fn next_event(&mut self, timeout: Duration) -> Result<Option<Event>>;
+
+    /// The time countdowns are checked against.
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Events read from the real terminal.
+pub struct TerminalEvents;
+
+impl EventSource for TerminalEvents {
+    fn next_event(&mut self, timeout: Duration) -> Result<Option<Event>> {
+        if event::poll(timeout)? {
+            Ok(Some(event::read()?))
+        } else {
+            Ok(None)
+        }
+    }
+}
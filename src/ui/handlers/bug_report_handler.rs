@@ -0,0 +1,55 @@
+use chrono::Local;
+
+use crate::{
+    bug_report::{BugReport, Environment},
+    doctor::{self, Report},
+    paths::Paths,
+};
+
+use super::super::app::App;
+
+/// Helper struct for writing a bug report bundle from the About overlay.
+pub struct BugReportHandler<'a> {
+    app: &'a mut App,
+}
+
+impl<'a> BugReportHandler<'a> {
+    pub fn new(app: &'a mut App) -> Self {
+        Self { app }
+    }
+
+    /// Write the bundle `wordle-warlord bug-report` would, without the stored
+    /// games, into the data directory and log where it went. The doctor
+    /// checks are the ones that need no network.
+    pub fn write(&mut self) {
+        let Some(dir) = self.app.db.data_dir() else {
+            self.app
+                .log_error("No bug report: history is in memory, so there is nowhere to write it");
+            return;
+        };
+        let paths = Paths::new(dir);
+        let mut checks = vec![doctor::check_data_dir(paths.root())];
+        if let Some(database) = self.app.db.path() {
+            checks.push(doctor::check_database(database));
+        }
+        checks.push(doctor::check_terminal(
+            crossterm::terminal::size(),
+            crossterm::style::available_color_count(),
+        ));
+
+        let written = BugReport::collect(
+            &self.app.db,
+            &paths,
+            &Report::new(checks),
+            &Environment::current(),
+            false,
+        )
+        .and_then(|report| report.write(paths.root(), Local::now()));
+        match written {
+            Ok(path) => self
+                .app
+                .log(format!("Bug report written to {}", path.display())),
+            Err(e) => self.app.log_error(format!("Bug report failed: {:#}", e)),
+        }
+    }
+}
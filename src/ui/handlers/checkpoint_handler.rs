@@ -0,0 +1,41 @@
+use std::time::Instant;
+
+use super::super::{app::App, types::CHECKPOINT_DELAY};
+use super::{GameHandler, SolverHandler};
+
+/// Helper struct for writing the open game and solver session rows a little
+/// after they change, so a burst of guesses costs one write each.
+pub struct CheckpointHandler<'a> {
+    app: &'a mut App,
+}
+
+impl<'a> CheckpointHandler<'a> {
+    pub fn new(app: &'a mut App) -> Self {
+        Self { app }
+    }
+
+    /// Write the checkpoints asked for once the first has waited
+    /// [`CHECKPOINT_DELAY`].
+    pub fn note_due(&mut self, now: Instant) {
+        if self
+            .app
+            .checkpoints
+            .since
+            .is_some_and(|since| now.duration_since(since) >= CHECKPOINT_DELAY)
+        {
+            self.flush();
+        }
+    }
+
+    /// Write every checkpoint asked for now. Each is its own write, so one
+    /// the database refuses doesn't hold back the other.
+    pub fn flush(&mut self) {
+        let pending = std::mem::take(&mut self.app.checkpoints);
+        if pending.game {
+            GameHandler::new(self.app).write_checkpoint();
+        }
+        if pending.session {
+            SolverHandler::new(self.app).write_session_checkpoint();
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::search::search;
+
+use super::super::{
+    app::App,
+    types::{DictionarySearchState, GameMode, Overlay},
+};
+
+/// Longest query kept; no word is longer.
+const MAX_QUERY_LEN: usize = 32;
+
+/// Helper struct for the dictionary search popup.
+pub struct DictionaryHandler<'a> {
+    app: &'a mut App,
+}
+
+impl<'a> DictionaryHandler<'a> {
+    pub fn new(app: &'a mut App) -> Self {
+        Self { app }
+    }
+
+    pub fn open(&mut self) {
+        self.app.overlay = Some(Overlay::DictionarySearch(DictionarySearchState::default()));
+    }
+
+    /// Add a letter or wildcard to the query; anything else is ignored.
+    pub fn push(&mut self, c: char) {
+        if !(c.is_ascii_alphabetic() || c == '_' || c == '*') {
+            return;
+        }
+        if let Some(Overlay::DictionarySearch(state)) = &mut self.app.overlay
+            && state.query.len() < MAX_QUERY_LEN
+        {
+            state.query.push(c.to_ascii_lowercase());
+            self.refresh();
+        }
+    }
+
+    pub fn pop(&mut self) {
+        if let Some(Overlay::DictionarySearch(state)) = &mut self.app.overlay {
+            state.query.pop();
+            self.refresh();
+        }
+    }
+
+    /// Move the highlight by `delta` matches, clamped to the list.
+    pub fn move_selection(&mut self, delta: isize) {
+        if let Some(Overlay::DictionarySearch(state)) = &mut self.app.overlay {
+            let last = state.hits.len().saturating_sub(1);
+            state.selected = state.selected.saturating_add_signed(delta).min(last);
+        }
+    }
+
+    /// Close the popup and put the highlighted word in the input field.
+    pub fn use_selected(&mut self) {
+        let Some(Overlay::DictionarySearch(state)) = &self.app.overlay else {
+            return;
+        };
+        let Some(&index) = state.hits.get(state.selected) else {
+            return;
+        };
+
+        let word = self.app.allowed_lookup.word(index).to_string();
+        self.app.input = if self.app.mode == GameMode::Solver {
+            format!("{} ", word)
+        } else {
+            word
+        };
+        self.app.overlay = None;
+    }
+
+    /// Search again for the edited query, highlighting the first match.
+    fn refresh(&mut self) {
+        if let Some(Overlay::DictionarySearch(state)) = &mut self.app.overlay {
+            state.hits = search(&self.app.allowed_lookup, &state.query);
+            state.selected = 0;
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use super::super::{app::App, types::FocusTarget};
+use super::SuggestionHandler;
+
+/// Helper struct for moving focus between panels and scrolling the panels
+/// that only scroll.
+pub struct FocusHandler<'a> {
+    app: &'a mut App,
+}
+
+impl<'a> FocusHandler<'a> {
+    pub fn new(app: &'a mut App) -> Self {
+        Self { app }
+    }
+
+    /// Move focus `delta` panels along the Tab order, wrapping at either end.
+    pub fn cycle(&mut self, delta: isize) {
+        let panels = self.app.focusable();
+        let current = self.app.focused();
+        let index = panels.iter().position(|&p| p == current).unwrap_or(0);
+        let next = (index as isize + delta).rem_euclid(panels.len() as isize) as usize;
+        self.focus(panels[next]);
+    }
+
+    /// Give `target` focus, selecting the top suggestion when it is the
+    /// suggestions panel and dropping the selection when it is not.
+    pub fn focus(&mut self, target: FocusTarget) {
+        let selected = self.app.suggestion_selected.is_some();
+        if selected != (target == FocusTarget::Suggestions) {
+            SuggestionHandler::new(self.app).toggle_selection();
+        }
+        self.app.focus = target;
+    }
+
+    /// Return focus to the input field, or to the history view in History mode.
+    pub fn reset(&mut self) {
+        let default = self.app.focusable()[0];
+        self.focus(default);
+    }
+
+    /// Scroll the Guesses panel `delta` rows toward older guesses (negative
+    /// toward the latest), stopping at either end.
+    pub fn scroll_guesses(&mut self, delta: isize) {
+        let hidden = self
+            .app
+            .solver
+            .guesses()
+            .len()
+            .saturating_sub(self.app.guess_rows.get());
+        self.app.guess_scroll = self
+            .app
+            .guess_scroll
+            .saturating_add_signed(delta)
+            .min(hidden);
+    }
+
+    /// Scroll the Logs panel `delta` lines toward older entries (negative
+    /// toward the latest), stopping at either end.
+    pub fn scroll_logs(&mut self, delta: isize) {
+        let hidden = self
+            .app
+            .logs
+            .lines()
+            .len()
+            .saturating_sub(self.app.log_rows.get());
+        self.app.log_scroll = self.app.log_scroll.saturating_add_signed(delta).min(hidden);
+    }
+}
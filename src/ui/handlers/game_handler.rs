@@ -1,10 +1,30 @@
 use crate::{
-    solver::{Feedback, SolverState},
-    wordlist::select_random_word,
+    kids::kids_answers,
+    scoring::{expected_pool_after, luck, pattern_string},
+    settings::Difficulty,
+    solver::{
+        Feedback, Guess, SolverState, check_hard_mode, check_phrase, check_word, generate_feedback,
+        generate_phrase_feedback, join_segments,
+    },
+    storage::{APP_VERSION, new_write_id},
+    tiers::WordDifficulty,
+    wordlist::{select_random_word, select_random_words},
 };
 use chrono::Utc;
+use std::time::Instant;
 
-use super::super::{app::App, history::GameOutcome, types::GameMode};
+use super::super::{
+    app::App,
+    history::{
+        AbandonReason, BoardResult, GameGuess, GameOutcome, GameRecord, HintKind, KidsGameRecord,
+        MultiBoardRecord, UNKNOWN_TARGET,
+    },
+    types::{
+        BOARD_COUNTS, BlitzState, BlitzTurn, GAUNTLET_PAUSE, GameMode, GauntletState,
+        GauntletSummaryState, HintBudget, MultiBoardState, Overlay, PendingAction,
+        STANDARD_GUESSES, Toast,
+    },
+};
 
 /// Helper struct for managing game-specific state transitions.
 pub struct GameHandler<'a> {
@@ -19,84 +39,945 @@ impl<'a> GameHandler<'a> {
     pub fn toggle_game_mode(&mut self) {
         if self.app.mode == GameMode::Solver {
             self.app.log("Starting new game");
-
-            if self.app.solver_session_active {
-                self.app.log("Solver session abandoned");
-                self.app.solver_session_active = false;
-                self.app.solver_session_start = None;
-                self.app.solver_session_paused = false;
-                self.app.solver_session_guesses.clear();
-            }
-
+            self.abandon_solver_session(AbandonReason::Switched);
             self.start_new_game();
         } else {
             self.app.log("Switching to solver mode");
             self.app.mode = GameMode::Solver;
+            SolverHandler::new(self.app).discard_phrase_guesses();
 
             // Start a new solver session
             self.app.solver_session_active = true;
             self.app.solver_session_start = Some(Utc::now());
+            self.app.solver_session_id = new_write_id();
             self.app.solver_session_paused = false; // Ensure not paused
             self.app.log("Solver session started");
 
-            SolverHandler::new(self.app).recompute();
-            self.app.analysis_dirty = true;
+            SolverHandler::new(self.app).refresh_derived_state();
+        }
+    }
+
+    /// Confirmation prompt if `action` would discard at least one guess.
+    pub fn discard_prompt(&self, action: PendingAction) -> Option<String> {
+        if action == PendingAction::GiveUp {
+            return self
+                .game_in_progress()
+                .then(|| "Give up and reveal the answer? y/N".to_string());
+        }
+
+        let (work, guesses) = match self.app.mode {
+            GameMode::Game if !self.app.game_over => ("game", self.game_guess_count()),
+            // History only pauses a solver session
+            GameMode::Solver if action != PendingAction::OpenHistory => {
+                ("solver session", self.app.solver.guesses().len())
+            }
+            _ => return None,
+        };
+
+        (guesses > 0).then(|| {
+            let plural = if guesses == 1 { "" } else { "es" };
+            format!(
+                "You have an unfinished {} with {} guess{} — abandon it? y/N",
+                work, guesses, plural
+            )
+        })
+    }
+
+    /// Record an unfinished game with at least one guess as abandoned before leaving it.
+    pub fn abandon_game(&mut self, reason: AbandonReason) {
+        if self.app.mode != GameMode::Game || self.app.game_over {
+            return;
+        }
+
+        let guesses = self.game_guess_count();
+        if guesses > 0 {
+            self.app
+                .log(format!("Game abandoned after {} guess(es)", guesses));
+            self.finish_game(GameOutcome::Abandoned(reason));
+        }
+    }
+
+    /// Record whatever is unfinished as quit, before the app exits.
+    pub fn quit(&mut self) {
+        self.abandon_game(AbandonReason::Quit);
+        self.abandon_solver_session(AbandonReason::Quit);
+    }
+
+    /// End the game now as forfeited, revealing the answer.
+    pub fn give_up(&mut self) {
+        if !self.game_in_progress() {
+            return;
+        }
+
+        self.app.log(format!(
+            "Gave up after {} guess(es)",
+            self.game_guess_count()
+        ));
+        self.app.game_forfeited = true;
+        self.finish_game(GameOutcome::Forfeited);
+    }
+
+    /// Flash the top suggestion and the pool it should leave; the game is
+    /// marked as assisted in history. Off in Expert games.
+    pub fn reveal_best_guess(&mut self) {
+        if !self.game_in_progress() {
+            return;
+        }
+        if self.app.game_difficulty == Difficulty::Expert {
+            self.app.log("Hints are off in Expert games");
+            return;
+        }
+        // Suggestions rank single words and start after the first guess
+        let best = match self.app.suggestions.first() {
+            Some(&(best, _)) if self.app.solver.segments().len() == 1 => best,
+            _ => {
+                self.app.log("No best guess to reveal yet");
+                return;
+            }
+        };
+
+        let words = &self.app.solution_words;
+        let pool = self.app.solver.filter(words);
+        let word = words.word(best);
+        let expected = expected_pool_after(word, words, &pool);
+        let text = format!(
+            "Best guess: {} — {} → {:.1} candidates expected",
+            word.to_uppercase(),
+            pool.len(),
+            expected
+        );
+        if !self.take_hint(HintKind::BestGuess) {
+            return;
+        }
+        self.app.toast = Some(Toast::new(text, Instant::now()));
+
+        // The word stays out of the log, like the target
+        self.app.log(format!(
+            "Best guess revealed (hint {})",
+            self.app.game_hints.reveals()
+        ));
+        self.checkpoint();
+    }
+
+    /// Spend a point of the game's hint budget on `kind`, if it costs one.
+    /// Refuses with a message once the budget is used up.
+    pub fn take_hint(&mut self, kind: HintKind) -> bool {
+        let costs = self.app.game_hints.costs(kind);
+        if !self.app.game_hints.spend(kind) {
+            let text = format!(
+                "Hint budget used up ({} of {})",
+                self.app.game_hints.used.len(),
+                self.app.game_hints.limit.unwrap_or_default()
+            );
+            self.app.log(&text);
+            self.app.toast = Some(Toast::new(text, Instant::now()));
+            return false;
+        }
+        if costs && let Some(left) = self.app.game_hints.remaining() {
+            self.app
+                .log(format!("Hint taken: {} ({} left)", kind.name(), left));
+        }
+        true
+    }
+
+    fn game_in_progress(&self) -> bool {
+        self.app.mode == GameMode::Game && !self.app.game_over
+    }
+
+    /// Guesses used so far in the current game, including timed-out blitz turns.
+    fn game_guess_count(&self) -> usize {
+        if let Some(boards) = &self.app.boards {
+            return boards.guesses.len();
+        }
+        let timeouts = self.app.blitz.as_ref().map_or(0, |b| b.timeouts());
+        self.app.solver.guesses().len() + timeouts
+    }
+
+    /// End the solver session, recording it as abandoned if it has guesses.
+    fn abandon_solver_session(&mut self, reason: AbandonReason) {
+        if !self.app.solver_session_active {
+            return;
+        }
+
+        self.app.log("Solver session abandoned");
+        let guesses = std::mem::take(&mut self.app.solver_session_guesses);
+        if !guesses.is_empty() {
+            let saved = self.app.db.save_solver_session_as(
+                &self.app.solver_session_id,
+                self.app.solver_session_start.unwrap_or_else(Utc::now),
+                &guesses,
+                Some(reason),
+            );
+            if let Err(e) = saved {
+                self.app
+                    .log_error(format!("Warning: failed to save solver session: {}", e));
+            }
+        }
+        self.app.solver_session_active = false;
+        self.app.solver_session_start = None;
+        self.app.solver_session_paused = false;
+        self.app.solver_session_id = new_write_id();
+    }
+
+    /// Start a blitz game where every guess must beat a countdown.
+    pub fn start_blitz_game(&mut self) {
+        if self.app.mode == GameMode::Solver {
+            self.abandon_solver_session(AbandonReason::Switched);
+        }
+
+        self.start_new_game();
+        if self.app.mode == GameMode::Game {
+            let seconds = self.app.settings.blitz_seconds;
+            self.app.blitz = Some(BlitzState::new(seconds, Instant::now()));
+            self.app
+                .log(format!("Blitz game started: {}s per guess", seconds));
+        }
+    }
+
+    /// Start a gauntlet: `size` different targets played back to back, each
+    /// game starting shortly after the last one ends. Gauntlet games are
+    /// always single words.
+    pub fn start_gauntlet(&mut self, size: usize) {
+        let tiered = TierHandler::new(self.app).tier_words();
+        let answers = tiered.as_ref().unwrap_or(&self.app.solution_words);
+        let targets = match select_random_words(answers, size.max(1)) {
+            Ok(targets) => targets,
+            Err(e) => {
+                self.app
+                    .log_error(format!("Failed to start gauntlet: {}", e));
+                return;
+            }
+        };
+
+        if self.app.mode == GameMode::Solver {
+            self.abandon_solver_session(AbandonReason::Switched);
+        }
+
+        let id = match self.app.db.create_gauntlet(targets.len()) {
+            Ok(id) => Some(id),
+            Err(e) => {
+                self.app
+                    .log_error(format!("Warning: failed to save gauntlet: {}", e));
+                None
+            }
+        };
+        self.begin_game(vec![targets[0].clone()]);
+        self.app
+            .log(format!("Gauntlet started: {} games", targets.len()));
+        self.app.gauntlet = Some(GauntletState::new(id, targets));
+    }
+
+    /// Start the gauntlet's next game, if it has one left.
+    pub fn next_gauntlet_game(&mut self) {
+        let Some(target) = self
+            .app
+            .gauntlet
+            .as_ref()
+            .and_then(|g| g.next_target())
+            .map(str::to_string)
+        else {
+            return;
+        };
+
+        self.begin_game(vec![target]);
+        if let Some(gauntlet) = &mut self.app.gauntlet {
+            gauntlet.next_at = None;
+            let message = format!(
+                "Gauntlet game {} of {}",
+                gauntlet.results.len() + 1,
+                gauntlet.targets.len()
+            );
+            self.app.log(message);
+        }
+    }
+
+    /// Leave the gauntlet before its last game, keeping the games played.
+    pub fn leave_gauntlet(&mut self) {
+        if let Some(gauntlet) = self.app.gauntlet.take() {
+            self.app.log(format!(
+                "Left the gauntlet after {} of {} games",
+                gauntlet.results.len(),
+                gauntlet.targets.len()
+            ));
+        }
+    }
+
+    /// Start a multi-board practice game: `count` different answers, each on a
+    /// board of its own, all played with the same guesses. There is a guess
+    /// more than a standard game for each board after the first. Boards are
+    /// always single words.
+    pub fn start_multi_board_game(&mut self, count: usize) {
+        let count = count.clamp(*BOARD_COUNTS.start(), *BOARD_COUNTS.end());
+        let tiered = TierHandler::new(self.app).tier_words();
+        let answers = tiered.as_ref().unwrap_or(&self.app.solution_words);
+        let targets = match select_random_words(answers, count) {
+            Ok(targets) => targets,
+            Err(e) => {
+                self.app
+                    .log_error(format!("Failed to start multi-board game: {}", e));
+                return;
+            }
+        };
+
+        if self.app.mode == GameMode::Solver {
+            self.abandon_solver_session(AbandonReason::Switched);
+        }
+
+        self.app.gauntlet = None;
+        self.begin_game(vec![targets[0].clone()]);
+        self.app.remaining_guesses = STANDARD_GUESSES + targets.len() - 1;
+        // Answer difficulty is scored per word, not per set of boards
+        self.app.game_word_difficulty = None;
+        let word_len = self.app.solver.word_len();
+        self.app.boards = Some(MultiBoardState::new(targets, word_len));
+        self.app.log(format!(
+            "Multi-board game started: {} boards, {} guesses",
+            count, self.app.remaining_guesses
+        ));
+    }
+
+    /// Start a shadow game: played alongside a puzzle elsewhere, with its
+    /// answer unknown until an all-green pattern reveals it.
+    pub fn start_shadow_game(&mut self) {
+        if self.app.mode == GameMode::Solver {
+            self.abandon_solver_session(AbandonReason::Switched);
+        }
+
+        self.app.gauntlet = None;
+        let word_len = self.app.solution_words.word_len();
+        self.reset_game(None, vec![word_len], None);
+        self.app
+            .log("Shadow game started: enter each guess with the pattern it got");
+    }
+
+    /// Start another game of the same variant as the one that just ended.
+    pub fn restart_game(&mut self) {
+        if self.app.shadow {
+            self.start_shadow_game();
+        } else if self.app.gauntlet.is_some() {
+            self.next_gauntlet_game();
+        } else if let Some(boards) = &self.app.boards {
+            self.start_multi_board_game(boards.boards.len());
+        } else if self.app.blitz.is_some() {
+            self.start_blitz_game();
+        } else {
+            self.start_new_game();
+        }
+    }
+
+    /// Advance timers; forfeits the current blitz turn once its deadline
+    /// passes, and starts the next gauntlet game once the pause is over.
+    pub fn tick(&mut self, now: Instant) {
+        if self.app.toast.as_ref().is_some_and(|t| !t.visible(now)) {
+            self.app.toast = None;
+        }
+
+        if self.app.mode == GameMode::Game
+            && self.app.game_over
+            && let Some(gauntlet) = &self.app.gauntlet
+            && gauntlet.next_at.is_some_and(|at| now >= at)
+        {
+            self.next_gauntlet_game();
+            return;
+        }
+
+        if self.app.mode != GameMode::Game || self.app.game_over {
+            return;
+        }
+
+        if self.app.pending_submit.is_some() {
+            InputHandler::new(self.app).auto_submit_due(now);
+        }
+
+        if let Some(blitz) = &self.app.blitz
+            && now >= blitz.deadline()
+        {
+            self.forfeit_turn(now);
+        }
+    }
+
+    fn forfeit_turn(&mut self, now: Instant) {
+        if let Some(blitz) = &mut self.app.blitz {
+            blitz.turns.push(BlitzTurn::TimedOut);
+            blitz.turn_started = now;
+        }
+
+        if self.app.kids {
+            self.app.log("Guess timed out");
+            return;
+        }
+        self.app.remaining_guesses = self.app.remaining_guesses.saturating_sub(1);
+        self.app.log(format!(
+            "Guess timed out ({} remaining)",
+            self.app.remaining_guesses
+        ));
+
+        if self.app.remaining_guesses == 0 {
+            self.app.log("Game over: out of guesses");
+            self.finish_game(GameOutcome::Lost);
+        } else {
+            self.checkpoint();
         }
     }
 
     pub fn start_new_game(&mut self) {
-        match select_random_word(&self.app.solution_words, self.app.solver.word_len()) {
-            Ok(target) => {
-                tracing::info!("New game started with target word: {}", target);
-                self.app.mode = GameMode::Game;
-                self.app.target_word = Some(target);
-                self.app.remaining_guesses = 6;
-                self.app.game_won = false;
-                self.app.game_over = false;
-                self.app.show_suggestions = false;
-                self.app.show_analysis = false;
-                self.app.solver = SolverState::new(self.app.solver.word_len());
-                self.app.entropy_history.clear();
-                self.app.input.clear();
-                SolverHandler::new(self.app).recompute();
-                self.app.analysis_dirty = true;
+        self.app.gauntlet = None;
+        if self.app.kids_mode() {
+            self.start_kids_game();
+            return;
+        }
+        let word_count = self.app.settings.phrase_words.max(1);
+        let tiered = TierHandler::new(self.app).tier_words();
+        let answers = tiered.as_ref().unwrap_or(&self.app.solution_words);
+        let words: crate::error::Result<Vec<String>> = (0..word_count)
+            .map(|_| select_random_word(answers))
+            .collect();
+        match words {
+            Ok(words) => self.begin_game(words),
+            Err(e) => {
+                self.app
+                    .log_error(format!("Failed to start new game: {}", e));
             }
-            Err(_) => {
-                self.app.log("Failed to start new game: no words available");
+        }
+    }
+
+    /// Start a kids' game: an everyday answer, Easy help with the suggestions
+    /// always shown, and no guess limit, so it only ends once solved or left.
+    /// Kids' games are always single words.
+    fn start_kids_game(&mut self) {
+        let answers = kids_answers(&self.app.solution_words);
+        match select_random_word(&answers) {
+            Ok(word) => self.begin_game(vec![word]),
+            Err(e) => {
+                self.app
+                    .log_error(format!("Failed to start kids' game: {}", e));
+                return;
             }
         }
+
+        self.app.kids = true;
+        self.app.game_difficulty = Difficulty::Easy;
+        self.app.show_suggestions = true;
+        self.app.show_analysis = false;
+        self.app.game_hints = HintBudget::new(None);
+        // Answer difficulty is scored against every answer, not everyday words
+        self.app.game_word_difficulty = None;
+        self.app
+            .log("Kids' game started: guess as many times as you like");
+        SolverHandler::new(self.app).refresh_derived_state();
     }
 
-    pub fn check_game_state(&mut self, feedback: &[Feedback]) {
+    /// Reset the game state for a game whose answer is `words`, one per segment.
+    fn begin_game(&mut self, words: Vec<String>) {
+        let target = words.join(" ");
+        // Debug only so the answer stays out of everyday log files
+        tracing::debug!("New game started with target word: {}", target);
+        // Phrases aren't scored as a whole
+        let word_difficulty = match &words[..] {
+            [word] => self.app.word_scores.as_ref().and_then(|s| s.get(word)),
+            _ => None,
+        };
+        let segments = words.iter().map(|w| w.chars().count()).collect();
+        self.reset_game(Some(target), segments, word_difficulty);
+    }
+
+    /// Reset the game state for a game with segments of `segments` letters;
+    /// a game without a `target` is a shadow game.
+    fn reset_game(
+        &mut self,
+        target: Option<String>,
+        segments: Vec<usize>,
+        word_difficulty: Option<WordDifficulty>,
+    ) {
+        self.app.mode = GameMode::Game;
+        self.app.shadow = target.is_none();
+        self.app.kids = false;
+        self.app.target_word = target;
+        self.app.remaining_guesses = STANDARD_GUESSES;
+        self.app.game_won = false;
+        self.app.game_over = false;
+        self.app.game_forfeited = false;
+        self.app.game_difficulty = self.app.settings.difficulty;
+        // Easy games start with everything on; Expert can't turn it on at all
+        let easy = self.app.game_difficulty == Difficulty::Easy;
+        self.app.show_suggestions = easy;
+        self.app.show_analysis = easy;
+        self.app.suggestion_selected = None;
+        self.app.blitz = None;
+        self.app.boards = None;
+        self.app.game_started = Some(Instant::now());
+        self.app.game_id = new_write_id();
+        self.app.game_luck.clear();
+        self.app.game_target_ranks.clear();
+        self.app.game_hints = HintBudget::new(self.app.settings.hint_budget);
+        self.app.game_word_difficulty = word_difficulty;
+        self.app.toast = None;
+        self.app.solver = SolverState::with_segments(segments);
+        self.app.entropy_history.clear();
+        self.app.input.clear();
+        SolverHandler::new(self.app).refresh_derived_state();
+    }
+
+    /// Score a validated guess against the target and advance the game.
+    ///
+    /// In a phrase game `word` holds one word per segment, separated by spaces.
+    pub fn submit_guess(&mut self, word: String) {
+        if self.app.boards.is_some() {
+            self.submit_board_guess(word);
+            return;
+        }
+        let Some(target) = self.app.target_word.clone() else {
+            return;
+        };
+
+        let phrase = self.app.solver.segments().len() > 1;
+        let (feedback, checked) = if phrase {
+            (
+                generate_phrase_feedback(&target, &word),
+                check_phrase(&word, self.app.solver.segments(), &self.app.allowed_lookup),
+            )
+        } else {
+            (
+                generate_feedback(&target, &word),
+                check_word(&word, self.app.solver.word_len(), &self.app.allowed_lookup),
+            )
+        };
+        let before = self.app.solver.clone();
+        let letters: String = word.split_whitespace().collect();
+        let checked = checked.and_then(|_| match self.app.game_difficulty {
+            Difficulty::Expert => check_hard_mode(&letters, self.app.solver.guesses()),
+            _ => Ok(()),
+        });
+        let result = checked.and_then(|_| {
+            self.app
+                .solver
+                .add_guess(Guess::new(letters, feedback.clone()))
+        });
+        if let Err(e) = result {
+            self.app.log(format!("Rejected guess {}: {}", word, e));
+            return;
+        }
+
+        // Game state settles before anything is logged or written
+        if !self.app.kids {
+            self.app.remaining_guesses -= 1;
+        }
+        let luck = self.guess_luck(&before, &word);
+        self.app.game_luck.push(luck);
+        if let Some(blitz) = &mut self.app.blitz {
+            let now = Instant::now();
+            blitz
+                .turns
+                .push(BlitzTurn::Guessed(now.duration_since(blitz.turn_started)));
+            blitz.turn_started = now;
+        }
+
+        self.app.log(format!(
+            "Game guess submitted: {} (luck: {:+.2})",
+            &word, luck
+        ));
+        self.check_game_state(&feedback);
+        if !self.app.game_over {
+            self.checkpoint();
+        }
+
+        SolverHandler::new(self.app).refresh_derived_state();
+    }
+
+    /// Play a guess on every board of a multi-board game not yet solved. The
+    /// game is won once all of them are.
+    fn submit_board_guess(&mut self, word: String) {
+        let checked = check_word(&word, self.app.solver.word_len(), &self.app.allowed_lookup)
+            .and_then(|_| match self.app.game_difficulty {
+                Difficulty::Expert => check_hard_mode(&word, self.app.solver.guesses()),
+                _ => Ok(()),
+            });
+        let Some(boards) = &mut self.app.boards else {
+            return;
+        };
+        if let Err(e) = checked.and_then(|_| boards.play(&word)) {
+            self.app.log(format!("Rejected guess {}: {}", word, e));
+            return;
+        }
+
+        let (solved, total, guesses) = (boards.solved(), boards.boards.len(), boards.guesses.len());
+        let won = boards.all_solved();
+        if let Some(board) = boards.focus() {
+            self.app.solver = board.solver.clone();
+            self.app.target_word = Some(board.target.clone());
+        }
+        self.app.remaining_guesses -= 1;
+        self.app.log(format!(
+            "Game guess submitted: {} ({} of {} boards solved)",
+            &word, solved, total
+        ));
+
+        if won {
+            self.finish_game(GameOutcome::Won { guesses });
+            self.app.log("Game won!");
+        } else if self.app.remaining_guesses == 0 {
+            self.app.log("Game over: out of guesses");
+            self.finish_game(GameOutcome::Lost);
+        }
+
+        SolverHandler::new(self.app).refresh_derived_state();
+    }
+
+    /// Record a shadow game guess with the pattern the puzzle elsewhere gave
+    /// it. An all-green pattern wins, and the word becomes the game's target.
+    pub fn submit_shadow_guess(&mut self, word: String, feedback: Vec<Feedback>) {
+        if !self.app.shadow || self.app.game_over {
+            return;
+        }
+
+        let before = self.app.solver.clone();
+        let checked = check_word(&word, self.app.solver.word_len(), &self.app.allowed_lookup)
+            .and_then(|_| match self.app.game_difficulty {
+                Difficulty::Expert => check_hard_mode(&word, self.app.solver.guesses()),
+                _ => Ok(()),
+            })
+            .and_then(|_| {
+                self.app
+                    .solver
+                    .add_guess(Guess::new(word.clone(), feedback.clone()))
+            });
+        if let Err(e) = checked {
+            self.app.log(format!("Rejected guess {}: {}", word, e));
+            return;
+        }
+
+        self.app.remaining_guesses -= 1;
+        let luck = self.guess_luck(&before, &word);
+        self.app.game_luck.push(luck);
         if feedback.iter().all(|&fb| fb == Feedback::Green) {
+            self.app.target_word = Some(word.clone());
+            self.app.game_word_difficulty =
+                self.app.word_scores.as_ref().and_then(|s| s.get(&word));
+        }
+
+        self.app.log(format!(
+            "Game guess submitted: {} {} (luck: {:+.2})",
+            &word,
+            pattern_string(&feedback),
+            luck
+        ));
+        self.check_game_state(&feedback);
+        if !self.app.game_over {
+            self.checkpoint();
+        }
+
+        SolverHandler::new(self.app).refresh_derived_state();
+    }
+
+    /// Take back the last guess; only Easy games allow it, and not in blitz.
+    pub fn undo_guess(&mut self) {
+        if self.app.game_difficulty != Difficulty::Easy {
             self.app.log(format!(
-                "Target word was {}",
-                self.app.target_word.as_ref().unwrap()
+                "Undo is off in {} games",
+                self.app.game_difficulty.name()
             ));
+            return;
+        }
+        if self.app.boards.is_some() {
+            self.app.log("Undo is off in multi-board games");
+            return;
+        }
+        if self.app.game_over || self.app.blitz.is_some() || self.app.solver.guesses().is_empty() {
+            return;
+        }
+
+        self.app.log("Undo requested");
+        SolverHandler::new(self.app).undo_guess();
+        self.app.game_luck.pop();
+        if !self.app.kids {
+            self.app.remaining_guesses += 1;
+        }
+        self.checkpoint();
+    }
+
+    /// Luck of the guess just added on top of `before`; in a phrase game, the
+    /// sum over its words, each measured against its own segment's candidates.
+    fn guess_luck(&self, before: &SolverState, word: &str) -> f64 {
+        let words = &self.app.solution_words;
+        if before.segments().len() == 1 {
+            let pool_before = before.filter(words);
+            let pool_after = self.app.solver.filter(words).len();
+            return luck(word, words, &pool_before, pool_after);
+        }
+
+        word.split_whitespace()
+            .enumerate()
+            .map(|(i, segment_word)| {
+                let pool_before = before.segment(i).filter(words);
+                let pool_after = self.app.solver.segment(i).filter(words).len();
+                luck(segment_word, words, &pool_before, pool_after)
+            })
+            .sum()
+    }
+
+    pub fn check_game_state(&mut self, feedback: &[Feedback]) {
+        if feedback.iter().all(|&fb| fb == Feedback::Green) {
+            let guesses = self.game_guess_count();
+            self.finish_game(GameOutcome::Won { guesses });
             self.app.log("Game won!");
-            self.app.game_won = true;
-            self.app.game_over = true;
-            let guesses = self.app.solver.guesses().len();
-            self.save_completed_game(GameOutcome::Won { guesses });
             return;
         }
 
-        if self.app.remaining_guesses == 0 {
+        // Kids' games have no guess limit
+        if !self.app.kids && self.app.remaining_guesses == 0 {
             self.app.log("Game over: out of guesses");
-            self.app.game_over = true;
-            self.save_completed_game(GameOutcome::Lost);
+            self.finish_game(GameOutcome::Lost);
         }
     }
 
-    fn save_completed_game(&mut self, outcome: GameOutcome) {
-        if let Some(ref target) = self.app.target_word.clone() {
-            let guesses: Vec<_> = self.app.solver.guesses().to_vec();
-            let timestamp = Utc::now();
-            if let Err(e) = self.app.db.save_game(timestamp, target, &guesses, &outcome) {
-                self.app.log(format!("Warning: failed to save game: {}", e));
+    /// End the game, reveal the target, and record the result.
+    ///
+    /// This is the only place the target reaches the log panel; it stays out
+    /// of the panel while the game is in progress.
+    fn finish_game(&mut self, outcome: GameOutcome) {
+        self.app.game_over = true;
+        self.app.game_won = matches!(outcome, GameOutcome::Won { .. });
+        // Games in a gauntlet are announced once, when the gauntlet ends
+        if self.app.gauntlet.is_none()
+            && let Some(summary) = game_over_summary(&outcome)
+        {
+            self.app.notify(&summary);
+        }
+        if self.app.boards.is_some() {
+            self.finish_board_game(outcome);
+            return;
+        }
+        if self.app.kids {
+            self.finish_kids_game(outcome);
+            return;
+        }
+        if let Some(target) = self.app.target_word.clone() {
+            self.app.log(format!("Target word was {}", target));
+            self.app.game_target_ranks = SolverHandler::new(self.app).target_ranks(&target);
+        }
+        if let Some(record) = self.game_record(outcome.clone())
+            && let Err(e) = self.app.db.save_game_as(&self.app.game_id, &record)
+        {
+            self.app
+                .log_error(format!("Warning: failed to save game: {}", e));
+        }
+        self.app.refresh_quick_stats();
+        self.advance_gauntlet(outcome);
+    }
+
+    /// Reveal every board's answer and record the multi-board game as one row.
+    fn finish_board_game(&mut self, outcome: GameOutcome) {
+        let Some(boards) = &self.app.boards else {
+            return;
+        };
+        let targets: Vec<&str> = boards.boards.iter().map(|b| b.target.as_str()).collect();
+        self.app
+            .log(format!("Target words were {}", targets.join(", ")));
+
+        let record = MultiBoardRecord {
+            timestamp: Utc::now(),
+            guesses: boards.guesses.clone(),
+            boards: boards
+                .boards
+                .iter()
+                .map(|b| BoardResult {
+                    target: b.target.clone(),
+                    solved_in: b.solved_in,
+                })
+                .collect(),
+            outcome,
+            difficulty: self.app.game_difficulty,
+            duration_secs: self.app.game_started.map(|t| t.elapsed().as_secs_f64()),
+            app_version: Some(APP_VERSION.to_string()),
+        };
+        if let Err(e) = self.app.db.save_multi_board_game(&record) {
+            self.app
+                .log_error(format!("Warning: failed to save game: {}", e));
+        }
+    }
+
+    /// Reveal the answer and record the kids' game apart from the others.
+    fn finish_kids_game(&mut self, outcome: GameOutcome) {
+        let Some(target) = self.app.target_word.clone() else {
+            return;
+        };
+        self.app.log(format!("Target word was {}", target));
+
+        let record = KidsGameRecord {
+            timestamp: Utc::now(),
+            target_word: target,
+            guesses: self
+                .app
+                .solver
+                .guesses()
+                .iter()
+                .map(|g| g.word.clone())
+                .collect(),
+            outcome,
+            duration_secs: self.app.game_started.map(|t| t.elapsed().as_secs_f64()),
+            app_version: Some(APP_VERSION.to_string()),
+        };
+        if let Err(e) = self.app.db.save_kids_game(&record) {
+            self.app
+                .log_error(format!("Warning: failed to save game: {}", e));
+        }
+    }
+
+    /// Link the game that just ended to its gauntlet, then queue the next
+    /// game, or show the summary after the last one.
+    fn advance_gauntlet(&mut self, outcome: GameOutcome) {
+        let Some(gauntlet_id) = self.app.gauntlet.as_ref().map(|g| g.id) else {
+            return;
+        };
+        if let Some(id) = gauntlet_id
+            && let Err(e) = self.app.db.add_to_gauntlet(id, &self.app.game_id)
+        {
+            self.app
+                .log_error(format!("Warning: failed to link gauntlet game: {}", e));
+        }
+        // Leaving mid-game ends the gauntlet rather than moving on
+        if matches!(outcome, GameOutcome::Abandoned(_)) {
+            return;
+        }
+
+        let guesses = self.game_guess_count();
+        let won = matches!(outcome, GameOutcome::Won { .. });
+        let Some(gauntlet) = &mut self.app.gauntlet else {
+            return;
+        };
+        gauntlet.results.push((guesses, won));
+        if gauntlet.next_target().is_some() {
+            gauntlet.next_at = Some(Instant::now() + GAUNTLET_PAUSE);
+            return;
+        }
+
+        let Some(gauntlet) = self.app.gauntlet.take() else {
+            return;
+        };
+        let summary = format!(
+            "Gauntlet over: {} of {} won in {} guesses",
+            gauntlet.wins(),
+            gauntlet.targets.len(),
+            gauntlet.total_guesses()
+        );
+        self.app.notify(&summary);
+        self.app.log(summary);
+        let Some(id) = gauntlet.id else {
+            return;
+        };
+        match self.app.db.load_gauntlet_games(id) {
+            Ok(games) => {
+                self.app.overlay = Some(Overlay::GauntletSummary(GauntletSummaryState::new(
+                    gauntlet.targets.len(),
+                    games,
+                )));
             }
+            Err(e) => self
+                .app
+                .log_error(format!("Failed to load gauntlet summary: {}", e)),
+        }
+    }
+
+    /// Write the unfinished game to history as an open row, so a crash
+    /// leaves a trace; the row is replaced when the game ends. Guesses are
+    /// written a moment later together with any that follow, while a game
+    /// with none left has its row removed at once.
+    pub fn checkpoint(&mut self) {
+        // Multi-board and kids' games are only written once they end
+        if self.app.boards.is_some() || self.app.kids {
+            return;
+        }
+        if self.game_guess_count() == 0 {
+            self.write_checkpoint();
+            return;
         }
+        self.app.checkpoints.game = true;
+        self.app.checkpoints.since.get_or_insert_with(Instant::now);
+    }
+
+    /// Write the open row now, unless the game has ended since it was asked
+    /// for and its final row replaced it.
+    pub fn write_checkpoint(&mut self) {
+        if !self.game_in_progress() {
+            return;
+        }
+        // The outcome isn't stored for an open row
+        if let Some(record) = self.game_record(GameOutcome::Lost)
+            && let Err(e) = self.app.db.checkpoint_game(&self.app.game_id, &record)
+        {
+            tracing::warn!("Failed to checkpoint game: {:#}", e);
+        }
+    }
+
+    /// The current game as a history record ending in `outcome`.
+    fn game_record(&self, outcome: GameOutcome) -> Option<GameRecord> {
+        let target = match &self.app.target_word {
+            Some(target) => target.clone(),
+            // Known once won; a shadow game lost or left never shows its answer
+            None if self.app.shadow => UNKNOWN_TARGET.to_string(),
+            None => return None,
+        };
+        Some(GameRecord {
+            timestamp: Utc::now(),
+            target_word: target,
+            guesses: self.recorded_guesses(),
+            outcome,
+            blitz: self.app.blitz.is_some(),
+            duration_secs: self.app.game_started.map(|t| t.elapsed().as_secs_f64()),
+            app_version: Some(APP_VERSION.to_string()),
+            difficulty: self.app.game_difficulty,
+            hints_used: self.app.game_hints.reveals(),
+            hint_budget: self.app.game_hints.limit,
+            hints: self.app.game_hints.used.clone(),
+            archived_at: None,
+            word_difficulty: self.app.game_word_difficulty,
+        })
+    }
+
+    /// Guesses in play order, with placeholders for timed-out blitz turns.
+    fn recorded_guesses(&self) -> Vec<GameGuess> {
+        let mut guesses = self
+            .app
+            .solver
+            .guesses()
+            .iter()
+            .zip(&self.app.game_luck)
+            .enumerate()
+            .map(|(i, (g, &luck))| GameGuess {
+                luck: Some(luck),
+                // Ranks are only worked out once the game ends
+                target_rank: self.app.game_target_ranks.get(i).copied().flatten(),
+                note: self.app.guess_notes.get(&i).map(|(_, note)| note.clone()),
+                // Phrase guesses are stored with their words separated by spaces
+                ..GameGuess::new(
+                    join_segments(&g.word, self.app.solver.segments()),
+                    g.feedback.clone(),
+                )
+            });
+
+        match &self.app.blitz {
+            Some(blitz) => blitz
+                .turns
+                .iter()
+                .filter_map(|turn| match turn {
+                    BlitzTurn::Guessed(_) => guesses.next(),
+                    BlitzTurn::TimedOut => Some(GameGuess::timed_out()),
+                })
+                .collect(),
+            None => guesses.collect(),
+        }
+    }
+}
+
+/// A line announcing how a game ended, or `None` for an abandoned game.
+fn game_over_summary(outcome: &GameOutcome) -> Option<String> {
+    match outcome {
+        GameOutcome::Won { guesses: 1 } => Some("Game won in 1 guess".to_string()),
+        GameOutcome::Won { guesses } => Some(format!("Game won in {} guesses", guesses)),
+        GameOutcome::Lost => Some("Game lost".to_string()),
+        GameOutcome::Forfeited => Some("Game forfeited".to_string()),
+        GameOutcome::Abandoned(_) => None,
     }
 }
 
-use super::SolverHandler;
+use super::{InputHandler, SolverHandler, TierHandler};
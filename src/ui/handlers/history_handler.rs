@@ -1,7 +1,12 @@
 use super::super::{
     app::App,
-    history::{HistoryData, HistoryViewMode},
-    types::GameMode,
+    history::{HistoryData, HistoryViewMode, StatsSection},
+    types::{GameMode, HISTORY_BATCH},
+};
+use super::SolverHandler;
+use crate::{
+    local_time::format_local_date,
+    report::{IsoWeek, ReportFormat, WeeklyReport},
 };
 
 /// Helper struct for managing history mode state and operations.
@@ -18,7 +23,12 @@ impl<'a> HistoryHandler<'a> {
     pub fn enter_history_mode(&mut self) {
         self.app.mode = GameMode::History;
         self.app.history_view_mode = HistoryViewMode::Stats;
+        self.app.history_stats_section = StatsSection::Overview;
         self.app.history_page = 0;
+        self.app.history_compare_split = None;
+        self.app.history_include_archived = false;
+        self.app.history_logs_expanded = false;
+        self.app.log_search = None;
 
         // Pause active solver session
         if self.app.solver_session_active && !self.app.solver_session_paused {
@@ -32,6 +42,7 @@ impl<'a> HistoryHandler<'a> {
     /// Exit history mode and return to solver mode.
     pub fn exit_history_mode(&mut self) {
         self.app.mode = GameMode::Solver;
+        SolverHandler::new(self.app).discard_phrase_guesses();
 
         // Resume solver session if it was paused
         if self.app.solver_session_active && self.app.solver_session_paused {
@@ -40,27 +51,293 @@ impl<'a> HistoryHandler<'a> {
         }
     }
 
+    /// Load sessions, the stats worked out in SQL and the first batch of
+    /// games, counting archived games only if they are included. The rest of
+    /// the games load a batch per loop, see [`step_load`](Self::step_load).
     pub fn load_history(&mut self) {
         self.app.log("Loading game history...");
+        let include_archived = self.app.history_include_archived;
 
-        let games = self.app.db.load_games().unwrap_or_else(|e| {
-            self.app.log(format!("Failed to load games: {}", e));
-            Vec::new()
-        });
+        let (stored_games, first_batch) = self
+            .app
+            .db
+            .count_games(include_archived)
+            .and_then(|count| {
+                let batch = self
+                    .app
+                    .db
+                    .load_games_page(include_archived, 0, HISTORY_BATCH)?;
+                Ok((count, batch))
+            })
+            .unwrap_or_else(|e| {
+                self.app.log_error(format!("Failed to load games: {}", e));
+                (0, Vec::new())
+            });
 
         let sessions = self.app.db.load_solver_sessions().unwrap_or_else(|e| {
             self.app
-                .log(format!("Failed to load solver sessions: {}", e));
+                .log_error(format!("Failed to load solver sessions: {}", e));
             Vec::new()
         });
 
-        let game_count = games.len();
-        let session_count = sessions.len();
-        self.app.history_data = Some(HistoryData::new(games, sessions));
-        self.app.log(format!(
+        let mut data = HistoryData::new(Vec::new(), sessions);
+        data.stored_games = stored_games;
+        data.zone = self.app.time_prefs().zone;
+        data.append_games(first_batch);
+        match (
+            self.app.db.first_guess_green_rates(include_archived),
+            self.app.db.greens_per_guess(include_archived),
+        ) {
+            (Ok(rates), Ok(greens)) => {
+                data.first_guess_green_rates = rates;
+                data.greens_per_guess = greens;
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                self.app
+                    .log_error(format!("Failed to load feedback stats: {}", e));
+            }
+        }
+        match (
+            self.app.db.game_averages_by_version(include_archived),
+            self.app.db.session_averages_by_version(),
+        ) {
+            (Ok(games), Ok(sessions)) => {
+                data.game_version_averages = games;
+                data.session_version_averages = sessions;
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                self.app
+                    .log_error(format!("Failed to load version stats: {}", e));
+            }
+        }
+        match self.app.db.game_stats_by_difficulty(include_archived) {
+            Ok(stats) => data.difficulty_stats = stats,
+            Err(e) => {
+                self.app
+                    .log_error(format!("Failed to load difficulty stats: {}", e));
+            }
+        }
+        match self.app.db.game_stats_by_opener(include_archived) {
+            Ok(stats) => data.opener_stats = stats,
+            Err(e) => {
+                self.app
+                    .log_error(format!("Failed to load opener stats: {}", e));
+            }
+        }
+        match self.app.db.multi_board_stats() {
+            Ok(stats) => data.multi_board_stats = stats,
+            Err(e) => {
+                self.app
+                    .log_error(format!("Failed to load multi-board stats: {}", e));
+            }
+        }
+
+        self.app.history_data = Some(data);
+        self.log_loaded();
+    }
+
+    /// Load the next batch of games; returns false once there are none left.
+    pub fn step_load(&mut self) -> bool {
+        let include_archived = self.app.history_include_archived;
+        let Some(data) = &self.app.history_data else {
+            return false;
+        };
+        if !data.is_loading() {
+            return false;
+        }
+
+        let offset = data.games.len();
+        match self
+            .app
+            .db
+            .load_games_page(include_archived, offset, HISTORY_BATCH)
+        {
+            Ok(batch) => {
+                let Some(data) = &mut self.app.history_data else {
+                    return false;
+                };
+                // Games deleted since the count leave fewer to load
+                if batch.is_empty() {
+                    data.stored_games = data.games.len();
+                }
+                data.append_games(batch);
+                self.log_loaded();
+            }
+            Err(e) => {
+                self.app.log_error(format!("Failed to load games: {}", e));
+                if let Some(data) = &mut self.app.history_data {
+                    data.stored_games = data.games.len();
+                    data.append_games(Vec::new());
+                }
+            }
+        }
+        self.app
+            .history_data
+            .as_ref()
+            .is_some_and(HistoryData::is_loading)
+    }
+
+    /// Load batches until the game at `position` in the list is in, or all are.
+    pub fn load_through(&mut self, position: usize) {
+        while self
+            .app
+            .history_data
+            .as_ref()
+            .is_some_and(|data| data.is_loading() && data.games.len() <= position)
+        {
+            if !self.step_load() {
+                break;
+            }
+        }
+    }
+
+    /// Note when every game has loaded.
+    fn log_loaded(&mut self) {
+        let Some(data) = &self.app.history_data else {
+            return;
+        };
+        if data.is_loading() {
+            return;
+        }
+        let message = format!(
             "Loaded {} game(s) and {} solver session(s) from history",
-            game_count, session_count
-        ));
+            data.games.len(),
+            data.solver_sessions.len()
+        );
+        self.app.log(message);
+    }
+
+    /// Archive the game shown in detail view, or bring it back if it is archived.
+    ///
+    /// With archived games hidden, the view moves on to the game that takes
+    /// its place in the list.
+    pub fn toggle_selected_archived(&mut self) {
+        let Some(data) = &self.app.history_data else {
+            return;
+        };
+        let (Some(game), Some(id)) = (data.selected_game(), data.selected_game_id()) else {
+            self.app.log("Only saved games can be archived");
+            return;
+        };
+        let archive = !game.is_archived();
+        let name = format!(
+            "{} from {}",
+            game.target_word.to_uppercase(),
+            format_local_date(game.timestamp, &self.app.time_prefs())
+        );
+
+        if let Err(e) = self.app.db.set_game_archived(id, archive) {
+            self.app.log_error(format!("Failed to archive game: {}", e));
+            return;
+        }
+        self.app.refresh_quick_stats();
+        self.reload_in_place();
+        if archive {
+            self.app.log(format!(
+                "Archived {}; A in the list shows archived games",
+                name
+            ));
+        } else {
+            self.app.log(format!("Restored {} from the archive", name));
+        }
+    }
+
+    /// Show archived games in the list and count them in the stats, or stop.
+    pub fn toggle_include_archived(&mut self) {
+        self.app.history_include_archived = !self.app.history_include_archived;
+        self.reload_in_place();
+        self.app.log(if self.app.history_include_archived {
+            "Showing archived games"
+        } else {
+            "Hiding archived games"
+        });
+    }
+
+    /// List games one short line each, or as the table when it fits.
+    pub fn toggle_compact_list(&mut self) {
+        self.app.history_compact_list = !self.app.history_compact_list;
+        self.app.log(if self.app.history_compact_list {
+            "Compact history list"
+        } else {
+            "History table where it fits"
+        });
+    }
+
+    /// Show game `id` in detail view, including archived games if it is one.
+    pub fn open_game(&mut self, id: i64) {
+        self.load_through(usize::MAX);
+        let shown = |app: &mut App| {
+            app.history_data
+                .as_mut()
+                .is_some_and(|data| data.select_game_id(id))
+        };
+        if !shown(self.app) && !self.app.history_include_archived {
+            self.app.history_include_archived = true;
+            self.load_history();
+            self.load_through(usize::MAX);
+        }
+        if shown(self.app) {
+            self.app.history_view_mode = HistoryViewMode::Detail;
+        } else {
+            self.app.log("That game is no longer in history");
+        }
+    }
+
+    /// Load history again, staying on the same page and list position.
+    fn reload_in_place(&mut self) {
+        let position = self
+            .app
+            .history_data
+            .as_ref()
+            .and_then(|data| data.cursor.position());
+        self.load_history();
+        if let Some(position) = position {
+            self.load_through(position);
+        }
+
+        let Some(ref mut data) = self.app.history_data else {
+            return;
+        };
+        self.app.history_page = self
+            .app
+            .history_page
+            .min(data.total_pages().saturating_sub(1));
+        if let Some(position) = position {
+            match data.visible_count().checked_sub(1) {
+                Some(last) => data.select_game(position.min(last)),
+                None => self.app.history_view_mode = HistoryViewMode::List,
+            }
+        }
+    }
+
+    /// Write this week's report, in Markdown, to a file beside the database.
+    pub fn export_week_report(&mut self) {
+        let Some(dir) = self.app.db.data_dir() else {
+            self.app
+                .log("Week report needs a database file to write beside");
+            return;
+        };
+        let prefs = self.app.time_prefs();
+        let week = IsoWeek::current(&prefs);
+        let path = dir.join(format!("report-{}.md", week));
+        let written =
+            WeeklyReport::build(&self.app.db, week, week.local_offset(&prefs)).and_then(|report| {
+                Ok(std::fs::write(
+                    &path,
+                    report.render(ReportFormat::Markdown),
+                )?)
+            });
+
+        match written {
+            Ok(()) => self
+                .app
+                .log(format!("Wrote the {} report to {}", week, path.display())),
+            Err(e) => self.app.log(format!(
+                "Failed to write week report to {}: {}",
+                path.display(),
+                e
+            )),
+        }
     }
 
     /// Switch to the next view mode (Stats -> List -> Solver -> Stats).
@@ -81,9 +358,54 @@ impl<'a> HistoryHandler<'a> {
             }
             HistoryViewMode::Detail => HistoryViewMode::Stats,
             HistoryViewMode::Solver => HistoryViewMode::Stats,
+            HistoryViewMode::Compare => HistoryViewMode::Stats,
         };
     }
 
+    /// Step back through the views in the reverse of [`cycle_view_mode`](Self::cycle_view_mode).
+    pub fn previous_view_mode(&mut self) {
+        match self.app.history_view_mode {
+            HistoryViewMode::Stats => self.app.history_view_mode = HistoryViewMode::Solver,
+            HistoryViewMode::Solver => self.app.history_view_mode = HistoryViewMode::List,
+            HistoryViewMode::List => self.app.history_view_mode = HistoryViewMode::Stats,
+            HistoryViewMode::Detail => self.return_to_list(),
+            HistoryViewMode::Compare => self.return_to_stats(),
+        }
+    }
+
+    /// Open the before/after comparison, splitting at the midpoint game by default.
+    pub fn open_compare(&mut self) {
+        // The split and both sides need every game
+        self.load_through(usize::MAX);
+        if self.app.history_compare_split.is_none() {
+            self.reset_compare_split();
+        }
+        self.app.history_view_mode = HistoryViewMode::Compare;
+    }
+
+    /// Move the comparison split date by `days` (negative moves it earlier).
+    pub fn shift_compare_split(&mut self, days: i64) {
+        if let Some(split) = self.app.history_compare_split {
+            self.app.history_compare_split = Some(split + chrono::Duration::days(days));
+        }
+    }
+
+    /// Reset the comparison split to divide the games into two halves.
+    pub fn reset_compare_split(&mut self) {
+        self.app.history_compare_split = self
+            .app
+            .history_data
+            .as_ref()
+            .map(|data| data.default_compare_split());
+    }
+
+    /// Show the Stats view's `section`; a no-op in the other views.
+    pub fn show_stats_section(&mut self, section: StatsSection) {
+        if self.app.history_view_mode == HistoryViewMode::Stats {
+            self.app.history_stats_section = section;
+        }
+    }
+
     /// Go to the next page in list view.
     pub fn next_page(&mut self) {
         if let Some(ref data) = self.app.history_data {
@@ -92,6 +414,7 @@ impl<'a> HistoryHandler<'a> {
                 self.app.history_page += 1;
             }
         }
+        self.load_page();
     }
 
     /// Go to the previous page in list view.
@@ -101,20 +424,76 @@ impl<'a> HistoryHandler<'a> {
         }
     }
 
+    /// Go to the first page in list view.
+    pub fn first_page(&mut self) {
+        self.app.history_page = 0;
+    }
+
+    /// Go to the last page in list view.
+    pub fn last_page(&mut self) {
+        if let Some(ref data) = self.app.history_data {
+            self.app.history_page = data.total_pages().saturating_sub(1);
+        }
+        self.load_page();
+    }
+
+    /// Fetch the games of the current list page, if they haven't loaded yet.
+    fn load_page(&mut self) {
+        self.load_through((self.app.history_page + 1) * 10 - 1);
+    }
+
     /// Select a game at the given index on the current page.
     pub fn select_game_on_page(&mut self, page_index: usize) {
         let global_index = self.app.history_page * 10 + page_index;
+        self.load_through(global_index);
         if let Some(ref mut data) = self.app.history_data
-            && global_index < data.games.len()
+            && global_index < data.visible_count()
         {
             data.select_game(global_index);
             self.app.history_view_mode = HistoryViewMode::Detail;
         }
     }
 
-    /// Return from detail view to list view.
+    /// Step the detail view to the game `delta` places away in the list.
+    pub fn step_game(&mut self, delta: isize) {
+        if let Some(position) = self
+            .app
+            .history_data
+            .as_ref()
+            .and_then(|data| data.cursor.position())
+        {
+            self.load_through(position.saturating_add_signed(delta));
+        }
+        let Some(ref mut data) = self.app.history_data else {
+            return;
+        };
+        if !data.cursor.step(delta) {
+            let end = if delta < 0 { "first" } else { "last" };
+            self.app.log(format!("Already at the {} game", end));
+        }
+    }
+
+    /// Jump the detail view to the first game in the list.
+    pub fn first_game(&mut self) {
+        if let Some(ref mut data) = self.app.history_data {
+            data.cursor.first();
+        }
+    }
+
+    /// Jump the detail view to the last game in the list.
+    pub fn last_game(&mut self) {
+        self.load_through(usize::MAX);
+        if let Some(ref mut data) = self.app.history_data {
+            data.cursor.last();
+        }
+    }
+
+    /// Return from detail view to list view, on the page of the game last viewed.
     pub fn return_to_list(&mut self) {
         if let Some(ref mut data) = self.app.history_data {
+            if let Some(position) = data.cursor.position() {
+                self.app.history_page = position / 10;
+            }
             data.clear_selection();
         }
         self.app.history_view_mode = HistoryViewMode::List;
@@ -1,18 +1,31 @@
 //! Input handling and validation.
 
+use std::time::Instant;
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use crate::{
-    analysis::compute_solution_pool_stats,
-    scoring::{get_optimal_word, score_and_sort},
-    solver::{Guess, generate_feedback, parse_pattern},
+    scoring::pattern_string,
+    settings::Difficulty,
+    solver::{Feedback, check_len, check_pattern, parse_pattern},
 };
 
 use super::super::{
     app::App,
-    types::{GameMode, InputStatus, ParsedInput},
+    history::{AbandonReason, HintKind},
+    memory::memory_report,
+    system_status::SystemStatus,
+    types::{
+        AUTO_SUBMIT_GRACE, ConfirmDiscardState, DICTIONARY_ROWS, EditGuessState, FocusTarget,
+        GameMode, InputMode, InputStatus, Overlay, ParsedInput, PendingAction,
+        SUBMIT_REPEAT_WINDOW, SnapshotStep,
+    },
+};
+use super::{
+    BugReportHandler, DictionaryHandler, FocusHandler, GameHandler, HistoryHandler, LayoutHandler,
+    LogHandler, MaintenanceHandler, SnapshotHandler, SolverHandler, SuggestionHandler, TierHandler,
+    UiStateHandler,
 };
-use super::{GameHandler, HistoryHandler, SolverHandler};
 
 /// Helper struct for managing keyboard input and user interactions.
 pub struct InputHandler<'a> {
@@ -25,79 +38,854 @@ impl<'a> InputHandler<'a> {
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) -> bool {
-        // Handle history mode navigation separately
+        let quit = self.dispatch_key(key);
+        // Don't leave focus on a panel the key hid
+        self.app.focus = self.app.focused();
+        quit
+    }
+
+    fn dispatch_key(&mut self, key: KeyEvent) -> bool {
+        self.app.paste_error = None;
+
+        if self.app.recompute.is_some() && key.code == KeyCode::Esc {
+            MaintenanceHandler::new(self.app).cancel_recompute();
+            return false;
+        }
+
+        // Overlays capture every key while open
+        if self.app.overlay.is_some() {
+            return self.handle_overlay_key(key);
+        }
+
+        if key.code == KeyCode::F(1) {
+            self.app.overlay = Some(Overlay::About(SystemStatus::collect(self.app)));
+            return false;
+        }
+
+        if key.code == KeyCode::F(12) {
+            let report = memory_report(self.app);
+            self.app.log(report);
+            return false;
+        }
+
+        // F6 moves focus everywhere, including History mode where Tab cycles views
+        if key.code == KeyCode::F(6) {
+            let delta = if key.modifiers.contains(KeyModifiers::SHIFT) {
+                -1
+            } else {
+                1
+            };
+            FocusHandler::new(self.app).cycle(delta);
+            return false;
+        }
+
+        // The focused panel sees keys before the global bindings
+        match self.app.focused() {
+            FocusTarget::Guesses if self.handle_guesses_key(key) => return false,
+            FocusTarget::Logs if self.handle_logs_key(key) => return false,
+            _ => {}
+        }
+
+        // Handle history mode navigation separately; it has no input field, so
+        // the vim motions apply whatever the input mode
         if self.app.mode == GameMode::History {
+            if self.app.settings.modal_input && self.handle_history_motion(key) {
+                return false;
+            }
             return self.handle_history_key(key);
         }
 
+        if self.app.settings.modal_input {
+            let mode = self.app.input_mode;
+            if mode == InputMode::Insert
+                && key.code == KeyCode::Esc
+                && self.app.suggestion_selected.is_none()
+            {
+                self.app.input_mode = InputMode::Normal;
+                return false;
+            }
+            if mode == InputMode::Normal && self.handle_normal_key(key) {
+                return false;
+            }
+        }
+
+        if self.app.suggestion_selected.is_some() && self.handle_suggestion_key(key) {
+            return false;
+        }
+
         match (key.code, key.modifiers) {
-            (KeyCode::Char('q' | 'Q'), KeyModifiers::CONTROL) => {
-                self.app.log("Exit requested");
-                return true;
+            (KeyCode::Char(c), KeyModifiers::CONTROL) => return self.handle_control_key(c),
+            (KeyCode::Tab, _) => FocusHandler::new(self.app).cycle(1),
+            (KeyCode::BackTab, _) => FocusHandler::new(self.app).cycle(-1),
+            (KeyCode::PageUp, _) => FocusHandler::new(self.app).scroll_guesses(1),
+            (KeyCode::PageDown, _) => FocusHandler::new(self.app).scroll_guesses(-1),
+            // Alt+Enter for terminals that can't tell Ctrl+Enter from Enter
+            (KeyCode::Enter, KeyModifiers::CONTROL | KeyModifiers::ALT) => self.submit_input(true),
+            (KeyCode::Enter, _) => self.submit_input(false),
+            (KeyCode::Up, _) => self.recall_input(true),
+            (KeyCode::Down, _) => self.recall_input(false),
+            (KeyCode::Backspace, _) => {
+                self.app.input.pop();
+                self.app.pending_submit = None;
+            }
+            (KeyCode::Char(c), _) => {
+                let c = self.app.normalizer.fold(c);
+                self.app.input.push(c);
+                self.arm_auto_submit();
             }
+            _ => {}
+        }
+        false
+    }
 
-            (KeyCode::Char('g' | 'G'), KeyModifiers::CONTROL) => {
-                self.app.log("Switching to game mode");
-                GameHandler::new(self.app).toggle_game_mode();
+    /// Start the auto-submit countdown if the input is now a whole valid
+    /// guess, and stop it otherwise.
+    fn arm_auto_submit(&mut self) {
+        self.app.pending_submit = self
+            .auto_submit_applies()
+            .then(|| Instant::now() + AUTO_SUBMIT_GRACE);
+    }
+
+    /// Whether the typed word would be auto-submitted: only in a game that is
+    /// still going, never in Solver mode or while entering shadow patterns.
+    fn auto_submit_applies(&self) -> bool {
+        self.app.settings.auto_submit
+            && self.app.mode == GameMode::Game
+            && !self.app.game_over
+            && !self.app.expects_pattern()
+            && matches!(self.input_status(), InputStatus::Valid)
+    }
+
+    /// Submit the typed word once its auto-submit pause is over.
+    pub fn auto_submit_due(&mut self, now: Instant) {
+        if self.app.pending_submit.is_none_or(|at| now < at) {
+            return;
+        }
+        self.app.pending_submit = None;
+        if self.auto_submit_applies() {
+            self.submit_input(false);
+        }
+    }
+
+    /// Vim-style commands in normal mode. Returns true if the key was consumed;
+    /// letters always are, so they never reach the input field.
+    fn handle_normal_key(&mut self, key: KeyEvent) -> bool {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return false;
+        }
+
+        let selected = self.app.suggestion_selected.is_some();
+        match key.code {
+            KeyCode::Char(c @ ('j' | 'k')) => {
+                let mut handler = SuggestionHandler::new(self.app);
+                if selected {
+                    handler.move_selection(if c == 'j' { 1 } else { -1 });
+                } else {
+                    handler.toggle_selection();
+                }
             }
+            KeyCode::Char('i' | 'a') if !selected => self.app.input_mode = InputMode::Insert,
+            KeyCode::Char('u') if !selected => self.undo(),
+            // The selected suggestion's own keys still apply
+            KeyCode::Char(_) if selected => return false,
+            KeyCode::Char(_) | KeyCode::Backspace => {}
+            _ => return false,
+        }
+        true
+    }
+
+    /// Replace the input with the previous (`older`) or next submitted line.
+    fn recall_input(&mut self, older: bool) {
+        let history = &mut self.app.input_history;
+        let line = if older {
+            history.older(&self.app.mode, &self.app.input)
+        } else {
+            history.newer(&self.app.mode)
+        };
+        if let Some(line) = line {
+            self.app.input = line;
+        }
+    }
+
+    /// Undo the last guess; in Game mode only Easy difficulty allows it.
+    fn undo(&mut self) {
+        match self.app.mode {
+            GameMode::Solver => {
+                self.app.log("Undo requested");
+                SolverHandler::new(self.app).undo_guess();
+            }
+            GameMode::Game => GameHandler::new(self.app).undo_guess(),
+            GameMode::History => {}
+        }
+    }
+
+    /// Vim-style history navigation. Returns true if the key was consumed.
+    fn handle_history_motion(&mut self, key: KeyEvent) -> bool {
+        use super::super::history::HistoryViewMode;
+
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return false;
+        }
+
+        let detail = self.app.history_view_mode == HistoryViewMode::Detail;
+        let mut handler = HistoryHandler::new(self.app);
+        match key.code {
+            KeyCode::Char('g') if detail => handler.first_game(),
+            KeyCode::Char('G') if detail => handler.last_game(),
+            KeyCode::Char('g') => handler.first_page(),
+            KeyCode::Char('G') => handler.last_page(),
+            KeyCode::Char('h') => handler.previous_view_mode(),
+            KeyCode::Char('l') => handler.cycle_view_mode(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_overlay_key(&mut self, key: KeyEvent) -> bool {
+        if key.code == KeyCode::Char('q') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.app.log("Exit requested");
+            return true;
+        }
+
+        if let Some(Overlay::EditGuess(_)) = self.app.overlay {
+            self.handle_edit_guess_key(key);
+            return false;
+        }
+
+        if let Some(Overlay::ConfirmDiscard(_)) = self.app.overlay {
+            self.handle_confirm_discard_key(key);
+            return false;
+        }
+
+        if let Some(Overlay::ConfirmRepeat { .. }) = self.app.overlay {
+            self.handle_confirm_repeat_key(key);
+            return false;
+        }
+
+        if let Some(Overlay::ResumeSession(_)) = self.app.overlay {
+            self.handle_resume_session_key(key);
+            return false;
+        }
+
+        if let Some(Overlay::RestoreUiState(_)) = self.app.overlay {
+            self.handle_restore_ui_state_key(key);
+            return false;
+        }
+
+        if let Some(Overlay::GuessNote { .. }) = self.app.overlay {
+            self.handle_guess_note_key(key);
+            return false;
+        }
+
+        if let Some(Overlay::Blacklist { .. }) = self.app.overlay {
+            self.handle_blacklist_key(key);
+            return false;
+        }
+
+        if let Some(Overlay::Snapshots(_)) = self.app.overlay {
+            self.handle_snapshot_key(key);
+            return false;
+        }
+
+        if let Some(Overlay::VerifyAnswer(_)) = self.app.overlay {
+            self.handle_verify_answer_key(key);
+            return false;
+        }
+
+        if let Some(Overlay::PanelLayout { .. }) = self.app.overlay {
+            self.handle_panel_layout_key(key);
+            return false;
+        }
+
+        if let Some(Overlay::DictionarySearch(_)) = self.app.overlay {
+            self.handle_dictionary_key(key);
+            return false;
+        }
+
+        if let Some(Overlay::GauntletSummary(_)) = self.app.overlay {
+            self.handle_gauntlet_summary_key(key);
+            return false;
+        }
+
+        // The score explanation and About are read-only: any key dismisses
+        // them, b in About after writing a bug report
+        if matches!(self.app.overlay, Some(Overlay::About(_))) && key.code == KeyCode::Char('b') {
+            BugReportHandler::new(self.app).write();
+        }
+        self.app.overlay = None;
+        false
+    }
+
+    fn handle_edit_guess_key(&mut self, key: KeyEvent) {
+        let Some(Overlay::EditGuess(state)) = &mut self.app.overlay else {
+            return;
+        };
+        let guess_count = self.app.solver.guesses().len();
+        let word_len = self.app.solver.word_len();
 
-            (KeyCode::Char('s' | 'S'), KeyModifiers::CONTROL) => {
-                if self.app.mode == GameMode::Game {
-                    self.app.log("Switching to solver mode");
-                    self.app.mode = GameMode::Solver;
-                    SolverHandler::new(self.app).recompute();
+        match (&mut state.pattern, key.code) {
+            (None, KeyCode::Esc) => self.app.overlay = None,
+            (None, KeyCode::Up) => state.selected = state.selected.saturating_sub(1),
+            (None, KeyCode::Down) => {
+                state.selected = (state.selected + 1).min(guess_count.saturating_sub(1));
+            }
+            (None, KeyCode::Enter) => {
+                let current = &self.app.solver.guesses()[state.selected].feedback;
+                state.pattern = Some(pattern_string(current));
+            }
+            (Some(_), KeyCode::Esc) => state.pattern = None,
+            (Some(pattern), KeyCode::Backspace) => {
+                pattern.pop();
+            }
+            (Some(pattern), KeyCode::Char(c))
+                if pattern.len() < word_len && Feedback::try_from(c).is_ok() =>
+            {
+                pattern.push(c.to_ascii_uppercase());
+            }
+            (Some(pattern), KeyCode::Enter) => {
+                let index = state.selected;
+                let word = &self.app.solver.guesses()[index].word;
+                let parsed =
+                    check_len(pattern.len(), word_len).and_then(|_| parse_pattern(pattern));
+                match parsed {
+                    Ok(feedback) => {
+                        if let Err(e) = check_pattern(word, &feedback) {
+                            self.app.log(format!("Edit rejected: {}", e));
+                            return;
+                        }
+                        self.app.overlay = None;
+                        if let Err(e) = SolverHandler::new(self.app).edit_guess(index, feedback) {
+                            self.app.log(format!("Edit rejected: {}", e));
+                        }
+                    }
+                    Err(e) => self.app.log(format!("Edit rejected: pattern {}", e)),
                 }
             }
+            _ => {}
+        }
+    }
+
+    fn handle_gauntlet_summary_key(&mut self, key: KeyEvent) {
+        let Some(Overlay::GauntletSummary(state)) = &mut self.app.overlay else {
+            return;
+        };
+        let count = state.games.len();
+
+        match key.code {
+            KeyCode::Esc => self.app.overlay = None,
+            KeyCode::Up => state.selected = state.selected.saturating_sub(1),
+            KeyCode::Down => state.selected = (state.selected + 1).min(count.saturating_sub(1)),
+            KeyCode::Enter => {
+                let Some(id) = state.selected_id() else {
+                    return;
+                };
+                self.app.overlay = None;
+                HistoryHandler::new(self.app).enter_history_mode();
+                HistoryHandler::new(self.app).open_game(id);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_blacklist_key(&mut self, key: KeyEvent) {
+        let Some(Overlay::Blacklist { selected }) = &mut self.app.overlay else {
+            return;
+        };
+        let count = self.app.session_blacklist.len();
+
+        match key.code {
+            KeyCode::Esc => self.app.overlay = None,
+            KeyCode::Up => *selected = selected.saturating_sub(1),
+            KeyCode::Down => *selected = (*selected + 1).min(count.saturating_sub(1)),
+            KeyCode::Enter | KeyCode::Delete | KeyCode::Backspace => {
+                let index = *selected;
+                let Some(word) = self.app.session_blacklist.iter().nth(index).cloned() else {
+                    return;
+                };
+                SuggestionHandler::new(self.app).unblacklist(&word);
+
+                let remaining = self.app.session_blacklist.len();
+                self.app.overlay = (remaining > 0).then(|| Overlay::Blacklist {
+                    selected: index.min(remaining - 1),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_snapshot_key(&mut self, key: KeyEvent) {
+        let Some(Overlay::Snapshots(state)) = &mut self.app.overlay else {
+            return;
+        };
+        let count = state.snapshots.len();
+
+        match (&mut state.step, key.code) {
+            (SnapshotStep::Browse, KeyCode::Esc) => self.app.overlay = None,
+            (SnapshotStep::Browse, KeyCode::Up) => {
+                state.selected = state.selected.saturating_sub(1);
+            }
+            (SnapshotStep::Browse, KeyCode::Down) => {
+                state.selected = (state.selected + 1).min(count.saturating_sub(1));
+            }
+            (SnapshotStep::Browse, KeyCode::Char('s')) => {
+                state.step = SnapshotStep::Naming(String::new());
+            }
+            (SnapshotStep::Browse, KeyCode::Enter) => {
+                SnapshotHandler::new(self.app).request_load();
+            }
+            (SnapshotStep::Browse, KeyCode::Char('d') | KeyCode::Delete) => {
+                SnapshotHandler::new(self.app).delete_selected();
+            }
+            (SnapshotStep::Naming(_), KeyCode::Esc) => state.step = SnapshotStep::Browse,
+            (SnapshotStep::Naming(name), KeyCode::Backspace) => {
+                name.pop();
+            }
+            (SnapshotStep::Naming(name), KeyCode::Char(c)) => name.push(c),
+            (SnapshotStep::Naming(name), KeyCode::Enter) => {
+                let name = std::mem::take(name);
+                SnapshotHandler::new(self.app).save(&name);
+            }
+            (SnapshotStep::ConfirmLoad, KeyCode::Char('y' | 'Y')) => {
+                SnapshotHandler::new(self.app).load_selected();
+            }
+            (SnapshotStep::ConfirmLoad, KeyCode::Char('n' | 'N') | KeyCode::Esc) => {
+                state.step = SnapshotStep::Browse;
+            }
+            _ => {}
+        }
+    }
+
+    /// Confirm the converged word, or type the real answer after saying no.
+    fn handle_verify_answer_key(&mut self, key: KeyEvent) {
+        let Some(Overlay::VerifyAnswer(state)) = &mut self.app.overlay else {
+            return;
+        };
+        let word_len = self.app.solver.word_len();
+
+        match (&mut state.answer, key.code) {
+            (None, KeyCode::Char('y' | 'Y')) => {
+                let Some(Overlay::VerifyAnswer(state)) = self.app.overlay.take() else {
+                    return;
+                };
+                SolverHandler::new(self.app).resolve_session(&state, &state.word);
+            }
+            (None, KeyCode::Char('n' | 'N')) => state.answer = Some(String::new()),
+            (None, KeyCode::Esc) => {
+                self.app.overlay = None;
+                self.app.log("Answer left unverified");
+            }
+            (Some(_), KeyCode::Esc) => state.answer = None,
+            (Some(answer), KeyCode::Backspace) => {
+                answer.pop();
+            }
+            (Some(answer), KeyCode::Char(c))
+                if answer.len() < word_len && c.is_ascii_alphabetic() =>
+            {
+                answer.push(c.to_ascii_lowercase());
+            }
+            (Some(answer), KeyCode::Enter) if answer.len() == word_len => {
+                let Some(Overlay::VerifyAnswer(state)) = self.app.overlay.take() else {
+                    return;
+                };
+                let answer = state.answer.clone().unwrap_or_default();
+                SolverHandler::new(self.app).resolve_session(&state, &answer);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_panel_layout_key(&mut self, key: KeyEvent) {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Enter) {
+            self.app.overlay = None;
+            return;
+        }
+
+        let mut handler = LayoutHandler::new(self.app);
+        match key.code {
+            KeyCode::Up => handler.move_panel(-1),
+            KeyCode::Down => handler.move_panel(1),
+            KeyCode::Tab => handler.move_highlight(1),
+            KeyCode::BackTab => handler.move_highlight(-1),
+            KeyCode::Char(' ') => handler.toggle_visibility(),
+            _ => {}
+        }
+    }
+
+    /// Typing edits the query rather than the input field underneath.
+    fn handle_dictionary_key(&mut self, key: KeyEvent) {
+        let mut handler = DictionaryHandler::new(self.app);
+        match key.code {
+            KeyCode::Esc => self.app.overlay = None,
+            KeyCode::Up => handler.move_selection(-1),
+            KeyCode::Down => handler.move_selection(1),
+            KeyCode::PageUp => handler.move_selection(-(DICTIONARY_ROWS as isize)),
+            KeyCode::PageDown => handler.move_selection(DICTIONARY_ROWS as isize),
+            KeyCode::Enter => handler.use_selected(),
+            KeyCode::Backspace => handler.pop(),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => handler.push(c),
+            _ => {}
+        }
+    }
+
+    /// Resolve a pending confirmation; keys other than y/n/Esc are ignored.
+    fn handle_confirm_discard_key(&mut self, key: KeyEvent) {
+        let Some(Overlay::ConfirmDiscard(state)) = &self.app.overlay else {
+            return;
+        };
+        let action = state.action;
+
+        match key.code {
+            KeyCode::Char('y' | 'Y') => {
+                self.app.overlay = None;
+                self.perform(action);
+            }
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+                self.app.overlay = None;
+                self.app.log("Kept unfinished work");
+            }
+            _ => {}
+        }
+    }
+
+    /// Submit the held repeat guess on y; otherwise leave it in the input to edit.
+    fn handle_confirm_repeat_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('y' | 'Y') => {
+                self.app.overlay = None;
+                self.submit_valid_input();
+            }
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => self.app.overlay = None,
+            _ => {}
+        }
+    }
+
+    /// Resume the offered session with y or Enter; n or Esc starts afresh.
+    fn handle_resume_session_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('y' | 'Y') | KeyCode::Enter => {
+                SolverHandler::new(self.app).resume_offered_session();
+                UiStateHandler::new(self.app).offer_restore();
+            }
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+                SolverHandler::new(self.app).decline_offered_session();
+                UiStateHandler::new(self.app).offer_restore();
+            }
+            _ => {}
+        }
+    }
+
+    /// Restore the last run's view with y or Enter; n or Esc keeps the fresh one.
+    fn handle_restore_ui_state_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('y' | 'Y') | KeyCode::Enter => {
+                UiStateHandler::new(self.app).restore_offered()
+            }
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+                UiStateHandler::new(self.app).decline_offered()
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_guess_note_key(&mut self, key: KeyEvent) {
+        let Some(Overlay::GuessNote { text }) = &mut self.app.overlay else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => self.app.overlay = None,
+            KeyCode::Backspace => {
+                text.pop();
+            }
+            KeyCode::Char(c) => text.push(c),
+            KeyCode::Enter => {
+                let text = std::mem::take(text);
+                self.app.overlay = None;
+                SolverHandler::new(self.app).save_guess_note(&text);
+            }
+            _ => {}
+        }
+    }
+
+    /// Run `action`, first asking for confirmation if it would discard guesses.
+    fn request(&mut self, action: PendingAction) {
+        if self.app.settings.confirm_discard
+            && let Some(prompt) = GameHandler::new(self.app).discard_prompt(action)
+        {
+            self.app.overlay = Some(Overlay::ConfirmDiscard(ConfirmDiscardState {
+                action,
+                prompt,
+            }));
+            return;
+        }
+
+        self.perform(action);
+    }
 
-            (KeyCode::Char('r' | 'R'), KeyModifiers::CONTROL) => {
+    fn perform(&mut self, action: PendingAction) {
+        // Giving up records its own outcome rather than an abandoned loss
+        if action != PendingAction::GiveUp {
+            GameHandler::new(self.app).abandon_game(AbandonReason::Switched);
+            GameHandler::new(self.app).leave_gauntlet();
+        }
+
+        match action {
+            PendingAction::ToggleGameMode => {
+                self.app.log("Switching to game mode");
+                GameHandler::new(self.app).toggle_game_mode();
+            }
+            PendingAction::StartBlitz => {
+                self.app.log("Starting blitz game");
+                GameHandler::new(self.app).start_blitz_game();
+            }
+            PendingAction::StartGauntlet => {
+                let size = self.app.settings.gauntlet_size;
+                GameHandler::new(self.app).start_gauntlet(size);
+            }
+            PendingAction::StartShadow => GameHandler::new(self.app).start_shadow_game(),
+            PendingAction::StartMultiBoard => {
+                let count = self.app.settings.board_count;
+                GameHandler::new(self.app).start_multi_board_game(count);
+            }
+            PendingAction::SwitchToSolver => {
+                self.app.log("Switching to solver mode");
+                self.app.mode = GameMode::Solver;
+                SolverHandler::new(self.app).discard_phrase_guesses();
+                SolverHandler::new(self.app).refresh_derived_state();
+            }
+            PendingAction::OpenHistory => {
                 self.app.log("Switching to history mode");
                 HistoryHandler::new(self.app).enter_history_mode();
             }
+            PendingAction::GiveUp => GameHandler::new(self.app).give_up(),
+        }
+    }
+
+    /// Keys offered to the suggestions panel while a row is selected.
+    /// Returns true if the key was consumed.
+    fn handle_suggestion_key(&mut self, key: KeyEvent) -> bool {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return false;
+        }
 
-            (KeyCode::Char('h' | 'H'), KeyModifiers::CONTROL) => {
-                if self.app.mode == GameMode::Game {
-                    self.app.show_suggestions = !self.app.show_suggestions;
-                    let status = if self.app.show_suggestions {
-                        "shown"
-                    } else {
-                        "hidden"
-                    };
-                    self.app.log(format!("Suggestions {}", status));
+        let mut handler = SuggestionHandler::new(self.app);
+        match key.code {
+            KeyCode::Up => handler.move_selection(-1),
+            KeyCode::Down => handler.move_selection(1),
+            KeyCode::Enter => handler.use_selected(),
+            KeyCode::Esc => handler.toggle_selection(),
+            KeyCode::Char('i') => handler.explain_selected(),
+            KeyCode::Char('g') => handler.toggle_hide_gray(),
+            KeyCode::Char('p') => handler.toggle_answer_priors(),
+            KeyCode::Char('x') => handler.blacklist_selected(false),
+            KeyCode::Char('X') => handler.blacklist_selected(true),
+            KeyCode::Char('b') => handler.open_blacklist(),
+            KeyCode::Char('e') => handler.export_pool(false),
+            KeyCode::Char('E') => handler.export_pool(true),
+            // Swallow other characters so they don't leak into the input field
+            KeyCode::Char(_) => {}
+            _ => return false,
+        }
+        true
+    }
+
+    /// Keys offered to the Guesses panel while it has focus.
+    /// Returns true if the key was consumed.
+    fn handle_guesses_key(&mut self, key: KeyEvent) -> bool {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return false;
+        }
+
+        let mut handler = FocusHandler::new(self.app);
+        match key.code {
+            KeyCode::Up => handler.scroll_guesses(1),
+            KeyCode::Down => handler.scroll_guesses(-1),
+            KeyCode::Home => handler.scroll_guesses(isize::MAX),
+            KeyCode::End => handler.scroll_guesses(isize::MIN),
+            KeyCode::Esc | KeyCode::Enter => handler.reset(),
+            // Swallow typing so it doesn't reach the unfocused input field
+            KeyCode::Char(_) | KeyCode::Backspace => {}
+            _ => return false,
+        }
+        true
+    }
+
+    /// Keys offered to the Logs panel while it has focus.
+    /// Returns true if the key was consumed.
+    fn handle_logs_key(&mut self, key: KeyEvent) -> bool {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return false;
+        }
+        if self.app.mode == GameMode::History && self.handle_history_logs_key(key) {
+            return true;
+        }
+
+        let page = self.app.log_rows.get().max(1) as isize;
+        let history = self.app.mode == GameMode::History;
+        let mut handler = FocusHandler::new(self.app);
+        match key.code {
+            KeyCode::Up => handler.scroll_logs(1),
+            KeyCode::Down => handler.scroll_logs(-1),
+            KeyCode::PageUp => handler.scroll_logs(page),
+            KeyCode::PageDown => handler.scroll_logs(-page),
+            KeyCode::Home => handler.scroll_logs(isize::MAX),
+            KeyCode::End => handler.scroll_logs(isize::MIN),
+            KeyCode::Esc => handler.reset(),
+            // History keys still apply; elsewhere typing must not reach the input field
+            KeyCode::Enter | KeyCode::Char(_) | KeyCode::Backspace if !history => {}
+            _ => return false,
+        }
+        true
+    }
+
+    /// Keys only the history-mode logs take: searching them, and switching
+    /// between the strip and the full-screen view.
+    fn handle_history_logs_key(&mut self, key: KeyEvent) -> bool {
+        if self.app.log_search.as_ref().is_some_and(|s| s.editing) {
+            LogHandler::new(self.app).edit_search(key);
+            return true;
+        }
+
+        let searching = self.app.log_search.is_some();
+        let expanded = self.app.history_logs_expanded;
+        let mut handler = LogHandler::new(self.app);
+        match key.code {
+            KeyCode::Char('/') => handler.start_search(),
+            KeyCode::Char('n') if searching => handler.jump(true),
+            KeyCode::Char('N') if searching => handler.jump(false),
+            KeyCode::Char('L') => handler.toggle_expanded(),
+            KeyCode::Esc if searching => self.app.log_search = None,
+            KeyCode::Esc if expanded => handler.toggle_expanded(),
+            // The history views are hidden behind the expanded logs
+            KeyCode::Enter | KeyCode::Char(_) | KeyCode::Backspace | KeyCode::Tab if expanded => {}
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_control_key(&mut self, c: char) -> bool {
+        match c.to_ascii_lowercase() {
+            'q' => {
+                self.app.log("Exit requested");
+                return true;
+            }
+
+            'g' => self.request(PendingAction::ToggleGameMode),
+
+            't' => self.request(PendingAction::StartBlitz),
+
+            'n' => self.request(PendingAction::StartGauntlet),
+
+            'u' => self.request(PendingAction::StartShadow),
+
+            'k' => self.request(PendingAction::StartMultiBoard),
+
+            's' if self.app.mode == GameMode::Game => self.request(PendingAction::SwitchToSolver),
+
+            'r' => self.request(PendingAction::OpenHistory),
+
+            'x' if self.app.mode == GameMode::Game => self.request(PendingAction::GiveUp),
+
+            'w' if self.app.mode != GameMode::History => TierHandler::new(self.app).cycle_tier(),
+
+            'd' if self.app.mode != GameMode::History => {
+                let difficulty = self.app.settings.difficulty.next();
+                self.app.settings.difficulty = difficulty;
+                self.app.save_settings();
+                let when = if self.app.mode == GameMode::Game && !self.app.game_over {
+                    " from the next game"
+                } else {
+                    ""
+                };
+                self.app
+                    .log(format!("Difficulty: {}{}", difficulty.name(), when));
+            }
+
+            // Kids' games always show their suggestions
+            'h' if self.app.mode == GameMode::Game
+                && self.app.game_difficulty != Difficulty::Expert
+                && !self.app.kids =>
+            {
+                if !self.app.show_suggestions
+                    && !self.app.game_over
+                    && !GameHandler::new(self.app).take_hint(HintKind::Suggestions)
+                {
+                    return false;
                 }
+                self.app.show_suggestions = !self.app.show_suggestions;
+                if !self.app.show_suggestions {
+                    self.app.suggestion_selected = None;
+                }
+                let status = if self.app.show_suggestions {
+                    "shown"
+                } else {
+                    "hidden"
+                };
+                self.app.log(format!("Suggestions {}", status));
             }
 
-            (KeyCode::Char('a' | 'A'), KeyModifiers::CONTROL) => {
-                if self.app.mode == GameMode::Game {
-                    self.app.show_analysis = !self.app.show_analysis;
-                    let status = if self.app.show_analysis {
-                        "shown"
-                    } else {
-                        "hidden"
-                    };
-                    self.app.log(format!("Analysis panels {}", status));
+            'a' if self.app.mode == GameMode::Game
+                && self.app.game_difficulty != Difficulty::Expert =>
+            {
+                if !self.app.show_analysis
+                    && !self.app.game_over
+                    && !GameHandler::new(self.app).take_hint(HintKind::Analysis)
+                {
+                    return false;
                 }
+                self.app.show_analysis = !self.app.show_analysis;
+                let status = if self.app.show_analysis {
+                    "shown"
+                } else {
+                    "hidden"
+                };
+                self.app.log(format!("Analysis panels {}", status));
+            }
+
+            'b' if self.app.mode == GameMode::Solver => SnapshotHandler::new(self.app).open(),
+            'b' if self.app.mode == GameMode::Game => {
+                GameHandler::new(self.app).reveal_best_guess()
             }
 
-            (KeyCode::Char('z' | 'Z'), KeyModifiers::CONTROL) => {
-                // Undo only works in Solver mode, not in Game mode
-                if self.app.mode == GameMode::Solver {
-                    self.app.log("Undo requested");
-                    SolverHandler::new(self.app).undo_guess();
+            'l' if self.app.mode == GameMode::Solver || self.app.show_analysis => {
+                SolverHandler::new(self.app).toggle_letter_information();
+            }
+
+            'p' if self.app.mode == GameMode::Solver || self.app.show_analysis => {
+                LayoutHandler::new(self.app).open();
+            }
+
+            'e' if self.app.mode == GameMode::Solver => {
+                let guess_count = self.app.solver.guesses().len();
+                if guess_count == 0 {
+                    self.app.log("No guesses to edit");
+                } else {
+                    self.app.overlay = Some(Overlay::EditGuess(EditGuessState {
+                        selected: guess_count - 1,
+                        pattern: None,
+                    }));
                 }
             }
 
-            (KeyCode::Enter, _) => self.submit_input(),
-            (KeyCode::Backspace, _) => {
-                self.app.input.pop();
+            'y' if self.app.mode == GameMode::Solver => {
+                SolverHandler::new(self.app).confirm_last_candidate();
+            }
+
+            'f' => DictionaryHandler::new(self.app).open(),
+
+            'z' => self.undo(),
+
+            // Most terminals can't send Ctrl+;, so Ctrl+O opens the same popup
+            ';' | 'o' if self.app.mode != GameMode::History => {
+                SolverHandler::new(self.app).open_guess_note();
             }
-            (KeyCode::Char(c), _) => self.app.input.push(c),
+
             _ => {}
         }
         false
     }
 
     fn handle_history_key(&mut self, key: KeyEvent) -> bool {
-        use super::super::history::HistoryViewMode;
+        use super::super::history::{HistoryViewMode, StatsSection};
 
         match key.code {
             KeyCode::Char('q' | 'Q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
@@ -114,35 +902,100 @@ impl<'a> InputHandler<'a> {
                 HistoryHandler::new(self.app).cycle_view_mode();
             }
 
-            KeyCode::PageDown => {
-                if self.app.history_view_mode == HistoryViewMode::List {
-                    HistoryHandler::new(self.app).next_page();
-                }
+            KeyCode::Char('L') => LogHandler::new(self.app).toggle_expanded(),
+
+            KeyCode::PageDown if self.app.history_view_mode == HistoryViewMode::List => {
+                HistoryHandler::new(self.app).next_page();
             }
 
-            KeyCode::PageUp => {
-                if self.app.history_view_mode == HistoryViewMode::List {
-                    HistoryHandler::new(self.app).prev_page();
-                }
+            KeyCode::PageUp if self.app.history_view_mode == HistoryViewMode::List => {
+                HistoryHandler::new(self.app).prev_page();
+            }
+
+            KeyCode::Left | KeyCode::Char('p')
+                if self.app.history_view_mode == HistoryViewMode::Detail =>
+            {
+                HistoryHandler::new(self.app).step_game(-1);
+            }
+
+            KeyCode::Right | KeyCode::Char('n')
+                if self.app.history_view_mode == HistoryViewMode::Detail =>
+            {
+                HistoryHandler::new(self.app).step_game(1);
+            }
+
+            KeyCode::Home if self.app.history_view_mode == HistoryViewMode::Detail => {
+                HistoryHandler::new(self.app).first_game();
+            }
+
+            KeyCode::End if self.app.history_view_mode == HistoryViewMode::Detail => {
+                HistoryHandler::new(self.app).last_game();
+            }
+
+            KeyCode::PageDown if self.app.history_view_mode == HistoryViewMode::Stats => {
+                HistoryHandler::new(self.app).show_stats_section(StatsSection::Timing);
+            }
+
+            KeyCode::PageUp if self.app.history_view_mode == HistoryViewMode::Stats => {
+                HistoryHandler::new(self.app).show_stats_section(StatsSection::Overview);
+            }
+
+            KeyCode::Char('c') if self.app.history_view_mode == HistoryViewMode::Stats => {
+                HistoryHandler::new(self.app).open_compare();
+            }
+
+            KeyCode::Char('a') if self.app.history_view_mode == HistoryViewMode::Detail => {
+                HistoryHandler::new(self.app).toggle_selected_archived();
+            }
+
+            KeyCode::Char('A')
+                if matches!(
+                    self.app.history_view_mode,
+                    HistoryViewMode::Stats | HistoryViewMode::List
+                ) =>
+            {
+                HistoryHandler::new(self.app).toggle_include_archived();
+            }
+
+            KeyCode::Char('v') if self.app.history_view_mode == HistoryViewMode::List => {
+                HistoryHandler::new(self.app).toggle_compact_list();
+            }
+
+            KeyCode::Char('w') if self.app.history_view_mode == HistoryViewMode::Stats => {
+                HistoryHandler::new(self.app).export_week_report();
+            }
+
+            KeyCode::Char('o') if self.app.history_view_mode == HistoryViewMode::Solver => {
+                MaintenanceHandler::new(self.app).start_recompute(false);
+            }
+
+            KeyCode::PageDown if self.app.history_view_mode == HistoryViewMode::Compare => {
+                HistoryHandler::new(self.app).shift_compare_split(1);
+            }
+
+            KeyCode::PageUp if self.app.history_view_mode == HistoryViewMode::Compare => {
+                HistoryHandler::new(self.app).shift_compare_split(-1);
+            }
+
+            KeyCode::Home if self.app.history_view_mode == HistoryViewMode::Compare => {
+                HistoryHandler::new(self.app).reset_compare_split();
             }
 
             KeyCode::Esc => match self.app.history_view_mode {
                 HistoryViewMode::Detail => {
                     HistoryHandler::new(self.app).return_to_list();
                 }
-                HistoryViewMode::List => {
+                HistoryViewMode::List | HistoryViewMode::Compare => {
                     HistoryHandler::new(self.app).return_to_stats();
                 }
                 _ => {}
             },
 
-            KeyCode::Char(c @ '0'..='9') => {
-                if self.app.history_view_mode == HistoryViewMode::List {
-                    let digit = c.to_digit(10).unwrap() as usize;
-                    // Map: 1-9 -> items 0-8, 0 -> item 9 (the 10th item)
-                    let index = if digit == 0 { 9 } else { digit - 1 };
-                    HistoryHandler::new(self.app).select_game_on_page(index);
-                }
+            KeyCode::Char(c @ '0'..='9') if self.app.history_view_mode == HistoryViewMode::List => {
+                let digit = c.to_digit(10).unwrap() as usize;
+                // Map: 1-9 -> items 0-8, 0 -> item 9 (the 10th item)
+                let index = if digit == 0 { 9 } else { digit - 1 };
+                HistoryHandler::new(self.app).select_game_on_page(index);
             }
 
             _ => {}
@@ -151,10 +1004,14 @@ impl<'a> InputHandler<'a> {
         false
     }
 
-    fn parse_input(&self) -> ParsedInput {
-        if self.app.mode == GameMode::Game {
+    pub fn parse_input(&self) -> ParsedInput {
+        if !self.app.expects_pattern() {
             let word = self.app.input.trim().to_lowercase();
-            if word.len() != self.app.solver.word_len() {
+            let letters = word.split_whitespace().map(str::len).sum::<usize>();
+            let segments = self.app.solver.segments().len();
+            if letters != self.app.solver.word_len()
+                || (segments > 1 && word.split_whitespace().count() != segments)
+            {
                 return ParsedInput::Invalid;
             }
             // In game mode, we don't parse pattern - it's generated
@@ -181,212 +1038,120 @@ impl<'a> InputHandler<'a> {
         }
 
         let feedback = match parse_pattern(pattern) {
-            Ok(f) => f,
-            Err(_) => return ParsedInput::Invalid,
+            Ok(f) if check_pattern(&word, &f).is_ok() => f,
+            _ => return ParsedInput::Invalid,
         };
 
         ParsedInput::Valid { word, feedback }
     }
 
-    pub fn input_status(&self) -> InputStatus {
-        if self.app.mode == GameMode::Game {
-            let guess = self.app.input.trim();
-
-            if guess.is_empty() {
-                return InputStatus::Incomplete;
-            }
-
-            if guess.len() != self.app.solver.word_len() {
-                return InputStatus::Invalid("guess length mismatch");
-            }
-
-            let guess_lower = guess.to_lowercase();
-
-            if !self.app.allowed_lookup.contains(&guess_lower) {
-                return InputStatus::Invalid("word not in allowed list");
-            }
-
-            return InputStatus::Valid;
-        }
-
-        let parts: Vec<_> = self.app.input.split_whitespace().collect();
-
-        if parts.is_empty() {
-            return InputStatus::Incomplete;
-        }
-
-        if parts.len() == 1 {
-            return InputStatus::Incomplete;
+    /// Insert pasted text. In Solver mode a paste of several lines is imported
+    /// as a `word pattern` transcript instead.
+    pub fn handle_paste(&mut self, text: &str) {
+        if self.app.overlay.is_some() || self.app.mode == GameMode::History {
+            return;
         }
+        self.app.paste_error = None;
 
-        if parts.len() > 2 {
-            return InputStatus::Invalid("too many fields");
+        let lines = text.lines().filter(|l| !l.trim().is_empty()).count();
+        if self.app.mode == GameMode::Solver && lines > 1 {
+            if let Err(e) = SolverHandler::new(self.app).import_transcript(text) {
+                self.app.log(format!("Transcript rejected: {}", e));
+                self.app.paste_error = Some(e.to_string());
+            }
+            return;
         }
 
-        let guess = parts[0];
-        let pattern = parts[1];
-
-        if guess.len() != self.app.solver.word_len() {
-            return InputStatus::Invalid("guess length mismatch");
-        } else if !self.app.allowed_lookup.contains(&guess.to_lowercase()) {
-            return InputStatus::Invalid("word not in allowed list");
-        }
+        let normalizer = self.app.normalizer;
+        self.app.input.extend(
+            text.chars()
+                .filter(|c| !c.is_control())
+                .map(|c| normalizer.fold(c)),
+        );
+    }
 
-        if pattern.len() != self.app.solver.word_len() {
-            return InputStatus::Invalid("pattern length mismatch");
-        }
+    pub fn input_status(&self) -> InputStatus {
+        self.app.input_status_immutable()
+    }
 
-        if parse_pattern(pattern).is_err() {
-            return InputStatus::Invalid("pattern must be G/Y/X");
+    /// Submit the input if it's valid; `force` lets through a solver pattern
+    /// no allowed word fits.
+    fn submit_input(&mut self, force: bool) {
+        self.app.pending_submit = None;
+
+        // Held Enter must neither resubmit nor skip past the result of a game
+        if self.app.input.trim().is_empty()
+            && self
+                .app
+                .last_submit
+                .is_some_and(|at| at.elapsed() < SUBMIT_REPEAT_WINDOW)
+        {
+            return;
         }
 
-        InputStatus::Valid
-    }
-
-    fn submit_input(&mut self) {
         if self.app.mode == GameMode::Game && self.app.game_over {
             self.app.log("Starting new game");
-            GameHandler::new(self.app).start_new_game();
+            GameHandler::new(self.app).restart_game();
             return;
         }
 
-        if !matches!(self.input_status(), InputStatus::Valid) {
+        // Rejected lines are kept too, so a typo can be recalled and fixed
+        self.app
+            .input_history
+            .record(&self.app.mode, &self.app.input);
+
+        let status = if force {
+            self.app.input_status_forced()
+        } else {
+            self.input_status()
+        };
+        if !matches!(status, InputStatus::Valid) {
             self.app
                 .log(format!("Input rejected: {:?}", self.app.input));
             return;
         }
 
-        if self.app.mode == GameMode::Game {
-            if let Some(ref target) = self.app.target_word {
-                let word = self.app.input.trim().to_lowercase();
-
-                if !self.app.allowed_lookup.contains(&word) {
-                    self.app
-                        .log(format!("Rejected guess not in allowed list: {}", word));
-                    return;
-                }
-
-                self.app.log(format!("Game guess submitted: {}", &word));
-
-                let feedback = generate_feedback(target, &word);
+        // Only reached with confirm_repeat_guess on; otherwise repeats are invalid
+        let word = self.input_word();
+        let letters: String = word.split_whitespace().collect();
+        if self.app.repeats_guess(&letters) {
+            self.app.overlay = Some(Overlay::ConfirmRepeat { word });
+            return;
+        }
 
-                self.app
-                    .solver
-                    .add_guess(Guess::new(word, feedback.clone()));
+        self.submit_valid_input();
+    }
 
-                self.app.remaining_guesses -= 1;
-                GameHandler::new(self.app).check_game_state(&feedback);
+    /// The guessed word of the input, lowercased; a phrase keeps its spaces.
+    fn input_word(&self) -> String {
+        let input = self.app.input.trim().to_lowercase();
+        if !self.app.expects_pattern() {
+            return input;
+        }
+        input
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    }
 
-                SolverHandler::new(self.app).recompute();
-                self.app.input.clear();
-            }
+    /// Submit input that has already been validated.
+    ///
+    /// The input is consumed before the guess is applied, so nothing that
+    /// follows can submit it a second time.
+    fn submit_valid_input(&mut self) {
+        if !self.app.expects_pattern() {
+            let word = std::mem::take(&mut self.app.input).trim().to_lowercase();
+            self.app.last_submit = Some(Instant::now());
+            GameHandler::new(self.app).submit_guess(word);
         } else if let ParsedInput::Valid { word, feedback } = self.parse_input() {
-            if !self.app.allowed_lookup.contains(&word) {
-                self.app
-                    .log(format!("Rejected guess not in allowed list: {}", word));
-                return;
-            }
-
-            // Calculate pool size and optimal word BEFORE applying the guess
-            let remaining_before = self.app.solver.filter(&self.app.solution_words);
-            let pool_size_before = remaining_before.len();
-
-            // Get optimal word at this step (before applying the guess)
-            let optimal = get_optimal_word(&remaining_before[..], &self.app.allowed_lookup);
-            let (optimal_word, optimal_score) = optimal.unwrap_or((String::from("-----"), 0));
-
-            // Get the score of the actual word chosen
-            let actual_score = if pool_size_before > 0 {
-                let scored = score_and_sort(&remaining_before[..], &self.app.allowed_lookup);
-                scored
-                    .iter()
-                    .find(|(w, _)| w == &word)
-                    .map(|(_, s)| *s)
-                    .unwrap_or(0)
-            } else {
-                0
-            };
-
-            // Add the guess
-            let guess = Guess::new(word.clone(), feedback.clone());
-            self.app.solver.add_guess(guess);
-
-            // Calculate pool size and entropy AFTER applying the guess
-            let remaining_after = self.app.solver.filter(&self.app.solution_words);
-            let pool_size_after = remaining_after.len();
-            let stats = compute_solution_pool_stats(&self.app.solution_words, &remaining_after);
-            let entropy = stats.entropy;
-
-            // Calculate deviation: positive means actual is better, negative means optimal was better
-            // Using score difference as a proxy for entropy difference
-            let score_deviation = actual_score as f64 - optimal_score as f64;
-
-            if self.app.solver_session_active && !self.app.solver_session_paused {
-                let sg = crate::ui::history::solver_types::SolverGuess {
-                    word: word.clone(),
-                    pool_size_before,
-                    pool_size_after,
-                    entropy,
-                    optimal_word: optimal_word.clone(),
-                    optimal_entropy: entropy - score_deviation,
-                    deviation_score: score_deviation,
-                };
-                self.app.solver_session_guesses.push(sg);
-                self.app.log(format!(
-                    "Solver guess: {} (pool: {}→{}, entropy: {:.2}, optimal: {}, deviation: {:.2})",
-                    &word,
-                    pool_size_before,
-                    pool_size_after,
-                    entropy,
-                    optimal_word,
-                    score_deviation
-                ));
-            } else {
-                self.app
-                    .log(format!("Solver guess submitted: {} {:?}", &word, feedback));
-            }
-
-            SolverHandler::new(self.app).recompute();
             self.app.input.clear();
-
-            // Check for session completion: pool narrowed to 1 OR all green feedback
-            let all_green = feedback
-                .iter()
-                .all(|f| *f == crate::solver::Feedback::Green);
-            if self.app.solver_session_active
-                && !self.app.solver_session_paused
-                && (pool_size_after == 1 || all_green)
-            {
-                let guess_count = self.app.solver.guesses().len();
-                self.app
-                    .log(format!("Solver session completed: {} guesses", guess_count));
-                self.reset_solver_and_start_new_session();
+            self.app.last_submit = Some(Instant::now());
+            if self.app.mode == GameMode::Game {
+                GameHandler::new(self.app).submit_shadow_guess(word, feedback);
+            } else {
+                SolverHandler::new(self.app).submit_guess(word, feedback);
             }
         }
     }
-
-    fn reset_solver_and_start_new_session(&mut self) {
-        let timestamp = self
-            .app
-            .solver_session_start
-            .unwrap_or_else(chrono::Utc::now);
-        let guesses = std::mem::take(&mut self.app.solver_session_guesses);
-        if !guesses.is_empty()
-            && let Err(e) = self.app.db.save_solver_session(timestamp, &guesses)
-        {
-            self.app
-                .log(format!("Warning: failed to save solver session: {}", e));
-        }
-
-        let word_len = self.app.solver.word_len();
-        self.app.solver = crate::solver::SolverState::new(word_len);
-        self.app.entropy_history.clear();
-        self.app.suggestions.clear();
-        self.app.analysis_dirty = true;
-
-        self.app.solver_session_active = true;
-        self.app.solver_session_paused = false;
-        self.app.solver_session_start = Some(chrono::Utc::now());
-        self.app.log("Solver session started");
-    }
 }
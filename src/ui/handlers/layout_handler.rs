@@ -0,0 +1,83 @@
+use crate::settings::PanelKind;
+
+use super::super::{app::App, types::Overlay};
+
+/// Helper struct for reordering and hiding the analysis panels.
+pub struct LayoutHandler<'a> {
+    app: &'a mut App,
+}
+
+impl<'a> LayoutHandler<'a> {
+    pub fn new(app: &'a mut App) -> Self {
+        Self { app }
+    }
+
+    pub fn open(&mut self) {
+        self.app.overlay = Some(Overlay::PanelLayout { selected: 0 });
+    }
+
+    /// Every panel as listed in the editor: visible ones in order, then hidden ones.
+    pub fn entries(panels: &[PanelKind]) -> Vec<(PanelKind, bool)> {
+        let hidden = PanelKind::ALL.into_iter().filter(|p| !panels.contains(p));
+        panels
+            .iter()
+            .map(|&p| (p, true))
+            .chain(hidden.map(|p| (p, false)))
+            .collect()
+    }
+
+    /// Move the highlight by `delta` rows.
+    pub fn move_highlight(&mut self, delta: isize) {
+        if let Some(Overlay::PanelLayout { selected }) = &mut self.app.overlay {
+            let last = PanelKind::ALL.len() - 1;
+            *selected = selected.saturating_add_signed(delta).min(last);
+        }
+    }
+
+    /// Move the highlighted visible panel up or down, keeping it highlighted.
+    pub fn move_panel(&mut self, delta: isize) {
+        let Some(Overlay::PanelLayout { selected }) = &mut self.app.overlay else {
+            return;
+        };
+        let panels = &mut self.app.settings.analysis_panels;
+        let from = *selected;
+        let Some(to) = from.checked_add_signed(delta) else {
+            return;
+        };
+        if from >= panels.len() || to >= panels.len() {
+            return;
+        }
+
+        panels.swap(from, to);
+        *selected = to;
+        self.app.save_settings();
+    }
+
+    /// Show a hidden panel at the bottom, or hide a visible one.
+    pub fn toggle_visibility(&mut self) {
+        let Some(Overlay::PanelLayout { selected }) = &mut self.app.overlay else {
+            return;
+        };
+        let entries = Self::entries(&self.app.settings.analysis_panels);
+        let Some(&(panel, visible)) = entries.get(*selected) else {
+            return;
+        };
+
+        let panels = &mut self.app.settings.analysis_panels;
+        if visible {
+            panels.retain(|&p| p != panel);
+            // Keep the highlight on the panel at its new place among the hidden ones
+            *selected = Self::entries(panels)
+                .iter()
+                .position(|&(p, _)| p == panel)
+                .unwrap_or(0);
+        } else {
+            panels.push(panel);
+            *selected = panels.len() - 1;
+        }
+
+        let status = if visible { "hidden" } else { "shown" };
+        self.app.log(format!("{} panel {}", panel.name(), status));
+        self.app.save_settings();
+    }
+}
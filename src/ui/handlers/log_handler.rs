@@ -0,0 +1,76 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::super::{
+    app::App,
+    types::{FocusTarget, LogSearch},
+};
+use super::FocusHandler;
+
+/// Helper struct for the history-mode log view: expanding it and searching it.
+pub struct LogHandler<'a> {
+    app: &'a mut App,
+}
+
+impl<'a> LogHandler<'a> {
+    pub fn new(app: &'a mut App) -> Self {
+        Self { app }
+    }
+
+    /// Switch between the compact log strip and logs filling the screen.
+    /// Expanding focuses the logs; collapsing hands focus back to the history
+    /// views and drops any search.
+    pub fn toggle_expanded(&mut self) {
+        self.app.history_logs_expanded = !self.app.history_logs_expanded;
+        if self.app.history_logs_expanded {
+            FocusHandler::new(self.app).focus(FocusTarget::Logs);
+        } else {
+            self.app.log_search = None;
+            FocusHandler::new(self.app).reset();
+        }
+    }
+
+    /// Start typing a search.
+    pub fn start_search(&mut self) {
+        self.app.log_search = Some(LogSearch {
+            editing: true,
+            ..LogSearch::default()
+        });
+    }
+
+    /// Type into the search: Enter jumps to the newest match, Esc gives up.
+    pub fn edit_search(&mut self, key: KeyEvent) {
+        let Some(search) = &mut self.app.log_search else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char(c) => search.query.push(c),
+            KeyCode::Backspace => {
+                search.query.pop();
+            }
+            KeyCode::Enter => {
+                search.editing = false;
+                self.jump(true);
+            }
+            KeyCode::Esc => self.app.log_search = None,
+            _ => {}
+        }
+    }
+
+    /// Move to the next match toward older lines, or toward newer ones,
+    /// scrolling it into the middle of the view.
+    pub fn jump(&mut self, older: bool) {
+        let lines = self.app.logs.lines();
+        let Some(search) = &mut self.app.log_search else {
+            return;
+        };
+        let Some(index) = search.step(&lines, older) else {
+            return;
+        };
+        search.current = Some(index);
+
+        let rows = self.app.log_rows.get().max(1);
+        let hidden = lines.len().saturating_sub(rows);
+        let start = index.saturating_sub(rows / 2).min(hidden);
+        self.app.log_scroll = hidden - start;
+    }
+}
@@ -0,0 +1,164 @@
+use anyhow::Result;
+
+use crate::{
+    scoring::STRATEGY,
+    solver::{Guess, SolverState, generate_feedback},
+};
+
+use super::super::{
+    app::App,
+    history::solver_types::{SolverGuess, SolverSession},
+    types::{GameMode, RecomputeJob, RecomputeSummary},
+};
+use super::{HistoryHandler, SolverHandler};
+
+/// Sessions replayed per step, small enough to keep the UI responsive.
+const RECOMPUTE_BATCH: usize = 5;
+
+/// Helper struct for history maintenance that rewrites stored sessions.
+pub struct MaintenanceHandler<'a> {
+    app: &'a mut App,
+}
+
+impl<'a> MaintenanceHandler<'a> {
+    pub fn new(app: &'a mut App) -> Self {
+        Self { app }
+    }
+
+    /// Begin replaying every stored solver session against the current word
+    /// lists and scorer; Esc cancels before anything is written.
+    pub fn start_recompute(&mut self, dry_run: bool) {
+        if self.app.recompute.is_some() {
+            return;
+        }
+
+        match self.app.db.load_solver_sessions_with_ids() {
+            Ok(sessions) => {
+                self.app.log(format!(
+                    "Recomputing optimal words for {} solver session(s){}; Esc cancels",
+                    sessions.len(),
+                    if dry_run { " (dry run)" } else { "" }
+                ));
+                self.app.recompute = Some(RecomputeJob {
+                    sessions,
+                    next: 0,
+                    updates: Vec::new(),
+                    summary: RecomputeSummary::default(),
+                    dry_run,
+                });
+            }
+            Err(e) => self
+                .app
+                .log_error(format!("Failed to load solver sessions: {}", e)),
+        }
+    }
+
+    /// Drop the recompute in progress; nothing has been written yet.
+    pub fn cancel_recompute(&mut self) {
+        if self.app.recompute.take().is_some() {
+            self.app
+                .log("Recompute cancelled; stored sessions are unchanged");
+        }
+    }
+
+    /// Replay the next batch of sessions, writing every update in one
+    /// transaction after the last. Returns the summary once finished.
+    pub fn step_recompute(&mut self) -> Option<Result<RecomputeSummary>> {
+        let mut job = self.app.recompute.take()?;
+
+        let end = (job.next + RECOMPUTE_BATCH).min(job.sessions.len());
+        for (id, session) in &job.sessions[job.next..end] {
+            match self.replay(session) {
+                Some(guesses) => {
+                    tally(&mut job.summary, &session.guesses, &guesses);
+                    job.updates.push((*id, guesses));
+                }
+                None => job.summary.skipped += 1,
+            }
+        }
+        job.next = end;
+
+        if job.next < job.sessions.len() {
+            self.app.log(format!(
+                "Recomputing optimal words: {}/{} sessions",
+                job.next,
+                job.sessions.len()
+            ));
+            self.app.recompute = Some(job);
+            return None;
+        }
+
+        Some(self.finish_recompute(job))
+    }
+
+    fn finish_recompute(&mut self, job: RecomputeJob) -> Result<RecomputeSummary> {
+        let summary = job.summary;
+        if job.dry_run {
+            let message = format!("Dry run, nothing written: {}", summary);
+            self.app.notify(&message);
+            self.app.log(message);
+            return Ok(summary);
+        }
+
+        if let Err(e) = self
+            .app
+            .db
+            .update_solver_session_guesses(&job.updates, STRATEGY)
+        {
+            self.app
+                .log_error(format!("Failed to save recomputed sessions: {}", e));
+            return Err(e);
+        }
+        let message = format!("Recomputed optimal words: {}", summary);
+        self.app.notify(&message);
+        self.app.log(message);
+
+        if self.app.mode == GameMode::History {
+            HistoryHandler::new(self.app).load_history();
+        }
+        Ok(summary)
+    }
+
+    /// Measure each guess of `session` again, or `None` if its patterns can't be recovered.
+    ///
+    /// Patterns come from the stored guesses, or from the confirmed answer for
+    /// sessions saved before patterns were kept.
+    fn replay(&mut self, session: &SolverSession) -> Option<Vec<SolverGuess>> {
+        let word_len = self.app.solution_words.word_len();
+        let answer = session
+            .resolved_word
+            .as_deref()
+            .filter(|a| a.len() == word_len);
+
+        let mut state = SolverState::new(word_len);
+        let mut measured = Vec::new();
+        for stored in &session.guesses {
+            let feedback = match (&stored.feedback, answer) {
+                (Some(feedback), _) => feedback.clone(),
+                (None, Some(answer)) => generate_feedback(answer, &stored.word),
+                (None, None) => return None,
+            };
+            let guess = Guess::new(stored.word.clone(), feedback);
+            measured.push(SolverHandler::new(self.app).measure_guess(&state, &guess));
+            state.add_guess(guess).ok()?;
+        }
+        Some(measured)
+    }
+}
+
+fn tally(summary: &mut RecomputeSummary, before: &[SolverGuess], after: &[SolverGuess]) {
+    summary.sessions += 1;
+    for (old, new) in before.iter().zip(after) {
+        summary.guesses += 1;
+        if old.optimal_word != new.optimal_word {
+            summary.optimal_changed += 1;
+        }
+        // A deviation in bits can't be compared with one in score
+        if old.metric == new.metric
+            && let (Some(old), Some(new)) = (old.deviation_score, new.deviation_score)
+        {
+            summary.deviation_change += (new - old).abs();
+            summary.deviation_compared += 1;
+        }
+    }
+}
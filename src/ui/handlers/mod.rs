@@ -1,9 +1,31 @@
+mod bug_report_handler;
+mod checkpoint_handler;
+mod dictionary_handler;
+mod focus_handler;
 mod game_handler;
 mod history_handler;
 mod input_handler;
+mod layout_handler;
+mod log_handler;
+mod maintenance_handler;
+mod snapshot_handler;
 mod solver_handler;
+mod suggestion_handler;
+mod tier_handler;
+mod ui_state_handler;
 
+pub use bug_report_handler::BugReportHandler;
+pub use checkpoint_handler::CheckpointHandler;
+pub use dictionary_handler::DictionaryHandler;
+pub use focus_handler::FocusHandler;
 pub use game_handler::GameHandler;
 pub use history_handler::HistoryHandler;
 pub use input_handler::InputHandler;
+pub use layout_handler::LayoutHandler;
+pub use log_handler::LogHandler;
+pub use maintenance_handler::MaintenanceHandler;
+pub use snapshot_handler::SnapshotHandler;
 pub use solver_handler::SolverHandler;
+pub use suggestion_handler::SuggestionHandler;
+pub use tier_handler::TierHandler;
+pub use ui_state_handler::UiStateHandler;
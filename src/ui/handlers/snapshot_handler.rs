@@ -0,0 +1,123 @@
+use super::super::{
+    app::App,
+    types::{Overlay, SnapshotState, SnapshotStep},
+};
+use super::SolverHandler;
+
+/// Helper struct for saving, loading, and deleting named solver snapshots.
+pub struct SnapshotHandler<'a> {
+    app: &'a mut App,
+}
+
+impl<'a> SnapshotHandler<'a> {
+    pub fn new(app: &'a mut App) -> Self {
+        Self { app }
+    }
+
+    /// Open the snapshot manager with the saved snapshots listed.
+    pub fn open(&mut self) {
+        match self.app.db.load_snapshots() {
+            Ok(snapshots) => {
+                self.app.overlay = Some(Overlay::Snapshots(SnapshotState {
+                    snapshots,
+                    selected: 0,
+                    step: SnapshotStep::Browse,
+                }));
+            }
+            Err(e) => self
+                .app
+                .log_error(format!("Failed to load snapshots: {}", e)),
+        }
+    }
+
+    /// Save the current solver state under `name` and refresh the list.
+    pub fn save(&mut self, name: &str) {
+        let name = name.trim();
+        if name.is_empty() {
+            return;
+        }
+
+        if let Err(e) = self.app.db.save_snapshot(name, &self.app.solver) {
+            self.app
+                .log_error(format!("Failed to save snapshot: {}", e));
+            return;
+        }
+        self.app.log(format!(
+            "Saved snapshot {} ({} guesses)",
+            name,
+            self.app.solver.guesses().len()
+        ));
+        self.open();
+    }
+
+    /// Ask before loading over existing guesses; load straight away otherwise.
+    pub fn request_load(&mut self) {
+        if self.app.solver.guesses().is_empty() {
+            self.load_selected();
+        } else if let Some(Overlay::Snapshots(state)) = &mut self.app.overlay
+            && !state.snapshots.is_empty()
+        {
+            state.step = SnapshotStep::ConfirmLoad;
+        }
+    }
+
+    /// Replace the solver state with the selected snapshot and start a fresh session.
+    pub fn load_selected(&mut self) {
+        let Some(Overlay::Snapshots(state)) = &mut self.app.overlay else {
+            return;
+        };
+        if state.selected >= state.snapshots.len() {
+            return;
+        }
+        let snapshot = state.snapshots.swap_remove(state.selected);
+
+        if snapshot.state.word_len() != self.app.solver.word_len() {
+            state.step = SnapshotStep::Browse;
+            self.app.log(format!(
+                "Snapshot {} is for {}-letter words",
+                snapshot.name,
+                snapshot.state.word_len()
+            ));
+            self.open();
+            return;
+        }
+
+        self.app.overlay = None;
+        self.app.solver = snapshot.state;
+
+        // The loaded guesses belong to another puzzle, so keep them out of session stats
+        self.app.solver_session_guesses.clear();
+        SolverHandler::new(self.app).checkpoint_session();
+        self.app.solver_session_active = true;
+        self.app.solver_session_paused = false;
+        self.app.solver_session_start = Some(chrono::Utc::now());
+        self.app.log(format!(
+            "Loaded snapshot {}; solver session started",
+            snapshot.name
+        ));
+
+        let mut solver = SolverHandler::new(self.app);
+        solver.rebuild_entropy_history();
+        solver.refresh_derived_state();
+    }
+
+    /// Delete the selected snapshot.
+    pub fn delete_selected(&mut self) {
+        let Some(Overlay::Snapshots(state)) = &mut self.app.overlay else {
+            return;
+        };
+        let Some(snapshot) = state.snapshots.get(state.selected) else {
+            return;
+        };
+        let name = snapshot.name.clone();
+
+        if let Err(e) = self.app.db.delete_snapshot(&name) {
+            self.app
+                .log_error(format!("Failed to delete snapshot: {}", e));
+            return;
+        }
+        state.snapshots.remove(state.selected);
+        state.selected = state.selected.min(state.snapshots.len().saturating_sub(1));
+        self.app.log(format!("Deleted snapshot {}", name));
+    }
+}
@@ -1,13 +1,35 @@
+use std::collections::HashMap;
+
 use crate::{
     analysis::{
-        compute_constraint_summary, compute_letter_analysis, compute_position_analysis,
-        compute_solution_pool_stats,
+        SolvedSource, compute_constraint_summary, compute_letter_analysis,
+        compute_letter_information, compute_position_analysis, compute_solution_pool_stats,
+        derive_forced_positions, diff_suggestions, format_constraints_compact,
+    },
+    cli_style::CliStyle,
+    error::{Result, TranscriptError},
+    kids::{is_kids_word, kids_suggestions},
+    priors::apply_priors,
+    scoring::{
+        ENTROPY_SCORING_MAX_POOL, entropy_optimal, expected_entropy, get_optimal_word,
+        pattern_distribution, pattern_string, score_and_sort, score_of,
     },
-    scoring::score_and_sort,
-    solver::SolverState,
+    solver::{
+        Feedback, Guess, SolverState, inconsistent_guesses, parse_transcript,
+        parse_transcript_lines,
+    },
+    storage::new_write_id,
 };
 
-use super::super::app::App;
+use super::super::{
+    app::App,
+    history::solver_types::{DeviationMetric, SolverGuess},
+    types::{
+        GameMode, Overlay, PATTERN_PREVIEW_MAX_POOL, PatternPreview, SUGGESTION_DIFF_ROWS,
+        VerifyAnswerState,
+    },
+};
+use super::{GameHandler, SuggestionHandler};
 
 /// Helper struct for managing solver-specific state and analysis.
 pub struct SolverHandler<'a> {
@@ -19,45 +41,301 @@ impl<'a> SolverHandler<'a> {
         Self { app }
     }
 
-    pub fn recompute(&mut self) {
+    /// Bring everything derived from the guesses up to date at once: the
+    /// suggestions, pool and entropy histories, analysis panels and pool stats.
+    ///
+    /// Called after every change to the guesses, so nothing drawn afterwards
+    /// mixes the old pool with the new.
+    pub fn refresh_derived_state(&mut self) {
         let remaining = self.app.solver.filter(&self.app.solution_words);
+        let previous = std::mem::take(&mut self.app.suggestions);
+        self.app.state_publisher.record_pool(remaining.len());
+        self.app.last_candidate = match remaining[..] {
+            [only]
+                if self.app.mode == GameMode::Solver && !self.app.solver.guesses().is_empty() =>
+            {
+                Some(only)
+            }
+            _ => None,
+        };
 
-        if self.app.solver.guesses().is_empty() {
-            self.app.suggestions.clear();
+        if self.app.kids {
+            // Everyday candidates are few enough to score before any guess
+            let words = &self.app.solution_words;
+            let everyday: Vec<u32> = remaining
+                .iter()
+                .copied()
+                .filter(|&i| is_kids_word(words.word(i)))
+                .collect();
+            let scored = self.score_pool(&everyday);
+            self.app.suggestions = kids_suggestions(&self.app.solution_words, &scored);
+        } else if !self.app.solver.guesses().is_empty() {
+            self.app.suggestions = self.score_pool(&remaining);
+        }
+        self.app.prior_shifts = if self.app.settings.answer_priors {
+            apply_priors(
+                &self.app.solution_words,
+                &mut self.app.suggestions,
+                &self.app.settings.prior_weights,
+            )
         } else {
-            self.app.suggestions = score_and_sort(&remaining, &self.app.allowed_lookup);
+            HashMap::new()
+        };
+
+        if self.app.settings.hide_gray_suggestions {
+            let constraints = compute_constraint_summary(&self.app.solver);
+            let words = &self.app.solution_words;
+            self.app
+                .suggestions
+                .retain(|&(i, _)| !words.word(i).chars().any(|c| constraints.is_excluded(c)));
         }
+        self.diff_suggestions(&previous);
+        self.log_flagged_suggestions(&previous);
+        self.prune_guess_notes();
 
+        SuggestionHandler::new(self.app).clamp_selection();
+        self.rebuild_pool_history();
+        self.update_win_window(&remaining);
+        self.app.guess_scroll = 0;
         self.app.analysis_dirty = true;
+        self.recompute_analysis();
+    }
+
+    /// Compare the suggestions with `previous` when exactly one guess was added
+    /// since; undo, edits, imports and resets clear the comparison instead.
+    /// The first guess has no earlier suggestions to compare with.
+    fn diff_suggestions(&mut self, previous: &[(u32, usize)]) {
+        let guesses = self.app.solver.guesses().len();
+        let words = &self.app.solution_words;
+        self.app.suggestion_deltas =
+            if guesses == self.app.suggestions_guesses + 1 && !previous.is_empty() {
+                let old: Vec<&str> = previous.iter().map(|&(i, _)| words.word(i)).collect();
+                let new: Vec<&str> = self
+                    .app
+                    .suggestions
+                    .iter()
+                    .map(|&(i, _)| words.word(i))
+                    .collect();
+                diff_suggestions(&old, &new, SUGGESTION_DIFF_ROWS)
+            } else {
+                Vec::new()
+            };
+        self.app.suggestions_guesses = guesses;
+    }
+
+    /// Note flagged words that just reached the top suggestions. Games keep
+    /// quiet, since the log would name candidates the panel may be hiding.
+    fn log_flagged_suggestions(&mut self, previous: &[(u32, usize)]) {
+        if self.app.flagged_words.is_empty() || self.app.mode != GameMode::Solver {
+            return;
+        }
+        let words = &self.app.solution_words;
+        let was_top: Vec<u32> = previous
+            .iter()
+            .take(SUGGESTION_DIFF_ROWS)
+            .map(|&(i, _)| i)
+            .collect();
+        let flagged: Vec<&str> = self
+            .app
+            .suggestions
+            .iter()
+            .take(SUGGESTION_DIFF_ROWS)
+            .filter(|(i, _)| !was_top.contains(i))
+            .map(|&(i, _)| words.word(i))
+            .filter(|w| self.app.flagged_words.contains(*w))
+            .collect();
+        if !flagged.is_empty() {
+            let message = format!(
+                "Not in the comparison list, so maybe not accepted: {}",
+                flagged.join(", ")
+            );
+            self.app.log(message);
+        }
     }
 
+    /// Plan out the rest of a single-word game from `remaining`; the planner
+    /// reuses its last answer while the pool stays the same.
+    fn update_win_window(&mut self, remaining: &[u32]) {
+        self.app.win_window = if self.app.mode == GameMode::Game
+            && !self.app.solver.guesses().is_empty()
+            && self.app.solver.segments().len() == 1
+        {
+            self.app
+                .planner
+                .win_window(&self.app.solution_words, remaining)
+        } else {
+            None
+        };
+    }
+
+    /// Replay the guesses to find the pool left after each one.
+    ///
+    /// Done on every recompute, so undo, edits, and loaded snapshots can't
+    /// leave stale sizes behind.
+    fn rebuild_pool_history(&mut self) {
+        self.app.pool_history.clear();
+        if self.app.solver.segments().len() > 1 {
+            return;
+        }
+
+        let mut replay = SolverState::new(self.app.solver.word_len());
+        let mut remaining = Vec::new();
+        for guess in self.app.solver.guesses() {
+            replay
+                .add_guess(guess.clone())
+                .expect("guesses were validated when first added");
+            replay.filter_into(&self.app.solution_words, &mut remaining);
+            self.app.pool_history.push(remaining.len());
+        }
+    }
+
+    /// Rebuild the analysis panels and pool stats, unless nothing has changed
+    /// since they were last built.
     pub fn recompute_analysis(&mut self) {
         if !self.app.analysis_dirty {
             return;
         }
 
         let remaining = self.app.solver.filter(&self.app.solution_words);
-
-        self.app.letter_analysis = Some(compute_letter_analysis(&remaining));
-        tracing::info!("LetterAnalysis: {:?}", self.app.letter_analysis);
-        self.app.position_analysis = Some(compute_position_analysis(&remaining, &self.app.solver));
-        tracing::info!("PositionAnalysis: {:?}", self.app.position_analysis);
-        self.app.constraint_summary = Some(compute_constraint_summary(&self.app.solver));
-        tracing::info!("ConstraintSummary: {:?}", self.app.constraint_summary);
-        self.app.solution_pool_stats = Some(compute_solution_pool_stats(
-            &self.app.solution_words,
+        let words = &self.app.solution_words;
+        let constraints = compute_constraint_summary(&self.app.solver);
+        self.app.letter_analysis = Some(if self.app.settings.letter_information {
+            compute_letter_information(words, &remaining, &constraints)
+        } else {
+            compute_letter_analysis(words, &remaining)
+        });
+        self.app.position_analysis = Some(compute_position_analysis(
+            words,
             &remaining,
+            &self.app.solver,
         ));
+        self.app.yellow_placements = derive_forced_positions(words, &remaining, &constraints);
+        let stats = compute_solution_pool_stats(words, &remaining);
+        self.note_inferred_positions();
 
-        tracing::info!("SolutionPoolStats: {:?}", self.app.solution_pool_stats);
-        if let Some(stats) = &self.app.solution_pool_stats {
-            // Only push if not rebuilding (i.e., during normal guess submission)
-            if self.app.entropy_history.len() < self.app.solver.guesses().len() {
-                self.app.entropy_history.push(stats.entropy);
-            }
+        tracing::info!(
+            pool = stats.total_remaining,
+            entropy = stats.entropy,
+            greens = constraints.greens.len(),
+            yellows = constraints.yellows.len(),
+            grays = constraints.grays.len(),
+            "Analysis recomputed"
+        );
+        // Full dumps are large; skip formatting them unless asked for
+        if self.app.settings.verbose_analysis_logging && tracing::enabled!(tracing::Level::DEBUG) {
+            tracing::debug!("LetterAnalysis: {:?}", self.app.letter_analysis);
+            tracing::debug!("PositionAnalysis: {:?}", self.app.position_analysis);
+            tracing::debug!("ConstraintSummary: {:?}", constraints);
+            tracing::debug!("SolutionPoolStats: {:?}", stats);
         }
 
+        // Only push if not rebuilding (i.e., during normal guess submission)
+        if self.app.entropy_history.len() < self.app.solver.guesses().len() {
+            self.app.entropy_history.push(stats.entropy);
+        }
+        self.app.constraint_summary = Some(constraints);
+        self.app.solution_pool_stats = Some(stats);
+        // The pool changed, so any preview is stale
+        self.app.pattern_preview = None;
+
         self.app.analysis_dirty = false;
+        self.app.analysis_version += 1;
+    }
+
+    /// Work out the feedback the word in the solver input could get, once
+    /// the input holds a known word and no pattern yet.
+    ///
+    /// Kept while the word stays the same; nothing is previewed for a pool
+    /// over [`PATTERN_PREVIEW_MAX_POOL`].
+    pub fn refresh_pattern_preview(&mut self) {
+        let word = self.app.input.trim().to_lowercase();
+        let typed_word = self.app.mode == GameMode::Solver
+            && word.len() == self.app.solver.word_len()
+            && !word.contains(char::is_whitespace)
+            && (self.app.allowed_lookup.contains(&word) || self.app.solution_words.contains(&word));
+        if !typed_word {
+            if self.app.pattern_preview.take().is_some() {
+                self.app.preview_version += 1;
+            }
+            return;
+        }
+        if self
+            .app
+            .pattern_preview
+            .as_ref()
+            .is_some_and(|preview| preview.word == word)
+        {
+            return;
+        }
+
+        let pool = self.app.solver.filter(&self.app.solution_words);
+        let odds = if pool.len() <= PATTERN_PREVIEW_MAX_POOL {
+            pattern_distribution(&word, &self.app.solution_words, &pool)
+        } else {
+            Vec::new()
+        };
+        self.app.pattern_preview = Some(PatternPreview { word, odds });
+        self.app.preview_version += 1;
+    }
+
+    /// Log each position solved by elimination, once until the guesses are cleared.
+    ///
+    /// Skipped while the analysis panels are hidden in a game, where it would be a hint.
+    fn note_inferred_positions(&mut self) {
+        if self.app.solver.guesses().is_empty() {
+            self.app.inferred_positions_noted.clear();
+            return;
+        }
+        if self.app.mode == GameMode::Game && !self.app.show_analysis {
+            return;
+        }
+        let Some(analysis) = &self.app.position_analysis else {
+            return;
+        };
+
+        let newly_inferred: Vec<(usize, char)> = analysis
+            .solved_source
+            .iter()
+            .zip(&analysis.solved_positions)
+            .enumerate()
+            .filter_map(|(pos, (source, letter))| match (source, letter) {
+                (Some(SolvedSource::Inferred), Some(letter)) => Some((pos, *letter)),
+                _ => None,
+            })
+            .filter(|(pos, _)| !self.app.inferred_positions_noted.contains(pos))
+            .collect();
+        for (pos, letter) in newly_inferred {
+            self.app.inferred_positions_noted.insert(pos);
+            self.app.log(format!(
+                "Position {} must be {}: every remaining candidate has it there",
+                pos + 1,
+                letter.to_ascii_uppercase()
+            ));
+        }
+    }
+
+    /// Solver mode works on single words; drop a phrase game's guesses on the way in.
+    pub fn discard_phrase_guesses(&mut self) {
+        if self.app.solver.segments().len() > 1 {
+            self.app.solver = SolverState::new(self.app.solution_words.word_len());
+            self.app.entropy_history.clear();
+            self.refresh_derived_state();
+        }
+    }
+
+    /// Switch the Letters panel between frequency and information ranking.
+    pub fn toggle_letter_information(&mut self) {
+        self.app.settings.letter_information = !self.app.settings.letter_information;
+        self.app.save_settings();
+
+        let ranking = if self.app.settings.letter_information {
+            "information"
+        } else {
+            "frequency"
+        };
+        self.app.log(format!("Letters ranked by {}", ranking));
+        self.app.analysis_dirty = true;
+        self.recompute_analysis();
     }
 
     pub fn undo_guess(&mut self) {
@@ -67,10 +345,11 @@ impl<'a> SolverHandler<'a> {
                 let last_guess = self.app.solver.guesses().last().unwrap();
                 tracing::info!("Solver undo: removed guess {}", last_guess.word);
             }
+            let word = self.app.solver.guesses().last().unwrap().word.clone();
             self.app.solver.pop_guess();
-            self.recompute();
+            self.drop_session_guess(&word);
             self.rebuild_entropy_history();
-            self.app.analysis_dirty = true;
+            self.refresh_derived_state();
         }
     }
 
@@ -78,11 +357,667 @@ impl<'a> SolverHandler<'a> {
         self.app.entropy_history.clear();
         let guesses = self.app.solver.guesses();
         let mut temp_solver = SolverState::new(self.app.solver.word_len());
+        let mut remaining = Vec::new();
         for guess in guesses {
-            temp_solver.add_guess(guess.clone());
-            let remaining = temp_solver.filter(&self.app.solution_words);
+            temp_solver
+                .add_guess(guess.clone())
+                .expect("guesses were validated when first added");
+            temp_solver.filter_into(&self.app.solution_words, &mut remaining);
             let stats = compute_solution_pool_stats(&self.app.solution_words, &remaining);
             self.app.entropy_history.push(stats.entropy);
         }
     }
+
+    /// Apply a validated solver guess, recording session metrics.
+    ///
+    /// Returns true when the guess completed the solver session.
+    pub fn submit_guess(&mut self, word: String, feedback: Vec<Feedback>) -> bool {
+        let before = self.app.solver.clone();
+
+        let guess = Guess::new(word.clone(), feedback.clone());
+        let sg = self.measure_guess(&before, &guess);
+        if let Err(e) = self.app.solver.add_guess(guess) {
+            self.app.log(format!("Rejected guess {}: {}", word, e));
+            return false;
+        }
+
+        let pool_size_after = sg.pool_size_after;
+
+        if self.app.solver_session_active && !self.app.solver_session_paused {
+            self.app.log(format!(
+                "Solver guess: {} (pool: {}→{}, entropy: {:.2}, optimal: {}, deviation: {})",
+                &word,
+                sg.pool_size_before,
+                sg.pool_size_after,
+                sg.entropy,
+                sg.optimal_word.as_deref().unwrap_or("-"),
+                sg.deviation_score
+                    .map_or("-".to_string(), |d| format!("{:.2}", d))
+            ));
+            self.app.solver_session_guesses.push(sg);
+            self.checkpoint_session();
+        } else {
+            self.app
+                .log(format!("Solver guess submitted: {} {:?}", &word, feedback));
+        }
+
+        self.refresh_derived_state();
+        self.complete_session_if_solved(pool_size_after, &feedback)
+    }
+
+    /// The lines of `text` that [`import_transcript`](Self::import_transcript)
+    /// would accept, in order, and an error for each line it would refuse.
+    pub fn valid_transcript_lines(&self, text: &str) -> (String, Vec<TranscriptError>) {
+        let (_, errors) = parse_transcript_lines(
+            &self.app.normalizer.normalize(text),
+            self.app.solver.word_len(),
+            &self.app.allowed_lookup,
+        );
+        let valid = text
+            .lines()
+            .enumerate()
+            .filter(|(i, _)| errors.iter().all(|e| e.line != i + 1))
+            .map(|(_, line)| format!("{}\n", line))
+            .collect();
+        (valid, errors)
+    }
+
+    /// Validate every line of a `word pattern` transcript, then apply them all as guesses.
+    ///
+    /// Nothing is applied if any line is invalid. Imported guesses are recorded
+    /// in the solver session like typed ones.
+    pub fn import_transcript(&mut self, text: &str) -> std::result::Result<usize, TranscriptError> {
+        let text = self.app.normalizer.normalize(text);
+        let guesses =
+            parse_transcript(&text, self.app.solver.word_len(), &self.app.allowed_lookup)?;
+        let Some(last) = guesses.last() else {
+            return Ok(0);
+        };
+        let last_feedback = last.feedback.clone();
+        let count = guesses.len();
+
+        let recording = self.app.solver_session_active && !self.app.solver_session_paused;
+        for guess in guesses {
+            let before = self.app.solver.clone();
+            let sg = self.measure_guess(&before, &guess);
+            self.app
+                .solver
+                .add_guess(guess)
+                .expect("transcript guesses were validated");
+            if recording {
+                self.app.solver_session_guesses.push(sg);
+            }
+        }
+        if recording {
+            self.checkpoint_session();
+        }
+
+        let pool_size = self.app.solver.filter(&self.app.solution_words).len();
+        self.app.log(format!(
+            "Imported {} guesses, pool now {}",
+            count, pool_size
+        ));
+        self.rebuild_entropy_history();
+        self.refresh_derived_state();
+        self.complete_session_if_solved(pool_size, &last_feedback);
+        Ok(count)
+    }
+
+    /// End the session once the last guess was all green, or once the pool is
+    /// down to one word if `solver_auto_complete` is set.
+    ///
+    /// Otherwise a single word left is announced and waits for
+    /// [`Self::confirm_last_candidate`]. A completed session is held open for
+    /// the user to confirm the answer.
+    fn complete_session_if_solved(&mut self, pool_size: usize, feedback: &[Feedback]) -> bool {
+        if !self.app.solver_session_active || self.app.solver_session_paused {
+            return false;
+        }
+        if feedback.iter().all(|f| *f == Feedback::Green) {
+            let word = self.app.solver.guesses().last().map(|g| g.word.clone());
+            self.complete_session(word);
+            return true;
+        }
+        if pool_size != 1 {
+            return false;
+        }
+        let word = self.last_candidate_word();
+        if self.app.settings.solver_auto_complete {
+            self.complete_session(word);
+            return true;
+        }
+        if let Some(word) = word {
+            self.app.log(format!(
+                "Only {} is left: enter it all green or press Ctrl+Y to confirm",
+                word.to_uppercase()
+            ));
+        }
+        false
+    }
+
+    /// Complete the solver session with the one word the guesses leave.
+    pub fn confirm_last_candidate(&mut self) {
+        if !self.app.solver_session_active || self.app.solver_session_paused {
+            self.app.log("No solver session to complete");
+            return;
+        }
+        match self.last_candidate_word() {
+            Some(word) => self.complete_session(Some(word)),
+            None => {
+                let pool_size = self.app.solver.filter(&self.app.solution_words).len();
+                self.app
+                    .log(format!("Nothing to confirm: {} answers left", pool_size));
+            }
+        }
+    }
+
+    fn last_candidate_word(&self) -> Option<String> {
+        self.app
+            .last_candidate
+            .map(|i| self.app.solution_words.word(i).to_string())
+    }
+
+    /// Save the session, start a new one, and ask whether `word` was the answer.
+    fn complete_session(&mut self, word: Option<String>) {
+        let guesses = self.app.solver.guesses().to_vec();
+        self.app.log(format!(
+            "Solver session completed: {} guesses",
+            guesses.len()
+        ));
+
+        if let (Some(session_id), Some(word)) = (self.reset_solver_and_start_new_session(), word) {
+            self.app.overlay = Some(Overlay::VerifyAnswer(VerifyAnswerState {
+                session_id,
+                word,
+                guesses,
+                answer: None,
+            }));
+        }
+    }
+
+    /// Record `answer` as the real answer of the session in `state`.
+    ///
+    /// When it is not the word the solver converged on, the guesses whose
+    /// entered patterns `answer` contradicts are logged.
+    pub fn resolve_session(&mut self, state: &VerifyAnswerState, answer: &str) {
+        let matched = answer == state.word;
+        if let Err(e) = self
+            .app
+            .db
+            .resolve_solver_session(state.session_id, answer, matched)
+        {
+            self.app
+                .log_error(format!("Warning: failed to record answer: {}", e));
+            return;
+        }
+
+        if matched {
+            self.app
+                .log(format!("Answer {} confirmed", answer.to_uppercase()));
+            return;
+        }
+
+        self.app.log(format!(
+            "Answer was {}, not {}",
+            answer.to_uppercase(),
+            state.word.to_uppercase()
+        ));
+        let inconsistent = inconsistent_guesses(&state.guesses, answer);
+        if inconsistent.is_empty() {
+            self.app.log(format!(
+                "Every entered pattern fits {}; it may be missing from the answer list",
+                answer.to_uppercase()
+            ));
+        }
+        for (i, expected) in inconsistent {
+            let guess = &state.guesses[i];
+            self.app.log(format!(
+                "Guess {} {} was entered as {}, but {} gives {}",
+                i + 1,
+                guess.word.to_uppercase(),
+                pattern_string(&guess.feedback),
+                answer.to_uppercase(),
+                pattern_string(&expected)
+            ));
+        }
+    }
+
+    /// Correct the feedback of an earlier guess and replay everything after it.
+    ///
+    /// The pool, suggestions, and entropy history are rebuilt, and the session
+    /// metrics of the edited guess and every later one are recomputed.
+    pub fn edit_guess(&mut self, index: usize, feedback: Vec<Feedback>) -> Result<()> {
+        let word = self.app.solver.guesses()[index].word.clone();
+        self.app
+            .solver
+            .replace_guess(index, Guess::new(word.clone(), feedback.clone()))?;
+
+        // Session metrics only line up with solver guesses when every guess was recorded
+        if self.app.solver_session_guesses.len() == self.app.solver.guesses().len() {
+            for i in index..self.app.solver.guesses().len() {
+                let before = self.app.solver.truncated(i);
+                let guess = self.app.solver.guesses()[i].clone();
+                self.app.solver_session_guesses[i] = self.measure_guess(&before, &guess);
+            }
+            self.checkpoint_session();
+        }
+
+        self.app.log(format!(
+            "Edited guess {}: {} now {}",
+            index + 1,
+            word,
+            pattern_string(&feedback)
+        ));
+
+        self.rebuild_entropy_history();
+        self.refresh_derived_state();
+        Ok(())
+    }
+
+    /// Replay the guesses and find `target` in the suggestions offered before each.
+    ///
+    /// Ranks are 1-based. A step whose suggestions lack `target`, because a
+    /// pattern ruled it out or it is blacklisted, gets `None`.
+    pub fn target_ranks(&self, target: &str) -> Vec<Option<usize>> {
+        let words = &self.app.solution_words;
+        (0..self.app.solver.guesses().len())
+            .map(|i| {
+                let pool = self.app.solver.truncated(i).filter(words);
+                self.score(&pool)
+                    .iter()
+                    .position(|&(w, _)| words.word(w) == target)
+                    .map(|pos| pos + 1)
+            })
+            .collect()
+    }
+
+    /// Every remaining candidate in score order, one per line, under a header
+    /// line with the constraints in compact form. With `scores`, each word is
+    /// followed by its score.
+    ///
+    /// Coloured, the guesses so far head the list as tiles and each word is
+    /// graded against the best score.
+    ///
+    /// Blacklisted words are still possible answers, so they are included.
+    pub fn pool_export(&self, scores: bool, style: CliStyle) -> String {
+        let words = &self.app.solution_words;
+        let pool = self.app.solver.filter(words);
+        let constraints = compute_constraint_summary(&self.app.solver);
+
+        let mut text = String::new();
+        if style.is_color() {
+            for guess in self.app.solver.guesses() {
+                text.push_str(&format!(
+                    "# {}\n",
+                    style.tiles(&guess.word, &guess.feedback)
+                ));
+            }
+        }
+        text.push_str(&format!(
+            "# {}\n",
+            format_constraints_compact(&constraints, self.app.solver.word_len())
+        ));
+        let scored = score_and_sort(words, &pool, &self.app.allowed_lookup);
+        let best = scored.first().map_or(0, |&(_, score)| score);
+        for (i, score) in scored {
+            let line = if scores {
+                format!("{} {}", words.word(i), score)
+            } else {
+                words.word(i).to_string()
+            };
+            text.push_str(&style.graded(line, score as f64, best as f64));
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Score the words of `pool` as guesses, leaving out blacklisted words.
+    fn score(&self, pool: &[u32]) -> Vec<(u32, usize)> {
+        let scored = score_and_sort(&self.app.solution_words, pool, &self.app.allowed_lookup);
+        self.without_blacklisted(scored)
+    }
+
+    /// [`score`](Self::score), reusing the scores of recently scored pools;
+    /// the word lists are fixed once loaded, so nothing else changes them.
+    fn score_pool(&mut self, pool: &[u32]) -> Vec<(u32, usize)> {
+        let scored = self.cached_scores(pool);
+        self.without_blacklisted(scored)
+    }
+
+    /// Every word of `pool` scored, blacklisted or not, from the cache when
+    /// the pool was scored recently.
+    fn cached_scores(&mut self, pool: &[u32]) -> Vec<(u32, usize)> {
+        match self.app.scored_pools.get(pool) {
+            Some(scores) => scores.to_vec(),
+            None => {
+                let scores =
+                    score_and_sort(&self.app.solution_words, pool, &self.app.allowed_lookup);
+                self.app.scored_pools.insert(pool, scores.clone());
+                scores
+            }
+        }
+    }
+
+    fn without_blacklisted(&self, mut scored: Vec<(u32, usize)>) -> Vec<(u32, usize)> {
+        if !self.app.session_blacklist.is_empty() {
+            let words = &self.app.solution_words;
+            scored.retain(|&(i, _)| !self.app.session_blacklist.contains(words.word(i)));
+        }
+        scored
+    }
+
+    /// Session metrics for playing `guess` on top of the guesses in `before`.
+    ///
+    /// The guess is measured against the word with the highest expected
+    /// entropy, or by heuristic score when the pool is over
+    /// [`ENTROPY_SCORING_MAX_POOL`].
+    pub fn measure_guess(&mut self, before: &SolverState, guess: &Guess) -> SolverGuess {
+        let remaining_before = before.filter(&self.app.solution_words);
+        let pool_size_before = remaining_before.len();
+        // Only the heuristic needs them, and the suggestions have often scored this pool
+        let scored = (pool_size_before > ENTROPY_SCORING_MAX_POOL)
+            .then(|| self.cached_scores(&remaining_before));
+        let words = &self.app.solution_words;
+
+        let optimal = match remaining_before[..] {
+            // The last candidate is the optimal word, and no guess can do better or worse
+            [only] => Some((
+                words.word(only).to_string(),
+                0.0,
+                0.0,
+                DeviationMetric::Entropy,
+            )),
+            _ if pool_size_before <= ENTROPY_SCORING_MAX_POOL => {
+                let mut candidates = remaining_before.clone();
+                candidates.retain(|&i| !self.app.session_blacklist.contains(words.word(i)));
+                entropy_optimal(words, &candidates).map(|(i, optimal_bits)| {
+                    let actual_bits = expected_entropy(&guess.word, words, &remaining_before);
+                    (
+                        words.word(i).to_string(),
+                        optimal_bits,
+                        actual_bits - optimal_bits,
+                        DeviationMetric::Entropy,
+                    )
+                })
+            }
+            // With nothing to score there is no optimal word to measure against
+            _ => scored.and_then(|scored| {
+                let blacklist = &self.app.session_blacklist;
+                let best = get_optimal_word(words, &scored, |w| blacklist.contains(w));
+                best.map(|(i, optimal_score)| {
+                    let actual_score = score_of(words, &guess.word, &scored).unwrap_or(0);
+                    // Positive means the chosen word outscored the optimal one
+                    (
+                        words.word(i).to_string(),
+                        optimal_score as f64,
+                        actual_score as f64 - optimal_score as f64,
+                        DeviationMetric::Heuristic,
+                    )
+                })
+            }),
+        };
+
+        let mut after = before.clone();
+        // An invalid guess leaves the pool unchanged; callers reject it separately
+        let _ = after.add_guess(guess.clone());
+        let remaining_after = after.filter(words);
+        let entropy = compute_solution_pool_stats(words, &remaining_after).entropy;
+
+        let (optimal_word, optimal_entropy, deviation_score, metric) = match optimal {
+            Some((word, optimal, deviation, metric)) => {
+                (Some(word), Some(optimal), Some(deviation), Some(metric))
+            }
+            None => (None, None, None, None),
+        };
+        SolverGuess {
+            word: guess.word.clone(),
+            pool_size_before,
+            pool_size_after: remaining_after.len(),
+            entropy,
+            optimal_word,
+            optimal_entropy,
+            deviation_score,
+            metric,
+            feedback: Some(guess.feedback.clone()),
+            note: None,
+        }
+    }
+
+    /// Save the session and start a new one, returning the saved session's id.
+    fn reset_solver_and_start_new_session(&mut self) -> Option<i64> {
+        let timestamp = self
+            .app
+            .solver_session_start
+            .unwrap_or_else(chrono::Utc::now);
+        let guesses = std::mem::take(&mut self.app.solver_session_guesses);
+        let session_id = if guesses.is_empty() {
+            None
+        } else {
+            let uuid = &self.app.solver_session_id;
+            match self
+                .app
+                .db
+                .save_solver_session_as(uuid, timestamp, &guesses, None)
+            {
+                Ok(id) => Some(id),
+                Err(e) => {
+                    self.app
+                        .log_error(format!("Warning: failed to save solver session: {}", e));
+                    None
+                }
+            }
+        };
+
+        let word_len = self.app.solver.word_len();
+        self.app.solver = SolverState::new(word_len);
+        self.app.entropy_history.clear();
+        self.refresh_derived_state();
+
+        self.app.solver_session_active = true;
+        self.app.solver_session_paused = false;
+        self.app.solver_session_start = Some(chrono::Utc::now());
+        self.app.solver_session_id = crate::storage::new_write_id();
+        self.app.log("Solver session started");
+        session_id
+    }
+
+    /// Open the note popup for the latest guess, prefilled with its note.
+    pub fn open_guess_note(&mut self) {
+        let Some(index) = self.app.solver.guesses().len().checked_sub(1) else {
+            self.app.log("No guess to note");
+            return;
+        };
+        if self.app.mode == GameMode::Game && self.app.game_over {
+            self.app.log("Notes can only go on a game in play");
+            return;
+        }
+        let text = self
+            .app
+            .guess_notes
+            .get(&index)
+            .map(|(_, note)| note.clone())
+            .unwrap_or_default();
+        self.app.overlay = Some(Overlay::GuessNote { text });
+    }
+
+    /// Attach `text` to the latest guess, or remove its note when blank, and
+    /// write it through to the open game or session row.
+    pub fn save_guess_note(&mut self, text: &str) {
+        let Some(last) = self.app.solver.guesses().last() else {
+            return;
+        };
+        let word = last.word.clone();
+        let index = self.app.solver.guesses().len() - 1;
+        let note = text.trim();
+        let note = (!note.is_empty()).then(|| note.to_string());
+        match &note {
+            Some(note) => {
+                self.app
+                    .guess_notes
+                    .insert(index, (word.clone(), note.clone()));
+                self.app
+                    .log(format!("Noted guess {}: {}", word.to_uppercase(), note));
+            }
+            None => {
+                if self.app.guess_notes.remove(&index).is_some() {
+                    self.app
+                        .log(format!("Removed the note on {}", word.to_uppercase()));
+                }
+            }
+        }
+
+        match self.app.mode {
+            GameMode::Game => GameHandler::new(self.app).checkpoint(),
+            GameMode::Solver
+                if self.app.solver_session_active && !self.app.solver_session_paused =>
+            {
+                if let Some(sg) = self.app.solver_session_guesses.last_mut()
+                    && sg.word == word
+                {
+                    sg.note = note;
+                    self.checkpoint_session();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Drop notes whose guess is gone, or whose slot now holds another word.
+    fn prune_guess_notes(&mut self) {
+        let guesses = self.app.solver.guesses();
+        self.app
+            .guess_notes
+            .retain(|&i, (word, _)| guesses.get(i).is_some_and(|g| g.word == *word));
+    }
+
+    /// Take an undone guess, and its note, off the session's record, so the
+    /// record only ever holds the guesses on the board.
+    fn drop_session_guess(&mut self, word: &str) {
+        if self.app.mode != GameMode::Solver || self.app.solver_session_paused {
+            return;
+        }
+        if self
+            .app
+            .solver_session_guesses
+            .last()
+            .is_some_and(|sg| sg.word == word)
+        {
+            self.app.solver_session_guesses.pop();
+            self.checkpoint_session();
+        }
+    }
+
+    /// Write the session so far to history as an open row, so a crash
+    /// leaves a trace; the row is replaced when the session is saved.
+    /// Guesses are written a moment later together with any that follow,
+    /// while a session with none left has its row removed at once.
+    pub fn checkpoint_session(&mut self) {
+        if self.app.solver_session_guesses.is_empty() {
+            self.write_session_checkpoint();
+            return;
+        }
+        self.app.checkpoints.session = true;
+        self.app
+            .checkpoints
+            .since
+            .get_or_insert_with(std::time::Instant::now);
+    }
+
+    /// Write the open row now, unless the session has ended since it was
+    /// asked for and its final row replaced it.
+    pub fn write_session_checkpoint(&mut self) {
+        if !self.app.solver_session_active {
+            return;
+        }
+        let timestamp = self
+            .app
+            .solver_session_start
+            .unwrap_or_else(chrono::Utc::now);
+        if let Err(e) = self.app.db.checkpoint_solver_session(
+            &self.app.solver_session_id,
+            timestamp,
+            &self.app.solver_session_guesses,
+        ) {
+            tracing::warn!("Failed to checkpoint solver session: {:#}", e);
+        }
+    }
+
+    /// Offer to resume the solver session the last run left, if it's recent
+    /// enough and nothing has been guessed yet.
+    pub fn offer_resume(&mut self) {
+        let hours = self.app.settings.resume_window_hours;
+        if hours == 0 || self.app.mode != GameMode::Solver || !self.app.solver.guesses().is_empty()
+        {
+            return;
+        }
+
+        let since = chrono::Utc::now() - chrono::Duration::hours(hours as i64);
+        match self.app.db.resumable_solver_session(since) {
+            Ok(Some(session)) => {
+                let word_len = self.app.solver.word_len();
+                if session.guesses.iter().all(|g| g.word.len() == word_len) {
+                    self.app.overlay = Some(Overlay::ResumeSession(session));
+                }
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to look for a session to resume: {:#}", e),
+        }
+    }
+
+    /// Rebuild the solver from the offered session's guesses and carry on
+    /// recording under its id.
+    pub fn resume_offered_session(&mut self) {
+        let Some(Overlay::ResumeSession(session)) = self.app.overlay.take() else {
+            return;
+        };
+
+        let mut solver = SolverState::new(self.app.solver.word_len());
+        for guess in &session.guesses {
+            let feedback = guess.feedback.clone().unwrap_or_default();
+            if let Err(e) = solver.add_guess(Guess::new(guess.word.clone(), feedback)) {
+                self.app
+                    .log_error(format!("Could not resume solver session: {}", e));
+                return;
+            }
+        }
+
+        let uuid = session.uuid.unwrap_or_else(new_write_id);
+        if let Err(e) = self.app.db.reopen_solver_session(session.id, &uuid) {
+            self.app
+                .log_error(format!("Could not resume solver session: {:#}", e));
+            return;
+        }
+        self.app.solver = solver;
+        self.app.guess_notes = session
+            .guesses
+            .iter()
+            .enumerate()
+            .filter_map(|(i, g)| Some((i, (g.word.clone(), g.note.clone()?))))
+            .collect();
+        self.app.solver_session_guesses = session.guesses;
+        self.app.solver_session_id = uuid;
+        self.app.solver_session_start = Some(session.timestamp);
+        self.app.solver_session_active = true;
+        self.app.solver_session_paused = false;
+        self.app.log(format!(
+            "Resumed solver session with {} guess(es)",
+            self.app.solver.guesses().len()
+        ));
+
+        self.rebuild_entropy_history();
+        self.refresh_derived_state();
+    }
+
+    /// Leave the offered session abandoned and keep the fresh one.
+    pub fn decline_offered_session(&mut self) {
+        let Some(Overlay::ResumeSession(session)) = self.app.overlay.take() else {
+            return;
+        };
+        match self.app.db.decline_solver_resume(session.id) {
+            Ok(()) => self.app.log("Earlier solver session left abandoned"),
+            Err(e) => self
+                .app
+                .log_error(format!("Warning: failed to update solver session: {:#}", e)),
+        }
+    }
 }
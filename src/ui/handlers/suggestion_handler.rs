@@ -0,0 +1,207 @@
+use chrono::Utc;
+
+use crate::{
+    analysis::compute_constraint_summary, cli_style::CliStyle, priors::prior,
+    scoring::explain_score,
+};
+
+use super::super::{
+    app::App,
+    types::{GameMode, Overlay},
+};
+use super::SolverHandler;
+
+/// Helper struct for the suggestions panel selection and its actions.
+pub struct SuggestionHandler<'a> {
+    app: &'a mut App,
+}
+
+impl<'a> SuggestionHandler<'a> {
+    pub fn new(app: &'a mut App) -> Self {
+        Self { app }
+    }
+
+    fn visible_count(&self) -> usize {
+        self.app
+            .suggestions
+            .len()
+            .min(self.app.suggestion_rows.get())
+    }
+
+    /// Enter or leave suggestion selection. Only possible while the panel is visible.
+    pub fn toggle_selection(&mut self) {
+        if self.app.suggestion_selected.is_some() {
+            self.app.suggestion_selected = None;
+            return;
+        }
+
+        let panel_visible = self.app.mode == GameMode::Solver || self.app.show_suggestions;
+        if panel_visible && self.visible_count() > 0 {
+            self.app.suggestion_selected = Some(0);
+        }
+    }
+
+    /// Move the selection by `delta` rows, clamped to the visible list.
+    pub fn move_selection(&mut self, delta: isize) {
+        let count = self.visible_count();
+        if let Some(selected) = self.app.suggestion_selected
+            && count > 0
+        {
+            let next = selected.saturating_add_signed(delta).min(count - 1);
+            self.app.suggestion_selected = Some(next);
+        }
+    }
+
+    /// Drop the selection if the list shrank beneath it.
+    pub fn clamp_selection(&mut self) {
+        let count = self.visible_count();
+        if let Some(selected) = self.app.suggestion_selected {
+            self.app.suggestion_selected = if count == 0 {
+                None
+            } else {
+                Some(selected.min(count - 1))
+            };
+        }
+    }
+
+    pub fn selected_word(&self) -> Option<String> {
+        self.app
+            .suggestion_selected
+            .and_then(|i| self.app.suggested_words().nth(i))
+            .map(|(w, _)| w.to_string())
+    }
+
+    /// Open the score breakdown popup for the selected suggestion.
+    pub fn explain_selected(&mut self) {
+        let Some(word) = self.selected_word() else {
+            return;
+        };
+
+        let pool = self.app.solver.filter(&self.app.solution_words);
+        let constraints = compute_constraint_summary(&self.app.solver);
+        let mut explanation = explain_score(
+            &word,
+            &self.app.solution_words,
+            &pool,
+            &self.app.allowed_lookup,
+            &constraints,
+        );
+        if self.app.settings.answer_priors {
+            explanation.prior = Some(prior(&word, &self.app.settings.prior_weights));
+        }
+
+        self.app.overlay = Some(Overlay::ScoreExplanation(explanation));
+    }
+
+    /// Copy the selected suggestion into the input field and leave selection.
+    pub fn use_selected(&mut self) {
+        if let Some(word) = self.selected_word() {
+            self.app.input = if self.app.mode == GameMode::Solver {
+                format!("{} ", word)
+            } else {
+                word
+            };
+        }
+        self.app.suggestion_selected = None;
+    }
+
+    /// Stop suggesting the selected word, for this session or, if `permanent`, for good.
+    pub fn blacklist_selected(&mut self, permanent: bool) {
+        let Some(word) = self.selected_word() else {
+            return;
+        };
+
+        if permanent && let Err(e) = self.app.db.add_to_blacklist(&word) {
+            self.app
+                .log_error(format!("Warning: failed to save blacklist: {}", e));
+        }
+        let scope = if permanent {
+            "permanently"
+        } else {
+            "for this session"
+        };
+        self.app.log(format!("Blacklisted {} {}", word, scope));
+        self.app.session_blacklist.insert(word);
+
+        SolverHandler::new(self.app).refresh_derived_state();
+    }
+
+    /// Open the popup listing blacklisted words.
+    pub fn open_blacklist(&mut self) {
+        if self.app.session_blacklist.is_empty() {
+            self.app.log("Blacklist is empty");
+        } else {
+            self.app.overlay = Some(Overlay::Blacklist { selected: 0 });
+        }
+    }
+
+    /// Allow a blacklisted word to be suggested again, removing any saved entry.
+    pub fn unblacklist(&mut self, word: &str) {
+        if !self.app.session_blacklist.remove(word) {
+            return;
+        }
+        if let Err(e) = self.app.db.remove_from_blacklist(word) {
+            self.app
+                .log_error(format!("Warning: failed to save blacklist: {}", e));
+        }
+        self.app.log(format!("Removed {} from blacklist", word));
+
+        SolverHandler::new(self.app).refresh_derived_state();
+    }
+
+    /// Write the candidate pool to a timestamped file beside the database.
+    pub fn export_pool(&mut self, scores: bool) {
+        let Some(dir) = self.app.db.data_dir() else {
+            self.app
+                .log("Pool export needs a database file to export beside");
+            return;
+        };
+        let path = dir.join(format!("pool-{}.txt", Utc::now().format("%Y%m%d-%H%M%S")));
+        let text = SolverHandler::new(self.app).pool_export(scores, CliStyle::PLAIN);
+        let count = text.lines().count() - 1;
+
+        match std::fs::write(&path, text) {
+            Ok(()) => self.app.log(format!(
+                "Exported {} candidates to {}",
+                count,
+                path.display()
+            )),
+            Err(e) => self.app.log(format!(
+                "Failed to export pool to {}: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    /// Toggle hiding suggestions that contain letters known to be absent.
+    pub fn toggle_hide_gray(&mut self) {
+        self.app.settings.hide_gray_suggestions = !self.app.settings.hide_gray_suggestions;
+        self.app.save_settings();
+
+        let status = if self.app.settings.hide_gray_suggestions {
+            "hidden"
+        } else {
+            "shown"
+        };
+        self.app
+            .log(format!("Suggestions with gray letters {}", status));
+
+        SolverHandler::new(self.app).refresh_derived_state();
+    }
+
+    /// Toggle re-ranking suggestions by how answer-like they look.
+    pub fn toggle_answer_priors(&mut self) {
+        self.app.settings.answer_priors = !self.app.settings.answer_priors;
+        self.app.save_settings();
+
+        let status = if self.app.settings.answer_priors {
+            "on"
+        } else {
+            "off"
+        };
+        self.app.log(format!("Answer priors {}", status));
+
+        SolverHandler::new(self.app).refresh_derived_state();
+    }
+}
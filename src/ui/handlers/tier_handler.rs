@@ -0,0 +1,107 @@
+use crate::{
+    tiers::{ScoreJob, WordScores, list_hash, next_tier},
+    wordtable::WordTable,
+};
+
+use super::super::{app::App, types::TierJob};
+
+/// Answer pools solved per step, small enough to keep the UI responsive.
+const SCORING_BATCH: usize = 100;
+
+/// Helper struct for answer difficulty scores and drawing targets by tier.
+pub struct TierHandler<'a> {
+    app: &'a mut App,
+}
+
+impl<'a> TierHandler<'a> {
+    pub fn new(app: &'a mut App) -> Self {
+        Self { app }
+    }
+
+    /// Load the scores cached for the answer list, or start scoring it if it
+    /// hasn't been seen before.
+    pub fn load_scores(&mut self) {
+        let list_hash = list_hash(&self.app.solution_words);
+        match self.app.db.load_word_scores(&list_hash) {
+            Ok(scores) if scores.len() == self.app.solution_words.len() => {
+                self.app.word_scores = Some(WordScores::from_scores(scores));
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => self
+                .app
+                .log_error(format!("Failed to load answer difficulty: {}", e)),
+        }
+
+        self.app.log(format!(
+            "Scoring the difficulty of {} answers for this word list",
+            self.app.solution_words.len()
+        ));
+        self.app.tier_job = Some(TierJob {
+            list_hash,
+            job: ScoreJob::new(&self.app.solution_words),
+        });
+    }
+
+    /// Score the next batch of answers, caching every score once the last is done.
+    pub fn step_scoring(&mut self) {
+        let Some(mut tier_job) = self.app.tier_job.take() else {
+            return;
+        };
+
+        if !tier_job.job.step(&self.app.solution_words, SCORING_BATCH) {
+            let (found, total) = tier_job.job.progress();
+            self.app
+                .log(format!("Scoring answer difficulty: {}/{}", found, total));
+            self.app.tier_job = Some(tier_job);
+            return;
+        }
+
+        let scores = tier_job.job.finish(&self.app.solution_words);
+        if let Err(e) = self
+            .app
+            .db
+            .save_word_scores(&tier_job.list_hash, &scores.scores())
+        {
+            self.app
+                .log_error(format!("Warning: failed to save answer difficulty: {}", e));
+        }
+        let summary = format!(
+            "Scored {} answers; new games can be drawn by tier",
+            scores.len()
+        );
+        self.app.notify(&summary);
+        self.app.log(summary);
+        self.app.word_scores = Some(scores);
+    }
+
+    /// Move to the next tier new games draw their answers from.
+    pub fn cycle_tier(&mut self) {
+        let tier = next_tier(self.app.settings.word_tier);
+        self.app.settings.word_tier = tier;
+        self.app.save_settings();
+        match tier {
+            Some(tier) => self
+                .app
+                .log(format!("Answers: {} tier from the next game", tier.name())),
+            None => self.app.log("Answers: any from the next game"),
+        }
+    }
+
+    /// The answers new games draw from when a tier is chosen, or `None` to
+    /// draw from every answer, as when the scores aren't ready yet.
+    pub fn tier_words(&self) -> Option<WordTable> {
+        let tier = self.app.settings.word_tier?;
+        let Some(scores) = &self.app.word_scores else {
+            self.app.log(format!(
+                "Answer difficulty isn't scored yet; drawing from every answer, not just {}",
+                tier.name()
+            ));
+            return None;
+        };
+        Some(WordTable::new(
+            scores.words_in(tier),
+            self.app.solution_words.word_len(),
+        ))
+    }
+}
@@ -0,0 +1,230 @@
+use std::{path::PathBuf, time::Instant};
+
+use chrono::{DateTime, Utc};
+
+use super::super::{
+    app::App,
+    history::HistoryViewMode,
+    types::{
+        FocusTarget, GameMode, Overlay, UI_STATE_DELAY, UI_STATE_FILE, UI_STATE_MAX_AGE_MINUTES,
+        UiJournalEntry, UiStateSnapshot,
+    },
+};
+use super::{FocusHandler, GameHandler, HistoryHandler};
+
+/// Helper struct for journaling the transient UI state and restoring it at startup.
+pub struct UiStateHandler<'a> {
+    app: &'a mut App,
+}
+
+impl<'a> UiStateHandler<'a> {
+    pub fn new(app: &'a mut App) -> Self {
+        Self { app }
+    }
+
+    /// The UI state worth restoring after a crash.
+    pub fn snapshot(&self) -> UiStateSnapshot {
+        let history = self.app.history_data.as_ref();
+        UiStateSnapshot {
+            mode: self.app.mode.clone(),
+            input: self.app.input.clone(),
+            input_mode: self.app.input_mode,
+            show_suggestions: self.app.show_suggestions,
+            show_analysis: self.app.show_analysis,
+            suggestion_selected: self.app.suggestion_selected,
+            focus: self.app.focus,
+            history_view_mode: self.app.history_view_mode.clone(),
+            history_stats_section: self.app.history_stats_section,
+            history_page: self.app.history_page,
+            history_include_archived: self.app.history_include_archived,
+            history_compare_split: self.app.history_compare_split.map(|t| t.to_rfc3339()),
+            history_selected: history.and_then(|data| data.selected_game_id()),
+        }
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        self.app.db.data_dir().map(|dir| dir.join(UI_STATE_FILE))
+    }
+
+    /// Read the state the last run journaled, keeping it to offer if it was
+    /// saved in the last few minutes.
+    pub fn load(&mut self, now: DateTime<Utc>) {
+        let Some(path) = self.path() else {
+            return;
+        };
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+            Err(e) => {
+                tracing::warn!("Failed to read {}: {}", path.display(), e);
+                return;
+            }
+        };
+        let entry: UiJournalEntry = match serde_json::from_str(&text) {
+            Ok(entry) => entry,
+            Err(e) => {
+                tracing::warn!("Ignoring unreadable {}: {}", path.display(), e);
+                return;
+            }
+        };
+        let recent = DateTime::parse_from_rfc3339(&entry.saved_at).is_ok_and(|saved| {
+            now - saved.with_timezone(&Utc) <= chrono::Duration::minutes(UI_STATE_MAX_AGE_MINUTES)
+        });
+        if recent {
+            self.app.ui_journal.pending = Some(entry);
+        }
+    }
+
+    /// Offer the state left by the last run, unless another prompt is up or
+    /// restoring it would change nothing.
+    pub fn offer_restore(&mut self) {
+        if self.app.overlay.is_some() {
+            return;
+        }
+        let Some(entry) = self.app.ui_journal.pending.take() else {
+            return;
+        };
+        if entry.state != self.snapshot() {
+            self.app.overlay = Some(Overlay::RestoreUiState(entry));
+        }
+    }
+
+    pub fn restore_offered(&mut self) {
+        if let Some(Overlay::RestoreUiState(entry)) = self.app.overlay.take() {
+            self.restore(&entry.state);
+            self.app.log("Restored where you left off");
+        }
+    }
+
+    pub fn decline_offered(&mut self) {
+        if let Some(Overlay::RestoreUiState(_)) = self.app.overlay.take() {
+            self.app.log("Starting from a fresh view");
+        }
+    }
+
+    /// Put the UI back as `state` describes, going through the usual mode
+    /// changes so sessions and history load as they would by hand. A resumed
+    /// solver session is never abandoned for a new game.
+    pub fn restore(&mut self, state: &UiStateSnapshot) {
+        match state.mode {
+            GameMode::Game
+                if self.app.mode == GameMode::Solver && self.app.solver.guesses().is_empty() =>
+            {
+                GameHandler::new(self.app).toggle_game_mode()
+            }
+            GameMode::History if self.app.mode != GameMode::History => self.restore_history(state),
+            _ => {}
+        }
+
+        self.app.input = state.input.clone();
+        self.app.input_mode = state.input_mode;
+        if self.app.mode == GameMode::Game {
+            self.app.show_suggestions = state.show_suggestions;
+            self.app.show_analysis = state.show_analysis;
+        }
+
+        if self.app.focusable().contains(&state.focus) {
+            FocusHandler::new(self.app).focus(state.focus);
+        }
+        if state.focus == FocusTarget::Suggestions
+            && let Some(selected) = state.suggestion_selected
+            && selected < self.app.suggestions.len()
+        {
+            self.app.suggestion_selected = Some(selected);
+        }
+    }
+
+    fn restore_history(&mut self, state: &UiStateSnapshot) {
+        let mut history = HistoryHandler::new(self.app);
+        history.enter_history_mode();
+        if state.history_include_archived {
+            history.toggle_include_archived();
+        }
+        if let Some(id) = state.history_selected {
+            history.open_game(id);
+        }
+        history.load_through((state.history_page + 1) * 10 - 1);
+
+        let app = &mut *self.app;
+        app.history_compare_split = state
+            .history_compare_split
+            .as_deref()
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc));
+        if let Some(data) = &app.history_data {
+            app.history_page = state.history_page.min(data.total_pages().saturating_sub(1));
+        }
+        let selected = app
+            .history_data
+            .as_ref()
+            .is_some_and(|data| data.selected_game().is_some());
+        app.history_view_mode = match state.history_view_mode {
+            HistoryViewMode::Detail if !selected => HistoryViewMode::List,
+            ref view => view.clone(),
+        };
+        app.history_stats_section = state.history_stats_section;
+        if app.history_view_mode == HistoryViewMode::Compare {
+            HistoryHandler::new(self.app).open_compare();
+        }
+    }
+
+    /// Journal the state once it has gone [`UI_STATE_DELAY`] without being
+    /// written since it changed.
+    pub fn note_changes(&mut self, now: Instant) {
+        if self.restore_undecided() {
+            return;
+        }
+        let state = self.snapshot();
+        if self.app.ui_journal.written.as_ref() == Some(&state) {
+            self.app.ui_journal.changed_at = None;
+            return;
+        }
+        let changed_at = *self.app.ui_journal.changed_at.get_or_insert(now);
+        if now.duration_since(changed_at) >= UI_STATE_DELAY {
+            self.write(state);
+        }
+    }
+
+    /// Journal the state now, as the app quits.
+    pub fn flush(&mut self) {
+        if self.restore_undecided() {
+            return;
+        }
+        let state = self.snapshot();
+        if self.app.ui_journal.written.as_ref() != Some(&state) {
+            self.write(state);
+        }
+    }
+
+    /// Whether the last run's state is yet to be restored or declined.
+    fn restore_undecided(&self) -> bool {
+        self.app.ui_journal.pending.is_some()
+            || matches!(self.app.overlay, Some(Overlay::RestoreUiState(_)))
+    }
+
+    fn write(&mut self, state: UiStateSnapshot) {
+        self.app.ui_journal.changed_at = None;
+        let Some(path) = self.path() else {
+            self.app.ui_journal.written = Some(state);
+            return;
+        };
+        let entry = UiJournalEntry {
+            saved_at: Utc::now().to_rfc3339(),
+            state,
+        };
+        // Written aside and renamed so a crash mid-write leaves the last copy whole
+        let temp = path.with_extension("json.tmp");
+        let written = serde_json::to_string(&entry)
+            .map_err(std::io::Error::other)
+            .and_then(|json| std::fs::write(&temp, json))
+            .and_then(|()| std::fs::rename(&temp, &path));
+        if let Err(e) = written {
+            tracing::warn!(
+                "Failed to journal the UI state to {}: {}",
+                path.display(),
+                e
+            );
+        }
+        self.app.ui_journal.written = Some(entry.state);
+    }
+}
@@ -0,0 +1,282 @@
+//! Key hints for the status bar, picked from the bindings that apply to what
+//! the app is showing.
+
+use crate::settings::Difficulty;
+
+use super::{
+    app::App,
+    history::{HistoryViewMode, StatsSection},
+    types::{FocusTarget, GameMode, InputMode, Overlay},
+};
+
+/// A key and what it does right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hint {
+    pub key: &'static str,
+    pub action: &'static str,
+}
+
+/// A binding worth a hint, and when it applies.
+struct Binding {
+    key: &'static str,
+    action: &'static str,
+    applies: fn(&App) -> bool,
+}
+
+const fn binding(key: &'static str, action: &'static str, applies: fn(&App) -> bool) -> Binding {
+    Binding {
+        key,
+        action,
+        applies,
+    }
+}
+
+/// Every hinted binding, most important first; the bar drops what doesn't fit
+/// from the end.
+const BINDINGS: &[Binding] = &[
+    binding("Esc", "cancel recompute", |app| app.recompute.is_some()),
+    binding("Esc", "close", |app| app.overlay.is_some()),
+    binding("b", "bug report", |app| {
+        matches!(app.overlay, Some(Overlay::About(_)))
+    }),
+    // A pool the guesses emptied needs a pattern fixed before anything else
+    binding("Ctrl+Z", "undo last guess", pool_conflict),
+    binding("Ctrl+E", "fix a pattern", pool_conflict),
+    // Solver
+    binding("Ctrl+Y", "confirm answer", |app| {
+        solving(app) && app.last_candidate.is_some()
+    }),
+    binding("Enter", "submit word + pattern", solving),
+    binding("Ctrl+Z", "undo", solving),
+    binding("Ctrl+E", "edit", solving),
+    binding("Ctrl+F", "search", solver_typing),
+    binding("Ctrl+B", "snapshots", solver_typing),
+    binding("Ctrl+G", "game", solver_typing),
+    // Game in play
+    binding("Backspace", "cancel submit", |app| {
+        playing(app) && app.pending_submit.is_some()
+    }),
+    binding("Enter", "submit", |app| playing(app) && !app.shadow),
+    binding("Enter", "submit word + pattern", |app| {
+        playing(app) && app.shadow
+    }),
+    binding("Ctrl+Z", "undo", |app| {
+        playing(app) && app.game_difficulty == Difficulty::Easy && app.boards.is_none()
+    }),
+    binding("Ctrl+B", "best guess", assisted),
+    binding("Ctrl+H", "hints", |app| assisted(app) && !app.kids),
+    binding("Ctrl+A", "analysis", assisted),
+    binding("Ctrl+X", "give up", playing),
+    // Game over
+    binding("Enter", "next game now", |app| {
+        game_over(app) && gauntlet_waiting(app)
+    }),
+    binding("Enter", "new game", |app| {
+        game_over(app) && !gauntlet_waiting(app)
+    }),
+    binding("Ctrl+T", "blitz", game_over),
+    binding("Ctrl+N", "gauntlet", game_over),
+    binding("Ctrl+U", "shadow", game_over),
+    binding("Ctrl+K", "boards", game_over),
+    binding("Ctrl+S", "solver", |app| playing(app) || game_over(app)),
+    // Vim normal mode
+    binding("i/a", "insert", normal_mode),
+    binding("j/k", "suggestions", normal_mode),
+    binding("u", "undo", normal_mode),
+    // Focused panels
+    binding("↑/↓", "move", suggestions),
+    binding("Enter", "use", suggestions),
+    binding("i", "explain", suggestions),
+    binding("x", "hide", suggestions),
+    binding("b", "blacklist", suggestions),
+    binding("↑/↓", "scroll", guesses),
+    binding("Home/End", "first/latest", guesses),
+    binding("Enter", "find", searching_logs),
+    binding("Esc", "cancel", searching_logs),
+    binding("↑/↓ PgUp/PgDn", "scroll", logs),
+    binding("n/N", "older/newer match", |app| {
+        history_logs(app) && app.log_search.is_some()
+    }),
+    binding("/", "search", history_logs),
+    binding("L", "full logs", |app| {
+        history_logs(app) && !app.history_logs_expanded
+    }),
+    binding("L", "compact logs", |app| {
+        history_logs(app) && app.history_logs_expanded
+    }),
+    binding("Esc", "done", |app| {
+        suggestions(app) || guesses(app) || (logs(app) && !searching_logs(app))
+    }),
+    // History
+    binding("PgDn", "by time", overview),
+    binding("PgUp", "overview", timing),
+    binding("c", "compare", stats_view),
+    binding("w", "week report", stats_view),
+    binding("1-9", "view game", list_view),
+    binding("PgUp/PgDn", "page", list_view),
+    binding("v", "compact", |app| {
+        list_view(app) && !app.history_compact_list
+    }),
+    binding("v", "table", |app| {
+        list_view(app) && app.history_compact_list
+    }),
+    binding("A", "show/hide archived", |app| {
+        stats_view(app) || list_view(app)
+    }),
+    binding("←/→", "prev/next", detail_view),
+    binding("Home/End", "first/last", detail_view),
+    binding("a", "archive", |app| {
+        detail_view(app) && selected_archived(app) == Some(false)
+    }),
+    binding("a", "restore", |app| {
+        detail_view(app) && selected_archived(app) == Some(true)
+    }),
+    binding("o", "recompute optimal", solver_view),
+    binding("PgUp/PgDn", "move split", compare_view),
+    binding("Home", "halves", compare_view),
+    binding("Tab", "views", browsing_history),
+    binding("L", "full logs", browsing_history),
+    binding("Esc", "back", |app| {
+        list_view(app) || detail_view(app) || compare_view(app)
+    }),
+    binding("Ctrl+R", "history", |app| {
+        solver_typing(app) || playing(app) || game_over(app)
+    }),
+    binding("Ctrl+R", "exit", browsing_history),
+    binding("Ctrl+Q", "quit", |_| true),
+];
+
+/// The hints for the app as it is now, most important first.
+pub fn status_hints(app: &App) -> Vec<Hint> {
+    BINDINGS
+        .iter()
+        .filter(|b| (b.applies)(app))
+        .map(|b| Hint {
+            key: b.key,
+            action: b.action,
+        })
+        .collect()
+}
+
+/// Keys reach the panels rather than a popup or a running recompute.
+fn unobstructed(app: &App) -> bool {
+    app.overlay.is_none() && app.recompute.is_none()
+}
+
+fn focused(app: &App, target: FocusTarget) -> bool {
+    unobstructed(app) && app.focused() == target
+}
+
+/// Typing goes into the input field.
+fn typing(app: &App) -> bool {
+    focused(app, FocusTarget::Input)
+        && !(app.settings.modal_input && app.input_mode == InputMode::Normal)
+}
+
+fn normal_mode(app: &App) -> bool {
+    focused(app, FocusTarget::Input)
+        && app.settings.modal_input
+        && app.input_mode == InputMode::Normal
+}
+
+fn solver_typing(app: &App) -> bool {
+    typing(app) && app.mode == GameMode::Solver
+}
+
+/// Solver input with guesses that still leave answers, or none yet.
+fn solving(app: &App) -> bool {
+    solver_typing(app) && !pool_conflict(app)
+}
+
+fn playing(app: &App) -> bool {
+    typing(app) && app.mode == GameMode::Game && !app.game_over
+}
+
+/// Playing a game that allows hints and the analysis panels.
+fn assisted(app: &App) -> bool {
+    playing(app) && app.game_difficulty != Difficulty::Expert
+}
+
+fn game_over(app: &App) -> bool {
+    typing(app) && app.mode == GameMode::Game && app.game_over
+}
+
+/// A gauntlet game has ended and the next starts on a countdown.
+fn gauntlet_waiting(app: &App) -> bool {
+    app.gauntlet.as_ref().is_some_and(|g| g.next_at.is_some())
+}
+
+fn suggestions(app: &App) -> bool {
+    focused(app, FocusTarget::Suggestions)
+}
+
+fn guesses(app: &App) -> bool {
+    focused(app, FocusTarget::Guesses)
+}
+
+fn logs(app: &App) -> bool {
+    focused(app, FocusTarget::Logs)
+}
+
+/// The history-mode logs have focus, with no search being typed.
+fn history_logs(app: &App) -> bool {
+    logs(app) && app.mode == GameMode::History && !searching_logs(app)
+}
+
+/// A search through the history-mode logs is being typed.
+fn searching_logs(app: &App) -> bool {
+    logs(app) && app.log_search.as_ref().is_some_and(|s| s.editing)
+}
+
+/// The solver guesses rule out every answer, so one of their patterns is wrong.
+fn pool_conflict(app: &App) -> bool {
+    solver_typing(app)
+        && !app.solver.guesses().is_empty()
+        && app
+            .solution_pool_stats
+            .as_ref()
+            .is_some_and(|stats| stats.total_remaining == 0)
+}
+
+fn browsing_history(app: &App) -> bool {
+    focused(app, FocusTarget::History) && app.mode == GameMode::History
+}
+
+fn history_view(app: &App, view: HistoryViewMode) -> bool {
+    browsing_history(app) && app.history_view_mode == view
+}
+
+fn stats_view(app: &App) -> bool {
+    history_view(app, HistoryViewMode::Stats)
+}
+
+fn overview(app: &App) -> bool {
+    stats_view(app) && app.history_stats_section == StatsSection::Overview
+}
+
+fn timing(app: &App) -> bool {
+    stats_view(app) && app.history_stats_section == StatsSection::Timing
+}
+
+fn list_view(app: &App) -> bool {
+    history_view(app, HistoryViewMode::List)
+}
+
+fn detail_view(app: &App) -> bool {
+    history_view(app, HistoryViewMode::Detail)
+}
+
+fn solver_view(app: &App) -> bool {
+    history_view(app, HistoryViewMode::Solver)
+}
+
+fn compare_view(app: &App) -> bool {
+    history_view(app, HistoryViewMode::Compare)
+}
+
+fn selected_archived(app: &App) -> Option<bool> {
+    app.history_data
+        .as_ref()?
+        .selected_game()
+        .map(|game| game.is_archived())
+}
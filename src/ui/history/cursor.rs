@@ -0,0 +1,123 @@
+//! Ordered view over the game list with a movable selection.
+
+/// The games the history views show, in display order, and which one is selected.
+///
+/// `order` holds indices into `HistoryData::games`. The list view pages through
+/// it and the detail view steps along it, so both agree on what "next" means.
+#[derive(Debug, Clone, Default)]
+pub struct GameCursor {
+    order: Vec<usize>,
+    position: Option<usize>,
+}
+
+impl GameCursor {
+    /// Show all `len` games in their stored order with nothing selected.
+    pub fn new(len: usize) -> Self {
+        Self {
+            order: (0..len).collect(),
+            position: None,
+        }
+    }
+
+    /// Number of games in the view.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Game indices in display order.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Position of the selection within the view.
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+
+    /// Index into the games list of the selected game.
+    pub fn current(&self) -> Option<usize> {
+        self.position.map(|p| self.order[p])
+    }
+
+    /// Select the game at `position` in the view; out of range is ignored.
+    pub fn select(&mut self, position: usize) {
+        if position < self.order.len() {
+            self.position = Some(position);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.position = None;
+    }
+
+    /// Move the selection by `delta`, stopping at either end.
+    ///
+    /// Returns false if the selection could not move.
+    pub fn step(&mut self, delta: isize) -> bool {
+        let Some(position) = self.position else {
+            return false;
+        };
+        let next = position
+            .saturating_add_signed(delta)
+            .min(self.order.len() - 1);
+        self.position = Some(next);
+        next != position
+    }
+
+    pub fn first(&mut self) {
+        self.select(0);
+    }
+
+    pub fn last(&mut self) {
+        if let Some(last) = self.order.len().checked_sub(1) {
+            self.select(last);
+        }
+    }
+
+    /// Append newly loaded games to the end of the view.
+    pub fn extend(&mut self, games: std::ops::Range<usize>) {
+        self.order.extend(games);
+    }
+
+    /// Replace the view, keeping the selected game if it is still visible.
+    pub fn reorder(&mut self, order: Vec<usize>) {
+        let current = self.current();
+        self.order = order;
+        self.position = current.and_then(|game| self.order.iter().position(|&i| i == game));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_clamps_at_ends() {
+        let mut cursor = GameCursor::new(3);
+        assert!(!cursor.step(1));
+
+        cursor.first();
+        assert!(!cursor.step(-1));
+        assert!(cursor.step(1));
+        assert!(cursor.step(5));
+        assert_eq!(cursor.position(), Some(2));
+        assert!(!cursor.step(1));
+    }
+
+    #[test]
+    fn test_reorder_keeps_selected_game() {
+        let mut cursor = GameCursor::new(4);
+        cursor.select(1);
+
+        cursor.reorder(vec![3, 1, 0]);
+        assert_eq!(cursor.position(), Some(1));
+        assert_eq!(cursor.current(), Some(1));
+
+        cursor.reorder(vec![3, 0]);
+        assert_eq!(cursor.current(), None);
+    }
+}
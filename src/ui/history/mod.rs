@@ -1,5 +1,12 @@
+mod cursor;
 pub mod solver_types;
+pub mod timing;
 mod types;
 
-pub use solver_types::{SolverOutcome, SolverStats};
-pub use types::{GameGuess, GameOutcome, GameRecord, HistoryData, HistoryStats, HistoryViewMode};
+pub use cursor::GameCursor;
+pub use solver_types::{DeviationMetric, SolverOutcome, SolverStats};
+pub use types::{
+    AbandonCounts, AbandonReason, BoardResult, GameGuess, GameOutcome, GameRecord, HintKind,
+    HistoryData, HistoryStats, HistoryViewMode, KidsGameRecord, MultiBoardRecord, StatsComparison,
+    StatsSection, UNKNOWN_TARGET,
+};
@@ -2,6 +2,31 @@
 
 use chrono::{DateTime, Utc};
 
+use crate::solver::Feedback;
+
+use super::types::{AbandonCounts, AbandonReason};
+
+/// What a guess's deviation from the optimal word was measured in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviationMetric {
+    /// Expected entropy against the pool, in bits.
+    Entropy,
+    /// Letter-frequency score, for pools over
+    /// [`ENTROPY_SCORING_MAX_POOL`](crate::scoring::ENTROPY_SCORING_MAX_POOL).
+    Heuristic,
+}
+
+impl DeviationMetric {
+    /// Unit of deviations in this metric.
+    pub fn unit(self) -> &'static str {
+        match self {
+            DeviationMetric::Entropy => "bits",
+            DeviationMetric::Heuristic => "score",
+        }
+    }
+}
+
 /// A single guess within a solver session
 #[derive(Debug, Clone)]
 pub struct SolverGuess {
@@ -9,22 +34,85 @@ pub struct SolverGuess {
     pub pool_size_before: usize,
     pub pool_size_after: usize,
     pub entropy: f64,
-    pub optimal_word: String,
-    pub optimal_entropy: f64,
-    pub deviation_score: f64,
+    /// Best word by `metric`; `None` when there was nothing to score, as with an empty pool.
+    pub optimal_word: Option<String>,
+    /// Expected entropy of `optimal_word`, or its score when measured by heuristic.
+    pub optimal_entropy: Option<f64>,
+    /// The guess's expected entropy or score less the optimal word's.
+    /// Recorded exactly when `optimal_word` is; skipped guesses count toward
+    /// neither adherence nor average deviation.
+    pub deviation_score: Option<f64>,
+    /// What `deviation_score` is measured in; set exactly when it is.
+    pub metric: Option<DeviationMetric>,
+    /// The pattern the guess got; `None` for sessions saved before patterns were kept.
+    pub feedback: Option<Vec<Feedback>>,
+    /// Note the player left on the guess while solving.
+    pub note: Option<String>,
 }
 
 impl SolverGuess {
-    /// Returns true if this guess was optimal (or within 0.01 of optimal)
-    pub fn was_optimal(&self) -> bool {
-        self.deviation_score >= -0.01
+    /// Whether this guess was optimal (or within 0.01 of optimal), if that was measured
+    pub fn was_optimal(&self) -> Option<bool> {
+        self.deviation_score.map(|d| d >= -0.01)
+    }
+}
+
+/// Percentage of measured guesses that were optimal, and their mean deviation.
+fn adherence_and_deviation<'a>(
+    guesses: impl Iterator<Item = &'a SolverGuess>,
+) -> Option<(f64, f64)> {
+    let (mut count, mut optimal, mut total) = (0, 0, 0.0);
+    for guess in guesses {
+        if let Some(deviation) = guess.deviation_score {
+            count += 1;
+            total += deviation;
+            if guess.was_optimal() == Some(true) {
+                optimal += 1;
+            }
+        }
+    }
+    (count > 0).then(|| {
+        let count = count as f64;
+        (optimal as f64 / count * 100.0, total / count)
+    })
+}
+
+/// Adherence and mean deviation over the guesses measured by one metric.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MetricStats {
+    pub measured: usize,
+    pub optimal_adherence: f64,
+    pub average_deviation: f64,
+}
+
+impl MetricStats {
+    fn over<'a>(guesses: impl Iterator<Item = &'a SolverGuess>, metric: DeviationMetric) -> Self {
+        let measured: Vec<&SolverGuess> = guesses.filter(|g| g.metric == Some(metric)).collect();
+        adherence_and_deviation(measured.iter().copied()).map_or_else(Self::default, |(a, d)| {
+            Self {
+                measured: measured.len(),
+                optimal_adherence: a,
+                average_deviation: d,
+            }
+        })
     }
 }
 
 /// Outcome of a solver session
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SolverOutcome {
-    Completed { guesses: usize },
+    Completed {
+        guesses: usize,
+    },
+    /// The answer the user reported was not the word the solver converged on.
+    CompletedMismatch {
+        guesses: usize,
+    },
+    /// The session was left before the puzzle was solved.
+    Abandoned {
+        guesses: usize,
+        reason: AbandonReason,
+    },
 }
 
 /// A complete solver session record
@@ -33,6 +121,8 @@ pub struct SolverSession {
     pub timestamp: DateTime<Utc>,
     pub guesses: Vec<SolverGuess>,
     pub outcome: SolverOutcome,
+    /// The real answer, once the user has confirmed or typed it.
+    pub resolved_word: Option<String>,
 }
 
 impl SolverSession {
@@ -41,22 +131,16 @@ impl SolverSession {
         self.guesses.len()
     }
 
-    /// Returns the percentage of guesses that were optimal
+    /// Returns the percentage of measured guesses that were optimal
     pub fn optimal_adherence(&self) -> f64 {
-        if self.guesses.is_empty() {
-            return 100.0;
-        }
-        let optimal_count = self.guesses.iter().filter(|g| g.was_optimal()).count();
-        (optimal_count as f64 / self.guesses.len() as f64) * 100.0
+        adherence_and_deviation(self.guesses.iter()).map_or(100.0, |(adherence, _)| adherence)
     }
 
-    /// Returns the average deviation from optimal path
-    pub fn average_deviation(&self) -> f64 {
-        if self.guesses.is_empty() {
-            return 0.0;
-        }
-        let total: f64 = self.guesses.iter().map(|g| g.deviation_score).sum();
-        total / self.guesses.len() as f64
+    /// Returns the average deviation from optimal path over the guesses
+    /// measured by `metric`, if there are any
+    pub fn average_deviation(&self, metric: DeviationMetric) -> Option<f64> {
+        let measured = self.guesses.iter().filter(|g| g.metric == Some(metric));
+        adherence_and_deviation(measured).map(|(_, deviation)| deviation)
     }
 
     /// Returns the average entropy per guess
@@ -76,8 +160,18 @@ pub struct SolverStats {
     pub completed_sessions: usize,
     pub average_guesses: f64,
     pub average_entropy: f64,
+    /// Percentage of measured guesses that were optimal, each by its own metric.
     pub optimal_adherence: f64,
-    pub average_deviation: f64,
+    /// Guesses measured by expected entropy, deviating in bits.
+    pub entropy: MetricStats,
+    /// Guesses in pools too large for entropy, deviating in heuristic score.
+    pub heuristic: MetricStats,
+    /// Sessions whose real answer the user reported.
+    pub verified_sessions: usize,
+    pub mismatched_sessions: usize,
+    pub abandoned: AbandonCounts,
+    /// Percentage of verified sessions that converged on the wrong word.
+    pub mismatch_rate: f64,
 }
 
 impl SolverStats {
@@ -94,8 +188,6 @@ impl SolverStats {
 
         let mut total_guesses = 0;
         let mut total_entropy = 0.0;
-        let mut total_optimal_guesses = 0;
-        let mut total_deviation = 0.0;
         let mut all_guess_count = 0;
 
         for session in sessions {
@@ -104,16 +196,20 @@ impl SolverStats {
                     stats.completed_sessions += 1;
                     total_guesses += guesses;
                 }
+                SolverOutcome::CompletedMismatch { guesses } => {
+                    stats.completed_sessions += 1;
+                    stats.mismatched_sessions += 1;
+                    total_guesses += guesses;
+                }
+                SolverOutcome::Abandoned { reason, .. } => stats.abandoned.add(reason),
+            }
+            if session.resolved_word.is_some() {
+                stats.verified_sessions += 1;
             }
 
-            // Count optimal guesses and deviations across all sessions
             for guess in &session.guesses {
                 all_guess_count += 1;
                 total_entropy += guess.entropy;
-                total_deviation += guess.deviation_score;
-                if guess.was_optimal() {
-                    total_optimal_guesses += 1;
-                }
             }
         }
 
@@ -123,11 +219,157 @@ impl SolverStats {
 
         if all_guess_count > 0 {
             stats.average_entropy = total_entropy / all_guess_count as f64;
-            stats.optimal_adherence =
-                (total_optimal_guesses as f64 / all_guess_count as f64) * 100.0;
-            stats.average_deviation = total_deviation / all_guess_count as f64;
         }
 
+        if stats.verified_sessions > 0 {
+            stats.mismatch_rate =
+                stats.mismatched_sessions as f64 / stats.verified_sessions as f64 * 100.0;
+        }
+
+        // Guesses with nothing to measure against are left out
+        let guesses = sessions.iter().flat_map(|s| &s.guesses);
+        if let Some((adherence, _)) = adherence_and_deviation(guesses.clone()) {
+            stats.optimal_adherence = adherence;
+        }
+        // Bits and scores don't average together
+        stats.entropy = MetricStats::over(guesses.clone(), DeviationMetric::Entropy);
+        stats.heuristic = MetricStats::over(guesses, DeviationMetric::Heuristic);
+
         stats
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guess(deviation_score: Option<f64>) -> SolverGuess {
+        SolverGuess {
+            word: "crane".to_string(),
+            pool_size_before: 0,
+            pool_size_after: 0,
+            entropy: 1.0,
+            optimal_word: deviation_score.map(|_| "stone".to_string()),
+            optimal_entropy: deviation_score.map(|d| 1.0 - d),
+            deviation_score,
+            metric: deviation_score.map(|_| DeviationMetric::Entropy),
+            feedback: None,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn test_unmeasured_guesses_excluded_from_stats() {
+        let session = SolverSession {
+            timestamp: Utc::now(),
+            guesses: vec![guess(None), guess(Some(-2.0)), guess(Some(0.0))],
+            outcome: SolverOutcome::Completed { guesses: 3 },
+            resolved_word: None,
+        };
+
+        assert_eq!(session.optimal_adherence(), 50.0);
+        assert_eq!(
+            session.average_deviation(DeviationMetric::Entropy),
+            Some(-1.0)
+        );
+
+        let stats = SolverStats::from_sessions(&[session]);
+        assert_eq!(stats.optimal_adherence, 50.0);
+        assert_eq!(stats.entropy.measured, 2);
+        assert_eq!(stats.entropy.average_deviation, -1.0);
+        // Entropy is still known for every guess
+        assert_eq!(stats.average_entropy, 1.0);
+    }
+
+    #[test]
+    fn test_session_without_measured_guesses() {
+        let session = SolverSession {
+            timestamp: Utc::now(),
+            guesses: vec![guess(None)],
+            outcome: SolverOutcome::Completed { guesses: 1 },
+            resolved_word: None,
+        };
+
+        assert_eq!(session.optimal_adherence(), 100.0);
+        assert_eq!(session.average_deviation(DeviationMetric::Entropy), None);
+
+        let stats = SolverStats::from_sessions(&[session]);
+        assert_eq!(stats.optimal_adherence, 0.0);
+        assert_eq!(stats.entropy, MetricStats::default());
+    }
+
+    #[test]
+    fn test_stats_segmented_by_metric() {
+        let scored = |deviation| SolverGuess {
+            metric: Some(DeviationMetric::Heuristic),
+            ..guess(Some(deviation))
+        };
+        let session = SolverSession {
+            timestamp: Utc::now(),
+            guesses: vec![
+                scored(-30.0),
+                scored(0.0),
+                guess(Some(-0.5)),
+                guess(Some(0.0)),
+            ],
+            outcome: SolverOutcome::Completed { guesses: 4 },
+            resolved_word: None,
+        };
+
+        assert_eq!(
+            session.average_deviation(DeviationMetric::Entropy),
+            Some(-0.25)
+        );
+        assert_eq!(
+            session.average_deviation(DeviationMetric::Heuristic),
+            Some(-15.0)
+        );
+
+        let stats = SolverStats::from_sessions(&[session]);
+        // Each guess is optimal or not by its own metric
+        assert_eq!(stats.optimal_adherence, 50.0);
+        assert_eq!(
+            stats.entropy,
+            MetricStats {
+                measured: 2,
+                optimal_adherence: 50.0,
+                average_deviation: -0.25,
+            }
+        );
+        assert_eq!(
+            stats.heuristic,
+            MetricStats {
+                measured: 2,
+                optimal_adherence: 50.0,
+                average_deviation: -15.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_mismatch_rate_over_verified_sessions() {
+        let session = |outcome, resolved_word: Option<&str>| SolverSession {
+            timestamp: Utc::now(),
+            guesses: vec![guess(Some(0.0)); 3],
+            outcome,
+            resolved_word: resolved_word.map(str::to_string),
+        };
+        let sessions = [
+            session(SolverOutcome::Completed { guesses: 3 }, Some("stone")),
+            session(
+                SolverOutcome::CompletedMismatch { guesses: 3 },
+                Some("slate"),
+            ),
+            session(SolverOutcome::Completed { guesses: 3 }, Some("crane")),
+            session(SolverOutcome::Completed { guesses: 3 }, Some("house")),
+            // Never verified, so it counts toward neither side
+            session(SolverOutcome::Completed { guesses: 3 }, None),
+        ];
+
+        let stats = SolverStats::from_sessions(&sessions);
+        assert_eq!(stats.completed_sessions, 5);
+        assert_eq!(stats.verified_sessions, 4);
+        assert_eq!(stats.mismatched_sessions, 1);
+        assert_eq!(stats.mismatch_rate, 25.0);
+    }
+}
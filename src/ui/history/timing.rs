@@ -0,0 +1,231 @@
+//! Game results by the local time of day and day of the week they were played.
+
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc, Weekday};
+
+use super::types::{AbandonReason, GameOutcome, GameRecord};
+
+/// Games a bucket needs before its averages are shown.
+pub const MIN_BUCKET_GAMES: usize = 3;
+
+/// A stretch of the day by the local clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayPart {
+    /// 05:00 to noon.
+    Morning,
+    /// Noon to 17:00.
+    Afternoon,
+    /// 17:00 to 22:00.
+    Evening,
+    /// 22:00 to 05:00, across midnight.
+    Night,
+}
+
+impl DayPart {
+    pub const ALL: [DayPart; 4] = [
+        DayPart::Morning,
+        DayPart::Afternoon,
+        DayPart::Evening,
+        DayPart::Night,
+    ];
+
+    pub fn from_hour(hour: u32) -> Self {
+        match hour {
+            5..=11 => DayPart::Morning,
+            12..=16 => DayPart::Afternoon,
+            17..=21 => DayPart::Evening,
+            _ => DayPart::Night,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DayPart::Morning => "Morning",
+            DayPart::Afternoon => "Afternoon",
+            DayPart::Evening => "Evening",
+            DayPart::Night => "Night",
+        }
+    }
+}
+
+/// The part of the day and weekday `timestamp` falls on in `tz`.
+///
+/// Both come from the wall clock in `tz`, so a game keeps the weekday it was
+/// played on locally and a daylight saving change moves the hour with it.
+pub fn local_buckets<Tz: TimeZone>(timestamp: DateTime<Utc>, tz: &Tz) -> (DayPart, Weekday) {
+    let local = timestamp.with_timezone(tz);
+    (DayPart::from_hour(local.hour()), local.weekday())
+}
+
+/// Results of the games in one bucket.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BucketStats {
+    /// Games counted toward the win rate; crashed games aren't.
+    pub games: usize,
+    pub wins: usize,
+    /// Guesses over the won games.
+    pub guesses: usize,
+}
+
+impl BucketStats {
+    fn add(&mut self, game: &GameRecord) {
+        match game.outcome {
+            GameOutcome::Abandoned(AbandonReason::Crashed) => return,
+            GameOutcome::Won { guesses } => {
+                self.wins += 1;
+                self.guesses += guesses;
+            }
+            _ => {}
+        }
+        self.games += 1;
+    }
+
+    /// Whether there are enough games for the averages to mean anything.
+    pub fn enough(&self) -> bool {
+        self.games >= MIN_BUCKET_GAMES
+    }
+
+    /// Percentage of games won, once there are enough games.
+    pub fn win_rate(&self) -> Option<f64> {
+        self.enough()
+            .then(|| self.wins as f64 / self.games as f64 * 100.0)
+    }
+
+    /// Mean guesses per won game, once there are enough games and a win.
+    pub fn average_guesses(&self) -> Option<f64> {
+        (self.enough() && self.wins > 0).then(|| self.guesses as f64 / self.wins as f64)
+    }
+}
+
+/// Results by part of the day, in [`DayPart::ALL`] order, and by weekday
+/// from Monday.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimingStats {
+    pub day_parts: [BucketStats; 4],
+    pub weekdays: [BucketStats; 7],
+}
+
+impl TimingStats {
+    pub fn from_games<Tz: TimeZone>(games: &[GameRecord], tz: &Tz) -> Self {
+        let mut stats = Self::default();
+        for game in games {
+            let (part, weekday) = local_buckets(game.timestamp, tz);
+            let part = DayPart::ALL.iter().position(|&p| p == part).unwrap_or(0);
+            stats.day_parts[part].add(game);
+            stats.weekdays[weekday.num_days_from_monday() as usize].add(game);
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Difficulty;
+    use chrono::FixedOffset;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn hours(offset: i32) -> FixedOffset {
+        FixedOffset::east_opt(offset * 3600).unwrap()
+    }
+
+    fn game(timestamp: DateTime<Utc>, outcome: GameOutcome) -> GameRecord {
+        GameRecord {
+            timestamp,
+            target_word: "stone".to_string(),
+            guesses: vec![],
+            outcome,
+            blitz: false,
+            duration_secs: None,
+            app_version: None,
+            difficulty: Difficulty::Normal,
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        }
+    }
+
+    #[test]
+    fn test_day_part_boundaries() {
+        assert_eq!(DayPart::from_hour(0), DayPart::Night);
+        assert_eq!(DayPart::from_hour(4), DayPart::Night);
+        assert_eq!(DayPart::from_hour(5), DayPart::Morning);
+        assert_eq!(DayPart::from_hour(11), DayPart::Morning);
+        assert_eq!(DayPart::from_hour(12), DayPart::Afternoon);
+        assert_eq!(DayPart::from_hour(17), DayPart::Evening);
+        assert_eq!(DayPart::from_hour(21), DayPart::Evening);
+        assert_eq!(DayPart::from_hour(22), DayPart::Night);
+        assert_eq!(DayPart::from_hour(23), DayPart::Night);
+    }
+
+    #[test]
+    fn test_midnight_keeps_the_local_weekday() {
+        let utc = hours(0);
+        assert_eq!(
+            local_buckets(at("2024-06-01T23:59:00Z"), &utc),
+            (DayPart::Night, Weekday::Sat)
+        );
+        assert_eq!(
+            local_buckets(at("2024-06-02T00:00:00Z"), &utc),
+            (DayPart::Night, Weekday::Sun)
+        );
+        // Monday in UTC is still Sunday evening in New York
+        assert_eq!(
+            local_buckets(at("2024-06-03T01:30:00Z"), &hours(-4)),
+            (DayPart::Evening, Weekday::Sun)
+        );
+        // and already Monday morning in Tokyo
+        assert_eq!(
+            local_buckets(at("2024-06-02T21:30:00Z"), &hours(9)),
+            (DayPart::Morning, Weekday::Mon)
+        );
+    }
+
+    #[test]
+    fn test_daylight_saving_offset_moves_the_bucket() {
+        // 09:30 UTC on the US fall-back day: 05:30 in daylight time, 04:30 after
+        let instant = at("2024-11-03T09:30:00Z");
+        assert_eq!(
+            local_buckets(instant, &hours(-4)),
+            (DayPart::Morning, Weekday::Sun)
+        );
+        assert_eq!(
+            local_buckets(instant, &hours(-5)),
+            (DayPart::Night, Weekday::Sun)
+        );
+    }
+
+    #[test]
+    fn test_small_buckets_have_no_averages() {
+        let utc = hours(0);
+        let games = vec![
+            game(at("2024-06-03T08:00:00Z"), GameOutcome::Won { guesses: 3 }),
+            game(at("2024-06-03T09:00:00Z"), GameOutcome::Won { guesses: 5 }),
+            game(at("2024-06-03T10:00:00Z"), GameOutcome::Lost),
+            game(
+                at("2024-06-03T11:00:00Z"),
+                GameOutcome::Abandoned(AbandonReason::Crashed),
+            ),
+            game(at("2024-06-03T23:00:00Z"), GameOutcome::Won { guesses: 2 }),
+        ];
+
+        let stats = TimingStats::from_games(&games, &utc);
+        let morning = stats.day_parts[0];
+        assert_eq!((morning.games, morning.wins), (3, 2));
+        assert_eq!(morning.average_guesses(), Some(4.0));
+        assert!((morning.win_rate().unwrap() - 66.67).abs() < 0.01);
+
+        let night = stats.day_parts[3];
+        assert_eq!(night.games, 1);
+        assert_eq!(night.win_rate(), None);
+        assert_eq!(night.average_guesses(), None);
+
+        assert_eq!(stats.weekdays[0].games, 4);
+    }
+}
@@ -1,16 +1,150 @@
 //! Data structures for game history tracking.
 
+use std::{cmp::Ordering, ops::Range};
+
 use chrono::{DateTime, Utc};
 
-use crate::solver::Feedback;
+use crate::{
+    local_time::DisplayZone,
+    settings::Difficulty,
+    solver::Feedback,
+    storage::{DifficultyStats, MultiBoardStats, OpenerStats, VersionAverage},
+    tiers::WordDifficulty,
+};
 
-use super::solver_types::{SolverSession, SolverStats};
+use super::{
+    GameCursor,
+    solver_types::{SolverSession, SolverStats},
+    timing::TimingStats,
+};
 
 /// Outcome of a completed game.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum GameOutcome {
-    Won { guesses: usize },
+    Won {
+        guesses: usize,
+    },
     Lost,
+    /// The player gave up and had the answer revealed.
+    Forfeited,
+    /// The game was left unfinished.
+    Abandoned(AbandonReason),
+}
+
+/// Why a game or solver session was left unfinished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AbandonReason {
+    /// Left for another mode.
+    Switched,
+    /// Open when the app was quit.
+    Quit,
+    /// Still open when the app stopped without quitting; closed at the next start.
+    Crashed,
+}
+
+impl AbandonReason {
+    /// The value stored in the `reason` column.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AbandonReason::Switched => "switched",
+            AbandonReason::Quit => "quit",
+            AbandonReason::Crashed => "crashed",
+        }
+    }
+
+    /// Parse a stored value, treating anything unknown as a mode switch.
+    pub fn from_stored(value: Option<&str>) -> Self {
+        match value {
+            Some("quit") => AbandonReason::Quit,
+            Some("crashed") => AbandonReason::Crashed,
+            _ => AbandonReason::Switched,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AbandonReason::Switched => "switched mode",
+            AbandonReason::Quit => "quit",
+            AbandonReason::Crashed => "crashed",
+        }
+    }
+}
+
+/// Help taken during a game, each costing a point of the hint budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HintKind {
+    /// The suggestions panel shown.
+    Suggestions,
+    /// The analysis panels shown.
+    Analysis,
+    /// The best guess revealed.
+    BestGuess,
+}
+
+impl HintKind {
+    /// The value stored in the `hints` column.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HintKind::Suggestions => "suggestions",
+            HintKind::Analysis => "analysis",
+            HintKind::BestGuess => "best_guess",
+        }
+    }
+
+    pub fn from_stored(value: &str) -> Option<Self> {
+        match value {
+            "suggestions" => Some(HintKind::Suggestions),
+            "analysis" => Some(HintKind::Analysis),
+            "best_guess" => Some(HintKind::BestGuess),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HintKind::Suggestions => "suggestions",
+            HintKind::Analysis => "analysis",
+            HintKind::BestGuess => "best guess",
+        }
+    }
+}
+
+/// Unfinished games or sessions, by why they were left.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AbandonCounts {
+    pub switched: usize,
+    pub quit: usize,
+    pub crashed: usize,
+}
+
+impl AbandonCounts {
+    pub fn add(&mut self, reason: AbandonReason) {
+        match reason {
+            AbandonReason::Switched => self.switched += 1,
+            AbandonReason::Quit => self.quit += 1,
+            AbandonReason::Crashed => self.crashed += 1,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.switched + self.quit + self.crashed
+    }
+
+    /// "3 (switched 1 · quit 1 · crashed 1)", or "0".
+    pub fn breakdown(&self) -> String {
+        if self.total() == 0 {
+            return "0".to_string();
+        }
+        format!(
+            "{} (switched {} · quit {} · crashed {})",
+            self.total(),
+            self.switched,
+            self.quit,
+            self.crashed
+        )
+    }
 }
 
 /// A single guess within a game.
@@ -18,8 +152,45 @@ pub enum GameOutcome {
 pub struct GameGuess {
     pub word: String,
     pub feedback: Vec<Feedback>,
+    /// Blitz turn forfeited by the countdown; `word` and `feedback` are empty.
+    pub timed_out: bool,
+    /// Candidates eliminated beyond what the guess was expected to eliminate.
+    pub luck: Option<f64>,
+    /// 1-based position of the answer in the suggestions shown before this
+    /// guess; `None` when it was not among them.
+    pub target_rank: Option<usize>,
+    /// Note the player left on the guess while playing.
+    pub note: Option<String>,
+}
+
+impl GameGuess {
+    pub fn new(word: String, feedback: Vec<Feedback>) -> Self {
+        Self {
+            word,
+            feedback,
+            timed_out: false,
+            luck: None,
+            target_rank: None,
+            note: None,
+        }
+    }
+
+    /// Placeholder for a blitz turn lost to the countdown.
+    pub fn timed_out() -> Self {
+        Self {
+            word: String::new(),
+            feedback: Vec::new(),
+            timed_out: true,
+            luck: None,
+            target_rank: None,
+            note: None,
+        }
+    }
 }
 
+/// Target stored for a shadow game that ended without the answer being seen.
+pub const UNKNOWN_TARGET: &str = "?";
+
 /// A complete game record parsed from logs.
 #[derive(Debug, Clone)]
 pub struct GameRecord {
@@ -27,6 +198,24 @@ pub struct GameRecord {
     pub target_word: String,
     pub guesses: Vec<GameGuess>,
     pub outcome: GameOutcome,
+    pub blitz: bool,
+    /// Seconds from game start to game over; absent for games recorded before timing.
+    pub duration_secs: Option<f64>,
+    /// Release that recorded the game; absent for games recorded before versions were kept.
+    pub app_version: Option<String>,
+    /// Normal for games recorded before difficulties were kept.
+    pub difficulty: Difficulty,
+    /// Best guesses revealed during the game.
+    pub hints_used: usize,
+    /// Hint points the game allowed; absent when it had no budget.
+    pub hint_budget: Option<usize>,
+    /// Hints taken, in order; empty for games played before they were recorded.
+    pub hints: Vec<HintKind>,
+    /// When the game was archived; archived games are hidden unless asked for.
+    pub archived_at: Option<DateTime<Utc>>,
+    /// How hard the answer was; absent for phrase games and games played
+    /// before answers were scored.
+    pub word_difficulty: Option<WordDifficulty>,
 }
 
 impl GameRecord {
@@ -35,23 +224,106 @@ impl GameRecord {
         self.guesses.len()
     }
 
+    /// Returns true if a best guess was revealed during the game.
+    pub fn assisted(&self) -> bool {
+        self.hints_used > 0
+    }
+
+    /// Returns true if any hint was taken: a panel shown or a best guess revealed.
+    pub fn hinted(&self) -> bool {
+        !self.hints.is_empty() || self.assisted()
+    }
+
+    /// The target word as listed, starred if the game was assisted.
+    pub fn listed_word(&self) -> String {
+        if self.assisted() {
+            format!("{}*", self.target_word)
+        } else {
+            self.target_word.clone()
+        }
+    }
+
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+
     /// Returns true if the game was lost.
     pub fn was_lost(&self) -> bool {
         matches!(self.outcome, GameOutcome::Lost)
     }
 }
 
+/// One board of a multi-board game: its answer and the guess that solved it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BoardResult {
+    pub target: String,
+    /// The 1-based guess that solved the board; absent if it never was.
+    pub solved_in: Option<usize>,
+}
+
+/// A multi-board practice game: one stream of guesses played on every board.
+/// Kept apart from single-board games and their stats.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiBoardRecord {
+    pub timestamp: DateTime<Utc>,
+    pub guesses: Vec<String>,
+    pub boards: Vec<BoardResult>,
+    /// Won once every board is solved, with the guesses that took.
+    pub outcome: GameOutcome,
+    pub difficulty: Difficulty,
+    pub duration_secs: Option<f64>,
+    pub app_version: Option<String>,
+}
+
+impl MultiBoardRecord {
+    pub fn solved(&self) -> usize {
+        self.boards.iter().filter(|b| b.solved_in.is_some()).count()
+    }
+}
+
+/// A kids' game: everyday answer, no guess limit, and kept apart from the
+/// other games and their stats.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KidsGameRecord {
+    pub timestamp: DateTime<Utc>,
+    pub target_word: String,
+    pub guesses: Vec<String>,
+    /// Won with the guesses that took, or left unsolved; never lost.
+    pub outcome: GameOutcome,
+    pub duration_secs: Option<f64>,
+    pub app_version: Option<String>,
+}
+
 /// Aggregated statistics across all games.
 #[derive(Debug, Clone, Default)]
 pub struct HistoryStats {
     pub total_games: usize,
     pub wins: usize,
     pub losses: usize,
+    /// Games given up; counted apart from losses.
+    pub forfeits: usize,
+    /// Games left unfinished. Crashed games count toward neither the win
+    /// rate nor streaks; the others count as not won.
+    pub abandoned: AbandonCounts,
     pub win_rate: f64,
     pub average_guesses: f64,
     pub current_streak: i32,
     pub best_win_streak: usize,
     pub guess_distribution: [usize; 6],
+    pub blitz_games: usize,
+    pub blitz_wins: usize,
+    /// Games in which a best guess was revealed.
+    pub assisted_games: usize,
+    /// Wins without any hint taken.
+    pub clean_wins: usize,
+    /// Wins with a panel shown or a best guess revealed.
+    pub hinted_wins: usize,
+    /// Archived games counted in; none unless archived games are included.
+    pub archived_games: usize,
+    /// Mean solve time in seconds over timed wins.
+    pub average_solve_secs: Option<f64>,
+    /// Mean luck per guess over guesses that recorded it.
+    pub average_luck: Option<f64>,
 }
 
 impl HistoryStats {
@@ -70,12 +342,37 @@ impl HistoryStats {
         let mut current_streak = 0;
         let mut best_win_streak = 0;
         let mut current_win_streak = 0;
+        let mut solve_secs = Vec::new();
+        let mut lucks = Vec::new();
 
         for game in games {
+            lucks.extend(game.guesses.iter().filter_map(|g| g.luck));
+
+            if game.assisted() {
+                stats.assisted_games += 1;
+            }
+
+            if game.is_archived() {
+                stats.archived_games += 1;
+            }
+
+            if game.blitz {
+                stats.blitz_games += 1;
+                if matches!(game.outcome, GameOutcome::Won { .. }) {
+                    stats.blitz_wins += 1;
+                }
+            }
+
             match game.outcome {
                 GameOutcome::Won { guesses } => {
                     stats.wins += 1;
+                    if game.hinted() {
+                        stats.hinted_wins += 1;
+                    } else {
+                        stats.clean_wins += 1;
+                    }
                     total_guesses_for_wins += guesses;
+                    solve_secs.extend(game.duration_secs);
 
                     if (1..=6).contains(&guesses) {
                         stats.guess_distribution[guesses - 1] += 1;
@@ -89,23 +386,34 @@ impl HistoryStats {
                     current_win_streak += 1;
                     best_win_streak = best_win_streak.max(current_win_streak);
                 }
-                GameOutcome::Lost => {
-                    stats.losses += 1;
+                GameOutcome::Lost => stats.losses += 1,
+                GameOutcome::Forfeited => stats.forfeits += 1,
+                GameOutcome::Abandoned(reason) => stats.abandoned.add(reason),
+            }
 
-                    if current_streak <= 0 {
-                        current_streak -= 1;
-                    } else {
-                        current_streak = -1;
-                    }
-                    current_win_streak = 0;
+            // A crash says nothing about how the game was going
+            let broke_streak = !matches!(
+                game.outcome,
+                GameOutcome::Won { .. } | GameOutcome::Abandoned(AbandonReason::Crashed)
+            );
+            if broke_streak {
+                if current_streak <= 0 {
+                    current_streak -= 1;
+                } else {
+                    current_streak = -1;
                 }
+                current_win_streak = 0;
             }
         }
 
         stats.current_streak = current_streak;
         stats.best_win_streak = best_win_streak;
 
-        let completed_games = stats.wins + stats.losses;
+        let completed_games = stats.wins
+            + stats.losses
+            + stats.forfeits
+            + stats.abandoned.switched
+            + stats.abandoned.quit;
         if completed_games > 0 {
             stats.win_rate = (stats.wins as f64 / completed_games as f64) * 100.0;
         }
@@ -114,17 +422,90 @@ impl HistoryStats {
             stats.average_guesses = total_guesses_for_wins as f64 / stats.wins as f64;
         }
 
+        if !lucks.is_empty() {
+            stats.average_luck = Some(lucks.iter().sum::<f64>() / lucks.len() as f64);
+        }
+
+        if !solve_secs.is_empty() {
+            stats.average_solve_secs =
+                Some(solve_secs.iter().sum::<f64>() / solve_secs.len() as f64);
+        }
+
         stats
     }
+
+    /// Compute statistics over games played within `range` (end exclusive).
+    pub fn from_games_in_range(games: &[GameRecord], range: Range<DateTime<Utc>>) -> Self {
+        let in_range: Vec<GameRecord> = games
+            .iter()
+            .filter(|g| range.contains(&g.timestamp))
+            .cloned()
+            .collect();
+        Self::from_games(&in_range)
+    }
+}
+
+/// Statistics for games before and after a split date.
+#[derive(Debug, Clone)]
+pub struct StatsComparison {
+    pub split: DateTime<Utc>,
+    pub before: HistoryStats,
+    pub after: HistoryStats,
+}
+
+impl StatsComparison {
+    pub fn new(games: &[GameRecord], split: DateTime<Utc>) -> Self {
+        Self {
+            split,
+            before: HistoryStats::from_games_in_range(games, DateTime::<Utc>::MIN_UTC..split),
+            after: HistoryStats::from_games_in_range(games, split..DateTime::<Utc>::MAX_UTC),
+        }
+    }
+
+    /// Change in win rate (percentage points); None if either range is empty.
+    pub fn win_rate_delta(&self) -> Option<f64> {
+        (self.before.total_games > 0 && self.after.total_games > 0)
+            .then_some(self.after.win_rate - self.before.win_rate)
+    }
+
+    /// Change in average guesses per win; None if either range has no wins.
+    pub fn average_guesses_delta(&self) -> Option<f64> {
+        (self.before.wins > 0 && self.after.wins > 0)
+            .then_some(self.after.average_guesses - self.before.average_guesses)
+    }
+
+    /// Change in average solve time; None if either range has no timed wins.
+    pub fn average_solve_secs_delta(&self) -> Option<f64> {
+        Some(self.after.average_solve_secs? - self.before.average_solve_secs?)
+    }
+
+    /// Change in the number of wins at each guess count.
+    pub fn distribution_delta(&self) -> [i64; 6] {
+        std::array::from_fn(|i| {
+            self.after.guess_distribution[i] as i64 - self.before.guess_distribution[i] as i64
+        })
+    }
+}
+
+/// Which part of the Stats view is shown; PageDown and PageUp move between them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsSection {
+    #[default]
+    Overview,
+    /// Results by time of day and weekday.
+    Timing,
 }
 
 /// Display mode for history viewer.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum HistoryViewMode {
-    Stats,  // Statistics dashboard
-    List,   // Paginated game list
-    Detail, // Single game detail view
-    Solver, // Solver statistics view
+    Stats,   // Statistics dashboard
+    List,    // Paginated game list
+    Detail,  // Single game detail view
+    Solver,  // Solver statistics view
+    Compare, // Stats before vs. after a split date
 }
 
 /// Container for all history data.
@@ -134,56 +515,164 @@ pub struct HistoryData {
     pub stats: HistoryStats,
     pub solver_sessions: Vec<SolverSession>,
     pub solver_stats: SolverStats,
-    pub selected_game_index: Option<usize>,
+    /// Row id of each game in `games`; empty when they weren't loaded from the database.
+    pub game_ids: Vec<i64>,
+    /// Games the database holds for this view; more than `games` holds while
+    /// the rest are still loading, and `stats` is only filled in once they are.
+    pub stored_games: usize,
+    /// Display order of the games and the selected one, shared by list and detail views.
+    pub cursor: GameCursor,
+    /// Share of first guesses green at each letter position.
+    pub first_guess_green_rates: Vec<f64>,
+    /// Mean green letters on the first, second, ... guess.
+    pub greens_per_guess: Vec<f64>,
+    /// Mean guesses per won game for each release that recorded games.
+    pub game_version_averages: Vec<VersionAverage>,
+    /// Mean guesses per solver session for each release that recorded sessions.
+    pub session_version_averages: Vec<VersionAverage>,
+    /// Results at each difficulty games were played at, easiest first.
+    pub difficulty_stats: Vec<DifficultyStats>,
+    /// Results by first guess, most played first, rare openers pooled last.
+    pub opener_stats: Vec<OpenerStats>,
+    /// Multi-board results for each number of boards played, fewest first.
+    pub multi_board_stats: Vec<MultiBoardStats>,
+    /// Results by local time of day and weekday, filled in with `stats`.
+    pub timing: TimingStats,
+    /// Zone `timing` buckets games in; set before the games are appended.
+    pub zone: DisplayZone,
 }
 
 impl HistoryData {
+    /// Split date dividing the games into two equal halves.
+    pub fn default_compare_split(&self) -> DateTime<Utc> {
+        self.games
+            .get(self.games.len() / 2)
+            .map_or_else(Utc::now, |g| g.timestamp)
+    }
+
     pub fn new(games: Vec<GameRecord>, sessions: Vec<SolverSession>) -> Self {
         let stats = HistoryStats::from_games(&games);
+        let timing = TimingStats::from_games(&games, &DisplayZone::System);
+        let game_count = games.len();
         let solver_stats = SolverStats::from_sessions(&sessions);
         Self {
             games,
             stats,
             solver_sessions: sessions,
             solver_stats,
-            selected_game_index: None,
+            game_ids: Vec::new(),
+            stored_games: game_count,
+            cursor: GameCursor::new(game_count),
+            first_guess_green_rates: Vec::new(),
+            greens_per_guess: Vec::new(),
+            game_version_averages: Vec::new(),
+            session_version_averages: Vec::new(),
+            difficulty_stats: Vec::new(),
+            opener_stats: Vec::new(),
+            multi_board_stats: Vec::new(),
+            timing,
+            zone: DisplayZone::System,
+        }
+    }
+
+    /// True until every stored game has been loaded.
+    pub fn is_loading(&self) -> bool {
+        self.games.len() < self.stored_games
+    }
+
+    /// Add the next batch of loaded games, working out the stats once the
+    /// last one is in.
+    pub fn append_games(&mut self, batch: Vec<(i64, GameRecord)>) {
+        let start = self.games.len();
+        for (id, game) in batch {
+            self.game_ids.push(id);
+            self.games.push(game);
+        }
+        self.cursor.extend(start..self.games.len());
+        if !self.is_loading() {
+            self.stats = HistoryStats::from_games(&self.games);
+            self.timing = TimingStats::from_games(&self.games, &self.zone);
         }
     }
 
-    /// Get the total number of pages for pagination (10 games per page).
+    /// Restrict the games shown to those matching `keep`, ordered by `compare`.
+    ///
+    /// The selected game stays selected if it is still shown.
+    pub fn arrange(
+        &mut self,
+        keep: impl Fn(&GameRecord) -> bool,
+        compare: impl Fn(&GameRecord, &GameRecord) -> Ordering,
+    ) {
+        let mut order: Vec<usize> = (0..self.games.len())
+            .filter(|&i| keep(&self.games[i]))
+            .collect();
+        order.sort_by(|&a, &b| compare(&self.games[a], &self.games[b]));
+        self.cursor.reorder(order);
+    }
+
+    /// Number of games shown in the list.
+    pub fn visible_count(&self) -> usize {
+        self.cursor.len()
+    }
+
+    /// Get the total number of pages for pagination (10 games per page),
+    /// counting games still to be loaded.
     pub fn total_pages(&self) -> usize {
-        if self.games.is_empty() {
+        let count = if self.is_loading() {
+            self.stored_games
+        } else {
+            self.cursor.len()
+        };
+        if count == 0 {
             1
         } else {
-            self.games.len().div_ceil(10) // Ceiling division
+            count.div_ceil(10) // Ceiling division
         }
     }
 
-    /// Get games for a specific page (0-indexed).
-    pub fn games_for_page(&self, page: usize) -> &[GameRecord] {
-        let start = page * 10;
-        let end = (start + 10).min(self.games.len());
-        if start >= self.games.len() {
-            &[]
-        } else {
-            &self.games[start..end]
-        }
+    /// Get the shown games for a specific page (0-indexed).
+    pub fn games_for_page(&self, page: usize) -> Vec<&GameRecord> {
+        self.cursor
+            .order()
+            .iter()
+            .skip(page * 10)
+            .take(10)
+            .map(|&i| &self.games[i])
+            .collect()
+    }
+
+    /// Row id of the currently selected game, if it was loaded from the database.
+    pub fn selected_game_id(&self) -> Option<i64> {
+        self.cursor
+            .current()
+            .and_then(|idx| self.game_ids.get(idx).copied())
     }
 
     /// Get the currently selected game, if any.
     pub fn selected_game(&self) -> Option<&GameRecord> {
-        self.selected_game_index.and_then(|idx| self.games.get(idx))
+        self.cursor.current().and_then(|idx| self.games.get(idx))
+    }
+
+    /// Select a game by its position in the shown list.
+    pub fn select_game(&mut self, position: usize) {
+        self.cursor.select(position);
     }
 
-    /// Select a game by its index in the games list.
-    pub fn select_game(&mut self, index: usize) {
-        if index < self.games.len() {
-            self.selected_game_index = Some(index);
+    /// Select the game with row id `id`, if it is shown; returns whether it was.
+    pub fn select_game_id(&mut self, id: i64) -> bool {
+        let position = self
+            .cursor
+            .order()
+            .iter()
+            .position(|&idx| self.game_ids.get(idx) == Some(&id));
+        if let Some(position) = position {
+            self.cursor.select(position);
         }
+        position.is_some()
     }
 
     /// Clear the game selection.
     pub fn clear_selection(&mut self) {
-        self.selected_game_index = None;
+        self.cursor.clear();
     }
 }
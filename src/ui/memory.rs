@@ -0,0 +1,108 @@
+//! Rough memory use of the app's growing structures, for spotting one that
+//! outgrows its cap in a long session.
+
+use crate::analysis::SuggestionDelta;
+
+use super::{app::App, history::solver_types::SolverGuess, types::GameMode};
+
+/// One structure's entries and the approximate bytes they hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Footprint {
+    pub name: &'static str,
+    pub entries: usize,
+    pub bytes: usize,
+}
+
+/// The footprint of each growing structure, in a fixed order.
+pub fn footprints(app: &App) -> Vec<Footprint> {
+    let footprint = |name, entries, bytes| Footprint {
+        name,
+        entries,
+        bytes,
+    };
+    let (log_lines, log_bytes) = app.logs.footprint();
+    let (panels, panel_bytes) = app.panel_cache.footprint();
+    let input_lines: Vec<&String> = [GameMode::Solver, GameMode::Game]
+        .iter()
+        .flat_map(|mode| app.input_history.entries(mode))
+        .collect();
+    let session = &app.solver_session_guesses;
+
+    vec![
+        footprint("logs", log_lines, log_bytes),
+        footprint(
+            "input history",
+            input_lines.len(),
+            input_lines.iter().map(|line| line.capacity()).sum(),
+        ),
+        footprint(
+            "guesses",
+            app.solver.guesses().len(),
+            app.solver
+                .guesses()
+                .iter()
+                .map(|g| g.word.capacity() + g.feedback.capacity())
+                .sum(),
+        ),
+        footprint(
+            "entropy history",
+            app.entropy_history.len(),
+            app.entropy_history.capacity() * size_of::<f64>(),
+        ),
+        footprint(
+            "pool history",
+            app.pool_history.len(),
+            app.pool_history.capacity() * size_of::<usize>(),
+        ),
+        footprint(
+            "session guesses",
+            session.len(),
+            session.capacity() * size_of::<SolverGuess>()
+                + session
+                    .iter()
+                    .map(|sg| {
+                        sg.word.capacity()
+                            + sg.optimal_word.as_ref().map_or(0, String::capacity)
+                            + sg.note.as_ref().map_or(0, String::capacity)
+                    })
+                    .sum::<usize>(),
+        ),
+        footprint(
+            "game luck",
+            app.game_luck.len(),
+            app.game_luck.capacity() * size_of::<f64>(),
+        ),
+        footprint(
+            "suggestions",
+            app.suggestions.len(),
+            app.suggestions.capacity() * size_of::<(u32, usize)>(),
+        ),
+        footprint(
+            "suggestion deltas",
+            app.suggestion_deltas.len(),
+            app.suggestion_deltas.capacity() * size_of::<SuggestionDelta>(),
+        ),
+        footprint(
+            "scored pools",
+            app.scored_pools.len(),
+            app.scored_pools.footprint(),
+        ),
+        footprint("panel cache", panels, panel_bytes),
+    ]
+}
+
+/// One log line summing up [`footprints`], e.g.
+/// `Memory ≈ 41.2 KiB: logs 300 (36.0 KiB), input history 12 (0.1 KiB), …`.
+pub fn memory_report(app: &App) -> String {
+    let footprints = footprints(app);
+    let total: usize = footprints.iter().map(|f| f.bytes).sum();
+    let parts: Vec<String> = footprints
+        .iter()
+        .map(|f| format!("{} {} ({})", f.name, f.entries, kib(f.bytes)))
+        .collect();
+    format!("Memory ≈ {}: {}", kib(total), parts.join(", "))
+}
+
+fn kib(bytes: usize) -> String {
+    format!("{:.1} KiB", bytes as f64 / 1024.0)
+}
@@ -1,44 +1,235 @@
 mod app;
+mod builder;
+mod events;
 mod handlers;
+mod hints;
 pub mod history;
+mod memory;
+mod plain;
+mod publisher;
 mod rendering;
+mod system_status;
+#[cfg(test)]
+mod test_support;
 #[cfg(test)]
 mod tests;
 mod types;
 
 pub use app::App;
+pub use builder::AppBuilder;
+pub use events::{EventSource, TerminalEvents};
+pub use publisher::STATE_FILE;
 pub use types::{GameMode, InputStatus, LogBuffer, ParsedInput};
 
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
 use crossterm::{
+    event::{
+        DisableBracketedPaste, EnableBracketedPaste, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+        supports_keyboard_enhancement,
+    },
 };
 use ratatui::{Terminal, backend::CrosstermBackend};
-use std::io::stdout;
+use std::io::{stdin, stdout};
+
+use crate::cli_style::CliStyle;
+use crate::wordlist::{
+    WordlistOptions, WordlistSource, diff_wordlists, load_comparison, load_solutions, load_words,
+};
+
+/// An app on the real word lists, with `transcript` guesses entered up front.
+fn build_app(
+    db: crate::storage::Database,
+    transcript: Option<String>,
+    wordlists: &WordlistOptions,
+) -> Result<App> {
+    let words = load_words(wordlists)?;
+    let solutions = load_solutions(wordlists)?;
+    let bundled = [("word", &words), ("solution", &solutions)]
+        .into_iter()
+        .filter(|(_, list)| list.source == WordlistSource::Bundled)
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
 
-use crate::wordlist::{load_solutions, load_words};
+    let cleaned = [("word", &words), ("solution", &solutions)]
+        .into_iter()
+        .filter(|(_, list)| !list.cleanup.is_empty())
+        .map(|(name, list)| (name, list.cleanup))
+        .collect::<Vec<_>>();
+    let comparison = load_comparison(wordlists)
+        .context("failed to read the comparison word list")?
+        .map(|upstream| diff_wordlists(&words.words, &upstream));
 
-/// Entry point for running the UI.
-pub fn run_ui(db: crate::storage::Database) -> Result<()> {
-    let words = load_words()?;
-    let solution_words = load_solutions()?;
-    let logs = LogBuffer::new();
+    let mut builder = AppBuilder::new()
+        .words(words.words)
+        .solutions(solutions.words)
+        .normalizer(wordlists.normalizer)
+        .wordlist_paths(words.path, solutions.path)
+        .db(db);
+    if let Some(transcript) = transcript {
+        builder = builder.transcript(transcript);
+    }
+    if let Some(diff) = &comparison {
+        builder = builder.flagged_words(diff.removed.iter().cloned());
+    }
+    let app = builder.build()?;
 
-    let mut app = App::new(words, solution_words, 5, logs.clone(), db);
+    if !bundled.is_empty() {
+        app.log(format!(
+            "Using the bundled {} list(s); they may be out of date",
+            bundled.join(" and ")
+        ));
+    }
+    for (name, cleanup) in cleaned {
+        app.log(format!(
+            "Cleaned the {} list: {} entries normalized, {} dropped",
+            name, cleanup.normalized, cleanup.dropped
+        ));
+    }
+    if let (Some(diff), Some(path)) = (comparison, &wordlists.compare_path) {
+        app.log(format!(
+            "Compared with {}: {} local word(s) missing from it, {} missing locally",
+            path.display(),
+            diff.removed.len(),
+            diff.added.len()
+        ));
+    }
+    Ok(app)
+}
+
+/// Entry point for running the UI, starting a gauntlet of `gauntlet` games if
+/// given, or kids' games for the whole run if `kids`.
+pub fn run_ui(
+    db: crate::storage::Database,
+    transcript: Option<String>,
+    wordlists: &WordlistOptions,
+    gauntlet: Option<usize>,
+    kids: bool,
+) -> Result<()> {
+    let mut app = build_app(db, transcript, wordlists)?;
+    handlers::TierHandler::new(&mut app).load_scores();
+    match gauntlet {
+        Some(size) => handlers::GameHandler::new(&mut app).start_gauntlet(size),
+        None if kids => {
+            app.kids_forced = true;
+            handlers::GameHandler::new(&mut app).start_new_game();
+        }
+        None => {
+            handlers::UiStateHandler::new(&mut app).load(chrono::Utc::now());
+            handlers::SolverHandler::new(&mut app).offer_resume();
+            // Offered once the session prompt is answered, if there is one
+            handlers::UiStateHandler::new(&mut app).offer_restore();
+        }
+    }
+    let terminal_check = crate::doctor::check_terminal(
+        crossterm::terminal::size(),
+        crossterm::style::available_color_count(),
+    );
+    app.warn_soft_failures(&[terminal_check]);
+    app.low_refresh_forced =
+        std::env::var_os("WW_LOW_REFRESH").is_some_and(|v| !v.is_empty() && v != "0");
 
     let mut stdout = stdout();
     enable_raw_mode()?;
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
+    // Without it most Unix terminals send Ctrl+Enter as a plain Enter
+    let enhanced_keys = supports_keyboard_enhancement().unwrap_or(false);
+    if enhanced_keys {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+        )?;
+    }
 
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let result = app.run(&mut terminal);
+    let result = app.run(&mut terminal, &mut TerminalEvents);
 
+    if enhanced_keys {
+        execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
+    }
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableBracketedPaste,
+        LeaveAlternateScreen
+    )?;
     terminal.show_cursor()?;
 
     result
 }
+
+/// The candidate pool after `transcript`, as [`SolverHandler::pool_export`] writes it.
+///
+/// Every bad transcript line is reported on stderr with its line number, and
+/// any is an error unless `skip_invalid`, which leaves them out instead.
+///
+/// No session is recorded, so a transcript that solves the puzzle leaves its
+/// last candidate in the pool rather than starting over.
+///
+/// [`SolverHandler::pool_export`]: handlers::SolverHandler::pool_export
+pub fn export_pool(
+    transcript: Option<String>,
+    wordlists: &WordlistOptions,
+    scores: bool,
+    skip_invalid: bool,
+    style: CliStyle,
+) -> Result<String> {
+    let db = crate::storage::Database::open_memory()?;
+    let mut app = build_app(db, None, wordlists)?;
+    app.solver_session_active = false;
+
+    let mut handler = handlers::SolverHandler::new(&mut app);
+    if let Some(transcript) = transcript {
+        let (valid, errors) = handler.valid_transcript_lines(&transcript);
+        for e in &errors {
+            eprintln!("{}", e);
+        }
+        if !errors.is_empty() && !skip_invalid {
+            bail!("{} invalid transcript line(s)", errors.len());
+        }
+        handler
+            .import_transcript(&valid)
+            .context("invalid transcript")?;
+    }
+    Ok(handler.pool_export(scores, style))
+}
+
+/// Replay every stored solver session against the current word lists and
+/// scorer, as history mode's `o` does, and describe what changed.
+pub fn recompute_optimal(
+    db: crate::storage::Database,
+    wordlists: &WordlistOptions,
+    dry_run: bool,
+) -> Result<String> {
+    let mut app = build_app(db, None, wordlists)?;
+    app.solver_session_active = false;
+
+    handlers::MaintenanceHandler::new(&mut app).start_recompute(dry_run);
+    loop {
+        if let Some(summary) = handlers::MaintenanceHandler::new(&mut app).step_recompute() {
+            let summary = summary.context("failed to save recomputed sessions")?;
+            let verb = if dry_run { "would change" } else { "changed" };
+            return Ok(format!("Recompute {}: {}", verb, summary));
+        }
+        if app.recompute.is_none() {
+            anyhow::bail!("could not load solver sessions");
+        }
+    }
+}
+
+/// Entry point for the line-oriented plain text frontend.
+pub fn run_plain(
+    db: crate::storage::Database,
+    transcript: Option<String>,
+    wordlists: &WordlistOptions,
+) -> Result<()> {
+    let mut app = build_app(db, transcript, wordlists)?;
+
+    app.run_plain(stdin().lock(), stdout())
+}
@@ -0,0 +1,137 @@
+//! Line-oriented frontend for screen readers and braille displays.
+
+use std::io::{BufRead, Write};
+
+use anyhow::Result;
+
+use crate::{
+    build_info::BuildInfo,
+    solver::{Feedback, Guess},
+};
+
+use super::{
+    app::App,
+    handlers::{InputHandler, SolverHandler},
+    types::{InputStatus, ParsedInput},
+};
+
+const PLAIN_SUGGESTION_COUNT: usize = 3;
+
+const PLAIN_HELP: &str = "Enter a guess as: <word> <pattern>, for example: crane GYXXX
+Pattern letters: G = green, Y = yellow, X = gray
+Commands: help, undo, quit";
+
+impl App {
+    /// Run the solver as a sequential text interface over `input` and `output`.
+    pub fn run_plain<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> Result<()> {
+        self.log("Plain UI started");
+        self.log("Solver session started");
+
+        writeln!(output, "Wordle Warlord solver, plain text mode.")?;
+        writeln!(output, "{}", PLAIN_HELP)?;
+
+        for line in input.lines() {
+            let line = line?;
+            let command = line.trim();
+
+            match command.to_lowercase().as_str() {
+                "" => continue,
+                "quit" | "exit" => {
+                    self.log("Exit requested");
+                    writeln!(output, "Goodbye.")?;
+                    return Ok(());
+                }
+                "help" => {
+                    writeln!(output, "{}", PLAIN_HELP)?;
+                    writeln!(output, "{}", BuildInfo::current().summary())?;
+                }
+                "undo" => self.plain_undo(&mut output)?,
+                _ => self.plain_submit(command, &mut output)?,
+            }
+
+            output.flush()?;
+        }
+
+        Ok(())
+    }
+
+    fn plain_undo<W: Write>(&mut self, output: &mut W) -> Result<()> {
+        let Some(last) = self.solver.guesses().last().map(|g| g.word.clone()) else {
+            writeln!(output, "Nothing to undo.")?;
+            return Ok(());
+        };
+
+        self.log("Undo requested");
+        SolverHandler::new(self).undo_guess();
+        writeln!(output, "Removed guess {}.", last.to_uppercase())?;
+        self.plain_summary(output)
+    }
+
+    fn plain_submit<W: Write>(&mut self, command: &str, output: &mut W) -> Result<()> {
+        self.input = command.to_string();
+
+        let handler = InputHandler::new(self);
+        let status = handler.input_status();
+        let parsed = handler.parse_input();
+
+        match (status, parsed) {
+            (InputStatus::Valid, ParsedInput::Valid { word, feedback }) => {
+                let guess = Guess::new(word, feedback);
+                writeln!(output, "{}", describe_guess(&guess))?;
+
+                let completed = SolverHandler::new(self).submit_guess(guess.word, guess.feedback);
+                if completed {
+                    // No popups here, so the session stays unverified
+                    self.overlay = None;
+                    writeln!(output, "Solved! Starting a new session.")?;
+                } else {
+                    self.plain_summary(output)?;
+                }
+            }
+            (InputStatus::Invalid(msg), _) => {
+                self.log(format!("Input rejected: {:?}", command));
+                writeln!(output, "Invalid input: {}.", msg)?;
+            }
+            _ => writeln!(output, "Incomplete input. Type help for the syntax.")?,
+        }
+
+        self.input.clear();
+        Ok(())
+    }
+
+    fn plain_summary<W: Write>(&self, output: &mut W) -> Result<()> {
+        let remaining = self.solver.filter(&self.solution_words).len();
+        writeln!(output, "Remaining candidates: {}.", remaining)?;
+
+        if self.suggestions.is_empty() {
+            writeln!(output, "No suggestions.")?;
+        } else {
+            let top: Vec<String> = self
+                .suggested_words()
+                .take(PLAIN_SUGGESTION_COUNT)
+                .map(|(w, _)| w.to_uppercase())
+                .collect();
+            writeln!(output, "Suggestions: {}.", top.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Spell out a guess letter by letter, e.g. "R green, A yellow, I gray".
+pub(in crate::ui) fn describe_guess(guess: &Guess) -> String {
+    guess
+        .word
+        .chars()
+        .zip(&guess.feedback)
+        .map(|(c, fb)| {
+            let color = match fb {
+                Feedback::Green => "green",
+                Feedback::Yellow => "yellow",
+                Feedback::Gray => "gray",
+            };
+            format!("{} {}", c.to_ascii_uppercase(), color)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
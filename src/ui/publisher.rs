@@ -0,0 +1,180 @@
+//! A small snapshot of what the app shows, kept in `current-state.json` for
+//! external scripts and widgets to poll.
+//!
+//! The file is replaced whole each time, never written in place, so a reader
+//! sees the previous snapshot or the next one and nothing in between. While a
+//! game is being played it holds only the colors of the guesses: no words, no
+//! answer and no suggestions.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::scoring::pattern_string;
+
+use super::{app::App, types::GameMode};
+
+/// The snapshot file, beside the database.
+pub const STATE_FILE: &str = "current-state.json";
+
+/// How long the snapshot must go unchanged before it is written, so a burst
+/// of guesses or keys is one write.
+pub const STATE_PUBLISH_DELAY: Duration = Duration::from_millis(250);
+
+/// One guess on the board.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoardRow {
+    /// Left out while a game is being played.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub word: Option<String>,
+    /// `G`, `Y` or `X` per letter.
+    pub pattern: String,
+}
+
+/// What external tools see.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpectatorSnapshot {
+    /// False once the app has quit.
+    pub active: bool,
+    /// `solver`, `game` or `history`.
+    pub mode: String,
+    pub board: Vec<BoardRow>,
+    pub game_over: bool,
+    /// The game's answer, once it is over.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    /// Answers the guesses still allow; absent in history.
+    pub pool_size: Option<usize>,
+    /// Bits of uncertainty left, `log2` of the pool size.
+    pub entropy_bits: Option<f64>,
+}
+
+impl SpectatorSnapshot {
+    /// The snapshot of `app` as it stands, with `pool_size` from the last recompute.
+    pub fn capture(app: &App, pool_size: Option<usize>) -> Self {
+        let playing = app.mode == GameMode::Game && !app.game_over;
+        let mode = match app.mode {
+            GameMode::Solver => "solver",
+            GameMode::Game => "game",
+            GameMode::History => "history",
+        };
+        let (board, pool_size) = if app.mode == GameMode::History {
+            (Vec::new(), None)
+        } else {
+            let board = app
+                .solver
+                .guesses()
+                .iter()
+                .map(|g| BoardRow {
+                    word: (!playing).then(|| g.word.clone()),
+                    pattern: pattern_string(&g.feedback),
+                })
+                .collect();
+            (board, pool_size)
+        };
+        let game_over = app.mode == GameMode::Game && app.game_over;
+        Self {
+            active: true,
+            mode: mode.to_string(),
+            board,
+            game_over,
+            answer: game_over.then(|| app.target_word.clone()).flatten(),
+            pool_size,
+            entropy_bits: pool_size.map(|n| (n.max(1) as f64).log2()),
+        }
+    }
+}
+
+/// A [`SpectatorSnapshot`] as written to [`STATE_FILE`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpectatorEntry {
+    /// RFC 3339.
+    pub updated_at: String,
+    #[serde(flatten)]
+    pub snapshot: SpectatorSnapshot,
+}
+
+/// Write `entry` to `path` aside and rename it over the old file, so readers
+/// never see half of it.
+pub fn write_atomically(path: &Path, entry: &SpectatorEntry) -> io::Result<()> {
+    let temp = path.with_extension("json.tmp");
+    let json = serde_json::to_string_pretty(entry).map_err(io::Error::other)?;
+    std::fs::write(&temp, json)?;
+    std::fs::rename(&temp, path)
+}
+
+/// Bookkeeping for publishing the snapshot a little after it changes.
+#[derive(Debug, Default)]
+pub struct StatePublisher {
+    /// Where to write; none for in-memory databases.
+    path: Option<PathBuf>,
+    /// Pool size from the last recompute.
+    pool_size: Option<usize>,
+    /// The snapshot last written.
+    written: Option<SpectatorSnapshot>,
+    /// When the snapshot first differed from `written`.
+    changed_at: Option<Instant>,
+}
+
+impl StatePublisher {
+    /// A publisher writing [`STATE_FILE`] in `data_dir`, if there is one.
+    pub fn new(data_dir: Option<&Path>) -> Self {
+        Self {
+            path: data_dir.map(|dir| dir.join(STATE_FILE)),
+            ..Default::default()
+        }
+    }
+
+    /// Note the pool a recompute left.
+    pub fn record_pool(&mut self, size: usize) {
+        self.pool_size = Some(size);
+    }
+
+    /// Write the snapshot once it has gone [`STATE_PUBLISH_DELAY`] without
+    /// being written since it changed.
+    pub fn note_changes(app: &mut App, now: Instant) {
+        if !app.settings.spectator_file || app.state_publisher.path.is_none() {
+            return;
+        }
+        let snapshot = SpectatorSnapshot::capture(app, app.state_publisher.pool_size);
+        let publisher = &mut app.state_publisher;
+        if publisher.written.as_ref() == Some(&snapshot) {
+            publisher.changed_at = None;
+            return;
+        }
+        let changed_at = *publisher.changed_at.get_or_insert(now);
+        if now.duration_since(changed_at) >= STATE_PUBLISH_DELAY {
+            publisher.write(snapshot);
+        }
+    }
+
+    /// Mark the snapshot inactive, as the app quits.
+    pub fn finish(app: &mut App) {
+        if !app.settings.spectator_file || app.state_publisher.path.is_none() {
+            return;
+        }
+        let mut snapshot = SpectatorSnapshot::capture(app, app.state_publisher.pool_size);
+        snapshot.active = false;
+        app.state_publisher.write(snapshot);
+    }
+
+    fn write(&mut self, snapshot: SpectatorSnapshot) {
+        self.changed_at = None;
+        let Some(path) = &self.path else {
+            return;
+        };
+        let entry = SpectatorEntry {
+            updated_at: Utc::now().to_rfc3339(),
+            snapshot,
+        };
+        if let Err(e) = write_atomically(path, &entry) {
+            tracing::warn!("Failed to write {}: {}", path.display(), e);
+        }
+        self.written = Some(entry.snapshot);
+    }
+}
@@ -1,71 +1,120 @@
 use ratatui::{
     Frame,
+    layout::Rect,
     style::{Color, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::ui::app::App;
+use crate::{analysis::YellowPlacement, settings::PanelKind, ui::app::App};
 
 impl App {
-    pub(in crate::ui) fn draw_constraint_summary(
-        &self,
-        f: &mut Frame,
-        area: ratatui::layout::Rect,
-    ) {
-        if let Some(summary) = &self.constraint_summary {
-            let mut lines = vec![Line::from("Active Constraints")];
+    pub(in crate::ui) fn draw_constraint_summary(&self, f: &mut Frame, area: Rect) {
+        if self.constraint_summary.is_none() {
+            return;
+        }
+        let lines = self.panel_cache.lines(
+            PanelKind::Constraints,
+            self.panel_key(PanelKind::Constraints, area),
+            || self.constraint_summary_lines(),
+        );
+
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(Block::default().borders(Borders::ALL).title("Constraints")),
+            area,
+        );
+    }
 
-            // Greens with guess source
-            for (letter, pos, guess) in &summary.greens {
-                lines.push(Line::from(vec![
-                    Span::styled("✓ ", Style::default().fg(Color::Green)),
-                    Span::raw(format!(
-                        "{}({}) from '{}'",
-                        letter,
-                        pos + 1,
-                        guess.to_uppercase()
-                    )),
-                ]));
-            }
+    fn constraint_summary_lines(&self) -> Vec<Line<'static>> {
+        let Some(summary) = &self.constraint_summary else {
+            return Vec::new();
+        };
+        let mut lines = vec![Line::from("Active Constraints")];
 
-            // Yellows with guess source
-            for (letter, positions, guess) in &summary.yellows {
-                let pos_str: String = positions
-                    .iter()
-                    .map(|p| (p + 1).to_string())
-                    .collect::<Vec<_>>()
-                    .join(",");
-                lines.push(Line::from(vec![
-                    Span::styled("✓ ", Style::default().fg(Color::Yellow)),
-                    Span::raw(format!(
-                        "{}({}) from '{}'",
-                        letter,
-                        pos_str,
-                        guess.to_uppercase()
-                    )),
-                ]));
-            }
+        // Greens with guess source
+        for (letter, pos, guess) in &summary.greens {
+            lines.push(Line::from(vec![
+                Span::styled("✓ ", Style::default().fg(Color::Green)),
+                Span::raw(format!(
+                    "{}({}) from '{}'",
+                    letter,
+                    pos + 1,
+                    guess.to_uppercase()
+                )),
+            ]));
+        }
 
-            // Grays
-            if !summary.grays.is_empty() {
-                let gray_str: String = summary
-                    .grays
-                    .iter()
-                    .map(|c| c.to_string())
-                    .collect::<Vec<String>>()
-                    .join(" ");
-                lines.push(Line::from(vec![
-                    Span::styled("✗ ", Style::default().fg(Color::DarkGray)),
-                    Span::raw(gray_str),
-                ]));
-            }
+        // Yellows with guess source, then where each can still go
+        for ((letter, positions, guess), placement) in
+            summary.yellows.iter().zip(&self.yellow_placements)
+        {
+            let pos_str: String = positions
+                .iter()
+                .map(|p| (p + 1).to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            lines.push(Line::from(vec![
+                Span::styled("✓ ", Style::default().fg(Color::Yellow)),
+                Span::raw(format!(
+                    "{}({}) from '{}'",
+                    letter,
+                    pos_str,
+                    guess.to_uppercase()
+                )),
+            ]));
+            lines.push(placement_line(placement));
+        }
 
-            f.render_widget(
-                Paragraph::new(lines)
-                    .block(Block::default().borders(Borders::ALL).title("Constraints")),
-                area,
-            );
+        // Grays
+        if !summary.grays.is_empty() {
+            let gray_str: String = summary
+                .grays
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<String>>()
+                .join(" ");
+            lines.push(Line::from(vec![
+                Span::styled("✗ ", Style::default().fg(Color::DarkGray)),
+                Span::raw(gray_str),
+            ]));
         }
+
+        // Letters in the answer that a gray copy capped
+        if !summary.limited.is_empty() {
+            let limited_str: String = summary
+                .limited
+                .iter()
+                .map(|(c, max)| format!("{} ×{} only", c.to_ascii_uppercase(), max))
+                .collect::<Vec<String>>()
+                .join("  ");
+            lines.push(Line::from(vec![
+                Span::styled("≤ ", Style::default().fg(Color::Yellow)),
+                Span::raw(limited_str),
+            ]));
+        }
+        lines
     }
 }
+
+fn placement_line(placement: &YellowPlacement) -> Line<'static> {
+    let letter = placement.letter;
+    if let Some(pos) = placement.forced() {
+        return Line::from(Span::styled(
+            format!("  {} → must be position {}", letter, pos + 1),
+            Style::default().fg(Color::LightGreen),
+        ));
+    }
+
+    let text = if placement.positions.is_empty() {
+        format!("  {} → no position left", letter)
+    } else {
+        let positions: Vec<String> = placement
+            .positions
+            .iter()
+            .map(|p| (p + 1).to_string())
+            .collect();
+        format!("  {} → can be {}", letter, positions.join(","))
+    };
+    Line::from(Span::styled(text, Style::default().fg(Color::DarkGray)))
+}
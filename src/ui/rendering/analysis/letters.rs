@@ -1,48 +1,69 @@
 use ratatui::{
     Frame,
+    layout::Rect,
     style::{Color, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::ui::app::App;
+use crate::{settings::PanelKind, ui::app::App};
 
 impl App {
-    pub(in crate::ui) fn draw_letter_analysis(&self, f: &mut Frame, area: ratatui::layout::Rect) {
-        if let Some(analysis) = &self.letter_analysis {
-            let mut freq: Vec<(char, usize)> =
-                analysis.frequencies.iter().map(|(c, v)| (*c, *v)).collect();
-
-            // Sort by frequency descending
-            freq.sort_by(|a, b| b.1.cmp(&a.1));
-
-            let max_bar = area.width.saturating_sub(8) as usize;
-
-            let mut lines = vec![
-                Line::from(format!("Remaining: {} words", analysis.total_words)),
-                Line::from(""),
-            ];
-
-            for (c, count) in freq.into_iter().take(10) {
-                let width = if analysis.max_frequency > 0 {
-                    (count * max_bar / analysis.max_frequency).max(1)
-                } else {
-                    0
-                };
-
-                let bar = "█".repeat(width);
-
-                lines.push(Line::from(vec![
-                    Span::raw(format!("{} {:>4} ", c, count)),
-                    Span::styled(bar, Style::default().fg(Color::Cyan)),
-                ]));
-            }
-
-            f.render_widget(
-                Paragraph::new(lines)
-                    .block(Block::default().borders(Borders::ALL).title("Letters")),
-                area,
-            );
+    pub(in crate::ui) fn draw_letter_analysis(&self, f: &mut Frame, area: Rect) {
+        if self.letter_analysis.is_none() {
+            return;
         }
+        let lines = self.panel_cache.lines(
+            PanelKind::Letters,
+            self.panel_key(PanelKind::Letters, area),
+            || self.letter_analysis_lines(area),
+        );
+
+        let title = if self.settings.letter_information {
+            "Letters (information, bits) | Ctrl+L: frequency"
+        } else {
+            "Letters (frequency) | Ctrl+L: information"
+        };
+        f.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title)),
+            area,
+        );
+    }
+
+    fn letter_analysis_lines(&self, area: Rect) -> Vec<Line<'static>> {
+        let Some(analysis) = &self.letter_analysis else {
+            return Vec::new();
+        };
+        let mut freq: Vec<(char, usize)> =
+            analysis.frequencies.iter().map(|(c, v)| (*c, *v)).collect();
+
+        // Sort by frequency descending
+        freq.sort_by_key(|b| std::cmp::Reverse(b.1));
+
+        let max_bar = area.width.saturating_sub(8) as usize;
+
+        let mut lines = vec![
+            Line::from(format!("Remaining: {} words", analysis.total_words)),
+            Line::from(""),
+        ];
+
+        for (c, count) in freq.into_iter().take(10) {
+            let width = (count * max_bar)
+                .checked_div(analysis.max_frequency)
+                .map_or(0, |w| w.max(1));
+
+            let bar = self.bar_glyph().repeat(width);
+
+            let value = if self.settings.letter_information {
+                format!("{:.2}", count as f64 / 1000.0)
+            } else {
+                count.to_string()
+            };
+            lines.push(Line::from(vec![
+                Span::raw(format!("{} {:>4} ", c, value)),
+                Span::styled(bar, Style::default().fg(Color::Cyan)),
+            ]));
+        }
+        lines
     }
 }
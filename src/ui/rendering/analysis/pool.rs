@@ -1,62 +1,130 @@
 use ratatui::{
     Frame,
-    text::Line,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::ui::app::App;
+use crate::{
+    analysis::format_guesses_to_go,
+    settings::PanelKind,
+    solver::Feedback,
+    ui::{
+        app::App,
+        rendering::entropy_chart::{EntropyPoint, render_entropy_chart},
+        types::{GameMode, PATTERN_PREVIEW_ROWS},
+    },
+};
 
 impl App {
-    pub(in crate::ui) fn draw_solution_pool(&self, f: &mut Frame, area: ratatui::layout::Rect) {
-        if let Some(stats) = &self.solution_pool_stats {
-            let mut lines = vec![
-                Line::from("Solution Pool"),
-                Line::from(format!("Total: {} remaining", stats.total_remaining)),
-                Line::from(format!(
-                    "Filtered: {:.1}% eliminated",
-                    stats.eliminated_percentage
-                )),
-                Line::from(format!("Entropy: {:.2} bits", stats.entropy)),
-                Line::from(""),
-            ];
-
-            if !self.entropy_history.is_empty() {
-                let graph_height = area.height.saturating_sub(lines.len() as u16 + 2) as usize;
-                let graph_width = area.width.saturating_sub(2) as usize;
-
-                if graph_height > 0 && graph_width > 0 {
-                    let start = self.entropy_history.len().saturating_sub(graph_width);
-                    let slice = &self.entropy_history[start..];
-
-                    let max_entropy = slice.iter().cloned().fold(0.0_f64, f64::max);
-
-                    let mut grid = vec![vec![' '; graph_width]; graph_height];
-
-                    for (i, value) in slice.iter().enumerate() {
-                        let x = i;
-
-                        let normalized = if max_entropy > 0.0 {
-                            ((value + 1.0).ln() / (max_entropy + 1.0).ln()).clamp(0.0, 1.0)
-                        } else {
-                            0.0
-                        };
-
-                        let y =
-                            graph_height - 1 - (normalized * (graph_height as f64 - 1.0)) as usize;
-
-                        grid[y][x] = '●';
-                    }
-
-                    for row in grid {
-                        lines.push(Line::from(row.into_iter().collect::<String>()));
-                    }
-                }
-            }
-
-            f.render_widget(
-                Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Pool")),
-                area,
-            );
+    /// The entropy left by each guess, with its optimality where the solver
+    /// session measured every guess on the board.
+    pub(in crate::ui) fn entropy_points(&self) -> Vec<EntropyPoint> {
+        let measured = &self.solver_session_guesses;
+        let aligned = measured.len() == self.entropy_history.len()
+            && measured
+                .iter()
+                .zip(self.solver.guesses())
+                .all(|(m, g)| m.word == g.word);
+        self.entropy_history
+            .iter()
+            .enumerate()
+            .map(|(i, &entropy)| EntropyPoint {
+                entropy,
+                optimal: measured
+                    .get(i)
+                    .filter(|_| aligned)
+                    .and_then(|m| m.was_optimal()),
+            })
+            .collect()
+    }
+
+    /// The likeliest feedback for the word typed into the solver input, e.g.
+    /// `XYXXG  34% → 12 left`, under a heading naming the word.
+    fn pattern_preview_lines(&self) -> Vec<Line<'static>> {
+        let Some(preview) = self
+            .pattern_preview
+            .as_ref()
+            .filter(|p| self.mode == GameMode::Solver && !p.odds.is_empty())
+        else {
+            return Vec::new();
+        };
+
+        let mut lines = vec![Line::from(format!(
+            "If you guess {}:",
+            preview.word.to_uppercase()
+        ))];
+        for odds in preview.odds.iter().take(PATTERN_PREVIEW_ROWS) {
+            let mut spans: Vec<Span> = odds
+                .feedback
+                .iter()
+                .map(|f| {
+                    let (letter, color) = match f {
+                        Feedback::Green => ("G", Color::Green),
+                        Feedback::Yellow => ("Y", Color::Yellow),
+                        Feedback::Gray => ("X", Color::DarkGray),
+                    };
+                    Span::styled(letter, Style::default().fg(color))
+                })
+                .collect();
+            spans.push(Span::raw(format!(
+                " {:>3.0}% → {} left",
+                odds.probability * 100.0,
+                odds.remaining
+            )));
+            lines.push(Line::from(spans));
+        }
+        lines
+    }
+
+    pub(in crate::ui) fn draw_solution_pool(&self, f: &mut Frame, area: Rect) {
+        if self.solution_pool_stats.is_none() {
+            return;
+        }
+        let lines = self.panel_cache.lines(
+            PanelKind::Pool,
+            self.panel_key(PanelKind::Pool, area),
+            || self.solution_pool_lines(area),
+        );
+
+        f.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Pool")),
+            area,
+        );
+    }
+
+    fn solution_pool_lines(&self, area: Rect) -> Vec<Line<'static>> {
+        let Some(stats) = &self.solution_pool_stats else {
+            return Vec::new();
+        };
+        let mut lines = vec![
+            Line::from("Solution Pool"),
+            Line::from(format!("Total: {} remaining", stats.total_remaining)),
+            Line::from(format!(
+                "Filtered: {:.1}% eliminated",
+                stats.eliminated_percentage
+            )),
+            Line::from(format!(
+                "Entropy: {:.2} bits · {}",
+                stats.entropy,
+                format_guesses_to_go(stats.expected_guesses)
+            )),
+        ];
+        lines.extend(self.pattern_preview_lines());
+        // The chart is the slowest thing to draw over a slow link
+        if self.low_refresh() {
+            return lines;
         }
+        lines.push(Line::from(""));
+
+        // The chart fills what the summary leaves inside the border
+        let chart_area = Rect {
+            height: area.height.saturating_sub(lines.len() as u16 + 2),
+            width: area.width.saturating_sub(2),
+            ..area
+        };
+        lines.extend(render_entropy_chart(&self.entropy_points(), chart_area));
+        lines
     }
 }
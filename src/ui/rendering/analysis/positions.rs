@@ -1,38 +1,63 @@
 use ratatui::{
     Frame,
+    layout::Rect,
+    style::{Color, Style},
     text::Line,
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::ui::app::App;
+use crate::{analysis::SolvedSource, settings::PanelKind, ui::app::App};
 
 impl App {
-    pub(in crate::ui) fn draw_position_analysis(&self, f: &mut Frame, area: ratatui::layout::Rect) {
-        if let Some(analysis) = &self.position_analysis {
-            let mut lines = vec![Line::from("Position Analysis"), Line::from("")];
+    pub(in crate::ui) fn draw_position_analysis(&self, f: &mut Frame, area: Rect) {
+        if self.position_analysis.is_none() {
+            return;
+        }
+        let lines = self.panel_cache.lines(
+            PanelKind::Positions,
+            self.panel_key(PanelKind::Positions, area),
+            || self.position_analysis_lines(),
+        );
+
+        f.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Positions")),
+            area,
+        );
+    }
 
-            for (pos, letters) in analysis.possible_letters.iter().enumerate() {
-                let letters_with_freq: Vec<String> = letters
-                    .iter()
-                    .map(|c| {
-                        if let Some(&count) = analysis.position_frequencies[pos].get(c) {
-                            format!("{}({})", c, count)
-                        } else {
-                            c.to_string()
-                        }
-                    })
-                    .collect();
+    fn position_analysis_lines(&self) -> Vec<Line<'static>> {
+        let Some(analysis) = &self.position_analysis else {
+            return Vec::new();
+        };
+        let mut lines = vec![Line::from("Position Analysis"), Line::from("")];
 
-                let letters_str = letters_with_freq.join(" ");
+        for (pos, letters) in analysis.possible_letters.iter().enumerate() {
+            let letters_with_freq: Vec<String> = letters
+                .iter()
+                .map(|c| {
+                    if let Some(&count) = analysis.position_frequencies[pos].get(c) {
+                        format!("{}({})", c, count)
+                    } else {
+                        c.to_string()
+                    }
+                })
+                .collect();
 
-                lines.push(Line::from(format!("Pos {}: [{}]", pos + 1, letters_str)));
-            }
+            let letters_str = letters_with_freq.join(" ");
 
-            f.render_widget(
-                Paragraph::new(lines)
-                    .block(Block::default().borders(Borders::ALL).title("Positions")),
-                area,
-            );
+            let text = format!("Pos {}: [{}]", pos + 1, letters_str);
+            lines.push(match analysis.solved_source[pos] {
+                Some(SolvedSource::Green { .. }) => {
+                    Line::styled(text, Style::default().fg(Color::Green))
+                }
+                // Solved by elimination, which the player may not have noticed
+                Some(SolvedSource::Inferred) => Line::styled(
+                    format!("{} (inferred)", text),
+                    Style::default().fg(Color::Cyan),
+                ),
+                None => Line::from(text),
+            });
         }
+        lines
     }
 }
@@ -0,0 +1,94 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+/// Columns each guess's bar takes, its gap included.
+const SLOT_WIDTH: usize = 3;
+
+/// The pool entropy left by one guess, and whether the guess did as well as
+/// the optimal word when it was measured.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntropyPoint {
+    pub entropy: f64,
+    pub optimal: Option<bool>,
+}
+
+/// A bar per guess, scaled linearly from 0 to the largest entropy in `history`.
+///
+/// The left axis is labeled at the maximum and at 0, where it meets the
+/// bottom axis of guess numbers, and the latest bar is followed by its value.
+/// Bars are green where the guess cut the pool at least as well as the
+/// optimal word would have, red where it fell short, and plain where that
+/// wasn't measured. When there are more guesses than fit, the latest are kept.
+pub fn render_entropy_chart(history: &[EntropyPoint], area: Rect) -> Vec<Line<'static>> {
+    let (width, height) = (area.width as usize, area.height as usize);
+    let Some(latest) = history.last() else {
+        return Vec::new();
+    };
+    let annotation = format!(" {:.2}", latest.entropy);
+    let max = history.iter().map(|p| p.entropy).fold(0.0_f64, f64::max);
+    let top_label = format!("{:.1}", max);
+    let label_width = top_label.len();
+
+    let slots = width.saturating_sub(label_width + 1 + annotation.len()) / SLOT_WIDTH;
+    if height < 2 || slots == 0 {
+        return Vec::new();
+    }
+    let first = history.len().saturating_sub(slots);
+    let shown = &history[first..];
+
+    let bar_rows = height - 1;
+    let filled: Vec<usize> = shown
+        .iter()
+        .map(|p| {
+            if max > 0.0 && p.entropy > 0.0 {
+                ((p.entropy / max * bar_rows as f64).ceil() as usize).clamp(1, bar_rows)
+            } else {
+                0
+            }
+        })
+        .collect();
+    let latest_top = filled.last().map_or(0, |&rows| rows.max(1));
+
+    let mut lines: Vec<Line> = (0..bar_rows)
+        .map(|row| {
+            let level = bar_rows - row;
+            let (label, axis) = if row == 0 {
+                (top_label.as_str(), '┤')
+            } else {
+                ("", '│')
+            };
+            let mut spans = vec![Span::styled(
+                format!("{:>label_width$}{}", label, axis),
+                Style::default().fg(Color::DarkGray),
+            )];
+            for (point, &rows) in shown.iter().zip(&filled) {
+                let cell = if rows >= level { "██ " } else { "   " };
+                spans.push(Span::styled(cell, bar_style(point)));
+            }
+            if level == latest_top {
+                spans.push(Span::raw(annotation.clone()));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    let numbers: String = (first + 1..=history.len())
+        .map(|n| format!("{:<SLOT_WIDTH$}", n))
+        .collect();
+    lines.push(Line::from(Span::styled(
+        format!("{:>label_width$}└{}", "0", numbers),
+        Style::default().fg(Color::DarkGray),
+    )));
+    lines
+}
+
+fn bar_style(point: &EntropyPoint) -> Style {
+    match point.optimal {
+        Some(true) => Style::default().fg(Color::Green),
+        Some(false) => Style::default().fg(Color::Red),
+        None => Style::default(),
+    }
+}
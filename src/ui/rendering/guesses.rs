@@ -1,39 +1,251 @@
 use ratatui::{
     Frame,
-    style::{Color, Style},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::{solver::Feedback, ui::app::App};
+use crate::{
+    settings::Difficulty,
+    solver::{Feedback, Guess, join_segments},
+    ui::{
+        app::App,
+        types::{
+            BOARDS_PER_ROW, Board, FocusTarget, GameMode, MAX_GUESS_ROWS, MultiBoardState,
+            STANDARD_GUESSES,
+        },
+    },
+};
 
 impl App {
-    pub(in crate::ui) fn draw_guesses(&self, f: &mut Frame, area: ratatui::layout::Rect) {
-        let lines: Vec<Line> = self
-            .solver
-            .guesses()
+    /// Height of the Guesses panel: six rows, growing with a longer solver
+    /// session up to [`MAX_GUESS_ROWS`], past which it scrolls.
+    pub(in crate::ui) fn guess_panel_height(&self) -> u16 {
+        // A multi-board game keeps a band of boards per row, each a header
+        // over its last six guesses
+        if let Some(boards) = &self.boards {
+            let bands = boards.boards.len().div_ceil(BOARDS_PER_ROW);
+            return (bands * (STANDARD_GUESSES + 1)) as u16 + 2;
+        }
+        let guesses = self.solver.guesses().len();
+        let separator = usize::from(guesses > STANDARD_GUESSES);
+        (guesses + separator).clamp(STANDARD_GUESSES, MAX_GUESS_ROWS) as u16 + 2
+    }
+
+    pub(in crate::ui) fn draw_guesses(&self, f: &mut Frame, area: Rect) {
+        if let Some(boards) = &self.boards {
+            self.draw_boards(f, area, boards);
+            return;
+        }
+        let guesses = self.solver.guesses();
+        let rows = area.height.saturating_sub(2) as usize;
+        let focused = self.focused() == FocusTarget::Guesses;
+
+        // Pool sizes would be a hint while a game hides its analysis, and
+        // Expert games never show them
+        let annotate = self.mode != GameMode::Game
+            || (self.game_difficulty != Difficulty::Expert
+                && (self.show_analysis || self.game_over));
+        let inner_width = area.width.saturating_sub(2) as usize;
+
+        // Each row with the guess it shows; a rule after the sixth guess
+        // marks where a standard game would have ended
+        let mut items: Vec<(Option<usize>, Line)> = guesses
             .iter()
-            .map(|g| {
-                let spans: Vec<Span> = g
+            .enumerate()
+            .map(|(i, g)| (Some(i), self.guess_line(i, g, annotate, inner_width)))
+            .collect();
+        if guesses.len() > STANDARD_GUESSES {
+            items.insert(
+                STANDARD_GUESSES,
+                (
+                    None,
+                    Line::from(Span::styled(
+                        format!("{:─^width$}", " beyond 6 ", width = inner_width),
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                ),
+            );
+        }
+        self.guess_rows
+            .set(rows.saturating_sub(items.len() - guesses.len()));
+
+        // At the latest guesses and unfocused, the earliest fold into one
+        // summary row so the latest always fit; focus or scrolling unfolds them
+        let collapsed = !focused && self.guess_scroll == 0 && items.len() > rows && rows > 1;
+        let window = rows - usize::from(collapsed);
+        let hidden = items.len().saturating_sub(window);
+        let start = hidden - self.guess_scroll.min(hidden);
+        let earlier = items[..start].iter().filter(|(i, _)| i.is_some()).count();
+        let shown: Vec<(Option<usize>, Line)> = items.drain(start..).take(window).collect();
+        let first = shown.iter().find_map(|&(i, _)| i);
+        let last = shown.iter().rev().find_map(|&(i, _)| i);
+
+        let mut lines = Vec::new();
+        if collapsed {
+            let plural = if earlier == 1 { "" } else { "es" };
+            lines.push(Line::from(Span::styled(
+                format!("… {} earlier guess{}", earlier, plural),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        lines.extend(shown.into_iter().map(|(_, line)| line));
+
+        let mut title = match (first, last) {
+            (Some(first), Some(last)) if hidden > 0 && first == last => {
+                format!("Guess {} of {}", first + 1, guesses.len())
+            }
+            (Some(first), Some(last)) if hidden > 0 => {
+                format!("Guesses {}-{} of {}", first + 1, last + 1, guesses.len())
+            }
+            _ => "Guesses".to_string(),
+        };
+        if focused {
+            title.push_str(" | ↑↓ Home/End: scroll · Esc: done");
+        } else if hidden > 0 {
+            title.push_str(" | PgUp/PgDn: scroll");
+        }
+        let border_style = if focused {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default()
+        };
+        f.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(border_style)
+                    .title(title),
+            ),
+            area,
+        );
+    }
+
+    /// Guess `i` as colored tiles, with the pool it left when `annotate` is set.
+    fn guess_line(
+        &self,
+        i: usize,
+        guess: &Guess,
+        annotate: bool,
+        inner_width: usize,
+    ) -> Line<'static> {
+        let mut feedback = guess.feedback.iter();
+        let word = join_segments(&guess.word, self.solver.segments());
+        let mut spans: Vec<Span> = self
+            .spellings
+            .display(&word)
+            .chars()
+            .map(|c| {
+                // A gap between the words of a phrase
+                if c == ' ' {
+                    return Span::raw("  ");
+                }
+                let style = match feedback.next() {
+                    Some(Feedback::Green) => Style::default().bg(Color::Green).fg(Color::Black),
+                    Some(Feedback::Yellow) => Style::default().bg(Color::Yellow).fg(Color::Black),
+                    Some(Feedback::Gray) | None => {
+                        Style::default().bg(Color::DarkGray).fg(Color::White)
+                    }
+                };
+                Span::styled(format!(" {} ", c.to_uppercase()), style)
+            })
+            .collect();
+
+        if let Some((_, note)) = self.guess_notes.get(&i) {
+            spans.push(Span::styled(
+                format!("  {}", note),
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::ITALIC),
+            ));
+        }
+
+        if annotate && let Some(pool) = self.pool_history.get(i) {
+            let pool = format!("→ {} ", pool);
+            let used: usize = spans.iter().map(|s| s.width()).sum();
+            let pad = inner_width.saturating_sub(used + pool.chars().count());
+            spans.push(Span::raw(" ".repeat(pad.max(1))));
+            spans.push(Span::styled(pool, Style::default().fg(Color::DarkGray)));
+        }
+        Line::from(spans)
+    }
+
+    /// Every board of a multi-board game as a compact grid of letter tiles.
+    fn draw_boards(&self, f: &mut Frame, area: Rect, boards: &MultiBoardState) {
+        let title = format!(
+            "Boards ({}/{} solved)",
+            boards.solved(),
+            boards.boards.len()
+        );
+        let block = Block::default().borders(Borders::ALL).title(title);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let bands = boards.boards.chunks(BOARDS_PER_ROW);
+        let band_areas = Layout::vertical(vec![
+            Constraint::Length(STANDARD_GUESSES as u16 + 1);
+            bands.len()
+        ])
+        .split(inner);
+        for (band, row) in bands.enumerate() {
+            let cells = Layout::horizontal(vec![
+                Constraint::Ratio(1, BOARDS_PER_ROW as u32);
+                BOARDS_PER_ROW
+            ])
+            .split(band_areas[band]);
+            for (i, board) in row.iter().enumerate() {
+                let number = band * BOARDS_PER_ROW + i;
+                f.render_widget(Paragraph::new(self.board_lines(number, board)), cells[i]);
+            }
+        }
+    }
+
+    /// Board `i`'s header over its latest guesses: its number and, once
+    /// solved, the guess that solved it; the answer is shown after the game.
+    fn board_lines(&self, i: usize, board: &Board) -> Vec<Line<'static>> {
+        let mut header = vec![Span::styled(
+            format!("#{} ", i + 1),
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        if let Some(n) = board.solved_in {
+            header.push(Span::styled(
+                format!("✓{} ", n),
+                Style::default().fg(Color::Green),
+            ));
+        }
+        if self.game_over {
+            let color = if board.solved_in.is_some() {
+                Color::Green
+            } else {
+                Color::Red
+            };
+            header.push(Span::styled(
+                board.target.to_uppercase(),
+                Style::default().fg(color),
+            ));
+        }
+
+        let guesses = board.solver.guesses();
+        let skip = guesses.len().saturating_sub(STANDARD_GUESSES);
+        let mut lines = vec![Line::from(header)];
+        lines.extend(guesses[skip..].iter().map(|guess| {
+            Line::from(
+                guess
                     .word
                     .chars()
-                    .zip(g.feedback.iter())
+                    .zip(&guess.feedback)
                     .map(|(c, fb)| {
                         let style = match fb {
                             Feedback::Green => Style::default().bg(Color::Green).fg(Color::Black),
                             Feedback::Yellow => Style::default().bg(Color::Yellow).fg(Color::Black),
                             Feedback::Gray => Style::default().bg(Color::DarkGray).fg(Color::White),
                         };
-                        Span::styled(format!(" {} ", c.to_ascii_uppercase()), style)
+                        Span::styled(c.to_uppercase().to_string(), style)
                     })
-                    .collect();
-                Line::from(spans)
-            })
-            .collect();
-
-        f.render_widget(
-            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Guesses")),
-            area,
-        );
+                    .collect::<Vec<_>>(),
+            )
+        }));
+        lines
     }
 }
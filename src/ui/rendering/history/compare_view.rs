@@ -0,0 +1,171 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+};
+
+use crate::{
+    local_time::{format_local, format_local_date},
+    ui::{
+        App,
+        history::{HistoryStats, StatsComparison},
+    },
+};
+
+/// Whether a metric improves as it rises or as it falls.
+#[derive(Clone, Copy)]
+enum Better {
+    Higher,
+    Lower,
+}
+
+impl App {
+    pub(in crate::ui) fn draw_compare_view(&self, f: &mut Frame, area: Rect) {
+        let (Some(data), Some(split)) = (&self.history_data, self.history_compare_split) else {
+            let paragraph = Paragraph::new("No game history available")
+                .block(Block::default().borders(Borders::ALL).title("Compare"));
+            f.render_widget(paragraph, area);
+            return;
+        };
+
+        let comparison = StatsComparison::new(&data.games, split);
+        let prefs = self.time_prefs();
+        let date = format_local(split, &prefs);
+
+        let table = Table::new(
+            comparison_rows(&comparison),
+            [
+                Constraint::Length(16),
+                Constraint::Length(18),
+                Constraint::Length(18),
+                Constraint::Length(12),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "".to_string(),
+                format!("Before {}", format_local_date(split, &prefs)),
+                format!("From {}", format_local_date(split, &prefs)),
+                "Change".to_string(),
+            ])
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .bottom_margin(1),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Compare at {}", date)),
+        );
+
+        f.render_widget(table, area);
+    }
+}
+
+fn comparison_rows(comparison: &StatsComparison) -> Vec<Row<'static>> {
+    let before = &comparison.before;
+    let after = &comparison.after;
+
+    let mut rows = vec![
+        Row::new(vec![
+            Cell::from("Games"),
+            Cell::from(before.total_games.to_string()),
+            Cell::from(after.total_games.to_string()),
+            Cell::from(""),
+        ]),
+        stat_row(
+            "Win Rate",
+            win_rate(before),
+            win_rate(after),
+            comparison.win_rate_delta(),
+            Better::Higher,
+            |d| format!("{:+.1}%", d),
+        ),
+        stat_row(
+            "Avg Guesses",
+            average_guesses(before),
+            average_guesses(after),
+            comparison.average_guesses_delta(),
+            Better::Lower,
+            |d| format!("{:+.2}", d),
+        ),
+        stat_row(
+            "Avg Solve Time",
+            solve_time(before),
+            solve_time(after),
+            comparison.average_solve_secs_delta(),
+            Better::Lower,
+            |d| format!("{:+.1}s", d),
+        ),
+        Row::new(vec![Cell::from("")]),
+    ];
+
+    let deltas = comparison.distribution_delta();
+    for (i, delta) in deltas.iter().enumerate() {
+        rows.push(Row::new(vec![
+            Cell::from(format!("Won in {}", i + 1)),
+            Cell::from(before.guess_distribution[i].to_string()),
+            Cell::from(after.guess_distribution[i].to_string()),
+            Cell::from(format!("{:+}", delta)),
+        ]));
+    }
+
+    rows
+}
+
+fn stat_row(
+    label: &'static str,
+    before: String,
+    after: String,
+    delta: Option<f64>,
+    better: Better,
+    format_delta: impl Fn(f64) -> String,
+) -> Row<'static> {
+    let delta_cell = match delta {
+        Some(d) => {
+            let improved = match better {
+                Better::Higher => d > 0.0,
+                Better::Lower => d < 0.0,
+            };
+            let color = if d == 0.0 {
+                Color::Gray
+            } else if improved {
+                Color::Green
+            } else {
+                Color::Red
+            };
+            Cell::from(Span::styled(format_delta(d), Style::default().fg(color)))
+        }
+        None => Cell::from("N/A"),
+    };
+
+    Row::new(vec![
+        Cell::from(label),
+        Cell::from(before),
+        Cell::from(after),
+        delta_cell,
+    ])
+}
+
+fn win_rate(stats: &HistoryStats) -> String {
+    if stats.total_games > 0 {
+        format!("{:.1}%", stats.win_rate)
+    } else {
+        "N/A".to_string()
+    }
+}
+
+fn average_guesses(stats: &HistoryStats) -> String {
+    if stats.wins > 0 {
+        format!("{:.2}", stats.average_guesses)
+    } else {
+        "N/A".to_string()
+    }
+}
+
+fn solve_time(stats: &HistoryStats) -> String {
+    stats
+        .average_solve_secs
+        .map_or_else(|| "N/A".to_string(), |s| format!("{:.1}s", s))
+}
@@ -6,7 +6,12 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::{solver::Feedback, ui::App};
+use crate::{
+    local_time::{TimePrefs, format_local, format_local_date},
+    normalizer::Spellings,
+    solver::Feedback,
+    ui::App,
+};
 
 impl App {
     pub(in crate::ui) fn draw_detail_view(&self, f: &mut Frame, area: Rect) {
@@ -22,10 +27,19 @@ impl App {
                     .split(area);
 
                 // Draw game header
-                draw_game_header(f, chunks[0], game);
+                let position = history_data.cursor.position().unwrap_or(0);
+                let title = format!("Game {}/{}", position + 1, history_data.visible_count());
+                draw_game_header(
+                    f,
+                    chunks[0],
+                    game,
+                    &self.spellings,
+                    &self.time_prefs(),
+                    title,
+                );
 
                 // Draw guesses
-                draw_game_guesses(f, chunks[1], game);
+                draw_game_guesses(f, chunks[1], game, &self.spellings);
             } else {
                 let text = vec![
                     Line::from(""),
@@ -46,18 +60,31 @@ impl App {
     }
 }
 
-fn draw_game_header(f: &mut Frame, area: Rect, game: &crate::ui::history::GameRecord) {
-    let date = game.timestamp.format("%Y-%m-%d %H:%M:%S").to_string();
+fn draw_game_header(
+    f: &mut Frame,
+    area: Rect,
+    game: &crate::ui::history::GameRecord,
+    spellings: &Spellings,
+    prefs: &TimePrefs,
+    title: String,
+) {
+    let date = format_local(game.timestamp, prefs);
     let outcome_text = match game.outcome {
         crate::ui::history::GameOutcome::Won { guesses } => {
             format!("Won in {} guess(es)", guesses)
         }
         crate::ui::history::GameOutcome::Lost => "Lost (ran out of guesses)".to_string(),
+        crate::ui::history::GameOutcome::Forfeited => "Gave up".to_string(),
+        crate::ui::history::GameOutcome::Abandoned(reason) => {
+            format!("Abandoned ({})", reason.name())
+        }
     };
 
     let outcome_color = match game.outcome {
         crate::ui::history::GameOutcome::Won { .. } => Color::Green,
         crate::ui::history::GameOutcome::Lost => Color::Red,
+        crate::ui::history::GameOutcome::Forfeited => Color::Magenta,
+        crate::ui::history::GameOutcome::Abandoned(_) => Color::DarkGray,
     };
 
     let lines = vec![
@@ -70,11 +97,18 @@ fn draw_game_header(f: &mut Frame, area: Rect, game: &crate::ui::history::GameRe
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD),
             ),
+            Span::raw("  |  Version: "),
+            Span::raw(
+                game.app_version
+                    .as_deref()
+                    .unwrap_or("unrecorded")
+                    .to_string(),
+            ),
         ]),
         Line::from(vec![
             Span::raw("  Target Word: "),
             Span::styled(
-                game.target_word.to_uppercase(),
+                spellings.display(&game.target_word).to_uppercase(),
                 Style::default()
                     .fg(Color::Yellow)
                     .add_modifier(Modifier::BOLD),
@@ -86,27 +120,76 @@ fn draw_game_header(f: &mut Frame, area: Rect, game: &crate::ui::history::GameRe
                     .fg(outcome_color)
                     .add_modifier(Modifier::BOLD),
             ),
+            Span::raw(if game.blitz { "  |  Blitz" } else { "" }),
+            Span::raw(game.word_difficulty.map_or(String::new(), |d| {
+                format!("  |  Word: {} ({:.2})", d.tier.name(), d.score)
+            })),
+            Span::raw(match game.hints_used {
+                0 => String::new(),
+                1 => "  |  Assisted: 1 hint".to_string(),
+                n => format!("  |  Assisted: {} hints", n),
+            }),
+            Span::raw(hints_text(game)),
+            Span::styled(
+                game.archived_at.map_or(String::new(), |at| {
+                    format!("  |  Archived {}", format_local_date(at, prefs))
+                }),
+                Style::default().fg(Color::DarkGray),
+            ),
         ]),
         Line::from(""),
     ];
 
-    let paragraph = Paragraph::new(lines).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title("Game Details | Esc: Back to List"),
-    );
+    let paragraph =
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
 
     f.render_widget(paragraph, area);
 }
 
-fn draw_game_guesses(f: &mut Frame, area: Rect, game: &crate::ui::history::GameRecord) {
+/// The hints a game took and its budget, e.g. `  |  Hints: analysis, best guess (2 of 3)`.
+fn hints_text(game: &crate::ui::history::GameRecord) -> String {
+    let taken = game
+        .hints
+        .iter()
+        .map(|h| h.name())
+        .collect::<Vec<_>>()
+        .join(", ");
+    match (game.hint_budget, taken.is_empty()) {
+        (None, true) => String::new(),
+        (None, false) => format!("  |  Hints: {}", taken),
+        (Some(budget), true) => format!("  |  Hints: none (0 of {})", budget),
+        (Some(budget), false) => {
+            format!("  |  Hints: {} ({} of {})", taken, game.hints.len(), budget)
+        }
+    }
+}
+
+fn draw_game_guesses(
+    f: &mut Frame,
+    area: Rect,
+    game: &crate::ui::history::GameRecord,
+    spellings: &Spellings,
+) {
     let mut lines = vec![Line::from("")];
 
     for (i, guess) in game.guesses.iter().enumerate() {
         // Build the colored guess display
         let mut spans = vec![Span::raw(format!("  {}. ", i + 1))];
 
-        for (ch, feedback) in guess.word.chars().zip(&guess.feedback) {
+        if guess.timed_out {
+            spans.push(Span::styled("⏱ timed out", Style::default().fg(Color::Red)));
+        }
+
+        // Phrase guesses keep a space between words, which gets a gap but no feedback
+        let mut feedback = guess.feedback.iter();
+        for ch in spellings.display(&guess.word).chars() {
+            if ch == ' ' {
+                spans.push(Span::raw("  "));
+                continue;
+            }
+            let Some(feedback) = feedback.next() else {
+                break;
+            };
             let color = match feedback {
                 Feedback::Green => Color::Green,
                 Feedback::Yellow => Color::Yellow,
@@ -122,6 +205,34 @@ fn draw_game_guesses(f: &mut Frame, area: Rect, game: &crate::ui::history::GameR
             ));
         }
 
+        if let Some(luck) = guess.luck {
+            let color = if luck >= 0.0 {
+                Color::Green
+            } else {
+                Color::Red
+            };
+            spans.push(Span::styled(
+                format!("  luck {:+.1}", luck),
+                Style::default().fg(color),
+            ));
+        }
+
+        if let Some(rank) = guess.target_rank {
+            spans.push(Span::styled(
+                format!("  answer #{}", rank),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+
+        if let Some(note) = &guess.note {
+            spans.push(Span::styled(
+                format!("  {}", note),
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::ITALIC),
+            ));
+        }
+
         lines.push(Line::from(spans));
         lines.push(Line::from(""));
     }
@@ -3,22 +3,72 @@ use ratatui::{
     layout::{Constraint, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Row, Table},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Row, Table},
 };
 
-use crate::ui::App;
+use crate::{
+    local_time::{TimePrefs, format_local},
+    ui::{
+        App,
+        history::{GameOutcome, GameRecord},
+    },
+};
+
+/// Widths the table needs besides its date column; narrower lists switch to
+/// one short line per game.
+const TABLE_WIDTH_WITHOUT_DATE: u16 = 57;
+
+/// One game as a short line: number to select it, outcome glyph and guess
+/// count, month and day, and the word, e.g. `3. ✔4 10-15 SAVVY`.
+pub(in crate::ui) fn compact_line(
+    number: usize,
+    game: &GameRecord,
+    prefs: &TimePrefs,
+) -> Line<'static> {
+    let (glyph, color) = match game.outcome {
+        GameOutcome::Won { .. } => ('✔', Color::Green),
+        GameOutcome::Lost => ('✘', Color::Red),
+        GameOutcome::Forfeited => ('⚑', Color::Magenta),
+        GameOutcome::Abandoned(_) => ('○', Color::DarkGray),
+    };
+    let mut spans = vec![
+        Span::raw(format!("{}. ", number)),
+        Span::styled(
+            format!("{}{}", glyph, game.guess_count()),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!(" {} ", prefs.date(game.timestamp).format("%m-%d"))),
+        Span::styled(
+            game.listed_word().to_uppercase(),
+            Style::default().fg(color),
+        ),
+    ];
+    if game.is_archived() {
+        spans.push(Span::styled(
+            " archived",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    Line::from(spans)
+}
 
 impl App {
     pub(in crate::ui) fn draw_list_view(&self, f: &mut Frame, area: Rect) {
         if let Some(ref history_data) = self.history_data {
+            let prefs = self.time_prefs();
             let games = history_data.games_for_page(self.history_page);
             let total_pages = history_data.total_pages();
 
             if games.is_empty() {
+                let message = if history_data.is_loading() {
+                    "Loading games…"
+                } else {
+                    "No games found"
+                };
                 let text = vec![
                     Line::from(""),
                     Line::from(Span::styled(
-                        "No games found",
+                        message,
                         Style::default()
                             .fg(Color::Yellow)
                             .add_modifier(Modifier::BOLD),
@@ -32,17 +82,45 @@ impl App {
                 return;
             }
 
-            // Create table rows
             let start_index = self.history_page * 10;
+            if self.history_compact_list
+                || area.width < TABLE_WIDTH_WITHOUT_DATE + prefs.datetime_width()
+            {
+                let items: Vec<ListItem> = games
+                    .iter()
+                    .enumerate()
+                    .map(|(page_idx, game)| ListItem::new(compact_line(page_idx + 1, game, &prefs)))
+                    .collect();
+                let title = format!(
+                    "History {}/{}{}",
+                    self.history_page + 1,
+                    total_pages,
+                    if self.history_include_archived {
+                        " +archived"
+                    } else {
+                        ""
+                    }
+                );
+                let list =
+                    List::new(items).block(Block::default().borders(Borders::ALL).title(title));
+                f.render_widget(list, area);
+                return;
+            }
+
+            // Create table rows
             let rows: Vec<Row> = games
                 .iter()
                 .enumerate()
                 .map(|(page_idx, game)| {
-                    let date = game.timestamp.format("%Y-%m-%d %H:%M").to_string();
+                    let date = format_local(game.timestamp, &prefs);
                     let guesses = game.guess_count().to_string();
-                    let outcome = match game.outcome {
-                        crate::ui::history::GameOutcome::Won { .. } => "Won",
-                        crate::ui::history::GameOutcome::Lost => "Lost",
+                    let mut outcome = match game.outcome {
+                        crate::ui::history::GameOutcome::Won { .. } => "Won".to_string(),
+                        crate::ui::history::GameOutcome::Lost => "Lost".to_string(),
+                        crate::ui::history::GameOutcome::Forfeited => "Gave up".to_string(),
+                        crate::ui::history::GameOutcome::Abandoned(reason) => {
+                            format!("Abandoned ({})", reason.as_str())
+                        }
                     };
 
                     let outcome_style = match game.outcome {
@@ -50,40 +128,49 @@ impl App {
                             Style::default().fg(Color::Green)
                         }
                         crate::ui::history::GameOutcome::Lost => Style::default().fg(Color::Red),
+                        crate::ui::history::GameOutcome::Forfeited => {
+                            Style::default().fg(Color::Magenta)
+                        }
+                        crate::ui::history::GameOutcome::Abandoned(_) => {
+                            Style::default().fg(Color::DarkGray)
+                        }
                     };
 
+                    if game.is_archived() {
+                        outcome.push_str(", archived");
+                    }
+
                     // Show number for selection (1-10)
                     let num = format!("{}.", page_idx + 1);
 
-                    Row::new(vec![
-                        num,
-                        date,
-                        game.target_word.clone(),
-                        guesses,
-                        outcome.to_string(),
-                    ])
-                    .style(outcome_style)
+                    Row::new(vec![num, date, game.listed_word(), guesses, outcome])
+                        .style(outcome_style)
                 })
                 .collect();
 
-            // Create title with page info and instructions
+            // Create title with page info
             let title = format!(
-                "Game History - Page {}/{} (Showing {}-{} of {}) | PgUp/PgDn: Navigate | 1-9: View Detail | Tab: Views | Esc: Stats | Ctrl+R: Exit",
+                "Game History - Page {}/{} (Showing {}-{} of {}{})",
                 self.history_page + 1,
                 total_pages,
                 start_index + 1,
                 start_index + games.len(),
-                history_data.games.len()
+                history_data.visible_count().max(history_data.stored_games),
+                if self.history_include_archived {
+                    ", archived included"
+                } else {
+                    ""
+                }
             );
 
             let table = Table::new(
                 rows,
                 [
-                    Constraint::Length(3),  // Number
-                    Constraint::Length(16), // Date
-                    Constraint::Length(10), // Word
-                    Constraint::Length(8),  // Guesses
-                    Constraint::Length(12), // Outcome
+                    Constraint::Length(3),                      // Number
+                    Constraint::Length(prefs.datetime_width()), // Date
+                    Constraint::Length(10),                     // Word
+                    Constraint::Length(8),                      // Guesses
+                    Constraint::Length(30),                     // Outcome
                 ],
             )
             .header(
@@ -1,19 +1,41 @@
+mod compare_view;
 mod detail_view;
-mod list_view;
+pub(in crate::ui) mod list_view;
 mod solver_view;
 mod stats_view;
+mod timing_view;
 
 use ratatui::{Frame, layout::Rect};
 
-use crate::ui::{App, history::HistoryViewMode};
+use crate::ui::{
+    App,
+    history::{HistoryViewMode, StatsSection},
+};
 
 impl App {
     pub(in crate::ui) fn draw_history_mode(&self, f: &mut Frame, area: Rect) {
+        let loading = self
+            .history_data
+            .as_ref()
+            .is_some_and(|data| data.is_loading());
         match self.history_view_mode {
+            // Both need every game; the SQL totals stand in until they load
+            HistoryViewMode::Stats | HistoryViewMode::Compare if loading => {
+                self.draw_loading_stats(f, area)
+            }
+            HistoryViewMode::Stats if self.history_stats_section == StatsSection::Timing => {
+                self.draw_timing_view(f, area)
+            }
             HistoryViewMode::Stats => self.draw_stats_view(f, area),
             HistoryViewMode::List => self.draw_list_view(f, area),
             HistoryViewMode::Detail => self.draw_detail_view(f, area),
             HistoryViewMode::Solver => self.draw_solver_view(f, area),
+            HistoryViewMode::Compare => self.draw_compare_view(f, area),
         }
     }
 }
+
+/// Body rows that fit in a bordered table with a header and its bottom margin.
+fn table_body_rows(area: Rect) -> usize {
+    area.height.saturating_sub(4) as usize
+}
@@ -3,10 +3,18 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Row, Table},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
 };
 
-use crate::ui::App;
+use crate::{
+    local_time::{TimePrefs, format_local},
+    solver::Feedback,
+    ui::{
+        App,
+        history::solver_types::{DeviationMetric, SolverSession},
+        rendering::entropy_chart::{EntropyPoint, render_entropy_chart},
+    },
+};
 
 impl App {
     pub(in crate::ui) fn draw_solver_view(&self, f: &mut Frame, area: Rect) {
@@ -16,15 +24,25 @@ impl App {
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Length(10), // Overall solver stats
+                    Constraint::Length(12), // Overall solver stats
                     Constraint::Length(8),  // Deviation analysis
                     Constraint::Min(5),     // Recent sessions
                 ])
                 .split(area);
 
+            let middle = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(72), Constraint::Min(20)])
+                .split(chunks[1]);
+
             draw_solver_stats(f, chunks[0], solver_stats);
-            draw_deviation_analysis(f, chunks[1], solver_stats);
-            draw_recent_sessions(f, chunks[2], history_data);
+            draw_deviation_analysis(f, middle[0], solver_stats, self.bar_glyph());
+            if self.low_refresh() {
+                draw_chart_off(f, middle[1]);
+            } else {
+                draw_latest_session_chart(f, middle[1], history_data.solver_sessions.last());
+            }
+            draw_recent_sessions(f, chunks[2], history_data, &self.time_prefs());
         } else {
             let text = vec![
                 Line::from(""),
@@ -67,7 +85,16 @@ fn draw_solver_stats(f: &mut Frame, area: Rect, stats: &crate::ui::history::Solv
 
     let avg_entropy_str = format!("{:.2}", stats.average_entropy);
     let adherence_str = format!("{:.1}%", stats.optimal_adherence);
-    let deviation_str = format!("{:.2}", stats.average_deviation);
+    let deviation_str = if stats.entropy.measured > 0 {
+        format!("{:.2} bits", stats.entropy.average_deviation)
+    } else {
+        "N/A".to_string()
+    };
+    let mismatch_str = if stats.verified_sessions > 0 {
+        format!("{:.1}%", stats.mismatch_rate)
+    } else {
+        "N/A".to_string()
+    };
 
     let lines = vec![
         Line::from(""),
@@ -86,6 +113,11 @@ fn draw_solver_stats(f: &mut Frame, area: Rect, stats: &crate::ui::history::Solv
                     .fg(Color::Green)
                     .add_modifier(Modifier::BOLD),
             ),
+            Span::raw("  |  Abandoned: "),
+            Span::styled(
+                stats.abandoned.breakdown(),
+                Style::default().fg(Color::DarkGray),
+            ),
         ]),
         Line::from(""),
         Line::from(vec![
@@ -119,19 +151,52 @@ fn draw_solver_stats(f: &mut Frame, area: Rect, stats: &crate::ui::history::Solv
                     })
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::raw("  |  Avg Deviation: "),
+            Span::raw("  |  Entropy Deviation: "),
             Span::styled(
                 deviation_str,
                 Style::default()
-                    .fg(if stats.average_deviation >= -0.1 {
+                    .fg(if stats.entropy.average_deviation >= -0.1 {
                         Color::Green
-                    } else if stats.average_deviation >= -0.5 {
+                    } else if stats.entropy.average_deviation >= -0.5 {
                         Color::Yellow
                     } else {
                         Color::Red
                     })
                     .add_modifier(Modifier::BOLD),
             ),
+            // Scores aren't bits, so large pools get their own figure
+            Span::styled(
+                if stats.heuristic.measured > 0 {
+                    format!(
+                        "  |  Large pools: {:.2} score over {} guesses",
+                        stats.heuristic.average_deviation, stats.heuristic.measured
+                    )
+                } else {
+                    String::new()
+                },
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  Verified: "),
+            Span::styled(
+                format!("{}", stats.verified_sessions),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  |  Mismatch Rate: "),
+            Span::styled(
+                mismatch_str,
+                Style::default()
+                    .fg(if stats.mismatched_sessions == 0 {
+                        Color::Green
+                    } else {
+                        Color::Red
+                    })
+                    .add_modifier(Modifier::BOLD),
+            ),
         ]),
         Line::from(""),
     ];
@@ -139,21 +204,26 @@ fn draw_solver_stats(f: &mut Frame, area: Rect, stats: &crate::ui::history::Solv
     let paragraph = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
-            .title("Solver Statistics | Tab: Views | Ctrl+R: Exit"),
+            .title("Solver Statistics"),
     );
 
     f.render_widget(paragraph, area);
 }
 
-fn draw_deviation_analysis(f: &mut Frame, area: Rect, stats: &crate::ui::history::SolverStats) {
+fn draw_deviation_analysis(
+    f: &mut Frame,
+    area: Rect,
+    stats: &crate::ui::history::SolverStats,
+    bar: &str,
+) {
     let bar_width = if stats.optimal_adherence > 0.0 {
         ((stats.optimal_adherence / 100.0) * 50.0) as usize
     } else {
         0
     };
 
-    let optimal_bar = "█".repeat(bar_width);
-    let deviation_bar = "█".repeat(50 - bar_width);
+    let optimal_bar = bar.repeat(bar_width);
+    let deviation_bar = bar.repeat(50 - bar_width);
 
     let lines = vec![
         Line::from(""),
@@ -181,8 +251,48 @@ fn draw_deviation_analysis(f: &mut Frame, area: Rect, stats: &crate::ui::history
     f.render_widget(paragraph, area);
 }
 
-fn draw_recent_sessions(f: &mut Frame, area: Rect, history_data: &crate::ui::history::HistoryData) {
-    let recent_count = 10.min(history_data.solver_sessions.len());
+/// The pool entropy after each guess of the most recent session.
+fn draw_latest_session_chart(f: &mut Frame, area: Rect, session: Option<&SolverSession>) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Latest Session Entropy");
+    let lines = match session {
+        Some(session) => {
+            let points: Vec<EntropyPoint> = session
+                .guesses
+                .iter()
+                .map(|g| EntropyPoint {
+                    entropy: g.entropy,
+                    optimal: g.was_optimal(),
+                })
+                .collect();
+            render_entropy_chart(&points, block.inner(area))
+        }
+        None => Vec::new(),
+    };
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Stands in for the chart in low-refresh mode.
+fn draw_chart_off(f: &mut Frame, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Latest Session Entropy");
+    let lines = vec![Line::styled(
+        "Off in low-refresh mode",
+        Style::default().fg(Color::DarkGray),
+    )];
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_recent_sessions(
+    f: &mut Frame,
+    area: Rect,
+    history_data: &crate::ui::history::HistoryData,
+    prefs: &TimePrefs,
+) {
+    let recent_count = super::table_body_rows(area).min(history_data.solver_sessions.len());
     let recent_sessions = if recent_count > 0 {
         &history_data.solver_sessions[history_data.solver_sessions.len() - recent_count..]
     } else {
@@ -193,28 +303,39 @@ fn draw_recent_sessions(f: &mut Frame, area: Rect, history_data: &crate::ui::his
         .iter()
         .rev()
         .map(|session| {
-            let date = session.timestamp.format("%Y-%m-%d %H:%M").to_string();
+            let date = format_local(session.timestamp, prefs);
             let guesses = session.guess_count().to_string();
             let adherence = format!("{:.1}%", session.optimal_adherence());
             let avg_entropy = format!("{:.2}", session.average_entropy());
-            let deviation = format!("{:.2}", session.average_deviation());
+            let deviation = session
+                .average_deviation(DeviationMetric::Entropy)
+                .map_or("—".to_string(), |d| format!("{:.2}", d));
             let outcome = match session.outcome {
                 crate::ui::history::SolverOutcome::Completed { .. } => "Completed",
+                crate::ui::history::SolverOutcome::CompletedMismatch { .. } => "Mismatch",
+                crate::ui::history::SolverOutcome::Abandoned { .. } => "Abandoned",
             };
 
             let outcome_style = match session.outcome {
                 crate::ui::history::SolverOutcome::Completed { .. } => {
                     Style::default().fg(Color::Green)
                 }
+                crate::ui::history::SolverOutcome::CompletedMismatch { .. } => {
+                    Style::default().fg(Color::Red)
+                }
+                crate::ui::history::SolverOutcome::Abandoned { .. } => {
+                    Style::default().fg(Color::DarkGray)
+                }
             };
 
             Row::new(vec![
-                date,
-                guesses,
-                adherence,
-                avg_entropy,
-                deviation,
-                outcome.to_string(),
+                Cell::from(date),
+                Cell::from(guesses),
+                Cell::from(adherence),
+                Cell::from(avg_entropy),
+                Cell::from(deviation),
+                Cell::from(outcome),
+                Cell::from(session_path(session)),
             ])
             .style(outcome_style)
         })
@@ -223,12 +344,13 @@ fn draw_recent_sessions(f: &mut Frame, area: Rect, history_data: &crate::ui::his
     let table = Table::new(
         rows,
         [
-            Constraint::Length(16), // Date
-            Constraint::Length(8),  // Guesses
-            Constraint::Length(12), // Adherence
-            Constraint::Length(10), // Avg Entropy
-            Constraint::Length(10), // Deviation
-            Constraint::Length(10), // Outcome
+            Constraint::Length(prefs.datetime_width()), // Date
+            Constraint::Length(8),                      // Guesses
+            Constraint::Length(12),                     // Adherence
+            Constraint::Length(10),                     // Avg Entropy
+            Constraint::Length(10),                     // Deviation
+            Constraint::Length(10),                     // Outcome
+            Constraint::Min(20),                        // Path
         ],
     )
     .header(
@@ -237,8 +359,9 @@ fn draw_recent_sessions(f: &mut Frame, area: Rect, history_data: &crate::ui::his
             "Guesses",
             "Adherence",
             "Entropy",
-            "Deviation",
+            "Dev (bits)",
             "Status",
+            "Path",
         ])
         .style(Style::default().add_modifier(Modifier::BOLD))
         .bottom_margin(1),
@@ -246,8 +369,38 @@ fn draw_recent_sessions(f: &mut Frame, area: Rect, history_data: &crate::ui::his
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .title("Recent Sessions (Latest 10)"),
+            .title(format!("Recent Sessions (Latest {})", recent_count)),
     );
 
     f.render_widget(table, area);
 }
+
+/// The session's guesses in order, each as colored tiles when its pattern was
+/// kept, or as plain text for sessions saved before patterns were.
+fn session_path(session: &SolverSession) -> Line<'static> {
+    let mut spans = Vec::new();
+    for (i, guess) in session.guesses.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(" "));
+        }
+        let Some(feedback) = &guess.feedback else {
+            spans.push(Span::raw(guess.word.clone()));
+            continue;
+        };
+        for (ch, feedback) in guess.word.chars().zip(feedback) {
+            let color = match feedback {
+                Feedback::Green => Color::Green,
+                Feedback::Yellow => Color::Yellow,
+                Feedback::Gray => Color::DarkGray,
+            };
+            spans.push(Span::styled(
+                ch.to_uppercase().to_string(),
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(color)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+    }
+    Line::from(spans)
+}
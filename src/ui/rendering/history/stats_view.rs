@@ -6,7 +6,11 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Row, Table},
 };
 
-use crate::ui::App;
+use crate::{
+    local_time::{TimePrefs, format_local},
+    storage::VersionAverage,
+    ui::{App, history::HistoryData},
+};
 
 impl App {
     pub(in crate::ui) fn draw_stats_view(&self, f: &mut Frame, area: Rect) {
@@ -17,20 +21,23 @@ impl App {
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Length(8),  // Overall stats
-                    Constraint::Length(10), // Guess distribution
-                    Constraint::Min(5),     // Recent games
+                    Constraint::Length(8),                                   // Overall stats
+                    Constraint::Length(12), // Guess distribution and feedback rates
+                    Constraint::Length(breakdown_rows(history_data) as u16), // Per-release and per-difficulty
+                    Constraint::Min(5),                                      // Recent games
                 ])
                 .split(area);
 
             // Draw overall statistics with help text
-            draw_overall_stats(f, chunks[0], stats);
+            draw_overall_stats(f, chunks[0], stats, self.history_include_archived);
 
             // Draw guess distribution
-            draw_guess_distribution(f, chunks[1], stats);
+            draw_guess_distribution(f, chunks[1], history_data, self.bar_glyph());
+
+            draw_breakdowns(f, chunks[2], history_data);
 
             // Draw recent games
-            draw_recent_games(f, chunks[2], history_data);
+            draw_recent_games(f, chunks[3], history_data, &self.time_prefs());
         } else {
             // No history loaded
             let text = vec![
@@ -61,7 +68,123 @@ impl App {
     }
 }
 
-fn draw_overall_stats(f: &mut Frame, area: Rect, stats: &crate::ui::history::HistoryStats) {
+impl App {
+    /// Totals worked out in SQL while the games themselves are still loading.
+    pub(in crate::ui) fn draw_loading_stats(&self, f: &mut Frame, area: Rect) {
+        let Some(ref history_data) = self.history_data else {
+            return;
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(7),
+                Constraint::Length(breakdown_rows(history_data) as u16),
+                Constraint::Min(3),
+            ])
+            .split(area);
+
+        let games: usize = history_data.difficulty_stats.iter().map(|d| d.games).sum();
+        let wins: usize = history_data.difficulty_stats.iter().map(|d| d.wins).sum();
+        let guesses: f64 = history_data
+            .difficulty_stats
+            .iter()
+            .filter_map(|d| d.average_guesses.map(|avg| avg * d.wins as f64))
+            .sum();
+        let win_rate = if games > 0 {
+            format!("{:.1}%", wins as f64 / games as f64 * 100.0)
+        } else {
+            "N/A".to_string()
+        };
+        let average = if wins > 0 {
+            format!("{:.2}", guesses / wins as f64)
+        } else {
+            "N/A".to_string()
+        };
+
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::raw("  Games Played: "),
+                Span::styled(
+                    games.to_string(),
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("  |  Wins: "),
+                Span::styled(
+                    wins.to_string(),
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("  |  Win Rate: "),
+                Span::styled(
+                    win_rate,
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("  |  Avg Guesses: "),
+                Span::styled(
+                    average,
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+        ];
+        if !history_data.first_guess_green_rates.is_empty() {
+            let rates: Vec<String> = history_data
+                .first_guess_green_rates
+                .iter()
+                .map(|r| format!("{:.0}%", r * 100.0))
+                .collect();
+            lines.push(Line::from(""));
+            lines.push(Line::from(format!(
+                "  First guess green by position: {}",
+                rates.join(" ")
+            )));
+        }
+        f.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Overall Statistics"),
+            ),
+            chunks[0],
+        );
+
+        draw_breakdowns(f, chunks[1], history_data);
+
+        let progress = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(
+                    "  Loading details… {} of {} games",
+                    history_data.games.len(),
+                    history_data.stored_games
+                ),
+                Style::default().fg(Color::Yellow),
+            )),
+            Line::from(
+                "  Streaks, the guess distribution and recent games follow once every game is in.",
+            ),
+        ];
+        f.render_widget(
+            Paragraph::new(progress).block(Block::default().borders(Borders::ALL).title("Details")),
+            chunks[2],
+        );
+    }
+}
+
+fn draw_overall_stats(
+    f: &mut Frame,
+    area: Rect,
+    stats: &crate::ui::history::HistoryStats,
+    include_archived: bool,
+) {
     let win_rate_str = format!("{:.1}%", stats.win_rate);
     let avg_guesses_str = if stats.wins > 0 {
         format!("{:.2}", stats.average_guesses)
@@ -94,11 +217,32 @@ fn draw_overall_stats(f: &mut Frame, area: Rect, stats: &crate::ui::history::His
                     .fg(Color::Green)
                     .add_modifier(Modifier::BOLD),
             ),
+            Span::styled(
+                format!(
+                    " ({} clean, {} hinted)",
+                    stats.clean_wins, stats.hinted_wins
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
             Span::raw("  |  Losses: "),
             Span::styled(
                 format!("{}", stats.losses),
                 Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
             ),
+            Span::raw("  |  Gave Up: "),
+            Span::styled(
+                format!("{}", stats.forfeits),
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  |  Blitz Wins: "),
+            Span::styled(
+                format!("{}/{}", stats.blitz_wins, stats.blitz_games),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
         ]),
         Line::from(""),
         Line::from(vec![
@@ -136,20 +280,59 @@ fn draw_overall_stats(f: &mut Frame, area: Rect, stats: &crate::ui::history::His
                     .fg(Color::Yellow)
                     .add_modifier(Modifier::BOLD),
             ),
+            Span::raw("  |  Avg Luck: "),
+            Span::styled(
+                stats
+                    .average_luck
+                    .map_or("N/A".to_string(), |l| format!("{:+.2}", l)),
+                Style::default()
+                    .fg(match stats.average_luck {
+                        Some(l) if l > 0.0 => Color::Green,
+                        Some(l) if l < 0.0 => Color::Red,
+                        _ => Color::Gray,
+                    })
+                    .add_modifier(Modifier::BOLD),
+            ),
         ]),
         Line::from(""),
+        Line::from(vec![
+            Span::raw("  Abandoned: "),
+            Span::styled(
+                stats.abandoned.breakdown(),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::raw("  |  Assisted (*): "),
+            Span::styled(
+                format!("{}", stats.assisted_games),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  |  Archived: "),
+            Span::styled(
+                if include_archived {
+                    format!("{} included", stats.archived_games)
+                } else {
+                    "hidden".to_string()
+                },
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
     ];
 
-    let paragraph = Paragraph::new(lines).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title("Statistics | Tab: List View | Ctrl+R: Exit | Ctrl+Q: Quit"),
-    );
+    let paragraph =
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Statistics"));
 
     f.render_widget(paragraph, area);
 }
 
-fn draw_guess_distribution(f: &mut Frame, area: Rect, stats: &crate::ui::history::HistoryStats) {
+fn draw_guess_distribution(
+    f: &mut Frame,
+    area: Rect,
+    history_data: &crate::ui::history::HistoryData,
+    bar: &str,
+) {
+    let stats = &history_data.stats;
     let max_count = *stats.guess_distribution.iter().max().unwrap_or(&1);
 
     let mut lines = vec![Line::from("")];
@@ -162,7 +345,7 @@ fn draw_guess_distribution(f: &mut Frame, area: Rect, stats: &crate::ui::history
             0
         };
 
-        let bar = "█".repeat(bar_width);
+        let bar = bar.repeat(bar_width);
 
         lines.push(Line::from(vec![
             Span::raw(format!("  {} ", guess_num)),
@@ -173,6 +356,28 @@ fn draw_guess_distribution(f: &mut Frame, area: Rect, stats: &crate::ui::history
 
     lines.push(Line::from(""));
 
+    if !history_data.first_guess_green_rates.is_empty() {
+        let rates: Vec<String> = history_data
+            .first_guess_green_rates
+            .iter()
+            .map(|r| format!("{:.0}%", r * 100.0))
+            .collect();
+        lines.push(Line::from(format!(
+            "  First guess green by position: {}",
+            rates.join(" ")
+        )));
+
+        let greens: Vec<String> = history_data
+            .greens_per_guess
+            .iter()
+            .map(|g| format!("{:.1}", g))
+            .collect();
+        lines.push(Line::from(format!(
+            "  Greens per guess: {}",
+            greens.join(" → ")
+        )));
+    }
+
     let paragraph = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
@@ -182,8 +387,269 @@ fn draw_guess_distribution(f: &mut Frame, area: Rect, stats: &crate::ui::history
     f.render_widget(paragraph, area);
 }
 
-fn draw_recent_games(f: &mut Frame, area: Rect, history_data: &crate::ui::history::HistoryData) {
-    let recent_count = 10.min(history_data.games.len());
+/// Most releases listed in the by-version table; the oldest are dropped first.
+const MAX_VERSION_ROWS: usize = 4;
+
+/// Releases that recorded a game or solver session, in the order first seen.
+fn versions(history_data: &HistoryData) -> Vec<Option<&str>> {
+    let mut versions: Vec<Option<&str>> = Vec::new();
+    for average in history_data
+        .game_version_averages
+        .iter()
+        .chain(&history_data.session_version_averages)
+    {
+        let version = average.app_version.as_deref();
+        if !versions.contains(&version) {
+            versions.push(version);
+        }
+    }
+    versions
+}
+
+/// Height of the by-version table, or zero when there is nothing to compare.
+fn version_rows(history_data: &HistoryData) -> usize {
+    match versions(history_data).len() {
+        0 => 0,
+        n => n.min(MAX_VERSION_ROWS) + 4,
+    }
+}
+
+/// Height of the by-difficulty table, or zero until games span two difficulties.
+fn difficulty_rows(history_data: &HistoryData) -> usize {
+    match history_data.difficulty_stats.len() {
+        0 | 1 => 0,
+        n => n + 4,
+    }
+}
+
+/// Height of the by-opener table, or zero until some opener has a row of its own.
+fn opener_rows(history_data: &HistoryData) -> usize {
+    let stats = &history_data.opener_stats;
+    if stats.iter().any(|s| s.key.is_some()) {
+        stats.len() + 4
+    } else {
+        0
+    }
+}
+
+/// Height of the by-boards table, or zero before any multi-board game.
+fn board_rows(history_data: &HistoryData) -> usize {
+    match history_data.multi_board_stats.len() {
+        0 => 0,
+        n => n + 4,
+    }
+}
+
+/// Height of the row holding the by-version, by-difficulty, by-opener and
+/// by-boards tables.
+fn breakdown_rows(history_data: &HistoryData) -> usize {
+    version_rows(history_data)
+        .max(difficulty_rows(history_data))
+        .max(opener_rows(history_data))
+        .max(board_rows(history_data))
+}
+
+type BreakdownTable = fn(&mut Frame, Rect, &HistoryData);
+
+/// Averages by release, if any games or sessions were recorded, beside
+/// results by difficulty once games span more than one, by opener once one
+/// was used often enough, and by boards once a multi-board game was played.
+fn draw_breakdowns(f: &mut Frame, area: Rect, history_data: &HistoryData) {
+    // Each table with its share of the row's width
+    let tables: Vec<(u16, BreakdownTable)> = [
+        (
+            version_rows(history_data),
+            3,
+            draw_version_averages as BreakdownTable,
+        ),
+        (difficulty_rows(history_data), 2, draw_difficulty_stats),
+        (opener_rows(history_data), 2, draw_opener_stats),
+        (board_rows(history_data), 2, draw_board_stats),
+    ]
+    .into_iter()
+    .filter(|&(rows, _, _)| rows > 0)
+    .map(|(_, share, draw)| (share, draw))
+    .collect();
+
+    let areas = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(tables.iter().map(|&(share, _)| Constraint::Fill(share)))
+        .split(area);
+    for (&(_, draw), &area) in tables.iter().zip(areas.iter()) {
+        draw(f, area, history_data);
+    }
+}
+
+fn draw_opener_stats(f: &mut Frame, area: Rect, history_data: &HistoryData) {
+    let rows: Vec<Row> = history_data
+        .opener_stats
+        .iter()
+        .map(|stats| {
+            Row::new(vec![
+                stats.label(),
+                stats.games.to_string(),
+                format!("{:.0}%", stats.win_rate()),
+                stats
+                    .average_guesses
+                    .map_or("-".to_string(), |a| format!("{:.2}", a)),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(6),
+            Constraint::Length(12),
+        ],
+    )
+    .header(
+        Row::new(vec!["Opener", "Games", "Won", "Avg Guesses"])
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .bottom_margin(1),
+    )
+    .block(Block::default().borders(Borders::ALL).title("By Opener"));
+
+    f.render_widget(table, area);
+}
+
+fn draw_difficulty_stats(f: &mut Frame, area: Rect, history_data: &HistoryData) {
+    let rows: Vec<Row> = history_data
+        .difficulty_stats
+        .iter()
+        .map(|stats| {
+            Row::new(vec![
+                stats.key.name().to_string(),
+                stats.games.to_string(),
+                format!("{:.0}%", stats.win_rate()),
+                stats
+                    .average_guesses
+                    .map_or("-".to_string(), |a| format!("{:.2}", a)),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(7),
+            Constraint::Length(6),
+            Constraint::Length(12),
+        ],
+    )
+    .header(
+        Row::new(vec!["Difficulty", "Games", "Won", "Avg Guesses"])
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .bottom_margin(1),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("By Difficulty"),
+    );
+
+    f.render_widget(table, area);
+}
+
+fn draw_board_stats(f: &mut Frame, area: Rect, history_data: &HistoryData) {
+    let rows: Vec<Row> = history_data
+        .multi_board_stats
+        .iter()
+        .map(|stats| {
+            Row::new(vec![
+                stats.key.to_string(),
+                stats.games.to_string(),
+                format!("{:.0}%", stats.win_rate()),
+                stats
+                    .average_guesses
+                    .map_or("-".to_string(), |a| format!("{:.2}", a)),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(6),
+            Constraint::Length(12),
+        ],
+    )
+    .header(
+        Row::new(vec!["Boards", "Games", "Won", "Avg Guesses"])
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .bottom_margin(1),
+    )
+    .block(Block::default().borders(Borders::ALL).title("By Boards"));
+
+    f.render_widget(table, area);
+}
+
+fn draw_version_averages(f: &mut Frame, area: Rect, history_data: &HistoryData) {
+    let versions = versions(history_data);
+    let skip = versions.len().saturating_sub(MAX_VERSION_ROWS);
+    let cells = |averages: &[VersionAverage], version: Option<&str>| {
+        averages
+            .iter()
+            .find(|a| a.app_version.as_deref() == version)
+            .map_or(["-".to_string(), "-".to_string()], |a| {
+                [a.count.to_string(), format!("{:.2}", a.average_guesses)]
+            })
+    };
+
+    let rows: Vec<Row> = versions
+        .into_iter()
+        .skip(skip)
+        .map(|version| {
+            let [wins, game_avg] = cells(&history_data.game_version_averages, version);
+            let [sessions, session_avg] = cells(&history_data.session_version_averages, version);
+            Row::new(vec![
+                version.unwrap_or("unrecorded").to_string(),
+                wins,
+                game_avg,
+                sessions,
+                session_avg,
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(12),
+            Constraint::Length(6),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Length(12),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "Version",
+            "Wins",
+            "Avg Guesses",
+            "Sessions",
+            "Avg Guesses",
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD))
+        .bottom_margin(1),
+    )
+    .block(Block::default().borders(Borders::ALL).title("By Version"));
+
+    f.render_widget(table, area);
+}
+
+fn draw_recent_games(
+    f: &mut Frame,
+    area: Rect,
+    history_data: &crate::ui::history::HistoryData,
+    prefs: &TimePrefs,
+) {
+    let recent_count = super::table_body_rows(area).min(history_data.games.len());
     let recent_games = if recent_count > 0 {
         &history_data.games[history_data.games.len() - recent_count..]
     } else {
@@ -194,29 +660,37 @@ fn draw_recent_games(f: &mut Frame, area: Rect, history_data: &crate::ui::histor
         .iter()
         .rev()
         .map(|game| {
-            let date = game.timestamp.format("%Y-%m-%d %H:%M").to_string();
+            let date = format_local(game.timestamp, prefs);
             let outcome = match game.outcome {
                 crate::ui::history::GameOutcome::Won { guesses } => {
                     format!("Won in {}", guesses)
                 }
                 crate::ui::history::GameOutcome::Lost => "Lost".to_string(),
+                crate::ui::history::GameOutcome::Forfeited => "Gave up".to_string(),
+                crate::ui::history::GameOutcome::Abandoned(reason) => {
+                    format!("Abandoned ({})", reason.as_str())
+                }
             };
 
             let outcome_style = match game.outcome {
                 crate::ui::history::GameOutcome::Won { .. } => Style::default().fg(Color::Green),
                 crate::ui::history::GameOutcome::Lost => Style::default().fg(Color::Red),
+                crate::ui::history::GameOutcome::Forfeited => Style::default().fg(Color::Magenta),
+                crate::ui::history::GameOutcome::Abandoned(_) => {
+                    Style::default().fg(Color::DarkGray)
+                }
             };
 
-            Row::new(vec![date, game.target_word.clone(), outcome]).style(outcome_style)
+            Row::new(vec![date, game.listed_word(), outcome]).style(outcome_style)
         })
         .collect();
 
     let table = Table::new(
         rows,
         [
-            Constraint::Length(16),
+            Constraint::Length(prefs.datetime_width()),
             Constraint::Length(10),
-            Constraint::Length(15),
+            Constraint::Length(20),
         ],
     )
     .header(
@@ -227,7 +701,7 @@ fn draw_recent_games(f: &mut Frame, area: Rect, history_data: &crate::ui::histor
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .title("Recent Games (Latest 10)"),
+            .title(format!("Recent Games (Latest {})", recent_count)),
     );
 
     f.render_widget(table, area);
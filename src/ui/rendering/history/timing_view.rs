@@ -0,0 +1,95 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::ui::{
+    App,
+    history::timing::{BucketStats, DayPart, MIN_BUCKET_GAMES},
+};
+
+/// Width of a bar for a 100% win rate.
+const BAR_WIDTH: f64 = 20.0;
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+impl App {
+    /// Win rate and average guesses by local time of day beside the same by weekday.
+    pub(in crate::ui) fn draw_timing_view(&self, f: &mut Frame, area: Rect) {
+        let Some(ref history_data) = self.history_data else {
+            return;
+        };
+        let timing = &history_data.timing;
+        let bar = self.bar_glyph();
+
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Fill(1), Constraint::Fill(1)])
+            .split(area);
+
+        let parts = DayPart::ALL
+            .iter()
+            .zip(&timing.day_parts)
+            .map(|(part, stats)| bucket_line(part.label(), stats, bar));
+        draw_chart(f, chunks[0], "By Time of Day", parts.collect());
+
+        let days = WEEKDAYS
+            .iter()
+            .zip(&timing.weekdays)
+            .map(|(day, stats)| bucket_line(day, stats, bar));
+        draw_chart(f, chunks[1], "By Day of Week", days.collect());
+    }
+}
+
+/// One bar of a chart: the win rate drawn to scale, then the figures, or
+/// n/a for a bucket with too few games to go by.
+fn bucket_line(label: &str, stats: &BucketStats, bar: &str) -> Line<'static> {
+    let label = Span::raw(format!("  {:<10}", label));
+    let Some(win_rate) = stats.win_rate() else {
+        return Line::from(vec![
+            label,
+            Span::styled(
+                format!("n/a ({} of {} games)", stats.games, MIN_BUCKET_GAMES),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]);
+    };
+    let width = (win_rate / 100.0 * BAR_WIDTH).round() as usize;
+    let average = stats
+        .average_guesses()
+        .map_or("-".to_string(), |a| format!("{:.2}", a));
+    Line::from(vec![
+        label,
+        Span::styled(
+            format!("{:<20}", bar.repeat(width)),
+            Style::default().fg(Color::Green),
+        ),
+        Span::raw(format!(
+            " {:>3.0}% won  {} avg  ({} games)",
+            win_rate, average, stats.games
+        )),
+    ])
+}
+
+fn draw_chart(f: &mut Frame, area: Rect, title: &str, bars: Vec<Line<'static>>) {
+    let mut lines = vec![Line::from("")];
+    for bar in bars {
+        lines.push(bar);
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(Span::styled(
+        "  Local time; win rate drawn, guesses averaged over wins",
+        Style::default().fg(Color::DarkGray),
+    )));
+    f.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title.to_string()),
+        ),
+        area,
+    );
+}
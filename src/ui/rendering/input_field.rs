@@ -4,31 +4,49 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::ui::{
-    app::App,
-    types::{GameMode, InputStatus},
+use crate::{
+    settings::Difficulty,
+    solver::{Feedback, matches_bytes},
+    ui::{
+        app::App,
+        types::{FocusTarget, GameMode, InputMode, InputStatus},
+    },
 };
 
 impl App {
     pub(in crate::ui) fn draw_input(&self, f: &mut Frame, area: ratatui::layout::Rect) {
         let status = self.input_status_immutable();
 
-        let (border_color, subtitle) = match status {
-            InputStatus::Incomplete => (Color::Gray, ""),
-            InputStatus::Valid => (Color::Green, ""),
-            InputStatus::Invalid(msg) => (Color::Red, msg),
+        let (border_color, subtitle) = match (&self.paste_error, status) {
+            (Some(error), _) => (Color::Red, format!("paste {}", error)),
+            (None, InputStatus::Incomplete) => (Color::Gray, String::new()),
+            (None, InputStatus::Valid) if self.pending_submit.is_some() => {
+                (Color::Green, "submitting, Backspace cancels".to_string())
+            }
+            (None, InputStatus::Valid) => (Color::Green, String::new()),
+            (None, InputStatus::Invalid(msg)) => (Color::Red, msg),
         };
 
-        let text = format!("{}▌", self.input);
+        let normal = self.settings.modal_input && self.input_mode == InputMode::Normal;
+        let (border_color, badge) = match (self.settings.modal_input, normal) {
+            (false, _) => (border_color, ""),
+            (true, false) => (border_color, "[INSERT] "),
+            (true, true) => (Color::Blue, "[NORMAL] "),
+        };
 
-        let help_text = if self.mode == GameMode::Game {
-            if self.game_over {
-                "Enter = new game | Ctrl+S = solver | Ctrl+R = history | Ctrl+Q = quit"
-            } else {
-                "Enter = submit | Ctrl+H = hints | Ctrl+A = analysis | Ctrl+S = solver | Ctrl+R = history | Ctrl+Q = quit"
-            }
+        // Dimmed while another panel has the keys
+        let focused = self.focused() == FocusTarget::Input;
+        let border_color = if focused {
+            border_color
         } else {
-            "Enter = submit | Ctrl+G = game | Ctrl+Z = undo | Ctrl+R = history | Ctrl+Q = quit"
+            Color::DarkGray
+        };
+
+        // No cursor while letters are commands or go elsewhere
+        let text = if normal || !focused {
+            self.input.clone()
+        } else {
+            format!("{}▌", self.input)
         };
 
         f.render_widget(
@@ -36,7 +54,7 @@ impl App {
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(border_color))
-                    .title(format!("Input {} | {}", subtitle, help_text)),
+                    .title(format!("{}Input {}", badge, subtitle)),
             ),
             area,
         );
@@ -44,23 +62,49 @@ impl App {
 
     // Helper method that doesn't require &mut
     pub(in crate::ui) fn input_status_immutable(&self) -> InputStatus {
-        use crate::solver::parse_pattern;
+        self.check_input(true)
+    }
 
-        if self.mode == GameMode::Game {
+    /// [`input_status_immutable`](Self::input_status_immutable) without the
+    /// check that some allowed word fits the pattern, for Ctrl+Enter or Alt+Enter.
+    pub(in crate::ui) fn input_status_forced(&self) -> InputStatus {
+        self.check_input(false)
+    }
+
+    fn check_input(&self, check_fit: bool) -> InputStatus {
+        use crate::solver::{
+            check_hard_mode, check_len, check_pattern, check_phrase, check_word, parse_pattern,
+        };
+
+        let word_len = self.solver.word_len();
+
+        if !self.expects_pattern() {
             let guess = self.input.trim();
 
             if guess.is_empty() {
                 return InputStatus::Incomplete;
             }
 
-            if guess.len() != self.solver.word_len() {
-                return InputStatus::Invalid("guess length mismatch");
+            let checked = if self.solver.segments().len() > 1 {
+                check_phrase(
+                    &guess.to_lowercase(),
+                    self.solver.segments(),
+                    &self.allowed_lookup,
+                )
+            } else {
+                check_word(&guess.to_lowercase(), word_len, &self.allowed_lookup)
+            };
+            if let Err(e) = checked {
+                return InputStatus::Invalid(format!("guess {}", e));
             }
-
-            let guess_lower = guess.to_lowercase();
-
-            if !self.allowed_lookup.contains(&guess_lower) {
-                return InputStatus::Invalid("word not in allowed list");
+            let letters: String = guess.to_lowercase().split_whitespace().collect();
+            if self.game_difficulty == Difficulty::Expert
+                && let Err(e) = check_hard_mode(&letters, self.solver.guesses())
+            {
+                return InputStatus::Invalid(e.to_string());
+            }
+            if self.refuses_repeat(&letters) {
+                return InputStatus::Invalid("already guessed".to_string());
             }
 
             return InputStatus::Valid;
@@ -77,26 +121,69 @@ impl App {
         }
 
         if parts.len() > 2 {
-            return InputStatus::Invalid("too many fields");
+            return InputStatus::Invalid("too many fields".to_string());
         }
 
         let guess = parts[0];
         let pattern = parts[1];
 
-        if guess.len() != self.solver.word_len() {
-            return InputStatus::Invalid("guess length mismatch");
-        } else if !self.allowed_lookup.contains(&guess.to_lowercase()) {
-            return InputStatus::Invalid("word not in allowed list");
+        if let Err(e) = check_word(&guess.to_lowercase(), word_len, &self.allowed_lookup) {
+            return InputStatus::Invalid(format!("guess {}", e));
         }
-
-        if pattern.len() != self.solver.word_len() {
-            return InputStatus::Invalid("pattern length mismatch");
+        if self.refuses_repeat(&guess.to_lowercase()) {
+            return InputStatus::Invalid("already guessed".to_string());
+        }
+        if self.mode == GameMode::Game
+            && self.game_difficulty == Difficulty::Expert
+            && let Err(e) = check_hard_mode(&guess.to_lowercase(), self.solver.guesses())
+        {
+            return InputStatus::Invalid(e.to_string());
         }
 
-        if parse_pattern(pattern).is_err() {
-            return InputStatus::Invalid("pattern must be G/Y/X");
+        let feedback = match check_len(pattern.chars().count(), word_len)
+            .and_then(|_| parse_pattern(pattern))
+        {
+            Ok(feedback) => feedback,
+            Err(e) => return InputStatus::Invalid(format!("pattern {}", e)),
+        };
+        // No word at all could give it, so Ctrl+Enter can't force it either
+        if let Err(e) = check_pattern(&guess.to_lowercase(), &feedback) {
+            return InputStatus::Invalid(e.to_string());
+        }
+        // Almost certainly a typo, and it would empty the pool for good
+        if check_fit && !self.pattern_fits(&guess.to_lowercase(), &feedback) {
+            return InputStatus::Invalid("no word matches this pattern — typo?".to_string());
         }
 
         InputStatus::Valid
     }
+
+    /// Whether any allowed word would give `feedback` for `guess`, remembered
+    /// for the last guess and pattern so redraws don't scan the list again.
+    fn pattern_fits(&self, guess: &str, feedback: &[Feedback]) -> bool {
+        if let Some((last_guess, last_feedback, fits)) = &*self.pattern_fit.borrow()
+            && last_guess == guess
+            && last_feedback == feedback
+        {
+            return *fits;
+        }
+
+        let table = &self.allowed_lookup;
+        let fits = table
+            .indices()
+            .any(|i| matches_bytes(table.bytes(i), guess.as_bytes(), feedback));
+        *self.pattern_fit.borrow_mut() = Some((guess.to_string(), feedback.to_vec(), fits));
+        fits
+    }
+
+    /// Whether `letters` were already guessed in this game or session; an
+    /// undone guess no longer counts.
+    pub(in crate::ui) fn repeats_guess(&self, letters: &str) -> bool {
+        self.solver.guesses().iter().any(|g| g.word == letters)
+    }
+
+    /// A repeat is refused outright unless the settings ask for a prompt instead.
+    fn refuses_repeat(&self, letters: &str) -> bool {
+        !self.settings.confirm_repeat_guess && self.repeats_guess(letters)
+    }
 }
@@ -1,26 +1,115 @@
 use ratatui::{
     Frame,
-    text::Line,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::ui::app::App;
+use crate::ui::{
+    app::App,
+    types::{FocusTarget, GameMode, LogSearch},
+};
 
 impl App {
     pub(in crate::ui) fn draw_logs(&self, f: &mut Frame, area: ratatui::layout::Rect) {
         let logs = self.logs.lines();
 
-        let height = area.height as usize;
-        let start = logs.len().saturating_sub(height);
+        let rows = area.height.saturating_sub(2) as usize;
+        self.log_rows.set(rows);
+        let hidden = logs.len().saturating_sub(rows);
+        let start = hidden - self.log_scroll.min(hidden);
 
+        let search = self.log_search.as_ref();
         let lines: Vec<Line> = logs[start..]
             .iter()
-            .map(|l| Line::from(l.clone()))
+            .take(rows)
+            .enumerate()
+            .map(|(i, l)| match search {
+                Some(search) if search.matches(l) => {
+                    highlight(l, search, search.current == Some(start + i))
+                }
+                _ => Line::from(l.clone()),
+            })
             .collect();
 
+        let focused = self.focused() == FocusTarget::Logs;
+        let mut title = "Logs".to_string();
+        if start < hidden {
+            title.push_str(&format!(" ({} newer below)", hidden - start));
+        }
+        match search {
+            Some(search) if search.editing => title.push_str(&format!(" | /{}▏", search.query)),
+            Some(search) => {
+                let found = search.matching_lines(&logs);
+                let place = search
+                    .current
+                    .and_then(|c| found.iter().position(|&i| i == c));
+                match place {
+                    Some(n) => title.push_str(&format!(
+                        " | /{}: {} of {}",
+                        search.query,
+                        n + 1,
+                        found.len()
+                    )),
+                    None => title.push_str(&format!(" | /{}: no matches", search.query)),
+                }
+            }
+            None if focused && self.mode == GameMode::History => {
+                title.push_str(" | ↑↓ PgUp/PgDn: scroll · /: search · L: size · Esc: done")
+            }
+            None if focused => title.push_str(" | ↑↓ PgUp/PgDn: scroll · Esc: done"),
+            None => {}
+        }
+        let border_style = if focused {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default()
+        };
+
         f.render_widget(
-            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Logs")),
+            Paragraph::new(lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(border_style)
+                    .title(title),
+            ),
             area,
         );
     }
 }
+
+/// `line` with each match of `search` picked out, in bold throughout if it is
+/// the match jumped to.
+fn highlight(line: &str, search: &LogSearch, current: bool) -> Line<'static> {
+    let base = if current {
+        Style::default().add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    let found =
+        Style::default()
+            .fg(Color::Black)
+            .bg(if current { Color::Cyan } else { Color::Yellow });
+
+    let lower = line.to_lowercase();
+    // Lowercasing changed where the characters fall, so mark the whole line
+    if lower.len() != line.len() {
+        return Line::styled(line.to_string(), base.patch(found));
+    }
+    let query = search.query.to_lowercase();
+    let mut spans = Vec::new();
+    let mut rest = 0;
+    for (at, _) in lower.match_indices(&query) {
+        if at < rest {
+            continue;
+        }
+        spans.push(Span::styled(line[rest..at].to_string(), base));
+        spans.push(Span::styled(
+            line[at..at + query.len()].to_string(),
+            base.patch(found),
+        ));
+        rest = at + query.len();
+    }
+    spans.push(Span::styled(line[rest..].to_string(), base));
+    Line::from(spans)
+}
@@ -1,50 +1,93 @@
 pub mod analysis;
+pub mod entropy_chart;
 mod guesses;
-mod history;
+pub(in crate::ui) mod history;
 mod input_field;
 mod logs;
+mod overlay;
 mod status;
 mod suggestions;
 
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
 };
 
-use crate::ui::{app::App, types::GameMode};
+use crate::{
+    settings::PanelKind,
+    ui::{
+        app::App,
+        types::{GameMode, PanelKey},
+    },
+};
 
 impl App {
+    /// What the cached lines of `panel` drawn in `area` depend on.
+    pub(in crate::ui) fn panel_key(&self, panel: PanelKind, area: Rect) -> PanelKey {
+        PanelKey {
+            analysis: self.analysis_version,
+            preview: if panel == PanelKind::Pool {
+                self.preview_version
+            } else {
+                0
+            },
+            area,
+            plain: self.low_refresh(),
+        }
+    }
+
     pub(in crate::ui) fn draw(&self, f: &mut Frame) {
+        // Every layout keeps the bottom row for the status bar
+        let [screen, status_bar] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(f.area());
+        self.draw_status_bar(f, status_bar);
+
         // History mode uses a different layout
         if self.mode == GameMode::History {
+            if self.history_logs_expanded {
+                self.draw_logs(f, screen);
+                self.draw_overlay(f);
+                return;
+            }
             let layout = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
                     Constraint::Min(10),   // History content
                     Constraint::Length(6), // Small log panel
                 ])
-                .split(f.area());
+                .split(screen);
 
             self.draw_history_mode(f, layout[0]);
             self.draw_logs(f, layout[1]);
+            self.draw_overlay(f);
             return;
         }
 
         // Regular Game/Solver layout
         // Determine if we should show analysis panels (always in Solver, toggle in Game)
-        let show_analysis_panel = self.mode == GameMode::Solver || self.show_analysis;
+        let show_analysis_panel = (self.mode == GameMode::Solver || self.show_analysis)
+            && !self.settings.analysis_panels.is_empty();
 
         let main_layout = if show_analysis_panel {
             Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
-                .split(f.area())
+                .split(screen)
         } else {
             // In Game mode with analysis hidden, use full width for game area
             Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints([Constraint::Percentage(100)])
-                .split(f.area())
+                .split(screen)
+        };
+
+        let left_column = if self.settings.quick_stats {
+            let [quick_stats, rest] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(main_layout[0]);
+            self.draw_quick_stats(f, quick_stats);
+            rest
+        } else {
+            main_layout[0]
         };
 
         // Dynamically adjust left layout based on whether suggestions should be shown
@@ -54,21 +97,21 @@ impl App {
             Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Length(3),
-                    Constraint::Length(8),
+                    Constraint::Length(self.game_status_height()),
+                    Constraint::Length(self.guess_panel_height()),
                     Constraint::Min(5),
                     Constraint::Length(3),
                 ])
-                .split(main_layout[0])
+                .split(left_column)
         } else {
             Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Length(3),
-                    Constraint::Length(8),
+                    Constraint::Length(self.game_status_height()),
+                    Constraint::Length(self.guess_panel_height()),
                     Constraint::Length(3),
                 ])
-                .split(main_layout[0])
+                .split(left_column)
         };
 
         if self.mode == GameMode::Game {
@@ -88,22 +131,50 @@ impl App {
 
         // Only draw analysis panels if enabled
         if show_analysis_panel {
+            let panels = &self.settings.analysis_panels;
             let right_layout = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(8),
-                    Constraint::Length(9),
-                    Constraint::Length(8),
-                    Constraint::Length(12),
-                    Constraint::Min(6), // logs panel
-                ])
+                .constraints(analysis_constraints(panels))
                 .split(main_layout[1]);
 
-            self.draw_letter_analysis(f, right_layout[0]);
-            self.draw_position_analysis(f, right_layout[1]);
-            self.draw_constraint_summary(f, right_layout[2]);
-            self.draw_solution_pool(f, right_layout[3]);
-            self.draw_logs(f, right_layout[4]);
+            for (&panel, &area) in panels.iter().zip(right_layout.iter()) {
+                match panel {
+                    PanelKind::Letters => self.draw_letter_analysis(f, area),
+                    PanelKind::Positions => self.draw_position_analysis(f, area),
+                    PanelKind::Constraints => self.draw_constraint_summary(f, area),
+                    PanelKind::Pool => self.draw_solution_pool(f, area),
+                    PanelKind::Logs => self.draw_logs(f, area),
+                }
+            }
         }
+
+        self.draw_toast(f);
+        self.draw_overlay(f);
     }
 }
+
+/// Heights for the visible analysis panels, top to bottom.
+///
+/// The logs panel takes whatever space is left; without it, the bottom
+/// panel does.
+pub(in crate::ui) fn analysis_constraints(panels: &[PanelKind]) -> Vec<Constraint> {
+    let has_logs = panels.contains(&PanelKind::Logs);
+    panels
+        .iter()
+        .enumerate()
+        .map(|(i, &panel)| {
+            let height = match panel {
+                PanelKind::Letters => 8,
+                PanelKind::Positions => 9,
+                PanelKind::Constraints => 8,
+                PanelKind::Pool => 12,
+                PanelKind::Logs => return Constraint::Min(6),
+            };
+            if !has_logs && i == panels.len() - 1 {
+                Constraint::Min(height)
+            } else {
+                Constraint::Length(height)
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,690 @@
+use std::time::Instant;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    local_time::{TimePrefs, format_local, format_local_time},
+    scoring::{LetterStatus, ScoreExplanation, pattern_string},
+    settings::PanelKind,
+    solver::{Feedback, Guess, explain_elimination, join_segments, matches},
+    storage::ResumableSession,
+    ui::{
+        app::App,
+        handlers::LayoutHandler,
+        history::{GameOutcome, HistoryViewMode},
+        system_status::SystemStatus,
+        types::{
+            DICTIONARY_ROWS, DictionarySearchState, EditGuessState, GameMode, GauntletSummaryState,
+            Overlay, SnapshotState, SnapshotStep, UiJournalEntry, VerifyAnswerState,
+        },
+    },
+};
+
+impl App {
+    /// The hint toast, along the top edge, until it runs out.
+    pub(in crate::ui) fn draw_toast(&self, f: &mut Frame) {
+        let Some(toast) = &self.toast else {
+            return;
+        };
+        if self.mode != GameMode::Game || !toast.visible(Instant::now()) {
+            return;
+        }
+
+        let area = f.area();
+        let width = (toast.text.chars().count() as u16 + 4).min(area.width);
+        let [area] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(Rect {
+                height: 3.min(area.height),
+                ..area
+            });
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(format!(" {}", toast.text)).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Yellow))
+                    .title("Hint"),
+            ),
+            area,
+        );
+    }
+
+    pub(in crate::ui) fn draw_overlay(&self, f: &mut Frame) {
+        let Some(overlay) = &self.overlay else {
+            return;
+        };
+
+        match overlay {
+            Overlay::ScoreExplanation(explanation) => {
+                let lines = score_explanation_lines(explanation);
+                let area = centered_rect(f.area(), 50, lines.len() as u16 + 2);
+                draw_popup(f, area, "Score Breakdown | any key: close", lines);
+            }
+            Overlay::EditGuess(state) => {
+                let lines = edit_guess_lines(self.solver.guesses(), state);
+                let area = centered_rect(f.area(), 56, lines.len() as u16 + 2);
+                let title = if state.pattern.is_some() {
+                    "Edit Guess | G/Y/X: pattern | Enter: apply | Esc: back"
+                } else {
+                    "Edit Guess | ↑/↓: choose | Enter: edit | Esc: close"
+                };
+                draw_popup(f, area, title, lines);
+            }
+            Overlay::ConfirmDiscard(state) => {
+                let lines = vec![Line::from(""), Line::from(format!(" {}", state.prompt))];
+                let area = centered_rect(f.area(), state.prompt.chars().count() as u16 + 4, 4);
+                draw_popup(f, area, "Confirm | y: abandon | n/Esc: keep", lines);
+            }
+            Overlay::ConfirmRepeat { word } => {
+                let prompt = format!(
+                    "{} was already guessed — submit it again? y/N",
+                    word.to_uppercase()
+                );
+                let lines = vec![Line::from(""), Line::from(format!(" {}", prompt))];
+                let area = centered_rect(f.area(), prompt.chars().count() as u16 + 4, 4);
+                draw_popup(f, area, "Confirm | y: submit | n/Esc: edit", lines);
+            }
+            Overlay::ResumeSession(session) => {
+                let lines = resume_session_lines(session, &self.time_prefs());
+                let area = centered_rect(f.area(), 54, lines.len() as u16 + 2);
+                draw_popup(
+                    f,
+                    area,
+                    "Resume | y/Enter: resume | n/Esc: start fresh",
+                    lines,
+                );
+            }
+            Overlay::RestoreUiState(entry) => {
+                let lines = restore_ui_state_lines(entry, &self.time_prefs());
+                let area = centered_rect(f.area(), 54, lines.len() as u16 + 2);
+                draw_popup(
+                    f,
+                    area,
+                    "Restore | y/Enter: restore | n/Esc: start fresh",
+                    lines,
+                );
+            }
+            Overlay::GuessNote { text } => {
+                let word = self
+                    .solver
+                    .guesses()
+                    .last()
+                    .map(|g| join_segments(&g.word, self.solver.segments()).to_uppercase())
+                    .unwrap_or_default();
+                let lines = vec![Line::from(""), Line::from(format!(" {}: {}_", word, text))];
+                let area = centered_rect(f.area(), 56, 4);
+                draw_popup(f, area, "Note | Enter: save | Esc: cancel", lines);
+            }
+            Overlay::Blacklist { selected } => {
+                let lines: Vec<Line> = self
+                    .session_blacklist
+                    .iter()
+                    .enumerate()
+                    .map(|(i, word)| {
+                        let marker = if i == *selected { "▶ " } else { "  " };
+                        Line::from(format!("{}{}", marker, word.to_uppercase()))
+                    })
+                    .collect();
+                let area = centered_rect(f.area(), 44, lines.len() as u16 + 2);
+                draw_popup(
+                    f,
+                    area,
+                    "Blacklist | ↑/↓: choose | Enter: remove | Esc: close",
+                    lines,
+                );
+            }
+            Overlay::Snapshots(state) => {
+                let lines = snapshot_lines(state, self.solver.guesses().len(), &self.time_prefs());
+                let area = centered_rect(f.area(), 60, lines.len() as u16 + 2);
+                let title = match state.step {
+                    SnapshotStep::Browse => {
+                        "Snapshots | ↑/↓: choose | Enter: load | s: save | d: delete | Esc: close"
+                    }
+                    SnapshotStep::Naming(_) => "Save Snapshot | Enter: save | Esc: back",
+                    SnapshotStep::ConfirmLoad => "Load Snapshot | y: load | n/Esc: back",
+                };
+                draw_popup(f, area, title, lines);
+            }
+            Overlay::VerifyAnswer(state) => {
+                let lines = verify_answer_lines(state, self.solver.word_len());
+                let area = centered_rect(f.area(), 48, lines.len() as u16 + 2);
+                let title = if state.answer.is_some() {
+                    "Actual Answer | Enter: record | Esc: back"
+                } else {
+                    "Session Solved | y: yes | n: no | Esc: skip"
+                };
+                draw_popup(f, area, title, lines);
+            }
+            Overlay::PanelLayout { selected } => {
+                let lines = panel_layout_lines(&self.settings.analysis_panels, *selected);
+                let area = centered_rect(f.area(), 66, lines.len() as u16 + 2);
+                draw_popup(
+                    f,
+                    area,
+                    "Panels | Tab: choose | ↑/↓: move | Space: show/hide | Esc: done",
+                    lines,
+                );
+            }
+            Overlay::DictionarySearch(state) => {
+                let lines = self.dictionary_lines(state);
+                let area = centered_rect(f.area(), 52, lines.len() as u16 + 2);
+                draw_popup(
+                    f,
+                    area,
+                    "Dictionary | ↑/↓: choose | Enter: use | Esc: close",
+                    lines,
+                );
+            }
+            Overlay::About(status) => {
+                let lines = about_lines(status);
+                let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 2;
+                let area = centered_rect(f.area(), width.max(40), lines.len() as u16 + 2);
+                draw_popup(f, area, "About | b: bug report | any key: close", lines);
+            }
+            Overlay::GauntletSummary(state) => {
+                let lines = gauntlet_summary_lines(state);
+                let area = centered_rect(f.area(), 60, lines.len() as u16 + 2);
+                draw_popup(
+                    f,
+                    area,
+                    "Gauntlet | ↑/↓: choose | Enter: view game | Esc: close",
+                    lines,
+                );
+            }
+        }
+    }
+
+    /// Why the highlighted word is or isn't still in the solver's pool: the
+    /// first guess it fails and the rule it breaks there.
+    fn elimination_lines(&self, word: &str) -> Vec<Line<'static>> {
+        let style = Style::default().fg(Color::Cyan);
+        let mut lines = match explain_elimination(word, self.solver.guesses()) {
+            Some(reason) => vec![
+                format!(
+                    " {} fails guess {} ({}):",
+                    word.to_uppercase(),
+                    reason.guess_index + 1,
+                    reason.guess.to_uppercase()
+                ),
+                format!("   {}", reason.failure.describe().unwrap_or_default()),
+            ],
+            None if self.solution_words.contains(word) => {
+                vec![format!(" {} is still possible", word.to_uppercase())]
+            }
+            None => vec![format!(
+                " {} fits every guess but isn't an answer",
+                word.to_uppercase()
+            )],
+        }
+        .into_iter()
+        .map(|line| Line::from(Span::styled(line, style)))
+        .collect::<Vec<_>>();
+        lines.push(Line::from(""));
+        lines
+    }
+
+    /// The query, a window of matches around the highlighted one, and the count.
+    ///
+    /// Each match is marked if it's a possible answer and, during a solver
+    /// session, whether the guesses so far still allow it.
+    fn dictionary_lines(&self, state: &DictionarySearchState) -> Vec<Line<'static>> {
+        let show_pool = self.mode == GameMode::Solver && self.solver_session_active;
+        let start = (state.selected + 1).saturating_sub(DICTIONARY_ROWS);
+        let end = (start + DICTIONARY_ROWS).min(state.hits.len());
+
+        let mut lines = vec![
+            Line::from(format!(" Search: {}▌", state.query)),
+            Line::from(""),
+        ];
+        for (i, &index) in state.hits[start..end].iter().enumerate() {
+            let word = self.allowed_lookup.word(index);
+            let marker = if start + i == state.selected {
+                "▶ "
+            } else {
+                "  "
+            };
+            let answer = self.solution_words.contains(word);
+            let mut spans = vec![
+                Span::styled(
+                    format!("{}{}  ", marker, word.to_uppercase()),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                if answer {
+                    Span::styled("answer    ", Style::default().fg(Color::Green))
+                } else {
+                    Span::styled("guess only", Style::default().fg(Color::DarkGray))
+                },
+            ];
+            if show_pool {
+                let in_pool = answer
+                    && self
+                        .solver
+                        .guesses()
+                        .iter()
+                        .all(|g| matches(word, &g.word, &g.feedback));
+                spans.push(if in_pool {
+                    Span::styled("  in pool", Style::default().fg(Color::Cyan))
+                } else {
+                    Span::styled("  ruled out", Style::default().fg(Color::DarkGray))
+                });
+            }
+            lines.push(Line::from(spans));
+        }
+
+        if !state.hits.is_empty() {
+            lines.push(Line::from(""));
+        }
+        if show_pool
+            && !self.solver.guesses().is_empty()
+            && let Some(&index) = state.hits.get(state.selected)
+        {
+            lines.extend(self.elimination_lines(self.allowed_lookup.word(index)));
+        }
+        let plural = if state.hits.len() == 1 { "" } else { "es" };
+        lines.push(Line::from(Span::styled(
+            match state.hits.len() {
+                _ if state.query.is_empty() => {
+                    " Type a word or prefix; _ is one letter, * any run".to_string()
+                }
+                0 => " No matches".to_string(),
+                n if n > DICTIONARY_ROWS => {
+                    format!(" {} match{} ({}-{} shown)", n, plural, start + 1, end)
+                }
+                n => format!(" {} match{}", n, plural),
+            },
+            Style::default().fg(Color::DarkGray),
+        )));
+        lines
+    }
+}
+
+fn panel_layout_lines(panels: &[PanelKind], selected: usize) -> Vec<Line<'static>> {
+    LayoutHandler::entries(panels)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (panel, visible))| {
+            let marker = if i == selected { "▶ " } else { "  " };
+            if visible {
+                Line::from(format!("{}{}. {}", marker, i + 1, panel.name()))
+            } else {
+                Line::from(Span::styled(
+                    format!("{}   {} (hidden)", marker, panel.name()),
+                    Style::default().fg(Color::DarkGray),
+                ))
+            }
+        })
+        .collect()
+}
+
+/// Center a box of at most `width` x `height` cells inside `area`.
+pub(in crate::ui) fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let [row] = Layout::vertical([Constraint::Length(height.min(area.height))])
+        .flex(Flex::Center)
+        .areas(area);
+    let [rect] = Layout::horizontal([Constraint::Length(width.min(area.width))])
+        .flex(Flex::Center)
+        .areas(row);
+    rect
+}
+
+fn draw_popup(f: &mut Frame, area: Rect, title: &str, lines: Vec<Line>) {
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(title.to_string()),
+        ),
+        area,
+    );
+}
+
+fn score_explanation_lines(explanation: &ScoreExplanation) -> Vec<Line<'static>> {
+    let kind = if explanation.is_candidate {
+        Span::styled("candidate", Style::default().fg(Color::Green))
+    } else {
+        Span::styled(
+            "probe (not a possible answer)",
+            Style::default().fg(Color::Yellow),
+        )
+    };
+
+    let letter_total = explanation.total - explanation.solution_bonus;
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                explanation.word.to_uppercase(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" — "),
+            kind,
+        ]),
+        Line::from(format!(
+            "Score: {} (letters {} + solution bonus {})",
+            explanation.total, letter_total, explanation.solution_bonus
+        )),
+    ];
+    if let Some(prior) = explanation.prior {
+        lines.push(Line::from(format!(
+            "Answer prior: ×{:.2} → {}",
+            prior,
+            (explanation.total as f64 * prior).round() as usize
+        )));
+    }
+    lines.push(Line::from(""));
+
+    for contribution in &explanation.letters {
+        let (label, color) = match contribution.status {
+            LetterStatus::Green => ("known green", Color::Green),
+            LetterStatus::Yellow => ("known yellow", Color::Yellow),
+            LetterStatus::Gray => ("known gray", Color::DarkGray),
+            LetterStatus::Unknown => ("untested", Color::White),
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!(
+                "  {} +{:<5}",
+                contribution.letter.to_ascii_uppercase(),
+                contribution.frequency
+            )),
+            Span::styled(label, Style::default().fg(color)),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(match &explanation.likely_feedback {
+        Some((pattern, size)) => format!(
+            "Most likely feedback: {} → {} left",
+            pattern_string(pattern),
+            size
+        ),
+        None => "Most likely feedback: — (empty pool)".to_string(),
+    }));
+
+    lines
+}
+
+fn edit_guess_lines(guesses: &[Guess], state: &EditGuessState) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line> = guesses
+        .iter()
+        .enumerate()
+        .map(|(i, guess)| {
+            let selected = i == state.selected;
+            let marker = if selected { "▶ " } else { "  " };
+            let mut spans = vec![Span::raw(format!("{}{}. ", marker, i + 1))];
+            spans.extend(guess.word.chars().zip(&guess.feedback).map(|(c, fb)| {
+                let color = match fb {
+                    Feedback::Green => Color::Green,
+                    Feedback::Yellow => Color::Yellow,
+                    Feedback::Gray => Color::DarkGray,
+                };
+                Span::styled(
+                    c.to_ascii_uppercase().to_string(),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                )
+            }));
+            Line::from(spans)
+        })
+        .collect();
+
+    if let (Some(pattern), Some(guess)) = (&state.pattern, guesses.get(state.selected)) {
+        let blanks = "_".repeat(guess.word.len().saturating_sub(pattern.len()));
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!(
+            "New pattern for {}: {}{}",
+            guess.word.to_uppercase(),
+            pattern,
+            blanks
+        )));
+    }
+
+    lines
+}
+
+/// When the session started, then each guess coloured by its pattern.
+fn resume_session_lines(session: &ResumableSession, prefs: &TimePrefs) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(format!(
+            " Solver session from {} was left unfinished",
+            format_local(session.timestamp, prefs)
+        )),
+        Line::from(""),
+    ];
+    for (i, guess) in session.guesses.iter().enumerate() {
+        let mut spans = vec![Span::raw(format!("  {}. ", i + 1))];
+        let feedback = guess.feedback.as_deref().unwrap_or_default();
+        spans.extend(guess.word.chars().zip(feedback).map(|(c, fb)| {
+            let color = match fb {
+                Feedback::Green => Color::Green,
+                Feedback::Yellow => Color::Yellow,
+                Feedback::Gray => Color::DarkGray,
+            };
+            Span::styled(
+                c.to_ascii_uppercase().to_string(),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            )
+        }));
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(" Resume it? Y/n"));
+    lines
+}
+
+fn restore_ui_state_lines(entry: &UiJournalEntry, prefs: &TimePrefs) -> Vec<Line<'static>> {
+    let state = &entry.state;
+    let saved = chrono::DateTime::parse_from_rfc3339(&entry.saved_at)
+        .map(|t| format_local_time(t.to_utc(), prefs))
+        .unwrap_or_default();
+    let mode = match state.mode {
+        GameMode::Solver => "Solver",
+        GameMode::Game => "Game",
+        GameMode::History => "History",
+    };
+    let mut lines = vec![
+        Line::from(format!(" The last run left off at {}", saved)),
+        Line::from(""),
+        Line::from(format!("  Mode: {}", mode)),
+    ];
+    if state.mode == GameMode::History {
+        let view = match state.history_view_mode {
+            HistoryViewMode::Stats => "stats".to_string(),
+            HistoryViewMode::List => format!("list, page {}", state.history_page + 1),
+            HistoryViewMode::Detail => "game detail".to_string(),
+            HistoryViewMode::Solver => "solver stats".to_string(),
+            HistoryViewMode::Compare => "comparison".to_string(),
+        };
+        lines.push(Line::from(format!("  View: {}", view)));
+    }
+    if !state.input.is_empty() {
+        lines.push(Line::from(format!("  Typed: {}", state.input)));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(" Pick up where you left off? Y/n"));
+    lines
+}
+
+/// The version, where each file lives, and whether history is being saved.
+fn about_lines(status: &SystemStatus) -> Vec<Line<'static>> {
+    let shown = |path: &Option<std::path::PathBuf>| {
+        path.as_ref()
+            .map_or("—".to_string(), |p| p.display().to_string())
+    };
+    let count = |n: Option<usize>| n.map_or("?".to_string(), |n| n.to_string());
+
+    let database = match status.database_bytes {
+        Some(bytes) => format!(
+            "{} ({:.1} KiB)",
+            shown(&status.database),
+            bytes as f64 / 1024.0
+        ),
+        None => shown(&status.database),
+    };
+    let (saving, color) = if status.in_memory() {
+        (
+            "in memory only; nothing is kept after exit".to_string(),
+            Color::Red,
+        )
+    } else if status.pending_writes > 0 {
+        (
+            format!(
+                "{} write(s) refused, retried at the next start",
+                status.pending_writes
+            ),
+            Color::Yellow,
+        )
+    } else if !status.persisting() {
+        ("the database can't be read".to_string(), Color::Red)
+    } else {
+        ("saved to disk".to_string(), Color::Green)
+    };
+
+    let mut lines = vec![
+        Line::from(format!(" {}", status.version)),
+        Line::from(""),
+        Line::from(format!("  Data directory  {}", shown(&status.data_dir))),
+        Line::from(format!("  Database        {}", database)),
+        Line::from("  Settings        stored in the database"),
+        Line::from(format!(
+            "  History         {} game(s), {} solver session(s)",
+            count(status.games),
+            count(status.solver_sessions)
+        )),
+        Line::from(vec![
+            Span::raw("  Saving          "),
+            Span::styled(saving, Style::default().fg(color)),
+        ]),
+        Line::from(""),
+        Line::from(" Word lists"),
+    ];
+    for list in &status.wordlists {
+        let source = match &list.path {
+            Some(path) => path.display().to_string(),
+            None => "bundled".to_string(),
+        };
+        lines.push(Line::from(format!(
+            "  {:<16}{} words, {}",
+            list.name, list.words, source
+        )));
+    }
+    lines
+}
+
+/// The totals, then the games ranked best first.
+fn gauntlet_summary_lines(state: &GauntletSummaryState) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(format!(
+            " Won {} of {} in {} guesses",
+            state.wins(),
+            state.size,
+            state.total_guesses()
+        )),
+        Line::from(""),
+    ];
+
+    for (rank, (_, game)) in state.games.iter().enumerate() {
+        let marker = if rank == state.selected { "▶ " } else { "  " };
+        let result = match game.outcome {
+            GameOutcome::Won { guesses } => format!("won in {}", guesses),
+            GameOutcome::Lost => "lost".to_string(),
+            GameOutcome::Forfeited => "gave up".to_string(),
+            GameOutcome::Abandoned(_) => "abandoned".to_string(),
+        };
+        let color = if matches!(game.outcome, GameOutcome::Won { .. }) {
+            Color::Green
+        } else {
+            Color::Red
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!(
+                "{}{}. {:<8}",
+                marker,
+                rank + 1,
+                game.target_word.to_uppercase()
+            )),
+            Span::styled(result, Style::default().fg(color)),
+        ]));
+    }
+
+    if state.games.len() < state.size {
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!(
+            " {} game(s) weren't saved",
+            state.size - state.games.len()
+        )));
+    }
+
+    lines
+}
+
+fn snapshot_lines(
+    state: &SnapshotState,
+    current_guesses: usize,
+    prefs: &TimePrefs,
+) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line> = state
+        .snapshots
+        .iter()
+        .enumerate()
+        .map(|(i, snapshot)| {
+            let marker = if i == state.selected { "▶ " } else { "  " };
+            Line::from(format!(
+                "{}{:<20} {} guesses  {}",
+                marker,
+                snapshot.name,
+                snapshot.state.guesses().len(),
+                format_local(snapshot.created_at, prefs)
+            ))
+        })
+        .collect();
+
+    if lines.is_empty() {
+        lines.push(Line::from("  No snapshots saved"));
+    }
+
+    match &state.step {
+        SnapshotStep::Browse => {}
+        SnapshotStep::Naming(name) => {
+            lines.push(Line::from(""));
+            lines.push(Line::from(format!("Name: {}_", name)));
+        }
+        SnapshotStep::ConfirmLoad => {
+            lines.push(Line::from(""));
+            lines.push(Line::from(format!(
+                "Replace the current {} guess(es)? y/N",
+                current_guesses
+            )));
+        }
+    }
+
+    lines
+}
+
+fn verify_answer_lines(state: &VerifyAnswerState, word_len: usize) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(format!(
+            " Was the answer {}? (y/n)",
+            state.word.to_uppercase()
+        )),
+    ];
+
+    if let Some(answer) = &state.answer {
+        let blanks = "_".repeat(word_len.saturating_sub(answer.len()));
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!(
+            " Actual answer: {}{}",
+            answer.to_uppercase(),
+            blanks
+        )));
+    }
+
+    lines
+}
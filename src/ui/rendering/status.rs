@@ -1,67 +1,340 @@
+use std::time::Instant;
+
 use ratatui::{
     Frame,
-    style::{Color, Style},
-    widgets::{Block, Borders, Paragraph},
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
 };
 
-use crate::ui::{app::App, types::GameMode};
+use crate::{
+    analysis::format_guesses_to_go,
+    settings::Difficulty,
+    storage::QuickStats,
+    ui::{app::App, hints::status_hints, types::GameMode},
+};
 
 impl App {
     pub(in crate::ui) fn draw_mode_indicator(&self, f: &mut Frame, area: ratatui::layout::Rect) {
-        let mode_text = format!(
-            "Mode: {} | Press Ctrl+G for Game Mode | Ctrl+R for History",
+        let mut mode_text = format!(
+            "Mode: {} | Difficulty: {} | Answers: {}",
             if self.mode == GameMode::Solver {
                 "Solver"
             } else {
                 "Game"
-            }
+            },
+            self.settings.difficulty.name(),
+            self.settings.word_tier.map_or("Any", |t| t.name())
         );
 
+        let mut style = Style::default();
+        if self.mode == GameMode::Solver
+            && let Some(i) = self.last_candidate
+        {
+            mode_text.push_str(&format!(
+                " | Only answer left: {}",
+                self.solution_words.word(i).to_uppercase()
+            ));
+            style = style.fg(Color::Green).add_modifier(Modifier::BOLD);
+        }
+
         f.render_widget(
-            Paragraph::new(mode_text).block(Block::default().borders(Borders::ALL).title("Mode")),
+            Paragraph::new(mode_text)
+                .style(style)
+                .block(Block::default().borders(Borders::ALL).title("Mode")),
             area,
         );
     }
 
+    /// Rows the game status takes: more for a kids' game's win banner.
+    pub(in crate::ui) fn game_status_height(&self) -> u16 {
+        if self.mode == GameMode::Game && self.kids && self.game_won {
+            5
+        } else {
+            3
+        }
+    }
+
     pub(in crate::ui) fn draw_game_status(&self, f: &mut Frame, area: ratatui::layout::Rect) {
-        let status_text = if self.game_over {
+        if self.kids && self.game_won {
+            self.draw_kids_win(f, area);
+            return;
+        }
+
+        let mut status_text = if self.game_over {
+            // Every board's answer once a multi-board game ends
+            let (noun, answer) = match &self.boards {
+                Some(boards) => (
+                    "words were",
+                    boards
+                        .boards
+                        .iter()
+                        .map(|b| b.target.to_uppercase())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+                None => (
+                    "word was",
+                    self.target_word.as_deref().unwrap_or("?").to_uppercase(),
+                ),
+            };
             if self.game_won {
-                format!(
-                    "🎉 You Won! The word was: {}",
-                    self.target_word
-                        .as_ref()
-                        .unwrap_or(&"?".to_string())
-                        .to_uppercase()
-                )
+                format!("🎉 You Won! The {}: {}", noun, answer)
+            } else if self.game_forfeited {
+                format!("🏳 You gave up. The {}: {}", noun, answer)
             } else {
-                format!(
-                    "💀 Game Over! The word was: {}",
-                    self.target_word
-                        .as_ref()
-                        .unwrap_or(&"?".to_string())
-                        .to_uppercase()
-                )
+                format!("💀 Game Over! The {}: {}", noun, answer)
             }
-        } else {
+        } else if self.kids {
             format!(
-                "Guesses remaining: {} | Ctrl+S: Solver | Ctrl+R: History",
-                self.remaining_guesses
+                "Guesses so far: {} — keep going, there's no limit!",
+                self.solver.guesses().len()
             )
+        } else {
+            format!("Guesses remaining: {}", self.remaining_guesses)
         };
 
         let color = if self.game_won {
             Color::Green
+        } else if self.game_forfeited {
+            Color::Magenta
         } else if self.game_over {
             Color::Red
         } else {
             Color::White
         };
 
+        // A hint about the pool, so it follows the same rules as the pool sizes
+        if !self.game_over
+            && self.show_analysis
+            && self.game_difficulty != Difficulty::Expert
+            && let Some(window) = self.win_window
+        {
+            let unit = if window.worst == 1 {
+                "guess"
+            } else {
+                "guesses"
+            };
+            if window.best == window.worst {
+                status_text.push_str(&format!(" | Win window: {} {}", window.worst, unit));
+            } else {
+                status_text.push_str(&format!(
+                    " | Win window: {}–{} {}",
+                    window.best, window.worst, unit
+                ));
+            }
+        }
+
+        if !self.game_over
+            && self.show_analysis
+            && self.game_difficulty != Difficulty::Expert
+            && let Some(stats) = &self.solution_pool_stats
+        {
+            status_text.push_str(&format!(
+                " | {}",
+                format_guesses_to_go(stats.expected_guesses)
+            ));
+        }
+
+        if !self.game_over
+            && self.game_difficulty != Difficulty::Expert
+            && let Some(left) = self.game_hints.remaining()
+        {
+            status_text.push_str(&format!(" | Hints left: {}", left));
+        }
+
+        if self.game_over && !self.game_luck.is_empty() {
+            let total: f64 = self.game_luck.iter().sum();
+            status_text.push_str(&format!(" | Luck: {:+.1}", total));
+        }
+
+        // Suggestions rank single words, so phrase games have no answer rank
+        if self.game_over && !self.game_target_ranks.is_empty() && self.solver.segments().len() == 1
+        {
+            let ranks: Vec<String> = self
+                .game_target_ranks
+                .iter()
+                .map(|rank| rank.map_or("—".to_string(), |r| format!("#{}", r)))
+                .collect();
+            status_text.push_str(&format!(" | Answer rank: {}", ranks.join(" → ")));
+        }
+
+        if let Some(gauntlet) = &self.gauntlet {
+            let finished = gauntlet.results.len();
+            let current = if self.game_over {
+                finished
+            } else {
+                finished + 1
+            };
+            status_text.push_str(&format!(
+                " | Gauntlet {}/{}: {} won, {} guesses",
+                current,
+                gauntlet.targets.len(),
+                gauntlet.wins(),
+                gauntlet.total_guesses()
+            ));
+            if let Some(next_at) = gauntlet.next_at {
+                let seconds = next_at
+                    .saturating_duration_since(Instant::now())
+                    .as_secs_f64()
+                    .ceil();
+                status_text.push_str(&format!(" | Next game in {}s", seconds));
+            }
+        }
+
+        let mut spans = Vec::new();
+        if let Some(blitz) = &self.blitz {
+            if self.game_over {
+                let average = blitz
+                    .average_decision_time()
+                    .map_or("—".to_string(), |d| format!("{:.1}s", d.as_secs_f64()));
+                status_text.push_str(&format!(
+                    " | Avg decision: {} | Timed out: {}",
+                    average,
+                    blitz.timeouts()
+                ));
+            } else {
+                let remaining = blitz.remaining(Instant::now()).as_secs_f64().ceil() as u64;
+                let timer_color = if remaining < 5 {
+                    Color::Red
+                } else {
+                    Color::Yellow
+                };
+                spans.push(Span::styled(
+                    format!("⏱ {}s ", remaining),
+                    Style::default()
+                        .fg(timer_color)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+        }
+        spans.push(Span::styled(status_text, Style::default().fg(color)));
+
+        let mut variants = Vec::new();
+        if self.shadow {
+            variants.push("Shadow");
+        }
+        if self.blitz.is_some() {
+            variants.push("Blitz");
+        }
+        if self.gauntlet.is_some() {
+            variants.push("Gauntlet");
+        }
+        if self.boards.is_some() {
+            variants.push("Multi-board");
+        }
+        if self.kids {
+            variants.push("Kids");
+        } else if self.game_difficulty != Difficulty::Normal {
+            variants.push(self.game_difficulty.name());
+        }
+        let title = if variants.is_empty() {
+            "Game Status".to_string()
+        } else {
+            format!("Game Status ({})", variants.join(", "))
+        };
+
+        f.render_widget(
+            Paragraph::new(Line::from(spans))
+                .block(Block::default().borders(Borders::ALL).title(title)),
+            area,
+        );
+    }
+
+    /// A big, cheerful banner in place of the status line once a kids' game
+    /// is solved.
+    fn draw_kids_win(&self, f: &mut Frame, area: Rect) {
+        let answer = self.target_word.as_deref().unwrap_or("?").to_uppercase();
+        let guesses = self.solver.guesses().len();
+        let unit = if guesses == 1 { "guess" } else { "guesses" };
+        let bold = Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD);
+        let lines = vec![
+            Line::styled("🌟 🎉 🌈  HOORAY, YOU DID IT!  🌈 🎉 🌟", bold),
+            Line::styled(
+                format!("⭐ You found {} in {} {}! ⭐", answer, guesses, unit),
+                bold,
+            ),
+            Line::styled(
+                "Press Enter to play again 🚀",
+                Style::default().fg(Color::Yellow),
+            ),
+        ];
+
+        f.render_widget(
+            Paragraph::new(lines).alignment(Alignment::Center).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Double)
+                    .border_style(Style::default().fg(Color::Magenta))
+                    .title("Game Status (Kids)"),
+            ),
+            area,
+        );
+    }
+
+    /// The one-line bar along the bottom: what the keys do now, or the last
+    /// error while it is fresh.
+    pub(in crate::ui) fn draw_status_bar(&self, f: &mut Frame, area: Rect) {
+        let error = self
+            .status_error
+            .borrow()
+            .as_ref()
+            .filter(|error| error.visible(Instant::now()))
+            .map(|error| error.text.clone());
+        let line = match error {
+            Some(text) => Line::from(Span::styled(
+                format!(" {}", text),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )),
+            None => {
+                let mut spans = vec![Span::raw(" ")];
+                for (i, hint) in status_hints(self).iter().enumerate() {
+                    if i > 0 {
+                        spans.push(Span::styled(" · ", Style::default().fg(Color::DarkGray)));
+                    }
+                    spans.push(Span::styled(
+                        hint.key,
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                    spans.push(Span::raw(format!(" {}", hint.action)));
+                }
+                Line::from(spans)
+            }
+        };
+        f.render_widget(Paragraph::new(line), area);
+    }
+
+    /// One line of lifetime results above the game panels, from the stats
+    /// read when the last game ended; nothing is read while drawing.
+    pub(in crate::ui) fn draw_quick_stats(&self, f: &mut Frame, area: Rect) {
         f.render_widget(
-            Paragraph::new(status_text)
-                .style(Style::default().fg(color))
-                .block(Block::default().borders(Borders::ALL).title("Game Status")),
+            Paragraph::new(format!(" {}", quick_stats_text(self.quick_stats.as_ref())))
+                .style(Style::default().fg(Color::Gray)),
             area,
         );
     }
 }
+
+/// `W/L 142-12 · 92% · streak 7 · avg 3.9`, with `—` for whatever isn't known.
+fn quick_stats_text(stats: Option<&QuickStats>) -> String {
+    let Some(stats) = stats else {
+        return "W/L — · — · streak — · avg —".to_string();
+    };
+    format!(
+        "W/L {}-{} · {} · streak {} · avg {}",
+        stats.wins,
+        stats.losses,
+        stats
+            .win_rate()
+            .map_or("—".to_string(), |rate| format!("{:.0}%", rate)),
+        stats.current_streak,
+        stats
+            .average_guesses
+            .map_or("—".to_string(), |average| format!("{:.1}", average))
+    )
+}
@@ -1,27 +1,181 @@
 use ratatui::{
     Frame,
-    widgets::{Block, Borders, List, ListItem},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState},
 };
 
-use crate::ui::app::App;
+use crate::{
+    analysis::SuggestionDelta,
+    ui::{
+        app::App,
+        types::{GameMode, SUGGESTION_DIFF_ROWS},
+    },
+};
 
 impl App {
     pub(in crate::ui) fn draw_suggestions(&self, f: &mut Frame, area: ratatui::layout::Rect) {
+        let exit = self.notable_exit();
+
+        // Fill the panel, keeping the last row for a footer when the list is
+        // cut short and one more for the notable exit
+        let capacity = area.height.saturating_sub(2) as usize;
+        let room = capacity.saturating_sub(usize::from(exit.is_some()));
+        let shown = if self.suggestions.len() > room {
+            room.saturating_sub(1)
+        } else {
+            self.suggestions.len()
+        };
+        self.suggestion_rows.set(shown);
+
         let items: Vec<ListItem> = if self.suggestions.is_empty() {
-            vec![ListItem::new("No suggestions yet")]
+            let mut items = Vec::new();
+            // Openers are for before the first guess; a game shouldn't hand them out
+            if self.mode == GameMode::Solver
+                && self.solver.guesses().is_empty()
+                && !self.opening_trio.words.is_empty()
+            {
+                items.push(ListItem::new(format!(
+                    "Trio: {}",
+                    self.opening_trio.joined()
+                )));
+                items.push(
+                    ListItem::new(format!(
+                        "{} letters before any feedback",
+                        self.opening_trio.letters
+                    ))
+                    .style(Style::default().fg(Color::DarkGray)),
+                );
+            }
+            items.push(ListItem::new("No suggestions yet"));
+            items
         } else {
-            self.suggestions
+            let mut items: Vec<ListItem> = self
+                .suggestions
                 .iter()
-                .take(10)
-                .map(|(w, s)| ListItem::new(format!("{w} ({s})")))
-                .collect()
+                .map(|&(i, s)| (i, self.solution_words.word(i), s))
+                .take(shown)
+                .enumerate()
+                .map(|(rank, (i, w, s))| {
+                    let shown = self.spellings.display(w);
+                    let mut spans = vec![Span::raw(format!("{shown} ({s})"))];
+                    if self.flagged_words.contains(w) {
+                        spans.push(Span::styled(" ?", Style::default().fg(Color::Yellow)));
+                    }
+                    // Only a move of more than two places is worth calling out
+                    if let Some(&shift) = self.prior_shifts.get(&i)
+                        && shift.abs() > 2
+                    {
+                        let color = if shift > 0 { Color::Green } else { Color::Red };
+                        spans.push(Span::styled(
+                            format!(" {:+}", shift),
+                            Style::default().fg(color).add_modifier(Modifier::DIM),
+                        ));
+                    }
+                    if rank < SUGGESTION_DIFF_ROWS
+                        && let Some(marker) = self.delta_marker(w)
+                    {
+                        spans.push(Span::raw(" "));
+                        spans.push(marker);
+                    }
+                    if self.mode == GameMode::Solver && self.last_candidate == Some(i) {
+                        spans.push(Span::raw(" ← only answer left"));
+                        return ListItem::new(Line::from(spans)).style(
+                            Style::default()
+                                .fg(Color::Green)
+                                .add_modifier(Modifier::BOLD),
+                        );
+                    }
+                    ListItem::new(Line::from(spans))
+                })
+                .collect();
+            if shown < self.suggestions.len() && capacity > 0 {
+                items.push(
+                    ListItem::new(format!("… and {} more", self.suggestions.len() - shown))
+                        .style(Style::default().fg(Color::DarkGray)),
+                );
+            }
+            if let Some((word, rank)) = exit
+                && items.len() < capacity
+            {
+                items.push(
+                    ListItem::new(format!("{} (was #{})", word, rank + 1)).style(
+                        Style::default()
+                            .fg(Color::DarkGray)
+                            .add_modifier(Modifier::CROSSED_OUT),
+                    ),
+                );
+            }
+            items
+        };
+
+        let mut title = format!("Suggestions (remaining: {}", self.suggestions.len());
+        if self.settings.hide_gray_suggestions {
+            title.push_str(", gray letters hidden");
+        }
+        if self.settings.answer_priors {
+            title.push_str(", answer priors");
+        }
+        title.push(')');
+        if !self.session_blacklist.is_empty() {
+            title.push_str(&format!(" ({} hidden)", self.session_blacklist.len()));
+        }
+        if self.suggestion_selected.is_some() {
+            title.push_str(
+                " | ↑↓ move · i explain · g gray filter · p priors · x/X blacklist · b list · e/E export · Enter use · Esc done",
+            );
+        }
+
+        let border_style = if self.suggestion_selected.is_some() {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default()
         };
 
-        let title = format!("Suggestions (remaining: {})", self.suggestions.len());
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(border_style)
+                    .title(title),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+
+        // The panel may have shrunk since the selection was made
+        let selected = self
+            .suggestion_selected
+            .map(|i| i.min(shown.saturating_sub(1)));
+        let mut state = ListState::default().with_selected(selected);
+        f.render_stateful_widget(list, area, &mut state);
+    }
+
+    /// ↑/↓ with the places moved, or NEW, for a word the last guess moved.
+    fn delta_marker(&self, word: &str) -> Option<Span<'static>> {
+        let (text, color) = match self.suggestion_deltas.iter().find(|d| d.word() == word)? {
+            SuggestionDelta::Moved { from, to, .. } if to < from => {
+                (format!("↑{}", from - to), Color::Green)
+            }
+            SuggestionDelta::Moved { from, to, .. } => (format!("↓{}", to - from), Color::Red),
+            SuggestionDelta::Entered { .. } => ("NEW".to_string(), Color::Cyan),
+            SuggestionDelta::Left { .. } => return None,
+        };
+        Some(Span::styled(text, Style::default().fg(color)))
+    }
 
-        f.render_widget(
-            List::new(items).block(Block::default().borders(Borders::ALL).title(title)),
-            area,
-        );
+    /// The highest-ranked word the last guess pushed out of the top
+    /// suggestions, preferring one no longer suggested at all.
+    fn notable_exit(&self) -> Option<(&str, usize)> {
+        let exits = self.suggestion_deltas.iter().filter_map(|d| match d {
+            SuggestionDelta::Left {
+                word,
+                rank,
+                eliminated,
+            } => Some((word.as_str(), *rank, *eliminated)),
+            _ => None,
+        });
+        exits
+            .min_by_key(|&(_, rank, eliminated)| (!eliminated, rank))
+            .map(|(word, rank, _)| (word, rank))
     }
 }
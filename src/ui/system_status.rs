@@ -0,0 +1,80 @@
+//! Where the app keeps its data and whether it is being saved, for the About
+//! overlay.
+
+use std::{fs, path::PathBuf};
+
+use crate::build_info::BuildInfo;
+
+use super::app::App;
+
+/// One word list: where it was read from and how many words it holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordlistStatus {
+    pub name: &'static str,
+    /// None for the list bundled into the binary.
+    pub path: Option<PathBuf>,
+    pub words: usize,
+}
+
+/// The app's files and storage, gathered once when the About overlay opens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemStatus {
+    pub version: String,
+    /// The directory the database, journal and backups live in; none when
+    /// the database is in memory.
+    pub data_dir: Option<PathBuf>,
+    pub database: Option<PathBuf>,
+    /// Size of the database file; none if it can't be read.
+    pub database_bytes: Option<u64>,
+    /// Finished games, archived ones included; none if the query failed.
+    pub games: Option<usize>,
+    /// Finished solver sessions; none if the query failed.
+    pub solver_sessions: Option<usize>,
+    /// History writes the database refused, waiting for the next start.
+    pub pending_writes: usize,
+    pub wordlists: Vec<WordlistStatus>,
+}
+
+impl SystemStatus {
+    /// Query the database and read file metadata; too slow to run per frame.
+    pub fn collect(app: &App) -> Self {
+        let database = app.db.path().map(PathBuf::from);
+        Self {
+            version: BuildInfo::current().summary(),
+            data_dir: app.db.data_dir().map(PathBuf::from),
+            database_bytes: database
+                .as_ref()
+                .and_then(|path| fs::metadata(path).ok())
+                .map(|meta| meta.len()),
+            database,
+            games: app.db.count_games(true).ok(),
+            solver_sessions: app.db.count_solver_sessions().ok(),
+            pending_writes: app.db.pending_writes(),
+            wordlists: vec![
+                WordlistStatus {
+                    name: "Allowed guesses",
+                    path: app.words_path.clone(),
+                    words: app.allowed_lookup.len(),
+                },
+                WordlistStatus {
+                    name: "Answers",
+                    path: app.solutions_path.clone(),
+                    words: app.solution_words.len(),
+                },
+            ],
+        }
+    }
+
+    /// Whether history lives only in memory and is lost on exit.
+    pub fn in_memory(&self) -> bool {
+        self.database.is_none()
+    }
+
+    /// Whether history is being saved: on disk, with nothing left queued.
+    pub fn persisting(&self) -> bool {
+        !self.in_memory()
+            && self.pending_writes == 0
+            && self.games.is_some()
+            && self.solver_sessions.is_some()
+    }
+}
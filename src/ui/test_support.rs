@@ -0,0 +1,130 @@
+//! Shared setup for UI tests.
+
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, bail};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+
+use super::{app::App, builder::AppBuilder, events::EventSource};
+use crate::notifier::Notifier;
+
+/// Guess and answer list used by [`AppBuilder::for_tests`].
+pub const FIXTURE_WORDS: [&str; 8] = [
+    "raise", "stone", "slate", "crane", "house", "apple", "world", "magic",
+];
+
+/// An app on the fixture word list with an in-memory database.
+pub fn create_test_app() -> App {
+    AppBuilder::for_tests()
+        .build()
+        .expect("failed to create test app")
+}
+
+/// A notifier that keeps every summary it's given, shared with its clones.
+#[derive(Clone, Default)]
+pub struct RecordingNotifier {
+    sent: Rc<RefCell<Vec<String>>>,
+}
+
+impl RecordingNotifier {
+    /// Summaries sent so far, oldest first.
+    pub fn sent(&self) -> Vec<String> {
+        self.sent.borrow().clone()
+    }
+}
+
+impl Notifier for RecordingNotifier {
+    fn notify(&self, summary: &str) {
+        self.sent.borrow_mut().push(summary.to_string());
+    }
+}
+
+/// The rendered text of each row of `buffer`.
+pub fn buffer_lines(buffer: &Buffer) -> Vec<String> {
+    (0..buffer.area.height)
+        .map(|y| {
+            (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect()
+        })
+        .collect()
+}
+
+enum Step {
+    Event(Event),
+    Idle(Duration),
+}
+
+/// A fixed sequence of key presses and idle periods for driving [`App::run`].
+///
+/// Idle periods time out one wait of the event loop and move the clock forward,
+/// so countdowns expire without the test sleeping. Running out of steps before
+/// the app quits is an error rather than a hang.
+#[derive(Default)]
+pub struct ScriptedEvents {
+    steps: VecDeque<Step>,
+    /// Idle time simulated so far, added to the real clock.
+    elapsed: Duration,
+}
+
+impl ScriptedEvents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn key(mut self, code: KeyCode, modifiers: KeyModifiers) -> Self {
+        self.steps
+            .push_back(Step::Event(Event::Key(KeyEvent::new(code, modifiers))));
+        self
+    }
+
+    pub fn press(self, code: KeyCode) -> Self {
+        self.key(code, KeyModifiers::NONE)
+    }
+
+    pub fn ctrl(self, c: char) -> Self {
+        self.key(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    /// Type each character of `text`, then press Enter.
+    pub fn line(self, text: &str) -> Self {
+        text.chars()
+            .fold(self, |script, c| script.press(KeyCode::Char(c)))
+            .press(KeyCode::Enter)
+    }
+
+    /// Paste `text` in one event, as a terminal with bracketed paste does.
+    pub fn paste(mut self, text: &str) -> Self {
+        self.steps
+            .push_back(Step::Event(Event::Paste(text.to_string())));
+        self
+    }
+
+    pub fn idle(mut self, duration: Duration) -> Self {
+        self.steps.push_back(Step::Idle(duration));
+        self
+    }
+}
+
+impl EventSource for ScriptedEvents {
+    fn next_event(&mut self, _timeout: Duration) -> Result<Option<Event>> {
+        match self.steps.pop_front() {
+            Some(Step::Event(event)) => Ok(Some(event)),
+            Some(Step::Idle(duration)) => {
+                self.elapsed += duration;
+                Ok(None)
+            }
+            None => bail!("scripted events ran out before the app quit"),
+        }
+    }
+
+    fn now(&self) -> Instant {
+        Instant::now() + self.elapsed
+    }
+}
@@ -1,28 +1,13 @@
 use super::{
     app::App,
+    builder::AppBuilder,
     handlers::{GameHandler, HistoryHandler, InputHandler, SolverHandler},
     history::{HistoryData, HistoryViewMode},
+    test_support::create_test_app,
     types::{GameMode, InputStatus, LogBuffer},
 };
 use crate::solver::{Feedback, Guess};
 
-fn create_test_app() -> App {
-    let words = vec![
-        "raise".to_string(),
-        "stone".to_string(),
-        "slate".to_string(),
-        "crane".to_string(),
-        "house".to_string(),
-        "apple".to_string(),
-        "world".to_string(),
-        "magic".to_string(),
-    ];
-    let solution_words = words.clone();
-    let logs = LogBuffer::new();
-    let db = crate::storage::Database::open_memory().expect("failed to create in-memory test db");
-    App::new(words, solution_words, 5, logs, db)
-}
-
 #[cfg(test)]
 mod app_tests {
     use super::*;
@@ -41,6 +26,19 @@ mod app_tests {
         assert!(app.target_word.is_none());
     }
 
+    #[test]
+    fn test_builder_defaults() {
+        let logs = LogBuffer::new();
+        let app = AppBuilder::for_tests().logs(logs.clone()).build().unwrap();
+
+        assert_eq!(app.solver.word_len(), 5);
+        assert_eq!(app.solution_words.len(), 8);
+        assert!(app.db.load_games().unwrap().is_empty());
+
+        app.log("hello");
+        assert_eq!(logs.lines(), ["hello"]);
+    }
+
     #[test]
     fn test_log_buffer() {
         let logs = LogBuffer::new();
@@ -88,7 +86,7 @@ mod input_handler_tests {
     fn test_input_validation_solver_mode_valid() {
         let mut app = create_test_app();
         app.mode = GameMode::Solver;
-        app.input = "raise GYXXX".to_string();
+        app.input = "raise YXXXX".to_string();
 
         let handler = InputHandler::new(&mut app);
         let status = handler.input_status();
@@ -329,7 +327,7 @@ mod solver_handler_tests {
                 Feedback::Green,
             ],
         );
-        app.solver.add_guess(guess);
+        app.solver.add_guess(guess).unwrap();
 
         assert_eq!(app.solver.guesses().len(), 1);
 
@@ -366,13 +364,13 @@ mod solver_handler_tests {
                 Feedback::Green,
             ],
         );
-        app.solver.add_guess(guess);
+        app.solver.add_guess(guess).unwrap();
 
         // Initially no suggestions computed
         app.suggestions.clear();
 
         // Recompute should populate suggestions
-        SolverHandler::new(&mut app).recompute();
+        SolverHandler::new(&mut app).refresh_derived_state();
 
         assert!(!app.suggestions.is_empty());
     }
@@ -382,7 +380,7 @@ mod solver_handler_tests {
         let mut app = create_test_app();
 
         // Get initial suggestion count
-        SolverHandler::new(&mut app).recompute();
+        SolverHandler::new(&mut app).refresh_derived_state();
         let initial_count = app.suggestions.len();
 
         // Add a guess that filters words
@@ -396,10 +394,10 @@ mod solver_handler_tests {
                 Feedback::Green,
             ],
         );
-        app.solver.add_guess(guess);
+        app.solver.add_guess(guess).unwrap();
 
         // Recompute with constraint
-        SolverHandler::new(&mut app).recompute();
+        SolverHandler::new(&mut app).refresh_derived_state();
         let filtered_count = app.suggestions.len();
 
         // Should have fewer suggestions after constraint
@@ -420,12 +418,30 @@ mod history_handler_tests {
                 target_word: "stone".to_string(),
                 guesses: vec![],
                 outcome: GameOutcome::Won { guesses: 3 },
+                blitz: false,
+                duration_secs: None,
+                app_version: None,
+                difficulty: crate::settings::Difficulty::Normal,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty: None,
             },
             GameRecord {
                 timestamp: Utc::now(),
                 target_word: "raise".to_string(),
                 guesses: vec![],
                 outcome: GameOutcome::Lost,
+                blitz: false,
+                duration_secs: None,
+                app_version: None,
+                difficulty: crate::settings::Difficulty::Normal,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty: None,
             },
         ];
         HistoryData::new(games, Vec::new())
@@ -569,6 +585,137 @@ mod history_handler_tests {
         assert_eq!(app.history_view_mode, HistoryViewMode::Stats);
         assert!(app.history_data.as_ref().unwrap().selected_game().is_none());
     }
+    #[test]
+    fn test_enter_history_loads_feedback_rates() {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("stone".to_string());
+        GameHandler::new(&mut app).submit_guess("slate".to_string());
+        GameHandler::new(&mut app).submit_guess("stone".to_string());
+
+        HistoryHandler::new(&mut app).enter_history_mode();
+
+        let data = app.history_data.as_ref().unwrap();
+        assert_eq!(data.first_guess_green_rates, [1.0, 0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(data.greens_per_guess, [2.0, 5.0]);
+    }
+}
+
+#[cfg(test)]
+mod history_compare_tests {
+    use super::*;
+    use crate::ui::history::{GameOutcome, GameRecord, HistoryStats, StatsComparison};
+    use chrono::{DateTime, TimeZone, Utc};
+
+    fn day(d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, d, 12, 0, 0).unwrap()
+    }
+
+    fn game(d: u32, outcome: GameOutcome, duration_secs: Option<f64>) -> GameRecord {
+        GameRecord {
+            timestamp: day(d),
+            target_word: "stone".to_string(),
+            guesses: vec![],
+            outcome,
+            blitz: false,
+            duration_secs,
+            app_version: None,
+            difficulty: crate::settings::Difficulty::Normal,
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        }
+    }
+
+    fn crafted_games() -> Vec<GameRecord> {
+        vec![
+            game(1, GameOutcome::Won { guesses: 5 }, Some(120.0)),
+            game(2, GameOutcome::Lost, Some(300.0)),
+            game(3, GameOutcome::Won { guesses: 4 }, Some(80.0)),
+            game(4, GameOutcome::Won { guesses: 3 }, Some(60.0)),
+        ]
+    }
+
+    #[test]
+    fn test_from_games_in_range_filters_by_timestamp() {
+        let games = crafted_games();
+
+        let stats = HistoryStats::from_games_in_range(&games, day(2)..day(4));
+
+        assert_eq!(stats.total_games, 2);
+        assert_eq!(stats.wins, 1);
+        assert_eq!(stats.guess_distribution, [0, 0, 0, 1, 0, 0]);
+        assert_eq!(stats.average_solve_secs, Some(80.0));
+    }
+
+    #[test]
+    fn test_comparison_deltas() {
+        let comparison = StatsComparison::new(&crafted_games(), day(3));
+
+        // Before: won in 5, lost. After: won in 4, won in 3.
+        assert_eq!(comparison.win_rate_delta(), Some(50.0));
+        assert_eq!(comparison.average_guesses_delta(), Some(-1.5));
+        assert_eq!(comparison.average_solve_secs_delta(), Some(-50.0));
+        assert_eq!(comparison.distribution_delta(), [0, 0, 1, 1, -1, 0]);
+    }
+
+    #[test]
+    fn test_comparison_deltas_with_empty_range() {
+        let comparison = StatsComparison::new(&crafted_games(), day(1));
+
+        assert_eq!(comparison.before.total_games, 0);
+        assert_eq!(comparison.win_rate_delta(), None);
+        assert_eq!(comparison.average_guesses_delta(), None);
+        assert_eq!(comparison.average_solve_secs_delta(), None);
+        assert_eq!(comparison.distribution_delta(), [0, 0, 1, 1, 1, 0]);
+    }
+
+    #[test]
+    fn test_solve_time_delta_needs_timed_wins() {
+        let games = vec![
+            game(1, GameOutcome::Won { guesses: 4 }, None),
+            game(2, GameOutcome::Won { guesses: 3 }, Some(60.0)),
+        ];
+
+        let comparison = StatsComparison::new(&games, day(2));
+
+        assert_eq!(comparison.average_guesses_delta(), Some(-1.0));
+        assert_eq!(comparison.average_solve_secs_delta(), None);
+    }
+
+    #[test]
+    fn test_open_compare_splits_into_halves_and_moves_split() {
+        let mut app = create_test_app();
+        app.mode = GameMode::History;
+        app.history_data = Some(HistoryData::new(crafted_games(), Vec::new()));
+
+        HistoryHandler::new(&mut app).open_compare();
+        assert_eq!(app.history_view_mode, HistoryViewMode::Compare);
+        assert_eq!(app.history_compare_split, Some(day(3)));
+
+        HistoryHandler::new(&mut app).shift_compare_split(-1);
+        assert_eq!(app.history_compare_split, Some(day(2)));
+
+        HistoryHandler::new(&mut app).reset_compare_split();
+        assert_eq!(app.history_compare_split, Some(day(3)));
+
+        HistoryHandler::new(&mut app).cycle_view_mode();
+        assert_eq!(app.history_view_mode, HistoryViewMode::Stats);
+    }
+
+    #[test]
+    fn test_completed_game_records_duration() {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        let target = app.target_word.clone().unwrap();
+
+        GameHandler::new(&mut app).submit_guess(target);
+
+        let games = app.db.load_games().unwrap();
+        assert!(games[0].duration_secs.is_some());
+    }
 }
 
 #[cfg(test)]
@@ -636,7 +783,7 @@ mod mode_switching_tests {
                 Feedback::Green,
             ],
         );
-        app.solver.add_guess(guess);
+        app.solver.add_guess(guess).unwrap();
         let guess_count = app.solver.guesses().len();
 
         // Switch to history and back
@@ -701,4 +848,8344 @@ mod analysis_toggle_tests {
         app.show_suggestions = !app.show_suggestions;
         assert!(!app.show_suggestions);
     }
+
+    #[test]
+    fn test_ctrl_l_ranks_letters_by_information() {
+        use crate::settings::Settings;
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let mut app = create_test_app();
+        SolverHandler::new(&mut app).recompute_analysis();
+        let frequency = &app.letter_analysis.as_ref().unwrap().frequencies;
+        // E is in 6 of the 8 fixture words
+        assert_eq!(frequency.get(&'e'), Some(&6));
+
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL));
+        assert!(app.settings.letter_information);
+        assert!(Settings::load(&app.db).unwrap().letter_information);
+
+        SolverHandler::new(&mut app).recompute_analysis();
+        let information = &app.letter_analysis.as_ref().unwrap().frequencies;
+        // 6 of 8 is about 0.81 bits
+        assert_eq!(information.get(&'e'), Some(&811));
+    }
+}
+
+#[cfg(test)]
+mod plain_tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn run_plain_script(app: &mut App, script: &str) -> String {
+        let mut output = Vec::new();
+        app.run_plain(Cursor::new(script), &mut output)
+            .expect("plain mode failed");
+        String::from_utf8(output).expect("plain output was not UTF-8")
+    }
+
+    #[test]
+    fn test_plain_guess_spells_out_feedback() {
+        let mut app = create_test_app();
+
+        let output = run_plain_script(&mut app, "magic XXXXX\nquit\n");
+
+        assert!(output.contains("M gray, A gray, G gray, I gray, C gray"));
+        assert!(output.contains("Remaining candidates: 3."));
+        assert!(output.contains("Suggestions:"));
+        assert!(output.ends_with("Goodbye.\n"));
+        assert_eq!(app.solver.guesses().len(), 1);
+    }
+
+    #[test]
+    fn test_plain_rejects_invalid_input() {
+        let mut app = create_test_app();
+
+        let output = run_plain_script(&mut app, "zzzzz GGGGG\nraise GZXXX\n");
+
+        assert!(output.contains("Invalid input: guess not in allowed list: zzzzz."));
+        assert!(output.contains("Invalid input: pattern character must be G/Y/X, got Z."));
+        assert!(app.solver.guesses().is_empty());
+    }
+
+    #[test]
+    fn test_plain_undo_and_help() {
+        let mut app = create_test_app();
+
+        let output = run_plain_script(&mut app, "undo\nmagic XXXXX\nundo\nhelp\n");
+
+        assert!(output.contains("Nothing to undo."));
+        assert!(output.contains("Removed guess MAGIC."));
+        assert!(output.contains("Commands: help, undo, quit"));
+        assert!(app.solver.guesses().is_empty());
+    }
+
+    #[test]
+    fn test_plain_records_completed_session() {
+        let mut app = create_test_app();
+
+        let output = run_plain_script(&mut app, "stone GGGGG\n");
+
+        assert!(output.contains("Solved!"));
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].guesses[0].word, "stone");
+    }
+}
+
+#[cfg(test)]
+mod suggestion_handler_tests {
+    use super::*;
+    use crate::{settings::Settings, ui::types::Overlay};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn app_with_suggestions() -> App {
+        let mut app = create_test_app();
+        app.solver
+            .add_guess(Guess::new("magic".to_string(), vec![Feedback::Gray; 5]))
+            .unwrap();
+        SolverHandler::new(&mut app).refresh_derived_state();
+        app
+    }
+
+    #[test]
+    fn test_tab_selects_and_arrows_move() {
+        let mut app = app_with_suggestions();
+        assert!(app.suggestions.len() > 1);
+
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.suggestion_selected, Some(0));
+
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.suggestion_selected, Some(1));
+
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.suggestion_selected, Some(0));
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.suggestion_selected, None);
+    }
+
+    #[test]
+    fn test_selection_swallows_characters() {
+        let mut app = app_with_suggestions();
+
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char('z'));
+
+        assert!(app.input.is_empty());
+    }
+
+    #[test]
+    fn test_explain_opens_and_closes_popup() {
+        let mut app = app_with_suggestions();
+        let (top_word, top_score) = app.suggested_words().next().unwrap();
+        let top = (top_word.to_string(), top_score);
+
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char('i'));
+
+        match &app.overlay {
+            Some(Overlay::ScoreExplanation(explanation)) => {
+                assert_eq!(explanation.word, top.0);
+                assert_eq!(explanation.total, top.1);
+            }
+            other => panic!("expected score explanation, got {:?}", other),
+        }
+
+        press(&mut app, KeyCode::Char('i'));
+        assert!(app.overlay.is_none());
+    }
+
+    #[test]
+    fn test_enter_copies_selection_into_input() {
+        let mut app = app_with_suggestions();
+        let top = app.suggested_words().next().unwrap().0.to_string();
+
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.input, format!("{} ", top));
+        assert_eq!(app.suggestion_selected, None);
+    }
+
+    #[test]
+    fn test_hide_gray_filters_and_persists() {
+        let mut app = create_test_app();
+        // STONE: S, T, O, N gray; E green
+        app.solver
+            .add_guess(Guess::new(
+                "stone".to_string(),
+                vec![
+                    Feedback::Gray,
+                    Feedback::Gray,
+                    Feedback::Gray,
+                    Feedback::Gray,
+                    Feedback::Green,
+                ],
+            ))
+            .unwrap();
+        SolverHandler::new(&mut app).refresh_derived_state();
+
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char('g'));
+
+        assert!(app.settings.hide_gray_suggestions);
+        assert!(
+            app.suggested_words()
+                .all(|(w, _)| !w.chars().any(|c| "ston".contains(c)))
+        );
+        assert!(Settings::load(&app.db).unwrap().hide_gray_suggestions);
+    }
+}
+
+#[cfg(test)]
+mod blitz_tests {
+    use super::*;
+    use crate::ui::types::BlitzTurn;
+    use std::time::{Duration, Instant};
+
+    fn start_blitz(app: &mut App) -> Instant {
+        app.settings.blitz_seconds = 30;
+        GameHandler::new(app).start_blitz_game();
+        app.blitz.as_ref().unwrap().turn_started
+    }
+
+    #[test]
+    fn test_start_blitz_game() {
+        let mut app = create_test_app();
+
+        start_blitz(&mut app);
+
+        assert_eq!(app.mode, GameMode::Game);
+        let blitz = app.blitz.as_ref().unwrap();
+        assert_eq!(blitz.seconds, 30);
+        assert!(blitz.turns.is_empty());
+    }
+
+    #[test]
+    fn test_tick_before_deadline_keeps_turn() {
+        let mut app = create_test_app();
+        let started = start_blitz(&mut app);
+
+        GameHandler::new(&mut app).tick(started + Duration::from_secs(29));
+
+        assert_eq!(app.remaining_guesses, 6);
+        assert!(app.blitz.as_ref().unwrap().turns.is_empty());
+    }
+
+    #[test]
+    fn test_deadline_expiry_forfeits_guess() {
+        let mut app = create_test_app();
+        let started = start_blitz(&mut app);
+        let expired = started + Duration::from_secs(30);
+
+        GameHandler::new(&mut app).tick(expired);
+
+        assert_eq!(app.remaining_guesses, 5);
+        let blitz = app.blitz.as_ref().unwrap();
+        assert_eq!(blitz.turns, vec![BlitzTurn::TimedOut]);
+        // The next turn's countdown restarts from the expiry
+        assert_eq!(blitz.deadline(), expired + Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_running_out_of_time_loses_and_records_blitz_game() {
+        let mut app = create_test_app();
+        let mut now = start_blitz(&mut app);
+
+        for _ in 0..6 {
+            now += Duration::from_secs(31);
+            GameHandler::new(&mut app).tick(now);
+        }
+
+        assert!(app.game_over);
+        assert!(!app.game_won);
+
+        let games = app.db.load_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert!(games[0].blitz);
+        assert!(games[0].was_lost());
+        assert_eq!(games[0].guesses.len(), 6);
+        assert!(games[0].guesses.iter().all(|g| g.timed_out));
+    }
+
+    #[test]
+    fn test_timeouts_interleave_with_guesses_in_record() {
+        let mut app = create_test_app();
+        let started = start_blitz(&mut app);
+        let target = app.target_word.clone().unwrap();
+        let wrong = ["raise", "stone"]
+            .into_iter()
+            .find(|w| *w != target)
+            .unwrap();
+
+        GameHandler::new(&mut app).tick(started + Duration::from_secs(30));
+        GameHandler::new(&mut app).submit_guess(wrong.to_string());
+        GameHandler::new(&mut app).submit_guess(target.clone());
+
+        assert!(app.game_won);
+        let games = app.db.load_games().unwrap();
+        let game = &games[0];
+        assert_eq!(
+            game.outcome,
+            crate::ui::history::GameOutcome::Won { guesses: 3 }
+        );
+        assert!(game.guesses[0].timed_out);
+        assert_eq!(game.guesses[1].word, wrong);
+        assert_eq!(game.guesses[2].word, target);
+        assert!(
+            app.blitz
+                .as_ref()
+                .unwrap()
+                .average_decision_time()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_new_regular_game_clears_blitz() {
+        let mut app = create_test_app();
+        start_blitz(&mut app);
+
+        GameHandler::new(&mut app).start_new_game();
+
+        assert!(app.blitz.is_none());
+    }
+
+    #[test]
+    fn test_stats_split_blitz_games() {
+        use crate::ui::history::{GameOutcome, GameRecord, HistoryStats};
+        use chrono::Utc;
+
+        let game = |blitz, outcome| GameRecord {
+            timestamp: Utc::now(),
+            target_word: "stone".to_string(),
+            guesses: vec![],
+            outcome,
+            blitz,
+            duration_secs: None,
+            app_version: None,
+            difficulty: crate::settings::Difficulty::Normal,
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        };
+        let games = vec![
+            game(true, GameOutcome::Won { guesses: 4 }),
+            game(true, GameOutcome::Lost),
+            game(false, GameOutcome::Won { guesses: 3 }),
+        ];
+
+        let stats = HistoryStats::from_games(&games);
+
+        assert_eq!(stats.blitz_games, 2);
+        assert_eq!(stats.blitz_wins, 1);
+        assert_eq!(stats.wins, 2);
+    }
+}
+
+#[cfg(test)]
+mod edit_guess_tests {
+    use super::*;
+    use crate::solver::{SolverState, parse_pattern};
+    use crate::ui::types::Overlay;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+    }
+
+    /// "raise" entered with S as gray instead of yellow, then "house".
+    fn app_with_mistyped_pattern() -> App {
+        let mut app = create_test_app();
+        SolverHandler::new(&mut app)
+            .submit_guess("raise".to_string(), parse_pattern("XXXXG").unwrap());
+        SolverHandler::new(&mut app)
+            .submit_guess("house".to_string(), parse_pattern("XYXYG").unwrap());
+        app
+    }
+
+    #[test]
+    fn test_edit_guess_matches_fresh_sequence() {
+        let mut app = app_with_mistyped_pattern();
+        assert!(app.solver.filter(&app.solution_words).is_empty());
+
+        SolverHandler::new(&mut app)
+            .edit_guess(0, parse_pattern("XXXYG").unwrap())
+            .unwrap();
+
+        let mut fresh = SolverState::new(5);
+        for (word, pattern) in [("raise", "XXXYG"), ("house", "XYXYG")] {
+            fresh
+                .add_guess(Guess::new(
+                    word.to_string(),
+                    parse_pattern(pattern).unwrap(),
+                ))
+                .unwrap();
+        }
+        assert_eq!(
+            app.solver.filter(&app.solution_words),
+            fresh.filter(&app.solution_words)
+        );
+        assert_eq!(app.solver.guesses()[1].word, "house");
+        assert_eq!(app.entropy_history.len(), 2);
+    }
+
+    #[test]
+    fn test_edit_guess_recomputes_session_metrics() {
+        let mut app = app_with_mistyped_pattern();
+        assert_eq!(app.solver_session_guesses[1].pool_size_after, 0);
+
+        SolverHandler::new(&mut app)
+            .edit_guess(0, parse_pattern("XXXYG").unwrap())
+            .unwrap();
+
+        let metrics = &app.solver_session_guesses;
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(metrics[0].pool_size_after, 1);
+        assert_eq!(metrics[1].pool_size_before, 1);
+        assert_eq!(metrics[1].pool_size_after, 1);
+    }
+
+    #[test]
+    fn test_edit_guess_via_keys() {
+        let mut app = app_with_mistyped_pattern();
+
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL));
+        assert!(matches!(app.overlay, Some(Overlay::EditGuess(_))));
+
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Enter);
+        for _ in 0..5 {
+            press(&mut app, KeyCode::Backspace);
+        }
+        type_text(&mut app, "xxxyg");
+        press(&mut app, KeyCode::Enter);
+
+        assert!(app.overlay.is_none());
+        assert_eq!(
+            app.solver.guesses()[0].feedback,
+            parse_pattern("XXXYG").unwrap()
+        );
+        assert_eq!(
+            app.solver.filter(&app.solution_words),
+            vec![app.solution_words.index_of("stone").unwrap()]
+        );
+        assert!(app.input.is_empty());
+    }
+
+    #[test]
+    fn test_edit_guess_incomplete_pattern_keeps_popup_open() {
+        let mut app = app_with_mistyped_pattern();
+
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL));
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Enter);
+
+        assert!(matches!(app.overlay, Some(Overlay::EditGuess(_))));
+        assert_eq!(
+            app.solver.guesses()[1].feedback,
+            parse_pattern("XYXYG").unwrap()
+        );
+
+        press(&mut app, KeyCode::Esc);
+        press(&mut app, KeyCode::Esc);
+        assert!(app.overlay.is_none());
+    }
+
+    #[test]
+    fn test_edit_guess_requires_guesses() {
+        let mut app = create_test_app();
+
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL));
+
+        assert!(app.overlay.is_none());
+    }
+}
+
+#[cfg(test)]
+mod luck_tests {
+    use super::*;
+    use crate::ui::history::HistoryStats;
+
+    fn start_game_with_target(target: &str) -> App {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some(target.to_string());
+        app
+    }
+
+    #[test]
+    fn test_guess_luck_against_expected_pool() {
+        // HOUSE leaves crane and apple sharing XXXXG and every other word on its
+        // own, so the expected pool afterwards is (6·1² + 2²) / 8 = 1.25
+        let mut lucky = start_game_with_target("stone");
+        GameHandler::new(&mut lucky).submit_guess("house".to_string());
+        assert_eq!(lucky.game_luck, vec![0.25]);
+
+        let mut unlucky = start_game_with_target("apple");
+        GameHandler::new(&mut unlucky).submit_guess("house".to_string());
+        assert_eq!(unlucky.game_luck, vec![-0.75]);
+    }
+
+    #[test]
+    fn test_luck_recorded_with_game_and_averaged() {
+        let mut app = start_game_with_target("stone");
+
+        GameHandler::new(&mut app).submit_guess("house".to_string());
+        GameHandler::new(&mut app).submit_guess("stone".to_string());
+
+        let games = app.db.load_games().unwrap();
+        let lucks: Vec<Option<f64>> = games[0].guesses.iter().map(|g| g.luck).collect();
+        assert_eq!(lucks, vec![Some(0.25), Some(0.0)]);
+
+        let stats = HistoryStats::from_games(&games);
+        assert_eq!(stats.average_luck, Some(0.125));
+    }
+
+    #[test]
+    fn test_new_game_resets_luck() {
+        let mut app = start_game_with_target("stone");
+        GameHandler::new(&mut app).submit_guess("house".to_string());
+
+        GameHandler::new(&mut app).start_new_game();
+
+        assert!(app.game_luck.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod history_cursor_tests {
+    use super::*;
+    use crate::ui::history::{GameOutcome, GameRecord};
+    use chrono::{Duration, TimeZone, Utc};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn game(day: i64, word: &str, outcome: GameOutcome) -> GameRecord {
+        GameRecord {
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap() + Duration::days(day),
+            target_word: word.to_string(),
+            guesses: vec![],
+            outcome,
+            blitz: false,
+            duration_secs: None,
+            app_version: None,
+            difficulty: crate::settings::Difficulty::Normal,
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        }
+    }
+
+    /// Wins only, fewest guesses first, newest first among ties.
+    fn create_arranged_app() -> App {
+        let mut data = HistoryData::new(
+            vec![
+                game(0, "stone", GameOutcome::Won { guesses: 4 }),
+                game(1, "raise", GameOutcome::Lost),
+                game(2, "slate", GameOutcome::Won { guesses: 2 }),
+                game(3, "crane", GameOutcome::Won { guesses: 4 }),
+                game(4, "house", GameOutcome::Lost),
+                game(5, "apple", GameOutcome::Won { guesses: 3 }),
+            ],
+            Vec::new(),
+        );
+        data.arrange(
+            |g| !g.was_lost(),
+            |a, b| {
+                let guesses = |g: &GameRecord| match g.outcome {
+                    GameOutcome::Won { guesses } => guesses,
+                    _ => 0,
+                };
+                guesses(a)
+                    .cmp(&guesses(b))
+                    .then(b.timestamp.cmp(&a.timestamp))
+            },
+        );
+
+        let mut app = create_test_app();
+        app.mode = GameMode::History;
+        app.history_view_mode = HistoryViewMode::List;
+        app.history_data = Some(data);
+        app
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn selected_word(app: &App) -> String {
+        let data = app.history_data.as_ref().unwrap();
+        data.selected_game().unwrap().target_word.clone()
+    }
+
+    #[test]
+    fn test_list_shows_arranged_order() {
+        let app = create_arranged_app();
+        let data = app.history_data.as_ref().unwrap();
+
+        let words: Vec<_> = data
+            .games_for_page(0)
+            .iter()
+            .map(|g| g.target_word.as_str())
+            .collect();
+        assert_eq!(words, ["slate", "apple", "crane", "stone"]);
+        assert_eq!(data.total_pages(), 1);
+    }
+
+    #[test]
+    fn test_next_and_prev_follow_arranged_order() {
+        let mut app = create_arranged_app();
+        press(&mut app, KeyCode::Char('1'));
+        assert_eq!(app.history_view_mode, HistoryViewMode::Detail);
+
+        let mut visited = vec![selected_word(&app)];
+        for _ in 0..3 {
+            press(&mut app, KeyCode::Right);
+            visited.push(selected_word(&app));
+        }
+        assert_eq!(visited, ["slate", "apple", "crane", "stone"]);
+
+        press(&mut app, KeyCode::Char('p'));
+        assert_eq!(selected_word(&app), "crane");
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(selected_word(&app), "stone");
+    }
+
+    #[test]
+    fn test_navigation_clamps_at_ends() {
+        let mut app = create_arranged_app();
+        press(&mut app, KeyCode::Char('4'));
+
+        press(&mut app, KeyCode::Right);
+        assert_eq!(selected_word(&app), "stone");
+        assert!(
+            app.logs
+                .lines()
+                .contains(&"Already at the last game".to_string())
+        );
+
+        press(&mut app, KeyCode::Home);
+        press(&mut app, KeyCode::Left);
+        assert_eq!(selected_word(&app), "slate");
+        assert!(
+            app.logs
+                .lines()
+                .contains(&"Already at the first game".to_string())
+        );
+    }
+
+    #[test]
+    fn test_home_and_end_jump() {
+        let mut app = create_arranged_app();
+        press(&mut app, KeyCode::Char('2'));
+
+        press(&mut app, KeyCode::End);
+        assert_eq!(selected_word(&app), "stone");
+        press(&mut app, KeyCode::Home);
+        assert_eq!(selected_word(&app), "slate");
+    }
+
+    #[test]
+    fn test_arrange_keeps_selection() {
+        let mut app = create_arranged_app();
+        press(&mut app, KeyCode::Char('3'));
+        let data = app.history_data.as_mut().unwrap();
+
+        // Back to all games in stored order: crane moves from 3rd to 4th
+        data.arrange(|_| true, |a, b| a.timestamp.cmp(&b.timestamp));
+        assert_eq!(data.selected_game().unwrap().target_word, "crane");
+        assert_eq!(data.cursor.position(), Some(3));
+
+        press(&mut app, KeyCode::Right);
+        assert_eq!(selected_word(&app), "house");
+    }
+
+    #[test]
+    fn test_return_to_list_shows_page_of_last_viewed_game() {
+        let mut app = create_test_app();
+        let games = (0..25)
+            .map(|day| game(day, "stone", GameOutcome::Won { guesses: 3 }))
+            .collect();
+        app.mode = GameMode::History;
+        app.history_view_mode = HistoryViewMode::List;
+        app.history_data = Some(HistoryData::new(games, Vec::new()));
+
+        press(&mut app, KeyCode::Char('1'));
+        press(&mut app, KeyCode::End);
+        press(&mut app, KeyCode::Esc);
+
+        assert_eq!(app.history_view_mode, HistoryViewMode::List);
+        assert_eq!(app.history_page, 2);
+    }
+}
+
+#[cfg(test)]
+mod confirm_discard_tests {
+    use super::*;
+    use crate::ui::history::{AbandonReason, GameOutcome};
+    use crate::ui::types::{Overlay, PendingAction};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn ctrl(app: &mut App, c: char) {
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+    }
+
+    fn pending(app: &App) -> Option<(PendingAction, &str)> {
+        match &app.overlay {
+            Some(Overlay::ConfirmDiscard(state)) => Some((state.action, state.prompt.as_str())),
+            _ => None,
+        }
+    }
+
+    fn game_in_progress() -> App {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("stone".to_string());
+        for word in ["raise", "slate", "crane"] {
+            GameHandler::new(&mut app).submit_guess(word.to_string());
+        }
+        app
+    }
+
+    #[test]
+    fn test_no_guesses_skips_prompt() {
+        let mut app = create_test_app();
+        app.mode = GameMode::Solver;
+
+        ctrl(&mut app, 'g');
+        assert_eq!(app.mode, GameMode::Game);
+        assert!(app.overlay.is_none());
+
+        ctrl(&mut app, 's');
+        assert_eq!(app.mode, GameMode::Solver);
+        assert!(app.overlay.is_none());
+        assert!(app.db.load_games().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_confirm_abandons_game_and_records_switch() {
+        let mut app = game_in_progress();
+
+        ctrl(&mut app, 's');
+        assert_eq!(
+            pending(&app),
+            Some((
+                PendingAction::SwitchToSolver,
+                "You have an unfinished game with 3 guesses — abandon it? y/N"
+            ))
+        );
+        assert_eq!(app.mode, GameMode::Game);
+        assert!(app.db.load_games().unwrap().is_empty());
+
+        press(&mut app, KeyCode::Char('y'));
+        assert!(app.overlay.is_none());
+        assert_eq!(app.mode, GameMode::Solver);
+
+        let games = app.db.load_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(
+            games[0].outcome,
+            GameOutcome::Abandoned(AbandonReason::Switched)
+        );
+        assert_eq!(games[0].guess_count(), 3);
+    }
+
+    #[test]
+    fn test_cancel_keeps_game() {
+        let mut app = game_in_progress();
+
+        ctrl(&mut app, 't');
+        // Only y/n/Esc resolve the prompt
+        press(&mut app, KeyCode::Char('x'));
+        assert!(pending(&app).is_some());
+
+        press(&mut app, KeyCode::Esc);
+        assert!(app.overlay.is_none());
+        assert_eq!(app.mode, GameMode::Game);
+        assert_eq!(app.solver.guesses().len(), 3);
+        assert!(app.blitz.is_none());
+        assert!(app.db.load_games().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_solver_session_prompt() {
+        let mut app = create_test_app();
+        app.mode = GameMode::Solver;
+        SolverHandler::new(&mut app).submit_guess("crane".to_string(), vec![Feedback::Gray; 5]);
+
+        ctrl(&mut app, 'g');
+        assert_eq!(
+            pending(&app),
+            Some((
+                PendingAction::ToggleGameMode,
+                "You have an unfinished solver session with 1 guess — abandon it? y/N"
+            ))
+        );
+
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(app.mode, GameMode::Solver);
+        assert_eq!(app.solver.guesses().len(), 1);
+
+        // History only pauses the session, so it needs no confirmation
+        ctrl(&mut app, 'r');
+        assert!(app.overlay.is_none());
+        assert_eq!(app.mode, GameMode::History);
+    }
+
+    #[test]
+    fn test_setting_disables_prompt() {
+        let mut app = game_in_progress();
+        app.settings.confirm_discard = false;
+
+        ctrl(&mut app, 'g');
+
+        assert!(app.overlay.is_none());
+        assert_eq!(app.mode, GameMode::Solver);
+        assert_eq!(app.db.load_games().unwrap().len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod blacklist_tests {
+    use super::*;
+    use crate::storage::Database;
+    use crate::ui::types::Overlay;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn app_with_db(db: Database) -> App {
+        AppBuilder::for_tests().db(db).build().unwrap()
+    }
+
+    /// Leaves stone, house, and world as the candidates.
+    fn guess_magic(app: &mut App) {
+        SolverHandler::new(app).submit_guess("magic".to_string(), vec![Feedback::Gray; 5]);
+    }
+
+    fn suggested(app: &App) -> Vec<String> {
+        app.suggested_words().map(|(w, _)| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_scores_reused_while_pool_unchanged() {
+        let mut app = create_test_app();
+        guess_magic(&mut app);
+        let mut before = suggested(&app);
+
+        // Recomputing the same pool reads the stored scores back
+        let pool = app.solver.filter(&app.solution_words);
+        let mut scores = app.scored_pools.get(&pool).unwrap().to_vec();
+        scores.reverse();
+        app.scored_pools.insert(&pool, scores);
+        SolverHandler::new(&mut app).refresh_derived_state();
+        before.reverse();
+        assert_eq!(suggested(&app), before);
+
+        // The blacklist still applies to reused scores
+        app.session_blacklist.insert(before[0].clone());
+        SolverHandler::new(&mut app).refresh_derived_state();
+        assert_eq!(suggested(&app), before[1..]);
+    }
+
+    #[test]
+    fn test_blacklisted_word_excluded_from_scoring() {
+        let mut app = create_test_app();
+        guess_magic(&mut app);
+        let before = suggested(&app);
+        assert_eq!(before.len(), 3);
+
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char('x'));
+
+        let top = &before[0];
+        assert!(app.session_blacklist.contains(top));
+        assert_eq!(suggested(&app), before[1..]);
+
+        // Later scoring passes keep leaving it out
+        SolverHandler::new(&mut app).refresh_derived_state();
+        assert!(!suggested(&app).contains(top));
+        assert!(app.db.load_blacklist().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_optimal_word_skips_blacklisted() {
+        let mut app = create_test_app();
+        guess_magic(&mut app);
+        let top = suggested(&app)[0].clone();
+        app.session_blacklist.insert(top.clone());
+
+        SolverHandler::new(&mut app).submit_guess("house".to_string(), vec![Feedback::Gray; 5]);
+
+        let last = app.solver_session_guesses.last().unwrap();
+        assert_ne!(last.optimal_word.as_deref(), Some(top.as_str()));
+    }
+
+    #[test]
+    fn test_permanent_blacklist_reloads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.db");
+        let path = path.to_str().unwrap();
+
+        let mut app = app_with_db(Database::open(path).unwrap());
+        guess_magic(&mut app);
+        let (session_only, saved) = (suggested(&app)[0].clone(), suggested(&app)[1].clone());
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char('x'));
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Char('X'), KeyModifiers::SHIFT));
+        assert_eq!(app.session_blacklist.len(), 2);
+        drop(app);
+
+        let mut app = app_with_db(Database::open(path).unwrap());
+        assert_eq!(app.session_blacklist.len(), 1);
+        assert!(app.session_blacklist.contains(&saved));
+
+        guess_magic(&mut app);
+        assert!(suggested(&app).contains(&session_only));
+        assert!(!suggested(&app).contains(&saved));
+    }
+
+    #[test]
+    fn test_unblacklist_from_overlay() {
+        let mut app = create_test_app();
+        guess_magic(&mut app);
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char('b'));
+        assert!(app.overlay.is_none());
+
+        app.session_blacklist.insert("house".to_string());
+        app.db.add_to_blacklist("house").unwrap();
+        app.session_blacklist.insert("stone".to_string());
+        SolverHandler::new(&mut app).refresh_derived_state();
+        assert_eq!(suggested(&app), ["world"]);
+
+        press(&mut app, KeyCode::Char('b'));
+        assert!(matches!(
+            app.overlay,
+            Some(Overlay::Blacklist { selected: 0 })
+        ));
+
+        press(&mut app, KeyCode::Enter);
+        assert!(!app.session_blacklist.contains("house"));
+        assert!(app.db.load_blacklist().unwrap().is_empty());
+        assert_eq!(suggested(&app).len(), 2);
+
+        press(&mut app, KeyCode::Enter);
+        assert!(app.session_blacklist.is_empty());
+        assert!(app.overlay.is_none());
+        assert_eq!(suggested(&app).len(), 3);
+    }
+}
+
+#[cfg(test)]
+mod snapshot_tests {
+    use super::*;
+    use crate::solver::SolverState;
+    use crate::ui::types::{Overlay, SnapshotState, SnapshotStep};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn type_str(app: &mut App, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+    }
+
+    fn open_snapshots(app: &mut App) {
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::CONTROL));
+    }
+
+    fn snapshot_state(app: &App) -> &SnapshotState {
+        match &app.overlay {
+            Some(Overlay::Snapshots(state)) => state,
+            other => panic!("expected snapshot overlay, got {:?}", other),
+        }
+    }
+
+    fn guess(app: &mut App, word: &str, pattern: &str) {
+        let feedback = crate::solver::parse_pattern(pattern).unwrap();
+        SolverHandler::new(app).submit_guess(word.to_string(), feedback);
+    }
+
+    fn save_as(app: &mut App, name: &str) {
+        open_snapshots(app);
+        press(app, KeyCode::Char('s'));
+        type_str(app, name);
+        press(app, KeyCode::Enter);
+        press(app, KeyCode::Esc);
+    }
+
+    fn guess_words(app: &App) -> Vec<String> {
+        app.solver
+            .guesses()
+            .iter()
+            .map(|g| g.word.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let mut app = create_test_app();
+        guess(&mut app, "magic", "XXXXX");
+        let saved_suggestions = app.suggestions.clone();
+        save_as(&mut app, "alice");
+        assert!(app.overlay.is_none());
+
+        SolverHandler::new(&mut app).undo_guess();
+        guess(&mut app, "apple", "XXXXG");
+        assert!(!app.solver_session_guesses.is_empty());
+
+        open_snapshots(&mut app);
+        assert_eq!(snapshot_state(&app).snapshots[0].name, "alice");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(snapshot_state(&app).step, SnapshotStep::ConfirmLoad);
+        press(&mut app, KeyCode::Char('y'));
+
+        assert!(app.overlay.is_none());
+        assert_eq!(guess_words(&app), ["magic"]);
+        assert_eq!(app.suggestions, saved_suggestions);
+        assert_eq!(app.entropy_history.len(), 1);
+        // Loaded guesses are not attributed to the new session
+        assert!(app.solver_session_active);
+        assert!(app.solver_session_guesses.is_empty());
+    }
+
+    #[test]
+    fn test_cancel_load_keeps_state() {
+        let mut app = create_test_app();
+        guess(&mut app, "magic", "XXXXX");
+        save_as(&mut app, "alice");
+        SolverHandler::new(&mut app).undo_guess();
+        guess(&mut app, "apple", "XXXXG");
+
+        open_snapshots(&mut app);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('n'));
+
+        assert_eq!(snapshot_state(&app).step, SnapshotStep::Browse);
+        assert_eq!(guess_words(&app), ["apple"]);
+    }
+
+    #[test]
+    fn test_load_without_guesses_skips_confirmation() {
+        let mut app = create_test_app();
+        guess(&mut app, "magic", "XXXXX");
+        save_as(&mut app, "alice");
+        SolverHandler::new(&mut app).undo_guess();
+
+        open_snapshots(&mut app);
+        press(&mut app, KeyCode::Enter);
+
+        assert!(app.overlay.is_none());
+        assert_eq!(guess_words(&app), ["magic"]);
+    }
+
+    #[test]
+    fn test_list_and_delete() {
+        let mut app = create_test_app();
+        guess(&mut app, "magic", "XXXXX");
+        save_as(&mut app, "alice");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        save_as(&mut app, "bob");
+
+        open_snapshots(&mut app);
+        let names: Vec<_> = snapshot_state(&app)
+            .snapshots
+            .iter()
+            .map(|s| s.name.clone())
+            .collect();
+        assert_eq!(names, ["bob", "alice"]);
+
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Char('d'));
+        let state = snapshot_state(&app);
+        assert_eq!(state.snapshots.len(), 1);
+        assert_eq!(state.snapshots[0].name, "bob");
+        assert_eq!(state.selected, 0);
+        assert_eq!(app.db.load_snapshots().unwrap().len(), 1);
+
+        press(&mut app, KeyCode::Delete);
+        assert!(snapshot_state(&app).snapshots.is_empty());
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(guess_words(&app), ["magic"]);
+    }
+
+    #[test]
+    fn test_load_rejects_other_word_length() {
+        let mut app = create_test_app();
+        app.db.save_snapshot("six", &SolverState::new(6)).unwrap();
+
+        open_snapshots(&mut app);
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.solver.word_len(), 5);
+        assert_eq!(snapshot_state(&app).snapshots.len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod spoiler_tests {
+    use super::*;
+    use crate::ui::test_support::FIXTURE_WORDS;
+    use std::time::{Duration, Instant};
+
+    fn mentions(app: &App, word: &str) -> bool {
+        app.logs.lines().iter().any(|line| line.contains(word))
+    }
+
+    fn start_game() -> (App, String) {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        let target = app.target_word.clone().unwrap();
+        (app, target)
+    }
+
+    #[test]
+    fn test_target_hidden_until_loss() {
+        let (mut app, target) = start_game();
+        assert!(!mentions(&app, &target));
+
+        let misses = FIXTURE_WORDS.iter().filter(|&&w| w != target).take(6);
+        for word in misses {
+            assert!(!app.game_over);
+            assert!(!mentions(&app, &target));
+            GameHandler::new(&mut app).submit_guess(word.to_string());
+        }
+
+        assert!(app.game_over);
+        assert!(mentions(&app, &format!("Target word was {}", target)));
+        // History comes from the database, not the log
+        assert_eq!(app.db.load_games().unwrap()[0].target_word, target);
+    }
+
+    #[test]
+    fn test_target_revealed_once_on_win() {
+        let (mut app, target) = start_game();
+
+        GameHandler::new(&mut app).submit_guess(target.clone());
+
+        let reveals = app
+            .logs
+            .lines()
+            .iter()
+            .filter(|line| **line == format!("Target word was {}", target))
+            .count();
+        assert_eq!(reveals, 1);
+        assert!(app.game_won);
+    }
+
+    #[test]
+    fn test_target_revealed_on_blitz_timeout() {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_blitz_game();
+        let target = app.target_word.clone().unwrap();
+
+        let mut now = Instant::now();
+        while !app.game_over {
+            assert!(!mentions(&app, &target));
+            now += Duration::from_secs(app.settings.blitz_seconds + 1);
+            GameHandler::new(&mut app).tick(now);
+        }
+
+        assert!(mentions(&app, &format!("Target word was {}", target)));
+    }
+
+    #[test]
+    fn test_target_revealed_on_abandon() {
+        let (mut app, target) = start_game();
+        let miss = FIXTURE_WORDS.iter().find(|&&w| w != target).unwrap();
+        GameHandler::new(&mut app).submit_guess(miss.to_string());
+        assert!(!mentions(&app, &target));
+
+        GameHandler::new(&mut app).abandon_game(crate::ui::history::AbandonReason::Switched);
+
+        assert!(mentions(&app, &format!("Target word was {}", target)));
+    }
+}
+
+#[cfg(test)]
+mod panel_size_tests {
+    use super::*;
+    use crate::ui::{
+        handlers::SuggestionHandler,
+        history::{GameOutcome, GameRecord},
+        test_support::buffer_lines,
+    };
+    use chrono::{Duration, TimeZone, Utc};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    /// Render with `draw` into a 100-column terminal of `height` rows and return its text.
+    fn render(app: &App, height: u16, draw: fn(&App, &mut ratatui::Frame)) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(100, height)).unwrap();
+        terminal.draw(|f| draw(app, f)).unwrap();
+        buffer_lines(terminal.backend().buffer())
+    }
+
+    fn draw_suggestions(app: &App, f: &mut ratatui::Frame) {
+        app.draw_suggestions(f, f.area());
+    }
+
+    fn draw_stats(app: &App, f: &mut ratatui::Frame) {
+        app.draw_stats_view(f, f.area());
+    }
+
+    /// Every fixture word suggested, as if scoring had run.
+    fn suggestion_app() -> App {
+        let mut app = create_test_app();
+        app.suggestions = (0..8).map(|i| (i, 100 - i as usize)).collect();
+        app
+    }
+
+    fn count_suggestion_rows(app: &App, lines: &[String]) -> usize {
+        let words: Vec<&str> = app.suggested_words().map(|(w, _)| w).collect();
+        lines
+            .iter()
+            .filter(|line| words.iter().any(|w| line.contains(&format!("{w} ("))))
+            .count()
+    }
+
+    #[test]
+    fn test_short_panel_truncates_with_footer() {
+        let app = suggestion_app();
+        let lines = render(&app, 6, draw_suggestions);
+
+        // Four inner rows: three suggestions and the footer
+        assert_eq!(count_suggestion_rows(&app, &lines), 3);
+        assert!(lines.iter().any(|line| line.contains("… and 5 more")));
+        assert_eq!(app.suggestion_rows.get(), 3);
+    }
+
+    #[test]
+    fn test_tall_panel_lists_everything() {
+        let app = suggestion_app();
+        let lines = render(&app, 14, draw_suggestions);
+
+        assert_eq!(count_suggestion_rows(&app, &lines), 8);
+        assert!(!lines.iter().any(|line| line.contains("more")));
+        assert_eq!(app.suggestion_rows.get(), 8);
+    }
+
+    #[test]
+    fn test_selection_limited_to_drawn_rows() {
+        let mut app = suggestion_app();
+        render(&app, 6, draw_suggestions);
+
+        let mut handler = SuggestionHandler::new(&mut app);
+        handler.toggle_selection();
+        handler.move_selection(10);
+        assert_eq!(app.suggestion_selected, Some(2));
+
+        render(&app, 14, draw_suggestions);
+        SuggestionHandler::new(&mut app).move_selection(10);
+        assert_eq!(app.suggestion_selected, Some(7));
+    }
+
+    fn history_app(games: i64) -> App {
+        let records = (0..games)
+            .map(|day| GameRecord {
+                timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap()
+                    + Duration::days(day),
+                target_word: "stone".to_string(),
+                guesses: vec![],
+                outcome: GameOutcome::Lost,
+                blitz: false,
+                duration_secs: None,
+                app_version: None,
+                difficulty: crate::settings::Difficulty::Normal,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty: None,
+            })
+            .collect();
+        let mut app = create_test_app();
+        app.history_data = Some(HistoryData::new(records, vec![]));
+        app
+    }
+
+    #[test]
+    fn test_recent_games_fill_panel() {
+        let app = history_app(30);
+
+        // Stats and distribution take 20 rows; the rest holds the recent games
+        let short = render(&app, 28, draw_stats);
+        assert!(short.iter().any(|l| l.contains("Recent Games (Latest 4)")));
+        assert_eq!(short.iter().filter(|l| l.contains("stone")).count(), 4);
+
+        let tall = render(&app, 44, draw_stats);
+        assert!(tall.iter().any(|l| l.contains("Recent Games (Latest 20)")));
+    }
+}
+
+#[cfg(test)]
+mod version_tests {
+    use super::*;
+    use crate::storage::APP_VERSION;
+
+    #[test]
+    fn test_finished_game_records_version() {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        let target = app.target_word.clone().unwrap();
+        GameHandler::new(&mut app).submit_guess(target);
+        assert!(app.game_won);
+
+        HistoryHandler::new(&mut app).enter_history_mode();
+        let data = app.history_data.as_ref().unwrap();
+        assert_eq!(data.games[0].app_version.as_deref(), Some(APP_VERSION));
+
+        let averages = &data.game_version_averages;
+        assert_eq!(averages.len(), 1);
+        assert_eq!(averages[0].app_version.as_deref(), Some(APP_VERSION));
+        assert_eq!(averages[0].average_guesses, 1.0);
+    }
+}
+
+#[cfg(test)]
+mod end_to_end_tests {
+    use super::*;
+    use crate::ui::{
+        history::GameOutcome,
+        test_support::{ScriptedEvents, buffer_lines},
+    };
+    use crossterm::event::KeyCode;
+    use ratatui::{Terminal, backend::TestBackend};
+    use std::time::Duration;
+
+    /// Fixture words as guesses, but STONE is always the answer.
+    fn stone_app() -> App {
+        AppBuilder::for_tests()
+            .solutions(vec!["stone".to_string()])
+            .build()
+            .unwrap()
+    }
+
+    /// Run `app` through `script` and return the last frame drawn before it quit.
+    fn run(app: &mut App, script: ScriptedEvents) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(140, 45)).unwrap();
+        app.run(&mut terminal, &mut { script }).unwrap();
+        buffer_lines(terminal.backend().buffer()).join("\n")
+    }
+
+    #[test]
+    fn test_win_game_then_review_it_in_history() {
+        let mut app = stone_app();
+
+        let frame = run(
+            &mut app,
+            ScriptedEvents::new()
+                .ctrl('g')
+                .line("crane")
+                .line("stone")
+                .ctrl('q'),
+        );
+        assert!(app.game_won);
+        assert!(frame.contains("You Won! The word was: STONE"));
+
+        let frame = run(
+            &mut app,
+            ScriptedEvents::new()
+                .ctrl('r')
+                .press(KeyCode::Tab)
+                .press(KeyCode::Char('1'))
+                .ctrl('q'),
+        );
+        assert_eq!(app.mode, GameMode::History);
+        assert!(frame.contains("Game 1/1"));
+        assert!(frame.contains("Won in 2 guess(es)"));
+
+        let games = app.db.load_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].target_word, "stone");
+        assert_eq!(games[0].outcome, GameOutcome::Won { guesses: 2 });
+        let words: Vec<&str> = games[0].guesses.iter().map(|g| g.word.as_str()).collect();
+        assert_eq!(words, ["crane", "stone"]);
+    }
+
+    #[test]
+    fn test_blitz_countdown_forfeits_while_idle() {
+        let mut app = stone_app();
+        let turn = Duration::from_secs(app.settings.blitz_seconds + 1);
+
+        let script = (0..6).fold(ScriptedEvents::new().ctrl('t'), |script, _| {
+            script.idle(turn)
+        });
+        let frame = run(&mut app, script.ctrl('q'));
+
+        assert!(app.game_over);
+        assert!(!app.game_won);
+        assert!(frame.contains("Game Over! The word was: STONE"));
+        assert!(frame.contains("Timed out: 6"));
+
+        let games = app.db.load_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert!(games[0].blitz);
+        assert_eq!(games[0].outcome, GameOutcome::Lost);
+        assert_eq!(games[0].guesses.len(), 6);
+        assert!(games[0].guesses.iter().all(|g| g.timed_out));
+    }
+
+    #[test]
+    fn test_solver_session_completes_and_is_saved() {
+        let mut app = create_test_app();
+
+        let frame = run(
+            &mut app,
+            ScriptedEvents::new()
+                .line("magic xxxxx")
+                .line("stone ggggg")
+                .ctrl('q'),
+        );
+        // A fresh session starts with the full pool
+        assert!(app.solver.guesses().is_empty());
+        assert!(frame.contains("Total: 8 remaining"));
+        assert!(
+            app.logs
+                .lines()
+                .iter()
+                .any(|l| l == "Solver session completed: 2 guesses")
+        );
+
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        let words: Vec<&str> = sessions[0]
+            .guesses
+            .iter()
+            .map(|g| g.word.as_str())
+            .collect();
+        assert_eq!(words, ["magic", "stone"]);
+    }
+
+    #[test]
+    fn test_script_running_out_is_an_error() {
+        let mut app = create_test_app();
+        let mut terminal = Terminal::new(TestBackend::new(140, 45)).unwrap();
+
+        let result = app.run(
+            &mut terminal,
+            &mut ScriptedEvents::new().line("magic xxxxx"),
+        );
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod transcript_tests {
+    use super::*;
+    use crate::ui::test_support::{ScriptedEvents, buffer_lines};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    /// Leaves stone and house.
+    const TRANSCRIPT: &str = "magic xxxxx\napple XXXXG\n";
+
+    #[test]
+    fn test_paste_imports_all_lines() {
+        let mut app = create_test_app();
+
+        InputHandler::new(&mut app).handle_paste(TRANSCRIPT);
+
+        let words: Vec<&str> = app
+            .solver
+            .guesses()
+            .iter()
+            .map(|g| g.word.as_str())
+            .collect();
+        assert_eq!(words, ["magic", "apple"]);
+        assert_eq!(app.solver_session_guesses.len(), 2);
+        assert_eq!(app.entropy_history.len(), 2);
+        assert!(app.input.is_empty());
+        let logs = app.logs.lines();
+        assert_eq!(logs.last().unwrap(), "Imported 2 guesses, pool now 2");
+    }
+
+    #[test]
+    fn test_imported_guesses_saved_with_session() {
+        let mut app = create_test_app();
+        InputHandler::new(&mut app).handle_paste(TRANSCRIPT);
+
+        SolverHandler::new(&mut app).submit_guess("stone".to_string(), vec![Feedback::Green; 5]);
+
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        let words: Vec<&str> = sessions[0]
+            .guesses
+            .iter()
+            .map(|g| g.word.as_str())
+            .collect();
+        assert_eq!(words, ["magic", "apple", "stone"]);
+        assert_eq!(sessions[0].guesses[1].pool_size_after, 2);
+    }
+
+    #[test]
+    fn test_bad_line_rejects_whole_paste() {
+        let mut app = create_test_app();
+
+        InputHandler::new(&mut app).handle_paste("magic xxxxx\nzzzzz XXXXG\nstone GGGGG");
+
+        assert!(app.solver.guesses().is_empty());
+        assert!(app.solver_session_guesses.is_empty());
+        let error = app.paste_error.clone().unwrap();
+        assert!(error.starts_with("line 2:"), "{}", error);
+
+        // The message stays until the next key press
+        InputHandler::new(&mut app).handle_key(crossterm::event::KeyEvent::new(
+            crossterm::event::KeyCode::Char('s'),
+            crossterm::event::KeyModifiers::NONE,
+        ));
+        assert_eq!(app.paste_error, None);
+    }
+
+    #[test]
+    fn test_single_line_paste_fills_input() {
+        let mut app = create_test_app();
+
+        InputHandler::new(&mut app).handle_paste("crane XXYXG\n");
+
+        assert_eq!(app.input, "crane XXYXG");
+        assert!(app.solver.guesses().is_empty());
+    }
+
+    #[test]
+    fn test_paste_event_shows_error_in_input_title() {
+        let mut app = create_test_app();
+        let mut terminal = Terminal::new(TestBackend::new(140, 45)).unwrap();
+
+        let mut script = ScriptedEvents::new()
+            .paste("magic xxxxx\nmagic xxxx")
+            .ctrl('q');
+        app.run(&mut terminal, &mut script).unwrap();
+
+        let frame = buffer_lines(terminal.backend().buffer()).join("\n");
+        assert!(frame.contains("Input paste line 2: length mismatch"));
+    }
+
+    #[test]
+    fn test_builder_imports_transcript() {
+        let app = AppBuilder::for_tests()
+            .transcript(TRANSCRIPT.to_string())
+            .build()
+            .unwrap();
+        assert_eq!(app.solver.guesses().len(), 2);
+
+        let result = AppBuilder::for_tests()
+            .transcript("magic xxxxx\nmagic".to_string())
+            .build();
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod optimal_word_tests {
+    use super::*;
+
+    #[test]
+    fn test_single_candidate_is_optimal_with_zero_deviation() {
+        let mut app = create_test_app();
+
+        // HOUSE leaves only STONE, so CRANE is played against a pool of one
+        let transcript = "apple XXXXG\nhouse XYXYG\ncrane XXXGG\nstone GGGGG";
+        InputHandler::new(&mut app).handle_paste(transcript);
+
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        let guesses = &sessions[0].guesses;
+        assert_eq!(guesses[2].pool_size_before, 1);
+        for last in &guesses[2..] {
+            assert_eq!(last.optimal_word.as_deref(), Some("stone"));
+            assert_eq!(last.deviation_score, Some(0.0));
+        }
+    }
+
+    #[test]
+    fn test_empty_pool_skips_deviation() {
+        let mut app = create_test_app();
+
+        // No fixture word avoids every letter of STONE and MAGIC
+        InputHandler::new(&mut app).handle_paste("magic xxxxx\nstone XXXXX\ncrane XXXXX");
+
+        let guesses = &app.solver_session_guesses;
+        assert_eq!(guesses.len(), 3);
+        assert_eq!(guesses[1].pool_size_after, 0);
+        assert!(guesses[1].deviation_score.is_some());
+        assert_eq!(guesses[2].pool_size_before, 0);
+        assert_eq!(guesses[2].optimal_word, None);
+        assert_eq!(guesses[2].optimal_entropy, None);
+        assert_eq!(guesses[2].deviation_score, None);
+    }
+}
+
+#[cfg(test)]
+mod placement_tests {
+    use super::*;
+    use crate::{solver::parse_pattern, ui::test_support::buffer_lines};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    /// The constraints panel after a single `word` guess with `pattern` feedback.
+    fn panel_after(word: &str, pattern: &str) -> String {
+        let mut app = create_test_app();
+        let feedback = parse_pattern(pattern).unwrap();
+        app.solver
+            .add_guess(Guess::new(word.to_string(), feedback))
+            .unwrap();
+        let mut handler = SolverHandler::new(&mut app);
+        handler.refresh_derived_state();
+        handler.recompute_analysis();
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 12)).unwrap();
+        terminal
+            .draw(|f| app.draw_constraint_summary(f, f.area()))
+            .unwrap();
+        buffer_lines(terminal.backend().buffer()).join("\n")
+    }
+
+    #[test]
+    fn test_open_positions_listed_for_yellow() {
+        // RAISE and MAGIC have A second, SLATE and CRANE third
+        let panel = panel_after("axxxx", "YXXXX");
+
+        assert!(panel.contains("a(1) from 'AXXXX'"));
+        assert!(panel.contains("a → can be 2,3"));
+    }
+
+    #[test]
+    fn test_single_open_position_is_derived_green() {
+        // Only STONE is left, so both yellows of HOUSE have one place to go
+        let panel = panel_after("house", "XYXYG");
+
+        assert!(panel.contains("o → must be position 3"));
+        assert!(panel.contains("s → must be position 1"));
+    }
+
+    #[test]
+    fn test_capped_letter_listed_apart_from_grays() {
+        let panel = panel_after("geese", "XYXXG");
+
+        assert!(panel.contains("E ×2 only"));
+        let grays = panel.lines().find(|l| l.contains('✗')).unwrap();
+        assert!(grays.contains("g s"));
+        assert!(!grays.contains('e'));
+    }
+}
+
+#[cfg(test)]
+mod analysis_logging_tests {
+    use super::*;
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+    use tracing::{Event, Level, Subscriber};
+    use tracing_subscriber::{
+        Layer,
+        filter::LevelFilter,
+        layer::{Context, SubscriberExt},
+        registry::Registry,
+    };
+
+    /// Counts events at info and debug level.
+    #[derive(Clone, Default)]
+    struct CountingLayer {
+        info: Arc<AtomicUsize>,
+        debug: Arc<AtomicUsize>,
+    }
+
+    impl<S: Subscriber> Layer<S> for CountingLayer {
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            match *event.metadata().level() {
+                Level::INFO => self.info.fetch_add(1, Ordering::SeqCst),
+                Level::DEBUG => self.debug.fetch_add(1, Ordering::SeqCst),
+                _ => 0,
+            };
+        }
+    }
+
+    /// Run `f` with `layer` installed and events up to `max_level` enabled.
+    fn with_counting(layer: &CountingLayer, max_level: LevelFilter, f: impl FnOnce()) {
+        let subscriber = Registry::default().with(max_level).with(layer.clone());
+        tracing::subscriber::with_default(subscriber, f);
+    }
+
+    /// An app with one guess entered and nothing derived from it yet.
+    fn app_with_guess() -> App {
+        let mut app = create_test_app();
+        app.solver
+            .add_guess(Guess::new("magic".to_string(), vec![Feedback::Gray; 5]))
+            .unwrap();
+        app
+    }
+
+    #[test]
+    fn test_one_info_event_per_recompute() {
+        let mut app = app_with_guess();
+        let layer = CountingLayer::default();
+
+        with_counting(&layer, LevelFilter::DEBUG, || {
+            SolverHandler::new(&mut app).refresh_derived_state()
+        });
+        assert_eq!(layer.info.load(Ordering::SeqCst), 1);
+        assert_eq!(layer.debug.load(Ordering::SeqCst), 0);
+
+        // Nothing changed, so asking for the analysis again logs nothing
+        with_counting(&layer, LevelFilter::DEBUG, || {
+            SolverHandler::new(&mut app).recompute_analysis()
+        });
+        assert_eq!(layer.info.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_verbose_setting_adds_debug_dumps() {
+        let mut app = app_with_guess();
+        app.settings.verbose_analysis_logging = true;
+        let layer = CountingLayer::default();
+
+        with_counting(&layer, LevelFilter::DEBUG, || {
+            SolverHandler::new(&mut app).refresh_derived_state()
+        });
+        assert_eq!(layer.info.load(Ordering::SeqCst), 1);
+        assert_eq!(layer.debug.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_verbose_dumps_skipped_when_debug_disabled() {
+        let mut app = app_with_guess();
+        app.settings.verbose_analysis_logging = true;
+        let layer = CountingLayer::default();
+
+        with_counting(&layer, LevelFilter::INFO, || {
+            SolverHandler::new(&mut app).refresh_derived_state()
+        });
+        assert_eq!(layer.info.load(Ordering::SeqCst), 1);
+        assert_eq!(layer.debug.load(Ordering::SeqCst), 0);
+    }
+}
+
+#[cfg(test)]
+mod verify_answer_tests {
+    use super::*;
+    use crate::ui::{history::SolverOutcome, types::Overlay};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn type_word(app: &mut App, word: &str) {
+        for c in word.chars() {
+            press(app, KeyCode::Char(c));
+        }
+    }
+
+    /// An app whose solver session converged on STONE and was confirmed.
+    fn solved_app() -> App {
+        let mut app = create_test_app();
+        InputHandler::new(&mut app).handle_paste("apple XXXXG\nhouse XYXYG");
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL));
+        app
+    }
+
+    #[test]
+    fn test_prompt_shown_when_last_candidate_confirmed() {
+        let app = solved_app();
+
+        let Some(Overlay::VerifyAnswer(state)) = &app.overlay else {
+            panic!("expected the answer prompt, got {:?}", app.overlay);
+        };
+        assert_eq!(state.word, "stone");
+        assert_eq!(state.guesses.len(), 2);
+        assert!(app.solver.guesses().is_empty());
+    }
+
+    #[test]
+    fn test_yes_records_resolved_word() {
+        let mut app = solved_app();
+
+        press(&mut app, KeyCode::Char('y'));
+
+        assert!(app.overlay.is_none());
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(sessions[0].resolved_word.as_deref(), Some("stone"));
+        assert_eq!(sessions[0].outcome, SolverOutcome::Completed { guesses: 2 });
+        assert_eq!(app.logs.lines().last().unwrap(), "Answer STONE confirmed");
+    }
+
+    #[test]
+    fn test_no_records_typed_answer_as_mismatch() {
+        let mut app = solved_app();
+
+        press(&mut app, KeyCode::Char('n'));
+        type_word(&mut app, "slatex");
+        // Only as many letters as a word holds are taken
+        let Some(Overlay::VerifyAnswer(state)) = &app.overlay else {
+            panic!("expected the answer prompt");
+        };
+        assert_eq!(state.answer.as_deref(), Some("slate"));
+        press(&mut app, KeyCode::Enter);
+
+        assert!(app.overlay.is_none());
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(sessions[0].resolved_word.as_deref(), Some("slate"));
+        assert_eq!(
+            sessions[0].outcome,
+            SolverOutcome::CompletedMismatch { guesses: 2 }
+        );
+
+        let logs = app.logs.lines();
+        let tail = &logs[logs.len() - 3..];
+        assert_eq!(
+            tail,
+            [
+                "Answer was SLATE, not STONE",
+                "Guess 1 APPLE was entered as XXXXG, but SLATE gives YXXYG",
+                "Guess 2 HOUSE was entered as XYXYG, but SLATE gives XXXYG",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_esc_leaves_session_unverified() {
+        let mut app = solved_app();
+
+        press(&mut app, KeyCode::Esc);
+
+        assert!(app.overlay.is_none());
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(sessions[0].resolved_word, None);
+    }
+}
+
+#[cfg(test)]
+mod target_rank_tests {
+    use super::*;
+
+    /// Play `guesses` in a game whose answer is STONE.
+    fn play(app: &mut App, guesses: &[&str]) {
+        GameHandler::new(app).start_new_game();
+        app.target_word = Some("stone".to_string());
+        for guess in guesses {
+            GameHandler::new(app).submit_guess(guess.to_string());
+        }
+    }
+
+    #[test]
+    fn test_rank_of_answer_before_each_guess() {
+        let mut app = create_test_app();
+
+        // STONE ranks 4th of all eight words, then 2nd behind HOUSE in what is left
+        play(&mut app, &["magic", "apple", "stone"]);
+
+        assert_eq!(app.game_target_ranks, [Some(4), Some(2), Some(2)]);
+        let games = app.db.load_games().unwrap();
+        let ranks: Vec<_> = games[0].guesses.iter().map(|g| g.target_rank).collect();
+        assert_eq!(ranks, [Some(4), Some(2), Some(2)]);
+    }
+
+    #[test]
+    fn test_blacklisted_answer_has_no_rank() {
+        let mut app = create_test_app();
+        app.session_blacklist.insert("stone".to_string());
+
+        play(&mut app, &["house", "stone"]);
+
+        assert_eq!(app.game_target_ranks, [None, None]);
+    }
+
+    #[test]
+    fn test_ranks_cleared_for_next_game() {
+        let mut app = create_test_app();
+        play(&mut app, &["stone"]);
+        assert_eq!(app.game_target_ranks, [Some(4)]);
+
+        GameHandler::new(&mut app).start_new_game();
+
+        assert!(app.game_target_ranks.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod pending_write_tests {
+    use super::*;
+    use crate::storage::{Database, JOURNAL_FILE};
+
+    #[test]
+    fn test_pending_writes_recovered_on_build() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.db");
+        let path = path.to_str().unwrap();
+        std::fs::write(
+            dir.path().join(JOURNAL_FILE),
+            r#"{"kind":"solver_session","uuid":"5f0c7b1e-2a4d-4c3e-9b8a-0d1e2f3a4b5c","timestamp":"2024-01-01T00:00:00Z","guess_count":0,"guesses_json":"[]","app_version":"0.1.0"}
+"#,
+        )
+        .unwrap();
+
+        let app = AppBuilder::for_tests()
+            .db(Database::open(path).unwrap())
+            .build()
+            .unwrap();
+
+        assert!(
+            app.logs
+                .lines()
+                .contains(&"Recovered 1 pending history write(s)".to_string())
+        );
+        assert_eq!(app.db.load_solver_sessions().unwrap().len(), 1);
+        assert!(!dir.path().join(JOURNAL_FILE).exists());
+    }
+}
+
+#[cfg(test)]
+mod pool_export_tests {
+    use super::*;
+    use crate::{cli_style::CliStyle, solver::Feedback, storage::Database};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    /// Leaves STONE, HOUSE and WORLD.
+    fn guess_magic(app: &mut App) {
+        SolverHandler::new(app).submit_guess("magic".to_string(), vec![Feedback::Gray; 5]);
+    }
+
+    #[test]
+    fn test_pool_export_follows_score_order() {
+        let mut app = create_test_app();
+        guess_magic(&mut app);
+        let expected: Vec<(String, usize)> = app
+            .suggested_words()
+            .map(|(w, s)| (w.to_string(), s))
+            .collect();
+
+        let plain = SolverHandler::new(&mut app).pool_export(false, CliStyle::PLAIN);
+        let mut lines = plain.lines();
+        assert_eq!(
+            lines.next(),
+            Some("# greens: _____, yellows: none, grays: ACGIM")
+        );
+        let words: Vec<&str> = lines.collect();
+        assert_eq!(
+            words,
+            expected.iter().map(|(w, _)| w.as_str()).collect::<Vec<_>>()
+        );
+
+        let scored = SolverHandler::new(&mut app).pool_export(true, CliStyle::PLAIN);
+        let expected_lines: Vec<String> = expected
+            .iter()
+            .map(|(w, s)| format!("{} {}", w, s))
+            .collect();
+        assert_eq!(scored.lines().skip(1).collect::<Vec<_>>(), expected_lines);
+    }
+
+    #[test]
+    fn test_colored_pool_export_shows_guesses_as_tiles() {
+        let mut app = create_test_app();
+        guess_magic(&mut app);
+
+        let colored = SolverHandler::new(&mut app).pool_export(true, CliStyle::COLOR);
+        let lines: Vec<&str> = colored.lines().collect();
+        assert_eq!(
+            lines[0],
+            format!("# {}", CliStyle::COLOR.tiles("magic", &[Feedback::Gray; 5]))
+        );
+        assert_eq!(lines[1], "# greens: _____, yellows: none, grays: ACGIM");
+        // The best word is green
+        assert!(lines[2].starts_with("\x1b[38;5;10m"));
+        assert_eq!(lines.len(), 5);
+    }
+
+    #[test]
+    fn test_pool_export_keeps_blacklisted_candidates() {
+        let mut app = create_test_app();
+        guess_magic(&mut app);
+        app.session_blacklist.insert("stone".to_string());
+        SolverHandler::new(&mut app).refresh_derived_state();
+
+        let text = SolverHandler::new(&mut app).pool_export(false, CliStyle::PLAIN);
+        assert!(text.lines().any(|line| line == "stone"));
+        assert_eq!(text.lines().count(), 4);
+    }
+
+    #[test]
+    fn test_export_key_writes_beside_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.db");
+        let mut app = AppBuilder::for_tests()
+            .db(Database::open(path.to_str().unwrap()).unwrap())
+            .build()
+            .unwrap();
+        guess_magic(&mut app);
+
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char('E'));
+
+        let exports: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|p| {
+                p.file_name()
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+                    .starts_with("pool-")
+            })
+            .collect();
+        assert_eq!(exports.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(&exports[0]).unwrap(),
+            SolverHandler::new(&mut app).pool_export(true, CliStyle::PLAIN)
+        );
+        assert!(
+            app.logs
+                .lines()
+                .iter()
+                .any(|line| line.starts_with("Exported 3 candidates to "))
+        );
+        assert!(app.input.is_empty());
+    }
+
+    #[test]
+    fn test_export_needs_database_file() {
+        let mut app = create_test_app();
+        guess_magic(&mut app);
+
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char('e'));
+
+        assert_eq!(
+            app.logs.lines().last().map(String::as_str),
+            Some("Pool export needs a database file to export beside")
+        );
+    }
+}
+
+#[cfg(test)]
+mod inferred_position_tests {
+    use super::*;
+    use crate::{solver::parse_pattern, ui::test_support::buffer_lines};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    /// An app where TAILS XXXXY leaves SHONE, SPORE and SMOKE, which share S, O and E.
+    fn app_after_tails(setup: impl FnOnce(&mut App)) -> App {
+        let words: Vec<String> = ["shone", "spore", "smoke", "tails"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let mut app = AppBuilder::new()
+            .words(words.clone())
+            .solutions(words)
+            .build()
+            .unwrap();
+        setup(&mut app);
+        app.solver
+            .add_guess(Guess::new(
+                "tails".to_string(),
+                parse_pattern("XXXXY").unwrap(),
+            ))
+            .unwrap();
+        SolverHandler::new(&mut app).refresh_derived_state();
+        SolverHandler::new(&mut app).recompute_analysis();
+        app
+    }
+
+    fn inferred_logs(app: &App) -> Vec<String> {
+        app.logs
+            .lines()
+            .into_iter()
+            .filter(|line| line.starts_with("Position "))
+            .collect()
+    }
+
+    #[test]
+    fn test_inferred_positions_logged_once() {
+        let mut app = app_after_tails(|_| {});
+        assert_eq!(
+            inferred_logs(&app),
+            [
+                "Position 1 must be S: every remaining candidate has it there",
+                "Position 3 must be O: every remaining candidate has it there",
+                "Position 5 must be E: every remaining candidate has it there",
+            ]
+        );
+
+        app.analysis_dirty = true;
+        SolverHandler::new(&mut app).recompute_analysis();
+        assert_eq!(inferred_logs(&app).len(), 3);
+
+        // Clearing the guesses starts the count over
+        SolverHandler::new(&mut app).undo_guess();
+        SolverHandler::new(&mut app).recompute_analysis();
+        assert!(app.inferred_positions_noted.is_empty());
+    }
+
+    #[test]
+    fn test_inferred_positions_tagged_in_panel() {
+        let app = app_after_tails(|_| {});
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 10)).unwrap();
+        terminal
+            .draw(|f| app.draw_position_analysis(f, f.area()))
+            .unwrap();
+        let panel = buffer_lines(terminal.backend().buffer()).join("\n");
+
+        assert!(panel.contains("Pos 1: [s(3)] (inferred)"));
+        assert!(!panel.contains("Pos 2: [h(1) p(1) m(1)] (inferred)"));
+        assert!(panel.contains("Pos 5: [e(3)] (inferred)"));
+    }
+
+    #[test]
+    fn test_no_inferred_log_with_game_analysis_hidden() {
+        let app = app_after_tails(|app| {
+            app.mode = GameMode::Game;
+            app.show_analysis = false;
+        });
+
+        assert!(inferred_logs(&app).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod phrase_game_tests {
+    use super::*;
+    use crate::{solver::parse_pattern, ui::test_support::buffer_lines};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    /// A two-word game whose answer is STONE APPLE.
+    fn phrase_game() -> App {
+        let mut app = create_test_app();
+        app.settings.phrase_words = 2;
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("stone apple".to_string());
+        app
+    }
+
+    #[test]
+    fn test_phrase_target_pairs_solution_words() {
+        let mut app = create_test_app();
+        app.settings.phrase_words = 2;
+        GameHandler::new(&mut app).start_new_game();
+
+        let target = app.target_word.clone().unwrap();
+        let words: Vec<&str> = target.split(' ').collect();
+        assert_eq!(words.len(), 2);
+        assert!(words.iter().all(|w| app.solution_words.contains(w)));
+        assert_eq!(app.solver.segments(), [5, 5]);
+    }
+
+    #[test]
+    fn test_phrase_guess_scored_per_word_and_saved() {
+        let mut app = phrase_game();
+
+        GameHandler::new(&mut app).submit_guess("stone house".to_string());
+        assert_eq!(
+            app.solver.guesses()[0].feedback,
+            parse_pattern("GGGGGXXXXG").unwrap()
+        );
+        assert!(!app.game_over);
+
+        GameHandler::new(&mut app).submit_guess("stone apple".to_string());
+        assert!(app.game_won);
+
+        let games = app.db.load_games().unwrap();
+        assert_eq!(games[0].target_word, "stone apple");
+        let words: Vec<&str> = games[0].guesses.iter().map(|g| g.word.as_str()).collect();
+        assert_eq!(words, ["stone house", "stone apple"]);
+        assert_eq!(games[0].guesses[0].feedback.len(), 10);
+    }
+
+    #[test]
+    fn test_phrase_input_needs_one_word_per_segment() {
+        let mut app = phrase_game();
+
+        app.input = "stone".to_string();
+        assert!(matches!(
+            app.input_status_immutable(),
+            InputStatus::Invalid(_)
+        ));
+        app.input = "stonehouse".to_string();
+        assert!(matches!(
+            app.input_status_immutable(),
+            InputStatus::Invalid(_)
+        ));
+        app.input = "stone house".to_string();
+        assert!(matches!(app.input_status_immutable(), InputStatus::Valid));
+
+        InputHandler::new(&mut app).handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(app.solver.guesses()[0].word, "stonehouse");
+    }
+
+    #[test]
+    fn test_phrase_guess_drawn_as_two_tile_groups() {
+        let mut app = phrase_game();
+        GameHandler::new(&mut app).submit_guess("stone house".to_string());
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 4)).unwrap();
+        terminal.draw(|f| app.draw_guesses(f, f.area())).unwrap();
+        let panel = buffer_lines(terminal.backend().buffer()).join("\n");
+
+        assert!(panel.contains(" S  T  O  N  E    H  O  U  S  E "));
+    }
+
+    #[test]
+    fn test_solver_mode_drops_phrase_guesses() {
+        let mut app = phrase_game();
+        GameHandler::new(&mut app).submit_guess("stone house".to_string());
+
+        GameHandler::new(&mut app).toggle_game_mode();
+
+        assert_eq!(app.mode, GameMode::Solver);
+        assert_eq!(app.solver.segments(), [5]);
+        assert!(app.solver.guesses().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod startup_warning_tests {
+    use super::*;
+    use crate::doctor::check_terminal;
+
+    #[test]
+    fn test_soft_failures_logged_as_warnings() {
+        let app = create_test_app();
+
+        app.warn_soft_failures(&[
+            check_terminal(Ok((120, 40)), 256),
+            check_terminal(Ok((60, 20)), 256),
+        ]);
+
+        let warnings: Vec<String> = app
+            .logs
+            .lines()
+            .into_iter()
+            .filter(|line| line.starts_with("Warning:"))
+            .collect();
+        assert_eq!(
+            warnings,
+            ["Warning: Terminal: 60x20, smaller than 80x24; enlarge the window, or use --plain"]
+        );
+    }
+}
+
+#[cfg(test)]
+mod panel_layout_tests {
+    use super::*;
+    use crate::{
+        settings::PanelKind,
+        ui::{rendering::analysis_constraints, test_support::buffer_lines, types::Overlay},
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend, layout::Constraint};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn rendered(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 60)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        buffer_lines(terminal.backend().buffer()).join("\n")
+    }
+
+    #[test]
+    fn test_constraints_follow_panel_order() {
+        assert_eq!(
+            analysis_constraints(&[PanelKind::Constraints, PanelKind::Pool, PanelKind::Logs]),
+            [
+                Constraint::Length(8),
+                Constraint::Length(12),
+                Constraint::Min(6)
+            ]
+        );
+
+        // Without logs the bottom panel takes the leftover space
+        assert_eq!(
+            analysis_constraints(&[PanelKind::Pool, PanelKind::Positions]),
+            [Constraint::Length(12), Constraint::Min(9)]
+        );
+        assert!(analysis_constraints(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_hidden_panels_not_drawn() {
+        let mut app = create_test_app();
+        SolverHandler::new(&mut app).recompute_analysis();
+        let screen = rendered(&app);
+        for title in ["Letters", "Positions", "Constraints", "Pool", "Logs"] {
+            assert!(screen.contains(title), "{} missing", title);
+        }
+
+        app.settings.analysis_panels = vec![PanelKind::Pool, PanelKind::Logs];
+        let screen = rendered(&app);
+        assert!(!screen.contains("Letters"));
+        assert!(!screen.contains("Positions"));
+        assert!(!screen.contains("Constraints"));
+        assert!(screen.contains("Pool"));
+        assert!(screen.contains("Logs"));
+    }
+
+    #[test]
+    fn test_editor_moves_and_hides_panels() {
+        let mut app = create_test_app();
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
+        assert!(matches!(
+            app.overlay,
+            Some(Overlay::PanelLayout { selected: 0 })
+        ));
+
+        // Hide Letters; the highlight follows it to the hidden rows
+        press(&mut app, KeyCode::Char(' '));
+        assert_eq!(
+            app.settings.analysis_panels,
+            [
+                PanelKind::Positions,
+                PanelKind::Constraints,
+                PanelKind::Pool,
+                PanelKind::Logs
+            ]
+        );
+        assert!(matches!(
+            app.overlay,
+            Some(Overlay::PanelLayout { selected: 4 })
+        ));
+
+        // Hidden panels can't be moved
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.settings.analysis_panels[0], PanelKind::Positions);
+
+        // Bring Pool to the top
+        press(&mut app, KeyCode::BackTab);
+        press(&mut app, KeyCode::BackTab);
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(
+            app.settings.analysis_panels,
+            [
+                PanelKind::Pool,
+                PanelKind::Positions,
+                PanelKind::Constraints,
+                PanelKind::Logs
+            ]
+        );
+
+        press(&mut app, KeyCode::Esc);
+        assert!(app.overlay.is_none());
+        let saved = crate::settings::Settings::load(&app.db).unwrap();
+        assert_eq!(saved.analysis_panels, app.settings.analysis_panels);
+    }
+}
+
+#[cfg(test)]
+mod messy_wordlist_tests {
+    use super::*;
+    use crate::{storage::Database, ui::build_app, wordlist::WordlistOptions};
+
+    #[test]
+    fn test_game_playable_against_messy_lists() {
+        let dir = tempfile::tempdir().unwrap();
+        let words = dir.path().join("words.txt");
+        let solutions = dir.path().join("solutions.txt");
+        std::fs::write(&words, "CRANE\r\nslate \r\n\r\nStone\r\ncrane\r\n").unwrap();
+        std::fs::write(&solutions, "# answers\r\nCRANE\r\n\r\n").unwrap();
+
+        let options = WordlistOptions {
+            words_path: Some(words),
+            solutions_path: Some(solutions),
+            offline: true,
+            ..Default::default()
+        };
+        let mut app = build_app(Database::open_memory().unwrap(), None, &options).unwrap();
+        assert!(
+            app.logs
+                .lines()
+                .contains(&"Cleaned the word list: 3 entries normalized, 1 dropped".to_string())
+        );
+
+        GameHandler::new(&mut app).start_new_game();
+        assert_eq!(app.target_word.as_deref(), Some("crane"));
+
+        GameHandler::new(&mut app).submit_guess("stone".to_string());
+        assert_eq!(app.solver.guesses().len(), 1);
+        GameHandler::new(&mut app).submit_guess("crane".to_string());
+        assert!(app.game_won);
+    }
+}
+
+#[cfg(test)]
+mod guess_pool_tests {
+    use super::*;
+    use crate::{
+        solver::parse_pattern,
+        ui::{handlers::FocusHandler, test_support::buffer_lines, types::FocusTarget},
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    /// An app whose pool can run down to one word without the session restarting.
+    fn app() -> App {
+        let mut app = create_test_app();
+        app.solver_session_active = false;
+        app
+    }
+
+    fn guess(app: &mut App, word: &str, pattern: &str) {
+        SolverHandler::new(app).submit_guess(word.to_string(), parse_pattern(pattern).unwrap());
+    }
+
+    fn guesses_panel(app: &App, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(40, height)).unwrap();
+        terminal.draw(|f| app.draw_guesses(f, f.area())).unwrap();
+        buffer_lines(terminal.backend().buffer())
+    }
+
+    #[test]
+    fn test_rows_show_pool_after_each_guess() {
+        let mut app = app();
+        // HOUSE leaves CRANE and APPLE; CRANE then leaves APPLE
+        guess(&mut app, "house", "XXXXG");
+        guess(&mut app, "crane", "XXYXG");
+
+        let lines = guesses_panel(&app, 6);
+        assert!(lines[1].trim_end().ends_with("→ 2 │"), "{}", lines[1]);
+        assert!(lines[2].trim_end().ends_with("→ 1 │"), "{}", lines[2]);
+
+        SolverHandler::new(&mut app).undo_guess();
+        assert_eq!(app.pool_history, [2]);
+        let lines = guesses_panel(&app, 6);
+        assert!(lines[1].trim_end().ends_with("→ 2 │"));
+        assert!(!lines[2].contains('→'));
+    }
+
+    #[test]
+    fn test_pool_hidden_while_game_hides_analysis() {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("apple".to_string());
+        GameHandler::new(&mut app).submit_guess("house".to_string());
+
+        assert!(!guesses_panel(&app, 6)[1].contains('→'));
+        app.show_analysis = true;
+        assert!(guesses_panel(&app, 6)[1].contains('→'));
+    }
+
+    #[test]
+    fn test_panel_scrolls_when_full() {
+        let mut app = app();
+        guess(&mut app, "world", "XXXXX");
+        guess(&mut app, "house", "XXXXX");
+        guess(&mut app, "magic", "XXXXX");
+
+        // Two rows fit: the earlier guesses fold into one, then the latest
+        let lines = guesses_panel(&app, 4);
+        assert!(lines[0].contains("Guess 3 of 3"));
+        assert!(lines[1].contains("… 2 earlier guesses"));
+        assert!(lines[2].contains(" M  A  G  I  C "));
+
+        let mut press = |code| {
+            InputHandler::new(&mut app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+        };
+        press(KeyCode::PageUp);
+        press(KeyCode::PageUp);
+        let lines = guesses_panel(&app, 4);
+        assert!(lines[0].contains("Guesses 1-2 of 3"));
+        assert!(lines[1].contains(" W  O  R  L  D "));
+
+        // A new guess jumps back to the latest
+        guess(&mut app, "apple", "XXXXX");
+        assert_eq!(app.guess_scroll, 0);
+    }
+
+    /// Ten guesses that each leave most of the pool, so a session runs long.
+    const LONG_SESSION: [&str; 10] = [
+        "fuzzy", "fuzzy", "fuzzy", "fuzzy", "fuzzy", "fuzzy", "fuzzy", "fuzzy", "fuzzy", "fuzzy",
+    ];
+
+    #[test]
+    fn test_panel_grows_past_six_guesses() {
+        let mut app = app();
+        assert_eq!(app.guess_panel_height(), 8);
+        for word in &LONG_SESSION[..6] {
+            guess(&mut app, word, "XXXXX");
+        }
+        assert_eq!(app.guess_panel_height(), 8);
+        for word in &LONG_SESSION[6..] {
+            guess(&mut app, word, "XXXXX");
+        }
+        // Ten guesses and the separator
+        assert_eq!(app.guess_panel_height(), 13);
+        for _ in 0..5 {
+            guess(&mut app, "fuzzy", "XXXXX");
+        }
+        assert_eq!(app.guess_panel_height(), 14);
+    }
+
+    #[test]
+    fn test_ten_guesses_all_shown_with_separator() {
+        let mut app = app();
+        for word in LONG_SESSION {
+            guess(&mut app, word, "XXXXX");
+        }
+
+        let lines = guesses_panel(&app, app.guess_panel_height());
+        assert!(lines[0].starts_with("┌Guesses─"), "{}", lines[0]);
+        assert!(lines[6].contains(" F  U  Z  Z  Y "));
+        assert!(lines[7].contains(" beyond 6 "), "{}", lines[7]);
+        assert!(lines[8].contains(" F  U  Z  Z  Y "));
+        assert!(lines[11].contains(" F  U  Z  Z  Y "));
+        assert!(lines[12].starts_with('└'));
+    }
+
+    #[test]
+    fn test_latest_guesses_kept_when_panel_short() {
+        let mut app = app();
+        for word in &LONG_SESSION[..9] {
+            guess(&mut app, word, "XXXXX");
+        }
+        guess(&mut app, "world", "XXXXX");
+
+        // Six rows: the first six guesses fold, leaving the rule and the latest four
+        let lines = guesses_panel(&app, 8);
+        assert!(lines[0].contains("Guesses 7-10 of 10"), "{}", lines[0]);
+        assert!(lines[1].contains("… 6 earlier guesses"));
+        assert!(lines[2].contains(" beyond 6 "));
+        assert!(lines[6].contains(" W  O  R  L  D "));
+
+        // Focused, the panel scrolls instead of folding
+        FocusHandler::new(&mut app).focus(FocusTarget::Guesses);
+        let lines = guesses_panel(&app, 8);
+        assert!(!lines.iter().any(|l| l.contains("earlier")));
+        assert!(lines[6].contains(" W  O  R  L  D "));
+    }
+
+    #[test]
+    fn test_long_session_saves_and_loads() {
+        let mut app = create_test_app();
+        for word in LONG_SESSION {
+            guess(&mut app, word, "XXXXX");
+        }
+        guess(&mut app, "stone", "GGGGG");
+
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].guesses.len(), 11);
+        let stats = crate::ui::history::SolverStats::from_sessions(&sessions);
+        assert_eq!(stats.completed_sessions, 1);
+        assert_eq!(stats.average_guesses, 11.0);
+    }
+}
+
+#[cfg(test)]
+mod give_up_tests {
+    use super::*;
+    use crate::ui::{
+        history::{GameOutcome, GameRecord, HistoryStats},
+        types::Overlay,
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, modifiers));
+    }
+
+    fn game_with_one_guess() -> App {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("apple".to_string());
+        GameHandler::new(&mut app).submit_guess("house".to_string());
+        app
+    }
+
+    #[test]
+    fn test_give_up_asks_then_records_forfeit() {
+        let mut app = game_with_one_guess();
+
+        press(&mut app, KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert!(matches!(app.overlay, Some(Overlay::ConfirmDiscard(_))));
+        assert!(!app.game_over);
+
+        press(&mut app, KeyCode::Char('y'), KeyModifiers::NONE);
+        assert!(app.game_over);
+        assert!(app.game_forfeited);
+        assert!(!app.game_won);
+
+        let games = app.db.load_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].outcome, GameOutcome::Forfeited);
+        assert_eq!(games[0].guess_count(), 1);
+    }
+
+    #[test]
+    fn test_give_up_declined_keeps_playing() {
+        let mut app = game_with_one_guess();
+
+        press(&mut app, KeyCode::Char('x'), KeyModifiers::CONTROL);
+        press(&mut app, KeyCode::Char('n'), KeyModifiers::NONE);
+
+        assert!(!app.game_over);
+        assert!(app.db.load_games().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_give_up_only_in_a_running_game() {
+        let mut app = create_test_app();
+        press(&mut app, KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert!(app.overlay.is_none());
+
+        let mut app = game_with_one_guess();
+        GameHandler::new(&mut app).submit_guess("apple".to_string());
+        GameHandler::new(&mut app).give_up();
+        assert!(app.game_won);
+        assert!(!app.game_forfeited);
+    }
+
+    #[test]
+    fn test_forfeits_counted_apart_from_losses() {
+        let game = |outcome| GameRecord {
+            timestamp: chrono::Utc::now(),
+            target_word: "stone".to_string(),
+            guesses: vec![],
+            outcome,
+            blitz: true,
+            duration_secs: None,
+            app_version: None,
+            difficulty: crate::settings::Difficulty::Normal,
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        };
+        let games = vec![
+            game(GameOutcome::Won { guesses: 2 }),
+            game(GameOutcome::Forfeited),
+            game(GameOutcome::Lost),
+            game(GameOutcome::Forfeited),
+        ];
+
+        let stats = HistoryStats::from_games(&games);
+
+        assert_eq!(stats.wins, 1);
+        assert_eq!(stats.losses, 1);
+        assert_eq!(stats.forfeits, 2);
+        assert_eq!(stats.win_rate, 25.0);
+        assert_eq!(stats.current_streak, -3);
+        assert_eq!(stats.guess_distribution, [0, 1, 0, 0, 0, 0]);
+        assert_eq!(stats.blitz_wins, 1);
+    }
+}
+
+#[cfg(test)]
+mod recompute_optimal_tests {
+    use super::*;
+    use crate::{
+        solver::{SolverState, generate_feedback},
+        storage::Database,
+        ui::{
+            handlers::MaintenanceHandler, history::solver_types::SolverGuess,
+            types::RecomputeSummary,
+        },
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    /// `raise` then `crane` against the answer crane, measured by `app`.
+    fn measured_session(app: &mut App) -> Vec<SolverGuess> {
+        let mut state = SolverState::new(5);
+        let mut guesses = Vec::new();
+        for word in ["raise", "crane"] {
+            let guess = Guess::new(word.to_string(), generate_feedback("crane", word));
+            guesses.push(SolverHandler::new(app).measure_guess(&state, &guess));
+            state.add_guess(guess).unwrap();
+        }
+        guesses
+    }
+
+    fn run(app: &mut App, dry_run: bool) -> RecomputeSummary {
+        MaintenanceHandler::new(app).start_recompute(dry_run);
+        loop {
+            if let Some(summary) = MaintenanceHandler::new(app).step_recompute() {
+                return summary.unwrap();
+            }
+        }
+    }
+
+    fn stored_optimal(db: &Database) -> Vec<Vec<Option<String>>> {
+        db.load_solver_sessions()
+            .unwrap()
+            .iter()
+            .map(|s| s.guesses.iter().map(|g| g.optimal_word.clone()).collect())
+            .collect()
+    }
+
+    /// A session saved with the full fixture, then an app whose answers have
+    /// shrunk so the first guess has a different best reply.
+    fn app_with_stale_session() -> App {
+        let mut old = create_test_app();
+        let guesses = measured_session(&mut old);
+        old.db
+            .save_solver_session(chrono::Utc::now(), &guesses)
+            .unwrap();
+
+        AppBuilder::new()
+            .words(words(&FIXTURE))
+            .solutions(words(&["house", "stone", "world"]))
+            .db(old.db)
+            .build()
+            .unwrap()
+    }
+
+    const FIXTURE: [&str; 8] = [
+        "raise", "stone", "slate", "crane", "house", "apple", "world", "magic",
+    ];
+
+    #[test]
+    fn test_recompute_rewrites_optimal_words() {
+        let mut app = app_with_stale_session();
+        let before = stored_optimal(&app.db);
+        let expected: Vec<Option<String>> = measured_session(&mut app)
+            .into_iter()
+            .map(|g| g.optimal_word)
+            .collect();
+        assert_ne!(before[0], expected);
+
+        let summary = run(&mut app, false);
+
+        assert_eq!(stored_optimal(&app.db), [expected]);
+        assert_eq!(summary.sessions, 1);
+        assert_eq!(summary.guesses, 2);
+        assert!(summary.optimal_changed >= 1);
+        assert!(app.recompute.is_none());
+        assert!(
+            app.logs
+                .lines()
+                .iter()
+                .any(|l| l.starts_with("Recomputed optimal words: 1 session(s) replayed"))
+        );
+    }
+
+    #[test]
+    fn test_dry_run_writes_nothing() {
+        let mut app = app_with_stale_session();
+        let before = stored_optimal(&app.db);
+
+        let summary = run(&mut app, true);
+
+        assert!(summary.optimal_changed >= 1);
+        assert_eq!(stored_optimal(&app.db), before);
+        assert!(
+            app.logs
+                .lines()
+                .iter()
+                .any(|l| l.starts_with("Dry run, nothing written"))
+        );
+    }
+
+    #[test]
+    fn test_escape_cancels_without_writing() {
+        let mut app = app_with_stale_session();
+        let before = stored_optimal(&app.db);
+        for _ in 0..6 {
+            let guesses = measured_session(&mut app);
+            app.db
+                .save_solver_session(chrono::Utc::now(), &guesses)
+                .unwrap();
+        }
+        app.mode = GameMode::History;
+        app.history_view_mode = HistoryViewMode::Solver;
+
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        InputHandler::new(&mut app).handle_key(key(KeyCode::Char('o')));
+        assert!(app.recompute.is_some());
+        assert!(MaintenanceHandler::new(&mut app).step_recompute().is_none());
+        InputHandler::new(&mut app).handle_key(key(KeyCode::Esc));
+
+        assert!(app.recompute.is_none());
+        assert_eq!(stored_optimal(&app.db)[0], before[0]);
+        assert!(
+            app.logs
+                .lines()
+                .contains(&"Recompute cancelled; stored sessions are unchanged".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sessions_without_patterns_need_an_answer() {
+        let mut app = create_test_app();
+        let mut guesses = measured_session(&mut app);
+        for guess in &mut guesses {
+            guess.feedback = None;
+            guess.optimal_word = None;
+        }
+        let resolved = app
+            .db
+            .save_solver_session(chrono::Utc::now(), &guesses)
+            .unwrap();
+        app.db
+            .save_solver_session(chrono::Utc::now(), &guesses)
+            .unwrap();
+        app.db
+            .resolve_solver_session(resolved, "crane", true)
+            .unwrap();
+
+        let summary = run(&mut app, false);
+
+        assert_eq!(summary.sessions, 1);
+        assert_eq!(summary.skipped, 1);
+        let optimal = stored_optimal(&app.db);
+        assert!(optimal[0].iter().all(Option::is_some));
+        assert!(optimal[1].iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_cli_recompute_reports_summary() {
+        let dir = tempfile::tempdir().unwrap();
+        let solutions = dir.path().join("solutions.txt");
+        let allowed = dir.path().join("words.txt");
+        std::fs::write(&solutions, "crane\nhouse\nworld\n").unwrap();
+        std::fs::write(&allowed, FIXTURE.join("\n")).unwrap();
+        let options = crate::wordlist::WordlistOptions {
+            words_path: Some(allowed),
+            solutions_path: Some(solutions),
+            offline: true,
+            ..Default::default()
+        };
+
+        let app = app_with_stale_session();
+        let report = crate::ui::recompute_optimal(app.db, &options, true).unwrap();
+
+        assert!(report.starts_with("Recompute would change: 1 session(s) replayed, 0 skipped"));
+    }
+}
+
+#[cfg(test)]
+mod modal_input_tests {
+    use super::*;
+    use crate::ui::{
+        history::{GameOutcome, GameRecord},
+        test_support::buffer_lines,
+        types::InputMode,
+    };
+    use chrono::Utc;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn press(app: &mut App, code: KeyCode) {
+        let modifiers = match code {
+            KeyCode::Char(c) if c.is_ascii_uppercase() => KeyModifiers::SHIFT,
+            _ => KeyModifiers::NONE,
+        };
+        InputHandler::new(app).handle_key(KeyEvent::new(code, modifiers));
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+    }
+
+    fn modal_app() -> App {
+        let mut app = create_test_app();
+        app.settings.modal_input = true;
+        app
+    }
+
+    #[test]
+    fn test_escape_ignored_without_modal_setting() {
+        let mut app = create_test_app();
+
+        press(&mut app, KeyCode::Esc);
+        type_text(&mut app, "jk");
+
+        assert_eq!(app.input_mode, InputMode::Insert);
+        assert_eq!(app.input, "jk");
+    }
+
+    #[test]
+    fn test_escape_and_i_switch_modes() {
+        let mut app = modal_app();
+        type_text(&mut app, "ra");
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.input_mode, InputMode::Normal);
+        type_text(&mut app, "xyz");
+        press(&mut app, KeyCode::Backspace);
+        assert_eq!(app.input, "ra");
+
+        press(&mut app, KeyCode::Char('i'));
+        assert_eq!(app.input_mode, InputMode::Insert);
+        type_text(&mut app, "ise");
+        assert_eq!(app.input, "raise");
+
+        press(&mut app, KeyCode::Esc);
+        press(&mut app, KeyCode::Char('a'));
+        assert_eq!(app.input_mode, InputMode::Insert);
+    }
+
+    #[test]
+    fn test_normal_letters_never_reach_input() {
+        let mut app = modal_app();
+        press(&mut app, KeyCode::Esc);
+
+        type_text(&mut app, "qwertyuiopasdfghjklzxcvbnmQWERTY");
+        // `i` and `a` switched back to insert part-way; start again
+        app.input.clear();
+        app.input_mode = InputMode::Normal;
+        type_text(&mut app, "bcdefghjklmnopqrstuvwxyz0123456789");
+
+        assert!(app.input.is_empty());
+        assert_eq!(app.input_mode, InputMode::Normal);
+    }
+
+    #[test]
+    fn test_j_and_k_move_through_suggestions() {
+        let mut app = modal_app();
+        app.solver
+            .add_guess(Guess::new("magic".to_string(), vec![Feedback::Gray; 5]))
+            .unwrap();
+        SolverHandler::new(&mut app).refresh_derived_state();
+        assert!(app.suggestions.len() > 2);
+        press(&mut app, KeyCode::Esc);
+
+        press(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.suggestion_selected, Some(0));
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.suggestion_selected, Some(2));
+        press(&mut app, KeyCode::Char('k'));
+        assert_eq!(app.suggestion_selected, Some(1));
+
+        // Esc drops the selection and stays in normal mode
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.suggestion_selected, None);
+        assert_eq!(app.input_mode, InputMode::Normal);
+    }
+
+    #[test]
+    fn test_u_undoes_in_solver_only() {
+        let mut app = modal_app();
+        type_text(&mut app, "magic xxxxx");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.solver.guesses().len(), 1);
+
+        press(&mut app, KeyCode::Esc);
+        press(&mut app, KeyCode::Char('u'));
+        assert!(app.solver.guesses().is_empty());
+
+        GameHandler::new(&mut app).start_new_game();
+        GameHandler::new(&mut app).submit_guess("raise".to_string());
+        press(&mut app, KeyCode::Char('u'));
+        assert_eq!(app.solver.guesses().len(), 1);
+    }
+
+    #[test]
+    fn test_history_motions_and_digits() {
+        let games = (0..25)
+            .map(|i| GameRecord {
+                timestamp: Utc::now(),
+                target_word: format!("game{}", i),
+                guesses: vec![],
+                outcome: GameOutcome::Won { guesses: 3 },
+                blitz: false,
+                duration_secs: None,
+                app_version: None,
+                difficulty: crate::settings::Difficulty::Normal,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty: None,
+            })
+            .collect();
+        let mut app = modal_app();
+        app.mode = GameMode::History;
+        app.history_view_mode = HistoryViewMode::Stats;
+        app.history_data = Some(HistoryData::new(games, Vec::new()));
+
+        press(&mut app, KeyCode::Char('l'));
+        assert_eq!(app.history_view_mode, HistoryViewMode::List);
+        press(&mut app, KeyCode::Char('G'));
+        assert_eq!(app.history_page, 2);
+        press(&mut app, KeyCode::Char('g'));
+        assert_eq!(app.history_page, 0);
+
+        press(&mut app, KeyCode::Char('3'));
+        assert_eq!(app.history_view_mode, HistoryViewMode::Detail);
+        press(&mut app, KeyCode::Char('h'));
+        assert_eq!(app.history_view_mode, HistoryViewMode::List);
+        press(&mut app, KeyCode::Char('h'));
+        assert_eq!(app.history_view_mode, HistoryViewMode::Stats);
+        press(&mut app, KeyCode::Char('h'));
+        assert_eq!(app.history_view_mode, HistoryViewMode::Solver);
+    }
+
+    #[test]
+    fn test_history_letters_unchanged_without_modal_setting() {
+        let mut app = create_test_app();
+        app.mode = GameMode::History;
+        app.history_view_mode = HistoryViewMode::Stats;
+
+        press(&mut app, KeyCode::Char('l'));
+
+        assert_eq!(app.history_view_mode, HistoryViewMode::Stats);
+    }
+
+    #[test]
+    fn test_badge_shows_mode() {
+        let mut app = modal_app();
+        let mut terminal = Terminal::new(TestBackend::new(160, 40)).unwrap();
+        let title = |terminal: &Terminal<TestBackend>| {
+            buffer_lines(terminal.backend().buffer())
+                .into_iter()
+                .find(|l| l.contains("Input"))
+                .unwrap()
+        };
+
+        terminal.draw(|f| app.draw(f)).unwrap();
+        assert!(title(&terminal).contains("[INSERT] Input"));
+
+        press(&mut app, KeyCode::Esc);
+        terminal.draw(|f| app.draw(f)).unwrap();
+        assert!(title(&terminal).contains("[NORMAL] Input"));
+        let screen = buffer_lines(terminal.backend().buffer()).join("\n");
+        assert!(
+            screen.contains("i/a insert · j/k suggestions"),
+            "{}",
+            screen
+        );
+    }
+}
+
+#[cfg(test)]
+mod difficulty_tests {
+    use super::*;
+    use crate::{
+        settings::Difficulty,
+        ui::{history::GameOutcome, test_support::buffer_lines},
+    };
+    use chrono::Utc;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn ctrl(app: &mut App, c: char) {
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+    }
+
+    /// A game at `difficulty` whose answer is slate.
+    fn game_at(difficulty: Difficulty) -> App {
+        let mut app = create_test_app();
+        app.settings.difficulty = difficulty;
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("slate".to_string());
+        app
+    }
+
+    fn screen(app: &App) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(160, 40)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        buffer_lines(terminal.backend().buffer())
+    }
+
+    #[test]
+    fn test_easy_shows_everything_and_allows_undo() {
+        let mut app = game_at(Difficulty::Easy);
+        assert!(app.show_suggestions);
+        assert!(app.show_analysis);
+
+        GameHandler::new(&mut app).submit_guess("stone".to_string());
+        GameHandler::new(&mut app).submit_guess("crane".to_string());
+        ctrl(&mut app, 'z');
+
+        assert_eq!(app.solver.guesses().len(), 1);
+        assert_eq!(app.remaining_guesses, 5);
+        assert_eq!(app.game_luck.len(), 1);
+    }
+
+    #[test]
+    fn test_normal_keeps_toggles_without_undo() {
+        let mut app = game_at(Difficulty::Normal);
+        assert!(!app.show_suggestions);
+        assert!(!app.show_analysis);
+
+        ctrl(&mut app, 'h');
+        ctrl(&mut app, 'a');
+        assert!(app.show_suggestions);
+        assert!(app.show_analysis);
+
+        GameHandler::new(&mut app).submit_guess("stone".to_string());
+        ctrl(&mut app, 'z');
+        assert_eq!(app.solver.guesses().len(), 1);
+        assert!(
+            app.logs
+                .lines()
+                .contains(&"Undo is off in Normal games".to_string())
+        );
+    }
+
+    #[test]
+    fn test_expert_gates_toggles_and_undo() {
+        let mut app = game_at(Difficulty::Expert);
+
+        ctrl(&mut app, 'h');
+        ctrl(&mut app, 'a');
+        assert!(!app.show_suggestions);
+        assert!(!app.show_analysis);
+
+        GameHandler::new(&mut app).submit_guess("stone".to_string());
+        ctrl(&mut app, 'z');
+        assert_eq!(app.solver.guesses().len(), 1);
+
+        let lines = screen(&app);
+        assert!(lines.iter().any(|l| l.contains("Game Status (Expert)")));
+        assert!(!lines.iter().any(|l| l.contains("Ctrl+H = hints")));
+        assert!(!lines.iter().any(|l| l.contains("→ ")));
+    }
+
+    #[test]
+    fn test_expert_enforces_hard_mode() {
+        let mut app = game_at(Difficulty::Expert);
+        // stone against slate: S and E green, T yellow
+        GameHandler::new(&mut app).submit_guess("stone".to_string());
+
+        app.input = "crane".to_string();
+        assert!(matches!(
+            app.input_status_immutable(),
+            InputStatus::Invalid(msg) if msg == "hard mode: 1st letter must be S"
+        ));
+        GameHandler::new(&mut app).submit_guess("crane".to_string());
+        assert_eq!(app.solver.guesses().len(), 1);
+        assert_eq!(app.remaining_guesses, 5);
+
+        GameHandler::new(&mut app).submit_guess("slate".to_string());
+        assert!(app.game_won);
+    }
+
+    #[test]
+    fn test_normal_allows_any_guess() {
+        let mut app = game_at(Difficulty::Normal);
+        GameHandler::new(&mut app).submit_guess("stone".to_string());
+        GameHandler::new(&mut app).submit_guess("crane".to_string());
+
+        assert_eq!(app.solver.guesses().len(), 2);
+    }
+
+    #[test]
+    fn test_difficulty_change_waits_for_next_game() {
+        let mut app = game_at(Difficulty::Normal);
+
+        ctrl(&mut app, 'd');
+        assert_eq!(app.settings.difficulty, Difficulty::Expert);
+        assert_eq!(app.game_difficulty, Difficulty::Normal);
+        assert!(
+            app.logs
+                .lines()
+                .contains(&"Difficulty: Expert from the next game".to_string())
+        );
+        assert_eq!(
+            crate::settings::Settings::load(&app.db).unwrap().difficulty,
+            Difficulty::Expert
+        );
+
+        GameHandler::new(&mut app).give_up();
+        GameHandler::new(&mut app).start_new_game();
+        assert_eq!(app.game_difficulty, Difficulty::Expert);
+    }
+
+    #[test]
+    fn test_saved_game_records_difficulty() {
+        let mut app = game_at(Difficulty::Expert);
+        GameHandler::new(&mut app).submit_guess("slate".to_string());
+        let mut app_easy = game_at(Difficulty::Easy);
+        GameHandler::new(&mut app_easy).give_up();
+
+        let games = app.db.load_games().unwrap();
+        assert_eq!(games[0].difficulty, Difficulty::Expert);
+        assert_eq!(games[0].outcome, GameOutcome::Won { guesses: 1 });
+        assert_eq!(
+            app_easy.db.load_games().unwrap()[0].difficulty,
+            Difficulty::Easy
+        );
+    }
+
+    #[test]
+    fn test_stats_view_segments_by_difficulty() {
+        let mut app = game_at(Difficulty::Expert);
+        GameHandler::new(&mut app).submit_guess("slate".to_string());
+        app.settings.difficulty = Difficulty::Normal;
+        GameHandler::new(&mut app).start_new_game();
+        GameHandler::new(&mut app).give_up();
+        HistoryHandler::new(&mut app).enter_history_mode();
+
+        let lines = screen(&app);
+        assert!(lines.iter().any(|l| l.contains("By Difficulty")));
+        let row = |name: &str| lines.iter().find(|l| l.contains(name)).unwrap().clone();
+        assert!(row("Expert ").contains("100%"));
+        assert!(row("Normal ").contains(" 0%"));
+
+        // A single difficulty has nothing to compare
+        let mut single = create_test_app();
+        single
+            .db
+            .save_game(&crate::ui::history::GameRecord {
+                timestamp: Utc::now(),
+                target_word: "slate".to_string(),
+                guesses: vec![],
+                outcome: GameOutcome::Lost,
+                blitz: false,
+                duration_secs: None,
+                app_version: None,
+                difficulty: Difficulty::Normal,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty: None,
+            })
+            .unwrap();
+        HistoryHandler::new(&mut single).enter_history_mode();
+        assert!(!screen(&single).iter().any(|l| l.contains("By Difficulty")));
+    }
+}
+
+#[cfg(test)]
+mod suggestion_diff_tests {
+    use super::*;
+    use crate::{
+        analysis::SuggestionDelta, solver::parse_pattern, ui::handlers::SuggestionHandler,
+        ui::test_support::buffer_lines,
+    };
+    use ratatui::{Terminal, backend::TestBackend};
+
+    const WORDS: [&str; 12] = [
+        "crane", "slate", "stone", "shone", "spine", "snake", "share", "stare", "store", "spare",
+        "space", "scare",
+    ];
+
+    fn app() -> App {
+        let solutions: Vec<String> = WORDS.iter().map(|w| w.to_string()).collect();
+        let mut words = solutions.clone();
+        words.extend(["fuzzy".to_string(), "blimp".to_string()]);
+        let mut app = AppBuilder::new()
+            .words(words)
+            .solutions(solutions)
+            .build()
+            .unwrap();
+        app.solver_session_active = false;
+        app
+    }
+
+    fn guess(app: &mut App, word: &str, pattern: &str) {
+        SolverHandler::new(app).submit_guess(word.to_string(), parse_pattern(pattern).unwrap());
+    }
+
+    /// Two guesses that keep most of the list: the second one eliminates
+    /// slate, spine, spare and space.
+    fn two_guesses() -> App {
+        let mut app = app();
+        guess(&mut app, "fuzzy", "XXXXX");
+        guess(&mut app, "blimp", "XXXXX");
+        app
+    }
+
+    #[test]
+    fn test_first_guess_has_no_deltas() {
+        let mut app = app();
+        guess(&mut app, "fuzzy", "XXXXX");
+        assert!(!app.suggestions.is_empty());
+        assert!(app.suggestion_deltas.is_empty());
+    }
+
+    #[test]
+    fn test_second_guess_records_deltas() {
+        let app = two_guesses();
+        let deltas = &app.suggestion_deltas;
+
+        assert!(deltas.iter().any(|d| matches!(
+            d,
+            SuggestionDelta::Moved { word, from: 3, to: 2 } if word == "share"
+        )));
+        assert!(deltas.iter().any(|d| matches!(
+            d,
+            SuggestionDelta::Entered { word, .. } if word == "shone"
+        )));
+        assert!(deltas.iter().any(|d| matches!(
+            d,
+            SuggestionDelta::Left { word, rank: 2, eliminated: true } if word == "spare"
+        )));
+        // stare stayed on top
+        assert!(deltas.iter().all(|d| d.word() != "stare"));
+    }
+
+    #[test]
+    fn test_panel_shows_markers_and_exit() {
+        let app = two_guesses();
+        let mut terminal = Terminal::new(TestBackend::new(60, 14)).unwrap();
+        terminal
+            .draw(|f| app.draw_suggestions(f, f.area()))
+            .unwrap();
+        let lines = buffer_lines(terminal.backend().buffer());
+
+        let row = |word: &str| {
+            lines
+                .iter()
+                .find(|l| l.contains(&format!("{word} (")))
+                .unwrap()
+                .clone()
+        };
+        assert!(row("share").contains("↑1"));
+        assert!(row("shone").contains("NEW"));
+        assert!(!row("stare").contains('↑') && !row("stare").contains('↓'));
+        assert!(lines.iter().any(|l| l.contains("spare (was #3)")));
+    }
+
+    #[test]
+    fn test_undo_clears_deltas() {
+        let mut app = two_guesses();
+        assert!(!app.suggestion_deltas.is_empty());
+
+        SolverHandler::new(&mut app).undo_guess();
+        assert!(app.suggestion_deltas.is_empty());
+
+        // The next guess compares against the list after the undo
+        guess(&mut app, "blimp", "XXXXX");
+        assert!(!app.suggestion_deltas.is_empty());
+    }
+
+    #[test]
+    fn test_recompute_without_new_guess_clears_deltas() {
+        let mut app = two_guesses();
+        SuggestionHandler::new(&mut app).toggle_hide_gray();
+        assert!(app.suggestion_deltas.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod week_report_tests {
+    use super::*;
+    use crate::{
+        report::IsoWeek,
+        storage::Database,
+        ui::history::{GameGuess, GameOutcome, GameRecord},
+    };
+    use chrono::Utc;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_week_report_key_writes_beside_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.db");
+        let mut app = AppBuilder::for_tests()
+            .db(Database::open(path.to_str().unwrap()).unwrap())
+            .build()
+            .unwrap();
+        app.db
+            .save_game(&GameRecord {
+                timestamp: Utc::now(),
+                target_word: "stone".to_string(),
+                guesses: vec![GameGuess::new("crane".to_string(), vec![]); 3],
+                outcome: GameOutcome::Won { guesses: 3 },
+                blitz: false,
+                duration_secs: None,
+                app_version: None,
+                difficulty: crate::settings::Difficulty::Normal,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty: None,
+            })
+            .unwrap();
+        HistoryHandler::new(&mut app).enter_history_mode();
+
+        press(&mut app, KeyCode::Char('w'));
+
+        let week = IsoWeek::current(&app.time_prefs());
+        let report =
+            std::fs::read_to_string(dir.path().join(format!("report-{}.md", week))).unwrap();
+        assert!(report.starts_with(&format!("# Week {}\n", week)));
+        assert!(report.contains("- **Best game:** STONE in 3"));
+        assert!(
+            app.logs
+                .lines()
+                .last()
+                .unwrap()
+                .starts_with(&format!("Wrote the {} report to ", week))
+        );
+    }
+
+    #[test]
+    fn test_week_report_only_from_stats_view() {
+        let mut app = create_test_app();
+        HistoryHandler::new(&mut app).enter_history_mode();
+        press(&mut app, KeyCode::Char('w'));
+        assert_eq!(
+            app.logs.lines().last().map(String::as_str),
+            Some("Week report needs a database file to write beside")
+        );
+
+        let logged = app.logs.lines().len();
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Char('w'));
+        assert_eq!(app.logs.lines().len(), logged);
+    }
+}
+
+#[cfg(test)]
+mod focus_tests {
+    use super::*;
+    use crate::{
+        settings::PanelKind,
+        solver::generate_feedback,
+        ui::{handlers::FocusHandler, types::FocusTarget},
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) -> bool {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn ctrl(app: &mut App, c: char) -> bool {
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL))
+    }
+
+    /// Two guesses against STONE and a few log entries, so there are
+    /// suggestions and both panels have something to scroll.
+    fn app_with_guesses() -> App {
+        let mut app = create_test_app();
+        for word in ["magic", "apple"] {
+            let feedback = generate_feedback("stone", word);
+            SolverHandler::new(&mut app).submit_guess(word.to_string(), feedback);
+        }
+        for i in 0..5 {
+            app.log(format!("entry {}", i));
+        }
+        app.guess_rows.set(1);
+        app.log_rows.set(2);
+        app
+    }
+
+    #[test]
+    fn test_input_has_focus_by_default() {
+        let mut app = app_with_guesses();
+        assert_eq!(app.focused(), FocusTarget::Input);
+
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.input, "s");
+    }
+
+    #[test]
+    fn test_tab_cycles_visible_panels() {
+        let mut app = app_with_guesses();
+        let mut order = Vec::new();
+        for _ in 0..4 {
+            press(&mut app, KeyCode::Tab);
+            order.push(app.focused());
+        }
+        assert_eq!(
+            order,
+            [
+                FocusTarget::Suggestions,
+                FocusTarget::Guesses,
+                FocusTarget::Logs,
+                FocusTarget::Input,
+            ]
+        );
+
+        press(&mut app, KeyCode::BackTab);
+        assert_eq!(app.focused(), FocusTarget::Logs);
+        press(&mut app, KeyCode::F(6));
+        assert_eq!(app.focused(), FocusTarget::Input);
+    }
+
+    #[test]
+    fn test_suggestions_focused_exactly_while_selected() {
+        let mut app = app_with_guesses();
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.suggestion_selected, Some(0));
+
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.suggestion_selected, None);
+        assert_eq!(app.focused(), FocusTarget::Guesses);
+    }
+
+    #[test]
+    fn test_up_routed_to_focused_panel() {
+        let mut app = app_with_guesses();
+
+        // Suggestions: moves the selection
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.suggestion_selected, Some(0));
+        assert_eq!(app.guess_scroll, 0);
+
+        // Guesses: scrolls toward the first guess
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.guess_scroll, 1);
+        assert_eq!(app.log_scroll, 0);
+
+        // Logs: scrolls the log, leaving the guesses where they were
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.log_scroll, 1);
+        assert_eq!(app.guess_scroll, 1);
+    }
+
+    #[test]
+    fn test_page_keys_scroll_logs_only_when_focused() {
+        let mut app = app_with_guesses();
+        press(&mut app, KeyCode::PageUp);
+        assert_eq!((app.guess_scroll, app.log_scroll), (1, 0));
+
+        FocusHandler::new(&mut app).focus(FocusTarget::Logs);
+        press(&mut app, KeyCode::PageUp);
+        assert_eq!((app.guess_scroll, app.log_scroll), (1, 2));
+        press(&mut app, KeyCode::End);
+        assert_eq!(app.log_scroll, 0);
+    }
+
+    #[test]
+    fn test_typing_stays_out_of_unfocused_input() {
+        let mut app = app_with_guesses();
+        FocusHandler::new(&mut app).focus(FocusTarget::Guesses);
+
+        press(&mut app, KeyCode::Char('s'));
+        press(&mut app, KeyCode::Enter);
+        assert!(app.input.is_empty());
+        assert_eq!(app.focused(), FocusTarget::Input);
+
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.input, "s");
+    }
+
+    #[test]
+    fn test_esc_returns_focus_to_input() {
+        let mut app = app_with_guesses();
+        FocusHandler::new(&mut app).focus(FocusTarget::Logs);
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.focused(), FocusTarget::Input);
+        assert_eq!(app.focus, FocusTarget::Input);
+    }
+
+    #[test]
+    fn test_quit_works_from_any_focus() {
+        let mut app = app_with_guesses();
+        for target in app.focusable() {
+            FocusHandler::new(&mut app).focus(target);
+            assert_eq!(app.focused(), target);
+            assert!(
+                ctrl(&mut app, 'q'),
+                "Ctrl+Q ignored with {:?} focused",
+                target
+            );
+        }
+
+        HistoryHandler::new(&mut app).enter_history_mode();
+        for target in [FocusTarget::History, FocusTarget::Logs] {
+            FocusHandler::new(&mut app).focus(target);
+            assert!(
+                ctrl(&mut app, 'q'),
+                "Ctrl+Q ignored with {:?} focused",
+                target
+            );
+        }
+    }
+
+    #[test]
+    fn test_focus_falls_back_when_panel_hidden() {
+        let mut app = app_with_guesses();
+        app.settings.analysis_panels = vec![PanelKind::Letters];
+        assert!(!app.focusable().contains(&FocusTarget::Logs));
+
+        app.focus = FocusTarget::Logs;
+        assert_eq!(app.focused(), FocusTarget::Input);
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.input, "s");
+        assert_eq!(app.focus, FocusTarget::Input);
+    }
+
+    #[test]
+    fn test_history_focus_uses_f6() {
+        let mut app = app_with_guesses();
+        HistoryHandler::new(&mut app).enter_history_mode();
+        assert_eq!(app.focused(), FocusTarget::History);
+
+        // Tab still cycles the views
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.history_view_mode, HistoryViewMode::List);
+        assert_eq!(app.focused(), FocusTarget::History);
+
+        press(&mut app, KeyCode::F(6));
+        assert_eq!(app.focused(), FocusTarget::Logs);
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.log_scroll, 1);
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.focused(), FocusTarget::History);
+        assert_eq!(app.history_view_mode, HistoryViewMode::List);
+    }
+
+    #[test]
+    fn test_focused_panel_border_hint() {
+        use crate::ui::test_support::buffer_lines;
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let mut app = app_with_guesses();
+        let draw = |app: &App| {
+            let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+            terminal.draw(|f| app.draw(f)).unwrap();
+            buffer_lines(terminal.backend().buffer()).join("\n")
+        };
+        assert!(!draw(&app).contains("Esc: done"));
+
+        FocusHandler::new(&mut app).focus(FocusTarget::Guesses);
+        let screen = draw(&app);
+        assert!(screen.contains("Home/End: scroll · Esc: done"));
+        assert!(!screen.contains("PgUp/PgDn: scroll · Esc: done"));
+
+        FocusHandler::new(&mut app).focus(FocusTarget::Logs);
+        assert!(draw(&app).contains("PgUp/PgDn: scroll · Esc: done"));
+    }
+}
+
+#[cfg(test)]
+mod win_window_tests {
+    use super::*;
+    use crate::{planner::WinWindow, settings::Difficulty, ui::test_support::buffer_lines};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn status(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(140, 3)).unwrap();
+        terminal
+            .draw(|f| app.draw_game_status(f, f.area()))
+            .unwrap();
+        buffer_lines(terminal.backend().buffer()).join("\n")
+    }
+
+    /// A game where HOUSE leaves crane and apple, which share XXXXG.
+    fn after_house() -> App {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("apple".to_string());
+        GameHandler::new(&mut app).submit_guess("house".to_string());
+        app
+    }
+
+    #[test]
+    fn test_no_window_before_first_guess() {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        assert_eq!(app.win_window, None);
+        assert!(!status(&app).contains("Win window"));
+    }
+
+    #[test]
+    fn test_window_after_guess() {
+        let mut app = after_house();
+        assert_eq!(app.win_window, Some(WinWindow { best: 1, worst: 2 }));
+        // Normal games show it only with the analysis open, like the pool sizes
+        assert!(!status(&app).contains("Win window"));
+        app.show_analysis = true;
+        assert!(status(&app).contains("Win window: 1–2 guesses"));
+    }
+
+    #[test]
+    fn test_window_hidden_in_expert_games() {
+        let mut app = after_house();
+        app.show_analysis = true;
+        app.game_difficulty = Difficulty::Expert;
+        assert!(!status(&app).contains("Win window"));
+    }
+
+    #[test]
+    fn test_no_window_in_solver_mode() {
+        let mut app = create_test_app();
+        SolverHandler::new(&mut app).submit_guess(
+            "house".to_string(),
+            crate::solver::parse_pattern("XXXXG").unwrap(),
+        );
+        assert_eq!(app.win_window, None);
+    }
+}
+
+#[cfg(test)]
+mod abandon_reason_tests {
+    use super::*;
+    use crate::{
+        solver::parse_pattern,
+        storage::{Database, new_write_id},
+        ui::handlers::CheckpointHandler,
+        ui::history::{AbandonReason, GameOutcome, GameRecord, HistoryStats, SolverOutcome},
+    };
+    use chrono::{Duration, Utc};
+
+    /// A game with one guess that can't be the answer.
+    fn game_with_guess() -> App {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("stone".to_string());
+        GameHandler::new(&mut app).submit_guess("crane".to_string());
+        app
+    }
+
+    fn solver_guess(app: &mut App) {
+        SolverHandler::new(app).submit_guess("crane".to_string(), parse_pattern("XXXXX").unwrap());
+    }
+
+    #[test]
+    fn test_game_checkpointed_while_open() {
+        let mut app = game_with_guess();
+        CheckpointHandler::new(&mut app).flush();
+        // Open rows stay out of history until the game ends
+        assert!(app.db.load_games().unwrap().is_empty());
+        assert_eq!(
+            app.db
+                .close_stale(Utc::now() + Duration::seconds(1))
+                .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_finished_game_replaces_checkpoint() {
+        let mut app = game_with_guess();
+        GameHandler::new(&mut app).submit_guess("stone".to_string());
+
+        let games = app.db.load_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].outcome, GameOutcome::Won { guesses: 2 });
+        assert_eq!(
+            app.db
+                .close_stale(Utc::now() + Duration::seconds(1))
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_quit_mid_game_records_quit() {
+        let mut app = game_with_guess();
+        GameHandler::new(&mut app).quit();
+
+        let games = app.db.load_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(
+            games[0].outcome,
+            GameOutcome::Abandoned(AbandonReason::Quit)
+        );
+    }
+
+    #[test]
+    fn test_quit_mid_session_records_quit() {
+        let mut app = create_test_app();
+        solver_guess(&mut app);
+        assert!(app.db.load_solver_sessions().unwrap().is_empty());
+
+        GameHandler::new(&mut app).quit();
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(
+            sessions[0].outcome,
+            SolverOutcome::Abandoned {
+                guesses: 1,
+                reason: AbandonReason::Quit
+            }
+        );
+    }
+
+    #[test]
+    fn test_switching_to_game_records_session_switch() {
+        let mut app = create_test_app();
+        solver_guess(&mut app);
+        GameHandler::new(&mut app).toggle_game_mode();
+
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(
+            sessions[0].outcome,
+            SolverOutcome::Abandoned {
+                guesses: 1,
+                reason: AbandonReason::Switched
+            }
+        );
+    }
+
+    #[test]
+    fn test_quit_without_guesses_records_nothing() {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        GameHandler::new(&mut app).quit();
+
+        assert!(app.db.load_games().unwrap().is_empty());
+        assert!(app.db.load_solver_sessions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_startup_closes_stale_rows() {
+        // A game left open by an earlier run
+        let db = Database::open_memory().unwrap();
+        let stale = GameRecord {
+            timestamp: Utc::now() - Duration::hours(1),
+            target_word: "stone".to_string(),
+            guesses: vec![crate::ui::history::GameGuess::new(
+                "crane".to_string(),
+                parse_pattern("XXXXX").unwrap(),
+            )],
+            outcome: GameOutcome::Lost,
+            blitz: false,
+            duration_secs: None,
+            app_version: None,
+            difficulty: crate::settings::Difficulty::Normal,
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        };
+        db.checkpoint_game(&new_write_id(), &stale).unwrap();
+
+        let app = AppBuilder::for_tests().db(db).build().unwrap();
+        assert!(
+            app.logs.lines().contains(
+                &"Closed 1 game(s) or solver session(s) left open by a crash".to_string()
+            )
+        );
+        let games = app.db.load_games().unwrap();
+        assert_eq!(
+            games[0].outcome,
+            GameOutcome::Abandoned(AbandonReason::Crashed)
+        );
+    }
+
+    #[test]
+    fn test_stats_break_down_abandoned() {
+        let game = |outcome| GameRecord {
+            timestamp: Utc::now(),
+            target_word: "stone".to_string(),
+            guesses: vec![],
+            outcome,
+            blitz: false,
+            duration_secs: None,
+            app_version: None,
+            difficulty: crate::settings::Difficulty::Normal,
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        };
+        let games = vec![
+            game(GameOutcome::Won { guesses: 3 }),
+            game(GameOutcome::Abandoned(AbandonReason::Switched)),
+            game(GameOutcome::Won { guesses: 4 }),
+            game(GameOutcome::Abandoned(AbandonReason::Crashed)),
+            game(GameOutcome::Abandoned(AbandonReason::Quit)),
+            game(GameOutcome::Won { guesses: 2 }),
+            game(GameOutcome::Abandoned(AbandonReason::Crashed)),
+        ];
+
+        let stats = HistoryStats::from_games(&games);
+        assert_eq!(stats.abandoned.switched, 1);
+        assert_eq!(stats.abandoned.quit, 1);
+        assert_eq!(stats.abandoned.crashed, 2);
+        assert_eq!(
+            stats.abandoned.breakdown(),
+            "4 (switched 1 · quit 1 · crashed 2)"
+        );
+        // Crashes count toward neither the win rate nor the streak
+        assert_eq!(stats.win_rate, 60.0);
+        assert_eq!(stats.current_streak, 1);
+    }
+}
+
+#[cfg(test)]
+mod hint_budget_tests {
+    use super::*;
+    use crate::ui::history::{HintKind, HistoryStats};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    /// A game with a budget of `budget` where HOUSE leaves crane and apple.
+    fn budgeted(budget: usize) -> App {
+        let mut app = create_test_app();
+        app.settings.hint_budget = Some(budget);
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("apple".to_string());
+        GameHandler::new(&mut app).submit_guess("house".to_string());
+        app
+    }
+
+    fn ctrl(app: &mut App, c: char) {
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+    }
+
+    #[test]
+    fn test_budget_exhausted_refuses_hints() {
+        let mut app = budgeted(2);
+        assert!(!app.show_suggestions);
+
+        ctrl(&mut app, 'h');
+        assert!(app.show_suggestions);
+        assert_eq!(app.game_hints.remaining(), Some(1));
+        // Hiding and showing again is already paid for
+        ctrl(&mut app, 'h');
+        ctrl(&mut app, 'h');
+        assert!(app.show_suggestions);
+        assert_eq!(app.game_hints.remaining(), Some(1));
+
+        ctrl(&mut app, 'b');
+        assert_eq!(app.game_hints.remaining(), Some(0));
+        assert_eq!(app.game_hints.reveals(), 1);
+
+        app.toast = None;
+        ctrl(&mut app, 'a');
+        assert!(!app.show_analysis);
+        let toast = app.toast.as_ref().unwrap();
+        assert_eq!(toast.text, "Hint budget used up (2 of 2)");
+
+        ctrl(&mut app, 'b');
+        assert_eq!(app.game_hints.reveals(), 1);
+        assert_eq!(
+            app.game_hints.used,
+            [HintKind::Suggestions, HintKind::BestGuess]
+        );
+    }
+
+    #[test]
+    fn test_budget_resets_each_game() {
+        let mut app = budgeted(1);
+        ctrl(&mut app, 'a');
+        assert!(app.show_analysis);
+        assert_eq!(app.game_hints.remaining(), Some(0));
+
+        app.settings.hint_budget = Some(3);
+        GameHandler::new(&mut app).start_new_game();
+        assert!(app.game_hints.used.is_empty());
+        assert_eq!(app.game_hints.remaining(), Some(3));
+        assert!(!app.show_analysis);
+
+        // No budget set: hints are recorded but never refused
+        app.settings.hint_budget = None;
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("apple".to_string());
+        GameHandler::new(&mut app).submit_guess("house".to_string());
+        ctrl(&mut app, 'a');
+        ctrl(&mut app, 'h');
+        ctrl(&mut app, 'b');
+        ctrl(&mut app, 'b');
+        assert_eq!(app.game_hints.used.len(), 4);
+        assert_eq!(app.game_hints.remaining(), None);
+    }
+
+    #[test]
+    fn test_hints_stored_and_wins_split() {
+        let mut app = budgeted(2);
+        ctrl(&mut app, 'h');
+        GameHandler::new(&mut app).submit_guess("apple".to_string());
+
+        app.last_submit = None;
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("world".to_string());
+        GameHandler::new(&mut app).submit_guess("world".to_string());
+
+        let games = app.db.load_games().unwrap();
+        assert_eq!(games[0].hint_budget, Some(2));
+        assert_eq!(games[0].hints, [HintKind::Suggestions]);
+        assert!(games[0].hinted());
+        // Showing a panel isn't a revealed best guess
+        assert!(!games[0].assisted());
+        assert!(games[1].hints.is_empty());
+        assert!(!games[1].hinted());
+
+        let stats = HistoryStats::from_games(&games);
+        assert_eq!((stats.clean_wins, stats.hinted_wins), (1, 1));
+    }
+}
+
+#[cfg(test)]
+mod best_guess_tests {
+    use super::*;
+    use crate::{
+        settings::Difficulty,
+        ui::{
+            history::{GameOutcome, GameRecord, HistoryStats},
+            test_support::buffer_lines,
+            types::TOAST_DURATION,
+        },
+    };
+    use ratatui::{Terminal, backend::TestBackend};
+    use std::time::Instant;
+
+    /// A game at `difficulty` where HOUSE leaves crane and apple.
+    fn after_house(difficulty: Difficulty) -> App {
+        let mut app = create_test_app();
+        app.settings.difficulty = difficulty;
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("apple".to_string());
+        GameHandler::new(&mut app).submit_guess("house".to_string());
+        app
+    }
+
+    fn screen(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        buffer_lines(terminal.backend().buffer()).join("\n")
+    }
+
+    #[test]
+    fn test_hint_names_top_suggestion() {
+        let mut app = after_house(Difficulty::Normal);
+        let best = app.suggested_words().next().unwrap().0.to_uppercase();
+        GameHandler::new(&mut app).reveal_best_guess();
+
+        let toast = app.toast.as_ref().unwrap();
+        assert!(
+            toast
+                .text
+                .starts_with(&format!("Best guess: {} — 2 →", best))
+        );
+        assert!(screen(&app).contains(&format!("Best guess: {}", best)));
+        // The word stays off the log panel
+        assert!(!app.logs.lines().iter().any(|l| l.contains(&best)));
+    }
+
+    #[test]
+    fn test_hints_counted_and_stored() {
+        let mut app = after_house(Difficulty::Normal);
+        GameHandler::new(&mut app).reveal_best_guess();
+        GameHandler::new(&mut app).reveal_best_guess();
+        assert_eq!(app.game_hints.reveals(), 2);
+
+        GameHandler::new(&mut app).submit_guess("apple".to_string());
+        let games = app.db.load_games().unwrap();
+        assert_eq!(games[0].hints_used, 2);
+        assert!(games[0].assisted());
+
+        GameHandler::new(&mut app).start_new_game();
+        assert_eq!(app.game_hints.reveals(), 0);
+    }
+
+    #[test]
+    fn test_no_hint_before_first_guess() {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        GameHandler::new(&mut app).reveal_best_guess();
+        assert!(app.toast.is_none());
+        assert_eq!(app.game_hints.reveals(), 0);
+    }
+
+    #[test]
+    fn test_hints_off_in_expert() {
+        let mut app = after_house(Difficulty::Expert);
+        GameHandler::new(&mut app).reveal_best_guess();
+        assert!(app.toast.is_none());
+        assert_eq!(app.game_hints.reveals(), 0);
+        assert!(
+            app.logs
+                .lines()
+                .contains(&"Hints are off in Expert games".to_string())
+        );
+    }
+
+    #[test]
+    fn test_toast_gone_after_duration() {
+        let mut app = after_house(Difficulty::Normal);
+        GameHandler::new(&mut app).reveal_best_guess();
+        let expired = Instant::now() + TOAST_DURATION;
+
+        // Not drawn once it runs out, even before the next tick clears it
+        app.toast.as_mut().unwrap().until = Instant::now();
+        assert!(!screen(&app).contains("Best guess:"));
+
+        GameHandler::new(&mut app).tick(expired);
+        assert!(app.toast.is_none());
+    }
+
+    #[test]
+    fn test_assisted_games_flagged() {
+        let game = |hints_used| GameRecord {
+            timestamp: chrono::Utc::now(),
+            target_word: "stone".to_string(),
+            guesses: vec![],
+            outcome: GameOutcome::Won { guesses: 3 },
+            blitz: false,
+            duration_secs: None,
+            app_version: None,
+            difficulty: Difficulty::Normal,
+            hints_used,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        };
+        let games = vec![game(0), game(1), game(3)];
+
+        assert_eq!(HistoryStats::from_games(&games).assisted_games, 2);
+        assert_eq!(games[0].listed_word(), "stone");
+        assert_eq!(games[1].listed_word(), "stone*");
+    }
+}
+
+#[cfg(test)]
+mod opening_trio_tests {
+    use super::*;
+    use crate::ui::test_support::buffer_lines;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    /// SLATE, CRONY and HUMID share no letters; the rest all repeat one.
+    fn app() -> App {
+        let words: Vec<String> = ["slate", "crony", "humid", "apple", "geese", "mamma"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        AppBuilder::new()
+            .words(words.clone())
+            .solutions(words)
+            .build()
+            .unwrap()
+    }
+
+    fn panel(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(60, 6)).unwrap();
+        terminal
+            .draw(|f| app.draw_suggestions(f, f.area()))
+            .unwrap();
+        buffer_lines(terminal.backend().buffer()).join("\n")
+    }
+
+    #[test]
+    fn test_trio_shown_before_first_guess() {
+        let app = app();
+        assert_eq!(app.opening_trio.letters, 15);
+        let panel = panel(&app);
+        assert!(panel.contains("Trio: SLATE · HUMID · CRONY"), "{}", panel);
+        assert!(panel.contains("15 letters before any feedback"));
+    }
+
+    #[test]
+    fn test_trio_gone_after_first_guess() {
+        let mut app = app();
+        SolverHandler::new(&mut app).submit_guess("apple".to_string(), vec![Feedback::Gray; 5]);
+        assert!(!panel(&app).contains("Trio:"));
+    }
+
+    #[test]
+    fn test_trio_not_offered_in_games() {
+        let mut app = app();
+        GameHandler::new(&mut app).start_new_game();
+        app.show_suggestions = true;
+        assert!(!panel(&app).contains("Trio:"));
+    }
+}
+
+#[cfg(test)]
+mod dictionary_search_tests {
+    use super::*;
+    use crate::{
+        solver::parse_pattern,
+        ui::{test_support::buffer_lines, types::Overlay},
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    /// SHAKE, FIGHT and ZESTY are allowed guesses but never answers.
+    fn app() -> App {
+        let words = |list: &[&str]| list.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        AppBuilder::new()
+            .words(words(&[
+                "slate", "stare", "shake", "stone", "light", "fight", "crane", "zesty",
+            ]))
+            .solutions(words(&["slate", "stare", "stone", "light", "crane"]))
+            .build()
+            .unwrap()
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn open(app: &mut App) {
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL));
+    }
+
+    fn search(app: &mut App, query: &str) {
+        open(app);
+        for c in query.chars() {
+            press(app, KeyCode::Char(c));
+        }
+    }
+
+    fn hits(app: &App) -> Vec<&str> {
+        let Some(Overlay::DictionarySearch(state)) = &app.overlay else {
+            panic!("dictionary search not open");
+        };
+        state
+            .hits
+            .iter()
+            .map(|&i| app.allowed_lookup.word(i))
+            .collect()
+    }
+
+    fn rendered(app: &App) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        buffer_lines(terminal.backend().buffer())
+    }
+
+    fn row<'a>(screen: &'a [String], word: &str) -> &'a str {
+        screen
+            .iter()
+            .find(|l| l.contains(&format!(" {}  ", word)))
+            .unwrap_or_else(|| panic!("{} not listed", word))
+    }
+
+    #[test]
+    fn test_typing_edits_query_not_input() {
+        let mut app = app();
+        app.input = "cr".to_string();
+        search(&mut app, "s_a_E");
+
+        assert_eq!(hits(&app), ["shake", "slate", "stare"]);
+        assert_eq!(app.input, "cr");
+
+        // Wildcards match whole words, so a shorter pattern finds nothing
+        press(&mut app, KeyCode::Backspace);
+        assert!(hits(&app).is_empty());
+        for _ in 0..3 {
+            press(&mut app, KeyCode::Backspace);
+        }
+        assert_eq!(hits(&app), ["shake", "slate", "stare", "stone"]);
+
+        press(&mut app, KeyCode::Esc);
+        assert!(app.overlay.is_none());
+        assert_eq!(app.input, "cr");
+    }
+
+    #[test]
+    fn test_enter_puts_selected_word_in_input() {
+        let mut app = app();
+        search(&mut app, "*ight");
+        assert_eq!(hits(&app), ["fight", "light"]);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+
+        assert!(app.overlay.is_none());
+        // The pattern is typed next
+        assert_eq!(app.input, "light ");
+
+        GameHandler::new(&mut app).start_new_game();
+        search(&mut app, "zesty");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.input, "zesty");
+    }
+
+    #[test]
+    fn test_enter_without_matches_keeps_popup() {
+        let mut app = app();
+        search(&mut app, "q");
+        assert!(hits(&app).is_empty());
+        press(&mut app, KeyCode::Enter);
+        assert!(app.overlay.is_some());
+        assert!(rendered(&app).iter().any(|l| l.contains("No matches")));
+    }
+
+    #[test]
+    fn test_hits_marked_as_answers_and_pool_members() {
+        let mut app = app();
+        // Leaves SLATE and STARE
+        SolverHandler::new(&mut app)
+            .submit_guess("shake".to_string(), parse_pattern("GXGXG").unwrap());
+        search(&mut app, "s");
+
+        let screen = rendered(&app);
+        assert!(row(&screen, "SHAKE").contains("guess only  ruled out"));
+        assert!(row(&screen, "SLATE").contains("answer      in pool"));
+        assert!(row(&screen, "STARE").contains("answer      in pool"));
+        assert!(row(&screen, "STONE").contains("answer      ruled out"));
+        assert!(screen.iter().any(|l| l.contains("4 matches")));
+    }
+
+    #[test]
+    fn test_selected_hit_explains_elimination() {
+        let mut app = app();
+        SolverHandler::new(&mut app)
+            .submit_guess("shake".to_string(), parse_pattern("GXGXG").unwrap());
+        search(&mut app, "s");
+
+        let screen = rendered(&app);
+        assert!(
+            screen
+                .iter()
+                .any(|l| l.contains("SHAKE fails guess 1 (SHAKE):"))
+        );
+        assert!(screen.iter().any(|l| l.contains("can't contain H")));
+
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        assert!(
+            rendered(&app)
+                .iter()
+                .any(|l| l.contains("STARE is still possible"))
+        );
+
+        press(&mut app, KeyCode::Down);
+        let screen = rendered(&app);
+        assert!(
+            screen
+                .iter()
+                .any(|l| l.contains("STONE fails guess 1 (SHAKE):"))
+        );
+        assert!(screen.iter().any(|l| l.contains("needs A at position 3")));
+    }
+
+    #[test]
+    fn test_pool_not_shown_in_games() {
+        let mut app = app();
+        GameHandler::new(&mut app).start_new_game();
+        search(&mut app, "s");
+
+        let screen = rendered(&app);
+        assert!(row(&screen, "STARE").contains("answer"));
+        assert!(!screen.iter().any(|l| l.contains("in pool")));
+        assert!(!screen.iter().any(|l| l.contains("still possible")));
+    }
+
+    #[test]
+    fn test_long_result_list_scrolls_with_selection() {
+        let words: Vec<String> = (b'a'..=b'z')
+            .map(|c| format!("{}aaaa", c as char))
+            .collect();
+        let mut app = AppBuilder::new()
+            .words(words.clone())
+            .solutions(words)
+            .build()
+            .unwrap();
+        search(&mut app, "*");
+        assert_eq!(hits(&app).len(), 26);
+
+        let screen = rendered(&app);
+        assert!(screen.iter().any(|l| l.contains("26 matches (1-12 shown)")));
+        assert!(!screen.iter().any(|l| l.contains(" MAAAA ")));
+
+        press(&mut app, KeyCode::PageDown);
+        press(&mut app, KeyCode::Down);
+        let screen = rendered(&app);
+        assert!(screen.iter().any(|l| l.contains("26 matches (3-14 shown)")));
+        assert!(row(&screen, "NAAAA").contains('▶'));
+
+        // Selection stops at the last match
+        for _ in 0..3 {
+            press(&mut app, KeyCode::PageDown);
+        }
+        assert!(row(&rendered(&app), "ZAAAA").contains('▶'));
+    }
+}
+
+#[cfg(test)]
+mod entropy_chart_tests {
+    use super::*;
+    use crate::ui::rendering::entropy_chart::{EntropyPoint, render_entropy_chart};
+    use ratatui::{
+        layout::Rect,
+        style::{Color, Style},
+        text::Line,
+    };
+
+    fn point(entropy: f64, optimal: Option<bool>) -> EntropyPoint {
+        EntropyPoint { entropy, optimal }
+    }
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_rows_for_fixed_history() {
+        let history = [
+            point(6.0, Some(true)),
+            point(3.0, Some(false)),
+            point(1.5, None),
+        ];
+        let lines = render_entropy_chart(&history, Rect::new(0, 0, 30, 5));
+
+        let rows: Vec<String> = lines.iter().map(text).collect();
+        assert_eq!(
+            rows,
+            [
+                "6.0┤██       ",
+                "   │██       ",
+                "   │██ ██    ",
+                "   │██ ██ ██  1.50",
+                "  0└1  2  3  ",
+            ]
+        );
+
+        // Green where as good as optimal, red where not, plain if unmeasured
+        let bottom = &lines[3].spans;
+        assert_eq!(bottom[1].style, Style::default().fg(Color::Green));
+        assert_eq!(bottom[2].style, Style::default().fg(Color::Red));
+        assert_eq!(bottom[3].style, Style::default());
+    }
+
+    #[test]
+    fn test_scale_is_linear() {
+        let history = [point(8.0, None), point(4.0, None), point(2.0, None)];
+        let lines = render_entropy_chart(&history, Rect::new(0, 0, 30, 9));
+
+        // Eight bar rows: 8 bits fills all, 4 half, 2 a quarter
+        let heights: Vec<usize> = (0..3)
+            .map(|bar| {
+                lines[..8]
+                    .iter()
+                    .filter(|l| l.spans[bar + 1].content.contains('█'))
+                    .count()
+            })
+            .collect();
+        assert_eq!(heights, [8, 4, 2]);
+    }
+
+    #[test]
+    fn test_latest_guesses_kept_when_narrow() {
+        let history: Vec<EntropyPoint> = (0..10).map(|i| point(10.0 - i as f64, None)).collect();
+        // Room for four bars beside the labels and annotation
+        let lines = render_entropy_chart(&history, Rect::new(0, 0, 22, 3));
+
+        assert_eq!(text(&lines[2]), "   0└7  8  9  10 ");
+        // The scale stays that of the whole session
+        assert!(text(&lines[0]).starts_with("10.0┤   "));
+        assert!(text(&lines[1]).ends_with(" 1.00"));
+    }
+
+    #[test]
+    fn test_nothing_drawn_without_room_or_history() {
+        assert!(render_entropy_chart(&[], Rect::new(0, 0, 30, 5)).is_empty());
+        let history = [point(3.0, None)];
+        assert!(render_entropy_chart(&history, Rect::new(0, 0, 30, 1)).is_empty());
+        assert!(render_entropy_chart(&history, Rect::new(0, 0, 10, 5)).is_empty());
+    }
+
+    #[test]
+    fn test_zero_entropy_draws_no_bar() {
+        let history = [point(0.0, Some(true))];
+        let lines = render_entropy_chart(&history, Rect::new(0, 0, 20, 3));
+        assert_eq!(
+            lines.iter().map(text).collect::<Vec<_>>(),
+            ["0.0┤   ", "   │    0.00", "  0└1  "]
+        );
+    }
+
+    #[test]
+    fn test_pool_points_carry_session_optimality() {
+        let mut app = create_test_app();
+        SolverHandler::new(&mut app).submit_guess("magic".to_string(), vec![Feedback::Gray; 5]);
+        SolverHandler::new(&mut app).recompute_analysis();
+
+        let points = app.entropy_points();
+        assert_eq!(points.len(), 1);
+        assert_eq!(
+            points[0].optimal,
+            app.solver_session_guesses[0].was_optimal()
+        );
+        assert!(points[0].optimal.is_some());
+
+        // Guesses the session didn't measure are drawn plain
+        app.solver_session_guesses.clear();
+        assert_eq!(app.entropy_points()[0].optimal, None);
+    }
+}
+
+#[cfg(test)]
+mod archive_tests {
+    use super::*;
+    use crate::ui::{
+        history::{GameGuess, GameOutcome, GameRecord},
+        test_support::buffer_lines,
+    };
+    use chrono::{Duration, Utc};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn press(app: &mut App, c: char) {
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+
+    fn press_code(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    /// STONE won, CRANE lost, SLATE won, a day apart in that order.
+    fn app_with_games() -> App {
+        let mut app = create_test_app();
+        let start = Utc::now() - Duration::days(3);
+        let games = [
+            ("stone", GameOutcome::Won { guesses: 2 }),
+            ("crane", GameOutcome::Lost),
+            ("slate", GameOutcome::Won { guesses: 4 }),
+        ];
+        for (day, (word, outcome)) in games.into_iter().enumerate() {
+            app.db
+                .save_game(&GameRecord {
+                    timestamp: start + Duration::days(day as i64),
+                    target_word: word.to_string(),
+                    guesses: vec![GameGuess::new("raise".to_string(), vec![Feedback::Gray; 5])],
+                    outcome,
+                    blitz: false,
+                    duration_secs: None,
+                    app_version: None,
+                    difficulty: crate::settings::Difficulty::Normal,
+                    hints_used: 0,
+                    hint_budget: None,
+                    hints: Vec::new(),
+                    archived_at: None,
+                    word_difficulty: None,
+                })
+                .unwrap();
+        }
+        HistoryHandler::new(&mut app).enter_history_mode();
+        app
+    }
+
+    fn data(app: &App) -> &crate::ui::history::HistoryData {
+        app.history_data.as_ref().unwrap()
+    }
+
+    #[test]
+    fn test_archived_game_leaves_stats_until_included() {
+        let mut app = app_with_games();
+        assert_eq!(
+            (data(&app).stats.total_games, data(&app).stats.wins),
+            (3, 2)
+        );
+
+        press_code(&mut app, KeyCode::Tab);
+        press(&mut app, '1');
+        press(&mut app, 'a');
+
+        assert!(
+            app.logs
+                .lines()
+                .last()
+                .unwrap()
+                .starts_with("Archived STONE from ")
+        );
+        assert_eq!(data(&app).stats.total_games, 2);
+        assert_eq!(data(&app).stats.wins, 1);
+        assert_eq!(data(&app).stats.archived_games, 0);
+        assert_eq!(data(&app).difficulty_stats[0].games, 2);
+        // The game that took its place in the list is shown
+        assert_eq!(app.history_view_mode, HistoryViewMode::Detail);
+        assert_eq!(data(&app).selected_game().unwrap().target_word, "crane");
+
+        press_code(&mut app, KeyCode::Esc);
+        press_code(&mut app, KeyCode::Esc);
+        press(&mut app, 'A');
+
+        assert_eq!(app.logs.lines().last().unwrap(), "Showing archived games");
+        assert_eq!(data(&app).stats.total_games, 3);
+        assert_eq!(data(&app).stats.wins, 2);
+        assert_eq!(data(&app).stats.archived_games, 1);
+        assert_eq!(data(&app).difficulty_stats[0].games, 3);
+        assert!(data(&app).games[0].is_archived());
+
+        press(&mut app, 'A');
+        assert_eq!(data(&app).stats.total_games, 2);
+    }
+
+    #[test]
+    fn test_archived_games_marked_in_list() {
+        let mut app = app_with_games();
+        press_code(&mut app, KeyCode::Tab);
+        press(&mut app, '2');
+        press(&mut app, 'a');
+        press_code(&mut app, KeyCode::Esc);
+
+        let mut terminal = Terminal::new(TestBackend::new(160, 30)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let screen = buffer_lines(terminal.backend().buffer()).join("\n");
+        assert!(screen.contains("of 2)"), "{}", screen);
+        assert!(screen.contains("A show/hide archived"), "{}", screen);
+        assert!(!screen.contains("crane"), "{}", screen);
+
+        press(&mut app, 'A');
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let screen = buffer_lines(terminal.backend().buffer()).join("\n");
+        assert!(screen.contains("Lost, archived"), "{}", screen);
+        assert!(screen.contains("archived included)"), "{}", screen);
+    }
+
+    #[test]
+    fn test_archived_game_restored() {
+        let mut app = app_with_games();
+        press(&mut app, 'A');
+        press_code(&mut app, KeyCode::Tab);
+        press(&mut app, '3');
+        press(&mut app, 'a');
+
+        // Still listed while archived games are shown
+        assert_eq!(data(&app).selected_game().unwrap().target_word, "slate");
+        assert!(data(&app).selected_game().unwrap().is_archived());
+
+        press(&mut app, 'a');
+        assert!(!data(&app).selected_game().unwrap().is_archived());
+        assert!(
+            app.logs
+                .lines()
+                .last()
+                .unwrap()
+                .starts_with("Restored SLATE from ")
+        );
+
+        // Archived games are hidden again next time history is opened
+        app.db
+            .set_game_archived(data(&app).selected_game_id().unwrap(), true)
+            .unwrap();
+        HistoryHandler::new(&mut app).enter_history_mode();
+        assert!(!app.history_include_archived);
+        assert_eq!(data(&app).stats.total_games, 2);
+    }
+
+    #[test]
+    fn test_only_saved_games_archived() {
+        let mut app = app_with_games();
+        let games = data(&app).games.clone();
+        let mut unsaved = HistoryData::new(games, Vec::new());
+        unsaved.select_game(0);
+        app.history_data = Some(unsaved);
+        app.history_view_mode = HistoryViewMode::Detail;
+
+        press(&mut app, 'a');
+
+        assert_eq!(
+            app.logs.lines().last().unwrap(),
+            "Only saved games can be archived"
+        );
+    }
+}
+
+#[cfg(test)]
+mod input_history_tests {
+    use super::*;
+    use crate::ui::types::{INPUT_HISTORY_LEN, InputHistory};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn submit(app: &mut App, line: &str) {
+        app.input = line.to_string();
+        press(app, KeyCode::Enter);
+    }
+
+    #[test]
+    fn test_walks_back_and_forward_through_lines() {
+        let mut history = InputHistory::default();
+        for line in ["raise XXXXX", "stone GXXXX", "crane XYXXX"] {
+            history.record(&GameMode::Solver, line);
+        }
+
+        let older = |h: &mut InputHistory| h.older(&GameMode::Solver, "");
+        assert_eq!(older(&mut history).as_deref(), Some("crane XYXXX"));
+        assert_eq!(older(&mut history).as_deref(), Some("stone GXXXX"));
+        assert_eq!(older(&mut history).as_deref(), Some("raise XXXXX"));
+        // Stays on the oldest
+        assert_eq!(older(&mut history), None);
+
+        let newer = |h: &mut InputHistory| h.newer(&GameMode::Solver);
+        assert_eq!(newer(&mut history).as_deref(), Some("stone GXXXX"));
+        assert_eq!(newer(&mut history).as_deref(), Some("crane XYXXX"));
+        assert_eq!(newer(&mut history).as_deref(), Some(""));
+        // Nothing further once back on the draft
+        assert_eq!(newer(&mut history), None);
+    }
+
+    #[test]
+    fn test_draft_restored_past_newest() {
+        let mut history = InputHistory::default();
+        history.record(&GameMode::Solver, "raise XXXXX");
+        history.record(&GameMode::Solver, "stone GXXXX");
+
+        assert_eq!(
+            history.older(&GameMode::Solver, "sla").as_deref(),
+            Some("stone GXXXX")
+        );
+        // The draft is only taken when recall starts
+        assert_eq!(
+            history.older(&GameMode::Solver, "stone GXXXX").as_deref(),
+            Some("raise XXXXX")
+        );
+        assert_eq!(
+            history.newer(&GameMode::Solver).as_deref(),
+            Some("stone GXXXX")
+        );
+        assert_eq!(history.newer(&GameMode::Solver).as_deref(), Some("sla"));
+
+        // Submitting ends recall, so the next walk keeps a fresh draft
+        history.older(&GameMode::Solver, "sla");
+        history.record(&GameMode::Solver, "slate XXXXX");
+        assert_eq!(history.newer(&GameMode::Solver), None);
+        assert_eq!(
+            history.older(&GameMode::Solver, "mag").as_deref(),
+            Some("slate XXXXX")
+        );
+        assert_eq!(history.newer(&GameMode::Solver).as_deref(), Some("mag"));
+    }
+
+    #[test]
+    fn test_ring_keeps_latest_lines() {
+        let mut history = InputHistory::default();
+        for i in 0..INPUT_HISTORY_LEN + 5 {
+            history.record(&GameMode::Game, &format!("line{}", i));
+        }
+        // Blank lines and repeats of the newest aren't kept
+        history.record(&GameMode::Game, "   ");
+        history.record(&GameMode::Game, &format!("line{} ", INPUT_HISTORY_LEN + 4));
+
+        let entries = history.entries(&GameMode::Game);
+        assert_eq!(entries.len(), INPUT_HISTORY_LEN);
+        assert_eq!(entries.front().map(String::as_str), Some("line5"));
+        assert_eq!(
+            entries.back().cloned(),
+            Some(format!("line{}", INPUT_HISTORY_LEN + 4))
+        );
+    }
+
+    #[test]
+    fn test_resubmitted_line_moves_up() {
+        let mut history = InputHistory::default();
+        for line in ["raise XXXXX", "stone GXXXX", "crane XYXXX"] {
+            history.record(&GameMode::Solver, line);
+        }
+        history.record(&GameMode::Solver, "raise XXXXX");
+
+        let entries: Vec<&str> = history
+            .entries(&GameMode::Solver)
+            .iter()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(entries, ["stone GXXXX", "crane XYXXX", "raise XXXXX"]);
+    }
+
+    #[test]
+    fn test_modes_kept_apart() {
+        let mut history = InputHistory::default();
+        history.record(&GameMode::Solver, "raise XXXXX");
+        history.record(&GameMode::Game, "stone");
+        history.record(&GameMode::History, "ignored");
+
+        assert_eq!(history.older(&GameMode::Game, "").as_deref(), Some("stone"));
+        // Recall in the other mode starts over from its own newest line
+        assert_eq!(
+            history.older(&GameMode::Solver, "cra").as_deref(),
+            Some("raise XXXXX")
+        );
+        assert_eq!(history.newer(&GameMode::Game), None);
+        assert_eq!(history.newer(&GameMode::Solver).as_deref(), Some("cra"));
+        assert!(history.entries(&GameMode::History).is_empty());
+    }
+
+    #[test]
+    fn test_rejected_input_recalled_with_arrows() {
+        let mut app = create_test_app();
+        app.solver_session_active = false;
+        submit(&mut app, "raise XXXXX");
+        submit(&mut app, "stone GXXQX");
+        assert_eq!(app.input, "stone GXXQX");
+
+        app.input = "sl".to_string();
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.input, "stone GXXQX");
+        press(&mut app, KeyCode::Up);
+        assert_eq!(app.input, "raise XXXXX");
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.input, "sl");
+    }
+
+    #[test]
+    fn test_arrows_move_suggestion_selection_first() {
+        let mut app = create_test_app();
+        app.solver_session_active = false;
+        submit(&mut app, "typo");
+        app.input.clear();
+        app.suggestions = (0..8).map(|i| (i, 100 - i as usize)).collect();
+        app.suggestion_selected = Some(0);
+
+        press(&mut app, KeyCode::Down);
+
+        assert_eq!(app.input, "");
+        assert_eq!(app.suggestion_selected, Some(1));
+    }
+}
+
+#[cfg(test)]
+mod pattern_preview_tests {
+    use super::*;
+    use crate::{
+        scoring::{pattern_distribution, pattern_string},
+        ui::{test_support::buffer_lines, types::PATTERN_PREVIEW_MAX_POOL},
+    };
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn preview_app(input: &str) -> App {
+        let mut app = create_test_app();
+        app.solver_session_active = false;
+        app.analysis_dirty = true;
+        SolverHandler::new(&mut app).recompute_analysis();
+        app.input = input.to_string();
+        SolverHandler::new(&mut app).refresh_pattern_preview();
+        app
+    }
+
+    #[test]
+    fn test_distribution_for_typed_word() {
+        let app = preview_app("Crane ");
+        let preview = app.pattern_preview.as_ref().unwrap();
+        assert_eq!(preview.word, "crane");
+
+        let pool = app.solver.filter(&app.solution_words);
+        assert_eq!(
+            preview.odds,
+            pattern_distribution("crane", &app.solution_words, &pool)
+        );
+        assert_eq!(
+            preview.odds.iter().map(|o| o.remaining).sum::<usize>(),
+            pool.len()
+        );
+        let total: f64 = preview.odds.iter().map(|o| o.probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_only_for_a_known_word_without_pattern() {
+        for input in ["cran", "crane XXXXX", "zzzzz", ""] {
+            let app = preview_app(input);
+            assert!(app.pattern_preview.is_none(), "{:?}", input);
+        }
+
+        // Changing the input drops the preview
+        let mut app = preview_app("crane");
+        app.input.pop();
+        SolverHandler::new(&mut app).refresh_pattern_preview();
+        assert!(app.pattern_preview.is_none());
+
+        let mut app = preview_app("crane");
+        app.mode = GameMode::Game;
+        SolverHandler::new(&mut app).refresh_pattern_preview();
+        assert!(app.pattern_preview.is_none());
+    }
+
+    #[test]
+    fn test_recomputed_when_pool_changes() {
+        let mut app = preview_app("house");
+        assert_eq!(app.pattern_preview.as_ref().unwrap().odds.len(), 7);
+
+        SolverHandler::new(&mut app).submit_guess(
+            "stone".to_string(),
+            crate::solver::parse_pattern("XXXXG").unwrap(),
+        );
+        SolverHandler::new(&mut app).recompute_analysis();
+        assert!(app.pattern_preview.is_none());
+
+        app.input = "house".to_string();
+        SolverHandler::new(&mut app).refresh_pattern_preview();
+        let pool = app.solver.filter(&app.solution_words).len();
+        let odds = &app.pattern_preview.as_ref().unwrap().odds;
+        assert_eq!(odds.iter().map(|o| o.remaining).sum::<usize>(), pool);
+    }
+
+    #[test]
+    fn test_nothing_previewed_for_large_pool() {
+        let words: Vec<String> = (0..=PATTERN_PREVIEW_MAX_POOL)
+            .map(|i| {
+                let letter = |n: usize| (b'a' + (n % 26) as u8) as char;
+                [i / 676, i / 26, i, 0, 1].into_iter().map(letter).collect()
+            })
+            .collect();
+        let mut app = AppBuilder::new()
+            .words(words.clone())
+            .solutions(words)
+            .build()
+            .unwrap();
+        app.input = "aaaab".to_string();
+
+        SolverHandler::new(&mut app).refresh_pattern_preview();
+
+        let preview = app.pattern_preview.as_ref().unwrap();
+        assert_eq!(preview.word, "aaaab");
+        assert!(preview.odds.is_empty());
+    }
+
+    #[test]
+    fn test_top_patterns_drawn_in_pool_panel() {
+        let mut app = preview_app("crane ");
+        // One row more than the panels take, for the status bar
+        let mut terminal = Terminal::new(TestBackend::new(120, 41)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let screen = buffer_lines(terminal.backend().buffer()).join("\n");
+
+        assert!(screen.contains("If you guess CRANE:"), "{}", screen);
+        let odds = &app.pattern_preview.as_ref().unwrap().odds;
+        for o in &odds[..3] {
+            let row = format!(
+                "{} {:>3.0}% → {} left",
+                pattern_string(&o.feedback),
+                o.probability * 100.0,
+                o.remaining
+            );
+            assert!(screen.contains(&row), "{}\n{}", row, screen);
+        }
+        let fourth = pattern_string(&odds[3].feedback);
+        assert!(!screen.contains(&format!("{} ", fourth)), "{}", screen);
+
+        app.input.clear();
+        SolverHandler::new(&mut app).refresh_pattern_preview();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let screen = buffer_lines(terminal.backend().buffer()).join("\n");
+        assert!(!screen.contains("If you guess"), "{}", screen);
+    }
+}
+
+#[cfg(test)]
+mod gauntlet_tests {
+    use super::*;
+    use crate::ui::{
+        history::{GameOutcome, HistoryViewMode},
+        types::{GAUNTLET_PAUSE, Overlay},
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::time::Instant;
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn start_gauntlet(app: &mut App, size: usize) {
+        app.settings.gauntlet_size = size;
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL));
+    }
+
+    fn target(app: &App) -> String {
+        app.target_word.clone().unwrap()
+    }
+
+    /// Guess a word other than the answer, then give up.
+    fn lose_after_one_guess(app: &mut App) {
+        let wrong = ["raise", "stone"]
+            .into_iter()
+            .find(|w| *w != target(app))
+            .unwrap();
+        GameHandler::new(app).submit_guess(wrong.to_string());
+        GameHandler::new(app).give_up();
+    }
+
+    /// A two-game gauntlet: the first solved in one guess, the second given
+    /// up after one wrong guess, the second game started by the timer.
+    fn played_gauntlet() -> (App, [String; 2]) {
+        let mut app = create_test_app();
+        start_gauntlet(&mut app, 2);
+        let first = target(&app);
+        GameHandler::new(&mut app).submit_guess(first.clone());
+
+        let gauntlet = app.gauntlet.as_ref().unwrap();
+        assert_eq!(gauntlet.results, [(1, true)]);
+        assert!(gauntlet.next_at.is_some());
+        GameHandler::new(&mut app).tick(Instant::now() + GAUNTLET_PAUSE);
+        assert!(!app.game_over);
+        let second = target(&app);
+        assert_ne!(first, second);
+
+        lose_after_one_guess(&mut app);
+        (app, [first, second])
+    }
+
+    #[test]
+    fn test_two_game_gauntlet_summary() {
+        let (app, [first, second]) = played_gauntlet();
+
+        assert!(app.gauntlet.is_none());
+        let Some(Overlay::GauntletSummary(summary)) = &app.overlay else {
+            panic!("expected the gauntlet summary, got {:?}", app.overlay);
+        };
+        assert_eq!(summary.size, 2);
+        assert_eq!((summary.wins(), summary.total_guesses()), (1, 2));
+        let ranked: Vec<_> = summary
+            .games
+            .iter()
+            .map(|(_, g)| (g.target_word.clone(), g.outcome.clone()))
+            .collect();
+        assert_eq!(
+            ranked,
+            [
+                (first, GameOutcome::Won { guesses: 1 }),
+                (second, GameOutcome::Forfeited)
+            ]
+        );
+        assert_eq!(
+            app.logs.lines().last().unwrap(),
+            "Gauntlet over: 1 of 2 won in 2 guesses"
+        );
+    }
+
+    #[test]
+    fn test_gauntlet_games_stored_as_group() {
+        let (mut app, _) = played_gauntlet();
+        let Some(Overlay::GauntletSummary(summary)) = &app.overlay else {
+            panic!("expected the gauntlet summary");
+        };
+        let ids: Vec<i64> = summary.games.iter().map(|(id, _)| *id).collect();
+
+        // A later game outside the gauntlet isn't part of the group
+        press(&mut app, KeyCode::Esc);
+        press(&mut app, KeyCode::Enter);
+        assert!(app.gauntlet.is_none());
+        lose_after_one_guess(&mut app);
+
+        let all = app.db.load_games_with_ids(false).unwrap();
+        assert_eq!(all.len(), 3);
+        let gauntlet_id: i64 = 1;
+        let grouped: Vec<i64> = app
+            .db
+            .load_gauntlet_games(gauntlet_id)
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(grouped, [all[0].0, all[1].0]);
+        assert_eq!(ids, grouped);
+    }
+
+    #[test]
+    fn test_summary_opens_game_detail() {
+        let (mut app, [_, second]) = played_gauntlet();
+
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+
+        assert!(app.overlay.is_none());
+        assert_eq!(app.mode, GameMode::History);
+        assert_eq!(app.history_view_mode, HistoryViewMode::Detail);
+        let data = app.history_data.as_ref().unwrap();
+        assert_eq!(data.selected_game().unwrap().target_word, second);
+    }
+
+    #[test]
+    fn test_enter_starts_next_game_without_waiting() {
+        let mut app = create_test_app();
+        start_gauntlet(&mut app, 3);
+        let first = target(&app);
+        GameHandler::new(&mut app).submit_guess(first.clone());
+
+        press(&mut app, KeyCode::Enter);
+
+        assert!(!app.game_over);
+        assert_ne!(target(&app), first);
+        let gauntlet = app.gauntlet.as_ref().unwrap();
+        assert_eq!(gauntlet.next_at, None);
+        assert_eq!(app.logs.lines().last().unwrap(), "Gauntlet game 2 of 3");
+    }
+
+    #[test]
+    fn test_leaving_mid_game_ends_gauntlet() {
+        let mut app = create_test_app();
+        app.settings.confirm_discard = false;
+        start_gauntlet(&mut app, 2);
+        let wrong = ["raise", "stone"]
+            .into_iter()
+            .find(|w| *w != target(&app))
+            .unwrap();
+        GameHandler::new(&mut app).submit_guess(wrong.to_string());
+
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL));
+
+        assert!(app.gauntlet.is_none());
+        assert!(app.overlay.is_none());
+        assert!(
+            app.logs
+                .lines()
+                .contains(&"Left the gauntlet after 0 of 2 games".to_string())
+        );
+        // The abandoned game still belongs to the gauntlet
+        assert_eq!(app.db.load_gauntlet_games(1).unwrap().len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod repeat_guess_tests {
+    use super::*;
+    use crate::{settings::Difficulty, ui::types::Overlay};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn submit(app: &mut App, line: &str) {
+        app.input = line.to_string();
+        press(app, KeyCode::Enter);
+    }
+
+    fn is_repeat(app: &App) -> bool {
+        matches!(app.input_status_immutable(), InputStatus::Invalid(msg) if msg == "already guessed")
+    }
+
+    /// A game with answer STONE, RAISE already guessed.
+    fn game_after_raise(difficulty: Difficulty) -> App {
+        let mut app = create_test_app();
+        app.settings.difficulty = difficulty;
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("stone".to_string());
+        submit(&mut app, "raise");
+        assert_eq!(app.solver.guesses().len(), 1);
+        app
+    }
+
+    #[test]
+    fn test_repeat_rejected_in_game() {
+        let mut app = game_after_raise(Difficulty::Normal);
+
+        submit(&mut app, "RAISE");
+
+        assert!(is_repeat(&app));
+        assert_eq!(app.solver.guesses().len(), 1);
+        assert_eq!(app.remaining_guesses, 5);
+        assert!(app.overlay.is_none());
+    }
+
+    #[test]
+    fn test_repeat_rejected_in_solver() {
+        let mut app = create_test_app();
+        app.solver_session_active = false;
+        submit(&mut app, "house XXXXG");
+
+        app.input = "house XXXXG".to_string();
+        assert!(is_repeat(&app));
+        // A different pattern is still the same zero-information word
+        app.input = "house GXXXG".to_string();
+        assert!(is_repeat(&app));
+        app.input = "apple XXXXG".to_string();
+        assert!(matches!(app.input_status_immutable(), InputStatus::Valid));
+    }
+
+    #[test]
+    fn test_repeat_confirmed_when_enabled() {
+        let mut app = game_after_raise(Difficulty::Normal);
+        app.settings.confirm_repeat_guess = true;
+
+        submit(&mut app, "raise");
+        assert!(matches!(
+            &app.overlay,
+            Some(Overlay::ConfirmRepeat { word }) if word == "raise"
+        ));
+        assert_eq!(app.solver.guesses().len(), 1);
+
+        // No keeps the word in the input for editing
+        press(&mut app, KeyCode::Char('n'));
+        assert!(app.overlay.is_none());
+        assert_eq!(app.input, "raise");
+        assert_eq!(app.solver.guesses().len(), 1);
+
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('y'));
+        assert!(app.overlay.is_none());
+        assert_eq!(app.solver.guesses().len(), 2);
+        assert_eq!(app.remaining_guesses, 4);
+    }
+
+    #[test]
+    fn test_undone_guess_can_be_guessed_again() {
+        let mut app = game_after_raise(Difficulty::Easy);
+
+        GameHandler::new(&mut app).undo_guess();
+        submit(&mut app, "raise");
+
+        assert_eq!(app.solver.guesses().len(), 1);
+        assert!(app.overlay.is_none());
+    }
+
+    #[test]
+    fn test_undone_solver_guess_can_be_entered_again() {
+        let mut app = create_test_app();
+        app.solver_session_active = false;
+        submit(&mut app, "house XXXXG");
+
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL));
+        app.input = "house XXXXG".to_string();
+
+        assert!(matches!(app.input_status_immutable(), InputStatus::Valid));
+    }
+}
+
+#[cfg(test)]
+mod history_loading_tests {
+    use super::*;
+    use crate::ui::{
+        history::{GameGuess, GameOutcome, GameRecord, HistoryViewMode},
+        test_support::buffer_lines,
+        types::HISTORY_BATCH,
+    };
+    use chrono::{Duration, Utc};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    const GAMES: usize = 500;
+
+    /// GAMES games a minute apart, every seventh one lost up to game 476,
+    /// so they end on a streak of 23 wins.
+    fn app_with_many_games() -> App {
+        let app = create_test_app();
+        let start = Utc::now() - Duration::days(1);
+        for i in 0..GAMES {
+            let outcome = if i % 7 == 0 && i <= 476 {
+                GameOutcome::Lost
+            } else {
+                GameOutcome::Won { guesses: 1 }
+            };
+            app.db
+                .save_game(&GameRecord {
+                    timestamp: start + Duration::minutes(i as i64),
+                    target_word: "stone".to_string(),
+                    guesses: vec![GameGuess::new(
+                        "stone".to_string(),
+                        vec![Feedback::Green; 5],
+                    )],
+                    outcome,
+                    blitz: false,
+                    duration_secs: None,
+                    app_version: None,
+                    difficulty: crate::settings::Difficulty::Normal,
+                    hints_used: 0,
+                    hint_budget: None,
+                    hints: Vec::new(),
+                    archived_at: None,
+                    word_difficulty: None,
+                })
+                .unwrap();
+        }
+        app
+    }
+
+    fn screen(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(140, 50)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        buffer_lines(terminal.backend().buffer()).join("\n")
+    }
+
+    fn data(app: &App) -> &crate::ui::history::HistoryData {
+        app.history_data.as_ref().unwrap()
+    }
+
+    #[test]
+    fn test_first_render_before_all_games_load() {
+        let mut app = app_with_many_games();
+
+        HistoryHandler::new(&mut app).enter_history_mode();
+
+        assert_eq!(data(&app).games.len(), HISTORY_BATCH);
+        assert_eq!(data(&app).stored_games, GAMES);
+        assert!(data(&app).is_loading());
+        // Streaks need every game, so the stats wait for the rest
+        assert_eq!(data(&app).stats.total_games, 0);
+        let screen = screen(&app);
+        assert!(
+            screen.contains("Loading details… 200 of 500 games"),
+            "{}",
+            screen
+        );
+        assert!(screen.contains("Games Played: 500"), "{}", screen);
+        assert!(!screen.contains("Current Streak"), "{}", screen);
+    }
+
+    #[test]
+    fn test_remaining_games_load_a_batch_per_step() {
+        let mut app = app_with_many_games();
+        HistoryHandler::new(&mut app).enter_history_mode();
+
+        assert!(HistoryHandler::new(&mut app).step_load());
+        assert_eq!(data(&app).games.len(), 2 * HISTORY_BATCH);
+        assert!(!HistoryHandler::new(&mut app).step_load());
+        assert!(!HistoryHandler::new(&mut app).step_load());
+
+        let data = data(&app);
+        assert_eq!(data.games.len(), GAMES);
+        assert_eq!(data.game_ids.len(), GAMES);
+        assert_eq!(data.visible_count(), GAMES);
+        assert_eq!(data.stats.total_games, GAMES);
+        assert_eq!(data.stats.current_streak, 23);
+        assert!(data.game_ids.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(
+            app.logs.lines().last().unwrap(),
+            "Loaded 500 game(s) and 0 solver session(s) from history"
+        );
+        let screen = screen(&app);
+        assert!(screen.contains("Current Streak: 23 wins"), "{}", screen);
+        assert!(!screen.contains("Loading details"), "{}", screen);
+    }
+
+    #[test]
+    fn test_last_page_fetches_its_games() {
+        let mut app = app_with_many_games();
+        HistoryHandler::new(&mut app).enter_history_mode();
+        app.history_view_mode = HistoryViewMode::List;
+
+        HistoryHandler::new(&mut app).last_page();
+
+        assert_eq!(app.history_page, GAMES / 10 - 1);
+        assert_eq!(data(&app).games_for_page(app.history_page).len(), 10);
+        let screen = screen(&app);
+        assert!(screen.contains("Showing 491-500 of 500"), "{}", screen);
+    }
+
+    #[test]
+    fn test_pages_count_games_not_yet_loaded() {
+        let mut app = app_with_many_games();
+        HistoryHandler::new(&mut app).enter_history_mode();
+
+        assert_eq!(data(&app).total_pages(), GAMES / 10);
+        let screen = {
+            app.history_view_mode = HistoryViewMode::List;
+            screen(&app)
+        };
+        assert!(screen.contains("Page 1/50"), "{}", screen);
+    }
+}
+
+#[cfg(test)]
+mod resume_session_tests {
+    use super::*;
+    use crate::{
+        solver::generate_feedback,
+        storage::{Database, new_write_id},
+        ui::{
+            history::{AbandonReason, SolverOutcome},
+            types::Overlay,
+        },
+    };
+    use chrono::{Duration, Utc};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn guess(app: &mut App, word: &str) {
+        SolverHandler::new(app).submit_guess(word.to_string(), generate_feedback("apple", word));
+    }
+
+    /// Start the app again on `app`'s database, as the next launch would.
+    fn relaunch(app: &mut App) -> App {
+        let db = std::mem::replace(&mut app.db, Database::open_memory().unwrap());
+        let mut app = AppBuilder::for_tests().db(db).build().unwrap();
+        SolverHandler::new(&mut app).offer_resume();
+        app
+    }
+
+    /// A solver session with MAGIC against APPLE, quit with SLATE and APPLE left.
+    fn quit_with_session() -> App {
+        let mut app = create_test_app();
+        guess(&mut app, "magic");
+        GameHandler::new(&mut app).quit();
+        app
+    }
+
+    #[test]
+    fn test_resume_rebuilds_solver() {
+        let mut app = relaunch(&mut quit_with_session());
+        assert!(matches!(app.overlay, Some(Overlay::ResumeSession(_))));
+
+        press(&mut app, KeyCode::Char('y'));
+
+        assert!(app.overlay.is_none());
+        let words: Vec<&str> = app
+            .solver
+            .guesses()
+            .iter()
+            .map(|g| g.word.as_str())
+            .collect();
+        assert_eq!(words, ["magic"]);
+        assert_eq!(app.solver_session_guesses.len(), 1);
+        assert_eq!(app.solver.filter(&app.solution_words).len(), 2);
+        // Back open, and finishing it replaces the abandoned row
+        assert!(app.db.load_solver_sessions().unwrap().is_empty());
+        guess(&mut app, "apple");
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].outcome, SolverOutcome::Completed { guesses: 2 });
+    }
+
+    #[test]
+    fn test_decline_keeps_session_abandoned() {
+        let mut app = relaunch(&mut quit_with_session());
+
+        press(&mut app, KeyCode::Esc);
+
+        assert!(app.overlay.is_none());
+        assert!(app.solver.guesses().is_empty());
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(
+            sessions[0].outcome,
+            SolverOutcome::Abandoned {
+                guesses: 1,
+                reason: AbandonReason::Quit
+            }
+        );
+        // Not offered a second time
+        assert!(relaunch(&mut app).overlay.is_none());
+    }
+
+    #[test]
+    fn test_old_session_not_offered() {
+        let mut app = create_test_app();
+        guess(&mut app, "magic");
+        let guesses = app.solver_session_guesses.clone();
+        app.db
+            .save_solver_session_as(
+                &new_write_id(),
+                Utc::now() - Duration::hours(3),
+                &guesses,
+                Some(AbandonReason::Quit),
+            )
+            .unwrap();
+        app.solver = crate::solver::SolverState::new(5);
+
+        app.settings.resume_window_hours = 2;
+        SolverHandler::new(&mut app).offer_resume();
+        assert!(app.overlay.is_none());
+
+        app.settings.resume_window_hours = 0;
+        SolverHandler::new(&mut app).offer_resume();
+        assert!(app.overlay.is_none());
+
+        app.settings.resume_window_hours = 4;
+        SolverHandler::new(&mut app).offer_resume();
+        assert!(matches!(app.overlay, Some(Overlay::ResumeSession(_))));
+    }
+}
+
+#[cfg(test)]
+mod wordlist_comparison_tests {
+    use super::*;
+    use crate::{
+        solver::generate_feedback,
+        storage::Database,
+        ui::test_support::{FIXTURE_WORDS, buffer_lines},
+    };
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn suggestions_panel(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(80, 12)).unwrap();
+        terminal
+            .draw(|f| app.draw_suggestions(f, f.area()))
+            .unwrap();
+        buffer_lines(terminal.backend().buffer()).join("\n")
+    }
+
+    /// MAGIC played against APPLE, leaving SLATE and APPLE.
+    fn guess_magic(app: &mut App) {
+        SolverHandler::new(app)
+            .submit_guess("magic".to_string(), generate_feedback("apple", "magic"));
+    }
+
+    #[test]
+    fn test_flagged_suggestion_marked_and_logged() {
+        let mut app = AppBuilder::for_tests()
+            .flagged_words(["slate".to_string()])
+            .build()
+            .unwrap();
+        guess_magic(&mut app);
+
+        let panel = suggestions_panel(&app);
+        let slate = panel.lines().find(|l| l.contains("slate (")).unwrap();
+        assert!(slate.contains(" ?"), "{}", slate);
+        let apple = panel.lines().find(|l| l.contains("apple (")).unwrap();
+        assert!(!apple.contains('?'), "{}", apple);
+        let notes = |app: &App| {
+            app.logs
+                .lines()
+                .iter()
+                .filter(|l| l.contains("Not in the comparison list, so maybe not accepted: slate"))
+                .count()
+        };
+        assert_eq!(notes(&app), 1);
+
+        // Still on top, so not noted again
+        SolverHandler::new(&mut app).refresh_derived_state();
+        assert_eq!(notes(&app), 1);
+    }
+
+    #[test]
+    fn test_comparison_list_flags_missing_words() {
+        let dir = tempfile::tempdir().unwrap();
+        let allowed = dir.path().join("words.txt");
+        let upstream = dir.path().join("upstream.txt");
+        std::fs::write(&allowed, FIXTURE_WORDS.join("\n")).unwrap();
+        std::fs::write(&upstream, "apple\nslate\nadieu\n").unwrap();
+        let options = crate::wordlist::WordlistOptions {
+            words_path: Some(allowed.clone()),
+            solutions_path: Some(allowed),
+            compare_path: Some(upstream),
+            offline: true,
+            ..Default::default()
+        };
+
+        let app = crate::ui::build_app(Database::open_memory().unwrap(), None, &options).unwrap();
+
+        assert!(app.flagged_words.contains("crane"));
+        assert!(!app.flagged_words.contains("slate"));
+        assert_eq!(app.flagged_words.len(), FIXTURE_WORDS.len() - 2);
+        assert!(
+            app.logs
+                .lines()
+                .iter()
+                .any(|l| l.ends_with("6 local word(s) missing from it, 1 missing locally"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod low_refresh_tests {
+    use super::*;
+    use crate::{
+        solver::generate_feedback,
+        ui::{test_support::buffer_lines, types::LOW_REFRESH_FRAME},
+    };
+    use ratatui::{Terminal, backend::TestBackend};
+    use std::time::{Duration, Instant};
+
+    fn draw(app: &App, terminal: &mut Terminal<TestBackend>) -> String {
+        terminal.draw(|f| app.draw(f)).unwrap();
+        buffer_lines(terminal.backend().buffer()).join("\n")
+    }
+
+    /// MAGIC played against APPLE, with the analysis worked out.
+    fn app_after_guess() -> App {
+        let mut app = create_test_app();
+        let mut handler = SolverHandler::new(&mut app);
+        handler.submit_guess("magic".to_string(), generate_feedback("apple", "magic"));
+        handler.recompute_analysis();
+        app
+    }
+
+    #[test]
+    fn test_unchanged_panels_not_rebuilt() {
+        let mut app = app_after_guess();
+        let mut terminal = Terminal::new(TestBackend::new(140, 45)).unwrap();
+
+        let first = draw(&app, &mut terminal);
+        let built = app.panel_cache.builds();
+        assert_eq!(built, 4);
+        assert_eq!(draw(&app, &mut terminal), first);
+        assert_eq!(app.panel_cache.builds(), built);
+
+        // Typing a word only changes the pool panel's preview
+        app.input = "slate".to_string();
+        SolverHandler::new(&mut app).refresh_pattern_preview();
+        let typed = draw(&app, &mut terminal);
+        assert!(typed.contains("If you guess SLATE:"));
+        assert_eq!(app.panel_cache.builds(), built + 1);
+
+        // A new guess changes them all
+        SolverHandler::new(&mut app)
+            .submit_guess("slate".to_string(), generate_feedback("apple", "slate"));
+        SolverHandler::new(&mut app).recompute_analysis();
+        draw(&app, &mut terminal);
+        assert_eq!(app.panel_cache.builds(), built + 5);
+    }
+
+    #[test]
+    fn test_low_refresh_spaces_out_frames() {
+        let mut app = create_test_app();
+        let now = Instant::now();
+        app.last_draw = Some(now);
+        assert_eq!(app.frame_wait(now), Duration::ZERO);
+
+        app.settings.low_refresh = true;
+        assert_eq!(app.frame_wait(now), LOW_REFRESH_FRAME);
+        assert_eq!(
+            app.frame_wait(now + Duration::from_millis(150)),
+            Duration::from_millis(50)
+        );
+        assert_eq!(app.frame_wait(now + LOW_REFRESH_FRAME), Duration::ZERO);
+
+        app.settings.low_refresh = false;
+        app.low_refresh_forced = true;
+        assert_eq!(app.frame_wait(now), LOW_REFRESH_FRAME);
+    }
+
+    #[test]
+    fn test_low_refresh_draws_plain_characters() {
+        let mut app = app_after_guess();
+        let mut terminal = Terminal::new(TestBackend::new(140, 45)).unwrap();
+
+        let full = draw(&app, &mut terminal);
+        assert!(full.contains('█'));
+
+        app.settings.low_refresh = true;
+        let plain = draw(&app, &mut terminal);
+        assert!(!plain.contains('█'), "{}", plain);
+        assert!(plain.contains("##"), "{}", plain);
+        // No entropy chart axis under the pool summary
+        assert!(full.contains('┤'));
+        assert!(!plain.contains('┤'), "{}", plain);
+    }
+}
+
+#[cfg(test)]
+mod guess_note_tests {
+    use super::*;
+    use crate::{
+        settings::Difficulty,
+        solver::generate_feedback,
+        ui::{test_support::buffer_lines, types::Overlay},
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn note(app: &mut App, text: &str) {
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL));
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+        press(app, KeyCode::Enter);
+    }
+
+    #[test]
+    fn test_note_saved_with_game() {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("stone".to_string());
+        GameHandler::new(&mut app).submit_guess("raise".to_string());
+        GameHandler::new(&mut app).submit_guess("slate".to_string());
+        note(&mut app, "should have tried crane");
+        assert!(app.overlay.is_none());
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let lines = buffer_lines(terminal.backend().buffer());
+        assert!(
+            lines
+                .iter()
+                .any(|l| l.contains(" E   should have tried crane"))
+        );
+
+        GameHandler::new(&mut app).submit_guess("stone".to_string());
+        let games = app.db.load_games().unwrap();
+        let notes: Vec<Option<&str>> = games[0].guesses.iter().map(|g| g.note.as_deref()).collect();
+        assert_eq!(notes, [None, Some("should have tried crane"), None]);
+    }
+
+    #[test]
+    fn test_note_prefilled_and_cleared_when_blank() {
+        let mut app = create_test_app();
+        SolverHandler::new(&mut app)
+            .submit_guess("magic".to_string(), generate_feedback("apple", "magic"));
+        note(&mut app, "opener test");
+        assert_eq!(
+            app.solver_session_guesses[0].note.as_deref(),
+            Some("opener test")
+        );
+
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL));
+        assert!(matches!(&app.overlay, Some(Overlay::GuessNote { text }) if text == "opener test"));
+        for _ in 0.."opener test".len() {
+            press(&mut app, KeyCode::Backspace);
+        }
+        press(&mut app, KeyCode::Enter);
+        assert!(app.guess_notes.is_empty());
+        assert_eq!(app.solver_session_guesses[0].note, None);
+    }
+
+    #[test]
+    fn test_undo_deletes_note() {
+        let mut app = create_test_app();
+        SolverHandler::new(&mut app)
+            .submit_guess("magic".to_string(), generate_feedback("apple", "magic"));
+        note(&mut app, "gamble");
+        SolverHandler::new(&mut app).undo_guess();
+
+        assert!(app.guess_notes.is_empty());
+        assert!(app.solver_session_guesses.is_empty());
+
+        // The same slot filled by another word starts without a note
+        SolverHandler::new(&mut app)
+            .submit_guess("slate".to_string(), generate_feedback("apple", "slate"));
+        assert!(app.guess_notes.is_empty());
+        assert!(app.solver_session_guesses.iter().all(|g| g.note.is_none()));
+    }
+
+    #[test]
+    fn test_undo_in_game_drops_note_from_record() {
+        let mut app = create_test_app();
+        app.settings.difficulty = Difficulty::Easy;
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("stone".to_string());
+        GameHandler::new(&mut app).submit_guess("raise".to_string());
+        note(&mut app, "bad start");
+        GameHandler::new(&mut app).undo_guess();
+        assert!(app.guess_notes.is_empty());
+
+        GameHandler::new(&mut app).submit_guess("stone".to_string());
+        let games = app.db.load_games().unwrap();
+        assert!(games[0].guesses.iter().all(|g| g.note.is_none()));
+    }
+}
+
+#[cfg(test)]
+mod answer_prior_tests {
+    use super::*;
+    use crate::{solver::generate_feedback, ui::test_support::buffer_lines};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    const WORDS: [&str; 8] = [
+        "foxes", "focal", "boxes", "hoses", "fores", "coals", "doles", "jumpy",
+    ];
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn suggestions_panel(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 12)).unwrap();
+        terminal
+            .draw(|f| app.draw_suggestions(f, f.area()))
+            .unwrap();
+        buffer_lines(terminal.backend().buffer()).join("\n")
+    }
+
+    /// JUMPY played against FOCAL, which rules out no other candidate.
+    fn app_after_guess() -> App {
+        let words: Vec<String> = WORDS.iter().map(|w| w.to_string()).collect();
+        let mut app = AppBuilder::for_tests()
+            .words(words.clone())
+            .solutions(words)
+            .build()
+            .unwrap();
+        SolverHandler::new(&mut app)
+            .submit_guess("jumpy".to_string(), generate_feedback("focal", "jumpy"));
+        app
+    }
+
+    fn rank(app: &App, word: &str) -> usize {
+        app.suggested_words().position(|(w, _)| w == word).unwrap()
+    }
+
+    #[test]
+    fn test_p_toggles_priors_and_persists() {
+        let mut app = app_after_guess();
+        assert!(!app.settings.answer_priors);
+        assert!(rank(&app, "foxes") < rank(&app, "focal"));
+
+        app.suggestion_selected = Some(0);
+        press(&mut app, KeyCode::Char('p'));
+
+        assert!(app.settings.answer_priors);
+        assert!(rank(&app, "foxes") > rank(&app, "focal"));
+        assert!(suggestions_panel(&app).contains("answer priors"));
+        assert!(
+            crate::settings::Settings::load(&app.db)
+                .unwrap()
+                .answer_priors
+        );
+
+        press(&mut app, KeyCode::Char('p'));
+        assert!(app.prior_shifts.is_empty());
+        assert!(rank(&app, "foxes") < rank(&app, "focal"));
+    }
+
+    #[test]
+    fn test_badge_for_big_moves_only() {
+        let mut app = app_after_guess();
+        app.settings.answer_priors = true;
+        SolverHandler::new(&mut app).refresh_derived_state();
+        assert!(app.prior_shifts.values().any(|shift| shift.abs() > 2));
+        assert!(app.prior_shifts.values().any(|shift| shift.abs() <= 2));
+
+        let panel = suggestions_panel(&app);
+        for (i, &shift) in &app.prior_shifts {
+            let word = app.solution_words.word(*i);
+            let line = panel
+                .lines()
+                .find(|l| l.contains(&format!("{word} (")))
+                .unwrap();
+            assert_eq!(
+                line.contains(&format!("{:+}", shift)),
+                shift.abs() > 2,
+                "{}",
+                line
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod opener_stats_tests {
+    use super::*;
+    use crate::{
+        settings::Difficulty,
+        ui::{
+            history::{GameGuess, GameOutcome, GameRecord},
+            test_support::buffer_lines,
+        },
+    };
+    use chrono::Utc;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn screen(app: &App) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(160, 40)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        buffer_lines(terminal.backend().buffer())
+    }
+
+    fn save_game(app: &App, opener: &str, outcome: GameOutcome) {
+        app.db
+            .save_game(&GameRecord {
+                timestamp: Utc::now(),
+                target_word: "stone".to_string(),
+                guesses: vec![GameGuess::new(opener.to_string(), vec![])],
+                outcome,
+                blitz: false,
+                duration_secs: None,
+                app_version: None,
+                difficulty: Difficulty::Normal,
+                hints_used: 0,
+                hint_budget: None,
+                hints: Vec::new(),
+                archived_at: None,
+                word_difficulty: None,
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_stats_view_breaks_down_by_opener() {
+        let mut app = create_test_app();
+        for outcome in [
+            GameOutcome::Won { guesses: 1 },
+            GameOutcome::Won { guesses: 1 },
+            GameOutcome::Lost,
+        ] {
+            save_game(&app, "crane", outcome);
+        }
+        save_game(&app, "slate", GameOutcome::Won { guesses: 1 });
+        HistoryHandler::new(&mut app).enter_history_mode();
+
+        let lines = screen(&app);
+        assert!(lines.iter().any(|l| l.contains("By Opener")));
+        let row = |name: &str| lines.iter().find(|l| l.contains(name)).unwrap().clone();
+        assert!(row("CRANE ").contains("67%"));
+        assert!(row("other ").contains("100%"));
+        assert!(!lines.iter().any(|l| l.contains("SLATE")));
+    }
+
+    #[test]
+    fn test_no_opener_table_until_one_is_used_enough() {
+        let mut app = create_test_app();
+        save_game(&app, "crane", GameOutcome::Won { guesses: 1 });
+        save_game(&app, "slate", GameOutcome::Lost);
+        HistoryHandler::new(&mut app).enter_history_mode();
+
+        assert!(!screen(&app).iter().any(|l| l.contains("By Opener")));
+    }
+}
+
+#[cfg(test)]
+mod normalizer_tests {
+    use super::*;
+    use crate::{normalizer::CharNormalizer, solver::Feedback, ui::test_support::buffer_lines};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn spanish_app() -> App {
+        let words: Vec<String> = ["cañon", "tacón", "canto", "perro", "árbol", "nieve"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        AppBuilder::new()
+            .words(words.clone())
+            .solutions(words)
+            .normalizer(CharNormalizer::Spanish)
+            .build()
+            .unwrap()
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_accented_guess_matches_plain_answer() {
+        let mut app = spanish_app();
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("tacon".to_string());
+
+        // Typed with the accent or without, it's the same word
+        type_text(&mut app, "TACON");
+        assert_eq!(app.solver.guesses()[0].feedback, [Feedback::Green; 5]);
+        assert!(app.game_over);
+    }
+
+    #[test]
+    fn test_tiles_show_original_spelling() {
+        let mut app = spanish_app();
+        // Against TACÓN
+        type_text(&mut app, "árbol YXXGX");
+        assert_eq!(app.solver.guesses()[0].word, "arbol");
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let lines = buffer_lines(terminal.backend().buffer());
+        assert!(lines.iter().any(|l| l.contains(" Á  R  B  O  L ")));
+        assert!(lines.iter().any(|l| l.contains("tacón (")));
+        // CAÑON would fit too, but words with Ñ aren't in the tables
+        assert!(
+            !lines
+                .iter()
+                .any(|l| l.contains("cañon") || l.contains("canon"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod ui_state_tests {
+    use super::*;
+    use crate::{
+        settings::Difficulty,
+        solver::Feedback,
+        storage::Database,
+        ui::{
+            handlers::{FocusHandler, SuggestionHandler, UiStateHandler},
+            history::{GameGuess, GameOutcome, GameRecord},
+            types::{FocusTarget, InputMode, Overlay, UI_STATE_FILE, UiStateSnapshot},
+        },
+    };
+    use chrono::Utc;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::{
+        path::Path,
+        time::{Duration, Instant},
+    };
+
+    fn app_at(dir: &Path) -> App {
+        AppBuilder::for_tests()
+            .db(Database::open(dir.join("history.db")).unwrap())
+            .build()
+            .unwrap()
+    }
+
+    /// `state` through its JSON form, restored into `app`.
+    fn round_trip(app: &mut App, state: &UiStateSnapshot) -> UiStateSnapshot {
+        let json = serde_json::to_string(state).unwrap();
+        let state: UiStateSnapshot = serde_json::from_str(&json).unwrap();
+        UiStateHandler::new(app).restore(&state);
+        UiStateHandler::new(app).snapshot()
+    }
+
+    #[test]
+    fn test_game_state_restored() {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).toggle_game_mode();
+        app.show_suggestions = true;
+        app.input = "sla".to_string();
+        app.input_mode = InputMode::Normal;
+        FocusHandler::new(&mut app).focus(FocusTarget::Guesses);
+        let state = UiStateHandler::new(&mut app).snapshot();
+
+        let mut fresh = create_test_app();
+        assert_eq!(round_trip(&mut fresh, &state), state);
+        assert_eq!(fresh.mode, GameMode::Game);
+        assert!(fresh.show_suggestions);
+        assert!(!fresh.show_analysis);
+    }
+
+    #[test]
+    fn test_selection_restored_in_resumed_session() {
+        // Stands in for the session resumed from the database
+        let guess = |app: &mut App| {
+            SolverHandler::new(app).submit_guess("magic".to_string(), vec![Feedback::Gray; 5]);
+        };
+        let mut app = create_test_app();
+        guess(&mut app);
+        FocusHandler::new(&mut app).focus(FocusTarget::Suggestions);
+        SuggestionHandler::new(&mut app).move_selection(2);
+        let state = UiStateHandler::new(&mut app).snapshot();
+        assert_eq!(state.suggestion_selected, Some(2));
+
+        let mut fresh = create_test_app();
+        guess(&mut fresh);
+        assert_eq!(round_trip(&mut fresh, &state), state);
+
+        // A game in the snapshot doesn't abandon the resumed session
+        let game = UiStateSnapshot {
+            mode: GameMode::Game,
+            ..state
+        };
+        UiStateHandler::new(&mut fresh).restore(&game);
+        assert_eq!(fresh.mode, GameMode::Solver);
+        assert_eq!(fresh.solver.guesses().len(), 1);
+    }
+
+    #[test]
+    fn test_history_view_restored() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app_at(dir.path());
+        for i in 0..25 {
+            app.db
+                .save_game(&GameRecord {
+                    timestamp: Utc::now() - chrono::Duration::minutes(i),
+                    target_word: "stone".to_string(),
+                    guesses: vec![GameGuess::new("crane".to_string(), vec![])],
+                    outcome: GameOutcome::Won { guesses: 1 },
+                    blitz: false,
+                    duration_secs: None,
+                    app_version: None,
+                    difficulty: Difficulty::Normal,
+                    hints_used: 0,
+                    hint_budget: None,
+                    hints: Vec::new(),
+                    archived_at: None,
+                    word_difficulty: None,
+                })
+                .unwrap();
+        }
+        HistoryHandler::new(&mut app).enter_history_mode();
+        HistoryHandler::new(&mut app).next_page();
+        HistoryHandler::new(&mut app).select_game_on_page(3);
+        FocusHandler::new(&mut app).focus(FocusTarget::Logs);
+        let state = UiStateHandler::new(&mut app).snapshot();
+        assert_eq!(state.history_view_mode, HistoryViewMode::Detail);
+        assert_eq!(state.history_page, 1);
+        assert!(state.history_selected.is_some());
+
+        let mut fresh = app_at(dir.path());
+        assert_eq!(round_trip(&mut fresh, &state), state);
+        assert_eq!(fresh.mode, GameMode::History);
+    }
+
+    #[test]
+    fn test_journal_written_after_delay_and_offered() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app_at(dir.path());
+        app.input = "crane".to_string();
+        let start = Instant::now();
+        UiStateHandler::new(&mut app).note_changes(start);
+        assert!(!dir.path().join(UI_STATE_FILE).exists());
+        UiStateHandler::new(&mut app).note_changes(start + Duration::from_secs(1));
+        assert!(dir.path().join(UI_STATE_FILE).exists());
+
+        // Too old to offer
+        let mut stale = app_at(dir.path());
+        UiStateHandler::new(&mut stale).load(Utc::now() + chrono::Duration::minutes(10));
+        UiStateHandler::new(&mut stale).offer_restore();
+        assert!(stale.overlay.is_none());
+
+        let mut fresh = app_at(dir.path());
+        UiStateHandler::new(&mut fresh).load(Utc::now());
+        UiStateHandler::new(&mut fresh).offer_restore();
+        assert!(matches!(fresh.overlay, Some(Overlay::RestoreUiState(_))));
+        // Nothing is written over the offered state while it is undecided
+        UiStateHandler::new(&mut fresh).note_changes(start + Duration::from_secs(5));
+        assert_eq!(fresh.ui_journal.written, None);
+
+        InputHandler::new(&mut fresh)
+            .handle_key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        assert!(fresh.overlay.is_none());
+        assert_eq!(fresh.input, "crane");
+    }
+
+    #[test]
+    fn test_unchanged_state_not_offered() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app_at(dir.path());
+        UiStateHandler::new(&mut app).flush();
+        assert!(dir.path().join(UI_STATE_FILE).exists());
+
+        let mut fresh = app_at(dir.path());
+        UiStateHandler::new(&mut fresh).load(Utc::now());
+        UiStateHandler::new(&mut fresh).offer_restore();
+        assert!(fresh.overlay.is_none());
+    }
+}
+
+#[cfg(test)]
+mod pattern_fit_tests {
+    use super::*;
+    use crate::{
+        solver::{generate_feedback, matches},
+        ui::{test_support::FIXTURE_WORDS, types::ParsedInput},
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn status(app: &mut App, input: &str) -> InputStatus {
+        app.input = input.to_string();
+        InputHandler::new(app).input_status()
+    }
+
+    #[test]
+    fn test_unmatched_pattern_blocked_until_forced() {
+        let mut app = create_test_app();
+        // No fixture word starts CRAN without ending in E
+        match status(&mut app, "crane GGGGX") {
+            InputStatus::Invalid(msg) => assert_eq!(msg, "no word matches this pattern — typo?"),
+            _ => panic!("expected the pattern to be refused"),
+        }
+
+        InputHandler::new(&mut app).handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(app.solver.guesses().is_empty());
+
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL));
+        assert_eq!(app.solver.guesses().len(), 1);
+    }
+
+    #[test]
+    fn test_alt_enter_forces_like_ctrl_enter() {
+        let mut app = create_test_app();
+        app.input = "crane GGGGX".to_string();
+
+        // What a terminal without keyboard enhancement sends for Alt+Enter
+        InputHandler::new(&mut app).handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT));
+        assert_eq!(app.solver.guesses().len(), 1);
+    }
+
+    #[test]
+    fn test_impossible_pattern_refused_even_forced() {
+        let mut app = create_test_app();
+        // CRAN fixed leaves E nowhere to go, whatever the word list
+        match status(&mut app, "crane GGGGY") {
+            InputStatus::Invalid(msg) => {
+                assert_eq!(msg, "impossible pattern: no place is left for the yellow E")
+            }
+            _ => panic!("expected the pattern to be refused"),
+        }
+        // Refused before any word is matched against it
+        assert!(app.pattern_fit.borrow().is_none());
+
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::CONTROL));
+        assert!(app.solver.guesses().is_empty());
+        assert!(matches!(
+            InputHandler::new(&mut app).parse_input(),
+            ParsedInput::Invalid
+        ));
+    }
+
+    #[test]
+    fn test_rare_pattern_allowed() {
+        let mut app = create_test_app();
+        let pattern = generate_feedback("magic", "raise");
+        let fits = FIXTURE_WORDS
+            .iter()
+            .filter(|w| matches(w, "raise", &pattern))
+            .count();
+        assert_eq!(fits, 1);
+
+        let input = format!("raise {}", crate::scoring::pattern_string(&pattern));
+        assert!(matches!(status(&mut app, &input), InputStatus::Valid));
+    }
+
+    #[test]
+    fn test_fit_remembered_for_the_input() {
+        let mut app = create_test_app();
+        status(&mut app, "crane GGGGX");
+        let remembered = app.pattern_fit.borrow().clone();
+        assert_eq!(
+            remembered,
+            Some((
+                "crane".to_string(),
+                crate::solver::parse_pattern("GGGGX").unwrap(),
+                false
+            ))
+        );
+    }
+}
+
+#[cfg(test)]
+mod timing_stats_tests {
+    use super::*;
+    use crate::{
+        settings::Difficulty,
+        ui::{
+            history::{GameOutcome, GameRecord},
+            test_support::buffer_lines,
+        },
+    };
+    use chrono::{Local, TimeZone, Utc};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    /// A game at `hour` local time on Monday, June 3 2024 plus `day` days.
+    fn game(day: u32, hour: u32, outcome: GameOutcome) -> GameRecord {
+        GameRecord {
+            timestamp: Local
+                .with_ymd_and_hms(2024, 6, 3 + day, hour, 0, 0)
+                .unwrap()
+                .with_timezone(&Utc),
+            target_word: "stone".to_string(),
+            guesses: vec![],
+            outcome,
+            blitz: false,
+            duration_secs: None,
+            app_version: None,
+            difficulty: Difficulty::Normal,
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        }
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_page_down_shows_timing_charts() {
+        let games = vec![
+            game(0, 8, GameOutcome::Won { guesses: 3 }),
+            game(0, 9, GameOutcome::Won { guesses: 4 }),
+            game(0, 10, GameOutcome::Lost),
+            game(1, 14, GameOutcome::Won { guesses: 5 }),
+            game(2, 23, GameOutcome::Lost),
+        ];
+        let mut app = create_test_app();
+        app.mode = GameMode::History;
+        app.history_view_mode = HistoryViewMode::Stats;
+        app.history_data = Some(HistoryData::new(games, Vec::new()));
+
+        press(&mut app, KeyCode::PageDown);
+        let mut terminal = Terminal::new(TestBackend::new(140, 30)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let lines = buffer_lines(terminal.backend().buffer());
+        let line = |label: &str| {
+            lines
+                .iter()
+                .find(|l| l.contains(&format!("  {:<10}", label)))
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        assert!(lines.iter().any(|l| l.contains("By Time of Day")));
+        assert!(lines.iter().any(|l| l.contains("By Day of Week")));
+        assert!(line("Morning").contains(" 67% won  3.50 avg  (3 games)"));
+        assert!(line("Afternoon").contains("n/a (1 of 3 games)"));
+        assert!(line("Night").contains("n/a (1 of 3 games)"));
+        assert!(line("Mon").contains(" 67% won  3.50 avg  (3 games)"));
+        assert!(line("Wed").contains("n/a (1 of 3 games)"));
+
+        press(&mut app, KeyCode::PageUp);
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let lines = buffer_lines(terminal.backend().buffer());
+        assert!(lines.iter().any(|l| l.contains("Guess Distribution")));
+    }
+}
+
+#[cfg(test)]
+mod key_repeat_tests {
+    use super::*;
+    use crate::ui::types::SUBMIT_REPEAT_WINDOW;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::time::Duration;
+
+    fn enter(app: &mut App) {
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    }
+
+    fn game_with_target(target: &str) -> App {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some(target.to_string());
+        app
+    }
+
+    #[test]
+    fn test_held_enter_submits_one_guess() {
+        let mut app = game_with_target("stone");
+        app.input = "crane".to_string();
+
+        enter(&mut app);
+        enter(&mut app);
+
+        assert_eq!(app.solver.guesses().len(), 1);
+        assert_eq!(app.remaining_guesses, 5);
+        assert_eq!(app.game_luck.len(), 1);
+        assert!(app.input.is_empty());
+        let logs = app.logs.lines();
+        let submitted = logs
+            .iter()
+            .filter(|l| l.contains("Game guess submitted"))
+            .count();
+        assert_eq!(submitted, 1);
+        assert!(!logs.iter().any(|l| l.contains("Input rejected")));
+    }
+
+    #[test]
+    fn test_held_enter_does_not_skip_the_result() {
+        let mut app = game_with_target("stone");
+        app.input = "stone".to_string();
+
+        enter(&mut app);
+        enter(&mut app);
+        assert!(app.game_over);
+        assert_eq!(app.target_word.as_deref(), Some("stone"));
+
+        // A deliberate press after the window starts the next game
+        app.last_submit = app
+            .last_submit
+            .map(|at| at - SUBMIT_REPEAT_WINDOW - Duration::from_millis(1));
+        enter(&mut app);
+        assert!(!app.game_over);
+    }
+}
+
+#[cfg(test)]
+mod guesses_to_go_tests {
+    use super::*;
+    use crate::{solver::parse_pattern, ui::test_support::buffer_lines};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn status(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(140, 3)).unwrap();
+        terminal
+            .draw(|f| app.draw_game_status(f, f.area()))
+            .unwrap();
+        buffer_lines(terminal.backend().buffer()).join("\n")
+    }
+
+    fn pool_panel(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(60, 12)).unwrap();
+        terminal
+            .draw(|f| app.draw_solution_pool(f, f.area()))
+            .unwrap();
+        buffer_lines(terminal.backend().buffer()).join("\n")
+    }
+
+    #[test]
+    fn test_game_status_counts_down_with_the_pool() {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("apple".to_string());
+        app.show_analysis = true;
+
+        // HOUSE leaves crane and apple
+        GameHandler::new(&mut app).submit_guess("house".to_string());
+        SolverHandler::new(&mut app).recompute_analysis();
+        assert!(
+            status(&app).contains("≈1.5 guesses to go"),
+            "{}",
+            status(&app)
+        );
+
+        // CRANE leaves only apple
+        GameHandler::new(&mut app).submit_guess("crane".to_string());
+        SolverHandler::new(&mut app).recompute_analysis();
+        assert!(status(&app).contains("| 1 guess to go"), "{}", status(&app));
+    }
+
+    #[test]
+    fn test_contradiction_shows_no_estimate() {
+        let mut app = create_test_app();
+        SolverHandler::new(&mut app)
+            .submit_guess("crane".to_string(), parse_pattern("GGGGY").unwrap());
+        SolverHandler::new(&mut app).recompute_analysis();
+        assert_eq!(app.solution_pool_stats.as_ref().unwrap().total_remaining, 0);
+        assert!(
+            pool_panel(&app).contains("bits · —"),
+            "{}",
+            pool_panel(&app)
+        );
+    }
+}
+
+#[cfg(test)]
+mod word_tier_tests {
+    use super::*;
+    use crate::{
+        tiers::{WordTier, list_hash},
+        ui::handlers::TierHandler,
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn scored_app() -> App {
+        let mut app = create_test_app();
+        TierHandler::new(&mut app).load_scores();
+        while app.tier_job.is_some() {
+            TierHandler::new(&mut app).step_scoring();
+        }
+        app
+    }
+
+    #[test]
+    fn test_new_list_scored_once_and_cached() {
+        let mut app = scored_app();
+        let scores = app.word_scores.as_ref().unwrap();
+        assert_eq!(scores.len(), app.solution_words.len());
+        let hash = list_hash(&app.solution_words);
+        assert_eq!(
+            app.db.load_word_scores(&hash).unwrap().len(),
+            app.solution_words.len()
+        );
+
+        // Seen before, so the cache is used without scoring again
+        app.word_scores = None;
+        TierHandler::new(&mut app).load_scores();
+        assert!(app.tier_job.is_none());
+        assert!(app.word_scores.is_some());
+    }
+
+    #[test]
+    fn test_games_drawn_from_the_chosen_tier() {
+        let mut app = scored_app();
+        app.settings.word_tier = Some(WordTier::Hard);
+        let hard: Vec<String> = app
+            .word_scores
+            .as_ref()
+            .unwrap()
+            .words_in(WordTier::Hard)
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        assert!(!hard.is_empty() && hard.len() < app.solution_words.len());
+
+        for _ in 0..30 {
+            GameHandler::new(&mut app).start_new_game();
+            let target = app.target_word.clone().unwrap();
+            assert!(hard.contains(&target), "{} isn't hard", target);
+            assert_eq!(app.game_word_difficulty.unwrap().tier, WordTier::Hard);
+        }
+
+        let target = app.target_word.clone().unwrap();
+        GameHandler::new(&mut app).submit_guess(target);
+        let saved = app.db.load_games().unwrap();
+        assert_eq!(saved[0].word_difficulty, app.game_word_difficulty);
+    }
+
+    #[test]
+    fn test_unscored_tier_draws_from_every_answer() {
+        let mut app = create_test_app();
+        app.settings.word_tier = Some(WordTier::Easy);
+        GameHandler::new(&mut app).start_new_game();
+        assert!(app.target_word.is_some());
+        assert_eq!(app.game_word_difficulty, None);
+        assert!(
+            app.logs
+                .lines()
+                .iter()
+                .any(|l| l.contains("drawing from every answer"))
+        );
+    }
+
+    #[test]
+    fn test_ctrl_w_cycles_the_tier() {
+        let mut app = create_test_app();
+        let ctrl_w = KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL);
+        InputHandler::new(&mut app).handle_key(ctrl_w);
+        assert_eq!(app.settings.word_tier, Some(WordTier::Easy));
+        for _ in 0..3 {
+            InputHandler::new(&mut app).handle_key(ctrl_w);
+        }
+        assert_eq!(app.settings.word_tier, None);
+        assert!(app.input.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod lazy_session_tests {
+    use super::*;
+    use crate::solver::parse_pattern;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_toggling_modes_writes_no_sessions() {
+        let mut app = create_test_app();
+        for _ in 0..6 {
+            GameHandler::new(&mut app).toggle_game_mode();
+        }
+        assert_eq!(app.mode, GameMode::Solver);
+
+        assert!(app.db.load_solver_sessions().unwrap().is_empty());
+        // Not even an open row waiting to be closed
+        assert_eq!(
+            app.db
+                .close_stale(Utc::now() + Duration::seconds(1))
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_first_guess_keeps_the_session_start() {
+        let mut app = create_test_app();
+        for _ in 0..4 {
+            GameHandler::new(&mut app).toggle_game_mode();
+        }
+        let start = app.solver_session_start.unwrap();
+
+        SolverHandler::new(&mut app)
+            .submit_guess("crane".to_string(), parse_pattern("XXXXX").unwrap());
+        GameHandler::new(&mut app).quit();
+
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].timestamp, start);
+        assert_eq!(sessions[0].guesses.len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod status_hint_tests {
+    use super::*;
+    use crate::{
+        settings::Difficulty, solver::parse_pattern, ui::hints::status_hints, ui::types::Overlay,
+    };
+    use ratatui::{Terminal, backend::TestBackend};
+    use std::time::{Duration, Instant};
+
+    fn hints(app: &App) -> Vec<String> {
+        status_hints(app)
+            .iter()
+            .map(|h| format!("{} {}", h.key, h.action))
+            .collect()
+    }
+
+    fn status_bar(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(160, 40)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let lines = crate::ui::test_support::buffer_lines(terminal.backend().buffer());
+        lines.last().unwrap().clone()
+    }
+
+    fn game_at(difficulty: Difficulty) -> App {
+        let mut app = create_test_app();
+        app.settings.difficulty = difficulty;
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("slate".to_string());
+        app
+    }
+
+    #[test]
+    fn test_solver_hints() {
+        let app = create_test_app();
+        let hints = hints(&app);
+        assert_eq!(hints[0], "Enter submit word + pattern");
+        assert!(hints.contains(&"Ctrl+G game".to_string()));
+        assert!(hints.contains(&"Ctrl+R history".to_string()));
+        assert_eq!(hints.last().unwrap(), "Ctrl+Q quit");
+        assert!(status_bar(&app).contains("Enter submit word + pattern · Ctrl+Z undo"));
+    }
+
+    #[test]
+    fn test_game_hints_follow_difficulty() {
+        let normal = hints(&game_at(Difficulty::Normal));
+        assert_eq!(normal[0], "Enter submit");
+        assert!(normal.contains(&"Ctrl+B best guess".to_string()));
+        assert!(normal.contains(&"Ctrl+S solver".to_string()));
+        assert!(!normal.contains(&"Ctrl+Z undo".to_string()));
+
+        assert!(hints(&game_at(Difficulty::Easy)).contains(&"Ctrl+Z undo".to_string()));
+
+        let expert = hints(&game_at(Difficulty::Expert));
+        assert!(!expert.iter().any(|h| h.starts_with("Ctrl+H")));
+        assert!(!expert.iter().any(|h| h.starts_with("Ctrl+B")));
+    }
+
+    #[test]
+    fn test_game_over_hints() {
+        let mut app = game_at(Difficulty::Normal);
+        GameHandler::new(&mut app).submit_guess("slate".to_string());
+        assert!(app.game_over);
+
+        let hints = hints(&app);
+        assert_eq!(hints[0], "Enter new game");
+        assert!(hints.contains(&"Ctrl+S solver".to_string()));
+        assert!(!hints.contains(&"Enter submit".to_string()));
+        assert!(status_bar(&app).contains("Enter new game · Ctrl+T blitz"));
+    }
+
+    #[test]
+    fn test_pool_conflict_leads_with_undo() {
+        let mut app = create_test_app();
+        let mut solver = SolverHandler::new(&mut app);
+        // Every fixture answer has one of these letters
+        solver.submit_guess("crane".to_string(), parse_pattern("XXXXX").unwrap());
+        solver.recompute_analysis();
+        assert_eq!(app.solution_pool_stats.as_ref().unwrap().total_remaining, 0);
+
+        let hints = hints(&app);
+        assert_eq!(
+            hints[..2],
+            ["Ctrl+Z undo last guess", "Ctrl+E fix a pattern"]
+        );
+        assert!(!hints.contains(&"Enter submit word + pattern".to_string()));
+    }
+
+    #[test]
+    fn test_panel_and_overlay_hints() {
+        let mut app = create_test_app();
+        app.settings.modal_input = true;
+        app.input_mode = crate::ui::types::InputMode::Normal;
+        assert_eq!(hints(&app)[0], "i/a insert");
+
+        app.overlay = Some(Overlay::Blacklist { selected: 0 });
+        assert_eq!(hints(&app), ["Esc close", "Ctrl+Q quit"]);
+    }
+
+    #[test]
+    fn test_history_hints_follow_view() {
+        let mut app = create_test_app();
+        HistoryHandler::new(&mut app).enter_history_mode();
+        let stats = hints(&app);
+        assert_eq!(stats[0], "PgDn by time");
+        assert!(stats.contains(&"Ctrl+R exit".to_string()));
+        assert!(!stats.contains(&"Esc back".to_string()));
+
+        app.history_view_mode = HistoryViewMode::List;
+        let list = hints(&app);
+        assert_eq!(list[0], "1-9 view game");
+        assert!(list.contains(&"Esc back".to_string()));
+    }
+
+    #[test]
+    fn test_error_stands_in_for_hints_until_it_expires() {
+        let app = create_test_app();
+        app.log_error("Warning: failed to save game: disk full");
+
+        let bar = status_bar(&app);
+        assert!(
+            bar.contains("Warning: failed to save game: disk full"),
+            "{}",
+            bar
+        );
+        assert!(!bar.contains("Ctrl+Q quit"), "{}", bar);
+        assert!(app.logs.lines().last().unwrap().contains("disk full"));
+
+        app.status_error.borrow_mut().as_mut().unwrap().until =
+            Instant::now() - Duration::from_secs(1);
+        assert!(status_bar(&app).contains("Ctrl+Q quit"));
+    }
+}
+
+#[cfg(test)]
+mod solver_path_tests {
+    use super::*;
+    use crate::{solver::parse_pattern, ui::history::solver_types::SolverGuess};
+    use ratatui::{Terminal, backend::TestBackend, style::Color};
+
+    fn guess(word: &str, pattern: Option<&str>) -> SolverGuess {
+        SolverGuess {
+            word: word.to_string(),
+            pool_size_before: 8,
+            pool_size_after: 2,
+            entropy: 1.0,
+            optimal_word: None,
+            optimal_entropy: None,
+            deviation_score: None,
+            metric: None,
+            feedback: pattern.map(|p| parse_pattern(p).unwrap()),
+            note: None,
+        }
+    }
+
+    #[test]
+    fn test_patterns_drawn_as_tiles_where_kept() {
+        let mut app = create_test_app();
+        let now = chrono::Utc::now();
+        app.db
+            .save_solver_session(now - chrono::Duration::hours(1), &[guess("world", None)])
+            .unwrap();
+        app.db
+            .save_solver_session(now, &[guess("crane", Some("GYXXX"))])
+            .unwrap();
+        HistoryHandler::new(&mut app).enter_history_mode();
+        app.history_view_mode = HistoryViewMode::Solver;
+
+        let mut terminal = Terminal::new(TestBackend::new(160, 40)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let lines = crate::ui::test_support::buffer_lines(buffer);
+
+        // The latest session comes first, its guess as tiles
+        let (y, line) = lines
+            .iter()
+            .enumerate()
+            .find(|(_, l)| l.contains("CRANE"))
+            .unwrap();
+        let x = line[..line.find("CRANE").unwrap()].chars().count();
+        let bg = |dx: usize| buffer[(x as u16 + dx as u16, y as u16)].bg;
+        assert_eq!(bg(0), Color::Green);
+        assert_eq!(bg(1), Color::Yellow);
+        assert_eq!(bg(2), Color::DarkGray);
+
+        // The older session had no pattern, so its guess is plain text
+        let (y, line) = lines
+            .iter()
+            .enumerate()
+            .find(|(_, l)| l.contains("world"))
+            .unwrap();
+        let x = line[..line.find("world").unwrap()].chars().count();
+        assert_eq!(buffer[(x as u16, y as u16)].bg, Color::Reset);
+    }
+}
+
+#[cfg(test)]
+mod shadow_game_tests {
+    use super::*;
+    use crate::{
+        solver::parse_pattern,
+        ui::history::{GameOutcome, UNKNOWN_TARGET},
+        ui::types::InputStatus,
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn enter(app: &mut App, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+        press(app, KeyCode::Enter);
+    }
+
+    fn start_shadow(app: &mut App) {
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+    }
+
+    #[test]
+    fn test_win_records_the_target_afterwards() {
+        let mut app = create_test_app();
+        start_shadow(&mut app);
+        assert_eq!(app.mode, GameMode::Game);
+        assert!(app.shadow);
+        assert!(app.target_word.is_none());
+
+        enter(&mut app, "crane XYXXX");
+        assert!(!app.game_over);
+        assert_eq!(app.remaining_guesses, 5);
+        assert!(app.target_word.is_none());
+
+        enter(&mut app, "world GGGGG");
+        assert!(app.game_over);
+        assert!(app.game_won);
+        assert_eq!(app.target_word.as_deref(), Some("world"));
+
+        let games = app.db.load_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].target_word, "world");
+        assert_eq!(games[0].outcome, GameOutcome::Won { guesses: 2 });
+        assert_eq!(games[0].guesses[0].word, "crane");
+        assert_eq!(
+            games[0].guesses[0].feedback,
+            parse_pattern("XYXXX").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_loss_records_an_unknown_target() {
+        let mut app = create_test_app();
+        start_shadow(&mut app);
+        for word in ["raise", "stone", "slate", "crane", "house", "apple"] {
+            GameHandler::new(&mut app)
+                .submit_shadow_guess(word.to_string(), parse_pattern("XXXXX").unwrap());
+        }
+        assert!(app.game_over);
+        assert!(!app.game_won);
+
+        let games = app.db.load_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].target_word, UNKNOWN_TARGET);
+        assert_eq!(games[0].outcome, GameOutcome::Lost);
+    }
+
+    #[test]
+    fn test_guesses_need_a_pattern() {
+        let mut app = create_test_app();
+        start_shadow(&mut app);
+
+        app.input = "crane".to_string();
+        assert!(matches!(
+            app.input_status_immutable(),
+            InputStatus::Incomplete
+        ));
+        app.input = "crane XYXXX".to_string();
+        assert!(matches!(app.input_status_immutable(), InputStatus::Valid));
+
+        // A regular game takes the word alone
+        GameHandler::new(&mut app).start_new_game();
+        assert!(!app.shadow);
+        app.input = "crane".to_string();
+        assert!(matches!(app.input_status_immutable(), InputStatus::Valid));
+    }
+
+    #[test]
+    fn test_new_game_after_a_shadow_game_is_shadowed() {
+        let mut app = create_test_app();
+        start_shadow(&mut app);
+        enter(&mut app, "world GGGGG");
+        assert!(app.game_over);
+
+        // Past the window that swallows a held Enter
+        app.last_submit = None;
+        press(&mut app, KeyCode::Enter);
+        assert!(app.shadow);
+        assert!(!app.game_over);
+        assert!(app.target_word.is_none());
+        assert!(app.solver.guesses().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod memory_bound_tests {
+    use super::*;
+    use crate::ui::{
+        memory::footprints,
+        types::{INPUT_HISTORY_LEN, MAX_LOG_LINES, SCORED_POOLS_KEPT, ScoredPools},
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn enter(app: &mut App, text: &str) {
+        app.input = text.to_string();
+        press(app, KeyCode::Enter);
+    }
+
+    fn undo(app: &mut App) {
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL));
+    }
+
+    #[test]
+    fn test_guess_undo_cycles_stay_bounded() {
+        let mut app = create_test_app();
+        for i in 0..500 {
+            enter(&mut app, "magic XXXXX");
+            SolverHandler::new(&mut app).recompute_analysis();
+            assert_eq!(app.solver_session_guesses.len(), 1);
+            assert_eq!(app.entropy_history.len(), 1);
+            undo(&mut app);
+            // A typo kept for recall, different every time
+            enter(&mut app, &format!("typo{}", i));
+        }
+
+        assert!(app.solver.guesses().is_empty());
+        assert!(app.entropy_history.is_empty());
+        assert!(app.pool_history.is_empty());
+        assert!(app.solver_session_guesses.is_empty());
+        assert_eq!(app.logs.lines().len(), MAX_LOG_LINES);
+        assert_eq!(
+            app.input_history.entries(&GameMode::Solver).len(),
+            INPUT_HISTORY_LEN
+        );
+        assert!(app.scored_pools.len() <= SCORED_POOLS_KEPT);
+        // Nothing is left for the session's row either
+        assert!(app.db.load_solver_sessions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_solved_session_starts_empty() {
+        let mut app = create_test_app();
+        // Leaves only WORLD, confirmed to complete the session
+        enter(&mut app, "crane XYXXX");
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL));
+
+        assert!(app.solver.guesses().is_empty());
+        assert!(app.entropy_history.is_empty());
+        assert!(app.pool_history.is_empty());
+        assert!(app.solver_session_guesses.is_empty());
+        assert_eq!(app.db.load_solver_sessions().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_log_ring_keeps_latest_lines() {
+        let logs = LogBuffer::new();
+        for i in 0..MAX_LOG_LINES + 200 {
+            logs.push(format!("line{}", i));
+        }
+        let lines = logs.lines();
+        assert_eq!(lines.len(), MAX_LOG_LINES);
+        assert_eq!(lines[0], "line200");
+        assert_eq!(
+            lines.last().cloned(),
+            Some(format!("line{}", MAX_LOG_LINES + 199))
+        );
+    }
+
+    #[test]
+    fn test_scored_pools_drop_least_recently_used() {
+        let mut pools = ScoredPools::default();
+        for i in 0..SCORED_POOLS_KEPT as u32 {
+            pools.insert(&[i], vec![(i, 1)]);
+        }
+        // Using the oldest keeps it past the next insert
+        assert!(pools.get(&[0]).is_some());
+        pools.insert(&[99], vec![(99, 1)]);
+
+        assert_eq!(pools.len(), SCORED_POOLS_KEPT);
+        assert!(pools.get(&[0]).is_some());
+        assert!(pools.get(&[1]).is_none());
+        assert_eq!(pools.get(&[99]), Some(&[(99, 1)][..]));
+    }
+
+    #[test]
+    fn test_f12_logs_memory_use() {
+        let mut app = create_test_app();
+        enter(&mut app, "magic XXXXX");
+        press(&mut app, KeyCode::F(12));
+
+        let report = app.logs.lines().last().cloned().unwrap();
+        assert!(report.starts_with("Memory ≈ "), "{}", report);
+        assert!(report.contains("session guesses 1 ("), "{}", report);
+        let session = footprints(&app)
+            .into_iter()
+            .find(|f| f.name == "session guesses")
+            .unwrap();
+        assert!(session.bytes > 0);
+    }
+}
+
+#[cfg(test)]
+mod quick_stats_tests {
+    use super::*;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn screen(app: &App) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(160, 40)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        crate::ui::test_support::buffer_lines(terminal.backend().buffer())
+    }
+
+    fn win_game(app: &mut App) {
+        GameHandler::new(app).start_new_game();
+        app.target_word = Some("stone".to_string());
+        GameHandler::new(app).submit_guess("stone".to_string());
+        assert!(app.game_won);
+    }
+
+    #[test]
+    fn test_refreshed_when_a_game_is_won() {
+        let mut app = create_test_app();
+        app.settings.quick_stats = true;
+        assert!(screen(&app)[0].contains("W/L 0-0 · — · streak 0 · avg —"));
+
+        win_game(&mut app);
+        let stats = app.quick_stats.unwrap();
+        assert_eq!((stats.wins, stats.current_streak), (1, 1));
+        assert!(screen(&app)[0].contains("W/L 1-0 · 100% · streak 1 · avg 1.0"));
+
+        // Hidden unless asked for
+        app.settings.quick_stats = false;
+        assert!(!screen(&app).iter().any(|line| line.contains("W/L")));
+    }
+
+    #[test]
+    fn test_unreadable_history_shows_dashes() {
+        let mut app = create_test_app();
+        app.settings.quick_stats = true;
+        app.db.close();
+
+        win_game(&mut app);
+        assert!(app.quick_stats.is_none());
+        assert!(screen(&app)[0].contains("W/L — · — · streak — · avg —"));
+    }
+}
+
+#[cfg(test)]
+mod state_publisher_tests {
+    use super::*;
+    use crate::solver::parse_pattern;
+    use crate::ui::publisher::{
+        BoardRow, STATE_FILE, STATE_PUBLISH_DELAY, SpectatorEntry, SpectatorSnapshot,
+        StatePublisher, write_atomically,
+    };
+    use std::{
+        path::Path,
+        sync::{
+            Arc,
+            atomic::{AtomicBool, Ordering},
+        },
+        time::Instant,
+    };
+
+    fn publishing_app(dir: &Path) -> App {
+        let mut app = create_test_app();
+        app.state_publisher = StatePublisher::new(Some(dir));
+        app
+    }
+
+    fn entry(dir: &Path) -> SpectatorEntry {
+        let text = std::fs::read_to_string(dir.join(STATE_FILE)).unwrap();
+        serde_json::from_str(&text).unwrap()
+    }
+
+    /// Publish whatever changed, past the delay, and read it back.
+    fn publish_to(app: &mut App, dir: &Path) -> SpectatorEntry {
+        let now = Instant::now();
+        StatePublisher::note_changes(app, now);
+        StatePublisher::note_changes(app, now + STATE_PUBLISH_DELAY);
+        entry(dir)
+    }
+
+    #[test]
+    fn test_game_in_progress_shows_colors_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = publishing_app(dir.path());
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("apple".to_string());
+        GameHandler::new(&mut app).submit_guess("house".to_string());
+
+        let snapshot = publish_to(&mut app, dir.path()).snapshot;
+        let text = std::fs::read_to_string(dir.path().join(STATE_FILE)).unwrap();
+        assert_eq!(snapshot.mode, "game");
+        assert!(!snapshot.game_over);
+        assert_eq!(
+            snapshot.board,
+            [BoardRow {
+                word: None,
+                pattern: "XXXXG".to_string()
+            }]
+        );
+        assert_eq!(snapshot.answer, None);
+        assert_eq!(snapshot.pool_size, Some(2));
+        assert_eq!(snapshot.entropy_bits, Some(1.0));
+        for spoiler in ["apple", "house", "crane"] {
+            assert!(!text.contains(spoiler), "{} in {}", spoiler, text);
+        }
+
+        GameHandler::new(&mut app).submit_guess("apple".to_string());
+        let snapshot = publish_to(&mut app, dir.path()).snapshot;
+        assert!(snapshot.game_over);
+        assert_eq!(snapshot.answer.as_deref(), Some("apple"));
+        assert_eq!(snapshot.board[0].word.as_deref(), Some("house"));
+    }
+
+    #[test]
+    fn test_solver_board_shows_words() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = publishing_app(dir.path());
+        SolverHandler::new(&mut app)
+            .submit_guess("magic".to_string(), parse_pattern("XXXXX").unwrap());
+
+        let snapshot = publish_to(&mut app, dir.path()).snapshot;
+        assert!(snapshot.active);
+        assert_eq!(snapshot.mode, "solver");
+        assert_eq!(snapshot.board[0].word.as_deref(), Some("magic"));
+        // STONE, HOUSE and WORLD
+        assert_eq!(snapshot.pool_size, Some(3));
+    }
+
+    #[test]
+    fn test_written_after_delay_and_marked_inactive_on_exit() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATE_FILE);
+        let mut app = publishing_app(dir.path());
+
+        let now = Instant::now();
+        StatePublisher::note_changes(&mut app, now);
+        assert!(!path.exists());
+        StatePublisher::note_changes(&mut app, now + STATE_PUBLISH_DELAY);
+        assert!(entry(dir.path()).snapshot.active);
+
+        StatePublisher::finish(&mut app);
+        assert!(!entry(dir.path()).snapshot.active);
+    }
+
+    #[test]
+    fn test_nothing_written_when_disabled() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = publishing_app(dir.path());
+        app.settings.spectator_file = false;
+
+        let now = Instant::now();
+        StatePublisher::note_changes(&mut app, now);
+        StatePublisher::note_changes(&mut app, now + STATE_PUBLISH_DELAY);
+        StatePublisher::finish(&mut app);
+        assert!(!dir.path().join(STATE_FILE).exists());
+    }
+
+    #[test]
+    fn test_readers_never_see_partial_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATE_FILE);
+        let done = Arc::new(AtomicBool::new(false));
+
+        let reader = {
+            let (path, done) = (path.clone(), done.clone());
+            std::thread::spawn(move || {
+                let mut reads = 0;
+                while !done.load(Ordering::Relaxed) {
+                    match std::fs::read_to_string(&path) {
+                        Ok(text) => {
+                            serde_json::from_str::<SpectatorEntry>(&text)
+                                .unwrap_or_else(|e| panic!("partial read: {}: {}", e, text));
+                            reads += 1;
+                        }
+                        Err(e) => assert_eq!(e.kind(), std::io::ErrorKind::NotFound),
+                    }
+                }
+                reads
+            })
+        };
+
+        for i in 0..500 {
+            // Boards of varying length so a torn write would show
+            let board = (0..i % 7)
+                .map(|_| BoardRow {
+                    word: Some("crane".to_string()),
+                    pattern: "GYXGY".to_string(),
+                })
+                .collect();
+            let entry = SpectatorEntry {
+                updated_at: chrono::Utc::now().to_rfc3339(),
+                snapshot: SpectatorSnapshot {
+                    active: true,
+                    mode: "solver".to_string(),
+                    board,
+                    game_over: false,
+                    answer: None,
+                    pool_size: Some(i),
+                    entropy_bits: None,
+                },
+            };
+            write_atomically(&path, &entry).unwrap();
+        }
+        done.store(true, Ordering::Relaxed);
+        assert!(reader.join().unwrap() > 0);
+    }
+}
+
+#[cfg(test)]
+mod compact_history_tests {
+    use super::*;
+    use crate::local_time::TimePrefs;
+    use crate::ui::{
+        history::{AbandonReason, GameGuess, GameOutcome, GameRecord},
+        rendering::history::list_view::compact_line,
+        test_support::buffer_lines,
+    };
+    use chrono::{TimeZone, Utc};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend, text::Line};
+
+    fn game(word: &str, guesses: usize, outcome: GameOutcome) -> GameRecord {
+        GameRecord {
+            timestamp: Utc.with_ymd_and_hms(2026, 2, 10, 12, 0, 0).unwrap(),
+            target_word: word.to_string(),
+            guesses: vec![GameGuess::new("crane".to_string(), vec![]); guesses],
+            outcome,
+            blitz: false,
+            duration_secs: None,
+            app_version: None,
+            difficulty: crate::settings::Difficulty::Normal,
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        }
+    }
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    fn history_app(games: Vec<GameRecord>) -> App {
+        let mut app = create_test_app();
+        app.mode = GameMode::History;
+        app.history_view_mode = HistoryViewMode::List;
+        app.history_data = Some(HistoryData::new(games, Vec::new()));
+        app
+    }
+
+    fn screen(app: &App, width: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, 30)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        buffer_lines(terminal.backend().buffer()).join("\n")
+    }
+
+    #[test]
+    fn test_compact_line_per_outcome() {
+        let won = game("savvy", 4, GameOutcome::Won { guesses: 4 });
+        assert_eq!(
+            text(&compact_line(1, &won, &TimePrefs::default())),
+            "1. ✔4 02-10 SAVVY"
+        );
+        let lost = game("savvy", 6, GameOutcome::Lost);
+        assert_eq!(
+            text(&compact_line(2, &lost, &TimePrefs::default())),
+            "2. ✘6 02-10 SAVVY"
+        );
+        let forfeited = game("savvy", 2, GameOutcome::Forfeited);
+        assert_eq!(
+            text(&compact_line(3, &forfeited, &TimePrefs::default())),
+            "3. ⚑2 02-10 SAVVY"
+        );
+        let abandoned = game("savvy", 1, GameOutcome::Abandoned(AbandonReason::Quit));
+        assert_eq!(
+            text(&compact_line(10, &abandoned, &TimePrefs::default())),
+            "10. ○1 02-10 SAVVY"
+        );
+    }
+
+    #[test]
+    fn test_compact_line_flags() {
+        let mut assisted = game("savvy", 3, GameOutcome::Won { guesses: 3 });
+        assisted.hints_used = 1;
+        assert_eq!(
+            text(&compact_line(1, &assisted, &TimePrefs::default())),
+            "1. ✔3 02-10 SAVVY*"
+        );
+
+        let mut archived = game("savvy", 6, GameOutcome::Lost);
+        archived.archived_at = Some(Utc::now());
+        assert_eq!(
+            text(&compact_line(1, &archived, &TimePrefs::default())),
+            "1. ✘6 02-10 SAVVY archived"
+        );
+
+        // A shadow game that ended without the answer
+        let shadow = game("?", 6, GameOutcome::Lost);
+        assert_eq!(
+            text(&compact_line(1, &shadow, &TimePrefs::default())),
+            "1. ✘6 02-10 ?"
+        );
+    }
+
+    #[test]
+    fn test_narrow_terminal_lists_compactly() {
+        let app = history_app(vec![
+            game("savvy", 4, GameOutcome::Won { guesses: 4 }),
+            game("crane", 6, GameOutcome::Lost),
+        ]);
+
+        let wide = screen(&app, 120);
+        assert!(wide.contains("Guesses"));
+        assert!(!wide.contains("✔4"));
+
+        let narrow = screen(&app, 50);
+        assert!(narrow.contains("1. ✔4 02-10 SAVVY"));
+        assert!(narrow.contains("2. ✘6 02-10 CRANE"));
+        assert!(!narrow.contains("Guesses"));
+    }
+
+    #[test]
+    fn test_toggle_and_digits_in_compact_mode() {
+        let mut app = history_app(vec![
+            game("savvy", 4, GameOutcome::Won { guesses: 4 }),
+            game("crane", 6, GameOutcome::Lost),
+        ]);
+        let press = |app: &mut App, c| {
+            InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+        };
+
+        press(&mut app, 'v');
+        assert!(app.history_compact_list);
+        assert!(screen(&app, 120).contains("2. ✘6 02-10 CRANE"));
+
+        press(&mut app, '2');
+        assert_eq!(app.history_view_mode, HistoryViewMode::Detail);
+        let data = app.history_data.as_ref().unwrap();
+        assert_eq!(data.selected_game().unwrap().target_word, "crane");
+
+        app.history_view_mode = HistoryViewMode::List;
+        press(&mut app, 'v');
+        assert!(screen(&app, 120).contains("Guesses"));
+    }
+
+    #[test]
+    fn test_dates_follow_the_timezone_setting() {
+        // Late evening of the 9th in New York
+        let mut late = game("savvy", 4, GameOutcome::Won { guesses: 4 });
+        late.timestamp = Utc.with_ymd_and_hms(2026, 2, 10, 3, 30, 0).unwrap();
+        let mut app = history_app(vec![late]);
+        app.settings.timezone = Some("UTC".to_string());
+        assert!(screen(&app, 120).contains("2026-02-10 03:30"));
+
+        app.settings.timezone = Some("America/New_York".to_string());
+        app.settings.hour_format = crate::local_time::HourFormat::Twelve;
+        assert!(screen(&app, 120).contains("2026-02-09 10:30 PM"));
+        assert!(screen(&app, 50).contains("1. ✔4 02-09 SAVVY"));
+    }
+}
+
+#[cfg(test)]
+mod solver_completion_tests {
+    use super::*;
+    use crate::ui::{test_support::buffer_lines, types::Overlay};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn enter(app: &mut App, text: &str) {
+        app.input = text.to_string();
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    }
+
+    fn confirm(app: &mut App) {
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL));
+    }
+
+    /// An app whose solver guesses leave only STONE.
+    fn converged_app() -> App {
+        let mut app = create_test_app();
+        enter(&mut app, "apple XXXXG");
+        enter(&mut app, "house XYXYG");
+        app
+    }
+
+    fn verified_word(app: &App) -> Option<(&str, usize)> {
+        match &app.overlay {
+            Some(Overlay::VerifyAnswer(state)) => Some((state.word.as_str(), state.guesses.len())),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_last_candidate_keeps_session_open() {
+        let app = converged_app();
+
+        assert!(app.overlay.is_none());
+        assert_eq!(app.solver.guesses().len(), 2);
+        assert_eq!(app.solver_session_guesses.len(), 2);
+        assert_eq!(
+            app.last_candidate.map(|i| app.solution_words.word(i)),
+            Some("stone")
+        );
+        assert_eq!(
+            app.logs.lines().last().unwrap(),
+            "Only STONE is left: enter it all green or press Ctrl+Y to confirm"
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(140, 45)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let frame = buffer_lines(terminal.backend().buffer()).join("\n");
+        assert!(frame.contains("Only answer left: STONE"));
+        assert!(frame.contains("Ctrl+Y confirm answer"));
+        assert!(frame.contains("← only answer left"));
+    }
+
+    #[test]
+    fn test_confirm_completes_with_last_candidate() {
+        let mut app = converged_app();
+
+        confirm(&mut app);
+
+        assert_eq!(verified_word(&app), Some(("stone", 2)));
+        assert!(app.solver.guesses().is_empty());
+        assert!(app.last_candidate.is_none());
+        assert_eq!(app.db.load_solver_sessions().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_confirm_needs_a_single_candidate() {
+        let mut app = create_test_app();
+        enter(&mut app, "magic XXXXX");
+
+        confirm(&mut app);
+
+        assert!(app.overlay.is_none());
+        assert_eq!(app.solver.guesses().len(), 1);
+        assert_eq!(
+            app.logs.lines().last().unwrap(),
+            "Nothing to confirm: 3 answers left"
+        );
+    }
+
+    #[test]
+    fn test_all_green_completes_after_last_candidate() {
+        let mut app = converged_app();
+
+        enter(&mut app, "stone GGGGG");
+
+        assert_eq!(verified_word(&app), Some(("stone", 3)));
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].guesses.len(), 3);
+    }
+
+    #[test]
+    fn test_auto_complete_ends_session_at_one_candidate() {
+        let mut app = create_test_app();
+        app.settings.solver_auto_complete = true;
+        enter(&mut app, "apple XXXXG");
+        enter(&mut app, "house XYXYG");
+
+        assert_eq!(verified_word(&app), Some(("stone", 2)));
+        assert!(app.solver.guesses().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod derived_state_tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn enter(app: &mut App, text: &str) {
+        app.input = text.to_string();
+        InputHandler::new(app).handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    }
+
+    /// The pool size as the suggestions, pool stats and letter analysis each see it.
+    fn pool_sizes(app: &App) -> (usize, usize, usize) {
+        (
+            app.suggestions.len(),
+            app.solution_pool_stats.as_ref().unwrap().total_remaining,
+            app.letter_analysis.as_ref().unwrap().total_words,
+        )
+    }
+
+    #[test]
+    fn test_undo_refreshes_everything_before_returning() {
+        let mut app = create_test_app();
+        enter(&mut app, "magic XXXXX");
+        enter(&mut app, "crane XYXXX");
+        assert_eq!(pool_sizes(&app), (1, 1, 1));
+
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL));
+
+        // No frame has been drawn since the undo
+        assert!(!app.analysis_dirty);
+        assert_eq!(pool_sizes(&app), (3, 3, 3));
+        assert_eq!(app.pool_history, [3]);
+        assert_eq!(app.entropy_history.len(), 1);
+        assert_eq!(
+            app.entropy_history[0],
+            app.solution_pool_stats.as_ref().unwrap().entropy
+        );
+    }
+
+    #[test]
+    fn test_new_app_starts_with_analysis() {
+        let app = create_test_app();
+
+        assert!(!app.analysis_dirty);
+        assert_eq!(
+            app.letter_analysis.as_ref().unwrap().total_words,
+            app.solution_words.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod deviation_metric_tests {
+    use super::*;
+    use crate::{
+        scoring::{ENTROPY_SCORING_MAX_POOL, expected_entropy},
+        solver::{SolverState, generate_feedback},
+        ui::history::DeviationMetric,
+    };
+
+    fn measure(app: &mut App, word: &str) -> crate::ui::history::solver_types::SolverGuess {
+        let guess = Guess::new(word.to_string(), generate_feedback("stone", word));
+        SolverHandler::new(app).measure_guess(&SolverState::new(5), &guess)
+    }
+
+    #[test]
+    fn test_small_pool_measured_by_entropy() {
+        let mut app = create_test_app();
+        let pool: Vec<u32> = app.solution_words.indices().collect();
+
+        let measured = measure(&mut app, "magic");
+
+        assert_eq!(measured.metric, Some(DeviationMetric::Entropy));
+        let optimal = measured.optimal_word.as_deref().unwrap();
+        let optimal_bits = expected_entropy(optimal, &app.solution_words, &pool);
+        let magic_bits = expected_entropy("magic", &app.solution_words, &pool);
+        assert_eq!(measured.optimal_entropy, Some(optimal_bits));
+        assert!((measured.deviation_score.unwrap() - (magic_bits - optimal_bits)).abs() < 1e-9);
+        assert_eq!(measured.was_optimal(), Some(false));
+
+        let best = measure(&mut app, optimal);
+        assert_eq!(best.was_optimal(), Some(true));
+    }
+
+    #[test]
+    fn test_large_pool_measured_by_score() {
+        // Distinct words spelled from the first letters, more than entropy scoring takes
+        let letters: Vec<char> = ('a'..='z').collect();
+        let solutions: Vec<String> = (0..=ENTROPY_SCORING_MAX_POOL)
+            .map(|n| {
+                let mut n = n;
+                (0..5)
+                    .map(|_| {
+                        let c = letters[n % 26];
+                        n /= 26;
+                        c
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut words = solutions.clone();
+        words.push("stone".to_string());
+        let mut app = AppBuilder::new()
+            .words(words)
+            .solutions(solutions)
+            .build()
+            .unwrap();
+
+        let measured = measure(&mut app, "stone");
+
+        assert_eq!(measured.pool_size_before, ENTROPY_SCORING_MAX_POOL + 1);
+        assert_eq!(measured.metric, Some(DeviationMetric::Heuristic));
+        assert!(measured.deviation_score.is_some());
+
+        // Measured against the stored scores the suggestions read too
+        let pool: Vec<u32> = app.solution_words.indices().collect();
+        let mut scores = app.scored_pools.get(&pool).unwrap().to_vec();
+        let optimal = app.solution_words.word(scores[0].0).to_string();
+        assert_eq!(measured.optimal_word.as_deref(), Some(optimal.as_str()));
+        scores.reverse();
+        let last = app.solution_words.word(scores[0].0).to_string();
+        app.scored_pools.insert(&pool, scores);
+        let remeasured = measure(&mut app, "stone");
+        assert_eq!(remeasured.optimal_word, Some(last));
+    }
+}
+
+#[cfg(test)]
+mod auto_submit_tests {
+    use super::*;
+    use crate::ui::{
+        test_support::{ScriptedEvents, buffer_lines},
+        types::AUTO_SUBMIT_GRACE,
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+    use std::time::{Duration, Instant};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn type_word(app: &mut App, word: &str) {
+        for c in word.chars() {
+            press(app, KeyCode::Char(c));
+        }
+    }
+
+    /// A game against STONE with auto-submit on.
+    fn auto_submit_game() -> App {
+        let mut app = create_test_app();
+        app.settings.auto_submit = true;
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("stone".to_string());
+        app
+    }
+
+    fn after_grace() -> Instant {
+        Instant::now() + AUTO_SUBMIT_GRACE + Duration::from_millis(100)
+    }
+
+    #[test]
+    fn test_valid_word_submitted_after_grace() {
+        let mut app = auto_submit_game();
+        type_word(&mut app, "cran");
+        assert!(app.pending_submit.is_none());
+
+        type_word(&mut app, "e");
+        assert!(app.pending_submit.is_some());
+
+        // Not yet: the grace window is still open
+        GameHandler::new(&mut app).tick(Instant::now());
+        assert_eq!(app.solver.guesses().len(), 0);
+
+        GameHandler::new(&mut app).tick(after_grace());
+        assert_eq!(app.solver.guesses().len(), 1);
+        assert_eq!(app.solver.guesses()[0].word, "crane");
+        assert!(app.input.is_empty());
+        assert!(app.pending_submit.is_none());
+    }
+
+    #[test]
+    fn test_invalid_word_not_submitted() {
+        let mut app = auto_submit_game();
+        type_word(&mut app, "zzzzz");
+
+        assert!(app.pending_submit.is_none());
+        assert!(matches!(
+            InputHandler::new(&mut app).input_status(),
+            InputStatus::Invalid(_)
+        ));
+
+        GameHandler::new(&mut app).tick(after_grace());
+        assert_eq!(app.solver.guesses().len(), 0);
+        assert_eq!(app.input, "zzzzz");
+    }
+
+    #[test]
+    fn test_backspace_cancels_pending_submit() {
+        let mut app = auto_submit_game();
+        type_word(&mut app, "crane");
+        press(&mut app, KeyCode::Backspace);
+
+        assert!(app.pending_submit.is_none());
+        GameHandler::new(&mut app).tick(after_grace());
+        assert_eq!(app.solver.guesses().len(), 0);
+        assert_eq!(app.input, "cran");
+    }
+
+    #[test]
+    fn test_off_by_default_and_in_solver_mode() {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_new_game();
+        type_word(&mut app, "crane");
+        assert!(app.pending_submit.is_none());
+
+        let mut app = create_test_app();
+        app.settings.auto_submit = true;
+        type_word(&mut app, "crane");
+        assert!(app.pending_submit.is_none());
+        GameHandler::new(&mut app).tick(after_grace());
+        assert_eq!(app.solver.guesses().len(), 0);
+    }
+
+    #[test]
+    fn test_run_loop_submits_while_idle() {
+        let mut app = AppBuilder::for_tests()
+            .solutions(vec!["stone".to_string()])
+            .build()
+            .unwrap();
+        app.settings.auto_submit = true;
+
+        let mut terminal = Terminal::new(TestBackend::new(140, 45)).unwrap();
+        let script = "crane"
+            .chars()
+            .fold(ScriptedEvents::new().ctrl('g'), |script, c| {
+                script.press(KeyCode::Char(c))
+            })
+            .idle(Duration::from_millis(400))
+            .ctrl('q');
+        app.run(&mut terminal, &mut { script }).unwrap();
+
+        assert_eq!(app.solver.guesses().len(), 1);
+        assert!(app.input.is_empty());
+    }
+
+    #[test]
+    fn test_pending_submit_shown_in_input_title() {
+        let mut app = auto_submit_game();
+        type_word(&mut app, "crane");
+
+        let mut terminal = Terminal::new(TestBackend::new(140, 45)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let frame = buffer_lines(terminal.backend().buffer()).join("\n");
+        assert!(frame.contains("submitting, Backspace cancels"));
+    }
+}
+
+#[cfg(test)]
+mod system_status_tests {
+    use super::*;
+    use crate::{
+        storage::{Database, JOURNAL_FILE},
+        ui::{system_status::SystemStatus, test_support::buffer_lines, types::Overlay},
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+    use std::fs;
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_in_memory_database_reported_as_not_saving() {
+        let app = create_test_app();
+        let status = SystemStatus::collect(&app);
+
+        assert!(status.in_memory());
+        assert!(!status.persisting());
+        assert_eq!(status.data_dir, None);
+        assert_eq!(status.database_bytes, None);
+        assert_eq!((status.games, status.solver_sessions), (Some(0), Some(0)));
+        assert_eq!(status.wordlists.len(), 2);
+        assert!(
+            status
+                .wordlists
+                .iter()
+                .all(|list| list.words == 8 && list.path.is_none())
+        );
+    }
+
+    #[test]
+    fn test_file_database_counts_and_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let words = dir.path().join("words.txt");
+        let db = Database::open(dir.path().join("history.db")).unwrap();
+        let mut app = AppBuilder::for_tests()
+            .solutions(vec!["stone".to_string()])
+            .wordlist_paths(Some(words.clone()), None)
+            .db(db)
+            .build()
+            .unwrap();
+        GameHandler::new(&mut app).start_new_game();
+        GameHandler::new(&mut app).submit_guess("stone".to_string());
+
+        let status = SystemStatus::collect(&app);
+        assert!(status.persisting());
+        assert_eq!(status.data_dir.as_deref(), Some(dir.path()));
+        assert_eq!(status.database, Some(dir.path().join("history.db")));
+        assert!(status.database_bytes.is_some_and(|bytes| bytes > 0));
+        assert_eq!(status.games, Some(1));
+        assert_eq!(status.solver_sessions, Some(0));
+        assert_eq!(status.wordlists[0].path, Some(words));
+        assert_eq!(status.wordlists[1].words, 1);
+    }
+
+    #[test]
+    fn test_pending_writes_mean_not_saving() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(dir.path().join("history.db")).unwrap();
+        let app = AppBuilder::for_tests().db(db).build().unwrap();
+        fs::write(dir.path().join(JOURNAL_FILE), "{}\n{}\n").unwrap();
+
+        let status = SystemStatus::collect(&app);
+        assert_eq!(status.pending_writes, 2);
+        assert!(!status.in_memory());
+        assert!(!status.persisting());
+    }
+
+    #[test]
+    fn test_f1_opens_about_and_any_key_closes_it() {
+        let mut app = create_test_app();
+        press(&mut app, KeyCode::F(1));
+        assert!(matches!(app.overlay, Some(Overlay::About(_))));
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let frame = buffer_lines(terminal.backend().buffer()).join("\n");
+        assert!(frame.contains("Data directory"));
+        assert!(frame.contains("in memory only"));
+        assert!(frame.contains("Allowed guesses 8 words, bundled"));
+
+        press(&mut app, KeyCode::Char('x'));
+        assert!(app.overlay.is_none());
+        assert!(app.input.is_empty());
+    }
+
+    #[test]
+    fn test_b_in_about_writes_a_bug_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(dir.path().join("history.db")).unwrap();
+        let mut app = AppBuilder::for_tests().db(db).build().unwrap();
+        press(&mut app, KeyCode::F(1));
+        press(&mut app, KeyCode::Char('b'));
+        assert!(app.overlay.is_none());
+
+        let bundles: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .filter(|name| name.starts_with("bug-report-") && name.ends_with(".tar.gz"))
+            .collect();
+        assert_eq!(bundles.len(), 1);
+        let logs = app.logs.lines();
+        assert!(logs.last().unwrap().contains(&bundles[0]), "{:?}", logs);
+    }
+
+    #[test]
+    fn test_bug_report_needs_a_data_directory() {
+        let mut app = create_test_app();
+        press(&mut app, KeyCode::F(1));
+        press(&mut app, KeyCode::Char('b'));
+        assert!(app.logs.lines().last().unwrap().contains("No bug report"));
+    }
+}
+
+#[cfg(test)]
+mod multi_board_tests {
+    use super::*;
+    use crate::ui::{history::GameOutcome, test_support::buffer_lines, types::MultiBoardState};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    /// A two-board game on `targets`, started the way Ctrl+K would.
+    fn start(app: &mut App, targets: [&str; 2]) {
+        GameHandler::new(app).start_multi_board_game(2);
+        let targets = targets.iter().map(|t| t.to_string()).collect();
+        app.boards = Some(MultiBoardState::new(targets, 5));
+    }
+
+    #[test]
+    fn test_start_grants_a_guess_per_extra_board() {
+        let mut app = create_test_app();
+        GameHandler::new(&mut app).start_multi_board_game(20);
+        assert_eq!(app.mode, GameMode::Game);
+        let boards = app.boards.as_ref().unwrap();
+        assert_eq!(boards.boards.len(), 8);
+        assert_eq!(app.remaining_guesses, 13);
+
+        // A regular game leaves the boards behind
+        GameHandler::new(&mut app).start_new_game();
+        assert!(app.boards.is_none());
+        assert_eq!(app.remaining_guesses, 6);
+    }
+
+    #[test]
+    fn test_win_solves_every_board_and_is_saved_apart() {
+        let mut app = create_test_app();
+        start(&mut app, ["crane", "world"]);
+
+        GameHandler::new(&mut app).submit_guess("crane".to_string());
+        assert!(!app.game_over);
+        assert_eq!(app.boards.as_ref().unwrap().solved(), 1);
+        // Hints move on to the board left
+        assert_eq!(app.target_word.as_deref(), Some("world"));
+        assert_eq!(app.solver.guesses().len(), 1);
+
+        GameHandler::new(&mut app).submit_guess("world".to_string());
+        assert!(app.game_over);
+        assert!(app.game_won);
+
+        let games = app.db.load_multi_board_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].outcome, GameOutcome::Won { guesses: 2 });
+        assert_eq!(games[0].guesses, ["crane", "world"]);
+        let solved: Vec<_> = games[0].boards.iter().map(|b| b.solved_in).collect();
+        assert_eq!(solved, [Some(1), Some(2)]);
+        assert!(app.db.load_games().unwrap().is_empty());
+        assert_eq!(app.db.multi_board_stats().unwrap()[0].wins, 1);
+    }
+
+    #[test]
+    fn test_running_out_of_guesses_loses() {
+        let mut app = create_test_app();
+        start(&mut app, ["crane", "world"]);
+        for word in [
+            "crane", "raise", "stone", "slate", "house", "apple", "magic",
+        ] {
+            GameHandler::new(&mut app).submit_guess(word.to_string());
+        }
+        assert!(app.game_over);
+        assert!(!app.game_won);
+
+        let games = app.db.load_multi_board_games().unwrap();
+        assert_eq!(games[0].outcome, GameOutcome::Lost);
+        assert_eq!(games[0].solved(), 1);
+        assert_eq!(games[0].guesses.len(), 7);
+    }
+
+    #[test]
+    fn test_undo_is_refused() {
+        let mut app = create_test_app();
+        start(&mut app, ["crane", "world"]);
+        GameHandler::new(&mut app).submit_guess("raise".to_string());
+        GameHandler::new(&mut app).undo_guess();
+        assert_eq!(app.boards.as_ref().unwrap().guesses.len(), 1);
+        assert_eq!(app.remaining_guesses, 6);
+    }
+
+    #[test]
+    fn test_boards_are_drawn_side_by_side() {
+        let mut app = create_test_app();
+        start(&mut app, ["crane", "world"]);
+        GameHandler::new(&mut app).submit_guess("crane".to_string());
+
+        let mut terminal = Terminal::new(TestBackend::new(40, app.guess_panel_height())).unwrap();
+        terminal.draw(|f| app.draw_guesses(f, f.area())).unwrap();
+        let lines = buffer_lines(terminal.backend().buffer());
+        assert!(lines[0].contains("Boards (1/2 solved)"));
+        assert!(lines[1].contains("#1 ✓1"));
+        assert!(lines[1].contains("#2"));
+        assert_eq!(lines[2].matches("CRANE").count(), 2);
+    }
+}
+
+#[cfg(test)]
+mod checkpoint_tests {
+    use super::*;
+    use crate::{
+        solver::parse_pattern,
+        ui::{
+            handlers::CheckpointHandler,
+            history::{GameGuess, GameOutcome, GameRecord},
+            types::CHECKPOINT_DELAY,
+        },
+    };
+    use chrono::{Duration, Utc};
+    use std::time::Instant;
+
+    fn solver_guess(app: &mut App, word: &str) {
+        SolverHandler::new(app).submit_guess(word.to_string(), parse_pattern("XXXXX").unwrap());
+    }
+
+    /// Open rows the next start would close as abandoned by a crash.
+    fn open_rows(app: &App) -> usize {
+        app.db
+            .close_stale(Utc::now() + Duration::seconds(1))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_burst_of_guesses_written_once_after_the_delay() {
+        let mut app = create_test_app();
+        for word in ["crane", "slate", "house"] {
+            solver_guess(&mut app, word);
+        }
+        let since = app.checkpoints.since.unwrap();
+
+        CheckpointHandler::new(&mut app).note_due(since + CHECKPOINT_DELAY / 2);
+        assert!(app.checkpoints.session);
+        CheckpointHandler::new(&mut app).note_due(since + CHECKPOINT_DELAY);
+        assert!(app.checkpoints.since.is_none());
+
+        // The one open row holds every guess
+        assert_eq!(open_rows(&app), 1);
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].guess_count(), 3);
+    }
+
+    #[test]
+    fn test_counts_correct_after_rapid_guesses_to_a_solve() {
+        let mut app = create_test_app();
+        solver_guess(&mut app, "crane");
+        solver_guess(&mut app, "slate");
+        SolverHandler::new(&mut app)
+            .submit_guess("house".to_string(), parse_pattern("GGGGG").unwrap());
+        CheckpointHandler::new(&mut app).note_due(Instant::now() + CHECKPOINT_DELAY);
+
+        // The final row replaced the checkpoint it made unnecessary
+        assert_eq!(open_rows(&app), 0);
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].guess_count(), 3);
+        let averages = app.db.session_averages_by_version().unwrap();
+        assert_eq!((averages[0].count, averages[0].average_guesses), (1, 3.0));
+    }
+
+    #[test]
+    fn test_undo_to_nothing_removes_the_row_at_once() {
+        let mut app = create_test_app();
+        solver_guess(&mut app, "crane");
+        CheckpointHandler::new(&mut app).flush();
+
+        SolverHandler::new(&mut app).undo_guess();
+        assert_eq!(open_rows(&app), 0);
+    }
+
+    #[test]
+    fn test_refused_checkpoint_does_not_lose_the_other() {
+        let mut app = create_test_app();
+        solver_guess(&mut app, "crane");
+        // A game started directly leaves the session open alongside it
+        GameHandler::new(&mut app).start_new_game();
+        app.target_word = Some("stone".to_string());
+        GameHandler::new(&mut app).submit_guess("slate".to_string());
+        assert!(app.checkpoints.game && app.checkpoints.session);
+
+        // A finished row already under the game's id refuses its open row
+        let finished = GameRecord {
+            timestamp: Utc::now(),
+            target_word: "stone".to_string(),
+            guesses: vec![GameGuess::new("stone".to_string(), vec![])],
+            outcome: GameOutcome::Won { guesses: 1 },
+            blitz: false,
+            duration_secs: None,
+            app_version: None,
+            difficulty: Default::default(),
+            hints_used: 0,
+            hint_budget: None,
+            hints: Vec::new(),
+            archived_at: None,
+            word_difficulty: None,
+        };
+        app.db.save_game_as(&app.game_id, &finished).unwrap();
+
+        CheckpointHandler::new(&mut app).flush();
+        assert_eq!(open_rows(&app), 1);
+        let sessions = app.db.load_solver_sessions().unwrap();
+        assert_eq!(sessions[0].guesses[0].word, "crane");
+    }
+}
+
+#[cfg(test)]
+mod kids_tests {
+    use super::*;
+    use crate::{
+        kids::{KIDS_SUGGESTIONS, is_kids_word},
+        ui::{history::GameOutcome, test_support::buffer_lines},
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    /// A kids' game on `target`, started the way a new game would be with
+    /// kids mode on.
+    fn start(app: &mut App, target: &str) {
+        app.settings.kids_mode = true;
+        GameHandler::new(app).start_new_game();
+        app.target_word = Some(target.to_string());
+    }
+
+    #[test]
+    fn test_no_guess_limit_and_saved_apart() {
+        let mut app = create_test_app();
+        start(&mut app, "apple");
+        assert!(app.kids);
+        assert!(app.show_suggestions);
+
+        for word in [
+            "raise", "stone", "slate", "crane", "house", "world", "magic",
+        ] {
+            GameHandler::new(&mut app).submit_guess(word.to_string());
+        }
+        assert!(!app.game_over);
+        assert_eq!(app.solver.guesses().len(), 7);
+
+        // Even out of guesses, a miss doesn't end the game
+        app.remaining_guesses = 0;
+        GameHandler::new(&mut app).check_game_state(&[Feedback::Gray; 5]);
+        assert!(!app.game_over);
+
+        GameHandler::new(&mut app).submit_guess("apple".to_string());
+        assert!(app.game_over);
+        assert!(app.game_won);
+
+        let games = app.db.load_kids_games().unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].outcome, GameOutcome::Won { guesses: 8 });
+        assert_eq!(games[0].target_word, "apple");
+        assert!(app.db.load_games().unwrap().is_empty());
+
+        // A regular game has its limit back
+        app.settings.kids_mode = false;
+        GameHandler::new(&mut app).start_new_game();
+        assert!(!app.kids);
+        assert_eq!(app.remaining_guesses, 6);
+    }
+
+    #[test]
+    fn test_suggestions_are_three_everyday_words() {
+        let mut app = create_test_app();
+        start(&mut app, "apple");
+
+        // Shown from the start, and they stay shown
+        assert_eq!(app.suggestions.len(), KIDS_SUGGESTIONS);
+        let words = &app.solution_words;
+        assert!(
+            app.suggestions
+                .iter()
+                .all(|&(i, _)| is_kids_word(words.word(i)))
+        );
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Char('h'), KeyModifiers::CONTROL));
+        assert!(app.show_suggestions);
+
+        // Only candidates left are suggested
+        GameHandler::new(&mut app).submit_guess("raise".to_string());
+        let pool = app.solver.filter(&app.solution_words);
+        assert!(app.suggestions.iter().all(|(i, _)| pool.contains(i)));
+        assert!(app.suggestions.len() <= KIDS_SUGGESTIONS);
+    }
+
+    #[test]
+    fn test_win_shows_a_banner() {
+        let mut app = create_test_app();
+        start(&mut app, "apple");
+        GameHandler::new(&mut app).submit_guess("apple".to_string());
+        assert_eq!(app.game_status_height(), 5);
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 5)).unwrap();
+        terminal
+            .draw(|f| app.draw_game_status(f, f.area()))
+            .unwrap();
+        let lines = buffer_lines(terminal.backend().buffer());
+        assert!(lines[1].contains("HOORAY"));
+        assert!(lines[2].contains("APPLE in 1 guess!"));
+    }
+}
+
+#[cfg(test)]
+mod notifier_tests {
+    use super::*;
+    use crate::ui::{
+        builder::AppBuilder,
+        handlers::{MaintenanceHandler, TierHandler},
+        history::AbandonReason,
+        test_support::RecordingNotifier,
+        types::GAUNTLET_PAUSE,
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::time::Instant;
+
+    /// An app with notifications on, and a handle on what it sends.
+    fn notifying_app() -> (App, RecordingNotifier) {
+        let notifier = RecordingNotifier::default();
+        let mut app = AppBuilder::for_tests()
+            .notifier(notifier.clone())
+            .build()
+            .unwrap();
+        app.settings.notify_when_done = true;
+        (app, notifier)
+    }
+
+    fn start_game(app: &mut App, target: &str) {
+        GameHandler::new(app).start_new_game();
+        app.target_word = Some(target.to_string());
+    }
+
+    #[test]
+    fn test_game_over_notifies_once() {
+        let (mut app, notifier) = notifying_app();
+        start_game(&mut app, "crane");
+        GameHandler::new(&mut app).submit_guess("raise".to_string());
+        assert!(notifier.sent().is_empty());
+
+        GameHandler::new(&mut app).submit_guess("crane".to_string());
+        assert_eq!(notifier.sent(), ["Game won in 2 guesses"]);
+
+        // Starting the next game says nothing
+        start_game(&mut app, "crane");
+        assert_eq!(notifier.sent().len(), 1);
+
+        GameHandler::new(&mut app).give_up();
+        assert_eq!(notifier.sent(), ["Game won in 2 guesses", "Game forfeited"]);
+    }
+
+    #[test]
+    fn test_silent_when_off_or_abandoned() {
+        let (mut app, notifier) = notifying_app();
+        start_game(&mut app, "crane");
+        GameHandler::new(&mut app).abandon_game(AbandonReason::Switched);
+        assert!(notifier.sent().is_empty());
+
+        app.settings.notify_when_done = false;
+        start_game(&mut app, "crane");
+        GameHandler::new(&mut app).submit_guess("crane".to_string());
+        assert!(app.game_over);
+        assert!(notifier.sent().is_empty());
+    }
+
+    #[test]
+    fn test_gauntlet_notifies_once_at_the_end() {
+        let (mut app, notifier) = notifying_app();
+        app.settings.gauntlet_size = 2;
+        InputHandler::new(&mut app)
+            .handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL));
+
+        let first = app.target_word.clone().unwrap();
+        GameHandler::new(&mut app).submit_guess(first);
+        assert!(notifier.sent().is_empty());
+
+        GameHandler::new(&mut app).tick(Instant::now() + GAUNTLET_PAUSE);
+        GameHandler::new(&mut app).give_up();
+        assert_eq!(notifier.sent(), ["Gauntlet over: 1 of 2 won in 1 guesses"]);
+    }
+
+    #[test]
+    fn test_background_jobs_notify_when_finished() {
+        let (mut app, notifier) = notifying_app();
+        TierHandler::new(&mut app).load_scores();
+        while app.tier_job.is_some() {
+            TierHandler::new(&mut app).step_scoring();
+        }
+        assert_eq!(
+            notifier.sent(),
+            ["Scored 8 answers; new games can be drawn by tier"]
+        );
+
+        MaintenanceHandler::new(&mut app).start_recompute(true);
+        while MaintenanceHandler::new(&mut app).step_recompute().is_none() {}
+        assert_eq!(notifier.sent().len(), 2);
+        assert!(notifier.sent()[1].starts_with("Dry run, nothing written"));
+    }
+}
+
+#[cfg(test)]
+mod history_log_view_tests {
+    use super::*;
+    use crate::ui::{
+        handlers::HistoryHandler,
+        hints::status_hints,
+        history::HistoryViewMode,
+        test_support::buffer_lines,
+        types::{FocusTarget, LogBuffer},
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn press(app: &mut App, code: KeyCode) {
+        InputHandler::new(app).handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+    }
+
+    /// History mode over a fixed log buffer, with the logs focused.
+    fn history_with_logs(lines: &[&str]) -> App {
+        let mut app = create_test_app();
+        HistoryHandler::new(&mut app).enter_history_mode();
+        app.logs = LogBuffer::new();
+        for line in lines {
+            app.logs.push(line.to_string());
+        }
+        app
+    }
+
+    fn screen(app: &App) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        buffer_lines(terminal.backend().buffer())
+    }
+
+    #[test]
+    fn test_toggle_between_strip_and_full_view() {
+        let mut app = history_with_logs(&["first", "second"]);
+        assert_eq!(app.focused(), FocusTarget::History);
+
+        press(&mut app, KeyCode::Char('L'));
+        assert!(app.history_logs_expanded);
+        assert_eq!(app.focused(), FocusTarget::Logs);
+        // The history views are out of reach behind the logs
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::F(6));
+        assert_eq!(app.history_view_mode, HistoryViewMode::Stats);
+        assert_eq!(app.focused(), FocusTarget::Logs);
+        assert!(screen(&app)[0].starts_with("┌Logs"));
+
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.history_logs_expanded);
+        assert_eq!(app.focused(), FocusTarget::History);
+
+        // From the focused strip too, and L collapses again
+        press(&mut app, KeyCode::F(6));
+        assert_eq!(app.focused(), FocusTarget::Logs);
+        press(&mut app, KeyCode::Char('L'));
+        assert!(app.history_logs_expanded);
+        assert!(
+            status_hints(&app)
+                .iter()
+                .any(|h| h.action == "compact logs")
+        );
+        press(&mut app, KeyCode::Char('L'));
+        assert!(!app.history_logs_expanded);
+
+        // Leaving history mode doesn't keep it for next time
+        press(&mut app, KeyCode::Char('L'));
+        HistoryHandler::new(&mut app).exit_history_mode();
+        HistoryHandler::new(&mut app).enter_history_mode();
+        assert!(!app.history_logs_expanded);
+    }
+
+    #[test]
+    fn test_search_jumps_between_matches() {
+        let mut app = history_with_logs(&[
+            "Imported 3 games",
+            "verify: ok",
+            "Import failed: disk full",
+            "unrelated",
+            "IMPORT done",
+        ]);
+        press(&mut app, KeyCode::Char('L'));
+        press(&mut app, KeyCode::Char('/'));
+        type_text(&mut app, "imporx");
+        press(&mut app, KeyCode::Backspace);
+        type_text(&mut app, "t");
+        assert_eq!(app.log_search.as_ref().unwrap().query, "import");
+
+        // Enter lands on the newest match, n works back, N forward, both wrap
+        press(&mut app, KeyCode::Enter);
+        let current = |app: &App| app.log_search.as_ref().unwrap().current;
+        assert_eq!(current(&app), Some(4));
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(current(&app), Some(2));
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(current(&app), Some(0));
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(current(&app), Some(4));
+        press(&mut app, KeyCode::Char('N'));
+        assert_eq!(current(&app), Some(0));
+        assert!(screen(&app)[0].contains("/import: 1 of 3"));
+
+        // Esc drops the search before it collapses the view
+        press(&mut app, KeyCode::Esc);
+        assert!(app.log_search.is_none());
+        assert!(app.history_logs_expanded);
+    }
+
+    #[test]
+    fn test_jump_scrolls_match_into_view() {
+        let lines: Vec<String> = (0..100).map(|i| format!("line {}", i)).collect();
+        let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let mut app = history_with_logs(&refs);
+        press(&mut app, KeyCode::Char('L'));
+        screen(&app);
+        press(&mut app, KeyCode::Char('/'));
+        type_text(&mut app, "line 10");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.log_search.as_ref().unwrap().current, Some(10));
+
+        let screen = screen(&app);
+        assert!(screen.iter().any(|row| row.contains("line 10 ")));
+        assert!(!screen.iter().any(|row| row.contains("line 99")));
+    }
+
+    #[test]
+    fn test_search_without_matches() {
+        let mut app = history_with_logs(&["first", "second"]);
+        press(&mut app, KeyCode::F(6));
+        press(&mut app, KeyCode::Char('/'));
+        type_text(&mut app, "zebra");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.log_search.as_ref().unwrap().current, None);
+        assert_eq!(app.log_scroll, 0);
+        assert!(
+            screen(&app)
+                .iter()
+                .any(|row| row.contains("/zebra: no matches"))
+        );
+    }
 }
@@ -1,32 +1,154 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, VecDeque},
+    fmt,
+    ops::RangeInclusive,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
-use crate::solver::Feedback;
+use ratatui::{layout::Rect, text::Line};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    scoring::{PatternOdds, ScoreExplanation},
+    settings::PanelKind,
+    solver::{Feedback, Guess, SolverState, generate_feedback},
+    storage::{ResumableSession, SolverSnapshot},
+    tiers::ScoreJob,
+};
+
+use super::history::{
+    GameOutcome, GameRecord, HintKind, HistoryViewMode, StatsSection,
+    solver_types::{SolverGuess, SolverSession},
+};
+use super::system_status::SystemStatus;
 
 pub const MAX_LOG_LINES: usize = 300;
 
+/// Shortest time between redraws in low-refresh mode.
+pub const LOW_REFRESH_FRAME: Duration = Duration::from_millis(200);
+
+/// What an analysis panel's lines were built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanelKey {
+    /// The app's analysis version when built.
+    pub analysis: u64,
+    /// The pattern preview's version, for the panel showing it; 0 for the others.
+    pub preview: u64,
+    pub area: Rect,
+    /// Whether low-refresh mode was on.
+    pub plain: bool,
+}
+
+/// Lines built for each analysis panel, reused until its key changes.
+#[derive(Debug, Default)]
+pub struct PanelCache {
+    entries: RefCell<HashMap<PanelKind, (PanelKey, Vec<Line<'static>>)>>,
+    /// Panels built rather than reused, since the app started.
+    builds: Cell<usize>,
+}
+
+impl PanelCache {
+    /// The lines for `panel`, built with `build` unless the cached ones have the same key.
+    pub fn lines(
+        &self,
+        panel: PanelKind,
+        key: PanelKey,
+        build: impl FnOnce() -> Vec<Line<'static>>,
+    ) -> Vec<Line<'static>> {
+        let mut entries = self.entries.borrow_mut();
+        if let Some((cached, lines)) = entries.get(&panel)
+            && *cached == key
+        {
+            return lines.clone();
+        }
+        let lines = build();
+        self.builds.set(self.builds.get() + 1);
+        entries.insert(panel, (key, lines.clone()));
+        lines
+    }
+
+    pub fn builds(&self) -> usize {
+        self.builds.get()
+    }
+
+    /// Panels cached and the rough bytes of their lines.
+    pub fn footprint(&self) -> (usize, usize) {
+        let entries = self.entries.borrow();
+        let bytes = entries
+            .values()
+            .flat_map(|(_, lines)| lines)
+            .map(|line| {
+                size_of::<Line>()
+                    + line
+                        .spans
+                        .iter()
+                        .map(|span| size_of_val(span) + span.content.len())
+                        .sum::<usize>()
+            })
+            .sum();
+        (entries.len(), bytes)
+    }
+}
+
+/// Suggestions listed before the panel has been drawn and measured.
+pub const SUGGESTION_ROWS: usize = 10;
+
+/// Guesses in a standard game, and the rows the Guesses panel always has.
+pub const STANDARD_GUESSES: usize = 6;
+
+/// Rows the Guesses panel grows to for a long solver session before it scrolls.
+pub const MAX_GUESS_ROWS: usize = 12;
+
+/// Top suggestions compared before and after each guess.
+pub const SUGGESTION_DIFF_ROWS: usize = 10;
+
+/// Largest pool for which the feedback a typed word would get is previewed.
+pub const PATTERN_PREVIEW_MAX_POOL: usize = 1000;
+
+/// Most likely patterns listed in the preview.
+pub const PATTERN_PREVIEW_ROWS: usize = 3;
+
+/// Games fetched at a time while history loads, a batch per loop.
+pub const HISTORY_BATCH: usize = 200;
+
+/// Submitted input lines kept for recall in each mode.
+pub const INPUT_HISTORY_LEN: usize = 50;
+
+/// Pools whose suggestion scores are kept, so undoing back to one doesn't
+/// score it again.
+pub const SCORED_POOLS_KEPT: usize = 4;
+
 /// Thread-safe circular log buffer with a maximum capacity.
 #[derive(Clone)]
 pub struct LogBuffer {
-    inner: Arc<Mutex<Vec<String>>>,
+    inner: Arc<Mutex<VecDeque<String>>>,
 }
 
 impl LogBuffer {
     pub fn new() -> Self {
         Self {
-            inner: Arc::new(Mutex::new(Vec::new())),
+            inner: Arc::new(Mutex::new(VecDeque::with_capacity(MAX_LOG_LINES))),
         }
     }
 
     pub fn push(&self, msg: String) {
         let mut buf = self.inner.lock().unwrap();
-        buf.push(msg);
-        if buf.len() > MAX_LOG_LINES {
-            buf.remove(0);
+        if buf.len() == MAX_LOG_LINES {
+            buf.pop_front();
         }
+        buf.push_back(msg);
     }
 
     pub fn lines(&self) -> Vec<String> {
-        self.inner.lock().unwrap().clone()
+        self.inner.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Lines held and the bytes of their text.
+    pub fn footprint(&self) -> (usize, usize) {
+        let buf = self.inner.lock().unwrap();
+        (buf.len(), buf.iter().map(String::capacity).sum())
     }
 }
 
@@ -36,10 +158,161 @@ impl Default for LogBuffer {
     }
 }
 
+/// A search through the log lines, typed after `/` with the history logs focused.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogSearch {
+    pub query: String,
+    /// Still being typed, so keys go to the query.
+    pub editing: bool,
+    /// The line last jumped to.
+    pub current: Option<usize>,
+}
+
+impl LogSearch {
+    /// Whether `line` contains the query, ignoring case; nothing matches an
+    /// empty query.
+    pub fn matches(&self, line: &str) -> bool {
+        !self.query.is_empty() && line.to_lowercase().contains(&self.query.to_lowercase())
+    }
+
+    /// Indices of the matching lines, oldest first.
+    pub fn matching_lines(&self, lines: &[String]) -> Vec<usize> {
+        (0..lines.len())
+            .filter(|&i| self.matches(&lines[i]))
+            .collect()
+    }
+
+    /// The match after the current one toward older lines, or toward newer
+    /// ones, wrapping at either end; the newest match when there's no current.
+    pub fn step(&self, lines: &[String], older: bool) -> Option<usize> {
+        let found = self.matching_lines(lines);
+        let Some(current) = self.current else {
+            return found.last().copied();
+        };
+        if older {
+            found
+                .iter()
+                .rev()
+                .find(|&&i| i < current)
+                .or(found.last())
+                .copied()
+        } else {
+            found
+                .iter()
+                .find(|&&i| i > current)
+                .or(found.first())
+                .copied()
+        }
+    }
+}
+
+/// Lines submitted from the input field this session, rejected ones included,
+/// kept apart for Solver and Game mode so recall only offers the current mode's.
+#[derive(Debug, Default)]
+pub struct InputHistory {
+    solver: VecDeque<String>,
+    game: VecDeque<String>,
+    recall: Option<Recall>,
+}
+
+/// Where Up/Down have walked to in one mode's lines.
+#[derive(Debug)]
+struct Recall {
+    mode: GameMode,
+    position: usize,
+    /// The unsent line, restored when Down steps past the newest entry.
+    draft: String,
+}
+
+impl InputHistory {
+    /// Remember a submitted line as the newest, least recently submitted
+    /// lines dropping off past [`INPUT_HISTORY_LEN`]; a line already kept
+    /// moves up rather than being kept twice, and blank lines are skipped.
+    pub fn record(&mut self, mode: &GameMode, line: &str) {
+        self.recall = None;
+        let line = line.trim();
+        let Some(entries) = self.entries_mut(mode) else {
+            return;
+        };
+        if line.is_empty() {
+            return;
+        }
+        if let Some(kept) = entries.iter().position(|entry| entry == line) {
+            entries.remove(kept);
+        }
+        entries.push_back(line.to_string());
+        if entries.len() > INPUT_HISTORY_LEN {
+            entries.pop_front();
+        }
+    }
+
+    /// The line before the one recalled, starting from the newest and
+    /// keeping `current` as the draft; None once at the oldest.
+    pub fn older(&mut self, mode: &GameMode, current: &str) -> Option<String> {
+        let len = self.entries(mode).len();
+        let position = match &mut self.recall {
+            Some(recall) if recall.mode == *mode => {
+                recall.position = recall.position.checked_sub(1)?;
+                recall.position
+            }
+            _ => {
+                let newest = len.checked_sub(1)?;
+                self.recall = Some(Recall {
+                    mode: mode.clone(),
+                    position: newest,
+                    draft: current.to_string(),
+                });
+                newest
+            }
+        };
+        Some(self.entries(mode)[position].clone())
+    }
+
+    /// The line after the one recalled, or the draft once past the newest;
+    /// None when nothing is being recalled.
+    pub fn newer(&mut self, mode: &GameMode) -> Option<String> {
+        let len = self.entries(mode).len();
+        let recall = self.recall.as_mut().filter(|r| r.mode == *mode)?;
+        if recall.position + 1 < len {
+            recall.position += 1;
+            let position = recall.position;
+            Some(self.entries(mode)[position].clone())
+        } else {
+            self.recall.take().map(|recall| recall.draft)
+        }
+    }
+
+    /// The mode's lines, oldest first.
+    pub fn entries(&self, mode: &GameMode) -> &VecDeque<String> {
+        static EMPTY: VecDeque<String> = VecDeque::new();
+        match mode {
+            GameMode::Solver => &self.solver,
+            GameMode::Game => &self.game,
+            GameMode::History => &EMPTY,
+        }
+    }
+
+    fn entries_mut(&mut self, mode: &GameMode) -> Option<&mut VecDeque<String>> {
+        match mode {
+            GameMode::Solver => Some(&mut self.solver),
+            GameMode::Game => Some(&mut self.game),
+            GameMode::History => None,
+        }
+    }
+}
+
+/// The feedback patterns the word in the solver input could get.
+#[derive(Debug, Clone)]
+pub struct PatternPreview {
+    pub word: String,
+    /// Most likely first; empty when the pool was too large to preview.
+    pub odds: Vec<PatternOdds>,
+}
+
 /// Input validation status.
 pub enum InputStatus {
     Incomplete,
-    Invalid(&'static str),
+    Invalid(String),
     Valid,
 }
 
@@ -54,9 +327,626 @@ pub enum ParsedInput {
 }
 
 /// Application operating mode.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum GameMode {
     Solver,
     Game,
     History,
 }
+
+/// Whether letters typed in Solver and Game mode go to the input field.
+///
+/// Only consulted when the `modal_input` setting is on; otherwise the app
+/// stays in insert mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputMode {
+    Insert,
+    /// Letters are vim-style commands and never reach the input field.
+    Normal,
+}
+
+/// The panel offered keys before the global bindings.
+///
+/// The input field has focus unless the user moves it; in History mode,
+/// which has no input field, the history view does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusTarget {
+    Input,
+    /// Holds exactly while a suggestion is selected.
+    Suggestions,
+    Guesses,
+    Logs,
+    History,
+}
+
+/// Modal popup drawn over the main layout; it receives keys before anything else.
+#[derive(Debug, Clone)]
+pub enum Overlay {
+    ScoreExplanation(ScoreExplanation),
+    EditGuess(EditGuessState),
+    ConfirmDiscard(ConfirmDiscardState),
+    /// A guess of a word already guessed, held until the user confirms it.
+    ConfirmRepeat {
+        word: String,
+    },
+    /// Blacklisted words, with the highlighted row.
+    Blacklist {
+        selected: usize,
+    },
+    Snapshots(SnapshotState),
+    VerifyAnswer(VerifyAnswerState),
+    /// Analysis panel editor, with the highlighted row.
+    PanelLayout {
+        selected: usize,
+    },
+    DictionarySearch(DictionarySearchState),
+    GauntletSummary(GauntletSummaryState),
+    /// A solver session left by the last run, offered at startup.
+    ResumeSession(ResumableSession),
+    /// Note being typed for the latest guess.
+    GuessNote {
+        text: String,
+    },
+    /// UI state journaled by the last run, offered at startup.
+    RestoreUiState(UiJournalEntry),
+    /// Where the data lives and whether history is being saved.
+    About(SystemStatus),
+}
+
+/// An Enter this soon after a submission, with nothing typed since, is taken
+/// for key repeat and ignored.
+pub const SUBMIT_REPEAT_WINDOW: Duration = Duration::from_millis(150);
+
+/// How long a full-length word waits to be auto-submitted, so Backspace can
+/// still take it back.
+pub const AUTO_SUBMIT_GRACE: Duration = Duration::from_millis(300);
+
+/// How long after the first guess not yet written the open game or solver
+/// session row is written, so a burst of guesses costs one write.
+pub const CHECKPOINT_DELAY: Duration = Duration::from_millis(500);
+
+/// Open rows asked to be rewritten but not yet written.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PendingCheckpoints {
+    /// When the first of them was asked for.
+    pub since: Option<Instant>,
+    pub game: bool,
+    pub session: bool,
+}
+
+/// File beside the database the UI state is journaled to.
+pub const UI_STATE_FILE: &str = "ui-state.json";
+
+/// How long after the first unsaved change the UI state is journaled.
+pub const UI_STATE_DELAY: Duration = Duration::from_secs(1);
+
+/// Oldest journaled UI state offered for restoring, in minutes.
+pub const UI_STATE_MAX_AGE_MINUTES: i64 = 5;
+
+/// Transient UI state, journaled so it survives a crash or an accidental quit.
+///
+/// Guesses, games and sessions are in the database already; this is only
+/// where the user was and what they had typed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UiStateSnapshot {
+    pub mode: GameMode,
+    pub input: String,
+    pub input_mode: InputMode,
+    pub show_suggestions: bool,
+    pub show_analysis: bool,
+    pub suggestion_selected: Option<usize>,
+    pub focus: FocusTarget,
+    pub history_view_mode: HistoryViewMode,
+    #[serde(default)]
+    pub history_stats_section: StatsSection,
+    pub history_page: usize,
+    pub history_include_archived: bool,
+    /// RFC 3339, as history timestamps are stored.
+    pub history_compare_split: Option<String>,
+    /// Row id of the game selected in history.
+    pub history_selected: Option<i64>,
+}
+
+/// A [`UiStateSnapshot`] as written to [`UI_STATE_FILE`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UiJournalEntry {
+    /// RFC 3339.
+    pub saved_at: String,
+    #[serde(flatten)]
+    pub state: UiStateSnapshot,
+}
+
+/// Bookkeeping for journaling the UI state a little after it changes.
+#[derive(Debug, Default)]
+pub struct UiJournal {
+    /// The state last written.
+    pub written: Option<UiStateSnapshot>,
+    /// When the state first differed from `written`.
+    pub changed_at: Option<Instant>,
+    /// The state left by the last run, until it is restored or declined;
+    /// nothing is written meanwhile so a second crash doesn't lose it.
+    pub pending: Option<UiJournalEntry>,
+}
+
+/// Rows of matches the dictionary search popup shows at once.
+pub const DICTIONARY_ROWS: usize = 12;
+
+/// A query against the allowed list, typed apart from the input field.
+#[derive(Debug, Clone, Default)]
+pub struct DictionarySearchState {
+    pub query: String,
+    /// Indices into the allowed list of every word matching `query`.
+    pub hits: Vec<u32>,
+    pub selected: usize,
+}
+
+/// Progress through the edit-guess popup.
+#[derive(Debug, Clone)]
+pub struct EditGuessState {
+    pub selected: usize,
+    /// Replacement pattern being typed; None while choosing the guess.
+    pub pattern: Option<String>,
+}
+
+/// Saved solver snapshots and progress through the snapshot manager popup.
+#[derive(Debug, Clone)]
+pub struct SnapshotState {
+    /// Most recently saved first.
+    pub snapshots: Vec<SolverSnapshot>,
+    pub selected: usize,
+    pub step: SnapshotStep,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotStep {
+    Browse,
+    /// Typing the name to save the current state under.
+    Naming(String),
+    /// Waiting for y/n before the selected snapshot replaces the current guesses.
+    ConfirmLoad,
+}
+
+/// Asking whether a finished solver session converged on the real answer.
+#[derive(Debug, Clone)]
+pub struct VerifyAnswerState {
+    /// Saved session the answer is recorded against.
+    pub session_id: i64,
+    /// The word the solver converged on.
+    pub word: String,
+    /// The session's guesses, checked against the real answer if it differs.
+    pub guesses: Vec<Guess>,
+    /// Real answer being typed after the user said no; None while asking.
+    pub answer: Option<String>,
+}
+
+/// Mode transition that can throw away unfinished work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingAction {
+    /// Ctrl+G: solver to a new game, or game back to solver.
+    ToggleGameMode,
+    /// Ctrl+T: start a blitz game.
+    StartBlitz,
+    /// Ctrl+N: start a gauntlet of games.
+    StartGauntlet,
+    /// Ctrl+U: start a shadow game of a puzzle played elsewhere.
+    StartShadow,
+    /// Ctrl+K: start a multi-board practice game.
+    StartMultiBoard,
+    /// Ctrl+S: leave the game for solver mode.
+    SwitchToSolver,
+    /// Ctrl+R: open the history viewer.
+    OpenHistory,
+    /// Ctrl+X: end the game as forfeited and reveal the answer.
+    GiveUp,
+}
+
+/// A transition held back until the user confirms discarding their work.
+#[derive(Debug, Clone)]
+pub struct ConfirmDiscardState {
+    pub action: PendingAction,
+    pub prompt: String,
+}
+
+/// How a single blitz turn ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlitzTurn {
+    Guessed(Duration),
+    TimedOut,
+}
+
+/// How long a toast stays on screen.
+pub const TOAST_DURATION: Duration = Duration::from_secs(3);
+
+/// How long an error stands in for the status bar hints.
+pub const STATUS_ERROR_DURATION: Duration = Duration::from_secs(5);
+
+/// A pool and its suggestion scores, blacklisted words included.
+#[derive(Debug, Clone)]
+pub struct ScoredPool {
+    pub pool: Vec<u32>,
+    pub scores: Vec<(u32, usize)>,
+}
+
+/// The last [`SCORED_POOLS_KEPT`] pools scored, most recently used last.
+#[derive(Debug, Default)]
+pub struct ScoredPools {
+    pools: VecDeque<ScoredPool>,
+}
+
+impl ScoredPools {
+    /// The scores kept for `pool`, which becomes the most recently used.
+    pub fn get(&mut self, pool: &[u32]) -> Option<&[(u32, usize)]> {
+        let kept = self.pools.iter().position(|p| p.pool == pool)?;
+        let scored = self.pools.remove(kept)?;
+        self.pools.push_back(scored);
+        self.pools.back().map(|p| p.scores.as_slice())
+    }
+
+    /// Keep the scores for `pool` in place of any kept before, dropping the
+    /// least recently used past the cap.
+    pub fn insert(&mut self, pool: &[u32], scores: Vec<(u32, usize)>) {
+        self.pools.retain(|p| p.pool != pool);
+        if self.pools.len() == SCORED_POOLS_KEPT {
+            self.pools.pop_front();
+        }
+        self.pools.push_back(ScoredPool {
+            pool: pool.to_vec(),
+            scores,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.pools.len()
+    }
+
+    /// Bytes held by the pools and their scores.
+    pub fn footprint(&self) -> usize {
+        self.pools
+            .iter()
+            .map(|p| {
+                p.pool.capacity() * size_of::<u32>()
+                    + p.scores.capacity() * size_of::<(u32, usize)>()
+            })
+            .sum()
+    }
+}
+
+/// A short message shown over the panels until `until`, and never after.
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub text: String,
+    pub until: Instant,
+}
+
+impl Toast {
+    pub fn new(text: String, now: Instant) -> Self {
+        Self::lasting(text, now, TOAST_DURATION)
+    }
+
+    pub fn lasting(text: String, now: Instant, duration: Duration) -> Self {
+        Self {
+            text,
+            until: now + duration,
+        }
+    }
+
+    pub fn visible(&self, now: Instant) -> bool {
+        now < self.until
+    }
+}
+
+/// The hints taken in the current game and how many it allows.
+///
+/// Showing suggestions or analysis costs a point the first time in a game;
+/// hiding and showing them again is free. Every best guess revealed costs one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HintBudget {
+    /// Points allowed; unlimited when unset.
+    pub limit: Option<usize>,
+    pub used: Vec<HintKind>,
+}
+
+impl HintBudget {
+    pub fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            used: Vec::new(),
+        }
+    }
+
+    /// Points left; `None` when unlimited.
+    pub fn remaining(&self) -> Option<usize> {
+        self.limit
+            .map(|limit| limit.saturating_sub(self.used.len()))
+    }
+
+    /// Whether taking `kind` now would cost a point.
+    pub fn costs(&self, kind: HintKind) -> bool {
+        kind == HintKind::BestGuess || !self.used.contains(&kind)
+    }
+
+    /// Take `kind`, spending a point if it costs one. False, with nothing
+    /// spent, if the budget is used up.
+    pub fn spend(&mut self, kind: HintKind) -> bool {
+        if !self.costs(kind) {
+            return true;
+        }
+        if self.remaining() == Some(0) {
+            return false;
+        }
+        self.used.push(kind);
+        true
+    }
+
+    /// Best guesses revealed.
+    pub fn reveals(&self) -> usize {
+        self.used
+            .iter()
+            .filter(|&&kind| kind == HintKind::BestGuess)
+            .count()
+    }
+}
+
+/// How long a finished gauntlet game stays on screen before the next one starts.
+pub const GAUNTLET_PAUSE: Duration = Duration::from_secs(3);
+
+/// Progress through a gauntlet: a fixed set of targets played back to back.
+#[derive(Debug, Clone)]
+pub struct GauntletState {
+    /// The gauntlet's row in the database; None if it couldn't be saved.
+    pub id: Option<i64>,
+    /// Every target, in play order.
+    pub targets: Vec<String>,
+    /// Guesses used in each finished game, and whether it was won.
+    pub results: Vec<(usize, bool)>,
+    /// When the next game starts, set once the current one has ended.
+    pub next_at: Option<Instant>,
+}
+
+impl GauntletState {
+    pub fn new(id: Option<i64>, targets: Vec<String>) -> Self {
+        Self {
+            id,
+            targets,
+            results: Vec::new(),
+            next_at: None,
+        }
+    }
+
+    /// The target of the game after the finished ones, if any are left.
+    pub fn next_target(&self) -> Option<&str> {
+        self.targets.get(self.results.len()).map(String::as_str)
+    }
+
+    pub fn wins(&self) -> usize {
+        self.results.iter().filter(|(_, won)| *won).count()
+    }
+
+    pub fn total_guesses(&self) -> usize {
+        self.results.iter().map(|(guesses, _)| guesses).sum()
+    }
+}
+
+/// Boards a multi-board game can have.
+pub const BOARD_COUNTS: RangeInclusive<usize> = 2..=8;
+
+/// Boards side by side in the Guesses panel before the next ones wrap below.
+pub const BOARDS_PER_ROW: usize = 4;
+
+/// One board of a multi-board game: its answer and the feedback each guess
+/// got there, up to the one that solved it.
+#[derive(Debug, Clone)]
+pub struct Board {
+    pub target: String,
+    pub solver: SolverState,
+    /// The 1-based guess that solved the board.
+    pub solved_in: Option<usize>,
+}
+
+/// A multi-board game: every guess is played on each board not yet solved.
+#[derive(Debug, Clone)]
+pub struct MultiBoardState {
+    pub boards: Vec<Board>,
+    /// Every guess, in order.
+    pub guesses: Vec<String>,
+}
+
+impl MultiBoardState {
+    pub fn new(targets: Vec<String>, word_len: usize) -> Self {
+        Self {
+            boards: targets
+                .into_iter()
+                .map(|target| Board {
+                    target,
+                    solver: SolverState::new(word_len),
+                    solved_in: None,
+                })
+                .collect(),
+            guesses: Vec::new(),
+        }
+    }
+
+    /// Score `word` on every board not yet solved.
+    pub fn play(&mut self, word: &str) -> crate::error::Result<()> {
+        let number = self.guesses.len() + 1;
+        for board in self.boards.iter_mut().filter(|b| b.solved_in.is_none()) {
+            let feedback = generate_feedback(&board.target, word);
+            let solved = feedback.iter().all(|&fb| fb == Feedback::Green);
+            board
+                .solver
+                .add_guess(Guess::new(word.to_string(), feedback))?;
+            if solved {
+                board.solved_in = Some(number);
+            }
+        }
+        self.guesses.push(word.to_string());
+        Ok(())
+    }
+
+    pub fn solved(&self) -> usize {
+        self.boards.iter().filter(|b| b.solved_in.is_some()).count()
+    }
+
+    pub fn all_solved(&self) -> bool {
+        self.solved() == self.boards.len()
+    }
+
+    /// The first board not yet solved, else the one solved last; the
+    /// suggestions and analysis follow it.
+    pub fn focus(&self) -> Option<&Board> {
+        self.boards
+            .iter()
+            .find(|b| b.solved_in.is_none())
+            .or_else(|| self.boards.iter().max_by_key(|b| b.solved_in))
+    }
+}
+
+/// A finished gauntlet's games, ranked, and the highlighted row.
+#[derive(Debug, Clone)]
+pub struct GauntletSummaryState {
+    /// Games the gauntlet was started with.
+    pub size: usize,
+    /// Row id and record of each game: wins by fewest guesses, then the rest,
+    /// ties in play order.
+    pub games: Vec<(i64, GameRecord)>,
+    pub selected: usize,
+}
+
+impl GauntletSummaryState {
+    pub fn new(size: usize, mut games: Vec<(i64, GameRecord)>) -> Self {
+        games.sort_by_key(|(_, game)| {
+            let won = matches!(game.outcome, GameOutcome::Won { .. });
+            (!won, game.guess_count())
+        });
+        Self {
+            size,
+            games,
+            selected: 0,
+        }
+    }
+
+    pub fn wins(&self) -> usize {
+        self.games
+            .iter()
+            .filter(|(_, game)| matches!(game.outcome, GameOutcome::Won { .. }))
+            .count()
+    }
+
+    pub fn total_guesses(&self) -> usize {
+        self.games.iter().map(|(_, game)| game.guess_count()).sum()
+    }
+
+    /// Row id of the highlighted game.
+    pub fn selected_id(&self) -> Option<i64> {
+        self.games.get(self.selected).map(|(id, _)| *id)
+    }
+}
+
+/// Countdown state for a blitz game.
+#[derive(Debug, Clone)]
+pub struct BlitzState {
+    pub seconds: u64,
+    pub turn_started: Instant,
+    pub turns: Vec<BlitzTurn>,
+}
+
+impl BlitzState {
+    pub fn new(seconds: u64, now: Instant) -> Self {
+        Self {
+            seconds,
+            turn_started: now,
+            turns: Vec::new(),
+        }
+    }
+
+    pub fn deadline(&self) -> Instant {
+        self.turn_started + Duration::from_secs(self.seconds)
+    }
+
+    /// Time left on the current turn's countdown.
+    pub fn remaining(&self, now: Instant) -> Duration {
+        self.deadline().saturating_duration_since(now)
+    }
+
+    pub fn timeouts(&self) -> usize {
+        self.turns
+            .iter()
+            .filter(|t| **t == BlitzTurn::TimedOut)
+            .count()
+    }
+
+    /// Average time taken for turns that ended in a guess.
+    pub fn average_decision_time(&self) -> Option<Duration> {
+        let times: Vec<Duration> = self
+            .turns
+            .iter()
+            .filter_map(|t| match t {
+                BlitzTurn::Guessed(d) => Some(*d),
+                BlitzTurn::TimedOut => None,
+            })
+            .collect();
+
+        if times.is_empty() {
+            None
+        } else {
+            Some(times.iter().sum::<Duration>() / times.len() as u32)
+        }
+    }
+}
+
+/// Answers of a word list not seen before being scored for difficulty.
+#[derive(Debug)]
+pub struct TierJob {
+    /// Key the scores are cached under once done.
+    pub list_hash: String,
+    pub job: ScoreJob,
+}
+
+/// Stored solver sessions being replayed to refresh their optimal words.
+#[derive(Debug)]
+pub struct RecomputeJob {
+    /// Every stored session with its row id, oldest first.
+    pub sessions: Vec<(i64, SolverSession)>,
+    /// Index of the next session to replay.
+    pub next: usize,
+    /// Replayed guesses to write back once every session is done.
+    pub updates: Vec<(i64, Vec<SolverGuess>)>,
+    pub summary: RecomputeSummary,
+    /// Report what would change without writing it.
+    pub dry_run: bool,
+}
+
+/// How much a recompute changed the stored optimal words.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecomputeSummary {
+    pub sessions: usize,
+    /// Sessions that couldn't be replayed: no patterns were kept and no
+    /// answer was confirmed, or their word length doesn't match the lists.
+    pub skipped: usize,
+    pub guesses: usize,
+    pub optimal_changed: usize,
+    /// Sum of the absolute deviation changes over guesses measured both times.
+    pub deviation_change: f64,
+    pub deviation_compared: usize,
+}
+
+impl fmt::Display for RecomputeSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} session(s) replayed, {} skipped; optimal word changed on {} of {} guess(es)",
+            self.sessions, self.skipped, self.optimal_changed, self.guesses
+        )?;
+        if self.deviation_compared > 0 {
+            write!(
+                f,
+                ", mean deviation change {:.2}",
+                self.deviation_change / self.deviation_compared as f64
+            )?;
+        }
+        Ok(())
+    }
+}
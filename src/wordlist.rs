@@ -1,54 +1,527 @@
-use anyhow::{Context, Result};
-use rand::seq::IndexedRandom;
+use flate2::read::DeflateDecoder;
+use rand::Rng;
 use reqwest::blocking::get;
+use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-const WORDLIST_URL: &str = "https://raw.githubusercontent.com/tabatkins/wordle-list/main/words";
-const SOLUTIONS_URL: &str = "https://gist.githubusercontent.com/cfreshman/a03ef2cba789d8cf00c08f767e0fad7b/raw/wordle-answers-alphabetical.txt";
+use crate::{
+    error::{Result, WordleError},
+    normalizer::CharNormalizer,
+    wordtable::WordTable,
+};
+
+pub const WORDLIST_URL: &str = "https://raw.githubusercontent.com/tabatkins/wordle-list/main/words";
+pub const SOLUTIONS_URL: &str = "https://gist.githubusercontent.com/cfreshman/a03ef2cba789d8cf00c08f767e0fad7b/raw/wordle-answers-alphabetical.txt";
 
 const WORDLIST_PATH: &str = "words.txt";
 const SOLUTIONS_PATH: &str = "solutions.txt";
 
-pub fn load_words() -> Result<Vec<String>> {
-    ensure_file(WORDLIST_PATH, WORDLIST_URL)?;
+/// Deflated copies of `assets/wordlists`, made by build.rs.
+const BUNDLED_WORDS: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/words.txt.deflate"));
+const BUNDLED_SOLUTIONS: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/solutions.txt.deflate"));
+
+const WORDS: ListSpec = ListSpec {
+    name: "word list",
+    cache_file: WORDLIST_PATH,
+    url: WORDLIST_URL,
+    bundled: BUNDLED_WORDS,
+};
+
+const SOLUTIONS: ListSpec = ListSpec {
+    name: "solution list",
+    cache_file: SOLUTIONS_PATH,
+    url: SOLUTIONS_URL,
+    bundled: BUNDLED_SOLUTIONS,
+};
+
+/// One word list: where it is cached, where it comes from, and its bundled copy.
+struct ListSpec {
+    name: &'static str,
+    cache_file: &'static str,
+    url: &'static str,
+    bundled: &'static [u8],
+}
+
+/// How to find the word lists. The defaults cache downloads in the working directory.
+#[derive(Debug, Clone, Default)]
+pub struct WordlistOptions {
+    /// Where downloads are cached; see [`Paths::wordlists`](crate::paths::Paths::wordlists).
+    pub cache_dir: PathBuf,
+    /// Read allowed guesses from this file instead.
+    pub words_path: Option<PathBuf>,
+    /// Read possible answers from this file instead.
+    pub solutions_path: Option<PathBuf>,
+    /// Never download; use the bundled lists when nothing is cached.
+    pub offline: bool,
+    /// A published list to check the allowed guesses against; suggestions it
+    /// lacks are flagged.
+    pub compare_path: Option<PathBuf>,
+    /// Letters the lists count as the same, folded when the app loads them.
+    pub normalizer: CharNormalizer,
+}
+
+/// Where a loaded word list came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordlistSource {
+    Path,
+    Cache,
+    Download,
+    /// Compiled into the binary, and possibly older than the published list.
+    Bundled,
+}
+
+#[derive(Debug)]
+pub struct Wordlist {
+    pub words: Vec<String>,
+    pub source: WordlistSource,
+    /// The file the list was read from; none for a bundled list.
+    pub path: Option<PathBuf>,
+    pub cleanup: Cleanup,
+}
+
+/// What [`normalize_words`] had to fix in a list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Cleanup {
+    /// Entries that were trimmed or lowercased.
+    pub normalized: usize,
+    /// Comment lines and duplicate entries.
+    pub dropped: usize,
+}
+
+impl Cleanup {
+    pub fn is_empty(&self) -> bool {
+        self.normalized == 0 && self.dropped == 0
+    }
+}
+
+/// Fetches a word list over the network.
+pub trait Fetcher {
+    fn fetch(&self, url: &str) -> Result<String>;
+}
+
+/// Downloads over HTTP.
+pub struct HttpFetcher;
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, url: &str) -> Result<String> {
+        Ok(get(url)?.error_for_status()?.text()?)
+    }
+}
+
+/// Downloads over HTTP, giving up after a fixed time.
+pub struct TimedFetcher(pub Duration);
+
+impl Fetcher for TimedFetcher {
+    fn fetch(&self, url: &str) -> Result<String> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(self.0)
+            .build()?;
+        Ok(client.get(url).send()?.error_for_status()?.text()?)
+    }
+}
+
+pub fn load_words(options: &WordlistOptions) -> Result<Wordlist> {
+    load_list(
+        &WORDS,
+        options.words_path.as_deref(),
+        &options.cache_dir,
+        options.offline,
+        &HttpFetcher,
+    )
+}
 
-    let text = fs::read_to_string(WORDLIST_PATH).context("failed to read wordlist")?;
+pub fn load_solutions(options: &WordlistOptions) -> Result<Wordlist> {
+    load_list(
+        &SOLUTIONS,
+        options.solutions_path.as_deref(),
+        &options.cache_dir,
+        options.offline,
+        &HttpFetcher,
+    )
+}
 
-    Ok(text.lines().map(|s| s.to_string()).collect())
+/// The comparison list named by `options`, normalized; `None` without one.
+pub fn load_comparison(options: &WordlistOptions) -> Result<Option<Vec<String>>> {
+    let Some(path) = &options.compare_path else {
+        return Ok(None);
+    };
+    let text = fs::read_to_string(path)?;
+    Ok(Some(normalize_words(text.lines()).0))
 }
 
-pub fn load_solutions() -> Result<Vec<String>> {
-    ensure_file(SOLUTIONS_PATH, SOLUTIONS_URL)?;
+/// Words in only one of two lists, each in alphabetical order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WordlistDiff {
+    /// In the new list but not the old.
+    pub added: Vec<String>,
+    /// In the old list but not the new.
+    pub removed: Vec<String>,
+}
 
-    let text = fs::read_to_string(SOLUTIONS_PATH).context("failed to read solutions")?;
+impl WordlistDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
 
-    Ok(text.lines().map(|s| s.to_string()).collect())
+/// What changed from `old` to `new`; both should already be normalized.
+pub fn diff_wordlists(old: &[String], new: &[String]) -> WordlistDiff {
+    let old_set: HashSet<&str> = old.iter().map(String::as_str).collect();
+    let new_set: HashSet<&str> = new.iter().map(String::as_str).collect();
+
+    let mut added: Vec<String> = new_set
+        .difference(&old_set)
+        .map(|w| w.to_string())
+        .collect();
+    let mut removed: Vec<String> = old_set
+        .difference(&new_set)
+        .map(|w| w.to_string())
+        .collect();
+    added.sort();
+    removed.sort();
+    WordlistDiff { added, removed }
+}
+
+pub fn select_random_word(words: &WordTable) -> Result<String> {
+    if words.is_empty() {
+        return Err(WordleError::EmptyPool);
+    }
+
+    let index = rand::rng().random_range(words.indices());
+    Ok(words.word(index).to_string())
+}
+
+/// `count` different words from `words`, in random order.
+pub fn select_random_words(words: &WordTable, count: usize) -> Result<Vec<String>> {
+    if words.len() < count {
+        return Err(WordleError::TooFewWords {
+            wanted: count,
+            available: words.len(),
+        });
+    }
+
+    Ok(
+        rand::seq::index::sample(&mut rand::rng(), words.len(), count)
+            .into_iter()
+            .map(|index| words.word(index as u32).to_string())
+            .collect(),
+    )
+}
+
+/// Resolve a list from, in order: an explicit path, the cached file, a fresh
+/// download into the cache, and the bundled copy.
+///
+/// An explicit path that can't be read is an error rather than a reason to fall back.
+fn load_list(
+    spec: &ListSpec,
+    path: Option<&Path>,
+    cache_dir: &Path,
+    offline: bool,
+    fetcher: &impl Fetcher,
+) -> Result<Wordlist> {
+    if let Some(path) = path {
+        return Ok(Wordlist::parse(
+            &fs::read_to_string(path)?,
+            WordlistSource::Path,
+            Some(path),
+        ));
+    }
+
+    let cache = cache_dir.join(spec.cache_file);
+    if cache.exists() {
+        return Ok(Wordlist::parse(
+            &fs::read_to_string(&cache)?,
+            WordlistSource::Cache,
+            Some(&cache),
+        ));
+    }
+
+    if !offline {
+        eprintln!("downloading {}...", spec.cache_file);
+        match fetcher.fetch(spec.url) {
+            Ok(text) => {
+                fs::write(&cache, &text)?;
+                return Ok(Wordlist::parse(
+                    &text,
+                    WordlistSource::Download,
+                    Some(&cache),
+                ));
+            }
+            Err(e) => tracing::warn!("Failed to download the {}: {}", spec.name, e),
+        }
+    }
+
+    let list = Wordlist::parse(&decompress(spec.bundled)?, WordlistSource::Bundled, None);
+    if list.words.is_empty() {
+        return Err(WordleError::NoWordlist(spec.name));
+    }
+    eprintln!("using the bundled {}; it may be out of date", spec.name);
+    tracing::warn!("Using the bundled {}; it may be out of date", spec.name);
+    Ok(list)
 }
 
-pub fn select_random_word(words: &[String], word_len: usize) -> Result<String> {
-    let filtered: Vec<&String> = words.iter().filter(|w| w.len() == word_len).collect();
+fn decompress(bytes: &[u8]) -> Result<String> {
+    let mut text = String::new();
+    DeflateDecoder::new(bytes).read_to_string(&mut text)?;
+    Ok(text)
+}
 
-    if filtered.is_empty() {
-        return Err(anyhow::anyhow!("no {}-letter words available", word_len));
+impl Wordlist {
+    fn parse(text: &str, source: WordlistSource, path: Option<&Path>) -> Self {
+        let (words, cleanup) = normalize_words(text.lines());
+        Self {
+            words,
+            source,
+            path: path.map(Path::to_path_buf),
+            cleanup,
+        }
     }
+}
 
-    let selected = filtered
-        .choose(&mut rand::rng())
-        .ok_or_else(|| anyhow::anyhow!("failed to select random word"))?;
+/// Trim and lowercase each entry, skipping blank lines, `#` comments, and
+/// repeats of an earlier entry.
+///
+/// Lists saved on Windows or typed by hand would otherwise hold entries like
+/// `"CRANE\r"` that never match normalized input.
+pub fn normalize_words<'a>(entries: impl IntoIterator<Item = &'a str>) -> (Vec<String>, Cleanup) {
+    let mut cleanup = Cleanup::default();
+    let mut seen = HashSet::new();
+    let mut words = Vec::new();
 
-    Ok(selected.to_string())
+    for entry in entries {
+        let trimmed = entry.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if trimmed.starts_with('#') {
+            cleanup.dropped += 1;
+            continue;
+        }
+
+        let word = trimmed.to_lowercase();
+        if word != entry {
+            cleanup.normalized += 1;
+        }
+        if seen.insert(word.clone()) {
+            words.push(word);
+        } else {
+            cleanup.dropped += 1;
+        }
+    }
+    (words, cleanup)
 }
 
-fn ensure_file(path: &str, url: &str) -> Result<()> {
-    if Path::new(path).exists() {
-        return Ok(());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{Compression, write::DeflateEncoder};
+    use std::{cell::Cell, io::Write};
+
+    /// Stands in for a machine with no network.
+    struct FailingFetcher {
+        calls: Cell<usize>,
+    }
+
+    impl Fetcher for FailingFetcher {
+        fn fetch(&self, _url: &str) -> Result<String> {
+            self.calls.set(self.calls.get() + 1);
+            Err(WordleError::Io(std::io::Error::other(
+                "network unreachable",
+            )))
+        }
+    }
+
+    fn failing() -> FailingFetcher {
+        FailingFetcher {
+            calls: Cell::new(0),
+        }
+    }
+
+    /// A list spec whose bundled copy holds `words`, compressed as build.rs does.
+    fn spec_bundling(words: &[&str]) -> ListSpec {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(words.join("\n").as_bytes()).unwrap();
+        ListSpec {
+            bundled: encoder.finish().unwrap().leak(),
+            ..WORDS
+        }
+    }
+
+    #[test]
+    fn test_select_random_word_empty_pool() {
+        let words = WordTable::new(["crane"], 5);
+
+        assert!(matches!(
+            select_random_word(&WordTable::new(["crane"], 6)),
+            Err(WordleError::EmptyPool)
+        ));
+        assert_eq!(select_random_word(&words).unwrap(), "crane");
+    }
+
+    #[test]
+    fn test_select_random_words_distinct() {
+        let words = WordTable::new(["crane", "slate", "stone"], 5);
+
+        let mut picked = select_random_words(&words, 3).unwrap();
+        picked.sort();
+        assert_eq!(picked, vec!["crane", "slate", "stone"]);
+        assert!(matches!(
+            select_random_words(&words, 4),
+            Err(WordleError::TooFewWords {
+                wanted: 4,
+                available: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_diff_wordlists() {
+        let (old, _) = normalize_words(["crane", "slate", "stone"]);
+        let (new, _) = normalize_words(["STONE", "adieu", "crane\r", "adieu"]);
+
+        let diff = diff_wordlists(&old, &new);
+        assert_eq!(diff.added, vec!["adieu"]);
+        assert_eq!(diff.removed, vec!["slate"]);
+        assert!(!diff.is_empty());
+        assert!(diff_wordlists(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_comparison_list_normalized() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("upstream.txt");
+        std::fs::write(&path, "# upstream\r\nCRANE\r\nslate\r\n").unwrap();
+
+        assert_eq!(load_comparison(&WordlistOptions::default()).unwrap(), None);
+        let options = WordlistOptions {
+            compare_path: Some(path),
+            ..Default::default()
+        };
+        assert_eq!(
+            load_comparison(&options).unwrap(),
+            Some(vec!["crane".to_string(), "slate".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_bundled_list_used_when_download_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let spec = spec_bundling(&["crane", "slate", "stone"]);
+        let fetcher = failing();
+
+        let list = load_list(&spec, None, dir.path(), false, &fetcher).unwrap();
+
+        assert_eq!(fetcher.calls.get(), 1);
+        assert_eq!(list.source, WordlistSource::Bundled);
+        assert_eq!(list.path, None);
+        assert_eq!(list.words, ["crane", "slate", "stone"]);
+        assert!(!dir.path().join(WORDLIST_PATH).exists());
+    }
+
+    #[test]
+    fn test_offline_skips_download() {
+        let dir = tempfile::tempdir().unwrap();
+        let spec = spec_bundling(&["crane"]);
+        let fetcher = failing();
+
+        let list = load_list(&spec, None, dir.path(), true, &fetcher).unwrap();
+
+        assert_eq!(fetcher.calls.get(), 0);
+        assert_eq!(list.source, WordlistSource::Bundled);
+    }
+
+    #[test]
+    fn test_explicit_path_then_cache_win_over_bundled() {
+        let dir = tempfile::tempdir().unwrap();
+        let spec = spec_bundling(&["crane"]);
+        let explicit = dir.path().join("mine.txt");
+        fs::write(&explicit, "house\nworld\n").unwrap();
+        fs::write(dir.path().join(WORDLIST_PATH), "apple\n").unwrap();
+
+        let list = load_list(&spec, Some(&explicit), dir.path(), true, &failing()).unwrap();
+        assert_eq!(list.source, WordlistSource::Path);
+        assert_eq!(list.path, Some(explicit));
+        assert_eq!(list.words, ["house", "world"]);
+
+        let list = load_list(&spec, None, dir.path(), true, &failing()).unwrap();
+        assert_eq!(list.source, WordlistSource::Cache);
+        assert_eq!(list.path, Some(dir.path().join(WORDLIST_PATH)));
+        assert_eq!(list.words, ["apple"]);
+    }
+
+    #[test]
+    fn test_cache_read_from_options_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(SOLUTIONS_PATH), "crane\nslate\n").unwrap();
+        let options = WordlistOptions {
+            cache_dir: dir.path().to_path_buf(),
+            offline: true,
+            ..Default::default()
+        };
+
+        let list = load_solutions(&options).unwrap();
+        assert_eq!(list.source, WordlistSource::Cache);
+        assert_eq!(list.words, ["crane", "slate"]);
     }
 
-    eprintln!("downloading {}...", path);
+    #[test]
+    fn test_user_list_normalized_on_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mine.txt");
+        fs::write(
+            &path,
+            "# my words\r\nCRANE\r\nslate  \r\n\r\n  House\r\ncrane\r\nslate\r\n",
+        )
+        .unwrap();
 
-    let text = get(url)?.error_for_status()?.text()?;
-    fs::write(path, text)?;
+        let list = load_list(&WORDS, Some(&path), dir.path(), true, &failing()).unwrap();
 
-    Ok(())
+        assert_eq!(list.words, ["crane", "slate", "house"]);
+        assert_eq!(
+            list.cleanup,
+            Cleanup {
+                normalized: 3,
+                dropped: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_clean_list_reports_nothing() {
+        let (words, cleanup) = normalize_words(["crane", "slate"]);
+
+        assert_eq!(words, ["crane", "slate"]);
+        assert!(cleanup.is_empty());
+    }
+
+    #[test]
+    fn test_nothing_bundled_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let result = load_list(&spec_bundling(&[]), None, dir.path(), true, &failing());
+
+        assert!(matches!(result, Err(WordleError::NoWordlist("word list"))));
+    }
+
+    #[test]
+    fn test_bundled_lists_match_assets() {
+        // build.rs counts the words it bundles
+        for (spec, count) in [
+            (&WORDS, env!("BUNDLED_WORDS_COUNT")),
+            (&SOLUTIONS, env!("BUNDLED_SOLUTIONS_COUNT")),
+        ] {
+            let dir = tempfile::tempdir().unwrap();
+            let count: usize = count.parse().unwrap();
+            assert!(count > 0, "nothing bundled for the {}", spec.name);
+
+            let list = load_list(spec, None, dir.path(), true, &failing()).unwrap();
+
+            assert_eq!(list.source, WordlistSource::Bundled);
+            assert_eq!(list.words.len(), count);
+            assert!(list.words.iter().all(|w| w.len() == 5), "{}", spec.name);
+        }
+    }
 }
@@ -0,0 +1,145 @@
+//! Compact storage for a word list of uniform length.
+
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// Words of a single length packed into one sorted byte buffer.
+///
+/// Words are addressed by `u32` index and borrowed straight from the buffer, so
+/// a table costs `word_len` bytes per word and lookups are a binary search.
+#[derive(Debug, Clone, Default)]
+pub struct WordTable {
+    word_len: usize,
+    bytes: Vec<u8>,
+}
+
+impl WordTable {
+    /// Build a table from the lowercase ASCII words in `words` with `word_len`
+    /// letters (at most 32); anything else is skipped, as are duplicates.
+    pub fn new<I, S>(words: I, word_len: usize) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut kept: Vec<[u8; 32]> = Vec::new();
+        let mut bytes = Vec::new();
+
+        if (1..=32).contains(&word_len) {
+            for word in words {
+                let word = word.as_ref().as_bytes();
+                if word.len() == word_len && word.iter().all(u8::is_ascii_lowercase) {
+                    let mut key = [0; 32];
+                    key[..word_len].copy_from_slice(word);
+                    kept.push(key);
+                }
+            }
+            kept.sort_unstable();
+            kept.dedup();
+
+            bytes.reserve_exact(kept.len() * word_len);
+            for key in &kept {
+                bytes.extend_from_slice(&key[..word_len]);
+            }
+        }
+
+        Self { word_len, bytes }
+    }
+
+    pub fn word_len(&self) -> usize {
+        self.word_len
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len().checked_div(self.word_len).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Heap bytes held by the table.
+    pub fn heap_size(&self) -> usize {
+        self.bytes.capacity()
+    }
+
+    /// Every index in the table, in alphabetical order of the words.
+    pub fn indices(&self) -> Range<u32> {
+        0..self.len() as u32
+    }
+
+    /// Raw letters of the word at `index`.
+    pub fn bytes(&self, index: u32) -> &[u8] {
+        let start = index as usize * self.word_len;
+        &self.bytes[start..start + self.word_len]
+    }
+
+    /// The word at `index`.
+    pub fn word(&self, index: u32) -> &str {
+        std::str::from_utf8(self.bytes(index)).expect("table only stores ASCII")
+    }
+
+    /// Iterate over every word in alphabetical order.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.indices().map(|i| self.word(i))
+    }
+
+    pub fn index_of(&self, word: &str) -> Option<u32> {
+        let word = word.as_bytes();
+        if word.len() != self.word_len {
+            return None;
+        }
+
+        let (mut lo, mut hi) = (0, self.len() as u32);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match self.bytes(mid).cmp(word) {
+                Ordering::Less => lo = mid + 1,
+                Ordering::Greater => hi = mid,
+                Ordering::Equal => return Some(mid),
+            }
+        }
+        None
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.index_of(word).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_sorts_dedups_and_filters() {
+        let table = WordTable::new(["stone", "crane", "CRANE", "stone", "apple", "cranes"], 5);
+
+        assert_eq!(table.len(), 3);
+        assert_eq!(
+            table.words().collect::<Vec<_>>(),
+            ["apple", "crane", "stone"]
+        );
+        assert_eq!(table.heap_size(), 15);
+    }
+
+    #[test]
+    fn test_lookup() {
+        let table = WordTable::new(["stone", "crane", "apple"], 5);
+
+        assert_eq!(table.index_of("crane"), Some(1));
+        assert_eq!(table.word(1), "crane");
+        assert!(table.contains("apple"));
+        assert!(table.contains("stone"));
+        assert!(!table.contains("slate"));
+        assert!(!table.contains("cran"));
+    }
+
+    #[test]
+    fn test_empty_table() {
+        let table = WordTable::new(Vec::<String>::new(), 5);
+
+        assert!(table.is_empty());
+        assert_eq!(table.indices(), 0..0);
+        assert!(!table.contains("crane"));
+    }
+}
@@ -0,0 +1,96 @@
+//! The command line, run as a separate process with piped standard input.
+
+use std::path::Path;
+
+use assert_cmd::{Command, cargo::cargo_bin_cmd};
+
+const WORDS: &str = "raise\nstone\nslate\ncrane\nhouse\napple\nworld\nmagic\n";
+
+/// The binary on the fixture list, with its data kept in `dir`.
+fn warlord(dir: &Path) -> Command {
+    let list = dir.join("words.txt");
+    std::fs::write(&list, WORDS).unwrap();
+    let mut cmd = cargo_bin_cmd!("wordle-warlord");
+    cmd.arg("--data-dir")
+        .arg(dir)
+        .arg("--words")
+        .arg(&list)
+        .arg("--solutions")
+        .arg(&list)
+        .args(["--color", "never"])
+        .env("RUST_BACKTRACE", "0");
+    cmd
+}
+
+/// `--pool` on the fixture list, with its data kept in `dir`.
+fn pool(dir: &Path) -> Command {
+    let mut cmd = warlord(dir);
+    cmd.arg("--pool");
+    cmd
+}
+
+#[test]
+fn test_transcript_from_stdin() {
+    let dir = tempfile::tempdir().unwrap();
+    pool(dir.path())
+        .args(["--transcript", "-"])
+        .write_stdin("magic XXXXX\n")
+        .assert()
+        .success()
+        .stdout("# greens: _____, yellows: none, grays: ACGIM\nhouse\nstone\nworld\n")
+        .stderr("");
+}
+
+#[test]
+fn test_invalid_stdin_lines_fail_with_line_numbers() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = pool(dir.path())
+        .args(["--transcript", "-"])
+        .write_stdin("magic XXXXX\nzzzzz XXXXX\n\nhouse XXQXX\n")
+        .assert()
+        .failure()
+        .stdout("")
+        .get_output()
+        .clone();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let lines: Vec<&str> = stderr.lines().collect();
+    assert_eq!(lines[0], "line 2: not in allowed list: zzzzz");
+    assert_eq!(lines[1], "line 4: character must be G/Y/X, got Q");
+    assert!(lines[2].contains("2 invalid transcript line(s)"));
+}
+
+#[test]
+fn test_skip_invalid_keeps_the_good_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    pool(dir.path())
+        .args(["--transcript", "-", "--skip-invalid"])
+        .write_stdin("zzzzz XXXXX\nmagic XXXXX\n")
+        .assert()
+        .success()
+        .stdout("# greens: _____, yellows: none, grays: ACGIM\nhouse\nstone\nworld\n")
+        .stderr("line 1: not in allowed list: zzzzz\n");
+}
+
+#[test]
+fn test_plain_mode_over_pipes() {
+    let dir = tempfile::tempdir().unwrap();
+    warlord(dir.path())
+        .arg("--plain")
+        .write_stdin("magic XXXXX\nundo\nquit\n")
+        .assert()
+        .success()
+        .stdout(
+            "Wordle Warlord solver, plain text mode.\n\
+             Enter a guess as: <word> <pattern>, for example: crane GYXXX\n\
+             Pattern letters: G = green, Y = yellow, X = gray\n\
+             Commands: help, undo, quit\n\
+             M gray, A gray, G gray, I gray, C gray\n\
+             Remaining candidates: 3.\n\
+             Suggestions: HOUSE, STONE, WORLD.\n\
+             Removed guess MAGIC.\n\
+             Remaining candidates: 8.\n\
+             No suggestions.\n\
+             Goodbye.\n",
+        );
+}